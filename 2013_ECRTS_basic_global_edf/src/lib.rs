@@ -0,0 +1,614 @@
+use clap::Parser;
+use lib::{
+    admission_control::AdmissionTest,
+    benchmark_loader::create_dag_set_from_benchmark_dir,
+    breakdown_factor::find_breakdown_factor,
+    core_recommendation::recommend_cores,
+    dag_creator::{
+        create_dag_set_from_dir, create_dag_set_from_dirs_with_quantization,
+        create_dag_set_names_from_dirs, RoundingMode, TimeQuantization,
+    },
+    dag_set_scheduler::{
+        CoreUtilizationCaps, DAGSetSchedulerBase, IdleCoreSelection, IdleInsertionPolicy,
+        PreemptiveType, TieBreaker, TieBreakingPolicy,
+    },
+    determinism::assert_deterministic,
+    execution_time_trace::ExecutionTimeTrace,
+    fault_injection::FaultInjection,
+    global_edf_scheduler::GlobalEDFScheduler,
+    global_fp_scheduler::GlobalFPScheduler,
+    graph_extension::{GraphExtension, NodeData},
+    homogeneous::HomogeneousProcessor,
+    hot_node_report::{get_hot_node_report, write_hot_node_report},
+    interrupt_model::{InterruptModel, InterruptSource},
+    log::{LogFormat, QuantizationLog},
+    power_down::PowerDownModel,
+    priority_assignment::PriorityAssignment,
+    processor::ProcessorBase,
+    resource_manager::LockingProtocol,
+    schedulability_screening::screen_dag_set,
+    schedule_witness::{get_schedule_witness, write_sag_csv, write_schedule_witness_csv},
+    util::{adjust_to_implicit_deadline, install_interrupt_handler, is_interrupted},
+};
+use log::warn;
+use petgraph::Graph;
+
+#[derive(Parser)]
+#[clap(
+    args_override_self = true,
+    name = "Basic_Global_EDF",
+    version = "1.0",
+    about = "About:
+    Basic_Global_EDF_Algorithm operates on the same assumption of period and end_to_end_deadline.
+    Therefore, the period shall be considered as the end_to_end_deadline.
+    If there is no period, the end_to_end_deadline shall be obtained."
+)]
+pub struct ArgParser {
+    ///Path to a TOML file with the arguments. Flags on the command line override it.
+    #[clap(long = "config")]
+    #[allow(dead_code)] // Read by `expand_config_args` before parsing.
+    config: Option<String>,
+    ///Path to DAGSet directory.
+    #[clap(short = 'd', long = "dag_dir_path", required = true)]
+    dag_dir_path: String,
+    ///Load the DAG set from the DOT and TGFF benchmark files in the DAGSet directory.
+    #[clap(short = 'b', long = "benchmark", default_value = "false")]
+    benchmark: bool,
+    ///Convert the times of the YAML files to integer multiples of this quantum (e.g., 0.01),
+    ///and add the induced utilization inflation to the log.
+    #[clap(long = "time_quantum")]
+    time_quantum: Option<f64>,
+    ///Rounding of the times with --time_quantum (ceil, floor or nearest).
+    ///Periods and deadlines are rounded in the reverse direction.
+    #[clap(long = "rounding_mode", default_value = "ceil")]
+    rounding_mode: RoundingMode,
+    ///Number of processing cores.
+    #[clap(short = 'c', long = "number_of_cores", required = true)]
+    number_of_cores: usize,
+    ///Path to output directory.
+    #[clap(short = 'o', long = "output_dir_path", default_value = "../outputs")]
+    output_dir_path: String,
+    ///Enable or disable preemptive scheduling.
+    #[clap(short = 'p', long = "enable_preemption", default_value = "false")]
+    enable_preemption: bool,
+    ///Defer the preemption of a node until it has run for this time since it started or
+    ///resumed. Only with preemption.
+    #[clap(long = "non_preemptive_region", requires = "enable_preemption")]
+    non_preemptive_region: Option<i32>,
+    ///Format of the output log (yaml, json or msgpack).
+    #[clap(short = 'l', long = "log_format", default_value = "yaml")]
+    log_format: LogFormat,
+    ///Add a run-length encoded trace of each core to the log.
+    #[clap(short = 't', long = "core_trace", default_value = "false")]
+    core_trace: bool,
+    ///Add the predecessor that made each job of each node ready, and how long the node waited
+    ///for it after the first predecessor completed, to the log.
+    #[clap(long = "readiness_gates", default_value = "false")]
+    readiness_gates: bool,
+    ///Write the log, a summary and a manifest into a new directory per run.
+    #[clap(short = 's', long = "run_dir", default_value = "false")]
+    run_dir: bool,
+    ///Skip the simulation if the DAG set fails the schedulability screening,
+    ///and record it as screened out in a run directory.
+    #[clap(short = 'x', long = "skip_infeasible", default_value = "false")]
+    skip_infeasible: bool,
+    ///Path to a CSV trace (dag_id,node_id,job_index,exec_time) of measured execution times.
+    #[clap(short = 'e', long = "execution_time_trace")]
+    execution_time_trace: Option<String>,
+    ///Path to a CSV trace (dag_id,node_id,job_index,attempt) of transient faults of the node
+    ///executions, to log the reliability of each DAG.
+    #[clap(long = "fault_trace")]
+    fault_trace: Option<String>,
+    ///Re-executions of a faulty node without the `max_retries` parameter.
+    #[clap(long = "max_retries", default_value = "0")]
+    max_retries: i32,
+    ///Choice among idle cores (first_fit, last_used, least_utilized or awake).
+    #[clap(short = 'i', long = "idle_core_selection", default_value = "first_fit")]
+    idle_core_selection: IdleCoreSelection,
+    ///Whether a core may idle with ready nodes (work_conserving or anticipatory). Anticipatory
+    ///keeps the last idle core for a job released before the node would complete if it would
+    ///otherwise miss its earlier deadline.
+    #[clap(long = "idle_insertion_policy", default_value = "work_conserving")]
+    idle_insertion_policy: IdleInsertionPolicy,
+    ///Dispatch a random ready node with the seed instead of the earliest deadline,
+    ///as a lower-bound baseline.
+    #[clap(long = "random_dispatch_seed")]
+    random_dispatch_seed: Option<u64>,
+    ///Dispatch the nodes on the remaining critical path of their job before the other nodes with
+    ///the same deadline. The runs keep their name, so `sched_gate --report` compares them to runs
+    ///without.
+    #[clap(long = "critical_path_boost", default_value = "false")]
+    critical_path_boost: bool,
+    ///Order of the ready nodes with the same deadline (node_id, remaining_work, utilization or
+    ///fifo).
+    #[clap(long = "tie_breaking_policy", default_value = "node_id")]
+    tie_breaking_policy: TieBreakingPolicy,
+    ///Schedule with global fixed priorities assigned by the heuristic (dkc, d_cmpr or
+    ///slack_monotonic) instead of EDF, as a baseline. The options of EDF are not allowed.
+    #[clap(
+        long = "priority_assignment",
+        conflicts_with_all = [
+            "skip_infeasible",
+            "execution_time_trace",
+            "fault_trace",
+            "random_dispatch_seed",
+            "critical_path_boost",
+            "priority_inheritance",
+            "admission_test",
+            "utilization_cap",
+            "interrupt_burst_length",
+            "recommend_cores",
+            "breakdown_precision",
+            "check_determinism",
+        ]
+    )]
+    priority_assignment: Option<PriorityAssignment>,
+    ///Release a triggered DAG with the deadline of the job triggering it if that is earlier, and
+    ///log the inheritance events with the latencies of the chains.
+    #[clap(long = "priority_inheritance", default_value = "false")]
+    priority_inheritance: bool,
+    ///Locking protocol of the shared resources declared by `resource_id` (priority_inheritance
+    ///or mpcp). Blocked nodes suspend until the resource is handed to them.
+    #[clap(long = "locking_protocol", default_value = "priority_inheritance")]
+    locking_protocol: LockingProtocol,
+    ///Admit each released job only if it passes the test (density or laxity) with the active
+    ///jobs. Rejected jobs never run and are counted in the log.
+    #[clap(long = "admission_test")]
+    admission_test: Option<AdmissionTest>,
+    ///Cap the utilization of each core (e.g., 0.9) to reserve headroom for OS and interrupt
+    ///overheads. Cores are throttled once busy for the cap of each window.
+    #[clap(long = "utilization_cap")]
+    utilization_cap: Option<f32>,
+    ///Window in which --utilization_cap is enforced.
+    #[clap(long = "utilization_cap_window", default_value = "10")]
+    utilization_cap_window: i32,
+    ///Steal each core for this many time units in sporadic interrupt bursts at the highest
+    ///priority, stalling the node running on the core.
+    #[clap(long = "interrupt_burst_length")]
+    interrupt_burst_length: Option<i32>,
+    ///Minimum time between the starts of the interrupt bursts of a core.
+    #[clap(long = "interrupt_inter_arrival_time", default_value = "100")]
+    interrupt_inter_arrival_time: i32,
+    ///Maximum random delay added to the inter-arrival time of the interrupt bursts.
+    #[clap(long = "interrupt_jitter", default_value = "0")]
+    interrupt_jitter: i32,
+    ///Seed of the random jitter of the interrupt bursts.
+    #[clap(long = "interrupt_seed", default_value = "0")]
+    interrupt_seed: u64,
+    ///Power down each core idle for this many time units. The next node dispatched to it
+    ///waits for --wake_up_latency, and the log includes the sleep intervals and the energy.
+    #[clap(long = "power_down_threshold")]
+    power_down_threshold: Option<i32>,
+    ///Time for a core powered down to wake up for a node.
+    #[clap(long = "wake_up_latency", default_value = "1")]
+    wake_up_latency: i32,
+    ///Power of an idle core relative to a busy core, for the energy.
+    #[clap(long = "idle_power", default_value = "0.5")]
+    idle_power: f32,
+    ///Power of a core powered down relative to a busy core, for the energy.
+    #[clap(long = "sleep_power", default_value = "0.1")]
+    sleep_power: f32,
+    ///Add a recommendation of the number of cores to the log.
+    #[clap(short = 'r', long = "recommend_cores", default_value = "false")]
+    recommend_cores: bool,
+    ///Search the largest factor of all execution times that meets all deadlines (breakdown
+    ///factor) with this precision, and add it to the log.
+    #[clap(long = "breakdown_precision")]
+    breakdown_precision: Option<f64>,
+    ///Upper bound of the breakdown factor search.
+    #[clap(long = "breakdown_max_factor", default_value = "10.0")]
+    breakdown_max_factor: f64,
+    ///Simulate the configuration again and panic if the log differs from the first simulation.
+    #[clap(long = "check_determinism", default_value = "false")]
+    check_determinism: bool,
+    ///Threads simulating at once for --check_determinism, each once more.
+    #[clap(long = "determinism_threads", default_value = "1")]
+    determinism_threads: usize,
+    ///Write the schedule witness and its np-schedulability-analysis input files (CSV).
+    #[clap(short = 'w', long = "witness", default_value = "false")]
+    witness: bool,
+    ///Write a ranking of the nodes of each DAG that most delay the DAG (YAML).
+    #[clap(short = 'n', long = "hot_nodes", default_value = "false")]
+    hot_nodes: bool,
+    ///Path to a Rhai script computing custom metrics from the log before it is dumped.
+    #[cfg(feature = "plugin")]
+    #[clap(long = "plugin_script")]
+    plugin_script: Option<String>,
+    ///Also write the log to `lib/tests/fixtures/` as a golden fixture for the tests.
+    #[clap(long = "record_fixtures", default_value = "false")]
+    record_fixtures: bool,
+}
+
+fn get_preemptive_type(arg: &ArgParser) -> PreemptiveType {
+    let key = if arg.priority_assignment.is_some() {
+        "dag_priority".to_string()
+    } else {
+        "node_absolute_deadline".to_string()
+    };
+    match (arg.enable_preemption, arg.non_preemptive_region) {
+        (false, _) => PreemptiveType::NonPreemptive,
+        (true, None) => PreemptiveType::Preemptive { key },
+        (true, Some(non_preemptive_region)) => PreemptiveType::LimitedPreemptive {
+            key,
+            non_preemptive_region,
+        },
+    }
+}
+
+fn get_interrupt_model(arg: &ArgParser, number_of_cores: usize) -> Option<InterruptModel> {
+    let burst_length = arg.interrupt_burst_length?;
+    Some(InterruptModel::new_uniform(
+        InterruptSource::new(
+            arg.interrupt_inter_arrival_time,
+            arg.interrupt_jitter,
+            burst_length,
+        ),
+        number_of_cores,
+        arg.interrupt_seed,
+    ))
+}
+
+fn create_processor(arg: &ArgParser, number_of_cores: usize) -> HomogeneousProcessor {
+    let mut processor = HomogeneousProcessor::new(number_of_cores);
+    if let Some(idle_threshold) = arg.power_down_threshold {
+        processor.set_power_down_model(PowerDownModel::new(
+            idle_threshold,
+            arg.wake_up_latency,
+            arg.idle_power,
+            arg.sleep_power,
+        ));
+    }
+    processor
+}
+
+/// The scheduler with the options of the arguments shared by all simulations of the DAG set.
+fn create_scheduler(
+    arg: &ArgParser,
+    dag_set: &[Graph<NodeData, i32>],
+    number_of_cores: usize,
+) -> GlobalEDFScheduler {
+    let mut scheduler = GlobalEDFScheduler::new_with_tie_breaker(
+        dag_set,
+        &create_processor(arg, number_of_cores),
+        TieBreaker::new(arg.tie_breaking_policy, dag_set),
+    );
+    scheduler.set_idle_core_selection(arg.idle_core_selection);
+    scheduler.set_idle_insertion_policy(arg.idle_insertion_policy);
+    if let Some(seed) = arg.random_dispatch_seed {
+        scheduler.set_random_dispatch(seed);
+    }
+    if arg.critical_path_boost {
+        scheduler.set_critical_path_boost();
+    }
+    if arg.priority_inheritance {
+        scheduler.set_priority_inheritance();
+    }
+    scheduler.set_locking_protocol(arg.locking_protocol);
+    if let Some(admission_test) = arg.admission_test {
+        scheduler.set_admission_control(admission_test);
+    }
+    if let Some(utilization_cap) = arg.utilization_cap {
+        scheduler.set_core_utilization_caps(CoreUtilizationCaps::new_uniform(
+            utilization_cap,
+            number_of_cores,
+            arg.utilization_cap_window,
+        ));
+    }
+    if let Some(interrupt_model) = get_interrupt_model(arg, number_of_cores) {
+        scheduler.set_interrupt_model(interrupt_model);
+    }
+    scheduler
+}
+
+/// Check that the worst response time of every DAG is within its period.
+fn is_schedulable(
+    scheduler: &mut impl DAGSetSchedulerBase<HomogeneousProcessor>,
+    dag_set: &[Graph<NodeData, i32>],
+) -> bool {
+    let log = scheduler.get_log_mut();
+    dag_set.iter().all(|dag| {
+        log.get_worst_response_time(dag.get_dag_param("dag_id") as usize)
+            <= dag.get_head_period().unwrap()
+    })
+}
+
+fn write_dag_set_info(
+    arg: &ArgParser,
+    scheduler: &mut impl DAGSetSchedulerBase<HomogeneousProcessor>,
+    quantization_log: Option<QuantizationLog>,
+) {
+    if !arg.benchmark {
+        scheduler
+            .get_log_mut()
+            .write_dag_names(&create_dag_set_names_from_dirs(&[&arg.dag_dir_path]));
+    }
+    if let Some(quantization_log) = quantization_log {
+        scheduler.get_log_mut().write_quantization(quantization_log);
+    }
+}
+
+/// Write the log of the simulation ending at `schedule_length` with the outputs of the arguments.
+fn dump_outputs(
+    arg: &ArgParser,
+    scheduler: &mut impl DAGSetSchedulerBase<HomogeneousProcessor>,
+    dag_set: &[Graph<NodeData, i32>],
+    file_name: &str,
+    schedule_length: i32,
+    result: bool,
+) {
+    if arg.core_trace {
+        scheduler.get_log_mut().write_core_traces(schedule_length);
+    }
+    if arg.readiness_gates {
+        scheduler.get_log_mut().write_readiness_gates(dag_set);
+    }
+
+    #[cfg(feature = "plugin")]
+    if let Some(script_path) = &arg.plugin_script {
+        scheduler.run_post_processing_script(script_path);
+    }
+
+    if arg.witness {
+        let witness = get_schedule_witness(scheduler.get_log_mut());
+        let file_path = format!("{}/{}", arg.output_dir_path, file_name);
+        write_schedule_witness_csv(&witness, &format!("{}_witness.csv", file_path));
+        write_sag_csv(
+            &witness,
+            dag_set,
+            &format!("{}_sag_jobs.csv", file_path),
+            &format!("{}_sag_precedence.csv", file_path),
+        );
+    }
+
+    if arg.hot_nodes {
+        let report = get_hot_node_report(dag_set, scheduler.get_log_mut());
+        write_hot_node_report(
+            &report,
+            &format!("{}/{}_hot_nodes.yaml", arg.output_dir_path, file_name),
+        );
+    }
+
+    if arg.run_dir {
+        scheduler.dump_run_dir(
+            &arg.output_dir_path,
+            file_name,
+            dag_set,
+            result,
+            arg.log_format,
+        );
+    } else {
+        scheduler.dump_simulation_log(&arg.output_dir_path, file_name, result, arg.log_format);
+    }
+    if arg.record_fixtures {
+        scheduler.dump_fixture(file_name, dag_set, result);
+    }
+}
+
+/// Simulate the DAG set with the global fixed priorities of `priority_assignment` instead of EDF.
+fn run_global_fp(
+    arg: &ArgParser,
+    dag_set: &[Graph<NodeData, i32>],
+    priority_assignment: PriorityAssignment,
+    quantization_log: Option<QuantizationLog>,
+) {
+    let mut gfp_scheduler = GlobalFPScheduler::new_with_priority_assignment(
+        dag_set,
+        &create_processor(arg, arg.number_of_cores),
+        priority_assignment,
+    );
+    gfp_scheduler.set_locking_protocol(arg.locking_protocol);
+    write_dag_set_info(arg, &mut gfp_scheduler, quantization_log);
+
+    let file_name = if arg.enable_preemption {
+        "gfp_preemptive"
+    } else {
+        "gfp_non_preemptive"
+    };
+    let schedule_length = gfp_scheduler.schedule(get_preemptive_type(arg));
+    let result = is_schedulable(&mut gfp_scheduler, dag_set);
+    dump_outputs(
+        arg,
+        &mut gfp_scheduler,
+        dag_set,
+        file_name,
+        schedule_length,
+        result,
+    );
+}
+
+/// Run the algorithm with the parsed arguments.
+pub fn run(arg: ArgParser) {
+    // Stop at the end of the current time unit on ctrl-c and dump the partial log.
+    install_interrupt_handler();
+
+    let mut quantization_log = None;
+    let mut dag_set = if arg.benchmark {
+        create_dag_set_from_benchmark_dir(&arg.dag_dir_path)
+    } else if let Some(time_quantum) = arg.time_quantum {
+        let (dag_set, log) = create_dag_set_from_dirs_with_quantization(
+            &[&arg.dag_dir_path],
+            &TimeQuantization::new(time_quantum, arg.rounding_mode),
+        );
+        quantization_log = Some(log);
+        dag_set
+    } else {
+        create_dag_set_from_dir(&arg.dag_dir_path)
+    };
+    adjust_to_implicit_deadline(&mut dag_set);
+
+    if let Some(priority_assignment) = arg.priority_assignment {
+        run_global_fp(&arg, &dag_set, priority_assignment, quantization_log);
+        return;
+    }
+
+    let execution_time_trace = arg
+        .execution_time_trace
+        .as_ref()
+        .map(|trace_path| ExecutionTimeTrace::from_csv(trace_path));
+    let fault_injection = arg
+        .fault_trace
+        .as_ref()
+        .map(|trace_path| FaultInjection::from_csv(trace_path, arg.max_retries));
+    let mut gedf_scheduler = create_scheduler(&arg, &dag_set, arg.number_of_cores);
+    if let Some(execution_time_trace) = &execution_time_trace {
+        gedf_scheduler.set_execution_time_trace(execution_time_trace.clone());
+    }
+    if let Some(fault_injection) = &fault_injection {
+        gedf_scheduler.set_fault_injection(fault_injection.clone());
+    }
+    write_dag_set_info(&arg, &mut gedf_scheduler, quantization_log);
+
+    // Change whether it is preemptive or not depending on the argument.
+    let file_name = match (arg.random_dispatch_seed.is_some(), arg.enable_preemption) {
+        (false, true) => "gedf_preemptive",
+        (false, false) => "gedf_non_preemptive",
+        (true, true) => "random_preemptive",
+        (true, false) => "random_non_preemptive",
+    };
+
+    if arg.skip_infeasible {
+        let screening_violations = screen_dag_set(&dag_set, arg.number_of_cores);
+        if !screening_violations.is_empty() {
+            gedf_scheduler.dump_screened_out_run_dir(
+                &arg.output_dir_path,
+                file_name,
+                &dag_set,
+                screening_violations,
+            );
+            return;
+        }
+    }
+
+    let schedule_length = gedf_scheduler.schedule(get_preemptive_type(&arg));
+    let result = is_schedulable(&mut gedf_scheduler, &dag_set);
+    for violation in gedf_scheduler.get_log_mut().verify_core_utilization_caps() {
+        warn!("{}", violation);
+    }
+    if arg.check_determinism && !is_interrupted() {
+        let simulate = || {
+            let mut scheduler = create_scheduler(&arg, &dag_set, arg.number_of_cores);
+            if let Some(execution_time_trace) = &execution_time_trace {
+                scheduler.set_execution_time_trace(execution_time_trace.clone());
+            }
+            if let Some(fault_injection) = &fault_injection {
+                scheduler.set_fault_injection(fault_injection.clone());
+            }
+            scheduler.schedule(get_preemptive_type(&arg));
+            scheduler.get_log_mut().clone()
+        };
+        assert_deterministic(
+            simulate,
+            arg.determinism_threads.max(1) + 1,
+            arg.determinism_threads,
+        );
+    }
+
+    // The simulations for the recommendation would also be interrupted.
+    if arg.recommend_cores && !is_interrupted() {
+        let core_recommendation = recommend_cores(&dag_set, |number_of_cores| {
+            let mut scheduler = create_scheduler(&arg, &dag_set, number_of_cores);
+            scheduler.schedule(get_preemptive_type(&arg));
+            is_schedulable(&mut scheduler, &dag_set)
+        });
+        gedf_scheduler
+            .get_log_mut()
+            .write_core_recommendation(core_recommendation);
+    }
+
+    if let Some(breakdown_precision) = arg.breakdown_precision.filter(|_| !is_interrupted()) {
+        let breakdown_factor = find_breakdown_factor(
+            &dag_set,
+            &get_preemptive_type(&arg),
+            breakdown_precision,
+            arg.breakdown_max_factor,
+            |inflated_dag_set| create_scheduler(&arg, inflated_dag_set, arg.number_of_cores),
+        );
+        gedf_scheduler
+            .get_log_mut()
+            .write_breakdown_factor(breakdown_factor);
+    }
+
+    dump_outputs(
+        &arg,
+        &mut gedf_scheduler,
+        &dag_set,
+        file_name,
+        schedule_length,
+        result,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lib::fixture::{assert_matches_fixture, create_test_output_dir, get_fixture_path};
+    use std::fs;
+
+    const DAG_DIR_PATH: &str = "../lib/tests/sample_dags/multiple_yaml";
+
+    /// The fixture is recorded with
+    /// `-d ../lib/tests/sample_dags/multiple_yaml -c 4 --record_fixtures`.
+    #[test]
+    fn test_run_matches_fixture() {
+        let output_dir_path = create_test_output_dir("gedf_fixture_test");
+        run(ArgParser::parse_from([
+            "gedf",
+            "-d",
+            DAG_DIR_PATH,
+            "-c",
+            "4",
+            "-o",
+            &output_dir_path,
+        ]));
+
+        let mut dag_set = create_dag_set_from_dir(DAG_DIR_PATH);
+        adjust_to_implicit_deadline(&mut dag_set);
+        let log_path = fs::read_dir(&output_dir_path)
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap()
+            .path();
+        assert_matches_fixture(
+            &fs::read_to_string(log_path).unwrap(),
+            &get_fixture_path("gedf_non_preemptive", &dag_set),
+        );
+        fs::remove_dir_all(output_dir_path).unwrap();
+    }
+
+    #[test]
+    fn test_run_with_priority_assignment() {
+        let args = [
+            "gedf",
+            "-d",
+            DAG_DIR_PATH,
+            "-c",
+            "4",
+            "--priority_assignment",
+            "dkc",
+        ];
+        assert!(ArgParser::try_parse_from(args.iter().chain(&["--critical_path_boost"])).is_err());
+
+        let output_dir_path = create_test_output_dir("gfp_priority_assignment_test");
+        run(ArgParser::parse_from(
+            args.iter().chain(&["-o", output_dir_path.as_str()]),
+        ));
+
+        let log_path = fs::read_dir(&output_dir_path)
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap()
+            .path();
+        assert!(log_path
+            .file_name()
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .ends_with("gfp_non_preemptive-log.yaml"));
+        assert!(fs::read_to_string(log_path)
+            .unwrap()
+            .contains("priority_assignment: dkc"));
+        fs::remove_dir_all(output_dir_path).unwrap();
+    }
+}
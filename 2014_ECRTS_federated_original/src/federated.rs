@@ -1,6 +1,10 @@
 //! This module implements the federated scheduling algorithm.
-use lib::graph_extension::{GraphExtension, NodeData};
-use petgraph::graph::Graph;
+use lib::{
+    dag_set_generator::UtilizationClass,
+    graph_extension::{GraphExtension, NodeData},
+    preprocessing_cache::{get_dag_hash, PreprocessingCache},
+};
+use petgraph::graph::{Graph, NodeIndex};
 use serde_derive::{Deserialize, Serialize};
 use FederateResult::{Schedulable, Unschedulable};
 
@@ -35,17 +39,17 @@ pub enum FederateResult {
 /// # Example
 ///
 /// ```
-/// use algorithm::federated::federated;
+/// use sched_sim::federated::federated;
 /// use petgraph::graph::Graph;
 /// use lib::graph_extension::NodeData;
-/// use std::collections::HashMap;
+/// use std::collections::BTreeMap;
 /// fn create_node(id: i32, key: &str, value: i32) -> NodeData {
-///  let mut params = HashMap::new();
+///  let mut params = BTreeMap::new();
 ///  params.insert(key.to_string(), value);
 ///  NodeData { id, params }
 /// }
 /// let mut dag = Graph::<NodeData, i32>::new();
-/// let mut params = HashMap::new();
+/// let mut params = BTreeMap::new();
 /// params.insert("execution_time".to_owned(), 2);
 /// params.insert("period".to_owned(), 143);
 /// let n0 = dag.add_node(NodeData { id: 2, params });
@@ -53,22 +57,60 @@ pub enum FederateResult {
 /// let n2 = dag.add_node(create_node(1, "execution_time", 6));
 /// dag.add_edge(n0, n1, 1);
 /// dag.add_edge(n1, n2, 1);
-/// let dag_set = vec![dag];
+/// let mut dag_set = vec![dag];
 /// let number_of_cores = 4;
-/// let can_schedule = federated(dag_set, number_of_cores);
+/// let can_schedule = federated(&mut dag_set, number_of_cores);
 /// ```
 ///
 pub fn federated(dag_set: &mut [Graph<NodeData, i32>], number_of_cores: usize) -> FederateResult {
+    federated_with_cache(dag_set, number_of_cores, &PreprocessingCache::disabled())
+}
+
+/// `federated` reusing the critical paths computed by previous runs.
+pub fn federated_with_cache(
+    dag_set: &mut [Graph<NodeData, i32>],
+    number_of_cores: usize,
+    preprocessing_cache: &PreprocessingCache,
+) -> FederateResult {
+    federated_with_allocator(
+        dag_set,
+        number_of_cores,
+        preprocessing_cache,
+        |_, _, high_dedicated_cores| high_dedicated_cores,
+    )
+}
+
+/// Common part of the federated variants.
+///
+/// `allocate_high` receives the index of a high-utilization DAG, the DAG and the number of cores
+/// the original federated scheduling dedicates to it, and returns the number of cores to dedicate.
+pub(crate) fn federated_with_allocator(
+    dag_set: &mut [Graph<NodeData, i32>],
+    number_of_cores: usize,
+    preprocessing_cache: &PreprocessingCache,
+    mut allocate_high: impl FnMut(usize, &mut Graph<NodeData, i32>, usize) -> usize,
+) -> FederateResult {
     let mut remaining_cores = number_of_cores;
     let mut low_utilizations = 0.0;
 
-    for dag in dag_set {
+    for (dag_i, dag) in dag_set.iter_mut().enumerate() {
         let period = dag.get_head_period().unwrap();
 
         // Conforms to the definition in the original paper
         let end_to_end_deadline = period; // implicit deadline
         let volume = dag.get_volume();
-        let critical_path = dag.get_critical_path();
+        let critical_path: Vec<NodeIndex> = preprocessing_cache
+            .get_or_compute("critical_path", get_dag_hash(dag), || {
+                let critical_path: Vec<usize> = dag
+                    .get_critical_path()
+                    .iter()
+                    .map(|node_i| node_i.index())
+                    .collect();
+                critical_path
+            })
+            .into_iter()
+            .map(NodeIndex::new)
+            .collect();
         let critical_path_wcet = dag.get_total_wcet_from_nodes(&critical_path);
 
         // Tasks that do not meet the following conditions are inappropriate for Federated
@@ -79,11 +121,14 @@ pub fn federated(dag_set: &mut [Graph<NodeData, i32>], number_of_cores: usize) -
             };
         }
 
-        let utilization = volume as f32 / period as f32;
-        if utilization > 1.0 {
-            let high_dedicated_cores = ((volume - critical_path_wcet) as f32
-                / (end_to_end_deadline - critical_path_wcet) as f32)
-                .ceil() as usize;
+        if UtilizationClass::classify(dag) == Some(UtilizationClass::Heavy) {
+            let high_dedicated_cores = allocate_high(
+                dag_i,
+                dag,
+                ((volume - critical_path_wcet) as f32
+                    / (end_to_end_deadline - critical_path_wcet) as f32)
+                    .ceil() as usize,
+            );
             if high_dedicated_cores > remaining_cores {
                 return Unschedulable {
                     reason: "Insufficient number of cores for high-utilization tasks.".to_string(),
@@ -93,7 +138,7 @@ pub fn federated(dag_set: &mut [Graph<NodeData, i32>], number_of_cores: usize) -
                 remaining_cores -= high_dedicated_cores;
             }
         } else {
-            low_utilizations += utilization;
+            low_utilizations += volume as f32 / period as f32;
         }
     }
     if remaining_cores as f32 > 2.0 * low_utilizations {
@@ -110,7 +155,6 @@ pub fn federated(dag_set: &mut [Graph<NodeData, i32>], number_of_cores: usize) -
 }
 
 #[cfg(test)]
-
 mod tests {
     use super::*;
     use std::collections::BTreeMap;
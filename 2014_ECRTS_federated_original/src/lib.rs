@@ -0,0 +1,181 @@
+pub mod federated;
+mod outputs_result;
+mod segmented_federated;
+mod watch;
+
+use clap::Parser;
+use federated::FederateResult;
+use lib::{
+    dag_creator::create_dag_set_from_dir,
+    dag_set_scheduler::{DAGSetSchedulerBase, PreemptiveType},
+    dag_task::DagTask,
+    federated_scheduler::FederatedScheduler,
+    fixture::record_fixture,
+    homogeneous,
+    log::LogFormat,
+    preprocessing_cache::PreprocessingCache,
+    processor::ProcessorBase,
+    util::{adjust_to_implicit_deadline, create_scheduler_log_yaml},
+};
+use outputs_result::{
+    dump_dag_set_info_to_yaml, dump_federated_result_to_yaml, dump_processor_info_to_yaml,
+    dump_segment_allocations_to_yaml,
+};
+
+/// Application description and arguments definition using clap crate
+#[derive(Parser)]
+#[clap(args_override_self = true)]
+/// Application arguments definition using clap crate
+pub struct AppArg {
+    ///Path to a TOML file with the arguments. Flags on the command line override it.
+    #[clap(long = "config")]
+    #[allow(dead_code)] // Read by `expand_config_args` before parsing.
+    config: Option<String>,
+    #[clap(
+        short = 'd',
+        long = "dag_dir_path",
+        required_unless_present = "watch_dir_path"
+    )]
+    dag_dir_path: Option<String>,
+    #[clap(short = 'c', long = "number_of_cores", required = true)]
+    number_of_cores: usize,
+    #[clap(short = 'o', long = "output_dir_path", default_value = "../outputs")]
+    output_dir_path: String,
+    ///Directory to watch. Each newly added sub-directory is simulated as a DAG set.
+    #[clap(short = 'w', long = "watch_dir_path", conflicts_with = "dag_dir_path")]
+    watch_dir_path: Option<String>,
+    ///Polling interval of the watched directory in milliseconds.
+    #[clap(long = "poll_interval_ms", default_value = "1000")]
+    poll_interval_ms: u64,
+    ///Allocate dedicated cores per segment between synchronization points.
+    #[clap(short = 's', long = "segmented", default_value = "false")]
+    segmented: bool,
+    #[clap(long = "cache_dir_path", default_value = "../outputs/cache")]
+    cache_dir_path: String,
+    #[clap(long = "no_cache", default_value = "false")]
+    no_cache: bool,
+    ///Also simulate the DAG set: the high-utilization DAGs on their dedicated cores and the
+    ///low-utilization DAGs under G-EDF on the remaining cores, and dump the schedule log.
+    #[clap(
+        long = "simulate",
+        default_value = "false",
+        conflicts_with = "segmented"
+    )]
+    simulate: bool,
+    ///Also write the log to `lib/tests/fixtures/` as a golden fixture for the tests.
+    #[clap(long = "record_fixtures", default_value = "false")]
+    record_fixtures: bool,
+}
+
+fn get_alg_name(segmented: bool) -> &'static str {
+    if segmented {
+        "segmented_federated"
+    } else {
+        "federated"
+    }
+}
+
+/// Run the federated test on a DAG set and dump the log.
+///
+/// # Returns
+///
+/// * The path of the dumped log file.
+/// * The result of the federated test.
+pub(crate) fn simulate_federated(
+    dag_dir_path: &str,
+    number_of_cores: usize,
+    output_dir_path: &str,
+    segmented: bool,
+    preprocessing_cache: &PreprocessingCache,
+) -> (String, FederateResult) {
+    let mut dag_set = create_dag_set_from_dir(dag_dir_path);
+    let (result, segment_allocations) = if segmented {
+        let (result, segment_allocations) = segmented_federated::segmented_federated(
+            &mut dag_set,
+            number_of_cores,
+            preprocessing_cache,
+        );
+        (result, Some(segment_allocations))
+    } else {
+        (
+            federated::federated_with_cache(&mut dag_set, number_of_cores, preprocessing_cache),
+            None,
+        )
+    };
+    let file_path = create_scheduler_log_yaml(output_dir_path, get_alg_name(segmented));
+    let homogeneous_processor = homogeneous::HomogeneousProcessor::new(number_of_cores);
+    dump_dag_set_info_to_yaml(&file_path, dag_set);
+    dump_processor_info_to_yaml(&file_path, &homogeneous_processor);
+    dump_federated_result_to_yaml(&file_path, result.clone());
+    if let Some(segment_allocations) = segment_allocations {
+        dump_segment_allocations_to_yaml(&file_path, segment_allocations);
+    }
+    (file_path, result)
+}
+
+/// Simulate federated scheduling of a DAG set with implicit deadlines and dump the schedule log.
+///
+/// # Returns
+///
+/// * The path of the dumped log file.
+pub(crate) fn simulate_federated_schedule(
+    dag_dir_path: &str,
+    number_of_cores: usize,
+    output_dir_path: &str,
+) -> String {
+    let mut dag_set = create_dag_set_from_dir(dag_dir_path);
+    adjust_to_implicit_deadline(&mut dag_set);
+    let homogeneous_processor = homogeneous::HomogeneousProcessor::new(number_of_cores);
+    let mut federated_scheduler = FederatedScheduler::new(&dag_set, &homogeneous_processor);
+    federated_scheduler.schedule(PreemptiveType::Preemptive {
+        key: "node_absolute_deadline".to_string(),
+    });
+    let log = federated_scheduler.get_log_mut();
+    let result = dag_set.iter().enumerate().all(|(dag_id, dag)| {
+        log.get_worst_response_time(dag_id)
+            <= DagTask::from_graph(dag).get_relative_deadline().unwrap()
+    });
+    federated_scheduler.dump_simulation_log(
+        output_dir_path,
+        "federated_simulation",
+        result,
+        LogFormat::Yaml,
+    )
+}
+
+/// Run the federated test with the parsed arguments.
+pub fn run(arg: AppArg) {
+    let preprocessing_cache = if arg.no_cache {
+        PreprocessingCache::disabled()
+    } else {
+        PreprocessingCache::new(&arg.cache_dir_path)
+    };
+    if let Some(watch_dir_path) = arg.watch_dir_path {
+        watch::watch_dir(
+            &watch_dir_path,
+            arg.number_of_cores,
+            &arg.output_dir_path,
+            arg.poll_interval_ms,
+            arg.segmented,
+            &preprocessing_cache,
+        );
+    }
+    let dag_dir_path = arg.dag_dir_path.unwrap();
+    let (file_path, _) = simulate_federated(
+        &dag_dir_path,
+        arg.number_of_cores,
+        &arg.output_dir_path,
+        arg.segmented,
+        &preprocessing_cache,
+    );
+    if arg.simulate {
+        simulate_federated_schedule(&dag_dir_path, arg.number_of_cores, &arg.output_dir_path);
+    }
+    if arg.record_fixtures {
+        record_fixture(
+            &file_path,
+            get_alg_name(arg.segmented),
+            &create_dag_set_from_dir(&dag_dir_path),
+        );
+    }
+}
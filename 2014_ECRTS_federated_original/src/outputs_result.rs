@@ -1,4 +1,4 @@
-use crate::federated::FederateResult;
+use crate::{federated::FederateResult, segmented_federated::DAGSegmentAllocation};
 use lib::{
     graph_extension::NodeData,
     log::{dump_struct, DAGSetInfo, ProcessorInfo},
@@ -17,6 +17,21 @@ pub(crate) fn dump_federated_result_to_yaml(file_path: &str, result: FederateRes
     dump_struct(file_path, &result_info);
 }
 
+#[derive(Serialize, Deserialize)]
+struct SegmentAllocationsInfo {
+    segment_allocations: Vec<DAGSegmentAllocation>,
+}
+
+pub(crate) fn dump_segment_allocations_to_yaml(
+    file_path: &str,
+    segment_allocations: Vec<DAGSegmentAllocation>,
+) {
+    let segment_allocations_info = SegmentAllocationsInfo {
+        segment_allocations,
+    };
+    dump_struct(file_path, &segment_allocations_info);
+}
+
 pub(crate) fn dump_dag_set_info_to_yaml(file_path: &str, dag_set: Vec<Graph<NodeData, i32>>) {
     let dag_set_info = DAGSetInfo::new(&dag_set);
     dump_struct(file_path, &dag_set_info);
@@ -31,12 +46,16 @@ pub(crate) fn dump_processor_info_to_yaml(file_path: &str, processor: &impl Proc
 mod tests {
     use super::*;
     use lib::{
+        fixture::{assert_matches_fixture, create_test_output_dir, get_fixture_path},
         graph_extension::NodeData,
         homogeneous,
         util::{create_yaml, load_yaml},
     };
     use petgraph::Graph;
-    use std::{collections::BTreeMap, fs::remove_file};
+    use std::{
+        collections::BTreeMap,
+        fs::{self, remove_file},
+    };
 
     fn create_node(id: i32, key: &str, value: i32) -> NodeData {
         let mut params = BTreeMap::new();
@@ -192,35 +211,21 @@ mod tests {
     #[test]
     fn test_dump_dag_set_info_to_yaml_normal() {
         let dag_set = vec![create_high_utilization_dag(), create_high_utilization_dag()];
-        let file_path = create_yaml("../lib/tests", "dag_set_info");
+        let fixture_path = get_fixture_path("federated_dag_set_info", &dag_set);
+        let dir_path = create_test_output_dir("federated_dag_set_info");
+        let file_path = create_yaml(&dir_path, "dag_set_info");
         dump_dag_set_info_to_yaml(&file_path, dag_set);
 
-        let yaml_docs = load_yaml(&file_path);
-        let yaml_doc = &yaml_docs[0];
-
+        assert_matches_fixture(&fs::read_to_string(&file_path).unwrap(), &fixture_path);
+        // Key values of the fixture, checked by hand.
+        let yaml_doc = &load_yaml(&file_path)[0];
         assert_eq!(yaml_doc["total_utilization"].as_f64().unwrap(), 1.4285715);
-        assert_eq!(
-            yaml_doc["each_dag_info"][1]["critical_path_length"]
-                .as_i64()
-                .unwrap(),
-            8
-        );
-        assert_eq!(yaml_doc["each_dag_info"][1]["period"].as_i64().unwrap(), 10);
-        assert_eq!(
-            yaml_doc["each_dag_info"][1]["end_to_end_deadline"]
-                .as_i64()
-                .unwrap(),
-            0
-        );
-        assert_eq!(yaml_doc["each_dag_info"][1]["volume"].as_i64().unwrap(), 14);
-        assert_eq!(
-            yaml_doc["each_dag_info"][1]["utilization"]
-                .as_f64()
-                .unwrap(),
-            0.71428573
-        );
-
-        remove_file(file_path).unwrap();
+        let dag_info = &yaml_doc["each_dag_info"][1];
+        assert_eq!(dag_info["critical_path_length"].as_i64().unwrap(), 8);
+        assert_eq!(dag_info["period"].as_i64().unwrap(), 10);
+        assert_eq!(dag_info["volume"].as_i64().unwrap(), 14);
+        assert_eq!(dag_info["utilization"].as_f64().unwrap(), 0.71428573);
+        fs::remove_dir_all(dir_path).unwrap();
     }
 
     #[test]
@@ -0,0 +1,324 @@
+//! This module implements a segmented variant of federated scheduling.
+//!
+//! A DAG is split into segments at synchronization points (nodes that every path passes through),
+//! and dedicated cores are allocated to each segment instead of to the whole DAG.
+//! Narrow segments then keep fewer cores busy, which reduces the reserved but unused core time.
+use crate::federated::{federated_with_allocator, FederateResult};
+use lib::{
+    graph_extension::{GraphExtension, NodeData},
+    preprocessing_cache::PreprocessingCache,
+};
+use petgraph::{
+    algo::toposort,
+    graph::{Graph, NodeIndex},
+};
+use serde_derive::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Tolerance for comparing the sum of segment response times with the deadline.
+const EPSILON: f32 = 1e-4;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct SegmentAllocation {
+    pub node_ids: Vec<i32>,
+    pub volume: i32,
+    pub critical_path_length: i32,
+    /// Time budget of the segment within the end-to-end deadline.
+    pub deadline: f32,
+    pub dedicated_cores: usize,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct DAGSegmentAllocation {
+    pub dag_id: usize,
+    /// Cores reserved for the DAG, i.e., the maximum over its segments.
+    pub dedicated_cores: usize,
+    /// Sum of `dedicated_cores * deadline` over the segments.
+    pub reserved_core_time: f32,
+    /// Core time reserved by the original federated scheduling (`cores * deadline`).
+    pub federated_reserved_core_time: f32,
+    pub segments: Vec<SegmentAllocation>,
+}
+
+/// Split `dag` into segments in execution order.
+///
+/// Each synchronization point forms a sequential segment by itself, and the nodes between two
+/// consecutive synchronization points form a parallel segment.
+/// Nodes in each segment are in topological order.
+pub fn create_sync_segments(dag: &Graph<NodeData, i32>) -> Vec<Vec<NodeIndex>> {
+    let sorted_nodes = toposort(dag, None).unwrap();
+    let sync_nodes: Vec<NodeIndex> = sorted_nodes
+        .iter()
+        .copied()
+        .filter(|&node_i| dag.get_parallel_process_nodes(node_i).is_none())
+        .collect();
+
+    // Every path passes through all synchronization points, so a node lies between the
+    // synchronization points that are its ancestors and the ones that are its descendants.
+    let mut gaps = vec![Vec::new(); sync_nodes.len() + 1];
+    for &node_i in sorted_nodes.iter() {
+        if sync_nodes.contains(&node_i) {
+            continue;
+        }
+        let anc_nodes = dag.get_anc_nodes(node_i).unwrap_or_default();
+        let gap_i = sync_nodes
+            .iter()
+            .filter(|sync_node| anc_nodes.contains(sync_node))
+            .count();
+        gaps[gap_i].push(node_i);
+    }
+
+    let mut segments = Vec::new();
+    for (gap_i, gap) in gaps.into_iter().enumerate() {
+        if !gap.is_empty() {
+            segments.push(gap);
+        }
+        if let Some(&sync_node) = sync_nodes.get(gap_i) {
+            segments.push(vec![sync_node]);
+        }
+    }
+    segments
+}
+
+/// Longest path within `segment`, whose nodes are in topological order.
+fn get_segment_critical_path_length(dag: &Graph<NodeData, i32>, segment: &[NodeIndex]) -> i32 {
+    let mut finish_times: HashMap<NodeIndex, i32> = HashMap::new();
+    for &node_i in segment {
+        let start_time = dag
+            .get_pre_nodes(node_i)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|pre_node| finish_times.get(pre_node))
+            .max()
+            .copied()
+            .unwrap_or(0);
+        finish_times.insert(
+            node_i,
+            start_time + dag.get_total_wcet_from_nodes(&[node_i]),
+        );
+    }
+    finish_times.values().max().copied().unwrap_or(0)
+}
+
+/// Upper bound of the response time of a segment on `cores` dedicated cores (Graham's bound).
+fn get_segment_response_time(volume: i32, critical_path_length: i32, cores: usize) -> f32 {
+    critical_path_length as f32 + (volume - critical_path_length) as f32 / cores as f32
+}
+
+/// Allocate cores to the segments of a high-utilization DAG.
+///
+/// Starting from one core per segment, a core is added to the segment that shortens the response
+/// time most per unit of additional reserved core time, until the segments fit in the deadline.
+/// Each segment receives at most `max_cores`, the number of cores of the original federated
+/// scheduling, so the result never needs more cores than federated scheduling.
+pub fn allocate_segments(
+    dag: &Graph<NodeData, i32>,
+    dag_id: usize,
+    end_to_end_deadline: i32,
+    max_cores: usize,
+) -> DAGSegmentAllocation {
+    let segments = create_sync_segments(dag);
+    let volumes: Vec<i32> = segments
+        .iter()
+        .map(|segment| dag.get_total_wcet_from_nodes(segment))
+        .collect();
+    let critical_path_lengths: Vec<i32> = segments
+        .iter()
+        .map(|segment| get_segment_critical_path_length(dag, segment))
+        .collect();
+    let response_time = |segment_i: usize, cores: usize| {
+        get_segment_response_time(volumes[segment_i], critical_path_lengths[segment_i], cores)
+    };
+
+    let mut cores = vec![1; segments.len()];
+    let mut total_response_time: f32 = (0..segments.len()).map(|i| response_time(i, 1)).sum();
+    while total_response_time > end_to_end_deadline as f32 + EPSILON {
+        let best_segment = (0..segments.len())
+            .filter(|&i| cores[i] < max_cores && volumes[i] > critical_path_lengths[i])
+            .max_by(|&a, &b| {
+                let gain = |i: usize| {
+                    (response_time(i, cores[i]) - response_time(i, cores[i] + 1))
+                        / critical_path_lengths[i] as f32
+                };
+                gain(a).total_cmp(&gain(b))
+            });
+        let Some(segment_i) = best_segment else {
+            break;
+        };
+        total_response_time -= response_time(segment_i, cores[segment_i])
+            - response_time(segment_i, cores[segment_i] + 1);
+        cores[segment_i] += 1;
+    }
+
+    // The remaining slack is given to the last segment.
+    let slack = (end_to_end_deadline as f32 - total_response_time).max(0.0);
+    let last_segment_i = segments.len() - 1;
+    let segment_allocations: Vec<SegmentAllocation> = segments
+        .iter()
+        .enumerate()
+        .map(|(i, segment)| {
+            let mut node_ids: Vec<i32> = segment.iter().map(|&node_i| dag[node_i].id).collect();
+            node_ids.sort();
+            SegmentAllocation {
+                node_ids,
+                volume: volumes[i],
+                critical_path_length: critical_path_lengths[i],
+                deadline: response_time(i, cores[i])
+                    + if i == last_segment_i { slack } else { 0.0 },
+                dedicated_cores: cores[i],
+            }
+        })
+        .collect();
+
+    DAGSegmentAllocation {
+        dag_id,
+        dedicated_cores: *cores.iter().max().unwrap(),
+        reserved_core_time: segment_allocations
+            .iter()
+            .map(|segment| segment.dedicated_cores as f32 * segment.deadline)
+            .sum(),
+        federated_reserved_core_time: (max_cores * end_to_end_deadline as usize) as f32,
+        segments: segment_allocations,
+    }
+}
+
+/// Federated scheduling that dedicates cores to high-utilization DAGs per segment.
+///
+/// Low-utilization DAGs are handled in the same way as the original federated scheduling.
+///
+/// # Returns
+///
+/// * The schedulability in the same form as `federated`.
+/// * The per-segment allocation of each high-utilization DAG.
+pub fn segmented_federated(
+    dag_set: &mut [Graph<NodeData, i32>],
+    number_of_cores: usize,
+    preprocessing_cache: &PreprocessingCache,
+) -> (FederateResult, Vec<DAGSegmentAllocation>) {
+    let mut allocations = Vec::new();
+    let result = federated_with_allocator(
+        dag_set,
+        number_of_cores,
+        preprocessing_cache,
+        |dag_id, dag, federated_cores| {
+            // Not schedulable even with the original allocation.
+            if federated_cores > number_of_cores {
+                return federated_cores;
+            }
+            let allocation =
+                allocate_segments(dag, dag_id, dag.get_head_period().unwrap(), federated_cores);
+            let dedicated_cores = allocation.dedicated_cores;
+            allocations.push(allocation);
+            dedicated_cores
+        },
+    );
+    (result, allocations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::federated::federated;
+    use std::collections::BTreeMap;
+
+    fn create_node(id: i32, key: &str, value: i32) -> NodeData {
+        let mut params = BTreeMap::new();
+        params.insert(key.to_string(), value);
+        NodeData { id, params }
+    }
+
+    fn create_high_utilization_dag() -> Graph<NodeData, i32> {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = {
+            let mut params = BTreeMap::new();
+            params.insert("execution_time".to_owned(), 4);
+            params.insert("period".to_owned(), 10);
+            dag.add_node(NodeData { id: 0, params })
+        };
+        let n1 = dag.add_node(create_node(1, "execution_time", 4));
+        let n2 = dag.add_node(create_node(2, "execution_time", 3));
+        let n3 = dag.add_node(create_node(3, "execution_time", 3));
+        dag.add_edge(n0, n1, 1);
+        dag.add_edge(n0, n2, 1);
+        dag.add_edge(n0, n3, 1);
+
+        dag
+    }
+
+    fn create_narrow_and_wide_dag() -> Graph<NodeData, i32> {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = {
+            let mut params = BTreeMap::new();
+            params.insert("execution_time".to_owned(), 2);
+            params.insert("period".to_owned(), 40);
+            dag.add_node(NodeData { id: 0, params })
+        };
+        // Narrow phase
+        let n1 = dag.add_node(create_node(1, "execution_time", 4));
+        let n2 = dag.add_node(create_node(2, "execution_time", 2));
+        let n3 = dag.add_node(create_node(3, "execution_time", 2));
+        let n4 = dag.add_node(create_node(4, "execution_time", 1));
+        dag.add_edge(n0, n1, 1);
+        dag.add_edge(n0, n2, 1);
+        dag.add_edge(n2, n3, 1);
+        dag.add_edge(n1, n4, 1);
+        dag.add_edge(n3, n4, 1);
+        // Wide phase
+        let n5 = dag.add_node(create_node(5, "execution_time", 8));
+        for id in 6..10 {
+            let node_i = dag.add_node(create_node(id, "execution_time", 8));
+            dag.add_edge(n4, node_i, 1);
+            dag.add_edge(node_i, n5, 1);
+        }
+
+        dag
+    }
+
+    #[test]
+    fn test_create_sync_segments_normal() {
+        let dag = create_narrow_and_wide_dag();
+        let segments: Vec<Vec<usize>> = create_sync_segments(&dag)
+            .iter()
+            .map(|segment| segment.iter().map(|node_i| node_i.index()).collect())
+            .collect();
+
+        assert_eq!(segments.len(), 5);
+        assert_eq!(segments[0], vec![0]);
+        assert_eq!(segments[1].len(), 3);
+        assert_eq!(segments[2], vec![4]);
+        assert_eq!(segments[3].len(), 4);
+        assert_eq!(segments[4], vec![5]);
+    }
+
+    #[test]
+    fn test_allocate_segments_normal() {
+        let dag = create_high_utilization_dag();
+        let allocation = allocate_segments(&dag, 0, 10, 3);
+
+        assert_eq!(allocation.dedicated_cores, 3);
+        assert_eq!(allocation.segments.len(), 2);
+        assert_eq!(allocation.segments[0].dedicated_cores, 1);
+        assert_eq!(allocation.segments[0].deadline, 4.0);
+        assert_eq!(allocation.segments[1].node_ids, vec![1, 2, 3]);
+        assert_eq!(allocation.segments[1].dedicated_cores, 3);
+        assert_eq!(allocation.segments[1].deadline, 6.0);
+        assert_eq!(allocation.reserved_core_time, 22.0);
+        assert_eq!(allocation.federated_reserved_core_time, 30.0);
+    }
+
+    #[test]
+    fn test_segmented_federated_narrow_and_wide() {
+        let mut dag_set = vec![create_narrow_and_wide_dag()];
+        let (result, allocations) =
+            segmented_federated(&mut dag_set, 4, &PreprocessingCache::disabled());
+
+        // The narrow phase keeps a single core while the wide phase uses more.
+        let segments = &allocations[0].segments;
+        assert_eq!(segments[1].dedicated_cores, 1);
+        assert!(segments[3].dedicated_cores > 1);
+        assert!(allocations[0].reserved_core_time < allocations[0].federated_reserved_core_time);
+        let total_deadline: f32 = segments.iter().map(|segment| segment.deadline).sum();
+        assert!((total_deadline - 40.0).abs() < EPSILON);
+        assert_eq!(result, federated(&mut dag_set, 4));
+    }
+}
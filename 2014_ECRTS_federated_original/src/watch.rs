@@ -0,0 +1,231 @@
+//! Streaming mode that simulates DAG sets as they are added to a directory.
+use crate::{federated::FederateResult, simulate_federated};
+use lib::{preprocessing_cache::PreprocessingCache, util::append_info_to_yaml};
+use log::{info, warn};
+use serde_derive::{Deserialize, Serialize};
+use std::{
+    collections::BTreeSet,
+    fs,
+    panic::{catch_unwind, AssertUnwindSafe},
+    path::PathBuf,
+    thread::sleep,
+    time::Duration,
+};
+
+const SUMMARY_FILE_NAME: &str = "federated-watch-summary.yaml";
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct WatchSummaryEntry {
+    dag_set_path: String,
+    log_file_path: Option<String>,
+    result: Option<FederateResult>,
+}
+
+/// Return the DAG set directories (sub-directories containing at least one YAML file).
+fn get_dag_set_dirs(watch_dir_path: &str) -> Vec<String> {
+    let Ok(read_dir) = fs::read_dir(watch_dir_path) else {
+        warn!("Failed to read the watched directory: {}", watch_dir_path);
+        return Vec::new();
+    };
+    let mut dag_set_dirs: Vec<String> = read_dir
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_dir() && contains_yaml(path))
+        .filter_map(|path| path.to_str().map(|path| path.to_string()))
+        .collect();
+    dag_set_dirs.sort();
+    dag_set_dirs
+}
+
+fn contains_yaml(dir_path: &PathBuf) -> bool {
+    fs::read_dir(dir_path).is_ok_and(|read_dir| {
+        read_dir.filter_map(|entry| entry.ok()).any(|entry| {
+            entry
+                .path()
+                .extension()
+                .is_some_and(|extension| extension == "yaml" || extension == "yml")
+        })
+    })
+}
+
+/// Simulate the DAG sets not seen yet and append their results to the rolling summary.
+///
+/// A DAG set that cannot be loaded is recorded without a result instead of stopping the watch.
+/// Producers should create each DAG set directory atomically (e.g., write then rename),
+/// because a directory is processed as soon as it contains a YAML file.
+///
+/// # Returns
+///
+/// * The DAG set directories processed in this call.
+pub(crate) fn process_new_dag_sets(
+    watch_dir_path: &str,
+    number_of_cores: usize,
+    output_dir_path: &str,
+    processed_dirs: &mut BTreeSet<String>,
+    segmented: bool,
+    preprocessing_cache: &PreprocessingCache,
+) -> Vec<String> {
+    let summary_file_path = format!("{}/{}", output_dir_path, SUMMARY_FILE_NAME);
+    let mut new_dirs = Vec::new();
+    for dag_set_dir in get_dag_set_dirs(watch_dir_path) {
+        if !processed_dirs.insert(dag_set_dir.clone()) {
+            continue;
+        }
+        info!("Simulating the new DAG set: {}", dag_set_dir);
+        let entry = match catch_unwind(AssertUnwindSafe(|| {
+            simulate_federated(
+                &dag_set_dir,
+                number_of_cores,
+                output_dir_path,
+                segmented,
+                preprocessing_cache,
+            )
+        })) {
+            Ok((log_file_path, result)) => WatchSummaryEntry {
+                dag_set_path: dag_set_dir.clone(),
+                log_file_path: Some(log_file_path),
+                result: Some(result),
+            },
+            Err(_) => {
+                warn!("Failed to simulate the DAG set: {}", dag_set_dir);
+                WatchSummaryEntry {
+                    dag_set_path: dag_set_dir.clone(),
+                    log_file_path: None,
+                    result: None,
+                }
+            }
+        };
+        // Each entry is appended as a sequence item so that the summary stays a valid YAML list.
+        let yaml = serde_yaml::to_string(&[entry]).expect("Failed to serialize.");
+        append_info_to_yaml(&summary_file_path, &yaml);
+        new_dirs.push(dag_set_dir);
+    }
+    new_dirs
+}
+
+/// Poll `watch_dir_path` forever and simulate every newly added DAG set.
+pub(crate) fn watch_dir(
+    watch_dir_path: &str,
+    number_of_cores: usize,
+    output_dir_path: &str,
+    poll_interval_ms: u64,
+    segmented: bool,
+    preprocessing_cache: &PreprocessingCache,
+) -> ! {
+    let mut processed_dirs = BTreeSet::new();
+    fs::create_dir_all(output_dir_path).expect("Failed to create the output directory.");
+    loop {
+        process_new_dag_sets(
+            watch_dir_path,
+            number_of_cores,
+            output_dir_path,
+            &mut processed_dirs,
+            segmented,
+            preprocessing_cache,
+        );
+        sleep(Duration::from_millis(poll_interval_ms));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::{create_dir_all, read_to_string, remove_dir_all, write};
+
+    const DAG_YAML: &str = "directed: true
+graph: {}
+links: []
+multigraph: false
+nodes:
+- execution_time: 3
+  id: 0
+  period: 10
+";
+
+    #[test]
+    fn test_process_new_dag_sets_normal() {
+        let watch_dir_path = "../lib/tests/watch_test_input";
+        let output_dir_path = "../lib/tests/watch_test_output";
+        create_dir_all(format!("{}/set_0", watch_dir_path)).unwrap();
+        write(format!("{}/set_0/dag_0.yaml", watch_dir_path), DAG_YAML).unwrap();
+        // A directory without YAML files is not a DAG set yet.
+        create_dir_all(format!("{}/set_1", watch_dir_path)).unwrap();
+        create_dir_all(output_dir_path).unwrap();
+
+        let mut processed_dirs = BTreeSet::new();
+        let new_dirs = process_new_dag_sets(
+            watch_dir_path,
+            4,
+            output_dir_path,
+            &mut processed_dirs,
+            false,
+            &PreprocessingCache::disabled(),
+        );
+        assert_eq!(new_dirs, vec![format!("{}/set_0", watch_dir_path)]);
+
+        let new_dirs = process_new_dag_sets(
+            watch_dir_path,
+            4,
+            output_dir_path,
+            &mut processed_dirs,
+            false,
+            &PreprocessingCache::disabled(),
+        );
+        assert!(new_dirs.is_empty());
+
+        write(format!("{}/set_1/dag_0.yaml", watch_dir_path), DAG_YAML).unwrap();
+        let new_dirs = process_new_dag_sets(
+            watch_dir_path,
+            4,
+            output_dir_path,
+            &mut processed_dirs,
+            false,
+            &PreprocessingCache::disabled(),
+        );
+        assert_eq!(new_dirs, vec![format!("{}/set_1", watch_dir_path)]);
+
+        let file_contents =
+            read_to_string(format!("{}/{}", output_dir_path, SUMMARY_FILE_NAME)).unwrap();
+        let summary: Vec<WatchSummaryEntry> = serde_yaml::from_str(&file_contents).unwrap();
+        assert_eq!(summary.len(), 2);
+        assert_eq!(summary[1].dag_set_path, format!("{}/set_1", watch_dir_path));
+        assert_eq!(
+            summary[1].result,
+            Some(FederateResult::Schedulable {
+                high_dedicated_cores: 0,
+                low_dedicated_cores: 4,
+            })
+        );
+
+        remove_dir_all(watch_dir_path).unwrap();
+        remove_dir_all(output_dir_path).unwrap();
+    }
+
+    #[test]
+    fn test_process_new_dag_sets_broken_dag_set() {
+        let watch_dir_path = "../lib/tests/watch_broken_test_input";
+        let output_dir_path = "../lib/tests/watch_broken_test_output";
+        create_dir_all(format!("{}/set_0", watch_dir_path)).unwrap();
+        write(format!("{}/set_0/dag_0.yaml", watch_dir_path), "nodes: []").unwrap();
+        create_dir_all(output_dir_path).unwrap();
+
+        let mut processed_dirs = BTreeSet::new();
+        let new_dirs = process_new_dag_sets(
+            watch_dir_path,
+            4,
+            output_dir_path,
+            &mut processed_dirs,
+            false,
+            &PreprocessingCache::disabled(),
+        );
+        assert_eq!(new_dirs.len(), 1);
+
+        let file_contents =
+            read_to_string(format!("{}/{}", output_dir_path, SUMMARY_FILE_NAME)).unwrap();
+        let summary: Vec<WatchSummaryEntry> = serde_yaml::from_str(&file_contents).unwrap();
+        assert_eq!(summary[0].result, None);
+        assert_eq!(summary[0].log_file_path, None);
+
+        remove_dir_all(watch_dir_path).unwrap();
+        remove_dir_all(output_dir_path).unwrap();
+    }
+}
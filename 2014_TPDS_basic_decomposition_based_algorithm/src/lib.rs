@@ -0,0 +1,150 @@
+use clap::Parser;
+use lib::{
+    breakdown_factor::find_breakdown_factor,
+    dag_creator::{create_dag_set_from_dir, create_dag_set_names_from_dirs},
+    dag_set_scheduler::{DAGSetSchedulerBase, PreemptiveType},
+    decomposition_scheduler::DecompositionScheduler,
+    graph_extension::GraphExtension,
+    homogeneous::HomogeneousProcessor,
+    log::LogFormat,
+    processor::ProcessorBase,
+    schedulability_screening::screen_dag_set,
+    util::{adjust_to_implicit_deadline, install_interrupt_handler, is_interrupted},
+};
+
+#[derive(Parser)]
+#[clap(
+    args_override_self = true,
+    name = "Basic_Decomposition_Based_Algorithm",
+    version = "1.0",
+    about = "About:
+    Basic_Decomposition_Based_Algorithm operates on the same assumption of period and end_to_end_deadline.
+    Therefore, the period shall be considered as the end_to_end_deadline.
+    If there is no period, the end_to_end_deadline shall be obtained."
+)]
+pub struct ArgParser {
+    ///Path to a TOML file with the arguments. Flags on the command line override it.
+    #[clap(long = "config")]
+    #[allow(dead_code)] // Read by `expand_config_args` before parsing.
+    config: Option<String>,
+    ///Path to DAGSet directory.
+    #[clap(short = 'd', long = "dag_dir_path", required = true)]
+    dag_dir_path: String,
+    ///Number of processing cores.
+    #[clap(short = 'c', long = "number_of_cores", required = true)]
+    number_of_cores: usize,
+    ///Path to output directory.
+    #[clap(short = 'o', long = "output_dir_path", default_value = "../outputs")]
+    output_dir_path: String,
+    ///Enable or disable preemptive scheduling.
+    #[clap(short = 'p', long = "enable_preemption", default_value = "false")]
+    enable_preemption: bool,
+    ///Format of the output log (yaml, json or msgpack).
+    #[clap(short = 'l', long = "log_format", default_value = "yaml")]
+    log_format: LogFormat,
+    ///Add a run-length encoded trace of each core to the log.
+    #[clap(short = 't', long = "core_trace", default_value = "false")]
+    core_trace: bool,
+    ///Write the log, a summary and a manifest into a new directory per run.
+    #[clap(short = 's', long = "run_dir", default_value = "false")]
+    run_dir: bool,
+    ///Skip the simulation if the DAG set fails the schedulability screening,
+    ///and record it as screened out in a run directory.
+    #[clap(short = 'x', long = "skip_infeasible", default_value = "false")]
+    skip_infeasible: bool,
+    ///Search the largest factor of all execution times that meets all deadlines (breakdown
+    ///factor) with this precision, and add it to the log.
+    #[clap(long = "breakdown_precision")]
+    breakdown_precision: Option<f64>,
+    ///Upper bound of the breakdown factor search.
+    #[clap(long = "breakdown_max_factor", default_value = "10.0")]
+    breakdown_max_factor: f64,
+    ///Also write the log to `lib/tests/fixtures/` as a golden fixture for the tests.
+    #[clap(long = "record_fixtures", default_value = "false")]
+    record_fixtures: bool,
+}
+
+/// Run the algorithm with the parsed arguments.
+pub fn run(arg: ArgParser) {
+    // Stop at the end of the current time unit on ctrl-c and dump the partial log.
+    install_interrupt_handler();
+
+    let mut dag_set = create_dag_set_from_dir(&arg.dag_dir_path);
+    adjust_to_implicit_deadline(&mut dag_set);
+    // Screen the DAGs before the decomposition changes their deadlines.
+    let screening_violations = screen_dag_set(&dag_set, arg.number_of_cores);
+
+    let homogeneous_processor = HomogeneousProcessor::new(arg.number_of_cores);
+    let mut decomposition_scheduler = DecompositionScheduler::new(&dag_set, &homogeneous_processor);
+    decomposition_scheduler
+        .get_log_mut()
+        .write_dag_names(&create_dag_set_names_from_dirs(&[&arg.dag_dir_path]));
+
+    // Change whether it is preemptive or not depending on the argument
+    let (preemptive_type, file_name) = if arg.enable_preemption {
+        (
+            PreemptiveType::Preemptive {
+                key: "int_scaled_node_relative_deadline".to_string(),
+            },
+            "decomp_gedf_preemptive",
+        )
+    } else {
+        (PreemptiveType::NonPreemptive, "decomp_gedf_non_preemptive")
+    };
+
+    if arg.skip_infeasible && !screening_violations.is_empty() {
+        decomposition_scheduler.dump_screened_out_run_dir(
+            &arg.output_dir_path,
+            file_name,
+            &dag_set,
+            screening_violations,
+        );
+        return;
+    }
+
+    let schedule_length = decomposition_scheduler.schedule(preemptive_type.clone());
+    if let Some(breakdown_precision) = arg.breakdown_precision.filter(|_| !is_interrupted()) {
+        let breakdown_factor = find_breakdown_factor(
+            &dag_set,
+            &preemptive_type,
+            breakdown_precision,
+            arg.breakdown_max_factor,
+            |inflated_dag_set| {
+                DecompositionScheduler::new(inflated_dag_set, &homogeneous_processor)
+            },
+        );
+        decomposition_scheduler
+            .get_log_mut()
+            .write_breakdown_factor(breakdown_factor);
+    }
+
+    // Check the result
+    let log = decomposition_scheduler.get_log_mut();
+    if arg.core_trace {
+        log.write_core_traces(schedule_length);
+    }
+    let result = dag_set.iter().all(|dag| {
+        log.get_worst_response_time(dag.get_dag_param("dag_id") as usize)
+            <= dag.get_head_period().unwrap()
+    });
+
+    if arg.run_dir {
+        decomposition_scheduler.dump_run_dir(
+            &arg.output_dir_path,
+            file_name,
+            &dag_set,
+            result,
+            arg.log_format,
+        );
+    } else {
+        decomposition_scheduler.dump_simulation_log(
+            &arg.output_dir_path,
+            file_name,
+            result,
+            arg.log_format,
+        );
+    }
+    if arg.record_fixtures {
+        decomposition_scheduler.dump_fixture(file_name, &dag_set, result);
+    }
+}
@@ -0,0 +1,63 @@
+pub mod semi_federated;
+
+use clap::Parser;
+use lib::{
+    dag_creator::create_dag_set_from_dir,
+    fixture::record_fixture,
+    log::{dump_struct, DAGSetInfo, ProcessorInfo},
+    partitioned_edf_scheduler::PartitioningHeuristic,
+    util::create_scheduler_log_yaml,
+};
+use semi_federated::{semi_federated, SemiFederateResult};
+use serde_derive::Serialize;
+
+#[derive(Parser)]
+#[clap(
+    args_override_self = true,
+    name = "Semi_Federated_Algorithm",
+    version = "1.0",
+    about = "About:
+    Semi_Federated_Algorithm dedicates the integer part of the cores required by each heavy DAG,
+    and packs the fractional part and the light DAGs as containers onto the shared cores.
+    The period shall be considered as the end_to_end_deadline."
+)]
+pub struct ArgParser {
+    ///Path to a TOML file with the arguments. Flags on the command line override it.
+    #[clap(long = "config")]
+    #[allow(dead_code)] // Read by `expand_config_args` before parsing.
+    config: Option<String>,
+    ///Path to DAGSet directory.
+    #[clap(short = 'd', long = "dag_dir_path", required = true)]
+    dag_dir_path: String,
+    ///Number of processing cores.
+    #[clap(short = 'c', long = "number_of_cores", required = true)]
+    number_of_cores: usize,
+    ///Path to output directory.
+    #[clap(short = 'o', long = "output_dir_path", default_value = "../outputs")]
+    output_dir_path: String,
+    ///Bin-packing heuristic of the containers (first_fit, best_fit or worst_fit).
+    #[clap(short = 'p', long = "packing_heuristic", default_value = "first_fit")]
+    packing_heuristic: PartitioningHeuristic,
+    ///Also write the log to `lib/tests/fixtures/` as a golden fixture for the tests.
+    #[clap(long = "record_fixtures", default_value = "false")]
+    record_fixtures: bool,
+}
+
+#[derive(Serialize)]
+struct ResultInfo {
+    result: SemiFederateResult,
+}
+
+/// Run the semi-federated test with the parsed arguments and dump the result.
+pub fn run(arg: ArgParser) {
+    let mut dag_set = create_dag_set_from_dir(&arg.dag_dir_path);
+    let result = semi_federated(&mut dag_set, arg.number_of_cores, arg.packing_heuristic);
+
+    let file_path = create_scheduler_log_yaml(&arg.output_dir_path, "semi_federated");
+    dump_struct(&file_path, &DAGSetInfo::new(&dag_set));
+    dump_struct(&file_path, &ProcessorInfo::new(arg.number_of_cores));
+    dump_struct(&file_path, &ResultInfo { result });
+    if arg.record_fixtures {
+        record_fixture(&file_path, "semi_federated", &dag_set);
+    }
+}
@@ -0,0 +1,7 @@
+use clap::Parser;
+use lib::cli_config::expand_config_args;
+use rtss_semi_federated::{run, ArgParser};
+
+fn main() {
+    run(ArgParser::parse_from(expand_config_args(std::env::args())));
+}
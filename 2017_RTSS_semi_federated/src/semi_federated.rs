@@ -0,0 +1,215 @@
+//! This module implements the semi-federated scheduling algorithm (Jiang et al., RTSS 2017).
+//!
+//! Federated scheduling dedicates `ceil(x)` cores to a heavy DAG, where `x = (C - L) / (D - L)`
+//! for the volume `C`, the critical path length `L` and the deadline `D`, and wastes the fraction
+//! of the last core. Semi-federated scheduling dedicates `floor(x)` cores and reserves the
+//! fraction `x - floor(x)` in a container on a shared core. Light DAGs run sequentially in
+//! containers of their utilization, and the containers are packed onto the remaining cores.
+//!
+//! The container bandwidth is the fraction itself, without the inflation for the supply delay
+//! of the container in the refined analysis of the paper.
+use lib::{
+    dag_task::UtilizationClass,
+    fractional_core_container::{pack_containers, CoreContainer},
+    graph_extension::{GraphExtension, NodeData},
+    partitioned_edf_scheduler::PartitioningHeuristic,
+};
+use petgraph::graph::Graph;
+use serde_derive::{Deserialize, Serialize};
+use SemiFederateResult::{Schedulable, Unschedulable};
+
+/// A fraction below this is regarded as an integer number of cores.
+const FRACTION_EPSILON: f32 = 1e-4;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct DAGCoreAllocation {
+    pub dag_id: usize,
+    pub dedicated_core_ids: Vec<usize>,
+    /// 0 if the DAG runs on its dedicated cores only.
+    pub container_bandwidth: f32,
+    pub shared_core_id: Option<usize>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub enum SemiFederateResult {
+    Schedulable {
+        dedicated_cores: usize,
+        shared_cores: usize,
+        allocations: Vec<DAGCoreAllocation>,
+    },
+    Unschedulable {
+        reason: String,
+        insufficient_cores: usize,
+    },
+}
+
+/// Allocate the dedicated cores and the containers of the DAGs.
+/// The dedicated cores are numbered from 0 in the order of the DAGs, followed by the shared cores.
+///
+/// # Arguments
+///
+/// * `dag_set` - DAGs with the "period" parameter, which is also the deadline.
+/// * `number_of_cores` - The total number of available processing cores.
+/// * `heuristic` - The bin-packing heuristic for the containers.
+pub fn semi_federated(
+    dag_set: &mut [Graph<NodeData, i32>],
+    number_of_cores: usize,
+    heuristic: PartitioningHeuristic,
+) -> SemiFederateResult {
+    let mut allocations = Vec::with_capacity(dag_set.len());
+    let mut containers = Vec::new();
+    let mut dedicated_cores = 0;
+
+    for (dag_i, dag) in dag_set.iter_mut().enumerate() {
+        let period = dag.get_head_period().unwrap();
+        let end_to_end_deadline = period; // implicit deadline
+        let volume = dag.get_volume();
+        let critical_path = dag.get_critical_path();
+        let critical_path_wcet = dag.get_total_wcet_from_nodes(&critical_path);
+        if critical_path_wcet > end_to_end_deadline {
+            return Unschedulable {
+                reason: "The critical path length is greater than end_to_end_deadline.".to_string(),
+                insufficient_cores: 0,
+            };
+        }
+
+        let (dag_dedicated_cores, container_bandwidth) =
+            if UtilizationClass::classify(dag) == Some(UtilizationClass::Heavy) {
+                let required_cores = (volume - critical_path_wcet) as f32
+                    / (end_to_end_deadline - critical_path_wcet) as f32;
+                let fraction = required_cores - required_cores.floor();
+                (
+                    required_cores.floor() as usize,
+                    if fraction > FRACTION_EPSILON {
+                        fraction
+                    } else {
+                        0.0
+                    },
+                )
+            } else {
+                (0, volume as f32 / end_to_end_deadline as f32)
+            };
+        if dedicated_cores + dag_dedicated_cores > number_of_cores {
+            return Unschedulable {
+                reason: "Insufficient number of cores for the dedicated cores.".to_string(),
+                insufficient_cores: dedicated_cores + dag_dedicated_cores - number_of_cores,
+            };
+        }
+        if container_bandwidth > 0.0 {
+            containers.push(CoreContainer::new(dag_i, container_bandwidth));
+        }
+        allocations.push(DAGCoreAllocation {
+            dag_id: dag_i,
+            dedicated_core_ids: (dedicated_cores..dedicated_cores + dag_dedicated_cores).collect(),
+            container_bandwidth,
+            shared_core_id: None,
+        });
+        dedicated_cores += dag_dedicated_cores;
+    }
+
+    let shared_cores = number_of_cores - dedicated_cores;
+    let packing = pack_containers(&containers, shared_cores, heuristic);
+    if !packing.is_feasible() {
+        let unfit_bandwidth: f32 = packing
+            .unfit_containers
+            .iter()
+            .map(|container| container.bandwidth)
+            .sum();
+        return Unschedulable {
+            reason: "Insufficient number of cores for the containers.".to_string(),
+            insufficient_cores: unfit_bandwidth.ceil() as usize,
+        };
+    }
+    for allocation in allocations.iter_mut() {
+        allocation.shared_core_id = packing
+            .get_core_index(allocation.dag_id)
+            .map(|core_i| dedicated_cores + core_i);
+    }
+    Schedulable {
+        dedicated_cores,
+        shared_cores,
+        allocations,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn create_node(id: i32, key: &str, value: i32) -> NodeData {
+        let mut params = BTreeMap::new();
+        params.insert(key.to_string(), value);
+        NodeData { id, params }
+    }
+
+    /// Volume 15, critical path 8 and period 10, which require 3.5 cores.
+    fn create_high_utilization_dag() -> Graph<NodeData, i32> {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = {
+            let mut params = BTreeMap::new();
+            params.insert("execution_time".to_owned(), 4);
+            params.insert("period".to_owned(), 10);
+            dag.add_node(NodeData { id: 0, params })
+        };
+        for (id, execution_time) in [(1, 4), (2, 3), (3, 3), (4, 1)] {
+            let node_i = dag.add_node(create_node(id, "execution_time", execution_time));
+            dag.add_edge(n0, node_i, 1);
+        }
+        dag
+    }
+
+    /// Utilization 1/3.
+    fn create_low_utilization_dag() -> Graph<NodeData, i32> {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = {
+            let mut params = BTreeMap::new();
+            params.insert("execution_time".to_owned(), 3);
+            params.insert("period".to_owned(), 30);
+            dag.add_node(NodeData { id: 0, params })
+        };
+        let n1 = dag.add_node(create_node(1, "execution_time", 7));
+        dag.add_edge(n0, n1, 1);
+        dag
+    }
+
+    #[test]
+    fn test_semi_federated_normal() {
+        let mut dag_set = vec![create_high_utilization_dag(), create_low_utilization_dag()];
+        // Federated scheduling would need 4 cores for the heavy DAG and 1 for the light DAG.
+        let result = semi_federated(&mut dag_set, 4, PartitioningHeuristic::FirstFit);
+        let Schedulable {
+            dedicated_cores,
+            shared_cores,
+            allocations,
+        } = result
+        else {
+            panic!("Unschedulable: {:?}", result);
+        };
+        assert_eq!((dedicated_cores, shared_cores), (3, 1));
+        assert_eq!(allocations[0].dedicated_core_ids, vec![0, 1, 2]);
+        assert_eq!(allocations[0].container_bandwidth, 0.5);
+        assert_eq!(allocations[0].shared_core_id, Some(3));
+        assert!(allocations[1].dedicated_core_ids.is_empty());
+        assert_eq!(allocations[1].shared_core_id, Some(3));
+    }
+
+    #[test]
+    fn test_semi_federated_lack_cores_for_containers() {
+        let mut dag_set = vec![create_high_utilization_dag(), create_low_utilization_dag()];
+        assert_eq!(
+            semi_federated(&mut dag_set, 3, PartitioningHeuristic::FirstFit),
+            Unschedulable {
+                reason: "Insufficient number of cores for the containers.".to_string(),
+                insufficient_cores: 1
+            }
+        );
+        assert_eq!(
+            semi_federated(&mut dag_set, 2, PartitioningHeuristic::FirstFit),
+            Unschedulable {
+                reason: "Insufficient number of cores for the dedicated cores.".to_string(),
+                insufficient_cores: 1
+            }
+        );
+    }
+}
@@ -0,0 +1,125 @@
+mod parallel_provider_consumer;
+mod prioritization_cpc_model;
+
+use clap::Parser;
+use lib::{
+    dag_creator::{create_dag_from_yaml, create_dag_names_from_yaml},
+    dag_scheduler::DAGSchedulerBase,
+    fixed_priority_scheduler::FixedPriorityScheduler,
+    graph_extension::{GraphExtension, NodeData},
+    homogeneous::HomogeneousProcessor,
+    log::{DAGSchedulerResultInfo, LogFormat},
+    lpt_scheduler::LptScheduler,
+    preprocessing_cache::{get_dag_hash, PreprocessingCache},
+    processor::ProcessorBase,
+};
+use log::warn;
+use petgraph::graph::{Graph, NodeIndex};
+use std::collections::BTreeMap;
+
+#[derive(Parser)]
+#[clap(
+    args_override_self = true,
+    name = "CPC_Model_Based_Algorithm",
+    version = "1.0",
+    about = "About:
+    The CPC_Model_Based_Algorithm operates under the assumption of a constrained deadline.
+    In essence, it presumes that the input Directed Acyclic Graph (DAG) incorporates and adheres to these constrained deadlines.
+    If, however, the input DAG does not contain a predefined constrained deadline, 
+    the algorithm will impose one by multiplying the period of the input DAG by an arbitrary multiplier."
+)]
+pub struct ArgParser {
+    ///Path to a TOML file with the arguments. Flags on the command line override it.
+    #[clap(long = "config")]
+    #[allow(dead_code)] // Read by `expand_config_args` before parsing.
+    config: Option<String>,
+    ///Path to DAG file.
+    #[clap(short = 'f', long = "dag_file_path", required = true)]
+    dag_file_path: String,
+    ///Number of processing cores.
+    #[clap(short = 'c', long = "number_of_cores", required = true)]
+    number_of_cores: usize,
+    ///Path to output directory.
+    #[clap(short = 'o', long = "output_dir_path", default_value = "../outputs")]
+    output_dir_path: String,
+    ///Multiplier to compute constrained deadlines
+    #[clap(short = 'r', long = "ratio_deadline_to_period", default_value = "1.0")]
+    ratio_deadline_to_period: f32,
+    ///Format of the output log (yaml, json or msgpack).
+    #[clap(short = 'l', long = "log_format", default_value = "yaml")]
+    log_format: LogFormat,
+    ///Path to the directory caching the CPC priorities of each DAG across runs.
+    #[clap(long = "cache_dir_path", default_value = "../outputs/cache")]
+    cache_dir_path: String,
+    ///Compute the CPC priorities without the cache.
+    #[clap(long = "no_cache", default_value = "false")]
+    no_cache: bool,
+    ///Also write the log to `lib/tests/fixtures/` as a golden fixture for the tests.
+    #[clap(long = "record_fixtures", default_value = "false")]
+    record_fixtures: bool,
+    ///Schedule by LPT (longest execution time first) instead of the CPC priorities, for comparison.
+    #[clap(long = "lpt", default_value = "false")]
+    lpt: bool,
+}
+
+/// Schedule `dag` with `scheduler` and dump the log as `alg_name`.
+fn schedule_and_dump<S: DAGSchedulerBase<HomogeneousProcessor>>(
+    mut scheduler: S,
+    dag: &Graph<NodeData, i32>,
+    arg: &ArgParser,
+    alg_name: &str,
+) {
+    let mut log = scheduler.get_log();
+    log.write_dag_names(create_dag_names_from_yaml(&arg.dag_file_path));
+    scheduler.set_log(log);
+    let (schedule_length, _) = scheduler.schedule();
+    let constrained_end_to_end_deadline = if let Some(deadline) = dag.get_end_to_end_deadline() {
+        deadline as f32
+    } else {
+        warn!("Since the end-to-end deadline is not set in the input DAG, the end-to-end deadline is determined using ratio_deadline_to_period.");
+        dag.get_head_period().unwrap() as f32 * arg.ratio_deadline_to_period
+    };
+    let result = (schedule_length as f32) <= constrained_end_to_end_deadline;
+    let result_info =
+        DAGSchedulerResultInfo::new(schedule_length, arg.ratio_deadline_to_period, result);
+    if arg.record_fixtures {
+        scheduler.dump_fixture(alg_name, dag, result_info.clone());
+    }
+    scheduler.dump_simulation_log(&arg.output_dir_path, alg_name, result_info, arg.log_format);
+}
+
+/// Run the algorithm with the parsed arguments.
+pub fn run(arg: ArgParser) {
+    if arg.ratio_deadline_to_period > 1.0 {
+        panic!("ratio_deadline_to_period must be less than or equal to 1.0");
+    }
+    let mut dag = create_dag_from_yaml(&arg.dag_file_path, false);
+    let homogeneous_processor = HomogeneousProcessor::new(arg.number_of_cores);
+    if arg.lpt {
+        let lpt_scheduler = LptScheduler::new(&dag, &homogeneous_processor);
+        schedule_and_dump(lpt_scheduler, &dag, &arg, "lpt");
+        return;
+    }
+    let preprocessing_cache = if arg.no_cache {
+        PreprocessingCache::disabled()
+    } else {
+        PreprocessingCache::new(&arg.cache_dir_path)
+    };
+    let priorities: BTreeMap<usize, i32> =
+        preprocessing_cache.get_or_compute("cpc_priorities", get_dag_hash(&dag), || {
+            let mut prioritized_dag = dag.clone();
+            prioritization_cpc_model::assign_priority_to_cpc_model(&mut prioritized_dag);
+            prioritized_dag
+                .node_indices()
+                .filter_map(|node_i| {
+                    let priority = *prioritized_dag[node_i].params.get("priority")?;
+                    Some((node_i.index(), priority))
+                })
+                .collect()
+        });
+    for (node_i, priority) in priorities {
+        dag.add_param(NodeIndex::new(node_i), "priority", priority);
+    }
+    let fixed_priority_scheduler = FixedPriorityScheduler::new(&dag, &homogeneous_processor);
+    schedule_and_dump(fixed_priority_scheduler, &dag, &arg, "cpc_model_based");
+}
@@ -1,5 +1,5 @@
 use crate::parallel_provider_consumer::{get_f_consumers, get_providers};
-use lib::graph_extension::{GraphExtension, NodeData};
+use lib::graph_extension::{GraphExtension, NodeData, NodeIndexMap};
 use petgraph::graph::{Graph, NodeIndex};
 
 //Create a dag for f_consumer only
@@ -19,12 +19,13 @@ fn create_shrunk_dag(
 }
 
 fn convert_shrunk_indices_to_original(
+    original_node_index_map: &NodeIndexMap,
     shrunk_dag: &Graph<NodeData, i32>,
     shrunk_indices: &[NodeIndex],
 ) -> Vec<NodeIndex> {
     shrunk_indices
         .iter()
-        .map(|&node_i| NodeIndex::new(shrunk_dag[node_i].id as usize))
+        .map(|&node_i| original_node_index_map.get_node_index(shrunk_dag[node_i].id))
         .collect()
 }
 
@@ -43,11 +44,13 @@ fn prioritize_path_from_head_with_increment(
 
 #[allow(dead_code)] //TODO: remove
 pub fn assign_priority_to_cpc_model(dag: &mut Graph<NodeData, i32>) {
-    assign_priority_to_cpc_model_core(dag, &mut dag.clone(), &mut 0);
+    let node_index_map = NodeIndexMap::new(dag);
+    assign_priority_to_cpc_model_core(dag, &node_index_map, &mut dag.clone(), &mut 0);
 }
 
 fn assign_priority_to_cpc_model_core(
     original_dag: &mut Graph<NodeData, i32>,
+    original_node_index_map: &NodeIndexMap,
     shrunk_dag: &mut Graph<NodeData, i32>,
     current_priority: &mut i32,
 ) {
@@ -55,9 +58,11 @@ fn assign_priority_to_cpc_model_core(
     let providers = get_providers(shrunk_dag, &critical_path);
     let f_consumers = get_f_consumers(shrunk_dag, &critical_path);
     //Rule 1. Priority is given to critical nodes
+    let original_critical_path =
+        convert_shrunk_indices_to_original(original_node_index_map, shrunk_dag, &critical_path);
     prioritize_path_from_head_with_increment(
         original_dag,
-        &convert_shrunk_indices_to_original(shrunk_dag, &critical_path),
+        &original_critical_path,
         current_priority,
     );
     //Rule 2. Priority is given to consumers for providers located before
@@ -70,21 +75,24 @@ fn assign_priority_to_cpc_model_core(
                 if f_consumer_critical_path.iter().any(|&node_i| {
                     f_consumer_dag
                         .get_pre_nodes(node_i)
-                        .map_or(false, |pre_nodes| pre_nodes.len() > 1)
+                        .is_some_and(|pre_nodes| pre_nodes.len() > 1)
                 }) {
                     assign_priority_to_cpc_model_core(
                         original_dag,
+                        original_node_index_map,
                         &mut f_consumer_dag,
                         current_priority,
                     );
                 } else {
                     //Rule 3. give high priority to the nodes in the longest path
+                    let original_path = convert_shrunk_indices_to_original(
+                        original_node_index_map,
+                        &f_consumer_dag,
+                        &f_consumer_critical_path,
+                    );
                     prioritize_path_from_head_with_increment(
                         original_dag,
-                        &convert_shrunk_indices_to_original(
-                            &f_consumer_dag,
-                            &f_consumer_critical_path,
-                        ),
+                        &original_path,
                         current_priority,
                     );
                 }
@@ -7,15 +7,19 @@
 //! -----------------
 use getset::{CopyGetters, Setters};
 use lib::{
+    compact_dag::{CompactDAG, CompactReadiness},
     core::ProcessResult,
     dag_scheduler::DAGSchedulerBase,
     dag_set_scheduler::{DAGSetSchedulerBase, DAGState, DAGStateManagerBase, PreemptiveType},
+    dag_task::DagTask,
+    execution_time_trace::ExecutionTimeTrace,
     getset_dag_set_scheduler, getset_dag_state_manager,
-    graph_extension::{GraphExtension, NodeData},
+    graph_extension::{get_node_index_maps, GraphExtension, NodeData, NodeIndexMap},
     homogeneous::HomogeneousProcessor,
     log::DAGSetSchedulerLog,
+    preprocessing_cache::{get_dag_hash, get_fnv_hash, PreprocessingCache},
     processor::ProcessorBase,
-    util::{get_hyper_period, get_process_core_indices},
+    util::{clear_interrupted, get_hyper_period, get_process_core_indices, is_interrupted},
 };
 use petgraph::{graph::NodeIndex, Graph};
 use std::collections::VecDeque;
@@ -74,7 +78,12 @@ pub struct DynFedDAGStateManager {
     execution_order: VecDeque<NodeIndex>,
     initial_execution_order: VecDeque<NodeIndex>,
     release_count: i32,
+    skipped_release_count: i32,
     dag_state: DAGState,
+    has_feedback_edge: Option<bool>,
+    dag_task: Option<DagTask>,
+    compact_dag: Option<CompactDAG>,
+    compact_readiness: Option<CompactReadiness>,
 }
 
 impl DAGStateManagerBase for DynFedDAGStateManager {
@@ -115,6 +124,17 @@ impl DynFedDAGStateManager {
         self.execution_order.pop_front().unwrap()
     }
 
+    /// Allocate the first ready node in the remaining execution order satisfying `can_allocate`,
+    /// skipping the nodes before it.
+    fn allocate_first(&mut self, can_allocate: impl Fn(NodeIndex) -> bool) -> Option<NodeIndex> {
+        let position = self
+            .execution_order
+            .iter()
+            .position(|&node_i| self.is_node_ready(node_i) && can_allocate(node_i))?;
+        self.num_using_cores += 1;
+        self.execution_order.remove(position)
+    }
+
     fn decrement_num_using_cores(&mut self) {
         self.num_using_cores -= 1;
     }
@@ -133,10 +153,37 @@ where
     T: DAGSchedulerBase<HomogeneousProcessor>,
 {
     dag_set: Vec<Graph<NodeData, i32>>,
+    node_index_maps: Vec<NodeIndexMap>,
     processor: HomogeneousProcessor,
     scheduler: T,
     log: DAGSetSchedulerLog,
     current_time: i32,
+    preprocessing_cache: PreprocessingCache,
+    execution_time_trace: Option<ExecutionTimeTrace>,
+    late_binding: bool,
+}
+
+impl<T> DynamicFederatedScheduler<T>
+where
+    T: DAGSchedulerBase<HomogeneousProcessor>,
+{
+    /// Reuse the minimum cores and execution orders computed by previous runs.
+    pub fn set_preprocessing_cache(&mut self, preprocessing_cache: PreprocessingCache) {
+        self.preprocessing_cache = preprocessing_cache;
+    }
+
+    pub fn set_execution_time_trace(&mut self, trace: ExecutionTimeTrace) {
+        self.execution_time_trace = Some(trace);
+    }
+
+    /// Let a free core of a DAG take the first ready node of the remaining execution order
+    /// instead of waiting until the head of the order is ready.
+    ///
+    /// This shortens the response times when nodes finish earlier than their WCETs, but gives up
+    /// the timing-anomaly freedom of replaying the order.
+    pub fn set_late_binding(&mut self) {
+        self.late_binding = true;
+    }
 }
 
 impl<T> DAGSetSchedulerBase<HomogeneousProcessor> for DynamicFederatedScheduler<T>
@@ -145,30 +192,57 @@ where
 {
     getset_dag_set_scheduler!(HomogeneousProcessor);
 
+    fn get_execution_time_trace(&self) -> Option<&ExecutionTimeTrace> {
+        self.execution_time_trace.as_ref()
+    }
+
     fn new(dag_set: &[Graph<NodeData, i32>], processor: &HomogeneousProcessor) -> Self {
         Self {
+            node_index_maps: get_node_index_maps(dag_set),
             dag_set: dag_set.to_vec(),
             processor: processor.clone(),
             scheduler: T::new(&Graph::<NodeData, i32>::new(), processor),
             log: DAGSetSchedulerLog::new(dag_set, processor.get_number_of_cores()),
             current_time: 0,
+            preprocessing_cache: PreprocessingCache::disabled(),
+            execution_time_trace: None,
+            late_binding: false,
         }
     }
 
     fn schedule(&mut self, _: PreemptiveType) -> i32 {
+        clear_interrupted();
+        self.log.write_schedule_start();
         // Initialize DAGStateManagers
         let mut managers = vec![DynFedDAGStateManager::default(); self.dag_set.len()];
+        // The result depends on the scheduler type, so the type is a part of the cache name.
+        let cache_name = format!(
+            "dynfed_minimum_cores-{:016x}",
+            get_fnv_hash(std::any::type_name::<T>())
+        );
         for dag in self.dag_set.iter() {
             let dag_id = dag.get_dag_param("dag_id") as usize;
-            let (minimum_cores, execution_order) =
-                calculate_minimum_cores_and_execution_order(dag, &mut self.scheduler);
+            let (minimum_cores, execution_order): (usize, Vec<usize>) = self
+                .preprocessing_cache
+                .get_or_compute(&cache_name, get_dag_hash(dag), || {
+                    let (minimum_cores, execution_order) =
+                        calculate_minimum_cores_and_execution_order(dag, &mut self.scheduler);
+                    (
+                        minimum_cores,
+                        execution_order
+                            .iter()
+                            .map(|node_i| node_i.index())
+                            .collect(),
+                    )
+                });
             managers[dag_id].set_minimum_cores(minimum_cores as i32);
-            managers[dag_id].set_execution_order(execution_order);
+            managers[dag_id]
+                .set_execution_order(execution_order.into_iter().map(NodeIndex::new).collect());
         }
 
         // Start scheduling
         let hyper_period = get_hyper_period(&self.dag_set);
-        while self.get_current_time() < hyper_period {
+        while self.get_current_time() < hyper_period && !is_interrupted() {
             // Release DAGs
             self.release_dags(&mut managers);
             // Start DAGs if there are free cores
@@ -188,8 +262,27 @@ where
                     continue;
                 }
 
+                if self.late_binding {
+                    while managers[dag_id].get_unused_cores() > 0 {
+                        let Some(node_i) = managers[dag_id]
+                            .allocate_first(|node_i| self.can_allocate_node(&dag[node_i]))
+                        else {
+                            break;
+                        };
+                        let core_id = self.processor.get_idle_core_index().unwrap();
+                        self.allocate_node(
+                            &dag[node_i],
+                            core_id,
+                            managers[dag_id].get_release_count() as usize,
+                        );
+                    }
+                    continue;
+                }
                 while let Some(node_i) = managers[dag_id].get_execution_order_head() {
-                    if dag.is_node_ready(*node_i) && managers[dag_id].get_unused_cores() > 0 {
+                    if managers[dag_id].is_node_ready(*node_i)
+                        && managers[dag_id].get_unused_cores() > 0
+                        && self.can_allocate_node(&dag[*node_i])
+                    {
                         let core_id = self.processor.get_idle_core_index().unwrap();
                         let node = &dag[managers[dag_id].allocate_head()];
                         self.allocate_node(
@@ -226,11 +319,12 @@ where
 mod tests {
     use super::*;
     use lib::fixed_priority_scheduler::FixedPriorityScheduler;
+    use lib::fixture::{assert_matches_fixture, create_test_output_dir, get_fixture_path};
     use lib::homogeneous::HomogeneousProcessor;
     use lib::processor::ProcessorBase;
     use lib::util::load_yaml;
     use std::collections::BTreeMap;
-    use std::fs::remove_file;
+    use std::fs;
 
     fn create_node(id: i32, key: &str, value: i32) -> NodeData {
         let mut params = BTreeMap::new();
@@ -298,155 +392,81 @@ mod tests {
         let time = dynfed.schedule(PreemptiveType::NonPreemptive);
         assert_eq!(time, 300);
 
-        let file_path = dynfed.dump_log("../lib/tests", "dyn_test");
-        let yaml_docs = load_yaml(&file_path);
-        let yaml_doc = &yaml_docs[0];
-
-        assert_eq!(
-            yaml_doc["dag_set_info"]["total_utilization"]
-                .as_f64()
-                .unwrap(),
-            3.705357
-        );
-        assert_eq!(
-            yaml_doc["dag_set_info"]["each_dag_info"][0]["critical_path_length"]
-                .as_i64()
-                .unwrap(),
-            50
-        );
-        assert_eq!(
-            yaml_doc["dag_set_info"]["each_dag_info"][0]["period"]
-                .as_i64()
-                .unwrap(),
-            150
-        );
-        assert_eq!(
-            yaml_doc["dag_set_info"]["each_dag_info"][0]["end_to_end_deadline"]
-                .as_i64()
-                .unwrap(),
-            50
-        );
-        assert_eq!(
-            yaml_doc["dag_set_info"]["each_dag_info"][0]["volume"]
-                .as_i64()
-                .unwrap(),
-            70
-        );
-        assert_eq!(
-            yaml_doc["dag_set_info"]["each_dag_info"][0]["utilization"]
-                .as_f64()
-                .unwrap(),
-            2.142857
-        );
-        assert_eq!(
-            yaml_doc["dag_set_info"]["each_dag_info"][1]["utilization"]
-                .as_f64()
-                .unwrap(),
-            1.5625
-        );
-
-        assert_eq!(
-            yaml_doc["processor_info"]["number_of_cores"]
-                .as_i64()
-                .unwrap(),
-            5
-        );
-
-        assert_eq!(yaml_doc["dag_set_log"][1]["dag_id"].as_i64().unwrap(), 1);
-        assert_eq!(
-            yaml_doc["dag_set_log"][1]["release_time"][0]
-                .as_i64()
-                .unwrap(),
-            0
-        );
-        assert_eq!(
-            yaml_doc["dag_set_log"][1]["finish_time"][0]
-                .as_i64()
-                .unwrap(),
-            53
-        );
-        assert_eq!(
-            yaml_doc["dag_set_log"][1]["response_time"][0]
-                .as_i64()
-                .unwrap(),
-            53
-        );
-        assert_eq!(
-            yaml_doc["dag_set_log"][1]["average_response_time"]
-                .as_f64()
-                .unwrap(),
-            53.0
-        );
-        assert_eq!(
-            yaml_doc["dag_set_log"][1]["worst_response_time"]
-                .as_i64()
-                .unwrap(),
-            53
-        );
+        let dir_path = create_test_output_dir("dynfed_normal");
+        let file_path = dynfed.dump_log(&dir_path, "dynfed_normal");
 
-        assert_eq!(
-            yaml_doc["node_set_logs"][1][2]["core_id"].as_i64().unwrap(),
-            1
-        );
-        assert_eq!(
-            yaml_doc["node_set_logs"][1][2]["dag_id"].as_i64().unwrap(),
-            1
-        );
-        assert_eq!(
-            yaml_doc["node_set_logs"][1][2]["node_id"].as_i64().unwrap(),
-            3
-        );
-        assert_eq!(
-            yaml_doc["node_set_logs"][1][2]["job_id"].as_i64().unwrap(),
-            0
+        assert_matches_fixture(
+            &fs::read_to_string(&file_path).unwrap(),
+            &get_fixture_path("dynfed_normal", &dag_set),
         );
+        // Key values of the fixture, checked by hand.
+        let yaml_doc = &load_yaml(&file_path)[0];
+        let dag_set_log = &yaml_doc["dag_set_log"][1];
+        assert_eq!(dag_set_log["release_time"][0].as_i64().unwrap(), 0);
+        assert_eq!(dag_set_log["finish_time"][0].as_i64().unwrap(), 53);
+        assert_eq!(dag_set_log["worst_response_time"].as_i64().unwrap(), 53);
+        let node_set_logs = &yaml_doc["node_set_logs"][1];
+        assert_eq!(node_set_logs[2]["core_id"].as_i64().unwrap(), 1);
+        assert_eq!(node_set_logs[2]["node_id"].as_i64().unwrap(), 3);
         // start_time
-        assert_eq!(
-            yaml_doc["node_set_logs"][1][2]["event_time"]
-                .as_str()
-                .unwrap(),
-            "11"
-        );
+        assert_eq!(node_set_logs[2]["event_time"].as_str().unwrap(), "11");
         // finish_time
+        assert_eq!(node_set_logs[4]["event_time"].as_str().unwrap(), "22");
+        let processor_log = &yaml_doc["processor_log"];
         assert_eq!(
-            yaml_doc["node_set_logs"][1][4]["event_time"]
-                .as_str()
-                .unwrap(),
-            "22"
-        );
-
-        assert_eq!(
-            yaml_doc["processor_log"]["average_utilization"]
-                .as_f64()
-                .unwrap(),
-            0.22133335
+            processor_log["average_utilization"].as_f64().unwrap(),
+            0.22133334
         );
         assert_eq!(
-            yaml_doc["processor_log"]["variance_utilization"]
-                .as_f64()
-                .unwrap(),
-            0.033460446
-        );
-
-        assert_eq!(
-            yaml_doc["processor_log"]["core_logs"][0]["core_id"]
-                .as_i64()
-                .unwrap(),
-            0
-        );
-        assert_eq!(
-            yaml_doc["processor_log"]["core_logs"][0]["total_proc_time"]
+            processor_log["core_logs"][0]["total_proc_time"]
                 .as_i64()
                 .unwrap(),
             156
         );
-        assert_eq!(
-            yaml_doc["processor_log"]["core_logs"][0]["utilization"]
-                .as_f64()
-                .unwrap(),
-            0.52
-        );
+        fs::remove_dir_all(dir_path).unwrap();
+    }
 
-        remove_file(file_path).unwrap();
+    #[test]
+    fn test_dynfed_late_binding() {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 1));
+        let n1 = dag.add_node(create_node(1, "execution_time", 4));
+        let n2 = dag.add_node(create_node(2, "execution_time", 2));
+        let n3 = dag.add_node(create_node(3, "execution_time", 4));
+        let n4 = dag.add_node(create_node(4, "execution_time", 3));
+        let n5 = dag.add_node(create_node(5, "execution_time", 1));
+        dag.add_param(n0, "period", 20);
+        dag.add_param(n5, "end_to_end_deadline", 10);
+        for (node_i, priority) in [(n0, 0), (n1, 0), (n2, 1), (n3, 2), (n4, 0), (n5, 0)] {
+            dag.add_param(node_i, "priority", priority);
+        }
+        dag.add_edge(n0, n1, 1);
+        dag.add_edge(n0, n2, 1);
+        dag.add_edge(n0, n3, 1);
+        dag.add_edge(n2, n4, 1);
+        dag.add_edge(n1, n5, 1);
+        dag.add_edge(n3, n5, 1);
+        dag.add_edge(n4, n5, 1);
+        dag.set_dag_param("dag_id", 0);
+
+        // The order on 2 cores is n0, n1, n2, n4, n3, n5, but n1 finishes at 2 instead of 5.
+        let mut trace = ExecutionTimeTrace::default();
+        trace.insert(0, 1, 0, 1);
+        let get_worst_response_time = |late_binding: bool| {
+            let mut dynfed: DynamicFederatedScheduler<
+                FixedPriorityScheduler<HomogeneousProcessor>,
+            > = DynamicFederatedScheduler::new(&[dag.clone()], &HomogeneousProcessor::new(2));
+            dynfed.set_execution_time_trace(trace.clone());
+            if late_binding {
+                dynfed.set_late_binding();
+            }
+            dynfed.schedule(PreemptiveType::NonPreemptive);
+            dynfed.get_log_mut().get_worst_response_time(0)
+        };
+
+        // The static order replay idles the free core until n4 is ready at 3.
+        assert_eq!(get_worst_response_time(false), 8);
+        // Late binding starts n3 on it at 2.
+        assert_eq!(get_worst_response_time(true), 7);
     }
 }
@@ -0,0 +1,169 @@
+mod dynfed;
+
+use clap::Parser;
+use dynfed::DynamicFederatedScheduler;
+use lib::{
+    breakdown_factor::find_breakdown_factor,
+    dag_creator::{create_dag_set_from_dir, create_dag_set_names_from_dirs},
+    dag_set_scheduler::{DAGSetSchedulerBase, PreemptiveType},
+    execution_time_trace::ExecutionTimeTrace,
+    fixed_priority_scheduler::FixedPriorityScheduler,
+    graph_extension::{GraphExtension, NodeData},
+    homogeneous::HomogeneousProcessor,
+    log::LogFormat,
+    preprocessing_cache::PreprocessingCache,
+    processor::ProcessorBase,
+    schedulability_screening::screen_dag_set,
+    util::{adjust_to_implicit_deadline, install_interrupt_handler, is_interrupted},
+};
+use petgraph::Graph;
+
+#[derive(Parser)]
+#[clap(
+    args_override_self = true,
+    name = "DynFed_Algorithm",
+    version = "1.0",
+    about = "About:
+    DynFed_Algorithm operates on the same assumption of period and end_to_end_deadline.
+    Therefore, the period shall be considered as the end_to_end_deadline.
+    If there is no period, the end_to_end_deadline shall be obtained."
+)]
+pub struct ArgParser {
+    ///Path to a TOML file with the arguments. Flags on the command line override it.
+    #[clap(long = "config")]
+    #[allow(dead_code)] // Read by `expand_config_args` before parsing.
+    config: Option<String>,
+    ///Path to DAGSet directory.
+    #[clap(short = 'd', long = "dag_dir_path", required = true)]
+    dag_dir_path: String,
+    ///Number of processing cores.
+    #[clap(short = 'c', long = "number_of_cores", required = true)]
+    number_of_cores: usize,
+    ///Path to output directory.
+    #[clap(short = 'o', long = "output_dir_path", default_value = "../outputs")]
+    output_dir_path: String,
+    ///Format of the output log (yaml, json or msgpack).
+    #[clap(short = 'l', long = "log_format", default_value = "yaml")]
+    log_format: LogFormat,
+    ///Add a run-length encoded trace of each core to the log.
+    #[clap(short = 't', long = "core_trace", default_value = "false")]
+    core_trace: bool,
+    ///Write the log, a summary and a manifest into a new directory per run.
+    #[clap(short = 's', long = "run_dir", default_value = "false")]
+    run_dir: bool,
+    ///Skip the simulation if the DAG set fails the schedulability screening,
+    ///and record it as screened out in a run directory.
+    #[clap(short = 'x', long = "skip_infeasible", default_value = "false")]
+    skip_infeasible: bool,
+    ///Path to the directory caching the minimum cores of each DAG across runs.
+    #[clap(long = "cache_dir_path", default_value = "../outputs/cache")]
+    cache_dir_path: String,
+    ///Compute the minimum cores of each DAG without the cache.
+    #[clap(long = "no_cache", default_value = "false")]
+    no_cache: bool,
+    ///Also write the log to `lib/tests/fixtures/` as a golden fixture for the tests.
+    #[clap(long = "record_fixtures", default_value = "false")]
+    record_fixtures: bool,
+    ///Path to a CSV trace (dag_id,node_id,job_index,exec_time) of measured execution times.
+    #[clap(short = 'e', long = "execution_time_trace")]
+    execution_time_trace: Option<String>,
+    ///Let a free core of a DAG take the first ready node of its execution order instead of
+    ///waiting for the head. The runs keep their name, so `sched_gate --report` compares them to
+    ///the static order replay.
+    #[clap(long = "late_binding", default_value = "false")]
+    late_binding: bool,
+    ///Search the largest factor of all execution times that meets all deadlines (breakdown
+    ///factor) with this precision, and add it to the log. The execution time trace is not
+    ///applied to the inflated DAG sets.
+    #[clap(long = "breakdown_precision")]
+    breakdown_precision: Option<f64>,
+    ///Upper bound of the breakdown factor search.
+    #[clap(long = "breakdown_max_factor", default_value = "10.0")]
+    breakdown_max_factor: f64,
+}
+
+/// Run the algorithm with the parsed arguments.
+pub fn run(arg: ArgParser) {
+    // Stop at the end of the current time unit on ctrl-c and dump the partial log.
+    install_interrupt_handler();
+
+    let mut dag_set = create_dag_set_from_dir(&arg.dag_dir_path);
+    adjust_to_implicit_deadline(&mut dag_set);
+
+    let homogeneous_processor = HomogeneousProcessor::new(arg.number_of_cores);
+    let create_scheduler = |dag_set: &[Graph<NodeData, i32>]| {
+        let mut scheduler: DynamicFederatedScheduler<FixedPriorityScheduler<HomogeneousProcessor>> =
+            DynamicFederatedScheduler::new(dag_set, &homogeneous_processor);
+        if !arg.no_cache {
+            scheduler.set_preprocessing_cache(PreprocessingCache::new(&arg.cache_dir_path));
+        }
+        if arg.late_binding {
+            scheduler.set_late_binding();
+        }
+        scheduler
+    };
+    let mut dynfed_scheduler = create_scheduler(&dag_set);
+    dynfed_scheduler
+        .get_log_mut()
+        .write_dag_names(&create_dag_set_names_from_dirs(&[&arg.dag_dir_path]));
+    if let Some(trace_path) = &arg.execution_time_trace {
+        dynfed_scheduler.set_execution_time_trace(ExecutionTimeTrace::from_csv(trace_path));
+    }
+
+    if arg.skip_infeasible {
+        let screening_violations = screen_dag_set(&dag_set, arg.number_of_cores);
+        if !screening_violations.is_empty() {
+            dynfed_scheduler.dump_screened_out_run_dir(
+                &arg.output_dir_path,
+                "FixedPriority",
+                &dag_set,
+                screening_violations,
+            );
+            return;
+        }
+    }
+
+    let schedule_length = dynfed_scheduler.schedule(PreemptiveType::NonPreemptive);
+    if let Some(breakdown_precision) = arg.breakdown_precision.filter(|_| !is_interrupted()) {
+        let breakdown_factor = find_breakdown_factor(
+            &dag_set,
+            &PreemptiveType::NonPreemptive,
+            breakdown_precision,
+            arg.breakdown_max_factor,
+            create_scheduler,
+        );
+        dynfed_scheduler
+            .get_log_mut()
+            .write_breakdown_factor(breakdown_factor);
+    }
+
+    // Check the result
+    let log = dynfed_scheduler.get_log_mut();
+    if arg.core_trace {
+        log.write_core_traces(schedule_length);
+    }
+    let result = dag_set.iter().all(|dag| {
+        log.get_worst_response_time(dag.get_dag_param("dag_id") as usize)
+            <= dag.get_head_period().unwrap()
+    });
+
+    if arg.run_dir {
+        dynfed_scheduler.dump_run_dir(
+            &arg.output_dir_path,
+            "FixedPriority",
+            &dag_set,
+            result,
+            arg.log_format,
+        );
+    } else {
+        dynfed_scheduler.dump_simulation_log(
+            &arg.output_dir_path,
+            "FixedPriority",
+            result,
+            arg.log_format,
+        );
+    }
+    if arg.record_fixtures {
+        dynfed_scheduler.dump_fixture("FixedPriority", &dag_set, result);
+    }
+}
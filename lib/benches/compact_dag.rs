@@ -0,0 +1,111 @@
+//! Memory and readiness pass of the petgraph DAG and `CompactDAG` on a layered DAG of 100k nodes
+//! and 300k edges. Run with `cargo bench -p lib --bench compact_dag`.
+use lib::{
+    compact_dag::{CompactDAG, CompactReadiness},
+    graph_extension::{GraphExtension, NodeData},
+};
+use petgraph::graph::{Graph, NodeIndex};
+use std::{
+    collections::{BTreeMap, VecDeque},
+    time::Instant,
+};
+
+fn create_node(id: i32, key: &str, value: i32) -> NodeData {
+    let mut params = BTreeMap::new();
+    params.insert(key.to_string(), value);
+    NodeData { id, params }
+}
+
+/// Layers of `width` nodes, each connected to 3 nodes of the next layer.
+fn create_layered_dag(layers: usize, width: usize) -> Graph<NodeData, i32> {
+    let mut dag = Graph::<NodeData, i32>::with_capacity(layers * width, layers * width * 3);
+    for node_id in 0..layers * width {
+        dag.add_node(create_node(node_id as i32, "execution_time", 1));
+    }
+    for layer in 0..layers - 1 {
+        for offset in 0..width {
+            for step in 0..3 {
+                dag.add_edge(
+                    NodeIndex::new(layer * width + offset),
+                    NodeIndex::new((layer + 1) * width + (offset + step) % width),
+                    1,
+                );
+            }
+        }
+    }
+    dag
+}
+
+/// Memory of the nodes (with their params map) and edges of petgraph. The `BTreeMap` node
+/// overhead is omitted, so this is a lower bound.
+fn get_graph_size(dag: &Graph<NodeData, i32>) -> usize {
+    let node_params_size: usize = dag
+        .node_weights()
+        .map(|node| {
+            node.params
+                .keys()
+                .map(|key| key.capacity() + 3 * std::mem::size_of::<usize>() + 4)
+                .sum::<usize>()
+        })
+        .sum();
+    dag.node_count() * std::mem::size_of::<petgraph::graph::Node<NodeData>>()
+        + dag.edge_count() * std::mem::size_of::<petgraph::graph::Edge<i32>>()
+        + node_params_size
+}
+
+/// Complete every node once in topological order with `pre_done_count` params, as the
+/// simulation loops did before `CompactReadiness`.
+fn run_petgraph_readiness_pass(dag: &Graph<NodeData, i32>) -> usize {
+    let mut dag = dag.clone();
+    let mut queue: VecDeque<NodeIndex> = dag.get_source_nodes().into();
+    let mut completed = 0;
+    while let Some(node_i) = queue.pop_front() {
+        completed += 1;
+        for suc_i in dag.get_suc_nodes(node_i).unwrap_or_default() {
+            let pre_done_count = dag[suc_i]
+                .params
+                .get("pre_done_count")
+                .copied()
+                .unwrap_or(0);
+            dag[suc_i]
+                .params
+                .insert("pre_done_count".to_string(), pre_done_count + 1);
+            if dag.is_node_ready(suc_i) {
+                queue.push_back(suc_i);
+            }
+        }
+    }
+    completed
+}
+
+fn run_compact_readiness_pass(compact_dag: &CompactDAG) -> usize {
+    let mut readiness = CompactReadiness::new(compact_dag);
+    let mut queue: VecDeque<usize> = compact_dag.get_source_nodes().into();
+    let mut completed = 0;
+    while let Some(node_i) = queue.pop_front() {
+        completed += 1;
+        queue.extend(readiness.complete(compact_dag, node_i));
+    }
+    completed
+}
+
+fn main() {
+    let dag = create_layered_dag(1000, 100);
+    let compact_dag = CompactDAG::from_graph(&dag);
+    println!(
+        "memory: petgraph >= {} bytes, compact {} bytes",
+        get_graph_size(&dag),
+        compact_dag.get_heap_size()
+    );
+
+    let start = Instant::now();
+    assert_eq!(run_petgraph_readiness_pass(&dag), dag.node_count());
+    let petgraph_time = start.elapsed();
+    let start = Instant::now();
+    assert_eq!(run_compact_readiness_pass(&compact_dag), dag.node_count());
+    let compact_time = start.elapsed();
+    println!(
+        "readiness pass: petgraph {:?}, compact {:?}",
+        petgraph_time, compact_time
+    );
+}
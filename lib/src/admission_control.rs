@@ -0,0 +1,148 @@
+//! Online admission control: a job released by a periodic DAG runs only if a quick test on the
+//! jobs active at its release passes. A rejected job is counted and never runs, and the next job
+//! of the DAG is released as usual.
+//!
+//! The tests are sufficient bounds cheap enough to run at every release, with `m` cores:
+//! - Density: the densities (`volume / D`) of the active jobs and the new job sum to at most `m`.
+//! - Laxity: the new job meets its deadline by Graham's bound, `L + (volume - L + W) / m <= D`,
+//!   where `L` is the critical path length and `W` the volume of the active jobs with no later
+//!   absolute deadline, which EDF runs first. Their executed part is pessimistically included.
+use crate::{
+    anticipatory_edf_scheduler::get_critical_path_lengths,
+    dag_task::DagTask,
+    graph_extension::{GraphExtension, NodeData},
+};
+use petgraph::graph::Graph;
+use serde_derive::{Deserialize, Serialize};
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AdmissionTest {
+    #[default]
+    Density,
+    Laxity,
+}
+
+impl FromStr for AdmissionTest {
+    type Err = String;
+
+    fn from_str(test: &str) -> Result<Self, Self::Err> {
+        match test.to_lowercase().as_str() {
+            "density" => Ok(Self::Density),
+            "laxity" => Ok(Self::Laxity),
+            _ => Err(format!("Unknown admission test: {}", test)),
+        }
+    }
+}
+
+/// The test with the parameters of each DAG by `dag_id`, computed once.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AdmissionControl {
+    test: AdmissionTest,
+    number_of_cores: usize,
+    volumes: Vec<i32>,
+    critical_path_lengths: Vec<i32>,
+    relative_deadlines: Vec<i32>,
+}
+
+impl AdmissionControl {
+    pub fn new(
+        test: AdmissionTest,
+        dag_set: &[Graph<NodeData, i32>],
+        number_of_cores: usize,
+    ) -> Self {
+        Self {
+            test,
+            number_of_cores,
+            volumes: dag_set.iter().map(|dag| dag.get_volume()).collect(),
+            critical_path_lengths: get_critical_path_lengths(dag_set),
+            relative_deadlines: dag_set
+                .iter()
+                .enumerate()
+                .map(|(dag_id, dag)| {
+                    DagTask::from_graph(dag)
+                        .get_relative_deadline()
+                        .unwrap_or_else(|| panic!("DAG {} has no deadline.", dag_id))
+                })
+                .collect(),
+        }
+    }
+
+    pub fn get_test(&self) -> AdmissionTest {
+        self.test
+    }
+
+    /// Whether the job of `dag_id` released at `current_time` is admitted, where `active_jobs`
+    /// are the DAG and the release time of each admitted job that has not completed.
+    pub fn admits(&self, dag_id: usize, current_time: i32, active_jobs: &[(usize, i32)]) -> bool {
+        let number_of_cores = self.number_of_cores as f32;
+        match self.test {
+            AdmissionTest::Density => {
+                let density = |dag_id: usize| {
+                    self.volumes[dag_id] as f32 / self.relative_deadlines[dag_id] as f32
+                };
+                active_jobs
+                    .iter()
+                    .map(|&(active_dag_id, _)| density(active_dag_id))
+                    .sum::<f32>()
+                    + density(dag_id)
+                    <= number_of_cores
+            }
+            AdmissionTest::Laxity => {
+                let absolute_deadline = current_time + self.relative_deadlines[dag_id];
+                let interfering_volume: i32 = active_jobs
+                    .iter()
+                    .filter(|&&(active_dag_id, release_time)| {
+                        release_time + self.relative_deadlines[active_dag_id] <= absolute_deadline
+                    })
+                    .map(|&(active_dag_id, _)| self.volumes[active_dag_id])
+                    .sum();
+                let critical_path_length = self.critical_path_lengths[dag_id];
+                critical_path_length as f32
+                    + (self.volumes[dag_id] - critical_path_length + interfering_volume) as f32
+                        / number_of_cores
+                    <= self.relative_deadlines[dag_id] as f32
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::create_node;
+
+    fn create_dag(execution_times: &[i32], deadline: i32) -> Graph<NodeData, i32> {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", execution_times[0]));
+        dag.add_param(n0, "period", deadline);
+        dag.add_param(n0, "end_to_end_deadline", deadline);
+        for (node_id, &execution_time) in execution_times.iter().enumerate().skip(1) {
+            let node_i = dag.add_node(create_node(
+                node_id as i32,
+                "execution_time",
+                execution_time,
+            ));
+            dag.add_edge(n0, node_i, 1);
+        }
+        dag
+    }
+
+    #[test]
+    fn test_admission_control_admits() {
+        // Volumes 6 and 8, critical path lengths 4 and 8, deadlines 6 and 10.
+        let dag_set = vec![create_dag(&[2, 2, 2], 6), create_dag(&[8], 10)];
+        let density_control = AdmissionControl::new(AdmissionTest::Density, &dag_set, 2);
+        assert!(density_control.admits(1, 0, &[(0, 0)]));
+        let density_control = AdmissionControl::new(AdmissionTest::Density, &dag_set, 1);
+        assert!(!density_control.admits(1, 0, &[(0, 0)]));
+
+        // DAG 1 released at 2 waits for DAG 0 released at 0, whose deadline is earlier, whereas
+        // DAG 0 does not wait for DAG 1 released at the same time.
+        let laxity_control = AdmissionControl::new(AdmissionTest::Laxity, &dag_set, 2);
+        assert!(laxity_control.admits(1, 2, &[]));
+        assert!(!laxity_control.admits(1, 2, &[(0, 0)]));
+        assert!(laxity_control.admits(0, 0, &[(1, 0)]));
+    }
+}
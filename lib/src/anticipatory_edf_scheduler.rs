@@ -0,0 +1,195 @@
+//! Global non-preemptive EDF with an eligibility test (anticipatory dispatching).
+//!
+//! Naive non-preemptive dispatching may start a long node just before a job with an earlier
+//! deadline is released, which then has to wait for the node to finish.
+//! This scheduler looks ahead at the known periodic release times and keeps the last idle core
+//! free when starting the node would make such a job miss its deadline. `GlobalEDFScheduler`
+//! applies the same test with `IdleInsertionPolicy::Anticipatory`.
+use crate::dag_set_scheduler::{DAGSetSchedulerBase, DAGState, DAGStateManagerBase};
+use crate::getset_dag_set_scheduler;
+use crate::{
+    graph_extension::{get_node_index_maps, GraphExtension, NodeData, NodeIndexMap, ReleaseSource},
+    homogeneous::HomogeneousProcessor,
+    log::DAGSetSchedulerLog,
+    processor::ProcessorBase,
+};
+use petgraph::graph::Graph;
+
+/// The critical path length of each DAG, the least time its next job needs after its release.
+pub fn get_critical_path_lengths(dag_set: &[Graph<NodeData, i32>]) -> Vec<i32> {
+    dag_set
+        .iter()
+        .map(|dag| {
+            let mut dag = dag.clone();
+            let critical_path = dag.get_critical_path();
+            dag.get_total_wcet_from_nodes(&critical_path)
+        })
+        .collect()
+}
+
+/// Whether dispatching `node_data` now lets the next periodic jobs meet their deadlines.
+///
+/// `node_data` is not eligible if it would occupy the last idle core until after the release of
+/// a job with an earlier deadline, and that job could not finish by its deadline after waiting.
+pub fn is_anticipatory_dispatch_eligible<T>(
+    scheduler: &impl DAGSetSchedulerBase<T>,
+    critical_path_lengths: &[i32],
+    node_data: &NodeData,
+    managers: &[impl DAGStateManagerBase],
+) -> bool
+where
+    T: ProcessorBase + Clone,
+{
+    if scheduler.get_processor().get_idle_core_num() > 1 {
+        return true;
+    }
+    let current_time = scheduler.get_current_time();
+    let finish_time = current_time + node_data.get_params_value("execution_time");
+    let node_absolute_deadline = node_data.get_params_value("node_absolute_deadline");
+
+    scheduler.get_dag_set().iter().all(|dag| {
+        let dag_id = dag.get_dag_param("dag_id") as usize;
+        let manager = &managers[dag_id];
+        if manager.get_dag_state() != DAGState::Waiting
+            || dag.get_release_source() != ReleaseSource::Periodic
+        {
+            return true;
+        }
+        let release_time =
+            dag.get_head_offset() + dag.get_head_period().unwrap() * manager.get_period_count();
+        if release_time <= current_time || release_time >= finish_time {
+            return true;
+        }
+        let end_to_end_deadline = dag.get_end_to_end_deadline().unwrap();
+        // Same absolute deadline as the one `release_dags` will assign.
+        let has_higher_priority = scheduler.get_absolute_deadline_formula().calculate(
+            release_time,
+            end_to_end_deadline,
+            manager.get_period_count() + 1,
+        ) < node_absolute_deadline;
+        !has_higher_priority
+            || finish_time + critical_path_lengths[dag_id] <= release_time + end_to_end_deadline
+    })
+}
+
+pub struct AnticipatoryEDFScheduler {
+    dag_set: Vec<Graph<NodeData, i32>>,
+    node_index_maps: Vec<NodeIndexMap>,
+    processor: HomogeneousProcessor,
+    log: DAGSetSchedulerLog,
+    current_time: i32,
+    critical_path_lengths: Vec<i32>,
+}
+
+impl DAGSetSchedulerBase<HomogeneousProcessor> for AnticipatoryEDFScheduler {
+    fn new(dag_set: &[Graph<NodeData, i32>], processor: &HomogeneousProcessor) -> Self {
+        Self {
+            node_index_maps: get_node_index_maps(dag_set),
+            dag_set: dag_set.to_vec(),
+            processor: processor.clone(),
+            log: DAGSetSchedulerLog::new(dag_set, processor.get_number_of_cores()),
+            current_time: 0,
+            critical_path_lengths: get_critical_path_lengths(dag_set),
+        }
+    }
+
+    getset_dag_set_scheduler!(HomogeneousProcessor);
+
+    /// See `is_anticipatory_dispatch_eligible`.
+    fn is_dispatch_eligible(
+        &self,
+        node_data: &NodeData,
+        managers: &[impl DAGStateManagerBase],
+    ) -> bool {
+        is_anticipatory_dispatch_eligible(self, &self.critical_path_lengths, node_data, managers)
+    }
+}
+
+#[cfg(all(test, feature = "yaml"))]
+mod tests {
+    use super::*;
+    use crate::test_util::create_node;
+    use crate::{
+        dag_set_scheduler::PreemptiveType, global_edf_scheduler::GlobalEDFScheduler,
+        util::load_yaml,
+    };
+    use petgraph::graph::NodeIndex;
+    use std::fs::remove_file;
+
+    fn create_single_node_dag(
+        execution_time: i32,
+        period: i32,
+        end_to_end_deadline: i32,
+    ) -> Graph<NodeData, i32> {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", execution_time));
+        dag.add_param(n0, "period", period);
+        dag.add_param(n0, "end_to_end_deadline", end_to_end_deadline);
+        dag
+    }
+
+    fn create_sample_dag_set() -> Vec<Graph<NodeData, i32>> {
+        let mut long_dag = create_single_node_dag(10, 40, 40);
+        // Released at 1 with a tight deadline.
+        let mut urgent_dag = create_single_node_dag(2, 40, 6);
+        urgent_dag.add_param(NodeIndex::new(0), "offset", 1);
+        long_dag.set_dag_param("dag_id", 0);
+        urgent_dag.set_dag_param("dag_id", 1);
+        vec![long_dag, urgent_dag]
+    }
+
+    fn get_worst_response_times(
+        scheduler: &mut impl DAGSetSchedulerBase<HomogeneousProcessor>,
+        test_name: &str,
+    ) -> Vec<i64> {
+        let file_path = scheduler.dump_log("../lib/tests", test_name);
+        let yaml_docs = load_yaml(&file_path);
+        remove_file(file_path).unwrap();
+        (0..2)
+            .map(|dag_id| {
+                yaml_docs[0]["dag_set_log"][dag_id]["worst_response_time"]
+                    .as_i64()
+                    .unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_anticipatory_edf_delays_long_node() {
+        let dag_set = create_sample_dag_set();
+        let processor = HomogeneousProcessor::new(1);
+
+        let mut anticipatory_edf_scheduler = AnticipatoryEDFScheduler::new(&dag_set, &processor);
+        anticipatory_edf_scheduler.schedule(PreemptiveType::NonPreemptive);
+        // The long node waits for the urgent node, which meets its deadline.
+        assert_eq!(
+            get_worst_response_times(&mut anticipatory_edf_scheduler, "anticipatory_edf_test"),
+            vec![13, 2]
+        );
+
+        // Naive non-preemptive EDF makes the urgent node miss its deadline.
+        let mut global_edf_scheduler = GlobalEDFScheduler::new(&dag_set, &processor);
+        global_edf_scheduler.schedule(PreemptiveType::NonPreemptive);
+        assert_eq!(
+            get_worst_response_times(&mut global_edf_scheduler, "naive_np_edf_test"),
+            vec![10, 11]
+        );
+    }
+
+    #[test]
+    fn test_anticipatory_edf_multiple_idle_cores() {
+        let dag_set = create_sample_dag_set();
+        let processor = HomogeneousProcessor::new(2);
+
+        // Another idle core is left for the urgent node, so nothing is delayed.
+        let mut anticipatory_edf_scheduler = AnticipatoryEDFScheduler::new(&dag_set, &processor);
+        anticipatory_edf_scheduler.schedule(PreemptiveType::NonPreemptive);
+        assert_eq!(
+            get_worst_response_times(
+                &mut anticipatory_edf_scheduler,
+                "anticipatory_edf_idle_test"
+            ),
+            vec![10, 2]
+        );
+    }
+}
@@ -0,0 +1,372 @@
+//! Aperiodic single-node jobs served by a polling or deferrable server next to periodic DAGs.
+//!
+//! The server is a periodic task with a budget of `budget` time units every `period`. It runs
+//! one time unit at a time on any core, with the end of its current period as the EDF deadline,
+//! and serves the queued aperiodic jobs in arrival order. The nodes of the DAGs are scheduled by
+//! global non-preemptive EDF, so the server cannot preempt a running node.
+//!
+//! * Polling server: the budget is lost whenever no aperiodic job is waiting.
+//! * Deferrable server: the budget is kept until the end of the period.
+//! * Constant bandwidth server: the budget is recharged when exhausted or when a job arrives at
+//!   the idle server, and the deadline is moved instead (see `cbs_scheduler`).
+use crate::cbs_scheduler::ConstantBandwidthServer;
+use crate::dag_set_scheduler::{
+    DAGSetSchedulerBase, DAGStateManager, DAGStateManagerBase, NodeDataWrapper, PreemptiveType,
+    ReadyQueue,
+};
+use crate::getset_dag_set_scheduler;
+use crate::{
+    core::ProcessResult,
+    graph_extension::{get_node_index_maps, NodeData, NodeIndexMap},
+    homogeneous::HomogeneousProcessor,
+    log::DAGSetSchedulerLog,
+    processor::ProcessorBase,
+    util::{clear_interrupted, get_hyper_period, get_process_core_indices, is_interrupted},
+};
+use petgraph::graph::Graph;
+#[cfg(feature = "random")]
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use serde_derive::Deserialize;
+use std::collections::{BTreeMap, VecDeque};
+#[cfg(feature = "random")]
+use std::ops::RangeInclusive;
+
+/// Marks the unit of server execution allocated to a core.
+const SERVER_KEY: &str = "aperiodic_server";
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct AperiodicJob {
+    pub arrival_time: i32,
+    pub execution_time: i32,
+}
+
+/// Load aperiodic jobs from a CSV trace with the header `arrival_time,execution_time`.
+#[cfg(feature = "csv")]
+pub fn load_aperiodic_jobs_from_csv(file_path: &str) -> Vec<AperiodicJob> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_path(file_path)
+        .unwrap_or_else(|err| panic!("Failed to open {}: {}", file_path, err));
+    let mut jobs: Vec<AperiodicJob> = reader
+        .deserialize()
+        .map(|record| {
+            record.unwrap_or_else(|err| panic!("Invalid record in {}: {}", file_path, err))
+        })
+        .collect();
+    if let Some(job) = jobs.iter().find(|job| job.execution_time <= 0) {
+        panic!(
+            "execution_time must be positive, but {} for the job arriving at {}.",
+            job.execution_time, job.arrival_time
+        );
+    }
+    jobs.sort_by_key(|job| job.arrival_time);
+    jobs
+}
+
+/// Generate jobs arriving by a Poisson process with `arrival_rate` jobs per time unit until
+/// `horizon`, each with an execution time drawn uniformly from `execution_time_range`.
+#[cfg(feature = "random")]
+pub fn generate_poisson_arrivals(
+    arrival_rate: f64,
+    execution_time_range: RangeInclusive<i32>,
+    horizon: i32,
+    seed: u64,
+) -> Vec<AperiodicJob> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut jobs = Vec::new();
+    let mut arrival_time = 0.0;
+    loop {
+        // Exponentially distributed inter-arrival time
+        arrival_time += -(1.0 - rng.gen::<f64>()).ln() / arrival_rate;
+        if arrival_time >= horizon as f64 {
+            break;
+        }
+        jobs.push(AperiodicJob {
+            arrival_time: arrival_time as i32,
+            execution_time: rng.gen_range(execution_time_range.clone()),
+        });
+    }
+    jobs
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ServerType {
+    Polling,
+    Deferrable,
+    ConstantBandwidth,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AperiodicServer {
+    pub server_type: ServerType,
+    pub period: i32,
+    pub budget: i32,
+}
+
+pub struct AperiodicServerScheduler {
+    dag_set: Vec<Graph<NodeData, i32>>,
+    node_index_maps: Vec<NodeIndexMap>,
+    processor: HomogeneousProcessor,
+    log: DAGSetSchedulerLog,
+    current_time: i32,
+    server: AperiodicServer,
+    aperiodic_jobs: Vec<AperiodicJob>,
+}
+
+impl DAGSetSchedulerBase<HomogeneousProcessor> for AperiodicServerScheduler {
+    fn new(dag_set: &[Graph<NodeData, i32>], processor: &HomogeneousProcessor) -> Self {
+        Self {
+            node_index_maps: get_node_index_maps(dag_set),
+            dag_set: dag_set.to_vec(),
+            processor: processor.clone(),
+            log: DAGSetSchedulerLog::new(dag_set, processor.get_number_of_cores()),
+            current_time: 0,
+            server: AperiodicServer {
+                server_type: ServerType::Polling,
+                period: 1,
+                budget: 0,
+            },
+            aperiodic_jobs: Vec::new(),
+        }
+    }
+
+    getset_dag_set_scheduler!(HomogeneousProcessor);
+
+    /// The preemptive type is ignored because the nodes are scheduled non-preemptively.
+    fn schedule(&mut self, _preemptive_type: PreemptiveType) -> i32 {
+        clear_interrupted();
+        self.log.write_schedule_start();
+        let mut managers = vec![DAGStateManager::default(); self.dag_set.len()];
+        let mut ready_queue = ReadyQueue::new();
+        let hyper_period = get_hyper_period(&self.dag_set);
+        let server = self.server;
+        // (arrival_time, remaining execution time) of the arrived jobs in arrival order
+        let mut aperiodic_queue: VecDeque<(i32, i32)> = VecDeque::new();
+        let mut arrivals = self.aperiodic_jobs.clone().into_iter().peekable();
+        let mut budget = 0;
+        let mut server_deadline = 0;
+        let mut constant_bandwidth_server = (server.server_type == ServerType::ConstantBandwidth)
+            .then(|| ConstantBandwidthServer::new(server.budget, server.period));
+        let mut server_node_data = NodeData {
+            id: -1,
+            params: BTreeMap::new(),
+        };
+        server_node_data
+            .params
+            .insert("execution_time".to_string(), 1);
+        server_node_data.params.insert(SERVER_KEY.to_string(), 1);
+        while self.get_current_time() < hyper_period && !is_interrupted() {
+            // Release DAGs
+            for ready_node in self.release_dags(&mut managers) {
+                ready_queue.insert_new(NodeDataWrapper::new(ready_node));
+                self.log.write_ready_queue_insertion();
+            }
+
+            // Queue the arrived aperiodic jobs and replenish the budget of the server.
+            while let Some(job) = arrivals.next_if(|job| job.arrival_time <= self.current_time) {
+                if let (true, Some(cbs)) =
+                    (aperiodic_queue.is_empty(), &mut constant_bandwidth_server)
+                {
+                    cbs.release(self.current_time, &mut self.log);
+                    budget = cbs.get_remaining_budget();
+                    server_deadline = cbs.get_deadline();
+                }
+                aperiodic_queue.push_back((job.arrival_time, job.execution_time));
+            }
+            if constant_bandwidth_server.is_none() && self.current_time % server.period == 0 {
+                budget = server.budget;
+                server_deadline = self.current_time + server.period;
+            }
+            if server.server_type == ServerType::Polling && aperiodic_queue.is_empty() {
+                budget = 0;
+            }
+
+            // The server runs before the ready nodes with later deadlines.
+            let is_server_ready = budget > 0 && !aperiodic_queue.is_empty();
+            let mut is_server_allocated = false;
+            if is_server_ready
+                && ready_queue.first().is_none_or(|wrapper: &NodeDataWrapper| {
+                    server_deadline <= wrapper.node_data.get_params_value("node_absolute_deadline")
+                })
+            {
+                is_server_allocated = self.allocate_server(&server_node_data);
+            }
+            while let Some(idle_core_i) = self.processor.get_idle_core_index() {
+                let Some(wrapper) = ready_queue
+                    .iter()
+                    .find(|wrapper| self.can_allocate_node(&wrapper.node_data))
+                    .cloned()
+                else {
+                    break;
+                };
+                ready_queue.remove(&wrapper);
+                self.log.write_ready_queue_removal();
+                let node_data = wrapper.convert_node_data();
+                let dag_id = node_data.get_params_value("dag_id") as usize;
+                let job_id = managers[dag_id].get_release_count() as usize;
+                self.log
+                    .write_allocating_job(&node_data, idle_core_i, job_id, self.current_time);
+                self.processor
+                    .allocate_specific_core(idle_core_i, &node_data);
+            }
+            if is_server_ready && !is_server_allocated {
+                self.allocate_server(&server_node_data);
+            }
+
+            // Process unit time
+            self.current_time += 1;
+            let process_result = self.processor.process();
+            let indices: Vec<usize> = get_process_core_indices(&process_result);
+            self.log.write_processing_time(&indices);
+
+            // Post-process on completion of node execution
+            for (core_id, result) in process_result.iter().enumerate() {
+                if let ProcessResult::Done(node_data) = result {
+                    if node_data.params.contains_key(SERVER_KEY) {
+                        budget -= 1;
+                        if let Some(cbs) = &mut constant_bandwidth_server {
+                            cbs.consume(1, self.current_time, &mut self.log);
+                            budget = cbs.get_remaining_budget();
+                            server_deadline = cbs.get_deadline();
+                        }
+                        let (arrival_time, remain_time) = aperiodic_queue.front_mut().unwrap();
+                        *remain_time -= 1;
+                        if *remain_time == 0 {
+                            self.log
+                                .write_aperiodic_response_time(self.current_time - *arrival_time);
+                            aperiodic_queue.pop_front();
+                        }
+                        continue;
+                    }
+                    for ready_node in
+                        self.post_process_on_node_completion(node_data, core_id, &mut managers)
+                    {
+                        ready_queue.insert_new(NodeDataWrapper::new(ready_node));
+                        self.log.write_ready_queue_insertion();
+                    }
+                }
+            }
+        }
+
+        self.log
+            .write_comparisons(ready_queue.get_comparison_count());
+        self.calculate_log();
+        self.log.calculate_aperiodic_log(aperiodic_queue.len());
+        self.get_current_time()
+    }
+}
+
+impl AperiodicServerScheduler {
+    pub fn set_server(&mut self, server: AperiodicServer) {
+        if server.period <= 0 || server.budget > server.period {
+            panic!(
+                "The budget {} of the server must fit in its period {}.",
+                server.budget, server.period
+            );
+        }
+        self.server = server;
+    }
+
+    pub fn set_aperiodic_jobs(&mut self, mut aperiodic_jobs: Vec<AperiodicJob>) {
+        aperiodic_jobs.sort_by_key(|job| job.arrival_time);
+        self.aperiodic_jobs = aperiodic_jobs;
+    }
+
+    /// Allocate one time unit of the server to an idle core.
+    fn allocate_server(&mut self, server_node_data: &NodeData) -> bool {
+        match self.processor.get_idle_core_index() {
+            Some(idle_core_i) => self
+                .processor
+                .allocate_specific_core(idle_core_i, server_node_data),
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph_extension::GraphExtension;
+    use crate::log::ServerEventKind;
+    use crate::test_util::create_node;
+
+    fn schedule_with_server(server_type: ServerType) -> AperiodicServerScheduler {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 4));
+        dag.add_param(n0, "period", 20);
+        dag.add_param(n0, "end_to_end_deadline", 20);
+        dag.set_dag_param("dag_id", 0);
+
+        let mut scheduler = AperiodicServerScheduler::new(&[dag], &HomogeneousProcessor::new(1));
+        scheduler.set_server(AperiodicServer {
+            server_type,
+            period: 5,
+            budget: 1,
+        });
+        scheduler.set_aperiodic_jobs(vec![AperiodicJob {
+            arrival_time: 1,
+            execution_time: 2,
+        }]);
+        scheduler.schedule(PreemptiveType::NonPreemptive);
+        scheduler
+    }
+
+    #[test]
+    fn test_aperiodic_server_polling() {
+        let mut scheduler = schedule_with_server(ServerType::Polling);
+        let log = scheduler.get_log_mut();
+        assert_eq!(log.get_worst_response_time(0), 4);
+        // The budget of the first period is lost before the job arrives.
+        let aperiodic_log = log.get_aperiodic_log().unwrap();
+        assert_eq!(aperiodic_log.get_response_time(), [10]);
+        assert_eq!(aperiodic_log.get_unfinished_jobs(), 0);
+    }
+
+    #[test]
+    fn test_aperiodic_server_deferrable() {
+        let mut scheduler = schedule_with_server(ServerType::Deferrable);
+        let log = scheduler.get_log_mut();
+        assert_eq!(log.get_worst_response_time(0), 4);
+        // The budget of the first period is kept until the node releases the core.
+        let aperiodic_log = log.get_aperiodic_log().unwrap();
+        assert_eq!(aperiodic_log.get_response_time(), [5]);
+        assert_eq!(aperiodic_log.get_worst_response_time(), 5);
+    }
+
+    #[test]
+    fn test_aperiodic_server_constant_bandwidth() {
+        let mut scheduler = schedule_with_server(ServerType::ConstantBandwidth);
+        let log = scheduler.get_log_mut();
+        // The job arriving at 1 gets the deadline 6 and runs after the node with the deadline 20
+        // releases the core, postponing the deadline when the budget is exhausted.
+        assert_eq!(log.get_worst_response_time(0), 4);
+        assert_eq!(log.get_aperiodic_log().unwrap().get_response_time(), [5]);
+        let server_events: Vec<(i32, ServerEventKind)> = log
+            .get_server_log()
+            .iter()
+            .map(|event| (event.time, event.kind))
+            .collect();
+        assert_eq!(
+            server_events,
+            vec![
+                (1, ServerEventKind::Replenished),
+                (5, ServerEventKind::Depleted),
+                (6, ServerEventKind::Depleted),
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "random")]
+    fn test_generate_poisson_arrivals_normal() {
+        let jobs = generate_poisson_arrivals(0.5, 1..=3, 100, 0);
+        assert!(!jobs.is_empty());
+        assert!(jobs
+            .windows(2)
+            .all(|pair| pair[0].arrival_time <= pair[1].arrival_time));
+        assert!(jobs.iter().all(
+            |job| (0..100).contains(&job.arrival_time) && (1..=3).contains(&job.execution_time)
+        ));
+        assert_eq!(jobs, generate_poisson_arrivals(0.5, 1..=3, 100, 0));
+    }
+}
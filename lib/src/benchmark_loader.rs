@@ -0,0 +1,370 @@
+//! Load public DAG benchmarks (task graphs in DOT, TGFF-generated sets) from a local path.
+//!
+//! The graphs are normalized into the internal format: node ids in order of appearance,
+//! `execution_time` on every node and `communication_time` as the edge weight.
+//! Fractional times are rounded to integers.
+use crate::dag_creator::{assign_dag_ids, remove_dag_id, validate_dag_structure};
+use crate::graph_extension::{GraphExtension, NodeData};
+use petgraph::{graph::Graph, prelude::*};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Node attributes read as `execution_time`, in order of precedence.
+const EXECUTION_TIME_KEYS: [&str; 6] = [
+    "execution_time",
+    "wcet",
+    "weight",
+    "cost",
+    "size",
+    "computation",
+];
+/// Edge attributes read as `communication_time`, in order of precedence.
+const COMMUNICATION_TIME_KEYS: [&str; 4] = ["communication_time", "weight", "cost", "size"];
+/// Graph attributes kept as DAG-level params.
+const DAG_PARAM_KEYS: [&str; 3] = ["period", "end_to_end_deadline", "offset"];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BenchmarkFormat {
+    Dot,
+    Tgff,
+}
+
+impl BenchmarkFormat {
+    pub fn from_path(file_path: &Path) -> Option<Self> {
+        match file_path.extension()?.to_str()? {
+            "dot" | "gv" => Some(BenchmarkFormat::Dot),
+            "tgff" => Some(BenchmarkFormat::Tgff),
+            _ => None,
+        }
+    }
+}
+
+fn parse_time(value: &str) -> Option<i32> {
+    value
+        .trim_matches('"')
+        .parse::<f64>()
+        .ok()
+        .map(|time| time.round() as i32)
+}
+
+/// Parse `key=value` pairs separated by commas, semicolons or whitespace.
+fn parse_dot_attributes(attributes: &str) -> BTreeMap<String, String> {
+    let mut parsed = BTreeMap::new();
+    let attributes = attributes.replace([',', ';'], " ");
+    let mut tokens = attributes.split_whitespace().peekable();
+    while let Some(token) = tokens.next() {
+        let (key, value) = match token.split_once('=') {
+            Some((key, "")) => (key.to_string(), tokens.next().unwrap_or("").to_string()),
+            Some((key, value)) => (key.to_string(), value.to_string()),
+            // `key = value`
+            None if tokens.peek() == Some(&"=") => {
+                tokens.next();
+                (token.to_string(), tokens.next().unwrap_or("").to_string())
+            }
+            None => continue,
+        };
+        parsed.insert(key.trim().to_string(), value.trim_matches('"').to_string());
+    }
+    parsed
+}
+
+/// Split a DOT statement into its body and the attributes in brackets.
+fn split_dot_statement(statement: &str) -> (&str, BTreeMap<String, String>) {
+    match (statement.find('['), statement.rfind(']')) {
+        (Some(begin), Some(end)) if begin < end => (
+            statement[..begin].trim(),
+            parse_dot_attributes(&statement[begin + 1..end]),
+        ),
+        _ => (statement.trim(), BTreeMap::new()),
+    }
+}
+
+fn find_time(attributes: &BTreeMap<String, String>, keys: &[&str]) -> Option<i32> {
+    keys.iter()
+        .find_map(|key| attributes.get(*key).and_then(|value| parse_time(value)))
+}
+
+/// Load a task graph in the DOT language, e.g., the Cholesky or FFT graphs.
+///
+/// Supported are node and edge (`->`) statements with bracketed attributes and graph attributes.
+/// The execution time of a node is the first of `execution_time`, `wcet`, `weight`, `cost`,
+/// `size` and `computation`. Other numeric node attributes are kept as params.
+/// The graph attributes `period` and `offset` are set on the source node and
+/// `end_to_end_deadline` on the sink node.
+pub fn create_dag_from_dot(file_path: &str) -> Graph<NodeData, i32> {
+    let content = fs::read_to_string(file_path)
+        .unwrap_or_else(|err| panic!("Failed to read {}: {}", file_path, err));
+    let content: String = content
+        .lines()
+        .map(|line| line.split("//").next().unwrap())
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let (Some(begin), Some(end)) = (content.find('{'), content.rfind('}')) else {
+        panic!("{} is not a DOT graph.", file_path);
+    };
+
+    let mut dag = Graph::<NodeData, i32>::new();
+    let mut node_indices: HashMap<String, NodeIndex> = HashMap::new();
+    let mut node_attributes: HashMap<NodeIndex, BTreeMap<String, String>> = HashMap::new();
+    let mut dag_params = BTreeMap::new();
+    let mut get_node_i = |dag: &mut Graph<NodeData, i32>, name: &str| {
+        let name = name.trim().trim_matches('"').to_string();
+        *node_indices.entry(name).or_insert_with(|| {
+            dag.add_node(NodeData {
+                id: dag.node_count() as i32,
+                params: BTreeMap::new(),
+            })
+        })
+    };
+
+    for statement in content[begin + 1..end].split([';', '\n']) {
+        let (body, attributes) = split_dot_statement(statement);
+        if body.is_empty() || body == "node" || body == "edge" {
+            continue;
+        }
+        if body == "graph" {
+            dag_params.extend(attributes);
+        } else if body.contains("->") {
+            let names: Vec<&str> = body.split("->").collect();
+            let communication_time = find_time(&attributes, &COMMUNICATION_TIME_KEYS).unwrap_or(0);
+            for pair in names.windows(2) {
+                let source_i = get_node_i(&mut dag, pair[0]);
+                let target_i = get_node_i(&mut dag, pair[1]);
+                dag.add_edge(source_i, target_i, communication_time);
+            }
+        } else if let Some((key, value)) = body.split_once('=') {
+            dag_params.insert(key.trim().to_string(), value.trim().to_string());
+        } else {
+            let node_i = get_node_i(&mut dag, body);
+            node_attributes
+                .entry(node_i)
+                .or_default()
+                .extend(attributes);
+        }
+    }
+
+    for node_i in dag.node_indices() {
+        let attributes = node_attributes.remove(&node_i).unwrap_or_default();
+        let Some(execution_time) = find_time(&attributes, &EXECUTION_TIME_KEYS) else {
+            panic!(
+                "Node {} in {} has no execution time.",
+                node_i.index(),
+                file_path
+            );
+        };
+        for (key, value) in attributes.iter() {
+            if !EXECUTION_TIME_KEYS.contains(&key.as_str()) {
+                if let Some(value) = parse_time(value) {
+                    dag.add_param(node_i, key, value);
+                }
+            }
+        }
+        dag.add_param(node_i, "execution_time", execution_time);
+    }
+    for key in DAG_PARAM_KEYS {
+        if let Some(value) = dag_params.get(key).and_then(|value| parse_time(value)) {
+            let node_i = if key == "end_to_end_deadline" {
+                dag.get_sink_nodes()[0]
+            } else {
+                dag.get_source_nodes()[0]
+            };
+            dag.add_param(node_i, key, value);
+        }
+    }
+    validate_dag_structure(&mut dag, true);
+    dag
+}
+
+/// Table of a TGFF `@PROC` or `@COMMUN` block: the value of the time column for each type.
+fn parse_tgff_table(lines: &[&str], time_columns: &[&str]) -> HashMap<i32, i32> {
+    let mut table = HashMap::new();
+    let mut header: Vec<&str> = Vec::new();
+    for line in lines {
+        if let Some(comment) = line.strip_prefix('#') {
+            if comment.chars().any(|c| c.is_alphabetic()) {
+                header = comment.split_whitespace().collect();
+            }
+            continue;
+        }
+        let row: Vec<&str> = line.split_whitespace().collect();
+        if header.first() != Some(&"type") || row.len() != header.len() {
+            continue;
+        }
+        let time_column = header
+            .iter()
+            .position(|column| time_columns.contains(column))
+            .unwrap_or(header.len() - 1);
+        if let (Ok(task_type), Some(time)) = (row[0].parse::<i32>(), parse_time(row[time_column])) {
+            table.entry(task_type).or_insert(time);
+        }
+    }
+    table
+}
+
+/// Load the task graphs of a TGFF file, one DAG per `@TASK_GRAPH` block.
+///
+/// The execution time of a task is taken from the first `@PROC` table by its `TYPE`, and
+/// the communication time of an arc from the first `@COMMUN` table (0 without the table).
+/// `PERIOD` is set on the source node and the latest `HARD_DEADLINE` on the sink node.
+pub fn create_dag_set_from_tgff(file_path: &str) -> Vec<Graph<NodeData, i32>> {
+    let content = fs::read_to_string(file_path)
+        .unwrap_or_else(|err| panic!("Failed to read {}: {}", file_path, err));
+    // (block name, lines)
+    let mut blocks: Vec<(String, Vec<&str>)> = Vec::new();
+    let mut current_block: Option<(String, Vec<&str>)> = None;
+    for line in content.lines().map(str::trim) {
+        if let Some(header) = line.strip_prefix('@') {
+            if header.ends_with('{') {
+                let name = header.split_whitespace().next().unwrap_or("").to_string();
+                current_block = Some((name, Vec::new()));
+            }
+        } else if line == "}" {
+            blocks.extend(current_block.take());
+        } else if let Some((_, lines)) = current_block.as_mut() {
+            if !line.is_empty() {
+                lines.push(line);
+            }
+        }
+    }
+
+    let find_table = |name: &str, time_columns: &[&str]| {
+        blocks
+            .iter()
+            .find(|(block_name, _)| block_name == name)
+            .map(|(_, lines)| parse_tgff_table(lines, time_columns))
+            .unwrap_or_default()
+    };
+    let exec_times = find_table("PROC", &["exec_time", "task_time", "time"]);
+    let communication_times = find_table("COMMUN", &["communication_time", "time"]);
+
+    let mut dag_set = Vec::new();
+    for (_, lines) in blocks.iter().filter(|(name, _)| name == "TASK_GRAPH") {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let mut node_indices: HashMap<&str, NodeIndex> = HashMap::new();
+        let mut period = None;
+        let mut end_to_end_deadline: Option<i32> = None;
+        for line in lines {
+            let tokens: Vec<&str> = line.split_whitespace().collect();
+            match tokens.as_slice() {
+                ["PERIOD", value, ..] => period = parse_time(value),
+                ["TASK", name, "TYPE", task_type, ..] => {
+                    let task_type: i32 = task_type.parse().unwrap();
+                    let Some(&execution_time) = exec_times.get(&task_type) else {
+                        panic!("No execution time for TYPE {} in {}.", task_type, file_path);
+                    };
+                    let mut params = BTreeMap::new();
+                    params.insert("execution_time".to_string(), execution_time);
+                    let node_i = dag.add_node(NodeData {
+                        id: dag.node_count() as i32,
+                        params,
+                    });
+                    node_indices.insert(name, node_i);
+                }
+                ["ARC", _, "FROM", source, "TO", target, "TYPE", arc_type, ..] => {
+                    let communication_time = arc_type
+                        .parse()
+                        .ok()
+                        .and_then(|arc_type| communication_times.get(&arc_type).copied())
+                        .unwrap_or(0);
+                    dag.add_edge(
+                        node_indices[source],
+                        node_indices[target],
+                        communication_time,
+                    );
+                }
+                ["HARD_DEADLINE", _, "ON", _, "AT", value, ..] => {
+                    end_to_end_deadline = end_to_end_deadline.max(parse_time(value));
+                }
+                _ => {}
+            }
+        }
+        if let Some(period) = period {
+            let source_i = dag.get_source_nodes()[0];
+            dag.add_param(source_i, "period", period);
+        }
+        if let Some(end_to_end_deadline) = end_to_end_deadline {
+            let sink_i = dag.get_sink_nodes()[0];
+            dag.add_param(sink_i, "end_to_end_deadline", end_to_end_deadline);
+        }
+        validate_dag_structure(&mut dag, true);
+        dag_set.push(dag);
+    }
+    dag_set
+}
+
+/// Load all DOT and TGFF files in `dir_path` (sorted by file name) as a DAG set.
+pub fn create_dag_set_from_benchmark_dir(dir_path: &str) -> Vec<Graph<NodeData, i32>> {
+    let mut file_paths: Vec<PathBuf> = fs::read_dir(dir_path)
+        .unwrap_or_else(|err| panic!("Failed to read {}: {}", dir_path, err))
+        .map(|dir_entry| dir_entry.unwrap().path())
+        .filter(|path| BenchmarkFormat::from_path(path).is_some())
+        .collect();
+    if file_paths.is_empty() {
+        panic!("No DOT or TGFF file found in {}", dir_path);
+    }
+    file_paths.sort();
+
+    let mut dag_set = Vec::new();
+    for file_path in file_paths.iter() {
+        let file_path_str = file_path.to_str().unwrap();
+        match BenchmarkFormat::from_path(file_path).unwrap() {
+            BenchmarkFormat::Dot => dag_set.push(create_dag_from_dot(file_path_str)),
+            BenchmarkFormat::Tgff => dag_set.extend(create_dag_set_from_tgff(file_path_str)),
+        }
+    }
+    for dag in dag_set.iter_mut() {
+        remove_dag_id(dag);
+    }
+    assign_dag_ids(&mut dag_set);
+    dag_set
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_dag_from_dot_normal() {
+        let dag = create_dag_from_dot("tests/sample_dags/benchmark/fft.dot");
+        assert_eq!(dag.node_count(), 5);
+        assert_eq!(dag.edge_count(), 6);
+        let execution_times: Vec<i32> = dag
+            .node_weights()
+            .map(|node_data| node_data.get_params_value("execution_time"))
+            .collect();
+        assert_eq!(execution_times, vec![2, 3, 3, 4, 2]);
+        assert_eq!(dag.get_head_period(), Some(40));
+        assert_eq!(dag.get_end_to_end_deadline(), Some(30));
+        let edge_i = dag.find_edge(NodeIndex::new(0), NodeIndex::new(1)).unwrap();
+        assert_eq!(dag[edge_i], 1);
+    }
+
+    #[test]
+    fn test_create_dag_set_from_tgff_normal() {
+        let dag_set = create_dag_set_from_tgff("tests/sample_dags/benchmark/tgff_sample.tgff");
+        assert_eq!(dag_set.len(), 2);
+        assert_eq!(dag_set[0].node_count(), 3);
+        assert_eq!(dag_set[0].edge_count(), 2);
+        assert_eq!(dag_set[0].get_head_period(), Some(300));
+        assert_eq!(dag_set[0].get_end_to_end_deadline(), Some(250));
+        assert_eq!(
+            dag_set[0][NodeIndex::new(1)].get_params_value("execution_time"),
+            12
+        );
+        assert_eq!(dag_set[0][EdgeIndex::new(0)], 5);
+        assert_eq!(dag_set[1].node_count(), 2);
+    }
+
+    #[test]
+    fn test_create_dag_set_from_benchmark_dir_normal() {
+        let dag_set = create_dag_set_from_benchmark_dir("tests/sample_dags/benchmark");
+        assert_eq!(dag_set.len(), 3);
+        for (dag_id, dag) in dag_set.iter().enumerate() {
+            assert_eq!(dag.get_dag_param("dag_id"), dag_id as i32);
+        }
+    }
+}
@@ -0,0 +1,202 @@
+//! Breakdown factor of a DAG set: the largest factor by which all execution times can be
+//! inflated while the simulation still meets all deadlines, as a single robustness metric to
+//! compare schedulers by their safety margin.
+use crate::{
+    dag_set_scheduler::{DAGSetSchedulerBase, PreemptiveType},
+    dag_task::DagTask,
+    graph_extension::NodeData,
+    processor::ProcessorBase,
+};
+use petgraph::Graph;
+use serde_derive::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BreakdownFactor {
+    /// Resolution of the search. The breakdown factor is `1 + k * precision` for an integer `k`.
+    precision: f64,
+    /// Upper bound of the search.
+    max_factor: f64,
+    /// Largest factor for which all deadlines are met. `None` if already missed without inflation.
+    breakdown_factor: Option<f64>,
+    /// Whether the DAG set is still schedulable at the largest factor within `max_factor`, so
+    /// that the breakdown factor is only a lower bound.
+    is_capped: bool,
+}
+
+impl BreakdownFactor {
+    pub fn get_breakdown_factor(&self) -> Option<f64> {
+        self.breakdown_factor
+    }
+
+    pub fn get_is_capped(&self) -> bool {
+        self.is_capped
+    }
+}
+
+/// Multiply the execution time of every node by `factor`, rounded up so that the demand is never
+/// underestimated.
+pub fn inflate_execution_times(
+    dag_set: &[Graph<NodeData, i32>],
+    factor: f64,
+) -> Vec<Graph<NodeData, i32>> {
+    let mut inflated_dag_set = dag_set.to_vec();
+    for dag in inflated_dag_set.iter_mut() {
+        for node in dag.node_weights_mut() {
+            if let Some(execution_time) = node.params.get_mut("execution_time") {
+                *execution_time = (*execution_time as f64 * factor - 1e-9).ceil() as i32;
+            }
+        }
+    }
+    inflated_dag_set
+}
+
+/// Largest step count in `[0, max_step_count]` for which `is_schedulable` holds (binary search).
+/// `None` if it fails at zero.
+fn search_step_count(
+    max_step_count: usize,
+    mut is_schedulable: impl FnMut(usize) -> bool,
+) -> Option<usize> {
+    if !is_schedulable(0) {
+        return None;
+    }
+    let (mut low, mut high) = (0, max_step_count);
+    while low < high {
+        let middle = (low + high).div_ceil(2);
+        if is_schedulable(middle) {
+            low = middle;
+        } else {
+            high = middle - 1;
+        }
+    }
+    Some(low)
+}
+
+/// Search the largest factor `1 + k * precision` up to `max_factor` by which the execution times
+/// of `dag_set` can be inflated while all DAGs meet their deadlines.
+///
+/// Each factor is simulated by a scheduler of `create_scheduler` on the inflated DAG set. The
+/// binary search assumes that longer execution times never make a DAG set schedulable again,
+/// which does not hold for every scheduler (scheduling anomalies), so the result is an estimate
+/// rather than a bound.
+pub fn find_breakdown_factor<T, S>(
+    dag_set: &[Graph<NodeData, i32>],
+    preemptive_type: &PreemptiveType,
+    precision: f64,
+    max_factor: f64,
+    create_scheduler: impl Fn(&[Graph<NodeData, i32>]) -> S,
+) -> BreakdownFactor
+where
+    T: ProcessorBase + Clone,
+    S: DAGSetSchedulerBase<T>,
+{
+    if precision <= 0.0 {
+        panic!(
+            "The precision of the breakdown factor must be positive, but is {}.",
+            precision
+        );
+    }
+    let get_factor = |step_count: usize| 1.0 + step_count as f64 * precision;
+    let max_step_count = ((max_factor - 1.0) / precision + 1e-9).floor().max(0.0) as usize;
+    let mut is_capped = false;
+    let step_count = search_step_count(max_step_count, |step_count| {
+        let inflated_dag_set = inflate_execution_times(dag_set, get_factor(step_count));
+        let mut scheduler = create_scheduler(&inflated_dag_set);
+        scheduler.schedule(preemptive_type.clone());
+        let log = scheduler.get_log_mut();
+        let is_schedulable = inflated_dag_set.iter().enumerate().all(|(dag_id, dag)| {
+            DagTask::from_graph(dag)
+                .get_relative_deadline()
+                .is_some_and(|deadline| log.get_worst_response_time(dag_id) <= deadline)
+        });
+        if step_count == max_step_count && is_schedulable {
+            is_capped = true;
+        }
+        is_schedulable
+    });
+    BreakdownFactor {
+        precision,
+        max_factor,
+        breakdown_factor: step_count.map(get_factor),
+        is_capped,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::create_node;
+    use crate::{
+        global_edf_scheduler::GlobalEDFScheduler, graph_extension::GraphExtension,
+        homogeneous::HomogeneousProcessor,
+    };
+
+    fn create_sample_dag_set(end_to_end_deadline: i32) -> Vec<Graph<NodeData, i32>> {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 4));
+        let n1 = dag.add_node(create_node(1, "execution_time", 6));
+        dag.add_edge(n0, n1, 1);
+        dag.add_param(n0, "period", 16);
+        dag.add_param(n1, "end_to_end_deadline", end_to_end_deadline);
+        dag.set_dag_param("dag_id", 0);
+        vec![dag]
+    }
+
+    fn find_global_edf_breakdown_factor(
+        dag_set: &[Graph<NodeData, i32>],
+        max_factor: f64,
+    ) -> BreakdownFactor {
+        let processor = HomogeneousProcessor::new(1);
+        find_breakdown_factor(
+            dag_set,
+            &PreemptiveType::NonPreemptive,
+            0.1,
+            max_factor,
+            |dag_set| GlobalEDFScheduler::new(dag_set, &processor),
+        )
+    }
+
+    #[test]
+    fn test_inflate_execution_times_normal() {
+        let inflated_dag_set = inflate_execution_times(&create_sample_dag_set(16), 1.25);
+        // 4 * 1.25 = 5 and 6 * 1.25 = 7.5, rounded up.
+        assert_eq!(inflated_dag_set[0].get_volume(), 13);
+        let inflated_dag_set = inflate_execution_times(&create_sample_dag_set(16), 1.1);
+        // 4.4 and 6.6 are rounded up to 5 and 7.
+        assert_eq!(inflated_dag_set[0].get_volume(), 12);
+    }
+
+    #[test]
+    fn test_search_step_count_normal() {
+        let mut simulation_count = 0;
+        let step_count = search_step_count(1000, |step_count| {
+            simulation_count += 1;
+            step_count <= 374
+        });
+        assert_eq!(step_count, Some(374));
+        assert!(simulation_count <= 11);
+
+        assert_eq!(search_step_count(1000, |_| true), Some(1000));
+        assert_eq!(search_step_count(1000, |_| false), None);
+        assert_eq!(search_step_count(0, |_| true), Some(0));
+    }
+
+    #[test]
+    fn test_find_breakdown_factor_normal() {
+        // The single core runs the chain in its volume: 5 + 8 = 13 at 1.3 and 6 + 9 = 15 at 1.5,
+        // but 7 + 10 = 17 at 1.6.
+        let breakdown_factor = find_global_edf_breakdown_factor(&create_sample_dag_set(16), 10.0);
+        assert!((breakdown_factor.get_breakdown_factor().unwrap() - 1.5).abs() < 1e-9);
+        assert!(!breakdown_factor.get_is_capped());
+
+        let breakdown_factor = find_global_edf_breakdown_factor(&create_sample_dag_set(16), 1.2);
+        assert!((breakdown_factor.get_breakdown_factor().unwrap() - 1.2).abs() < 1e-9);
+        assert!(breakdown_factor.get_is_capped());
+    }
+
+    #[test]
+    fn test_find_breakdown_factor_missed_without_inflation() {
+        let breakdown_factor = find_global_edf_breakdown_factor(&create_sample_dag_set(9), 10.0);
+        assert_eq!(breakdown_factor.get_breakdown_factor(), None);
+        assert!(!breakdown_factor.get_is_capped());
+    }
+}
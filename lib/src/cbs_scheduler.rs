@@ -0,0 +1,370 @@
+//! Constant bandwidth servers (CBS, Abeni and Buttazzo, RTSS 1998) under global preemptive EDF.
+//!
+//! A server reserves `budget` time units every `period` for the DAG it wraps. The nodes of a
+//! served DAG are scheduled with the deadline of the server instead of their own, so a DAG that
+//! overruns its reservation only delays itself. The other DAGs are scheduled with their own
+//! absolute deadlines.
+//!
+//! * On a release, the idle server keeps its budget and deadline if the budget can be used by the
+//!   deadline without exceeding its bandwidth, and is otherwise recharged with the deadline one
+//!   period later.
+//! * Each time unit a node of the DAG runs consumes one unit of the budget. When the budget is
+//!   exhausted, it is recharged and the deadline is postponed by a period.
+use crate::dag_set_scheduler::{
+    DAGSetSchedulerBase, DAGStateManager, DAGStateManagerBase, PreemptiveType,
+};
+use crate::getset_dag_set_scheduler;
+use crate::{
+    core::ProcessResult,
+    graph_extension::{get_node_index_maps, GraphExtension, NodeData, NodeIndexMap},
+    homogeneous::HomogeneousProcessor,
+    log::{DAGSetSchedulerLog, JobEventTimes, ServerEvent, ServerEventKind},
+    processor::ProcessorBase,
+    util::{clear_interrupted, get_hyper_period, get_process_core_indices, is_interrupted},
+};
+use petgraph::graph::Graph;
+use std::collections::BTreeMap;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConstantBandwidthServer {
+    pub budget: i32,
+    pub period: i32,
+    /// The served DAG, or `None` for the server of aperiodic jobs.
+    dag_id: Option<usize>,
+    remaining_budget: i32,
+    deadline: i32,
+}
+
+impl ConstantBandwidthServer {
+    pub fn new(budget: i32, period: i32) -> Self {
+        if budget <= 0 || budget > period {
+            panic!(
+                "The budget {} of the server must be positive and fit in its period {}.",
+                budget, period
+            );
+        }
+        Self {
+            budget,
+            period,
+            dag_id: None,
+            remaining_budget: 0,
+            deadline: 0,
+        }
+    }
+
+    pub fn get_remaining_budget(&self) -> i32 {
+        self.remaining_budget
+    }
+
+    pub fn get_deadline(&self) -> i32 {
+        self.deadline
+    }
+
+    /// A job arrives at the idle server at `time`.
+    pub fn release(&mut self, time: i32, log: &mut DAGSetSchedulerLog) {
+        // remaining_budget / (deadline - time) >= budget / period, compared exactly.
+        if self.remaining_budget as i64 * self.period as i64
+            >= (self.deadline - time) as i64 * self.budget as i64
+        {
+            self.remaining_budget = self.budget;
+            self.deadline = time + self.period;
+            self.write_event(time, ServerEventKind::Replenished, log);
+        }
+    }
+
+    /// Consume `time` units of the budget by `current_time`.
+    pub fn consume(&mut self, time: i32, current_time: i32, log: &mut DAGSetSchedulerLog) {
+        self.remaining_budget -= time;
+        // Several cores may exhaust more than one budget in a time unit.
+        while self.remaining_budget <= 0 {
+            self.remaining_budget += self.budget;
+            self.deadline += self.period;
+            self.write_event(current_time, ServerEventKind::Depleted, log);
+        }
+    }
+
+    fn write_event(&self, time: i32, kind: ServerEventKind, log: &mut DAGSetSchedulerLog) {
+        log.write_server_event(ServerEvent {
+            dag_id: self.dag_id,
+            time,
+            kind,
+            budget: self.remaining_budget,
+            deadline: self.deadline,
+        });
+    }
+}
+
+pub struct CBSScheduler {
+    dag_set: Vec<Graph<NodeData, i32>>,
+    node_index_maps: Vec<NodeIndexMap>,
+    processor: HomogeneousProcessor,
+    log: DAGSetSchedulerLog,
+    current_time: i32,
+    servers: BTreeMap<usize, ConstantBandwidthServer>,
+}
+
+impl DAGSetSchedulerBase<HomogeneousProcessor> for CBSScheduler {
+    fn new(dag_set: &[Graph<NodeData, i32>], processor: &HomogeneousProcessor) -> Self {
+        Self {
+            node_index_maps: get_node_index_maps(dag_set),
+            dag_set: dag_set.to_vec(),
+            processor: processor.clone(),
+            log: DAGSetSchedulerLog::new(dag_set, processor.get_number_of_cores()),
+            current_time: 0,
+            servers: BTreeMap::new(),
+        }
+    }
+
+    getset_dag_set_scheduler!(HomogeneousProcessor);
+
+    /// The preemptive type is ignored because the nodes are always scheduled preemptively.
+    fn schedule(&mut self, _preemptive_type: PreemptiveType) -> i32 {
+        clear_interrupted();
+        self.log.write_schedule_start();
+        let mut managers = vec![DAGStateManager::default(); self.dag_set.len()];
+        let mut ready_nodes: Vec<NodeData> = Vec::new();
+        let hyper_period = get_hyper_period(&self.dag_set);
+        while self.get_current_time() < hyper_period && !is_interrupted() {
+            // Release DAGs
+            for ready_node in self.release_dags(&mut managers) {
+                let dag_id = ready_node.get_params_value("dag_id") as usize;
+                if let Some(server) = self.servers.get_mut(&dag_id) {
+                    server.release(self.current_time, &mut self.log);
+                }
+                ready_nodes.push(ready_node);
+            }
+
+            let selected_nodes = self.select_nodes(&ready_nodes);
+
+            // Preempt the running nodes that are not selected.
+            let current_time = self.get_current_time();
+            for core_i in 0..self.processor.get_number_of_cores() {
+                let Some(running_node) = self.processor.cores[core_i].get_processing_node() else {
+                    continue;
+                };
+                if selected_nodes.contains(&get_node_key(running_node)) {
+                    continue;
+                }
+                let preempted_node_data = self.processor.preempt(core_i).unwrap();
+                let dag_id = preempted_node_data.get_params_value("dag_id") as usize;
+                self.log.write_job_event(
+                    &preempted_node_data,
+                    core_i,
+                    managers[dag_id].get_release_count() as usize - 1,
+                    JobEventTimes::PreemptedTime(current_time),
+                );
+                ready_nodes.push(preempted_node_data);
+            }
+
+            // Allocate the selected ready nodes to the idle cores.
+            for (dag_id, node_id) in selected_nodes {
+                let Some(position) = ready_nodes
+                    .iter()
+                    .position(|node| get_node_key(node) == (dag_id, node_id))
+                else {
+                    continue;
+                };
+                let node_data = ready_nodes.remove(position);
+                let idle_core_i = self.processor.get_idle_core_index().unwrap();
+                self.allocate_node(
+                    &node_data,
+                    idle_core_i,
+                    managers[dag_id].get_release_count() as usize,
+                );
+            }
+
+            let mut running_times = BTreeMap::new();
+            for core in self.processor.cores.iter() {
+                if let Some(node_data) = core.get_processing_node() {
+                    *running_times
+                        .entry(node_data.get_params_value("dag_id") as usize)
+                        .or_insert(0) += 1;
+                }
+            }
+
+            // Process unit time
+            let process_result = self.process_unit_time();
+            let indices: Vec<usize> = get_process_core_indices(&process_result);
+            self.log.write_processing_time(&indices);
+
+            // Consume the budgets of the servers of the running DAGs.
+            for (dag_id, running_time) in running_times {
+                if let Some(server) = self.servers.get_mut(&dag_id) {
+                    server.consume(running_time, self.current_time, &mut self.log);
+                }
+            }
+
+            // Post-process on completion of node execution
+            for (core_id, result) in process_result.iter().enumerate() {
+                if let ProcessResult::Done(node_data) = result {
+                    ready_nodes.extend(self.post_process_on_node_completion(
+                        node_data,
+                        core_id,
+                        &mut managers,
+                    ));
+                }
+            }
+        }
+
+        self.calculate_log();
+        self.get_current_time()
+    }
+}
+
+impl CBSScheduler {
+    /// Wrap the DAG of `dag_id` in `server`.
+    pub fn set_server(&mut self, dag_id: usize, mut server: ConstantBandwidthServer) {
+        if dag_id >= self.dag_set.len() {
+            panic!("DAG {} does not exist.", dag_id);
+        }
+        server.dag_id = Some(dag_id);
+        self.servers.insert(dag_id, server);
+    }
+
+    pub fn get_server(&self, dag_id: usize) -> Option<&ConstantBandwidthServer> {
+        self.servers.get(&dag_id)
+    }
+
+    /// The deadline of the server of the DAG of `node_data`, or its own absolute deadline.
+    fn get_scheduling_deadline(&self, node_data: &NodeData) -> i32 {
+        let dag_id = node_data.get_params_value("dag_id") as usize;
+        match self.servers.get(&dag_id) {
+            Some(server) => server.get_deadline(),
+            None => node_data.get_params_value("node_absolute_deadline"),
+        }
+    }
+
+    /// Select the `(dag_id, node_id)` to execute in the next time unit by EDF over the running
+    /// and ready nodes, preferring the running nodes on ties to avoid preemptions.
+    /// No DAG takes more cores than its `max_parallelism`.
+    fn select_nodes(&self, ready_nodes: &[NodeData]) -> Vec<(usize, i32)> {
+        let running_nodes = self
+            .processor
+            .cores
+            .iter()
+            .filter_map(|core| core.get_processing_node().as_ref());
+        let mut candidates: Vec<(i32, bool, (usize, i32))> = running_nodes
+            .map(|node_data| (node_data, false))
+            .chain(ready_nodes.iter().map(|node_data| (node_data, true)))
+            .map(|(node_data, is_ready)| {
+                (
+                    self.get_scheduling_deadline(node_data),
+                    is_ready,
+                    get_node_key(node_data),
+                )
+            })
+            .collect();
+        candidates.sort();
+
+        let number_of_cores = self.processor.get_number_of_cores();
+        let mut allocated_counts = vec![0; self.dag_set.len()];
+        let mut selected_nodes = Vec::new();
+        for (_, _, (dag_id, node_id)) in candidates {
+            if selected_nodes.len() == number_of_cores {
+                break;
+            }
+            let max_parallelism = self.dag_set[dag_id]
+                .get_max_parallelism()
+                .unwrap_or(number_of_cores);
+            if allocated_counts[dag_id] < max_parallelism {
+                allocated_counts[dag_id] += 1;
+                selected_nodes.push((dag_id, node_id));
+            }
+        }
+        selected_nodes
+    }
+}
+
+fn get_node_key(node_data: &NodeData) -> (usize, i32) {
+    (
+        node_data.get_params_value("dag_id") as usize,
+        node_data.get_id(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::global_edf_scheduler::GlobalEDFScheduler;
+    use crate::test_util::create_node;
+
+    fn create_dag_set() -> Vec<Graph<NodeData, i32>> {
+        let mut dag0 = Graph::<NodeData, i32>::new();
+        let n0 = dag0.add_node(create_node(0, "execution_time", 4));
+        dag0.add_param(n0, "period", 10);
+        dag0.add_param(n0, "end_to_end_deadline", 10);
+        dag0.set_dag_param("dag_id", 0);
+
+        let mut dag1 = Graph::<NodeData, i32>::new();
+        let n0 = dag1.add_node(create_node(0, "execution_time", 3));
+        dag1.add_param(n0, "period", 10);
+        dag1.add_param(n0, "end_to_end_deadline", 6);
+        dag1.set_dag_param("dag_id", 1);
+
+        vec![dag0, dag1]
+    }
+
+    #[test]
+    fn test_cbs_scheduler_normal() {
+        let dag_set = create_dag_set();
+        let mut cbs_scheduler = CBSScheduler::new(&dag_set, &HomogeneousProcessor::new(1));
+        cbs_scheduler.set_server(0, ConstantBandwidthServer::new(2, 5));
+        assert_eq!(cbs_scheduler.schedule(PreemptiveType::NonPreemptive), 10);
+
+        // DAG 0 runs first with the server deadline 5 and is preempted when the budget runs out.
+        let log = cbs_scheduler.get_log_mut();
+        assert_eq!(log.get_worst_response_time(0), 7);
+        assert_eq!(log.get_worst_response_time(1), 5);
+        let server_events: Vec<(i32, ServerEventKind, i32)> = log
+            .get_server_log()
+            .iter()
+            .map(|event| (event.time, event.kind, event.deadline))
+            .collect();
+        assert_eq!(
+            server_events,
+            vec![
+                (0, ServerEventKind::Replenished, 5),
+                (2, ServerEventKind::Depleted, 10),
+                (7, ServerEventKind::Depleted, 15),
+            ]
+        );
+        assert!(log
+            .get_server_log()
+            .iter()
+            .all(|event| event.dag_id == Some(0)));
+
+        // Without the server, DAG 1 with the earlier deadline runs first.
+        let mut global_edf_scheduler =
+            GlobalEDFScheduler::new(&dag_set, &HomogeneousProcessor::new(1));
+        global_edf_scheduler.schedule(PreemptiveType::NonPreemptive);
+        assert_eq!(
+            global_edf_scheduler
+                .get_log_mut()
+                .get_worst_response_time(1),
+            3
+        );
+    }
+
+    #[test]
+    fn test_constant_bandwidth_server_release_keeps_budget() {
+        let mut log = DAGSetSchedulerLog::new(&create_dag_set(), 1);
+        let mut server = ConstantBandwidthServer::new(2, 10);
+        server.release(0, &mut log);
+        server.consume(1, 1, &mut log);
+        // 1 unit of budget by the deadline 10 does not exceed the bandwidth 2 / 10 at time 3.
+        server.release(3, &mut log);
+        assert_eq!(server.get_remaining_budget(), 1);
+        assert_eq!(server.get_deadline(), 10);
+        // It does at the deadline, so the server is recharged.
+        server.release(10, &mut log);
+        assert_eq!(server.get_remaining_budget(), 2);
+        assert_eq!(server.get_deadline(), 20);
+        assert_eq!(log.get_server_log().len(), 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_constant_bandwidth_server_new_over_period() {
+        ConstantBandwidthServer::new(6, 5);
+    }
+}
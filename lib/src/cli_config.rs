@@ -0,0 +1,116 @@
+//! Read the command line arguments of a binary from a TOML file (`--config <file.toml>`).
+//!
+//! Each key is the long name of a flag of the binary, e.g., `dag_dir_path` or `number_of_cores`.
+//! The keys are read in alphabetical order. Tables only group the keys, so
+//! `[platform] number_of_cores = 4` is `--number_of_cores 4`.
+//! A `true` boolean sets the flag, an array repeats it. The arguments of the file are placed
+//! before those on the command line, so flags on the command line override the file when the
+//! binary's parser lets later occurrences override earlier ones (`args_override_self`).
+use std::fs;
+use toml::{Table, Value};
+
+const CONFIG_FLAG: &str = "--config";
+
+fn push_value_args(args: &mut Vec<String>, key: &str, value: &Value) {
+    match value {
+        Value::Table(table) => {
+            for (key, value) in table {
+                push_value_args(args, key, value);
+            }
+        }
+        Value::Array(values) => {
+            for value in values {
+                push_value_args(args, key, value);
+            }
+        }
+        Value::Boolean(true) => args.push(format!("--{}", key)),
+        Value::Boolean(false) => {}
+        Value::String(value) => args.push(format!("--{}={}", key, value)),
+        value => args.push(format!("--{}={}", key, value)),
+    }
+}
+
+/// The arguments specified by the TOML file at `file_path`.
+pub fn load_config_args(file_path: &str) -> Vec<String> {
+    let content = fs::read_to_string(file_path)
+        .unwrap_or_else(|err| panic!("Failed to read {}: {}", file_path, err));
+    let table: Table = content
+        .parse()
+        .unwrap_or_else(|err| panic!("Invalid TOML in {}: {}", file_path, err));
+    let mut args = Vec::new();
+    for (key, value) in table.iter() {
+        push_value_args(&mut args, key, value);
+    }
+    args
+}
+
+/// Insert the arguments of the file given by `--config` after the binary name.
+///
+/// # Example
+///
+/// ```ignore
+/// let arg = ArgParser::parse_from(expand_config_args(std::env::args()));
+/// ```
+pub fn expand_config_args(args: impl IntoIterator<Item = String>) -> Vec<String> {
+    let args: Vec<String> = args.into_iter().collect();
+    let config_path = args.iter().enumerate().find_map(|(arg_i, arg)| {
+        if arg == CONFIG_FLAG {
+            args.get(arg_i + 1).cloned()
+        } else {
+            arg.strip_prefix(CONFIG_FLAG)
+                .and_then(|arg| arg.strip_prefix('='))
+                .map(str::to_string)
+        }
+    });
+    let Some(config_path) = config_path else {
+        return args;
+    };
+    let mut expanded_args = args[..1.min(args.len())].to_vec();
+    expanded_args.extend(load_config_args(&config_path));
+    expanded_args.extend(args.into_iter().skip(1));
+    expanded_args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_config_args_normal() {
+        let file_path = "../lib/tests/cli_config_test.toml";
+        fs::write(
+            file_path,
+            "dag_dir_path = \"../dags\"\nrun_dir = true\ncore_trace = false\n\n[platform]\nnumber_of_cores = 4\n",
+        )
+        .unwrap();
+        let args = expand_config_args(
+            ["gedf", "--config", file_path, "-c", "8"]
+                .iter()
+                .map(|arg| arg.to_string()),
+        );
+        fs::remove_file(file_path).unwrap();
+
+        assert_eq!(
+            args,
+            vec![
+                "gedf",
+                "--dag_dir_path=../dags",
+                "--number_of_cores=4",
+                "--run_dir",
+                "--config",
+                file_path,
+                "-c",
+                "8"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_expand_config_args_without_config() {
+        let args: Vec<String> = ["gedf", "-c", "8"]
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect();
+        assert_eq!(expand_config_args(args.clone()), args);
+    }
+}
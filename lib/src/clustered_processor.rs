@@ -0,0 +1,226 @@
+//! Homogeneous processor whose cores are partitioned into clusters of consecutive cores.
+//!
+//! The cores are numbered across the clusters, so that the processor runs and logs like a
+//! `HomogeneousProcessor`, and the cluster-aware schedulers query the cores of a single cluster.
+use crate::{
+    core::ProcessResult,
+    graph_extension::NodeData,
+    homogeneous::HomogeneousProcessor,
+    log::{EnergyLog, PowerLog},
+    processor::ProcessorBase,
+};
+use std::ops::Range;
+
+#[derive(Clone, Debug)]
+pub struct ClusteredProcessor {
+    processor: HomogeneousProcessor,
+    /// Cores of each cluster.
+    cluster_core_ranges: Vec<Range<usize>>,
+}
+
+impl ProcessorBase for ClusteredProcessor {
+    /// A single cluster of all the cores, i.e., global scheduling.
+    fn new(num_cores: usize) -> Self {
+        Self::new_with_cluster_sizes(&[num_cores])
+    }
+
+    fn allocate_specific_core(&mut self, core_id: usize, node_data: &NodeData) -> bool {
+        self.processor.allocate_specific_core(core_id, node_data)
+    }
+
+    fn process(&mut self) -> Vec<ProcessResult> {
+        self.processor.process()
+    }
+
+    fn process_except(&mut self, stalled_cores: &[bool]) -> Vec<ProcessResult> {
+        self.processor.process_except(stalled_cores)
+    }
+
+    fn process_for(&mut self, max_time: i32) -> (i32, Vec<ProcessResult>) {
+        self.processor.process_for(max_time)
+    }
+
+    fn get_number_of_cores(&self) -> usize {
+        self.processor.get_number_of_cores()
+    }
+
+    fn get_idle_core_index(&self) -> Option<usize> {
+        self.processor.get_idle_core_index()
+    }
+
+    fn get_idle_core_num(&self) -> usize {
+        self.processor.get_idle_core_num()
+    }
+
+    fn get_idle_core_indices(&self) -> Vec<usize> {
+        self.processor.get_idle_core_indices()
+    }
+
+    fn get_processing_node(&self, core_id: usize) -> Option<&NodeData> {
+        self.processor.get_processing_node(core_id)
+    }
+
+    fn preempt(&mut self, core_id: usize) -> Option<NodeData> {
+        self.processor.preempt(core_id)
+    }
+
+    fn set_processing_node_param(&mut self, core_id: usize, key: &str, value: i32) {
+        self.processor
+            .set_processing_node_param(core_id, key, value);
+    }
+
+    fn get_max_value_and_index(&self, key: &str) -> Option<(i32, usize)> {
+        self.processor.get_max_value_and_index(key)
+    }
+
+    fn get_preemptable_core_index_after(
+        &self,
+        key: &str,
+        value: i32,
+        non_preemptive_region: i32,
+    ) -> Option<usize> {
+        self.processor
+            .get_preemptable_core_index_after(key, value, non_preemptive_region)
+    }
+
+    fn get_number_of_running_nodes(&self, dag_id: i32) -> usize {
+        self.processor.get_number_of_running_nodes(dag_id)
+    }
+
+    fn get_run_time(&self, core_id: usize) -> i32 {
+        self.processor.get_run_time(core_id)
+    }
+
+    fn is_core_asleep(&self, core_id: usize) -> bool {
+        self.processor.is_core_asleep(core_id)
+    }
+
+    fn get_power_log(&self) -> Option<PowerLog> {
+        self.processor.get_power_log()
+    }
+
+    fn get_energy_log(&self) -> Option<EnergyLog> {
+        self.processor.get_energy_log()
+    }
+}
+
+impl ClusteredProcessor {
+    /// Cluster `i` consists of the next `cluster_sizes[i]` cores.
+    pub fn new_with_cluster_sizes(cluster_sizes: &[usize]) -> Self {
+        if cluster_sizes.is_empty() || cluster_sizes.contains(&0) {
+            panic!(
+                "Each cluster needs at least one core. cluster_sizes: {:?}",
+                cluster_sizes
+            );
+        }
+        let mut cluster_core_ranges = Vec::with_capacity(cluster_sizes.len());
+        let mut first_core_id = 0;
+        for &cluster_size in cluster_sizes {
+            cluster_core_ranges.push(first_core_id..first_core_id + cluster_size);
+            first_core_id += cluster_size;
+        }
+        Self {
+            processor: HomogeneousProcessor::new(first_core_id),
+            cluster_core_ranges,
+        }
+    }
+
+    /// `num_cores` cores in clusters of `cluster_size`, of which the last may be smaller.
+    pub fn new_with_cluster_size(num_cores: usize, cluster_size: usize) -> Self {
+        if cluster_size == 0 {
+            panic!("The cluster size must be positive.");
+        }
+        let cluster_sizes: Vec<usize> = (0..num_cores)
+            .step_by(cluster_size)
+            .map(|first_core_id| cluster_size.min(num_cores - first_core_id))
+            .collect();
+        Self::new_with_cluster_sizes(&cluster_sizes)
+    }
+
+    pub fn get_processor(&self) -> &HomogeneousProcessor {
+        &self.processor
+    }
+
+    pub fn get_number_of_clusters(&self) -> usize {
+        self.cluster_core_ranges.len()
+    }
+
+    pub fn get_cluster_sizes(&self) -> Vec<usize> {
+        self.cluster_core_ranges.iter().map(Range::len).collect()
+    }
+
+    pub fn get_cluster_core_ids(&self, cluster_id: usize) -> Range<usize> {
+        self.cluster_core_ranges[cluster_id].clone()
+    }
+
+    pub fn get_cluster_id(&self, core_id: usize) -> usize {
+        self.cluster_core_ranges
+            .iter()
+            .position(|core_ids| core_ids.contains(&core_id))
+            .unwrap_or_else(|| panic!("Core {} does not exist.", core_id))
+    }
+
+    pub fn get_idle_core_indices_in_cluster(&self, cluster_id: usize) -> Vec<usize> {
+        self.get_cluster_core_ids(cluster_id)
+            .filter(|&core_id| self.processor.cores[core_id].get_is_idle())
+            .collect()
+    }
+
+    /// `get_preemptable_core_index_after` within the cluster.
+    pub fn get_preemptable_core_index_in_cluster(
+        &self,
+        cluster_id: usize,
+        key: &str,
+        value: i32,
+        non_preemptive_region: i32,
+    ) -> Option<usize> {
+        self.processor.get_preemptable_core_index_among(
+            self.get_cluster_core_ids(cluster_id),
+            key,
+            value,
+            non_preemptive_region,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::create_node;
+
+    #[test]
+    fn test_clustered_processor_normal() {
+        let mut clustered_processor = ClusteredProcessor::new_with_cluster_size(5, 2);
+        assert_eq!(clustered_processor.get_number_of_cores(), 5);
+        assert_eq!(clustered_processor.get_cluster_sizes(), vec![2, 2, 1]);
+        assert_eq!(clustered_processor.get_cluster_core_ids(1), 2..4);
+        assert_eq!(clustered_processor.get_cluster_id(4), 2);
+
+        let mut node = create_node(0, "execution_time", 2);
+        node.params.insert("node_absolute_deadline".to_string(), 10);
+        clustered_processor.allocate_specific_core(2, &node);
+        assert_eq!(
+            clustered_processor.get_idle_core_indices_in_cluster(1),
+            vec![3]
+        );
+        // The node is preemptable only from its own cluster.
+        assert_eq!(
+            clustered_processor.get_preemptable_core_index_in_cluster(
+                1,
+                "node_absolute_deadline",
+                5,
+                0
+            ),
+            Some(2)
+        );
+        assert_eq!(
+            clustered_processor.get_preemptable_core_index_in_cluster(
+                0,
+                "node_absolute_deadline",
+                5,
+                0
+            ),
+            None
+        );
+    }
+}
@@ -0,0 +1,330 @@
+//! Clustered EDF: the cores are partitioned into clusters (see `ClusteredProcessor`), whole DAGs
+//! are assigned to clusters by a bin-packing heuristic on their utilizations, and each cluster
+//! runs global EDF on the nodes of its DAGs, which migrate only within the cluster.
+//!
+//! A cluster can take DAGs up to a total utilization of its number of cores. As in
+//! `partitioned_edf_scheduler`, the DAGs are packed in the order of `dag_id`, and a DAG that fits
+//! in no cluster is placed in the cluster with the most remaining capacity and recorded as unfit.
+use crate::dag_set_scheduler::{DAGSetSchedulerBase, NodeDataWrapper, PreemptiveType, ReadyQueue};
+use crate::getset_dag_set_scheduler;
+use crate::{
+    clustered_processor::ClusteredProcessor,
+    core::ProcessResult,
+    dag_set_scheduler::{DAGStateManager, DAGStateManagerBase},
+    dag_task::DagTask,
+    graph_extension::{get_node_index_maps, NodeData, NodeIndexMap},
+    log::{ClusteringLog, DAGSetSchedulerLog, JobEventTimes},
+    partitioned_edf_scheduler::PartitioningHeuristic,
+    processor::ProcessorBase,
+    util::{clear_interrupted, get_hyper_period, get_process_core_indices, is_interrupted},
+};
+use petgraph::graph::Graph;
+use serde_derive::{Deserialize, Serialize};
+
+/// Assignment of the DAGs to the clusters.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ClusterAssignment {
+    /// Cluster of each DAG by `dag_id`.
+    pub cluster_ids: Vec<usize>,
+    pub cluster_utilizations: Vec<f32>,
+    /// DAGs that fit in no cluster.
+    pub unfit_dag_ids: Vec<usize>,
+}
+
+/// Best-Fit takes the cluster with the least remaining capacity, and Worst-Fit the one with the
+/// most. Ties are broken by the smallest cluster index.
+pub fn assign_dag_set_to_clusters(
+    dag_set: &[Graph<NodeData, i32>],
+    cluster_sizes: &[usize],
+    heuristic: PartitioningHeuristic,
+) -> ClusterAssignment {
+    let number_of_clusters = cluster_sizes.len();
+    let mut assignment = ClusterAssignment {
+        cluster_ids: Vec::with_capacity(dag_set.len()),
+        cluster_utilizations: vec![0.0; number_of_clusters],
+        unfit_dag_ids: Vec::new(),
+    };
+    for (dag_id, dag) in dag_set.iter().enumerate() {
+        let utilization = DagTask::from_graph(dag)
+            .get_utilization()
+            .unwrap_or_else(|| panic!("DAG {} has no period.", dag_id));
+        let remaining_capacities: Vec<f32> = (0..number_of_clusters)
+            .map(|cluster_i| {
+                cluster_sizes[cluster_i] as f32 - assignment.cluster_utilizations[cluster_i]
+            })
+            .collect();
+        let fit_cluster_indices = (0..number_of_clusters)
+            .filter(|&cluster_i| utilization <= remaining_capacities[cluster_i]);
+        let cluster_i = match heuristic {
+            PartitioningHeuristic::FirstFit => fit_cluster_indices.min(),
+            PartitioningHeuristic::BestFit => fit_cluster_indices
+                .min_by(|&a, &b| remaining_capacities[a].total_cmp(&remaining_capacities[b])),
+            PartitioningHeuristic::WorstFit => fit_cluster_indices
+                .rev()
+                .max_by(|&a, &b| remaining_capacities[a].total_cmp(&remaining_capacities[b])),
+        }
+        .unwrap_or_else(|| {
+            assignment.unfit_dag_ids.push(dag_id);
+            (0..number_of_clusters)
+                .rev()
+                .max_by(|&a, &b| remaining_capacities[a].total_cmp(&remaining_capacities[b]))
+                .unwrap()
+        });
+        assignment.cluster_ids.push(cluster_i);
+        assignment.cluster_utilizations[cluster_i] += utilization;
+    }
+    assignment
+}
+
+pub struct ClusteredScheduler {
+    dag_set: Vec<Graph<NodeData, i32>>,
+    node_index_maps: Vec<NodeIndexMap>,
+    processor: ClusteredProcessor,
+    log: DAGSetSchedulerLog,
+    current_time: i32,
+    assignment: ClusterAssignment,
+}
+
+impl DAGSetSchedulerBase<ClusteredProcessor> for ClusteredScheduler {
+    /// Assign with First-Fit.
+    fn new(dag_set: &[Graph<NodeData, i32>], processor: &ClusteredProcessor) -> Self {
+        Self::new_with_heuristic(dag_set, processor, PartitioningHeuristic::default())
+    }
+
+    getset_dag_set_scheduler!(ClusteredProcessor);
+
+    fn schedule(&mut self, preemptive_type: PreemptiveType) -> i32 {
+        clear_interrupted();
+        self.log.write_schedule_start();
+        let mut managers = vec![DAGStateManager::default(); self.dag_set.len()];
+        let mut ready_queues = vec![ReadyQueue::new(); self.processor.get_number_of_clusters()];
+        let hyper_period = get_hyper_period(&self.dag_set);
+        while self.current_time < hyper_period && !is_interrupted() {
+            // Release DAGs
+            for ready_node in self.release_dags(&mut managers) {
+                self.insert_ready_node(&mut ready_queues, ready_node);
+            }
+
+            // Allocate the ready nodes of each cluster by global EDF within the cluster.
+            for (cluster_i, ready_queue) in ready_queues.iter_mut().enumerate() {
+                while let Some(head) = ready_queue.first().cloned() {
+                    let core_i = if let Some(&idle_core_i) = self
+                        .processor
+                        .get_idle_core_indices_in_cluster(cluster_i)
+                        .first()
+                    {
+                        idle_core_i
+                    } else {
+                        let Some(preemptive_key) = preemptive_type.get_key() else {
+                            break;
+                        };
+                        ready_queue.count_comparison();
+                        let Some(core_i) = self.processor.get_preemptable_core_index_in_cluster(
+                            cluster_i,
+                            preemptive_key,
+                            head.node_data.get_params_value(preemptive_key),
+                            preemptive_type.get_non_preemptive_region(),
+                        ) else {
+                            break;
+                        };
+                        let current_time = self.current_time;
+                        let preempted_node_data = self.processor.preempt(core_i).unwrap();
+                        self.log.write_job_event(
+                            &preempted_node_data,
+                            core_i,
+                            managers[preempted_node_data.get_params_value("dag_id") as usize]
+                                .get_release_count() as usize
+                                - 1,
+                            JobEventTimes::PreemptedTime(current_time),
+                        );
+                        ready_queue.insert_new(NodeDataWrapper::new(preempted_node_data));
+                        self.log.write_ready_queue_insertion();
+                        core_i
+                    };
+                    ready_queue.remove(&head);
+                    self.log.write_ready_queue_removal();
+                    let node_data = head.convert_node_data();
+                    self.allocate_node(
+                        &node_data,
+                        core_i,
+                        managers[node_data.get_params_value("dag_id") as usize].get_release_count()
+                            as usize,
+                    );
+                }
+            }
+
+            // Process unit time
+            let process_result = self.process_unit_time();
+            self.log
+                .write_processing_time(&get_process_core_indices(&process_result));
+
+            // Post-process on completion of node execution
+            for (core_id, result) in process_result.iter().enumerate() {
+                if let ProcessResult::Done(node_data) = result {
+                    let ready_nodes =
+                        self.post_process_on_node_completion(node_data, core_id, &mut managers);
+                    for ready_node in ready_nodes {
+                        self.insert_ready_node(&mut ready_queues, ready_node);
+                    }
+                }
+            }
+        }
+
+        self.log.write_comparisons(
+            ready_queues
+                .iter()
+                .map(ReadyQueue::get_comparison_count)
+                .sum(),
+        );
+        self.calculate_log();
+        self.current_time
+    }
+}
+
+impl ClusteredScheduler {
+    pub fn new_with_heuristic(
+        dag_set: &[Graph<NodeData, i32>],
+        processor: &ClusteredProcessor,
+        heuristic: PartitioningHeuristic,
+    ) -> Self {
+        let assignment =
+            assign_dag_set_to_clusters(dag_set, &processor.get_cluster_sizes(), heuristic);
+        let mut log = DAGSetSchedulerLog::new(dag_set, processor.get_number_of_cores());
+        log.write_clustering(ClusteringLog::new(
+            heuristic,
+            (0..processor.get_number_of_clusters())
+                .map(|cluster_i| processor.get_cluster_core_ids(cluster_i).collect())
+                .collect(),
+            &assignment,
+        ));
+        Self {
+            node_index_maps: get_node_index_maps(dag_set),
+            dag_set: dag_set.to_vec(),
+            processor: processor.clone(),
+            log,
+            current_time: 0,
+            assignment,
+        }
+    }
+
+    pub fn get_assignment(&self) -> &ClusterAssignment {
+        &self.assignment
+    }
+
+    /// Insert the node into the ready queue of the cluster of its DAG.
+    fn insert_ready_node(&mut self, ready_queues: &mut [ReadyQueue], node_data: NodeData) {
+        let cluster_i = self.assignment.cluster_ids[node_data.get_params_value("dag_id") as usize];
+        ready_queues[cluster_i].insert_new(NodeDataWrapper::new(node_data));
+        self.log.write_ready_queue_insertion();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph_extension::GraphExtension;
+    use crate::test_util::create_node;
+
+    fn create_single_node_dag(execution_time: i32, dag_id: i32) -> Graph<NodeData, i32> {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", execution_time));
+        dag.add_param(n0, "period", 8);
+        dag.add_param(n0, "end_to_end_deadline", 8);
+        dag.set_dag_param("dag_id", dag_id);
+        dag
+    }
+
+    #[test]
+    fn test_clustered_scheduler_normal() {
+        // Utilizations 0.75, 0.75, 0.625, 0.625, 0.25 on two clusters of two cores.
+        let dag_set = vec![
+            create_single_node_dag(6, 0),
+            create_single_node_dag(6, 1),
+            create_single_node_dag(5, 2),
+            create_single_node_dag(5, 3),
+            create_single_node_dag(2, 4),
+        ];
+        let cluster_sizes = [2, 2];
+        let get_cluster_ids =
+            |heuristic| assign_dag_set_to_clusters(&dag_set, &cluster_sizes, heuristic).cluster_ids;
+        assert_eq!(
+            get_cluster_ids(PartitioningHeuristic::WorstFit),
+            vec![0, 1, 0, 1, 0]
+        );
+        assert_eq!(
+            assign_dag_set_to_clusters(&dag_set, &[1], PartitioningHeuristic::FirstFit)
+                .unfit_dag_ids,
+            vec![1, 2, 3, 4]
+        );
+
+        let processor = ClusteredProcessor::new_with_cluster_sizes(&cluster_sizes);
+        let mut scheduler = ClusteredScheduler::new(&dag_set, &processor);
+        assert_eq!(scheduler.get_assignment().cluster_ids, vec![0, 0, 1, 1, 0]);
+        scheduler.schedule(PreemptiveType::Preemptive {
+            key: "node_absolute_deadline".to_string(),
+        });
+        let log = scheduler.get_log_mut();
+        // DAG 4 waits for DAGs 0 and 1 in cluster 0, although the cores of cluster 1 are idle
+        // from 5.
+        assert_eq!(log.get_worst_response_times(), vec![6, 6, 5, 5, 8]);
+        let clustering = log.get_clustering().unwrap();
+        assert_eq!(clustering.get_heuristic(), PartitioningHeuristic::FirstFit);
+        let cluster_logs = clustering.get_cluster_logs();
+        assert_eq!(cluster_logs[1].get_core_ids(), &vec![2, 3]);
+        assert_eq!(cluster_logs[0].get_dag_ids(), &vec![0, 1, 4]);
+        assert_eq!(cluster_logs[0].get_average_utilization(), 0.875);
+        assert_eq!(cluster_logs[1].get_average_utilization(), 0.625);
+    }
+
+    #[test]
+    fn test_clustered_scheduler_tie() {
+        // Utilizations 0.5 on two clusters of two cores. Ties are broken by the smallest index.
+        let dag_set = vec![
+            create_single_node_dag(4, 0),
+            create_single_node_dag(4, 1),
+            create_single_node_dag(4, 2),
+        ];
+        let get_cluster_ids =
+            |heuristic| assign_dag_set_to_clusters(&dag_set, &[2, 2], heuristic).cluster_ids;
+        assert_eq!(
+            get_cluster_ids(PartitioningHeuristic::BestFit),
+            vec![0, 0, 0]
+        );
+        assert_eq!(
+            get_cluster_ids(PartitioningHeuristic::WorstFit),
+            vec![0, 1, 0]
+        );
+    }
+
+    #[test]
+    fn test_clustered_scheduler_unfit() {
+        // A DAG of utilization 1.5 fits only in a cluster of two cores.
+        let dag_set = vec![create_single_node_dag(12, 0), create_single_node_dag(4, 1)];
+        let assignment =
+            assign_dag_set_to_clusters(&dag_set, &[1, 2], PartitioningHeuristic::FirstFit);
+        assert_eq!(assignment.cluster_ids, vec![1, 0]);
+        assert!(assignment.unfit_dag_ids.is_empty());
+
+        // On two clusters of one core, it is placed in cluster 0 (tie) and recorded as unfit.
+        let processor = ClusteredProcessor::new_with_cluster_sizes(&[1, 1]);
+        let mut scheduler = ClusteredScheduler::new(&dag_set, &processor);
+        assert_eq!(scheduler.get_assignment().cluster_ids, vec![0, 1]);
+        assert_eq!(scheduler.get_assignment().unfit_dag_ids, vec![0]);
+        scheduler.schedule(PreemptiveType::Preemptive {
+            key: "node_absolute_deadline".to_string(),
+        });
+        // DAG 0 cannot complete on the single core of cluster 0.
+        assert_eq!(
+            scheduler.get_log_mut().get_worst_response_times(),
+            vec![i32::MAX, 4]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "DAG 0 has no period.")]
+    fn test_clustered_scheduler_no_period() {
+        let mut dag = Graph::<NodeData, i32>::new();
+        dag.add_node(create_node(0, "execution_time", 1));
+        assign_dag_set_to_clusters(&[dag], &[1], PartitioningHeuristic::FirstFit);
+    }
+}
@@ -0,0 +1,316 @@
+//! Compact representation of very large DAGs for simulation loops.
+//!
+//! The petgraph DAGs keep a `BTreeMap` of params per node, which dominates the memory of DAGs
+//! with 100k+ nodes. `CompactDAG` keeps the params needed by the simulation in arrays indexed by
+//! the node index (structure of arrays), and the edges in compressed sparse row (CSR) form, so
+//! that the successors of a node are a contiguous slice. Loading, validation and analysis stay on
+//! the petgraph DAGs, which are converted with `CompactDAG::from_graph` and `to_graph`.
+//!
+//! The default `schedule` loops of `DAGSchedulerBase` and `DAGSetSchedulerBase` find the
+//! successors and the ready nodes of completed nodes through `CompactDAG` and `CompactReadiness`,
+//! while the nodes they dispatch are still those of the petgraph DAGs.
+//!
+//! The `compact_dag` benchmark compares the memory and a readiness pass of both representations
+//! on a layered DAG of 100k nodes (`cargo bench -p lib --bench compact_dag`).
+use crate::graph_extension::{GraphExtension, NodeData};
+use petgraph::graph::{Graph, NodeIndex};
+use std::collections::{BTreeMap, VecDeque};
+
+/// Priority of the nodes without the `priority` param.
+pub const NO_PRIORITY: i32 = i32::MAX;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CompactDAG {
+    node_ids: Vec<i32>,
+    execution_times: Vec<i32>,
+    /// `priority` param of each node, or `NO_PRIORITY`.
+    priorities: Vec<i32>,
+    period: Option<i32>,
+    end_to_end_deadline: Option<i32>,
+    offset: Option<i32>,
+    /// The successors of node `i` are `successors[successor_offsets[i]..successor_offsets[i + 1]]`.
+    successor_offsets: Vec<u32>,
+    successors: Vec<u32>,
+    /// Communication time of each edge, in the order of `successors`.
+    communication_times: Vec<i32>,
+    predecessor_counts: Vec<u32>,
+}
+
+impl CompactDAG {
+    /// The node indices of `dag` are kept, and the other params of the nodes are dropped.
+    pub fn from_graph(dag: &Graph<NodeData, i32>) -> Self {
+        let node_count = dag.node_count();
+        let mut successor_offsets = Vec::with_capacity(node_count + 1);
+        let mut successors = Vec::with_capacity(dag.edge_count());
+        let mut communication_times = Vec::with_capacity(dag.edge_count());
+        let mut predecessor_counts = vec![0; node_count];
+        successor_offsets.push(0);
+        for node_i in dag.node_indices() {
+            // In the order of `get_suc_nodes` (reverse order of insertion), so that the
+            // simulation loops find the ready nodes in the same order as on the petgraph DAG.
+            for edge in dag.edges(node_i) {
+                use petgraph::visit::EdgeRef;
+                let (target, communication_time) = (edge.target().index() as u32, *edge.weight());
+                successors.push(target);
+                communication_times.push(communication_time);
+                predecessor_counts[target as usize] += 1;
+            }
+            successor_offsets.push(successors.len() as u32);
+        }
+        let dag_params = dag.get_dag_params();
+        Self {
+            node_ids: dag.node_weights().map(|node| node.id).collect(),
+            execution_times: dag
+                .node_weights()
+                .map(|node| node.get_params_value("execution_time"))
+                .collect(),
+            priorities: dag
+                .node_weights()
+                .map(|node| node.params.get("priority").copied().unwrap_or(NO_PRIORITY))
+                .collect(),
+            period: dag_params.period,
+            end_to_end_deadline: dag_params.end_to_end_deadline,
+            offset: dag_params.offset,
+            successor_offsets,
+            successors,
+            communication_times,
+            predecessor_counts,
+        }
+    }
+
+    /// The petgraph DAG with the params kept by `from_graph`. The DAG-level params are on the
+    /// source (period, offset) and sink (end-to-end deadline) nodes.
+    pub fn to_graph(&self) -> Graph<NodeData, i32> {
+        let mut dag = Graph::<NodeData, i32>::with_capacity(self.node_count(), self.edge_count());
+        for node_i in 0..self.node_count() {
+            let mut params = BTreeMap::new();
+            params.insert("execution_time".to_string(), self.execution_times[node_i]);
+            if self.priorities[node_i] != NO_PRIORITY {
+                params.insert("priority".to_string(), self.priorities[node_i]);
+            }
+            dag.add_node(NodeData {
+                id: self.node_ids[node_i],
+                params,
+            });
+        }
+        for node_i in 0..self.node_count() {
+            let range = self.get_successor_range(node_i);
+            // petgraph lists the outgoing edges in reverse order of insertion.
+            for (&suc_i, &communication_time) in self.successors[range.clone()]
+                .iter()
+                .zip(&self.communication_times[range])
+                .rev()
+            {
+                dag.add_edge(
+                    NodeIndex::new(node_i),
+                    NodeIndex::new(suc_i as usize),
+                    communication_time,
+                );
+            }
+        }
+        for (key, value, nodes) in [
+            ("period", self.period, self.get_source_nodes()),
+            ("offset", self.offset, self.get_source_nodes()),
+            (
+                "end_to_end_deadline",
+                self.end_to_end_deadline,
+                self.get_sink_nodes(),
+            ),
+        ] {
+            if let Some(value) = value {
+                for node_i in nodes {
+                    dag.add_param(NodeIndex::new(node_i), key, value);
+                }
+            }
+        }
+        dag
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.node_ids.len()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.successors.len()
+    }
+
+    pub fn get_node_id(&self, node_i: usize) -> i32 {
+        self.node_ids[node_i]
+    }
+
+    pub fn get_execution_time(&self, node_i: usize) -> i32 {
+        self.execution_times[node_i]
+    }
+
+    pub fn get_priority(&self, node_i: usize) -> i32 {
+        self.priorities[node_i]
+    }
+
+    pub fn get_period(&self) -> Option<i32> {
+        self.period
+    }
+
+    pub fn get_end_to_end_deadline(&self) -> Option<i32> {
+        self.end_to_end_deadline
+    }
+
+    pub fn get_offset(&self) -> Option<i32> {
+        self.offset
+    }
+
+    fn get_successor_range(&self, node_i: usize) -> std::ops::Range<usize> {
+        self.successor_offsets[node_i] as usize..self.successor_offsets[node_i + 1] as usize
+    }
+
+    pub fn get_successors(&self, node_i: usize) -> &[u32] {
+        &self.successors[self.get_successor_range(node_i)]
+    }
+
+    pub fn get_predecessor_count(&self, node_i: usize) -> u32 {
+        self.predecessor_counts[node_i]
+    }
+
+    pub fn get_source_nodes(&self) -> Vec<usize> {
+        (0..self.node_count())
+            .filter(|&node_i| self.predecessor_counts[node_i] == 0)
+            .collect()
+    }
+
+    pub fn get_sink_nodes(&self) -> Vec<usize> {
+        (0..self.node_count())
+            .filter(|&node_i| self.get_successors(node_i).is_empty())
+            .collect()
+    }
+
+    pub fn get_volume(&self) -> i32 {
+        self.execution_times.iter().sum()
+    }
+
+    /// Length of the longest path in execution times, as `get_critical_path` with
+    /// `get_total_wcet_from_nodes` but without building the path.
+    pub fn get_critical_path_length(&self) -> i32 {
+        let mut readiness = CompactReadiness::new(self);
+        let mut finish_times = vec![0; self.node_count()];
+        let mut queue: VecDeque<usize> = self.get_source_nodes().into();
+        while let Some(node_i) = queue.pop_front() {
+            finish_times[node_i] += self.execution_times[node_i];
+            for &suc_i in self.get_successors(node_i) {
+                let suc_i = suc_i as usize;
+                finish_times[suc_i] = finish_times[suc_i].max(finish_times[node_i]);
+            }
+            queue.extend(readiness.complete(self, node_i));
+        }
+        finish_times.into_iter().max().unwrap_or(0)
+    }
+
+    /// Approximate heap memory of the arrays in bytes.
+    pub fn get_heap_size(&self) -> usize {
+        use std::mem::size_of;
+        self.node_ids.capacity() * size_of::<i32>()
+            + self.execution_times.capacity() * size_of::<i32>()
+            + self.priorities.capacity() * size_of::<i32>()
+            + self.successor_offsets.capacity() * size_of::<u32>()
+            + self.successors.capacity() * size_of::<u32>()
+            + self.communication_times.capacity() * size_of::<i32>()
+            + self.predecessor_counts.capacity() * size_of::<u32>()
+    }
+}
+
+/// Remaining predecessors of each node of a job of a `CompactDAG`, which replaces the
+/// `pre_done_count` params of the petgraph DAGs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CompactReadiness {
+    remaining_predecessors: Vec<u32>,
+}
+
+impl CompactReadiness {
+    pub fn new(dag: &CompactDAG) -> Self {
+        Self {
+            remaining_predecessors: dag.predecessor_counts.clone(),
+        }
+    }
+
+    /// Reset for the next job.
+    pub fn reset(&mut self, dag: &CompactDAG) {
+        self.remaining_predecessors
+            .copy_from_slice(&dag.predecessor_counts);
+    }
+
+    pub fn is_ready(&self, node_i: usize) -> bool {
+        self.remaining_predecessors[node_i] == 0
+    }
+
+    /// Complete `node_i` and return its successors that became ready.
+    pub fn complete<'a>(
+        &'a mut self,
+        dag: &'a CompactDAG,
+        node_i: usize,
+    ) -> impl Iterator<Item = usize> + 'a {
+        dag.get_successors(node_i).iter().filter_map(move |&suc_i| {
+            let remaining = &mut self.remaining_predecessors[suc_i as usize];
+            *remaining -= 1;
+            (*remaining == 0).then_some(suc_i as usize)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::create_node;
+
+    fn create_sample_dag() -> Graph<NodeData, i32> {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 2));
+        let n1 = dag.add_node(create_node(1, "execution_time", 5));
+        let n2 = dag.add_node(create_node(2, "execution_time", 3));
+        let n3 = dag.add_node(create_node(3, "execution_time", 1));
+        dag.add_param(n0, "period", 20);
+        dag.add_param(n0, "offset", 4);
+        dag.add_param(n1, "priority", 0);
+        dag.add_param(n3, "end_to_end_deadline", 15);
+        dag.add_edge(n0, n1, 2);
+        dag.add_edge(n0, n2, 1);
+        dag.add_edge(n1, n3, 1);
+        dag.add_edge(n2, n3, 3);
+        dag
+    }
+
+    #[test]
+    fn test_compact_dag_from_graph_normal() {
+        let compact_dag = CompactDAG::from_graph(&create_sample_dag());
+        assert_eq!(compact_dag.node_count(), 4);
+        assert_eq!(compact_dag.edge_count(), 4);
+        assert_eq!(compact_dag.get_successors(0), &[2, 1]);
+        assert_eq!(compact_dag.get_predecessor_count(3), 2);
+        assert_eq!(compact_dag.get_priority(1), 0);
+        assert_eq!(compact_dag.get_priority(2), NO_PRIORITY);
+        assert_eq!(compact_dag.get_period(), Some(20));
+        assert_eq!(compact_dag.get_end_to_end_deadline(), Some(15));
+        assert_eq!(compact_dag.get_offset(), Some(4));
+        assert_eq!(compact_dag.get_volume(), 11);
+        assert_eq!(compact_dag.get_critical_path_length(), 8);
+
+        // The round trip keeps the structure and the simulated params.
+        assert_eq!(CompactDAG::from_graph(&compact_dag.to_graph()), compact_dag);
+    }
+
+    #[test]
+    fn test_compact_readiness_normal() {
+        let compact_dag = CompactDAG::from_graph(&create_sample_dag());
+        let mut readiness = CompactReadiness::new(&compact_dag);
+        assert_eq!(
+            readiness.complete(&compact_dag, 0).collect::<Vec<_>>(),
+            vec![2, 1]
+        );
+        assert!(readiness.is_ready(1));
+        assert!(!readiness.is_ready(3));
+        assert_eq!(readiness.complete(&compact_dag, 1).count(), 0);
+        assert_eq!(
+            readiness.complete(&compact_dag, 2).collect::<Vec<_>>(),
+            vec![3]
+        );
+        readiness.reset(&compact_dag);
+        assert_eq!(readiness, CompactReadiness::new(&compact_dag));
+    }
+}
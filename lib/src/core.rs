@@ -18,6 +18,8 @@ pub struct Core {
     #[get = "pub with_prefix"]
     pub processing_node: Option<NodeData>,
     pub remain_proc_time: i32,
+    /// Time the node has run since it was allocated, i.e., started or resumed.
+    pub run_time: i32,
 }
 
 impl Default for Core {
@@ -26,6 +28,7 @@ impl Default for Core {
             is_idle: true,
             processing_node: None,
             remain_proc_time: 0,
+            run_time: 0,
         }
     }
 }
@@ -39,6 +42,7 @@ impl Core {
         }
         self.is_idle = false;
         self.processing_node = Some(node_data.clone());
+        self.run_time = 0;
         if let Some(exec_time) = node_data.params.get("execution_time") {
             self.remain_proc_time = *exec_time;
             true
@@ -49,10 +53,16 @@ impl Core {
     }
 
     pub fn process(&mut self) -> ProcessResult {
+        self.process_for(1)
+    }
+
+    /// Process `time` units at once. `time` must not exceed the remaining processing time.
+    pub fn process_for(&mut self, time: i32) -> ProcessResult {
         if self.is_idle {
             return Idle;
         }
-        self.remain_proc_time -= 1;
+        self.remain_proc_time -= time;
+        self.run_time += time;
         if self.remain_proc_time == 0 {
             self.is_idle = true;
             let finish_node_data = self.processing_node.clone().unwrap();
@@ -82,13 +92,7 @@ impl Core {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::BTreeMap;
-
-    fn create_node(id: i32, key: &str, value: i32) -> NodeData {
-        let mut params = BTreeMap::new();
-        params.insert(key.to_string(), value);
-        NodeData { id, params }
-    }
+    use crate::test_util::create_node;
 
     #[test]
     fn test_core_default_params() {
@@ -133,6 +137,19 @@ mod tests {
         assert_eq!(core.remain_proc_time, 8);
     }
 
+    #[test]
+    fn test_core_process_for_normal() {
+        let mut core = Core::default();
+        core.allocate(&create_node(0, "execution_time", 10));
+        assert_eq!(core.process_for(4), Continue);
+        assert_eq!(core.remain_proc_time, 6);
+        assert_eq!(
+            core.process_for(6),
+            Done(create_node(0, "execution_time", 10))
+        );
+        assert!(core.is_idle);
+    }
+
     #[test]
     fn test_core_process_no_allocated() {
         let mut core = Core::default();
@@ -0,0 +1,188 @@
+//! Recommend the number of cores for a DAG set.
+use crate::dag_task::{DagTask, UtilizationClass};
+use crate::federated_scheduler::get_dedicated_core_count;
+use crate::graph_extension::{GraphExtension, NodeData};
+use crate::util::sum_in_fixed_order;
+use petgraph::Graph;
+use serde_derive::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct CoreRecommendation {
+    /// Necessary condition: the total utilization must not exceed the number of cores.
+    by_utilization_bound: usize,
+    /// Sufficient condition by the federated scheduling test (implicit deadlines).
+    /// `None` if a critical path is longer than its period.
+    by_federated_test: Option<usize>,
+    /// Fewest cores with which the simulation met all deadlines.
+    /// `None` if the simulation failed even with the upper bound of the search.
+    by_simulation: Option<usize>,
+}
+
+impl CoreRecommendation {
+    pub fn get_by_utilization_bound(&self) -> usize {
+        self.by_utilization_bound
+    }
+
+    pub fn get_by_federated_test(&self) -> Option<usize> {
+        self.by_federated_test
+    }
+
+    pub fn get_by_simulation(&self) -> Option<usize> {
+        self.by_simulation
+    }
+}
+
+/// Minimum number of cores satisfying the utilization bound (at least one).
+pub fn get_cores_by_utilization_bound(dag_set: &[Graph<NodeData, i32>]) -> usize {
+    let total_utilization = sum_in_fixed_order(
+        dag_set
+            .iter()
+            .map(|dag| dag.get_volume() as f32 / dag.get_head_period().unwrap() as f32),
+    );
+    (total_utilization.ceil() as usize).max(1)
+}
+
+/// Minimum number of cores with which the federated scheduling test succeeds.
+///
+/// Each heavy DAG (see `UtilizationClass::classify`) receives its dedicated cores as in
+/// `FederatedScheduler`, and the light DAGs share the remaining cores, which must exceed twice
+/// their total utilization. `None` if a heavy DAG cannot meet its deadline on dedicated cores.
+pub fn get_cores_by_federated_test(dag_set: &[Graph<NodeData, i32>]) -> Option<usize> {
+    let mut heavy_dedicated_cores = 0;
+    let mut light_utilizations = 0.0;
+    for (dag_id, dag) in dag_set.iter().enumerate() {
+        match UtilizationClass::classify(dag)
+            .unwrap_or_else(|| panic!("DAG {} has no period.", dag_id))
+        {
+            UtilizationClass::Heavy => heavy_dedicated_cores += get_dedicated_core_count(dag)?,
+            UtilizationClass::Light => {
+                light_utilizations += DagTask::from_graph(dag).get_utilization().unwrap()
+            }
+        }
+    }
+    Some(heavy_dedicated_cores + (2.0 * light_utilizations).floor() as usize + 1)
+}
+
+/// Fewest cores in `[lower_bound, upper_bound]` for which `is_schedulable` holds (binary search).
+///
+/// The search assumes that adding cores never breaks schedulability, which does not hold for
+/// every scheduler (scheduling anomalies), so the result is a recommendation rather than a bound.
+pub fn search_cores_by_simulation(
+    lower_bound: usize,
+    upper_bound: usize,
+    mut is_schedulable: impl FnMut(usize) -> bool,
+) -> Option<usize> {
+    if lower_bound > upper_bound || !is_schedulable(upper_bound) {
+        return None;
+    }
+    let (mut low, mut high) = (lower_bound, upper_bound);
+    while low < high {
+        let middle = (low + high) / 2;
+        if is_schedulable(middle) {
+            high = middle;
+        } else {
+            low = middle + 1;
+        }
+    }
+    Some(high)
+}
+
+/// Recommend the number of cores for `dag_set`.
+///
+/// `is_schedulable` simulates the DAG set on the given number of cores.
+/// The simulation is searched between the utilization bound and the federated bound
+/// (or the total number of nodes if the federated test is not applicable).
+pub fn recommend_cores(
+    dag_set: &[Graph<NodeData, i32>],
+    is_schedulable: impl FnMut(usize) -> bool,
+) -> CoreRecommendation {
+    let by_utilization_bound = get_cores_by_utilization_bound(dag_set);
+    let by_federated_test = get_cores_by_federated_test(dag_set);
+    let upper_bound = by_federated_test
+        .unwrap_or_else(|| dag_set.iter().map(|dag| dag.node_count()).sum())
+        .max(by_utilization_bound);
+    CoreRecommendation {
+        by_utilization_bound,
+        by_federated_test,
+        by_simulation: search_cores_by_simulation(
+            by_utilization_bound,
+            upper_bound,
+            is_schedulable,
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::create_node;
+
+    fn create_high_utilization_dag() -> Graph<NodeData, i32> {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 4));
+        let n1 = dag.add_node(create_node(1, "execution_time", 4));
+        let n2 = dag.add_node(create_node(2, "execution_time", 3));
+        let n3 = dag.add_node(create_node(3, "execution_time", 3));
+        dag.add_param(n0, "period", 10);
+        dag.add_edge(n0, n1, 1);
+        dag.add_edge(n0, n2, 1);
+        dag.add_edge(n0, n3, 1);
+
+        dag
+    }
+
+    fn create_low_utilization_dag() -> Graph<NodeData, i32> {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 3));
+        let n1 = dag.add_node(create_node(1, "execution_time", 3));
+        dag.add_param(n0, "period", 30);
+        dag.add_edge(n0, n1, 1);
+
+        dag
+    }
+
+    #[test]
+    fn test_get_cores_by_utilization_bound_normal() {
+        let dag_set = vec![create_high_utilization_dag(), create_low_utilization_dag()];
+        // 1.4 + 0.2
+        assert_eq!(get_cores_by_utilization_bound(&dag_set), 2);
+    }
+
+    #[test]
+    fn test_get_cores_by_federated_test_normal() {
+        let dag_set = vec![
+            create_high_utilization_dag(),
+            create_high_utilization_dag(),
+            create_low_utilization_dag(),
+        ];
+        // 3 cores for each high-utilization DAG and floor(2 * 0.2) + 1 for the low one.
+        assert_eq!(get_cores_by_federated_test(&dag_set), Some(7));
+    }
+
+    #[test]
+    fn test_search_cores_by_simulation_normal() {
+        assert_eq!(
+            search_cores_by_simulation(1, 10, |cores| cores >= 4),
+            Some(4)
+        );
+        assert_eq!(search_cores_by_simulation(1, 10, |_| false), None);
+    }
+
+    #[test]
+    fn test_recommend_cores_normal() {
+        let dag_set = vec![create_high_utilization_dag(), create_low_utilization_dag()];
+        let mut simulated_cores = Vec::new();
+        let recommendation = recommend_cores(&dag_set, |cores| {
+            simulated_cores.push(cores);
+            cores >= 3
+        });
+
+        assert_eq!(recommendation.get_by_utilization_bound(), 2);
+        assert_eq!(recommendation.get_by_federated_test(), Some(4));
+        assert_eq!(recommendation.get_by_simulation(), Some(3));
+        // The search never goes outside the bounds.
+        assert!(simulated_cores
+            .iter()
+            .all(|&cores| (2..=4).contains(&cores)));
+    }
+}
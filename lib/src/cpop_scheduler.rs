@@ -0,0 +1,244 @@
+//! Critical-Path-On-a-Processor (CPOP) list scheduling of a DAG on cores of different speeds
+//! (Topcuoglu et al., TPDS 2002).
+//!
+//! The priority of a node is the sum of its upward and downward ranks, computed with the cost of
+//! each node averaged over the cores. The nodes whose priority equals that of the source form the
+//! critical path, which is pinned to the core executing it fastest. The other nodes go to the core
+//! giving the earliest finish time (EFT). A node takes `ceil(execution_time / speed)` on a core
+//! as in `SpeedModel`, and is appended after the last node of its core without being inserted
+//! into idle gaps. Communication times are ignored.
+use crate::{
+    graph_extension::{GraphExtension, NodeData, SpeedModel},
+    log::DAGSchedulerLog,
+};
+use petgraph::{algo::toposort, graph::Graph, graph::NodeIndex};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet};
+
+pub struct CPOPScheduler {
+    dag: Graph<NodeData, i32>,
+    core_speeds: Vec<f32>,
+    log: DAGSchedulerLog,
+    /// Core of each node (by id), decided by `schedule`.
+    assignment: BTreeMap<i32, usize>,
+}
+
+impl CPOPScheduler {
+    pub fn new(dag: &Graph<NodeData, i32>, core_speeds: &[f32]) -> Self {
+        if core_speeds.is_empty() {
+            panic!("CPOP needs at least one core.");
+        }
+        if let Some(speed) = core_speeds.iter().find(|speed| **speed <= 0.0) {
+            panic!("The speed of a core must be positive: {}", speed);
+        }
+        Self {
+            dag: dag.clone(),
+            core_speeds: core_speeds.to_vec(),
+            log: DAGSchedulerLog::new(dag, core_speeds.len()),
+            assignment: BTreeMap::new(),
+        }
+    }
+
+    fn get_execution_time(&self, node_i: NodeIndex, core_i: usize) -> i32 {
+        SpeedModel::Fixed(self.core_speeds[core_i]).get_execution_time(&self.dag[node_i])
+    }
+
+    /// The cost summed over the cores, i.e., the average cost scaled by the number of cores,
+    /// so that the ranks are compared exactly.
+    fn get_total_cost(&self, node_i: NodeIndex) -> i32 {
+        (0..self.core_speeds.len())
+            .map(|core_i| self.get_execution_time(node_i, core_i))
+            .sum()
+    }
+
+    /// The upward rank plus the downward rank of each node, indexed by node index.
+    pub fn get_priorities(&self) -> Vec<i32> {
+        let order = toposort(&self.dag, None).expect("CPOP needs an acyclic DAG.");
+        let mut upward_ranks = vec![0; self.dag.node_count()];
+        for &node_i in order.iter().rev() {
+            let max_suc_rank = self
+                .dag
+                .get_suc_nodes(node_i)
+                .unwrap_or_default()
+                .iter()
+                .map(|suc_i| upward_ranks[suc_i.index()])
+                .max()
+                .unwrap_or(0);
+            upward_ranks[node_i.index()] = self.get_total_cost(node_i) + max_suc_rank;
+        }
+        let mut downward_ranks = vec![0; self.dag.node_count()];
+        for &node_i in &order {
+            downward_ranks[node_i.index()] = self
+                .dag
+                .get_pre_nodes(node_i)
+                .unwrap_or_default()
+                .iter()
+                .map(|pre_i| downward_ranks[pre_i.index()] + self.get_total_cost(*pre_i))
+                .max()
+                .unwrap_or(0);
+        }
+        upward_ranks
+            .iter()
+            .zip(&downward_ranks)
+            .map(|(upward_rank, downward_rank)| upward_rank + downward_rank)
+            .collect()
+    }
+
+    /// Follow the nodes with the highest priority from the source with the highest priority.
+    pub fn get_critical_path_nodes(&self) -> Vec<NodeIndex> {
+        let priorities = self.get_priorities();
+        let get_highest = |nodes: Vec<NodeIndex>| {
+            nodes
+                .into_iter()
+                .max_by_key(|node_i| (priorities[node_i.index()], Reverse(node_i.index())))
+        };
+        let mut critical_path = Vec::new();
+        let mut node = get_highest(self.dag.get_source_nodes());
+        while let Some(node_i) = node {
+            critical_path.push(node_i);
+            node = get_highest(self.dag.get_suc_nodes(node_i).unwrap_or_default());
+        }
+        critical_path
+    }
+
+    /// Schedule the DAG once.
+    ///
+    /// # Returns
+    ///
+    /// * The schedule length.
+    pub fn schedule(&mut self) -> i32 {
+        let priorities = self.get_priorities();
+        let critical_path = self.get_critical_path_nodes();
+        let critical_path_core = (0..self.core_speeds.len())
+            .min_by_key(|&core_i| {
+                critical_path
+                    .iter()
+                    .map(|&node_i| self.get_execution_time(node_i, core_i))
+                    .sum::<i32>()
+            })
+            .unwrap();
+
+        let mut core_ready_times = vec![0; self.core_speeds.len()];
+        let mut finish_times = vec![0; self.dag.node_count()];
+        let mut pre_done_counts = vec![0; self.dag.node_count()];
+        let mut ready_queue: BTreeSet<(Reverse<i32>, NodeIndex)> = self
+            .dag
+            .get_source_nodes()
+            .into_iter()
+            .map(|node_i| (Reverse(priorities[node_i.index()]), node_i))
+            .collect();
+        let mut schedule_length = 0;
+        while let Some((_, node_i)) = ready_queue.pop_first() {
+            let data_ready_time = self
+                .dag
+                .get_pre_nodes(node_i)
+                .unwrap_or_default()
+                .iter()
+                .map(|pre_i| finish_times[pre_i.index()])
+                .max()
+                .unwrap_or(0);
+            let get_finish_time = |core_i: usize| {
+                core_ready_times[core_i].max(data_ready_time)
+                    + self.get_execution_time(node_i, core_i)
+            };
+            let core_i = if critical_path.contains(&node_i) {
+                critical_path_core
+            } else {
+                (0..self.core_speeds.len())
+                    .min_by_key(|&core_i| (get_finish_time(core_i), core_i))
+                    .unwrap()
+            };
+            let execution_time = self.get_execution_time(node_i, core_i);
+            let finish_time = get_finish_time(core_i);
+            let node_data = self.dag[node_i].clone();
+            self.log
+                .write_allocating_job(&node_data, core_i, finish_time - execution_time);
+            self.log
+                .write_processing_time_for(&[core_i], execution_time);
+            self.log
+                .write_finishing_job(&node_data, core_i, finish_time);
+            self.assignment.insert(node_data.id, core_i);
+            core_ready_times[core_i] = finish_time;
+            finish_times[node_i.index()] = finish_time;
+            schedule_length = schedule_length.max(finish_time);
+
+            for suc_i in self.dag.get_suc_nodes(node_i).unwrap_or_default() {
+                pre_done_counts[suc_i.index()] += 1;
+                if pre_done_counts[suc_i.index()]
+                    == self.dag.get_pre_nodes(suc_i).unwrap_or_default().len()
+                {
+                    ready_queue.insert((Reverse(priorities[suc_i.index()]), suc_i));
+                }
+            }
+        }
+
+        self.log.calculate_utilization(schedule_length);
+        schedule_length
+    }
+
+    pub fn get_log(&self) -> DAGSchedulerLog {
+        self.log.clone()
+    }
+
+    pub fn get_assignment(&self) -> &BTreeMap<i32, usize> {
+        &self.assignment
+    }
+
+    /// The speed of the core each node is assigned to, for the metrics of the log.
+    pub fn get_speed_model(&self) -> SpeedModel {
+        SpeedModel::Assignment(
+            self.assignment
+                .iter()
+                .map(|(&node_id, &core_i)| (node_id, self.core_speeds[core_i]))
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::create_node;
+
+    /// n0 -> {n1, n2} -> n3, whose critical path is n0 -> n1 -> n3.
+    fn create_sample_dag() -> Graph<NodeData, i32> {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 4));
+        let n1 = dag.add_node(create_node(1, "execution_time", 4));
+        let n2 = dag.add_node(create_node(2, "execution_time", 2));
+        let n3 = dag.add_node(create_node(3, "execution_time", 2));
+        dag.add_edge(n0, n1, 1);
+        dag.add_edge(n0, n2, 1);
+        dag.add_edge(n1, n3, 1);
+        dag.add_edge(n2, n3, 1);
+        dag
+    }
+
+    #[test]
+    fn test_cpop_get_priorities_normal() {
+        let cpop_scheduler = CPOPScheduler::new(&create_sample_dag(), &[1.0, 2.0]);
+        assert_eq!(cpop_scheduler.get_priorities(), vec![15, 15, 12, 15]);
+        assert_eq!(
+            cpop_scheduler.get_critical_path_nodes(),
+            vec![NodeIndex::new(0), NodeIndex::new(1), NodeIndex::new(3)]
+        );
+    }
+
+    #[test]
+    fn test_cpop_schedule_normal() {
+        let mut cpop_scheduler = CPOPScheduler::new(&create_sample_dag(), &[1.0, 2.0]);
+        // The critical path runs on the fast core 1, and n2 finishes earlier on core 0.
+        assert_eq!(cpop_scheduler.schedule(), 5);
+        assert_eq!(
+            cpop_scheduler.get_assignment(),
+            &BTreeMap::from([(0, 1), (1, 1), (2, 0), (3, 1)])
+        );
+        assert_eq!(cpop_scheduler.get_speed_model().get_speed(2), 1.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_cpop_new_no_core() {
+        CPOPScheduler::new(&create_sample_dag(), &[]);
+    }
+}
@@ -1,10 +1,16 @@
 //! Generate a petgraph DAG object from a yaml file
-use crate::graph_extension::{GraphExtension, NodeData};
+//!
+//! The yaml files are in the node-link format of RD-Gen, whose property names are mapped to the
+//! internal params.
+use crate::graph_extension::{DAGNames, GraphExtension, NodeData, NodeIndexMap, StructureIssue};
+use crate::log::{DAGQuantization, QuantizationLog};
 use crate::util::load_yaml;
+pub use crate::util::validate_dag_ids;
 
 use log::warn;
-use petgraph::{graph::Graph, prelude::*};
-use std::{collections::BTreeMap, path::PathBuf};
+use petgraph::graph::Graph;
+use serde_derive::{Deserialize, Serialize};
+use std::{collections::BTreeMap, path::PathBuf, str::FromStr};
 use yaml_rust::Yaml;
 
 fn get_minimum_decimal_places(yaml: &Yaml) -> usize {
@@ -43,6 +49,168 @@ fn get_minimum_decimal_places(yaml: &Yaml) -> usize {
     minimum_decimal_places
 }
 
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RoundingMode {
+    /// Round the execution times up, which never underestimates the demand.
+    #[default]
+    Ceil,
+    Floor,
+    Nearest,
+}
+
+impl RoundingMode {
+    fn round(self, value: f64) -> f64 {
+        match self {
+            Self::Ceil => value.ceil(),
+            Self::Floor => value.floor(),
+            Self::Nearest => value.round(),
+        }
+    }
+
+    fn reverse(self) -> Self {
+        match self {
+            Self::Ceil => Self::Floor,
+            Self::Floor => Self::Ceil,
+            Self::Nearest => Self::Nearest,
+        }
+    }
+}
+
+impl FromStr for RoundingMode {
+    type Err = String;
+
+    fn from_str(rounding_mode: &str) -> Result<Self, Self::Err> {
+        match rounding_mode.to_lowercase().as_str() {
+            "ceil" => Ok(Self::Ceil),
+            "floor" => Ok(Self::Floor),
+            "nearest" => Ok(Self::Nearest),
+            _ => Err(format!("Unknown rounding mode: {}", rounding_mode)),
+        }
+    }
+}
+
+/// Params bounding the time of a DAG. They are rounded in the reverse direction of the
+/// rounding mode, so that ceil also never overestimates the time available.
+const TIMING_CONSTRAINT_KEYS: [&str; 3] = ["period", "end_to_end_deadline", "max_period"];
+
+/// Convert the times of the YAML files (integer or float) to integer multiples of `time_quantum`.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TimeQuantization {
+    pub time_quantum: f64,
+    pub rounding_mode: RoundingMode,
+}
+
+impl TimeQuantization {
+    pub fn new(time_quantum: f64, rounding_mode: RoundingMode) -> Self {
+        if time_quantum <= 0.0 {
+            panic!(
+                "The time quantum must be positive, but is {}.",
+                time_quantum
+            );
+        }
+        Self {
+            time_quantum,
+            rounding_mode,
+        }
+    }
+
+    pub fn quantize(&self, key: &str, value: f64) -> i32 {
+        let quanta = value / self.time_quantum;
+        // Values that are multiples of the quantum up to the float error, e.g., 0.3 / 0.1.
+        if (quanta - quanta.round()).abs() < 1e-9 {
+            return quanta.round() as i32;
+        }
+        let rounding_mode = if TIMING_CONSTRAINT_KEYS.contains(&key) {
+            self.rounding_mode.reverse()
+        } else {
+            self.rounding_mode
+        };
+        rounding_mode.round(quanta) as i32
+    }
+}
+
+/// Params that RD-Gen writes with its own property names, e.g., `Execution_time` or
+/// `End-to-end deadline` for `execution_time` or `end_to_end_deadline`.
+/// `elasticity` and `max_period` are the params of the elastic scheduling (see `elastic_scheduler`).
+const RD_GEN_PARAM_KEYS: [&str; 7] = [
+    "execution_time",
+    "communication_time",
+    "period",
+    "end_to_end_deadline",
+    "offset",
+    "elasticity",
+    "max_period",
+];
+
+/// The internal name of the param `key`. The other params keep their name.
+fn get_param_key(key: &str) -> String {
+    let normalized_key = key.trim().to_lowercase().replace([' ', '-'], "_");
+    if RD_GEN_PARAM_KEYS.contains(&normalized_key.as_str()) {
+        normalized_key
+    } else {
+        key.to_string()
+    }
+}
+
+/// The value of the param `key` in a node or link of a yaml file.
+fn get_param_value<'a>(yaml: &'a Yaml, key: &str) -> Option<&'a Yaml> {
+    yaml.as_hash()?
+        .iter()
+        .find_map(|(yaml_key, value)| (get_param_key(yaml_key.as_str()?) == key).then_some(value))
+}
+
+/// Utilization (`volume / period`) of the DAG in `yaml_doc` before quantization.
+fn get_yaml_utilization(yaml_doc: &Yaml) -> f32 {
+    let nodes = yaml_doc["nodes"]
+        .as_vec()
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    let as_f64 = |value: &Yaml| value.as_f64().or(value.as_i64().map(|int| int as f64));
+    let volume: f64 = nodes
+        .iter()
+        .filter_map(|node| as_f64(get_param_value(node, "execution_time")?))
+        .sum();
+    match nodes
+        .iter()
+        .find_map(|node| as_f64(get_param_value(node, "period")?))
+    {
+        Some(period) if period > 0.0 => (volume / period) as f32,
+        _ => 0.0,
+    }
+}
+
+/// Report the structural issues and the conflicting DAG-level params of `dag`,
+/// and remove duplicate edges if `fix_duplicate_edges`.
+///
+/// # Returns
+///
+/// * The issues found before fixing.
+pub fn validate_dag_structure(
+    dag: &mut Graph<NodeData, i32>,
+    fix_duplicate_edges: bool,
+) -> Vec<StructureIssue> {
+    let issues = dag.lint_structure();
+    for issue in issues.iter() {
+        warn!("Malformed DAG structure: {:?}", issue);
+    }
+    for conflict in dag.get_param_conflicts() {
+        warn!(
+            "Conflicting DAG-level param {}: {:?}. The first is used.",
+            conflict.key, conflict.values
+        );
+    }
+    if fix_duplicate_edges
+        && issues
+            .iter()
+            .any(|issue| matches!(issue, StructureIssue::DuplicateEdge { .. }))
+    {
+        let removed_edges = dag.remove_duplicate_edges();
+        warn!("{} duplicate edges were removed.", removed_edges);
+    }
+    issues
+}
+
 /// load yaml file and return a dag object (petgraph)
 ///
 /// # Arguments
@@ -79,9 +247,49 @@ pub fn create_dag_from_yaml(file_path: &str, exist_other_float_dag: bool) -> Gra
         int_conversion_factor = 100000;
     }
 
+    create_dag_from_yaml_doc(yaml_doc, |_key, value| match value {
+        Yaml::Integer(integer) => (integer * int_conversion_factor as i64) as i32,
+        Yaml::Real(_) => (value.as_f64().unwrap() * int_conversion_factor as f64).round() as i32,
+        _ => panic!("Unknown type: {}", std::any::type_name::<Yaml>()),
+    })
+}
+
+/// Load a yaml file with the times converted by `quantization`, and return the DAG with its
+/// utilization before and after the quantization.
+pub fn create_dag_from_yaml_with_quantization(
+    file_path: &str,
+    quantization: &TimeQuantization,
+) -> (Graph<NodeData, i32>, DAGQuantization) {
+    let yaml_docs = load_yaml(file_path);
+    let yaml_doc = &yaml_docs[0];
+    let dag = create_dag_from_yaml_doc(yaml_doc, |key, value| match value {
+        Yaml::Integer(integer) => quantization.quantize(key, *integer as f64),
+        Yaml::Real(_) => quantization.quantize(key, value.as_f64().unwrap()),
+        _ => panic!("Unknown type: {}", std::any::type_name::<Yaml>()),
+    });
+    let quantized_utilization = match dag.get_head_period() {
+        Some(period) if period > 0 => dag.get_volume() as f32 / period as f32,
+        _ => 0.0,
+    };
+    (
+        dag,
+        DAGQuantization {
+            original_utilization: get_yaml_utilization(yaml_doc),
+            quantized_utilization,
+        },
+    )
+}
+
+/// Build the DAG of `yaml_doc`, converting each numeric param and communication time with
+/// `convert(key, value)`.
+fn create_dag_from_yaml_doc(
+    yaml_doc: &Yaml,
+    convert: impl Fn(&str, &Yaml) -> i32,
+) -> Graph<NodeData, i32> {
     // Check if nodes and links fields exist
     if let (Some(nodes), Some(links)) = (yaml_doc["nodes"].as_vec(), yaml_doc["links"].as_vec()) {
         let mut dag = Graph::<NodeData, i32>::new();
+        let mut node_index_map = NodeIndexMap::default();
 
         // add nodes to dag
         for node in nodes {
@@ -90,62 +298,39 @@ pub fn create_dag_from_yaml(file_path: &str, exist_other_float_dag: bool) -> Gra
 
             // add node parameters to BTreeMap
             for (key, value) in node.as_hash().unwrap() {
-                let key_str = key.as_str().unwrap();
-                if key_str != "id" {
-                    match value {
-                        Yaml::Integer(_i) => {
-                            params.insert(
-                                key_str.to_owned(),
-                                (value.as_i64().unwrap() * int_conversion_factor as i64) as i32,
-                            );
-                        }
-                        Yaml::Real(_r) => {
-                            params.insert(
-                                key_str.to_owned(),
-                                (value.as_f64().unwrap() * int_conversion_factor as f64).round()
-                                    as i32,
-                            );
-                        }
-                        _ => {
-                            panic!("Unknown type: {}", std::any::type_name::<Yaml>());
-                        }
-                    }
+                let key = get_param_key(key.as_str().unwrap());
+                // Names are read by `create_dag_names_from_yaml`.
+                if key != "id" && key != "name" {
+                    params.insert(key.clone(), convert(&key, value));
                 }
             }
-            dag.add_node(NodeData { id, params });
+            node_index_map.add_node(&mut dag, NodeData { id, params });
         }
 
-        // add edges to dag
+        // add edges to dag, whose ends are given by node id
+        let get_node_index = |link: &Yaml, key: &str| {
+            let node_id = link[key].as_i64().unwrap() as i32;
+            node_index_map
+                .get(node_id)
+                .unwrap_or_else(|| panic!("The {} of a link is not a node: {}", key, node_id))
+        };
         for link in links {
-            let source = link["source"].as_i64().unwrap() as usize;
-            let target = link["target"].as_i64().unwrap() as usize;
-            let mut communication_time = 0;
-
-            match &link["communication_time"] {
-                Yaml::Integer(communication_time_value) => {
-                    communication_time = *communication_time_value as i32 * int_conversion_factor;
-                }
-                Yaml::Real(communication_time_value) => {
-                    communication_time = (communication_time_value.parse::<f32>().unwrap()
-                        * int_conversion_factor as f32)
-                        as i32;
-                }
-                Yaml::BadValue => {}
-                _ => unreachable!(),
-            }
-            dag.add_edge(
-                NodeIndex::new(source),
-                NodeIndex::new(target),
-                communication_time,
-            );
+            let source = get_node_index(link, "source");
+            let target = get_node_index(link, "target");
+            let communication_time = match get_param_value(link, "communication_time") {
+                None => 0,
+                Some(value) => convert("communication_time", value),
+            };
+            dag.add_edge(source, target, communication_time);
         }
+        validate_dag_structure(&mut dag, true);
         dag
     } else {
         panic!("YAML files are not DAG structures.");
     }
 }
 
-fn get_yaml_paths_from_dir(dir_path: &str) -> Vec<String> {
+pub fn get_yaml_paths_from_dir(dir_path: &str) -> Vec<String> {
     if !std::fs::metadata(dir_path).unwrap().is_dir() {
         panic!("Not a directory");
     }
@@ -183,25 +368,149 @@ fn get_yaml_paths_from_dir(dir_path: &str) -> Vec<String> {
 /// let first_node_exe_time = dag_set[0][dag_set[0].node_indices().next().unwrap()].params["execution_time"];
 /// ```
 pub fn create_dag_set_from_dir(dir_path: &str) -> Vec<Graph<NodeData, i32>> {
-    let mut file_path_list = get_yaml_paths_from_dir(dir_path);
-    file_path_list.sort();
-    let exist_float_dag = get_yaml_paths_from_dir(dir_path).iter().any(|file_path| {
+    create_dag_set_from_dirs(&[dir_path])
+}
+
+/// Load the yaml files of several directories as a single DAGSet.
+///
+/// The DAGs are ordered by directory in the given order, then by file name, and `dag_id` is
+/// numbered through all directories.
+pub fn create_dag_set_from_dirs(dir_paths: &[&str]) -> Vec<Graph<NodeData, i32>> {
+    let file_path_list = get_sorted_yaml_paths_from_dirs(dir_paths);
+    let exist_float_dag = file_path_list.iter().any(|file_path| {
         let yaml_doc = &load_yaml(file_path)[0];
         get_minimum_decimal_places(yaml_doc) > 0
     });
-    let mut dag_set: Vec<Graph<NodeData, i32>> = Vec::new();
+    let mut dag_set: Vec<Graph<NodeData, i32>> = file_path_list
+        .iter()
+        .map(|file_path| {
+            let mut dag = create_dag_from_yaml(file_path, exist_float_dag);
+            remove_dag_id(&mut dag);
+            dag
+        })
+        .collect();
+    assign_dag_ids(&mut dag_set);
+    dag_set
+}
 
-    for (dag_id, file_path) in file_path_list.iter().enumerate() {
-        let mut dag = create_dag_from_yaml(file_path, exist_float_dag);
+/// Load the yaml files of several directories as a single DAGSet like `create_dag_set_from_dirs`,
+/// with the times converted by `quantization` instead of scaled to integers.
+pub fn create_dag_set_from_dirs_with_quantization(
+    dir_paths: &[&str],
+    quantization: &TimeQuantization,
+) -> (Vec<Graph<NodeData, i32>>, QuantizationLog) {
+    let (mut dag_set, each_dag_quantization): (Vec<_>, Vec<_>) =
+        get_sorted_yaml_paths_from_dirs(dir_paths)
+            .iter()
+            .map(|file_path| {
+                let (mut dag, dag_quantization) =
+                    create_dag_from_yaml_with_quantization(file_path, quantization);
+                remove_dag_id(&mut dag);
+                (dag, dag_quantization)
+            })
+            .unzip();
+    assign_dag_ids(&mut dag_set);
+    (
+        dag_set,
+        QuantizationLog::new(*quantization, each_dag_quantization),
+    )
+}
+
+/// Load each subdirectory of `dir_path` as a DAGSet, e.g., the DAG set folders of RD-Gen.
+///
+/// The DAGSets are ordered by directory name, and `dag_id` is numbered within each DAGSet.
+pub fn create_dag_sets_from_dir(dir_path: &str) -> Vec<Vec<Graph<NodeData, i32>>> {
+    let mut dag_set_dir_paths: Vec<String> = PathBuf::from(dir_path)
+        .read_dir()
+        .unwrap_or_else(|err| panic!("Failed to open {}: {}", dir_path, err))
+        .map(|dir_entry_result| dir_entry_result.unwrap().path())
+        .filter(|path| path.is_dir())
+        .map(|path| path.to_str().unwrap().to_string())
+        .collect();
+    if dag_set_dir_paths.is_empty() {
+        panic!("No DAG set directory found in {}", dir_path);
+    }
+    dag_set_dir_paths.sort();
+    dag_set_dir_paths
+        .iter()
+        .map(|dag_set_dir_path| create_dag_set_from_dir(dag_set_dir_path))
+        .collect()
+}
+
+/// Names of the DAG (`name` of `graph`) and of its nodes in a yaml file.
+pub fn create_dag_names_from_yaml(file_path: &str) -> DAGNames {
+    let yaml_docs = load_yaml(file_path);
+    let yaml_doc = &yaml_docs[0];
+    let node_names = yaml_doc["nodes"]
+        .as_vec()
+        .map(|nodes| {
+            nodes
+                .iter()
+                .filter_map(|node| {
+                    let name = node["name"].as_str()?;
+                    Some((node["id"].as_i64().unwrap() as i32, name.to_string()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    DAGNames {
+        dag_name: yaml_doc["graph"]["name"].as_str().map(str::to_string),
+        node_names,
+    }
+}
+
+/// Names of the DAGs loaded by `create_dag_set_from_dirs`, indexed by `dag_id`.
+pub fn create_dag_set_names_from_dirs(dir_paths: &[&str]) -> Vec<DAGNames> {
+    get_sorted_yaml_paths_from_dirs(dir_paths)
+        .iter()
+        .map(|file_path| create_dag_names_from_yaml(file_path))
+        .collect()
+}
+
+fn get_sorted_yaml_paths_from_dirs(dir_paths: &[&str]) -> Vec<String> {
+    dir_paths
+        .iter()
+        .flat_map(|dir_path| {
+            let mut file_path_list = get_yaml_paths_from_dir(dir_path);
+            file_path_list.sort();
+            file_path_list
+        })
+        .collect()
+}
+
+/// Remove the `dag_id` a loaded file may carry (e.g., a dumped DAGSet),
+/// because it depends on the position in the DAGSet, not on the DAG.
+pub fn remove_dag_id(dag: &mut Graph<NodeData, i32>) {
+    for node_data in dag.node_weights_mut() {
+        node_data.params.remove("dag_id");
+    }
+}
+
+/// Set `dag_id` to the position of each DAG in the DAGSet, which the logs use as an index.
+///
+/// # Panics
+///
+/// If a DAG already has another `dag_id`. A DAG keeps its `dag_id` once assigned.
+pub fn assign_dag_ids(dag_set: &mut [Graph<NodeData, i32>]) {
+    for (dag_id, dag) in dag_set.iter_mut().enumerate() {
+        let assigned_dag_ids = dag.get_dag_param_values("dag_id");
+        if let Some(&(_, assigned_dag_id)) = assigned_dag_ids
+            .iter()
+            .find(|&&(_, assigned_dag_id)| assigned_dag_id != dag_id as i32)
+        {
+            panic!(
+                "DAG at position {} already has dag_id {}. dag_id must not be reassigned.",
+                dag_id, assigned_dag_id
+            );
+        }
         dag.set_dag_param("dag_id", dag_id as i32);
-        dag_set.push(dag);
     }
-    dag_set
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use petgraph::graph::NodeIndex;
 
     #[test]
     fn test_get_minimum_decimal_places_normal() {
@@ -210,6 +519,45 @@ mod tests {
         let number_of_digits = get_minimum_decimal_places(yaml_doc);
         assert_eq!(number_of_digits, 1, "number of digits is expected to be 1");
     }
+    #[test]
+    fn test_create_dag_from_yaml_duplicate_edges() {
+        let dag = create_dag_from_yaml("tests/sample_dags/duplicate_edges.yaml", false);
+        assert_eq!(dag.edge_count(), 2);
+        assert_eq!(
+            dag.get_pre_nodes(NodeIndex::new(1)).unwrap(),
+            vec![NodeIndex::new(0)]
+        );
+    }
+
+    #[test]
+    fn test_create_dag_from_yaml_elastic_params() {
+        let dag = create_dag_from_yaml("tests/sample_dags/elastic_format.yaml", false);
+        let n0 = NodeIndex::new(0);
+        assert_eq!(dag[n0].get_params_value("elasticity"), 2);
+        assert_eq!(dag[n0].get_params_value("max_period"), 40);
+        assert_eq!(dag.get_head_period(), Some(10));
+    }
+
+    #[test]
+    fn test_create_dag_from_yaml_non_contiguous_ids() {
+        let dag = create_dag_from_yaml("tests/sample_dags/non_contiguous_ids.yaml", false);
+        let node_index_map = NodeIndexMap::new(&dag);
+        let sink_i = node_index_map.get_node_index(40);
+        let mut pre_node_ids: Vec<i32> = dag
+            .get_pre_nodes(sink_i)
+            .unwrap()
+            .iter()
+            .map(|&pre_i| dag[pre_i].id)
+            .collect();
+        pre_node_ids.sort();
+        assert_eq!(pre_node_ids, vec![20, 30]);
+        assert_eq!(
+            dag.get_source_nodes(),
+            vec![node_index_map.get_node_index(10)]
+        );
+        assert_eq!(dag.get_volume(), 11);
+    }
+
     #[test]
     fn test_create_dag_set_from_dir_multiple_int_yaml() {
         let dag_set = create_dag_set_from_dir("tests/sample_dags/multiple_yaml");
@@ -240,6 +588,89 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg(feature = "generator")]
+    fn test_create_dag_set_from_dirs_normal() {
+        let dag_set = create_dag_set_from_dirs(&[
+            "tests/sample_dags/multiple_yaml",
+            "tests/sample_dags/multiple_float_yaml",
+        ]);
+        assert_eq!(dag_set.len(), 4);
+        validate_dag_ids(&dag_set);
+        // The float DAGs of the second directory scale the first one too.
+        assert_eq!(
+            dag_set[0][NodeIndex::new(0)].params["execution_time"],
+            300000
+        );
+        assert_eq!(
+            dag_set[3][NodeIndex::new(0)].params["execution_time"],
+            301000
+        );
+
+        // dag_id of dumped DAGs is replaced by the position in the loaded DAGSet.
+        let mut dumped_dag_set = create_dag_set_from_dir("tests/sample_dags/multiple_yaml");
+        let dir_path = "tests/create_dag_set_from_dirs_test";
+        crate::dag_set_generator::dump_dag_set_to_dir(&dumped_dag_set, dir_path);
+        let dag_set = create_dag_set_from_dirs(&[dir_path, dir_path]);
+        std::fs::remove_dir_all(dir_path).unwrap();
+        assert_eq!(dag_set[3].get_dag_param("dag_id"), 3);
+        assign_dag_ids(&mut dumped_dag_set);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_assign_dag_ids_reassignment() {
+        let mut dag_set = create_dag_set_from_dir("tests/sample_dags/multiple_yaml");
+        dag_set.swap(0, 1);
+        assign_dag_ids(&mut dag_set);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_validate_dag_ids_collision() {
+        let mut dag_set = create_dag_set_from_dir("tests/sample_dags/multiple_yaml");
+        dag_set[1].set_dag_param("dag_id", 0);
+        validate_dag_ids(&dag_set);
+    }
+
+    #[test]
+    fn test_create_dag_set_names_from_dirs_normal() {
+        let dag_set = create_dag_set_from_dir("tests/sample_dags/named_yaml");
+        assert!(!dag_set[0][NodeIndex::new(0)].params.contains_key("name"));
+
+        let dag_set_names = create_dag_set_names_from_dirs(&["tests/sample_dags/named_yaml"]);
+        assert_eq!(dag_set_names.len(), 1);
+        assert_eq!(
+            dag_set_names[0].dag_name,
+            Some("lidar_pipeline".to_string())
+        );
+        assert_eq!(dag_set_names[0].get_node_name(1), Some("ground_removal"));
+        // Node 2 has no name.
+        assert_eq!(dag_set_names[0].get_node_name(2), None);
+        assert!(
+            create_dag_set_names_from_dirs(&["tests/sample_dags/multiple_yaml"])
+                .iter()
+                .all(|dag_names| *dag_names == DAGNames::default())
+        );
+    }
+
+    #[test]
+    fn test_create_dag_sets_from_dir_rd_gen() {
+        let dag_sets = create_dag_sets_from_dir("tests/sample_dags/rd_gen_dag_sets");
+        assert_eq!(dag_sets.len(), 2);
+        assert_eq!(dag_sets[0].len(), 2);
+        assert_eq!(dag_sets[1].len(), 1);
+
+        let dag = &dag_sets[0][0];
+        assert_eq!(dag.get_head_period(), Some(50));
+        assert_eq!(dag.get_end_to_end_deadline(), Some(40));
+        assert_eq!(dag.get_head_offset(), 5);
+        assert_eq!(dag.get_volume(), 12);
+        assert_eq!(dag[dag.edge_indices().next().unwrap()], 3);
+        assert_eq!(dag_sets[0][1].get_dag_param("dag_id"), 1);
+        assert_eq!(dag_sets[1][0].get_dag_param("dag_id"), 0);
+    }
+
     #[test]
     fn test_create_dag_set_from_dir_int_float_yaml() {
         let dag_set = create_dag_set_from_dir("tests/sample_dags/multiple_int_float_yaml");
@@ -283,9 +714,9 @@ mod tests {
     fn test_create_dag_from_yaml_chain_base() {
         let dag = create_dag_from_yaml("tests/sample_dags/chain_base_format.yaml", false);
         let first_node = dag.node_indices().next().unwrap();
-        let last_node = dag.node_indices().last().unwrap();
+        let last_node = dag.node_indices().next_back().unwrap();
         let first_edge = dag.edge_indices().next().unwrap();
-        let last_edge = dag.edge_indices().last().unwrap();
+        let last_edge = dag.edge_indices().next_back().unwrap();
 
         assert_eq!(dag.node_count(), 22, "number of nodes is expected to be 22");
         assert_eq!(
@@ -334,9 +765,9 @@ mod tests {
     fn test_create_dag_from_yaml_fan_in_fan_out() {
         let dag = create_dag_from_yaml("tests/sample_dags/fan_in_fan_out_format.yaml", false);
         let first_node = dag.node_indices().next().unwrap();
-        let last_node = dag.node_indices().last().unwrap();
+        let last_node = dag.node_indices().next_back().unwrap();
         let first_edge = dag.edge_indices().next().unwrap();
-        let last_edge = dag.edge_indices().last().unwrap();
+        let last_edge = dag.edge_indices().next_back().unwrap();
 
         assert_eq!(dag.node_count(), 20, "number of nodes is expected to be 20");
         assert_eq!(
@@ -391,9 +822,9 @@ mod tests {
     fn test_create_dag_from_yaml_gnp() {
         let dag = create_dag_from_yaml("tests/sample_dags/gnp_format.yaml", false);
         let first_node = dag.node_indices().next().unwrap();
-        let last_node = dag.node_indices().last().unwrap();
+        let last_node = dag.node_indices().next_back().unwrap();
         let first_edge = dag.edge_indices().next().unwrap();
-        let last_edge = dag.edge_indices().last().unwrap();
+        let last_edge = dag.edge_indices().next_back().unwrap();
 
         assert_eq!(dag.node_count(), 70, "number of nodes is expected to be 70");
         assert_eq!(dag[first_node].id, 0, "first node id is expected to be 0");
@@ -471,9 +902,9 @@ mod tests {
     fn test_create_dag_from_yaml_float_params() {
         let dag = create_dag_from_yaml("tests/sample_dags/float_params.yaml", false);
         let first_node = dag.node_indices().next().unwrap();
-        let last_node = dag.node_indices().last().unwrap();
+        let last_node = dag.node_indices().next_back().unwrap();
         let first_edge = dag.edge_indices().next().unwrap();
-        let last_edge = dag.edge_indices().last().unwrap();
+        let last_edge = dag.edge_indices().next_back().unwrap();
 
         assert_eq!(dag.node_count(), 3, "number of nodes is expected to be 3");
         assert_eq!(
@@ -531,9 +962,9 @@ mod tests {
     fn test_create_dag_from_dag_int_when_other_dag_float() {
         let dag = create_dag_from_yaml("tests/sample_dags/gnp_format.yaml", true);
         let first_node = dag.node_indices().next().unwrap();
-        let last_node = dag.node_indices().last().unwrap();
+        let last_node = dag.node_indices().next_back().unwrap();
         let first_edge = dag.edge_indices().next().unwrap();
-        let last_edge = dag.edge_indices().last().unwrap();
+        let last_edge = dag.edge_indices().next_back().unwrap();
 
         assert_eq!(dag.node_count(), 70, "number of nodes is expected to be 70");
         assert_eq!(dag[first_node].id, 0, "first node id is expected to be 0");
@@ -1,11 +1,22 @@
+#[cfg(all(feature = "yaml", feature = "analysis"))]
+use crate::fixture::{get_fixture_path, FIXTURE_DIR};
 use crate::{
+    compact_dag::{CompactDAG, CompactReadiness},
     core::ProcessResult,
-    graph_extension::{GraphExtension, NodeData},
+    dag_set_scheduler::PreemptiveType,
+    graph_extension::{GraphExtension, NodeData, NodeIndexMap},
     log::DAGSchedulerLog,
     processor::ProcessorBase,
-    util::{create_scheduler_log_yaml, get_process_core_indices},
+    util::get_process_core_indices,
+};
+#[cfg(feature = "yaml")]
+use crate::{
+    log::{DAGSchedulerResultInfo, LogFormat, SimulationLog},
+    util::{create_scheduler_log_file, create_scheduler_log_yaml},
 };
 use petgraph::graph::{Graph, NodeIndex};
+#[cfg(feature = "random")]
+use rand::{rngs::StdRng, seq::SliceRandom};
 use std::collections::VecDeque;
 
 const DUMMY_EXECUTION_TIME: i32 = 1;
@@ -26,14 +37,27 @@ where
     where
         Self: Sized;
     fn sort_ready_queue(ready_queue: &mut VecDeque<NodeData>);
+    /// Whether and by which key the head of the ready queue preempts running nodes when no core is
+    /// idle, as in `DAGSetSchedulerBase::schedule`. Running nodes with a `preemption_threshold`
+    /// are only preempted by higher priorities (see `get_preemptable_core_index_after`).
+    fn get_preemptive_type(&self) -> PreemptiveType {
+        PreemptiveType::NonPreemptive
+    }
+    /// Random number generator to shuffle the sorted ready queue at each decision point, so that
+    /// the ready nodes are dispatched in random order. `None` keeps the sorted order.
+    #[cfg(feature = "random")]
+    fn get_random_dispatch_rng_mut(&mut self) -> Option<&mut StdRng> {
+        None
+    }
     // method implementation
     fn schedule(&mut self) -> (i32, VecDeque<NodeIndex>) {
         {
-            let mut dag = self.get_dag(); //To avoid adding pre_node_count to the original DAG
+            let mut dag = self.get_dag(); //To avoid adding the dummy nodes to the original DAG
             let mut processor = self.get_processor();
             let mut ready_queue = VecDeque::new();
             let mut log = self.get_log();
             let mut execution_order = VecDeque::new();
+            let preemptive_type = self.get_preemptive_type();
             let source_node_i = dag.add_dummy_source_node();
 
             dag[source_node_i]
@@ -43,12 +67,19 @@ where
             dag[sink_node_i]
                 .params
                 .insert("execution_time".to_string(), DUMMY_EXECUTION_TIME);
+            let node_index_map = NodeIndexMap::new(&dag);
+            let compact_dag = CompactDAG::from_graph(&dag);
+            let mut compact_readiness = CompactReadiness::new(&compact_dag);
 
             ready_queue.push_back(dag[source_node_i].clone());
 
             let mut current_time = 0;
             loop {
                 Self::sort_ready_queue(&mut ready_queue);
+                #[cfg(feature = "random")]
+                if let Some(rng) = self.get_random_dispatch_rng_mut() {
+                    ready_queue.make_contiguous().shuffle(rng);
+                }
 
                 // Assign the highest priority task first to the first idle core found.
                 while let Some(core_index) = processor.get_idle_core_index() {
@@ -62,41 +93,60 @@ where
                                 current_time - DUMMY_EXECUTION_TIME,
                             );
                         }
-                        execution_order.push_back(NodeIndex::new(node_d.id as usize));
+                        if !node_d.params.contains_key("is_preempted") {
+                            execution_order.push_back(node_index_map.get_node_index(node_d.id));
+                        }
                     } else {
                         break;
                     }
                 }
 
-                // Move one unit time so that the core state of the previous loop does not remain.
-                let mut process_result = processor.process();
-                current_time += 1;
+                // Preempt the lowest-priority running node while the head of the ready queue can.
+                // The dummy nodes have no priority, so they neither preempt nor are preempted.
+                while let Some(preemptive_key) = preemptive_type.get_key() {
+                    let Some(core_index) = ready_queue.front().and_then(|head| {
+                        processor.get_preemptable_core_index_after(
+                            preemptive_key,
+                            *head.params.get(preemptive_key)?,
+                            preemptive_type.get_non_preemptive_region(),
+                        )
+                    }) else {
+                        break;
+                    };
+                    let preempted_node_d = processor.preempt(core_index).unwrap();
+                    log.write_preempted_job(
+                        &preempted_node_d,
+                        core_index,
+                        current_time - DUMMY_EXECUTION_TIME,
+                    );
+                    let node_d = ready_queue.pop_front().unwrap();
+                    processor.allocate_specific_core(core_index, &node_d);
+                    log.write_allocating_job(
+                        &node_d,
+                        core_index,
+                        current_time - DUMMY_EXECUTION_TIME,
+                    );
+                    if !node_d.params.contains_key("is_preempted") {
+                        execution_order.push_back(node_index_map.get_node_index(node_d.id));
+                    }
+                    ready_queue.push_back(preempted_node_d);
+                    Self::sort_ready_queue(&mut ready_queue);
+                }
+
+                // Process until there is a task finished, which is the next decision point.
+                let (elapsed_time, process_result) = processor.process_for(i32::MAX - current_time);
+                current_time += elapsed_time;
                 // TODO: Will be refactoring the core structure to have a core log.
                 // Write the processing time of the core to the log.
                 let indices: Vec<usize> = get_process_core_indices(&process_result);
-                log.write_processing_time(&indices);
-
-                // Process until there is a task finished.
-                while !process_result
-                    .iter()
-                    .any(|result| matches!(result, ProcessResult::Done(_)))
-                {
-                    process_result = processor.process();
-                    current_time += 1;
-
-                    // TODO: Will be refactoring the core structure to have a core log.
-                    // Write the processing time of the core to the log.
-                    let indices: Vec<usize> = get_process_core_indices(&process_result);
-                    log.write_processing_time(&indices)
-                }
+                log.write_processing_time_for(&indices, elapsed_time);
 
                 let finish_nodes: Vec<NodeIndex> = process_result
                     .iter()
                     .enumerate()
                     .filter_map(|(core_id, result)| {
                         if let ProcessResult::Done(node_data) = result {
-                            let node_id = node_data.id as usize;
-                            let node_i = NodeIndex::new(node_id);
+                            let node_i = node_index_map.get_node_index(node_data.id);
                             if node_i != source_node_i && node_i != sink_node_i {
                                 log.write_finishing_job(
                                     node_data,
@@ -111,26 +161,18 @@ where
                     })
                     .collect();
 
-                if finish_nodes.len() == 1 && dag.get_suc_nodes(finish_nodes[0]).is_none() {
+                if finish_nodes.len() == 1
+                    && compact_dag
+                        .get_successors(finish_nodes[0].index())
+                        .is_empty()
+                {
                     break; // The scheduling has finished because the dummy sink node has completed.
                 }
 
                 // Executable if all predecessor nodes are done
                 for finish_node in finish_nodes {
-                    let suc_nodes = dag.get_suc_nodes(finish_node).unwrap_or_default();
-                    for suc_node in suc_nodes {
-                        if dag[suc_node].params.contains_key("pre_done_count") {
-                            dag.update_param(
-                                suc_node,
-                                "pre_done_count",
-                                dag[suc_node].get_params_value("pre_done_count") + 1,
-                            );
-                        } else {
-                            dag.add_param(suc_node, "pre_done_count", 1);
-                        }
-                        if dag.is_node_ready(suc_node) {
-                            ready_queue.push_back(dag[suc_node].clone());
-                        }
+                    for suc_i in compact_readiness.complete(&compact_dag, finish_node.index()) {
+                        ready_queue.push_back(dag[NodeIndex::new(suc_i)].clone());
                     }
                 }
             }
@@ -153,10 +195,42 @@ where
         }
     }
 
+    #[cfg(feature = "yaml")]
     fn dump_log(&self, dir_path: &str, alg_name: &str) -> String {
         let file_path = create_scheduler_log_yaml(dir_path, alg_name);
         self.get_log().dump_log_to_yaml(&file_path);
 
         file_path
     }
+
+    /// Dump the log and the result as a single document in `format`.
+    #[cfg(feature = "yaml")]
+    fn dump_simulation_log(
+        &self,
+        dir_path: &str,
+        alg_name: &str,
+        result: DAGSchedulerResultInfo,
+        format: LogFormat,
+    ) -> String {
+        let file_path = create_scheduler_log_file(dir_path, alg_name, format.get_extension());
+        SimulationLog::new(self.get_log(), result).dump(&file_path, format);
+
+        file_path
+    }
+
+    /// Dump the log and the result as YAML to the fixture of `alg_name` (see `fixture`),
+    /// named after the input `dag`.
+    #[cfg(all(feature = "yaml", feature = "analysis"))]
+    fn dump_fixture(
+        &self,
+        alg_name: &str,
+        dag: &Graph<NodeData, i32>,
+        result: DAGSchedulerResultInfo,
+    ) -> String {
+        std::fs::create_dir_all(FIXTURE_DIR).unwrap();
+        let fixture_path = get_fixture_path(alg_name, std::slice::from_ref(dag));
+        SimulationLog::new(self.get_log(), result).dump(&fixture_path, LogFormat::Yaml);
+
+        fixture_path
+    }
 }
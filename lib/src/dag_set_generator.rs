@@ -0,0 +1,274 @@
+//! Generate DAG sets with a mix of heavy and light DAGs.
+//!
+//! Comparisons between federated and global scheduling depend heavily on how the utilization is
+//! distributed over the DAGs, so the ratio of heavy DAGs (utilization above one) is configurable.
+//! The class of each DAG is recorded in its source node as `utilization_class`
+//! so that the results can be analyzed per class.
+pub use crate::dag_task::UtilizationClass;
+use crate::graph_extension::{GraphExtension, NodeData};
+use petgraph::{graph::Graph, visit::EdgeRef};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use serde_derive::Serialize;
+use std::{collections::BTreeMap, fs};
+
+impl UtilizationClass {
+    fn to_param(self) -> i32 {
+        match self {
+            Self::Light => 0,
+            Self::Heavy => 1,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct DAGSetGenerationConfig {
+    pub number_of_dags: usize,
+    /// Ratio of heavy DAGs in the DAG set (rounded to the nearest number of DAGs).
+    pub heavy_ratio: f32,
+    pub heavy_utilization_range: (f32, f32),
+    pub light_utilization_range: (f32, f32),
+    /// Number of nodes of each DAG, including the source and the sink.
+    pub number_of_nodes_range: (usize, usize),
+    /// The period of each DAG is chosen from these candidates.
+    pub periods: Vec<i32>,
+    pub seed: u64,
+}
+
+impl Default for DAGSetGenerationConfig {
+    fn default() -> Self {
+        Self {
+            number_of_dags: 10,
+            heavy_ratio: 0.2,
+            heavy_utilization_range: (1.0, 3.0),
+            light_utilization_range: (0.05, 0.5),
+            number_of_nodes_range: (5, 20),
+            periods: vec![100, 200, 400, 500, 1000],
+            seed: 0,
+        }
+    }
+}
+
+/// Generate a fork-join DAG whose volume matches `utilization` with implicit deadline.
+/// The critical path is not controlled, so a heavy DAG may be infeasible by itself.
+/// The number of nodes is reduced if the volume is too small to give each node one unit.
+fn generate_fork_join_dag(
+    rng: &mut StdRng,
+    number_of_nodes: usize,
+    period: i32,
+    utilization: f32,
+) -> Graph<NodeData, i32> {
+    let volume = ((utilization * period as f32).round() as i32).max(3);
+    let number_of_nodes = number_of_nodes.clamp(3, volume as usize);
+
+    // Each node has at least one unit and the rest is split by random weights.
+    let weights: Vec<f32> = (0..number_of_nodes)
+        .map(|_| rng.gen_range(0.1..1.0))
+        .collect();
+    let total_weight: f32 = weights.iter().sum();
+    let spare_volume = volume - number_of_nodes as i32;
+    let mut execution_times: Vec<i32> = weights
+        .iter()
+        .map(|weight| 1 + (spare_volume as f32 * weight / total_weight) as i32)
+        .collect();
+    // Assign the rounding error to a middle node.
+    execution_times[1] += volume - execution_times.iter().sum::<i32>();
+
+    let mut dag = Graph::<NodeData, i32>::new();
+    let node_indices: Vec<_> = execution_times
+        .iter()
+        .enumerate()
+        .map(|(id, &execution_time)| {
+            let params = BTreeMap::from([("execution_time".to_string(), execution_time)]);
+            dag.add_node(NodeData::new(id as i32, params))
+        })
+        .collect();
+    let (source_i, sink_i) = (node_indices[0], node_indices[number_of_nodes - 1]);
+    for &middle_i in &node_indices[1..number_of_nodes - 1] {
+        dag.add_edge(source_i, middle_i, 0);
+        dag.add_edge(middle_i, sink_i, 0);
+    }
+    dag.add_param(source_i, "period", period);
+    dag.add_param(sink_i, "end_to_end_deadline", period);
+
+    dag
+}
+
+/// Generate a DAG set with `round(number_of_dags * heavy_ratio)` heavy DAGs followed by light DAGs.
+///
+/// # Returns
+///
+/// * The DAG set with `dag_id` and `utilization_class` set.
+pub fn generate_dag_set(config: &DAGSetGenerationConfig) -> Vec<Graph<NodeData, i32>> {
+    if !(0.0..=1.0).contains(&config.heavy_ratio) {
+        panic!("heavy_ratio must be in [0, 1], but {}.", config.heavy_ratio);
+    }
+    let mut rng = StdRng::seed_from_u64(config.seed);
+    let number_of_heavy_dags = (config.number_of_dags as f32 * config.heavy_ratio).round() as usize;
+
+    (0..config.number_of_dags)
+        .map(|dag_id| {
+            let (class, (min_utilization, max_utilization)) = if dag_id < number_of_heavy_dags {
+                (UtilizationClass::Heavy, config.heavy_utilization_range)
+            } else {
+                (UtilizationClass::Light, config.light_utilization_range)
+            };
+            let utilization = rng.gen_range(min_utilization..=max_utilization);
+            let period = config.periods[rng.gen_range(0..config.periods.len())];
+            let number_of_nodes =
+                rng.gen_range(config.number_of_nodes_range.0..=config.number_of_nodes_range.1);
+
+            let mut dag = generate_fork_join_dag(&mut rng, number_of_nodes, period, utilization);
+            let source_i = dag.get_source_nodes()[0];
+            dag.add_param(source_i, "utilization_class", class.to_param());
+            dag.set_dag_param("dag_id", dag_id as i32);
+            dag
+        })
+        .collect()
+}
+
+#[derive(Serialize)]
+struct YamlLink {
+    source: usize,
+    target: usize,
+    communication_time: i32,
+}
+
+#[derive(Serialize)]
+struct YamlDAG {
+    directed: bool,
+    multigraph: bool,
+    links: Vec<YamlLink>,
+    nodes: Vec<BTreeMap<String, i32>>,
+}
+
+/// Write each DAG to `dir_path` in the format read by `create_dag_set_from_dir`.
+///
+/// `dag_id` is not written because it is assigned from the file order when loaded.
+pub fn dump_dag_set_to_dir(dag_set: &[Graph<NodeData, i32>], dir_path: &str) {
+    fs::create_dir_all(dir_path).unwrap();
+    for (dag_i, dag) in dag_set.iter().enumerate() {
+        let yaml_dag = YamlDAG {
+            directed: true,
+            multigraph: false,
+            links: dag
+                .edge_references()
+                .map(|edge| YamlLink {
+                    source: edge.source().index(),
+                    target: edge.target().index(),
+                    communication_time: *edge.weight(),
+                })
+                .collect(),
+            nodes: dag
+                .node_weights()
+                .map(|node| {
+                    let mut params = node.params.clone();
+                    params.remove("dag_id");
+                    params.insert("id".to_string(), node.id);
+                    params
+                })
+                .collect(),
+        };
+        let yaml = serde_yaml::to_string(&yaml_dag).expect("Failed to serialize.");
+        fs::write(format!("{}/dag_{:04}.yaml", dir_path, dag_i), yaml).unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{dag_creator::create_dag_set_from_dir, log::DAGSetInfo};
+
+    fn get_utilization(dag: &Graph<NodeData, i32>) -> f32 {
+        dag.get_volume() as f32 / dag.get_head_period().unwrap() as f32
+    }
+
+    #[test]
+    fn test_generate_dag_set_normal() {
+        let config = DAGSetGenerationConfig {
+            number_of_dags: 8,
+            heavy_ratio: 0.25,
+            ..Default::default()
+        };
+        let dag_set = generate_dag_set(&config);
+
+        assert_eq!(dag_set.len(), 8);
+        for (dag_id, dag) in dag_set.iter().enumerate() {
+            assert_eq!(dag.get_dag_param("dag_id"), dag_id as i32);
+            let utilization = get_utilization(dag);
+            if dag_id < 2 {
+                assert_eq!(
+                    UtilizationClass::from_dag(dag),
+                    Some(UtilizationClass::Heavy)
+                );
+                assert!((1.0 - 0.01..=3.0 + 0.01).contains(&utilization));
+            } else {
+                assert_eq!(
+                    UtilizationClass::from_dag(dag),
+                    Some(UtilizationClass::Light)
+                );
+                assert!((0.05 - 0.01..=0.5 + 0.01).contains(&utilization));
+            }
+            assert!(dag.lint_structure().is_empty());
+        }
+
+        // The same seed generates the same DAG set.
+        let regenerated_dag_set = generate_dag_set(&config);
+        assert!(dag_set
+            .iter()
+            .zip(regenerated_dag_set.iter())
+            .all(|(dag, regenerated_dag)| dag.get_volume() == regenerated_dag.get_volume()));
+    }
+
+    #[test]
+    fn test_utilization_class_classify_normal() {
+        let create_dag = |execution_time: i32| {
+            let mut dag = Graph::<NodeData, i32>::new();
+            let mut params = BTreeMap::new();
+            params.insert("execution_time".to_string(), execution_time);
+            let n0 = dag.add_node(NodeData { id: 0, params });
+            dag.add_param(n0, "period", 10);
+            dag
+        };
+        assert_eq!(
+            UtilizationClass::classify(&create_dag(11)),
+            Some(UtilizationClass::Heavy)
+        );
+        assert_eq!(
+            UtilizationClass::classify(&create_dag(10)),
+            Some(UtilizationClass::Light)
+        );
+
+        let info = DAGSetInfo::new(&[create_dag(11), create_dag(10), create_dag(2)]);
+        assert_eq!(info.get_heavy_dags().get_number_of_dags(), 1);
+        assert_eq!(info.get_light_dags().get_number_of_dags(), 2);
+        assert!((info.get_light_dags().get_total_utilization() - 1.2).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_dump_dag_set_to_dir_normal() {
+        let dag_set = generate_dag_set(&DAGSetGenerationConfig {
+            number_of_dags: 3,
+            heavy_ratio: 0.5,
+            ..Default::default()
+        });
+        let dir_path = "tests/generated_dag_set_test";
+        dump_dag_set_to_dir(&dag_set, dir_path);
+        let loaded_dag_set = create_dag_set_from_dir(dir_path);
+        fs::remove_dir_all(dir_path).unwrap();
+
+        assert_eq!(loaded_dag_set.len(), 3);
+        for (dag, loaded_dag) in dag_set.iter().zip(loaded_dag_set.iter()) {
+            assert_eq!(loaded_dag.node_count(), dag.node_count());
+            assert_eq!(loaded_dag.edge_count(), dag.edge_count());
+            assert_eq!(loaded_dag.get_volume(), dag.get_volume());
+            assert_eq!(
+                loaded_dag.get_dag_param("dag_id"),
+                dag.get_dag_param("dag_id")
+            );
+            assert_eq!(
+                UtilizationClass::from_dag(loaded_dag),
+                UtilizationClass::from_dag(dag)
+            );
+        }
+    }
+}
@@ -1,28 +1,228 @@
+#[cfg(feature = "random")]
+use crate::interrupt_model::InterruptModel;
 use crate::{
+    admission_control::AdmissionControl,
+    compact_dag::{CompactDAG, CompactReadiness},
     core::ProcessResult,
-    graph_extension::{GraphExtension, NodeData},
-    log::{DAGSetSchedulerLog, JobEventTimes},
+    dag_task::DagTask,
+    decomposition::{ABSOLUTE_DEADLINE_FACTOR, DEADLINE_FACTOR},
+    execution_time_trace::ExecutionTimeTrace,
+    fault_injection::FaultInjection,
+    graph_extension::{GraphExtension, NodeData, NodeIndexMap, ReleaseSource},
+    log::{
+        DAGSetSchedulerLog, DegradationDecision, FaultEvent, InheritanceEvent, JobEventTimes,
+        ModeChangeEvent, ReleaseEvent,
+    },
+    mixed_criticality::{Criticality, CriticalityManager},
     processor::ProcessorBase,
-    util::{create_scheduler_log_yaml, get_hyper_period, get_process_core_indices},
+    resource_manager::{LockingProtocol, ResourceManager},
+    util::{clear_interrupted, get_hyper_period, get_process_core_indices, is_interrupted},
 };
+#[cfg(all(feature = "yaml", feature = "analysis"))]
+use crate::{
+    fixture::{get_fixture_path, FIXTURE_DIR},
+    output_log::{RunDirectory, RunStatus, RunSummary},
+    schedulability_screening::ScreeningViolation,
+    schedule_score::{calculate_schedule_score, ScoreWeights},
+};
+#[cfg(feature = "yaml")]
+use crate::{
+    log::{LogFilter, LogFormat, SimulationLog},
+    util::{create_scheduler_log_file, create_scheduler_log_yaml},
+};
+use log::warn;
 use petgraph::graph::{Graph, NodeIndex};
-use std::{cmp::Ordering, collections::BTreeSet};
+#[cfg(feature = "random")]
+use rand::{rngs::StdRng, seq::SliceRandom};
+use serde_derive::{Deserialize, Serialize};
+use std::{
+    cell::Cell,
+    cmp::{Ordering, Reverse},
+    collections::{vec_deque, BTreeMap, BTreeSet, VecDeque},
+    str::FromStr,
+};
 
 // Define a new wrapper type
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct NodeDataWrapper {
     pub node_data: NodeData,
+    /// Order among the nodes with the same deadline (see `TieBreaker`).
+    pub tie_break_key: i32,
+}
+
+impl NodeDataWrapper {
+    pub fn new(node_data: NodeData) -> Self {
+        Self::new_with_tie_break_key(node_data, 0)
+    }
+
+    pub fn new_with_tie_break_key(node_data: NodeData, tie_break_key: i32) -> Self {
+        Self {
+            node_data,
+            tie_break_key,
+        }
+    }
+
+    pub fn convert_node_data(&self) -> NodeData {
+        self.node_data.clone()
+    }
+
+    /// Key of the canonical ready queue order, compared lexicographically.
+    ///
+    /// 1. Absolute deadline (`deadline_key`)
+    /// 2. `tie_break_key`
+    /// 3. `dag_id`
+    /// 4. Node id
+    /// 5. `job_id` (set on release)
+    ///
+    /// The key is unique among the nodes in a ready queue, so the order is total and
+    /// the simulation results do not depend on the insertion order or the platform.
+    fn get_order_key(&self, deadline_key: &str) -> (i32, i32, i32, i32, i32) {
+        (
+            self.node_data.get_params_value(deadline_key),
+            self.tie_break_key,
+            self.node_data.get_params_value("dag_id"),
+            self.node_data.get_id(),
+            self.node_data.params.get("job_id").copied().unwrap_or(0),
+        )
+    }
+
+    /// The decomposition-based algorithm compares the deadlines of the segments.
+    fn get_deadline_key(&self, other: &Self) -> &'static str {
+        if self
+            .node_data
+            .params
+            .contains_key("int_scaled_node_absolute_deadline")
+            && other
+                .node_data
+                .params
+                .contains_key("int_scaled_node_absolute_deadline")
+        {
+            "int_scaled_node_absolute_deadline"
+        } else {
+            "node_absolute_deadline"
+        }
+    }
+
+    /// Whether `self` and `other` tie on the deadline, which the ready queue order compares first.
+    pub fn has_same_deadline(&self, other: &Self) -> bool {
+        let deadline_key = self.get_deadline_key(other);
+        self.node_data.get_params_value(deadline_key)
+            == other.node_data.get_params_value(deadline_key)
+    }
 }
 
+/// The canonical ready queue order of `get_order_key`. All schedulers share this order.
 impl Ord for NodeDataWrapper {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.partial_cmp(other).unwrap_or(Ordering::Equal)
+        let deadline_key = self.get_deadline_key(other);
+        self.get_order_key(deadline_key)
+            .cmp(&other.get_order_key(deadline_key))
     }
 }
 
-impl NodeDataWrapper {
-    pub fn convert_node_data(&self) -> NodeData {
-        self.node_data.clone()
+impl PartialOrd for NodeDataWrapper {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Ready nodes in the canonical order, counting the comparisons made by the queue and by the
+/// scheduler deciding on its nodes to measure the decision overhead of the scheduler.
+#[derive(Clone, Debug, Default)]
+pub struct ReadyQueue {
+    /// Sorted by the canonical order without duplicates.
+    wrappers: VecDeque<NodeDataWrapper>,
+    comparison_count: Cell<u64>,
+}
+
+impl ReadyQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Position of `wrapper` if it is in the queue, or else where it would be inserted.
+    fn search(&self, wrapper: &NodeDataWrapper) -> Result<usize, usize> {
+        self.wrappers.binary_search_by(|queued_wrapper| {
+            self.count_comparison();
+            queued_wrapper.cmp(wrapper)
+        })
+    }
+
+    /// Whether the node was not yet in the queue.
+    #[must_use]
+    pub fn insert(&mut self, wrapper: NodeDataWrapper) -> bool {
+        match self.search(&wrapper) {
+            Ok(_) => false,
+            Err(index) => {
+                self.wrappers.insert(index, wrapper);
+                true
+            }
+        }
+    }
+
+    /// Insert a node that is not in the queue, e.g., one that just became ready or was
+    /// preempted. `insert` would drop it silently if the queue had it already.
+    pub fn insert_new(&mut self, wrapper: NodeDataWrapper) {
+        let node_id = wrapper.node_data.get_id();
+        let dag_id = wrapper.node_data.params.get("dag_id").copied();
+        if !self.insert(wrapper) {
+            panic!(
+                "Node {} of DAG {:?} is already in the ready queue.",
+                node_id, dag_id
+            );
+        }
+    }
+
+    /// Whether the node was in the queue.
+    pub fn remove(&mut self, wrapper: &NodeDataWrapper) -> bool {
+        match self.search(wrapper) {
+            Ok(index) => {
+                self.wrappers.remove(index);
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    pub fn first(&self) -> Option<&NodeDataWrapper> {
+        self.wrappers.front()
+    }
+
+    pub fn pop_first(&mut self) -> Option<NodeDataWrapper> {
+        self.wrappers.pop_front()
+    }
+
+    pub fn iter(&self) -> vec_deque::Iter<'_, NodeDataWrapper> {
+        self.wrappers.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.wrappers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.wrappers.is_empty()
+    }
+
+    pub fn retain(&mut self, f: impl FnMut(&NodeDataWrapper) -> bool) {
+        self.wrappers.retain(f);
+    }
+
+    /// Apply `f` to the nodes, whose order may change.
+    pub fn update_nodes(&mut self, f: impl Fn(&mut NodeData)) {
+        for mut wrapper in std::mem::take(&mut self.wrappers) {
+            f(&mut wrapper.node_data);
+            self.insert_new(wrapper);
+        }
+    }
+
+    /// Count a comparison made by the scheduler, e.g., between the head and a running node.
+    pub fn count_comparison(&self) {
+        self.comparison_count.set(self.comparison_count.get() + 1);
+    }
+
+    pub fn get_comparison_count(&self) -> u64 {
+        self.comparison_count.get()
     }
 }
 
@@ -40,7 +240,36 @@ pub trait DAGStateManagerBase {
     fn set_release_count(&mut self, release_count: i32);
     fn get_dag_state(&self) -> DAGState;
     fn set_dag_state(&mut self, dag_state: DAGState);
+    fn get_has_feedback_edge(&self) -> Option<bool>;
+    fn set_has_feedback_edge(&mut self, has_feedback_edge: bool);
+    fn get_dag_task(&self) -> Option<&DagTask>;
+    fn set_dag_task(&mut self, dag_task: DagTask);
+    fn get_skipped_release_count(&self) -> i32;
+    fn set_skipped_release_count(&mut self, skipped_release_count: i32);
+    fn get_compact_dag(&self) -> Option<&CompactDAG>;
+    /// Also start the readiness of the nodes of the first job.
+    fn set_compact_dag(&mut self, compact_dag: CompactDAG);
+    fn get_compact_readiness(&self) -> Option<&CompactReadiness>;
+    /// Complete `node_i` of the current job and return its successors that became ready.
+    fn complete_compact_node(&mut self, node_i: NodeIndex) -> Vec<NodeIndex>;
+    /// Reset the readiness of the nodes for the next job.
+    fn reset_compact_readiness(&mut self);
     // method implementation
+    /// Whether all predecessors of `node_i` of the current job have completed.
+    fn is_node_ready(&self, node_i: NodeIndex) -> bool {
+        self.get_compact_readiness()
+            .is_some_and(|readiness| readiness.is_ready(node_i.index()))
+    }
+
+    /// Periods passed so far including the skipped releases, which give the next release time.
+    fn get_period_count(&self) -> i32 {
+        self.get_release_count() + self.get_skipped_release_count()
+    }
+
+    fn skip_release(&mut self) {
+        self.set_skipped_release_count(self.get_skipped_release_count() + 1);
+    }
+
     fn complete_execution(&mut self) {
         self.set_dag_state(DAGState::Waiting);
     }
@@ -66,6 +295,53 @@ macro_rules! getset_dag_state_manager {
         fn set_dag_state(&mut self, dag_state: DAGState) {
             self.dag_state = dag_state;
         }
+        fn get_has_feedback_edge(&self) -> Option<bool> {
+            self.has_feedback_edge
+        }
+        fn set_has_feedback_edge(&mut self, has_feedback_edge: bool) {
+            self.has_feedback_edge = Some(has_feedback_edge);
+        }
+        fn get_dag_task(&self) -> Option<&$crate::dag_task::DagTask> {
+            self.dag_task.as_ref()
+        }
+        fn set_dag_task(&mut self, dag_task: $crate::dag_task::DagTask) {
+            self.dag_task = Some(dag_task);
+        }
+        fn get_skipped_release_count(&self) -> i32 {
+            self.skipped_release_count
+        }
+        fn set_skipped_release_count(&mut self, skipped_release_count: i32) {
+            self.skipped_release_count = skipped_release_count;
+        }
+        fn get_compact_dag(&self) -> Option<&$crate::compact_dag::CompactDAG> {
+            self.compact_dag.as_ref()
+        }
+        fn set_compact_dag(&mut self, compact_dag: $crate::compact_dag::CompactDAG) {
+            self.compact_readiness = Some($crate::compact_dag::CompactReadiness::new(&compact_dag));
+            self.compact_dag = Some(compact_dag);
+        }
+        fn get_compact_readiness(&self) -> Option<&$crate::compact_dag::CompactReadiness> {
+            self.compact_readiness.as_ref()
+        }
+        fn complete_compact_node(&mut self, node_i: NodeIndex) -> Vec<NodeIndex> {
+            let compact_dag = self
+                .compact_dag
+                .as_ref()
+                .unwrap_or_else(|| panic!("The DAG of node {:?} is not released.", node_i));
+            self.compact_readiness
+                .as_mut()
+                .unwrap()
+                .complete(compact_dag, node_i.index())
+                .map(NodeIndex::new)
+                .collect()
+        }
+        fn reset_compact_readiness(&mut self) {
+            if let (Some(compact_dag), Some(compact_readiness)) =
+                (self.compact_dag.as_ref(), self.compact_readiness.as_mut())
+            {
+                compact_readiness.reset(compact_dag);
+            }
+        }
     };
 }
 
@@ -73,21 +349,338 @@ macro_rules! getset_dag_state_manager {
 pub struct DAGStateManager {
     dag_state: DAGState,
     release_count: i32,
+    /// Periodic releases dropped because the previous job was still running.
+    skipped_release_count: i32,
+    /// Whether the DAG has a feedback edge, found at its first release.
+    has_feedback_edge: Option<bool>,
+    /// DAG-level params of the DAG, read at its first release.
+    dag_task: Option<DagTask>,
+    /// Structure of the DAG for the readiness of its nodes, built at its first release.
+    compact_dag: Option<CompactDAG>,
+    compact_readiness: Option<CompactReadiness>,
+    /// Remaining budget of the split nodes of the current job on their first core, by node id.
+    split_budgets: BTreeMap<i32, i32>,
 }
 
 impl DAGStateManagerBase for DAGStateManager {
     getset_dag_state_manager!();
 }
 
+impl DAGStateManager {
+    pub fn start_split_budget(&mut self, node_id: i32, budget: i32) {
+        self.split_budgets.insert(node_id, budget);
+    }
+
+    /// `None` if the node is not running its first portion.
+    pub fn get_split_budget(&self, node_id: i32) -> Option<i32> {
+        self.split_budgets.get(&node_id).copied()
+    }
+
+    /// Consume a time unit of the budget, and return whether the budget is exhausted.
+    pub fn consume_split_budget(&mut self, node_id: i32) -> bool {
+        let budget = self
+            .split_budgets
+            .get_mut(&node_id)
+            .unwrap_or_else(|| panic!("Node {} has no split budget.", node_id));
+        *budget -= 1;
+        *budget <= 0
+    }
+
+    pub fn end_split_budget(&mut self, node_id: i32) {
+        self.split_budgets.remove(&node_id);
+    }
+}
+
+/// How `release_dags` computes the absolute deadlines of a released job.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AbsoluteDeadlineFormula {
+    /// `release_time + relative_deadline`, which respects constrained deadlines and offsets.
+    ReleaseTime,
+    /// `relative_deadline * release_count`, which only holds for implicit deadlines without offsets.
+    /// This is the original formula and the default.
+    #[default]
+    ReleaseCount,
+}
+
+impl AbsoluteDeadlineFormula {
+    /// `release_count` is counted from 1 for the first job and includes the skipped releases.
+    pub fn calculate(self, release_time: i32, relative_deadline: i32, release_count: i32) -> i32 {
+        match self {
+            Self::ReleaseTime => release_time + relative_deadline,
+            Self::ReleaseCount => relative_deadline * release_count,
+        }
+    }
+}
+
+/// How the default `schedule` chooses among the idle cores for the next node.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IdleCoreSelection {
+    /// The idle core with the smallest index, which biases the load to low-index cores.
+    #[default]
+    FirstFit,
+    /// The core that became idle most recently, e.g., to reuse warm caches.
+    LastUsed,
+    /// The core with the least cumulative processing time, which balances the utilization.
+    LeastUtilized,
+    /// The first idle core that has not powered down, if any, to avoid the wake-up latency
+    /// (see `ProcessorBase::is_core_asleep`).
+    Awake,
+}
+
+impl IdleCoreSelection {
+    /// Ties are broken by the smallest index. `asleep_cores` tells whether each core is asleep.
+    pub fn select(
+        self,
+        idle_core_indices: &[usize],
+        log: &DAGSetSchedulerLog,
+        asleep_cores: &[bool],
+    ) -> Option<usize> {
+        match self {
+            Self::FirstFit => idle_core_indices.first().copied(),
+            Self::LastUsed => idle_core_indices
+                .iter()
+                .copied()
+                .min_by_key(|&core_i| Reverse(log.get_last_idle_time(core_i))),
+            Self::LeastUtilized => idle_core_indices
+                .iter()
+                .copied()
+                .min_by_key(|&core_i| log.get_total_proc_time(core_i)),
+            Self::Awake => idle_core_indices
+                .iter()
+                .copied()
+                .min_by_key(|&core_i| asleep_cores[core_i]),
+        }
+    }
+}
+
+impl FromStr for IdleCoreSelection {
+    type Err = String;
+
+    fn from_str(selection: &str) -> Result<Self, Self::Err> {
+        match selection.to_lowercase().as_str() {
+            "first_fit" => Ok(Self::FirstFit),
+            "last_used" => Ok(Self::LastUsed),
+            "least_utilized" => Ok(Self::LeastUtilized),
+            "awake" => Ok(Self::Awake),
+            _ => Err(format!("Unknown idle core selection: {}", selection)),
+        }
+    }
+}
+
+/// Whether a scheduler may leave a core idle although a ready node could be dispatched.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IdleInsertionPolicy {
+    /// Dispatch whenever a core is idle.
+    #[default]
+    WorkConserving,
+    /// Keep the last idle core free if the node would make a job released before it completes,
+    /// with an earlier deadline, miss that deadline (see `AnticipatoryEDFScheduler`).
+    Anticipatory,
+}
+
+impl FromStr for IdleInsertionPolicy {
+    type Err = String;
+
+    fn from_str(policy: &str) -> Result<Self, Self::Err> {
+        match policy.to_lowercase().as_str() {
+            "work_conserving" => Ok(Self::WorkConserving),
+            "anticipatory" => Ok(Self::Anticipatory),
+            _ => Err(format!("Unknown idle insertion policy: {}", policy)),
+        }
+    }
+}
+
+/// Order of the ready nodes with the same deadline, before `dag_id` and the node id.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TieBreakingPolicy {
+    /// By `dag_id`, then by node id.
+    #[default]
+    NodeId,
+    /// The node with the longer remaining path (see `get_remaining_path_length`) first.
+    RemainingWork,
+    /// The node of the DAG with the higher utilization first.
+    Utilization,
+    /// The node that became ready earlier first.
+    Fifo,
+}
+
+impl FromStr for TieBreakingPolicy {
+    type Err = String;
+
+    fn from_str(policy: &str) -> Result<Self, Self::Err> {
+        match policy.to_lowercase().as_str() {
+            "node_id" => Ok(Self::NodeId),
+            "remaining_work" => Ok(Self::RemainingWork),
+            "utilization" => Ok(Self::Utilization),
+            "fifo" => Ok(Self::Fifo),
+            _ => Err(format!("Unknown tie-breaking policy: {}", policy)),
+        }
+    }
+}
+
+/// Strategy of a `TieBreakingPolicy`, giving the `tie_break_key` of the ready nodes (smaller
+/// first). The keys are kept here rather than in the nodes of the DAGs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TieBreaker {
+    tie_breaking_policy: TieBreakingPolicy,
+    /// Key of each node by `dag_id` and node id. The keys of `Fifo` are written when the nodes
+    /// become ready.
+    keys: Vec<BTreeMap<i32, i32>>,
+}
+
+impl TieBreaker {
+    pub fn new(tie_breaking_policy: TieBreakingPolicy, dag_set: &[Graph<NodeData, i32>]) -> Self {
+        let keys = match tie_breaking_policy {
+            TieBreakingPolicy::NodeId | TieBreakingPolicy::Fifo => {
+                vec![BTreeMap::new(); dag_set.len()]
+            }
+            TieBreakingPolicy::RemainingWork => dag_set
+                .iter()
+                .map(|dag| {
+                    let remaining_path_lengths = dag.get_remaining_path_lengths();
+                    dag.node_indices()
+                        .map(|node_i| (dag[node_i].id, -remaining_path_lengths[node_i.index()]))
+                        .collect()
+                })
+                .collect(),
+            TieBreakingPolicy::Utilization => {
+                let utilizations: Vec<f32> = dag_set
+                    .iter()
+                    .map(|dag| DagTask::from_graph(dag).get_utilization().unwrap_or(0.0))
+                    .collect();
+                let mut dag_ids: Vec<usize> = (0..dag_set.len()).collect();
+                dag_ids.sort_by(|&a, &b| utilizations[b].total_cmp(&utilizations[a]));
+                let mut keys = vec![BTreeMap::new(); dag_set.len()];
+                for (rank, dag_id) in dag_ids.into_iter().enumerate() {
+                    keys[dag_id] = dag_set[dag_id]
+                        .node_weights()
+                        .map(|node| (node.id, rank as i32))
+                        .collect();
+                }
+                keys
+            }
+        };
+        Self {
+            tie_breaking_policy,
+            keys,
+        }
+    }
+
+    pub fn get_tie_breaking_policy(&self) -> TieBreakingPolicy {
+        self.tie_breaking_policy
+    }
+
+    pub fn get_tie_break_key(&self, node_data: &NodeData) -> i32 {
+        self.keys[node_data.get_params_value("dag_id") as usize]
+            .get(&node_data.id)
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Write the time at which `node_data` becomes ready as its key, for `Fifo`.
+    pub fn write_ready_time(&mut self, node_data: &NodeData, current_time: i32) {
+        if self.tie_breaking_policy == TieBreakingPolicy::Fifo {
+            self.keys[node_data.get_params_value("dag_id") as usize]
+                .insert(node_data.id, current_time);
+        }
+    }
+}
+
+/// Share of each core that the default `schedule` may use, reserving the rest as headroom
+/// (e.g., for OS and interrupt overheads).
+///
+/// As in the throttling of real-time tasks in Linux, each core may be busy for
+/// `cap * window` time units in each window, and is throttled for the rest of the window.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CoreUtilizationCaps {
+    caps: Vec<f32>,
+    window: i32,
+}
+
+impl CoreUtilizationCaps {
+    pub fn new(caps: Vec<f32>, window: i32) -> Self {
+        if let Some(cap) = caps.iter().find(|cap| !(0.0..=1.0).contains(*cap)) {
+            panic!("Utilization cap {} is not within [0, 1].", cap);
+        }
+        if window <= 0 {
+            panic!("The window of the utilization caps must be positive.");
+        }
+        Self { caps, window }
+    }
+
+    /// The same cap for all cores.
+    pub fn new_uniform(cap: f32, number_of_cores: usize, window: i32) -> Self {
+        Self::new(vec![cap; number_of_cores], window)
+    }
+
+    pub fn get_cap(&self, core_i: usize) -> f32 {
+        self.caps[core_i]
+    }
+
+    pub fn get_caps(&self) -> &[f32] {
+        &self.caps
+    }
+
+    pub fn get_window(&self) -> i32 {
+        self.window
+    }
+
+    /// Time units the core may be busy in a window of `window_length`,
+    /// which is shorter than the window at the end of the schedule.
+    pub fn get_budget(&self, core_i: usize, window_length: i32) -> i32 {
+        // The margin keeps e.g. 0.9 * 10 from being floored to 8.
+        (self.caps[core_i] * window_length as f32 + 1e-4).floor() as i32
+    }
+}
+
+#[derive(Clone, Default)]
 pub enum PreemptiveType {
+    #[default]
     NonPreemptive,
-    Preemptive { key: String },
+    Preemptive {
+        key: String,
+    },
+    /// Deferred preemption: a node is preempted by higher priorities (smaller `key`) only after
+    /// it has run for `non_preemptive_region` since it started or resumed, and otherwise at its
+    /// boundary, i.e., when it completes.
+    LimitedPreemptive {
+        key: String,
+        non_preemptive_region: i32,
+    },
+}
+
+impl PreemptiveType {
+    /// The priority key compared for preemption, or `None` if nodes are never preempted.
+    pub fn get_key(&self) -> Option<&str> {
+        match self {
+            Self::NonPreemptive => None,
+            Self::Preemptive { key } | Self::LimitedPreemptive { key, .. } => Some(key),
+        }
+    }
+
+    /// Time a node runs before it can be preempted, 0 if fully preemptive.
+    pub fn get_non_preemptive_region(&self) -> i32 {
+        match self {
+            Self::LimitedPreemptive {
+                non_preemptive_region,
+                ..
+            } => *non_preemptive_region,
+            _ => 0,
+        }
+    }
 }
 
 pub trait DAGSetSchedulerBase<T: ProcessorBase + Clone> {
     // getter, setter
     fn get_dag_set(&self) -> Vec<Graph<NodeData, i32>>;
+    /// The DAG set without cloning it, for the checks made at every decision point.
+    fn get_dag_set_ref(&self) -> &[Graph<NodeData, i32>];
+    /// Only the params of the nodes may change, so the node index maps stay valid.
     fn set_dag_set(&mut self, dag_set: Vec<Graph<NodeData, i32>>);
+    fn get_node_index_maps(&self) -> &[NodeIndexMap];
     fn get_processor_mut(&mut self) -> &mut T;
     fn get_processor(&self) -> &T;
     fn get_log_mut(&mut self) -> &mut DAGSetSchedulerLog;
@@ -96,6 +689,207 @@ pub trait DAGSetSchedulerBase<T: ProcessorBase + Clone> {
     // method definition
     fn new(dag_set: &[Graph<NodeData, i32>], processor: &T) -> Self;
     // method implementation
+    fn get_node_index(&self, dag_id: usize, node_id: i32) -> NodeIndex {
+        self.get_node_index_maps()[dag_id].get_node_index(node_id)
+    }
+
+    fn get_absolute_deadline_formula(&self) -> AbsoluteDeadlineFormula {
+        AbsoluteDeadlineFormula::default()
+    }
+
+    fn get_idle_core_selection(&self) -> IdleCoreSelection {
+        IdleCoreSelection::default()
+    }
+
+    /// Caps enforced by throttling the cores in the default `schedule`. `None` for no caps.
+    fn get_core_utilization_caps(&self) -> Option<&CoreUtilizationCaps> {
+        None
+    }
+
+    /// Interrupt bursts stealing the cores at top priority in the default `schedule`.
+    /// `None` for no interrupts.
+    #[cfg(feature = "random")]
+    fn get_interrupt_model(&self) -> Option<&InterruptModel> {
+        None
+    }
+
+    /// Random number generator to dispatch a random ready node instead of the head of the ready
+    /// queue, as a lower-bound baseline. `None` dispatches the head.
+    #[cfg(feature = "random")]
+    fn get_random_dispatch_rng_mut(&mut self) -> Option<&mut StdRng> {
+        None
+    }
+
+    /// The ready node to dispatch next among those `can_allocate` accepts: a random one with
+    /// `get_random_dispatch_rng_mut`, or else the one of `select_candidate`.
+    fn select_ready_node(
+        &mut self,
+        ready_queue: &ReadyQueue,
+        can_allocate: impl Fn(&T, &NodeData) -> bool,
+    ) -> Option<NodeDataWrapper> {
+        #[cfg(feature = "random")]
+        let is_random = self.get_random_dispatch_rng_mut().is_some();
+        let candidates = ready_queue
+            .iter()
+            .filter(|wrapper| can_allocate(self.get_processor(), &wrapper.node_data));
+        #[cfg(feature = "random")]
+        if is_random {
+            let candidates: Vec<&NodeDataWrapper> = candidates.collect();
+            return candidates
+                .choose(self.get_random_dispatch_rng_mut().unwrap())
+                .map(|wrapper| (*wrapper).clone());
+        }
+        self.select_candidate(candidates)
+    }
+
+    /// The node to dispatch among `candidates`, the ready nodes `can_allocate` accepts in the
+    /// ready queue order. The first one by default.
+    fn select_candidate<'a>(
+        &self,
+        mut candidates: impl Iterator<Item = &'a NodeDataWrapper>,
+    ) -> Option<NodeDataWrapper> {
+        candidates.next().cloned()
+    }
+
+    /// Whether a job triggering a DAG donates its absolute deadline to the triggered job if it is
+    /// earlier than the deadline of the triggered job, to prevent priority inversion along chains
+    /// of DAGs. Only DAG deadlines (`end_to_end_deadline`) are donated and inherited.
+    fn get_priority_inheritance(&self) -> bool {
+        false
+    }
+
+    /// How the holders of the shared resources declared by `resource_id` are prioritized
+    /// (see `resource_manager`).
+    fn get_locking_protocol(&self) -> LockingProtocol {
+        LockingProtocol::default()
+    }
+
+    /// Measured execution times replacing the WCETs of the released jobs.
+    fn get_execution_time_trace(&self) -> Option<&ExecutionTimeTrace> {
+        None
+    }
+
+    /// Transient faults of the node executions in the default `schedule`. `None` for no faults.
+    fn get_fault_injection(&self) -> Option<&FaultInjection> {
+        None
+    }
+
+    /// Factor of the virtual deadlines of EDF-VD (see `EdfVdScheduler`). `None` for the native
+    /// deadlines in both modes.
+    fn get_virtual_deadline_factor(&self) -> Option<f32> {
+        None
+    }
+
+    /// Shorten the deadline of the job just released by `ready_node` to `factor` of its relative
+    /// deadline, rounded up, keeping the native deadline in `native_absolute_deadline`.
+    fn set_virtual_deadline(&mut self, ready_node: &mut NodeData, factor: f32) {
+        let Some(&absolute_deadline) = ready_node.params.get("node_absolute_deadline") else {
+            return;
+        };
+        let release_time = self.get_current_time();
+        let virtual_deadline =
+            release_time + ((absolute_deadline - release_time) as f32 * factor).ceil() as i32;
+        let mut dag_set = self.get_dag_set();
+        let dag = &mut dag_set[ready_node.get_params_value("dag_id") as usize];
+        dag.set_dag_param("native_absolute_deadline", absolute_deadline);
+        dag.set_dag_param("node_absolute_deadline", virtual_deadline);
+        self.set_dag_set(dag_set);
+        for (key, value) in [
+            ("native_absolute_deadline", absolute_deadline),
+            ("node_absolute_deadline", virtual_deadline),
+        ] {
+            ready_node.params.insert(key.to_string(), value);
+        }
+    }
+
+    /// Restore the native deadlines of the unfinished jobs with virtual deadlines, in the DAGs,
+    /// the ready queue and on the cores.
+    fn restore_native_deadlines(&mut self, ready_queue: &mut ReadyQueue) {
+        let restore = |node_data: &mut NodeData| {
+            if let Some(native_deadline) = node_data.params.remove("native_absolute_deadline") {
+                node_data
+                    .params
+                    .insert("node_absolute_deadline".to_string(), native_deadline);
+            }
+        };
+        let mut dag_set = self.get_dag_set();
+        for dag in dag_set.iter_mut() {
+            dag.node_weights_mut().for_each(restore);
+        }
+        self.set_dag_set(dag_set);
+        ready_queue.update_nodes(restore);
+        for core_i in 0..self.get_processor().get_number_of_cores() {
+            let Some(native_deadline) = self
+                .get_processor()
+                .get_processing_node(core_i)
+                .and_then(|node_data| node_data.params.get("native_absolute_deadline").copied())
+            else {
+                continue;
+            };
+            self.get_processor_mut().set_processing_node_param(
+                core_i,
+                "node_absolute_deadline",
+                native_deadline,
+            );
+        }
+    }
+
+    /// Order of the ready nodes with the same deadline. `None` orders them by `dag_id` and
+    /// node id.
+    fn get_tie_breaker(&self) -> Option<&TieBreaker> {
+        None
+    }
+
+    fn get_tie_breaker_mut(&mut self) -> Option<&mut TieBreaker> {
+        None
+    }
+
+    /// `node_data` with its key of `get_tie_breaker` for the ready queue.
+    fn create_node_data_wrapper(&self, node_data: NodeData) -> NodeDataWrapper {
+        let tie_break_key = self
+            .get_tie_breaker()
+            .map_or(0, |tie_breaker| tie_breaker.get_tie_break_key(&node_data));
+        NodeDataWrapper::new_with_tie_break_key(node_data, tie_break_key)
+    }
+
+    /// Test deciding whether each released job of a periodic DAG runs. `None` admits all jobs.
+    fn get_admission_control(&self) -> Option<&AdmissionControl> {
+        None
+    }
+
+    /// Whether the job of the DAG released now passes the admission control, logging the
+    /// decision. The active jobs are the released jobs of the DAGs that are not waiting.
+    fn admit_job(&mut self, dag_id: usize, managers: &[impl DAGStateManagerBase]) -> bool {
+        if self.get_admission_control().is_none() {
+            return true;
+        }
+        let current_time = self.get_current_time();
+        let log = self.get_log_mut();
+        let active_jobs: Vec<(usize, i32)> = managers
+            .iter()
+            .enumerate()
+            .filter(|(_, manager)| manager.get_dag_state() != DAGState::Waiting)
+            .filter_map(|(active_dag_id, _)| {
+                Some((active_dag_id, *log.get_release_times(active_dag_id).last()?))
+            })
+            .collect();
+        let is_admitted =
+            self.get_admission_control()
+                .unwrap()
+                .admits(dag_id, current_time, &active_jobs);
+        if is_admitted {
+            self.get_log_mut().write_admitted_job();
+        } else {
+            self.get_log_mut().write_rejected_job(dag_id, current_time);
+        }
+        is_admitted
+    }
+
+    /// Release the DAGs whose next job is due.
+    ///
+    /// A periodic DAG is due at `offset + period * period_count`, where the skipped releases
+    /// also advance `period_count`.
+    /// A triggered DAG is due while it has triggers left by `post_process_on_node_completion`.
     fn release_dags(&mut self, managers: &mut [impl DAGStateManagerBase]) -> Vec<NodeData> {
         let current_time = self.get_current_time();
         let mut ready_nodes = Vec::new();
@@ -103,33 +897,143 @@ pub trait DAGSetSchedulerBase<T: ProcessorBase + Clone> {
 
         for dag in dag_set.iter_mut() {
             let dag_id = dag.get_dag_param("dag_id") as usize;
-            if (managers[dag_id].get_dag_state() == DAGState::Waiting)
-                && (current_time
-                    == dag.get_head_offset()
-                        + dag.get_head_period().unwrap() * managers[dag_id].get_release_count())
-            {
+            let release_source = dag.get_release_source();
+            let is_waiting = managers[dag_id].get_dag_state() == DAGState::Waiting;
+            if managers[dag_id].get_dag_task().is_none() {
+                managers[dag_id].set_dag_task(DagTask::from_graph(dag));
+                managers[dag_id].set_compact_dag(CompactDAG::from_graph(dag));
+            }
+            let dag_task = managers[dag_id].get_dag_task().unwrap();
+            let (offset, period, end_to_end_deadline) = (
+                dag_task.offset,
+                dag_task.period,
+                dag_task.end_to_end_deadline,
+            );
+            let is_release_due = match release_source {
+                ReleaseSource::Periodic => {
+                    let period = period.unwrap_or_else(|| panic!("DAG {} has no period.", dag_id));
+                    let release_time = offset + period * managers[dag_id].get_period_count();
+                    // A DAG with a feedback edge is released as soon as the previous job completes
+                    // if it could not be released on time, instead of skipping the release.
+                    let has_feedback_edge =
+                        managers[dag_id].get_has_feedback_edge().unwrap_or_else(|| {
+                            let has_feedback_edge = !dag.get_feedback_sinks().is_empty();
+                            managers[dag_id].set_has_feedback_edge(has_feedback_edge);
+                            has_feedback_edge
+                        });
+                    if current_time == release_time && !is_waiting {
+                        let release_event = if has_feedback_edge {
+                            ReleaseEvent::Queued { release_time }
+                        } else {
+                            // Move on to the next period so that every missed release is logged.
+                            managers[dag_id].skip_release();
+                            ReleaseEvent::Skipped { release_time }
+                        };
+                        self.get_log_mut()
+                            .write_release_event(dag_id, release_event);
+                    } else if current_time > release_time && has_feedback_edge && is_waiting {
+                        self.get_log_mut().write_release_event(
+                            dag_id,
+                            ReleaseEvent::LateStart {
+                                release_time,
+                                start_time: current_time,
+                                delay: current_time - release_time,
+                            },
+                        );
+                    }
+                    current_time == release_time
+                        || (current_time > release_time && has_feedback_edge)
+                }
+                ReleaseSource::Triggered { .. } => get_pending_trigger_count(dag) > 0,
+            };
+            if is_waiting && is_release_due {
+                // A rejected job counts as released and completed at once.
+                if release_source == ReleaseSource::Periodic && !self.admit_job(dag_id, managers) {
+                    managers[dag_id].release();
+                    managers[dag_id].complete_execution();
+                    continue;
+                }
                 managers[dag_id].release();
+                let release_count = managers[dag_id].get_release_count();
+                let period_count = managers[dag_id].get_period_count();
+                dag.set_dag_param("job_id", release_count - 1);
+                if let Some(trace) = self.get_execution_time_trace() {
+                    apply_execution_time_trace(dag, trace, dag_id, release_count - 1);
+                }
+                // Deadlines of triggered DAGs are always relative to the release time.
+                let mut inheritance = None;
+                let formula = match release_source {
+                    ReleaseSource::Periodic => self.get_absolute_deadline_formula(),
+                    ReleaseSource::Triggered {
+                        dag_id: triggering_dag_id,
+                        ..
+                    } => {
+                        let source_i = dag.get_source_nodes()[0];
+                        dag.update_param(
+                            source_i,
+                            "pending_trigger_count",
+                            get_pending_trigger_count(dag) - 1,
+                        );
+                        inheritance = take_donated_deadline(dag).map(
+                            |(inherited_deadline, triggering_job_id, triggering_release_time)| {
+                                InheritanceEvent {
+                                    triggering_dag_id,
+                                    triggering_job_id: triggering_job_id as usize,
+                                    triggering_release_time,
+                                    triggered_dag_id: dag_id,
+                                    triggered_job_id: (release_count - 1) as usize,
+                                    time: current_time,
+                                    own_deadline: 0,
+                                    inherited_deadline,
+                                    chain_latency: None,
+                                }
+                            },
+                        );
+                        AbsoluteDeadlineFormula::ReleaseTime
+                    }
+                };
                 // If Node does not have individual deadlines, use DAG deadline.
                 if dag[NodeIndex::new(0)]
                     .params
                     .contains_key("int_scaled_node_relative_deadline")
                 {
+                    // Both terms are scaled by `ABSOLUTE_DEADLINE_FACTOR` to compare the jobs of
+                    // different releases.
+                    let scaled_release_time = current_time
+                        .checked_mul(ABSOLUTE_DEADLINE_FACTOR)
+                        .expect("The release time is too late for the scaled deadlines.");
                     for node_i in dag.node_indices() {
-                        let node_relative_deadline =
-                            dag[node_i].get_params_value("int_scaled_node_relative_deadline");
+                        let node_relative_deadline = dag[node_i]
+                            .get_params_value("int_scaled_node_relative_deadline")
+                            / (DEADLINE_FACTOR / ABSOLUTE_DEADLINE_FACTOR);
                         dag[node_i].params.insert(
                             "int_scaled_node_absolute_deadline".to_string(),
-                            node_relative_deadline * managers[dag_id].get_release_count(),
+                            formula.calculate(
+                                scaled_release_time,
+                                node_relative_deadline,
+                                period_count,
+                            ),
                         );
                     }
                 } else {
-                    dag.set_dag_param(
-                        "node_absolute_deadline",
-                        dag.get_end_to_end_deadline().unwrap()
-                            * managers[dag_id].get_release_count(),
-                    );
+                    let end_to_end_deadline = end_to_end_deadline
+                        .unwrap_or_else(|| panic!("DAG {} has no end-to-end deadline.", dag_id));
+                    let mut absolute_deadline =
+                        formula.calculate(current_time, end_to_end_deadline, period_count);
+                    if let Some(mut inheritance_event) = inheritance {
+                        inheritance_event.own_deadline = absolute_deadline;
+                        absolute_deadline =
+                            absolute_deadline.min(inheritance_event.inherited_deadline);
+                        self.get_log_mut()
+                            .write_inheritance_event(inheritance_event);
+                    }
+                    dag.set_dag_param("node_absolute_deadline", absolute_deadline);
                 }
-                ready_nodes.push(dag[dag.get_source_nodes()[0]].clone());
+                let source_node = dag[dag.get_source_nodes()[0]].clone();
+                if let Some(tie_breaker) = self.get_tie_breaker_mut() {
+                    tie_breaker.write_ready_time(&source_node, current_time);
+                }
+                ready_nodes.push(source_node);
                 self.get_log_mut()
                     .write_dag_release_time(dag_id, current_time);
             }
@@ -138,12 +1042,73 @@ pub trait DAGSetSchedulerBase<T: ProcessorBase + Clone> {
         ready_nodes
     }
 
+    /// Whether `node_data` at the head of the ready queue may be dispatched to an idle core now.
+    ///
+    /// Work-conserving schedulers always dispatch. Returning `false` leaves the cores idle.
+    fn is_dispatch_eligible(
+        &self,
+        _node_data: &NodeData,
+        _managers: &[impl DAGStateManagerBase],
+    ) -> bool {
+        true
+    }
+
+    /// Whether the DAG of `node_data` runs fewer nodes than its `max_parallelism`.
+    fn can_allocate_node(&self, node_data: &NodeData) -> bool {
+        let dag_id = node_data.get_params_value("dag_id");
+        is_below_max_parallelism(
+            self.get_processor(),
+            dag_id,
+            self.get_dag_set_ref()[dag_id as usize].get_max_parallelism(),
+        )
+    }
+
+    /// Time of the `optional_execution_time` of `node_data` to execute after its mandatory part
+    /// (`execution_time`). The optional part is truncated to the remaining laxity of the DAG.
+    fn get_optional_execution_budget(&self, node_data: &NodeData) -> i32 {
+        let optional_execution_time = node_data.get_params_value("optional_execution_time");
+        let Some(&absolute_deadline) = node_data.params.get("node_absolute_deadline") else {
+            return optional_execution_time;
+        };
+        let dag = &self.get_dag_set_ref()[node_data.get_params_value("dag_id") as usize];
+        let laxity = absolute_deadline
+            - self.get_current_time()
+            - dag.get_remaining_path_length(self.get_node_index(
+                node_data.get_params_value("dag_id") as usize,
+                node_data.get_id(),
+            ));
+        optional_execution_time.min(laxity.max(0))
+    }
+
+    /// Nodes with `optional_execution_time` run for their mandatory part plus the budget of
+    /// `get_optional_execution_budget`, decided when they are first allocated.
     fn allocate_node(&mut self, node_data: &NodeData, core_id: usize, job_id: usize) {
+        if !self.can_allocate_node(node_data) {
+            panic!(
+                "DAG {} already runs max_parallelism nodes.",
+                node_data.get_params_value("dag_id")
+            );
+        }
+        let mut node_data = node_data.clone();
+        if node_data.params.contains_key("optional_execution_time")
+            && !node_data.params.contains_key("optional_budget")
+        {
+            let optional_budget = self.get_optional_execution_budget(&node_data);
+            self.get_log_mut().write_optional_execution(
+                node_data.get_params_value("dag_id") as usize,
+                optional_budget,
+                node_data.get_params_value("optional_execution_time"),
+            );
+            node_data
+                .params
+                .insert("optional_budget".to_string(), optional_budget);
+            *node_data.params.get_mut("execution_time").unwrap() += optional_budget;
+        }
         self.get_processor_mut()
-            .allocate_specific_core(core_id, node_data);
+            .allocate_specific_core(core_id, &node_data);
         let current_time = self.get_current_time();
         self.get_log_mut()
-            .write_allocating_job(node_data, core_id, job_id, current_time)
+            .write_allocating_job(&node_data, core_id, job_id, current_time)
     }
 
     fn process_unit_time(&mut self) -> Vec<ProcessResult> {
@@ -157,109 +1122,557 @@ pub trait DAGSetSchedulerBase<T: ProcessorBase + Clone> {
         core_id: usize,
         managers: &mut [impl DAGStateManagerBase],
     ) -> Vec<NodeData> {
-        let mut dag_set = self.get_dag_set();
         let current_time = self.get_current_time();
-        let log = self.get_log_mut();
-
-        log.write_job_event(
+        self.get_log_mut().write_job_event(
             node,
             core_id,
             (managers[node.get_params_value("dag_id") as usize].get_release_count() - 1) as usize,
             JobEventTimes::FinishTime(current_time),
         );
+        self.complete_node(node, managers)
+    }
+
+    /// The node to re-execute if the execution of `node_data` just completed on `core_id` is
+    /// faulty and has retries left, or `None` to complete the node. The re-execution starts over
+    /// with the execution time of the node in its DAG.
+    fn retry_on_fault(
+        &mut self,
+        node_data: &NodeData,
+        core_id: usize,
+        managers: &[impl DAGStateManagerBase],
+    ) -> Option<NodeData> {
+        let fault_injection = self.get_fault_injection()?;
+        let dag_id = node_data.get_params_value("dag_id") as usize;
+        let job_id = (managers[dag_id].get_release_count() - 1) as usize;
+        let attempt = node_data.params.get("attempt").copied().unwrap_or(0);
+        if !fault_injection.is_faulty(dag_id, node_data.get_id(), job_id as i32, attempt) {
+            return None;
+        }
+        let retried = attempt < fault_injection.get_max_retries(node_data);
+        let current_time = self.get_current_time();
+        self.get_log_mut().write_fault_event(FaultEvent {
+            dag_id,
+            node_id: node_data.get_id(),
+            job_id,
+            time: current_time,
+            core_id,
+            attempt,
+            retried,
+        });
+        if !retried {
+            return None;
+        }
+        // The faulty execution ends as the completed executions do.
+        self.get_log_mut().write_job_event(
+            node_data,
+            core_id,
+            job_id,
+            JobEventTimes::FinishTime(current_time),
+        );
+        let dag = &self.get_dag_set_ref()[dag_id];
+        let mut retry_node_data = dag[self.get_node_index(dag_id, node_data.get_id())].clone();
+        retry_node_data
+            .params
+            .insert("attempt".to_string(), attempt + 1);
+        Some(retry_node_data)
+    }
+
+    /// Whether to drop the `optional` node `node_data` instead of dispatching it because its DAG
+    /// would miss the deadline even if it ran the rest of the job without interference.
+    fn should_drop_optional_node(&self, node_data: &NodeData) -> bool {
+        self.get_optional_node_laxity(node_data)
+            .is_some_and(|laxity| laxity < 0)
+    }
+
+    /// Remaining laxity of the DAG if the `optional` node `node_data` started now.
+    /// `None` for mandatory nodes and nodes without `node_absolute_deadline`.
+    fn get_optional_node_laxity(&self, node_data: &NodeData) -> Option<i32> {
+        if node_data
+            .params
+            .get("optional")
+            .is_none_or(|&optional| optional == 0)
+        {
+            return None;
+        }
+        let absolute_deadline = *node_data.params.get("node_absolute_deadline")?;
+        let dag_id = node_data.get_params_value("dag_id") as usize;
+        let remaining_path_length = self.get_dag_set_ref()[dag_id]
+            .get_remaining_path_length(self.get_node_index(dag_id, node_data.get_id()));
+        Some(absolute_deadline - self.get_current_time() - remaining_path_length)
+    }
+
+    /// Skip the `optional` node `node_data`. Its successors treat it as completed.
+    fn drop_optional_node(
+        &mut self,
+        node_data: &NodeData,
+        managers: &mut [impl DAGStateManagerBase],
+    ) -> Vec<NodeData> {
+        let dag_id = node_data.get_params_value("dag_id") as usize;
+        let degradation_decision = DegradationDecision {
+            dag_id,
+            node_id: node_data.get_id(),
+            job_id: (managers[dag_id].get_release_count() - 1) as usize,
+            time: self.get_current_time(),
+            laxity: self.get_optional_node_laxity(node_data).unwrap_or(0),
+        };
+        self.get_log_mut()
+            .write_degradation_decision(degradation_decision);
+        self.complete_node(node_data, managers)
+    }
+
+    /// Update the successors, the DAG state and the triggered DAGs on completion of `node`.
+    fn complete_node(
+        &mut self,
+        node: &NodeData,
+        managers: &mut [impl DAGStateManagerBase],
+    ) -> Vec<NodeData> {
+        let mut dag_set = self.get_dag_set();
+        let current_time = self.get_current_time();
+        let priority_inheritance = self.get_priority_inheritance();
         let dag_id = node.get_params_value("dag_id") as usize;
-        let dag = &mut dag_set[dag_id];
+        let node_i = self.get_node_index(dag_id, node.get_id());
+        let log = self.get_log_mut();
+
+        let dag = &dag_set[dag_id];
 
         let mut ready_nodes = Vec::new();
-        if let Some(suc_nodes) = dag.get_suc_nodes(NodeIndex::new(node.get_id() as usize)) {
-            for suc_node in suc_nodes {
-                if dag[suc_node].params.contains_key("pre_done_count") {
-                    dag.update_param(
-                        suc_node,
-                        "pre_done_count",
-                        dag[suc_node].get_params_value("pre_done_count") + 1,
-                    );
-                } else {
-                    dag.add_param(suc_node, "pre_done_count", 1);
-                }
-                if dag.is_node_ready(suc_node) {
-                    ready_nodes.push(dag[suc_node].clone());
-                }
+        let mut is_dag_completed = false;
+        let is_sink = managers[dag_id]
+            .get_compact_dag()
+            .unwrap_or_else(|| panic!("DAG {} is not released.", dag_id))
+            .get_successors(node_i.index())
+            .is_empty();
+        if !is_sink {
+            for suc_node in managers[dag_id].complete_compact_node(node_i) {
+                ready_nodes.push(dag[suc_node].clone());
             }
         } else {
             log.write_dag_finish_time(dag_id, current_time);
-            dag.set_dag_param("pre_done_count", 0);
+            log.write_dag_quality(dag_id);
+            // Jobs with per-node deadlines have no single absolute deadline. The slack of jobs
+            // with virtual deadlines is to their native deadline.
+            if let Some(absolute_deadline) = node
+                .params
+                .get("native_absolute_deadline")
+                .or_else(|| node.params.get("node_absolute_deadline"))
+            {
+                log.write_dag_slack(dag_id, absolute_deadline - current_time);
+            }
+            if let Some(&job_id) = node.params.get("job_id") {
+                log.write_chain_finish_time(dag_id, job_id as usize, current_time);
+            }
+            managers[dag_id].reset_compact_readiness();
             managers[dag_id].complete_execution();
+            is_dag_completed = true;
+        }
+
+        // Trigger the DAGs waiting for this node or this DAG to complete.
+        for triggered_dag in dag_set.iter_mut() {
+            if let ReleaseSource::Triggered {
+                dag_id: trigger_dag_id,
+                node_id,
+            } = triggered_dag.get_release_source()
+            {
+                if trigger_dag_id == dag_id
+                    && node_id.map_or(is_dag_completed, |node_id| node_id == node.get_id())
+                {
+                    let source_i = triggered_dag.get_source_nodes()[0];
+                    let pending_trigger_count = get_pending_trigger_count(triggered_dag);
+                    triggered_dag[source_i].params.insert(
+                        "pending_trigger_count".to_string(),
+                        pending_trigger_count + 1,
+                    );
+                    if let (true, Some(&absolute_deadline), Some(&release_time)) = (
+                        priority_inheritance,
+                        node.params.get("node_absolute_deadline"),
+                        log.get_release_times(dag_id).last(),
+                    ) {
+                        donate_deadline(
+                            triggered_dag,
+                            absolute_deadline,
+                            node.get_params_value("job_id"),
+                            release_time,
+                        );
+                    }
+                }
+            }
         }
 
         self.set_dag_set(dag_set);
+        if let Some(tie_breaker) = self.get_tie_breaker_mut() {
+            for ready_node in ready_nodes.iter() {
+                tie_breaker.write_ready_time(ready_node, current_time);
+            }
+        }
 
         ready_nodes
     }
 
+    /// Drop the unfinished jobs of the DAGs in `dag_ids`, removing their nodes from the ready
+    /// queue and the cores. The next jobs of the DAGs are released as usual.
+    ///
+    /// # Returns
+    ///
+    /// * The DAGs whose jobs were dropped.
+    fn drop_jobs(
+        &mut self,
+        dag_ids: &[usize],
+        ready_queue: &mut ReadyQueue,
+        managers: &mut [impl DAGStateManagerBase],
+    ) -> Vec<usize> {
+        let current_time = self.get_current_time();
+        let is_dropped = |node_data: &NodeData| {
+            dag_ids.contains(&(node_data.get_params_value("dag_id") as usize))
+        };
+        let ready_queue_len = ready_queue.len();
+        ready_queue.retain(|wrapper| !is_dropped(&wrapper.node_data));
+        for _ in ready_queue.len()..ready_queue_len {
+            self.get_log_mut().write_ready_queue_removal();
+        }
+        for core_i in 0..self.get_processor().get_number_of_cores() {
+            if !self
+                .get_processor()
+                .get_processing_node(core_i)
+                .is_some_and(is_dropped)
+            {
+                continue;
+            }
+            let preempted_node_data = self.get_processor_mut().preempt(core_i).unwrap();
+            self.get_log_mut().write_job_event(
+                &preempted_node_data,
+                core_i,
+                (managers[preempted_node_data.get_params_value("dag_id") as usize]
+                    .get_release_count() as usize)
+                    - 1,
+                JobEventTimes::PreemptedTime(current_time),
+            );
+        }
+        let mut dropped_dag_ids = Vec::new();
+        for &dag_id in dag_ids {
+            if managers[dag_id].get_dag_state() == DAGState::Waiting {
+                continue;
+            }
+            managers[dag_id].reset_compact_readiness();
+            managers[dag_id].complete_execution();
+            self.get_log_mut().write_dropped_job(dag_id);
+            dropped_dag_ids.push(dag_id);
+        }
+        dropped_dag_ids
+    }
+
+    /// Finalize the statistics over the simulated time.
+    /// If the simulation was interrupted, the log is marked as truncated.
     fn calculate_log(&mut self) {
         let current_time = self.get_current_time();
+        let power_log = self.get_processor().get_power_log();
+        let energy_log = self.get_processor().get_energy_log();
+        let has_fault_injection = self.get_fault_injection().is_some();
         let log = self.get_log_mut();
+        if let Some(power_log) = power_log {
+            log.write_power_log(power_log);
+        }
+        if let Some(energy_log) = energy_log {
+            log.write_energy_log(energy_log);
+        }
+        if is_interrupted() {
+            log.write_truncated();
+        }
         log.calculate_utilization(current_time);
         log.calculate_response_time();
+        if has_fault_injection {
+            log.calculate_reliability();
+        }
+        log.calculate_core_share();
+        log.calculate_decision_overhead(current_time);
+        log.calculate_resource_usage();
     }
 
     fn can_preempt(
         &self,
         preemptive_type: &PreemptiveType,
+        ready_queue: &ReadyQueue,
         ready_head_node: &NodeDataWrapper,
     ) -> Option<usize> {
-        if let PreemptiveType::Preemptive {
-            key: preemptive_key,
-        } = &preemptive_type
-        {
-            let (max_value, core_i) = self
-                .get_processor()
-                .get_max_value_and_index(preemptive_key)
-                .unwrap();
-
-            if max_value
-                > ready_head_node
-                    .convert_node_data()
-                    .get_params_value(preemptive_key)
-            {
-                return Some(core_i);
-            }
-        }
-
-        None
+        let preemptive_key = preemptive_type.get_key()?;
+        ready_queue.count_comparison();
+        // Running nodes with a `preemption_threshold` are only preempted by higher priorities.
+        self.get_processor().get_preemptable_core_index_after(
+            preemptive_key,
+            ready_head_node
+                .convert_node_data()
+                .get_params_value(preemptive_key),
+            preemptive_type.get_non_preemptive_region(),
+        )
     }
 
     fn schedule(&mut self, preemptive_type: PreemptiveType) -> i32 {
+        clear_interrupted();
+        self.get_log_mut().write_schedule_start();
         // Start scheduling
         let mut managers = vec![DAGStateManager::default(); self.get_dag_set().len()];
-        let mut ready_queue = BTreeSet::new();
+        let mut ready_queue = ReadyQueue::new();
         let hyper_period = get_hyper_period(&self.get_dag_set());
-        while self.get_current_time() < hyper_period {
+        for dag in self.get_dag_set().iter() {
+            dag.validate_lock_groups();
+        }
+        let number_of_cores = self.get_processor().get_number_of_cores();
+        // (dag_id, lock_group) of the group each core is reserved for.
+        let mut core_locks = vec![None; number_of_cores];
+        let core_caps = self.get_core_utilization_caps().cloned();
+        if let Some(core_caps) = &core_caps {
+            let log = self.get_log_mut();
+            log.write_core_utilization_caps(core_caps.get_caps());
+            let total_cap: f32 = core_caps.get_caps().iter().sum();
+            if log.get_total_utilization() > total_cap {
+                warn!(
+                    "The total utilization {} of the DAG set exceeds the total utilization cap {}.",
+                    log.get_total_utilization(),
+                    total_cap
+                );
+            }
+        }
+        // Busy time of each core in the current window of the caps.
+        let mut window_busy_times = vec![0; number_of_cores];
+        #[cfg(feature = "random")]
+        let mut interrupt_bursts = self
+            .get_interrupt_model()
+            .map(|interrupt_model| interrupt_model.generate_bursts(hyper_period));
+        #[cfg(feature = "random")]
+        if interrupt_bursts.is_some() {
+            self.get_log_mut().write_interrupt_model();
+        }
+        let max_parallelisms: Vec<Option<usize>> = self
+            .get_dag_set()
+            .iter()
+            .map(|dag| dag.get_max_parallelism())
+            .collect();
+        let can_allocate = |processor: &T, node_data: &NodeData| {
+            let dag_id = node_data.get_params_value("dag_id");
+            is_below_max_parallelism(processor, dag_id, max_parallelisms[dag_id as usize])
+        };
+        // Without preemption, the holders of resources inherit the order of the ready queue.
+        let mut resource_manager = ResourceManager::new(
+            &self.get_dag_set(),
+            preemptive_type
+                .get_key()
+                .unwrap_or("node_absolute_deadline"),
+            self.get_locking_protocol(),
+        );
+        let mut criticality_manager = CriticalityManager::new(&self.get_dag_set());
+        let virtual_deadline_factor = self.get_virtual_deadline_factor();
+        while self.get_current_time() < hyper_period && !is_interrupted() {
+            // Return to the LO mode at an idle instant.
+            if let Some(criticality_manager) = &mut criticality_manager {
+                if ready_queue.is_empty()
+                    && self.get_processor().get_idle_core_indices().len() == number_of_cores
+                    && criticality_manager.return_to_lo()
+                {
+                    let current_time = self.get_current_time();
+                    self.get_log_mut().write_mode_change_event(ModeChangeEvent {
+                        time: current_time,
+                        mode: Criticality::Lo,
+                        dag_id: None,
+                        node_id: None,
+                        dropped_dag_ids: Vec::new(),
+                    });
+                }
+            }
+
             // Release DAGs
             let ready_nodes = self.release_dags(&mut managers);
-            for ready_node in ready_nodes {
-                ready_queue.insert(NodeDataWrapper {
-                    node_data: ready_node,
-                });
+            for mut ready_node in ready_nodes {
+                // The jobs of the LO-criticality DAGs are dropped on release in the HI mode.
+                let dag_id = ready_node.get_params_value("dag_id") as usize;
+                if criticality_manager
+                    .as_ref()
+                    .is_some_and(|criticality_manager| criticality_manager.is_dropped(dag_id))
+                {
+                    self.drop_jobs(&[dag_id], &mut ready_queue, &mut managers);
+                    continue;
+                }
+                // The jobs of the HI-criticality DAGs have virtual deadlines in the LO mode.
+                if let (Some(factor), Some(criticality_manager)) =
+                    (virtual_deadline_factor, &criticality_manager)
+                {
+                    if criticality_manager.get_mode() == Criticality::Lo
+                        && criticality_manager.get_dag_criticality(dag_id) == Criticality::Hi
+                    {
+                        self.set_virtual_deadline(&mut ready_node, factor);
+                    }
+                }
+                ready_queue.insert_new(self.create_node_data_wrapper(ready_node));
+                self.get_log_mut().write_ready_queue_insertion();
+            }
+
+            // Throttle the cores that used up their budget in the current window. A node running on
+            // a throttled core returns to the ready queue if `preemptive_type` lets it be
+            // preempted now, and otherwise completes before the core is throttled.
+            let mut throttled_cores = vec![false; number_of_cores];
+            if let Some(core_caps) = &core_caps {
+                let current_time = self.get_current_time();
+                let window_start = current_time - current_time % core_caps.get_window();
+                if window_start == current_time {
+                    window_busy_times.fill(0);
+                }
+                let window_length = core_caps.get_window().min(hyper_period - window_start);
+                let idle_core_indices = self.get_processor().get_idle_core_indices();
+                for core_i in 0..number_of_cores {
+                    if window_busy_times[core_i] < core_caps.get_budget(core_i, window_length) {
+                        continue;
+                    }
+                    throttled_cores[core_i] = true;
+                    if !idle_core_indices.contains(&core_i) {
+                        if preemptive_type.get_key().is_none()
+                            || self.get_processor().get_run_time(core_i)
+                                < preemptive_type.get_non_preemptive_region()
+                        {
+                            continue;
+                        }
+                        let preempted_node_data = self.get_processor_mut().preempt(core_i).unwrap();
+                        self.get_log_mut().write_job_event(
+                            &preempted_node_data,
+                            core_i,
+                            (managers[preempted_node_data.get_params_value("dag_id") as usize]
+                                .get_release_count() as usize)
+                                - 1,
+                            JobEventTimes::PreemptedTime(current_time),
+                        );
+                        ready_queue.insert_new(self.create_node_data_wrapper(preempted_node_data));
+                        self.get_log_mut().write_ready_queue_insertion();
+                    }
+                    self.get_log_mut().write_core_throttled_time(core_i);
+                }
+            }
+
+            // Cores in an interrupt burst stall their running node and accept no node.
+            #[cfg(feature = "random")]
+            let stolen_cores = match &mut interrupt_bursts {
+                Some(interrupt_bursts) => {
+                    interrupt_bursts.get_stolen_cores(self.get_current_time())
+                }
+                None => vec![false; number_of_cores],
+            };
+            #[cfg(not(feature = "random"))]
+            let stolen_cores = vec![false; number_of_cores];
+
+            // Dispatch the next member of each locked group to the core reserved for it.
+            for core_i in self.get_processor().get_idle_core_indices() {
+                if throttled_cores[core_i] || stolen_cores[core_i] {
+                    continue;
+                }
+                let Some(lock_key) = core_locks[core_i] else {
+                    continue;
+                };
+                let Some(wrapper) = ready_queue
+                    .iter()
+                    .find(|wrapper| {
+                        get_lock_key(&wrapper.node_data) == Some(lock_key)
+                            && can_allocate(self.get_processor(), &wrapper.node_data)
+                    })
+                    .cloned()
+                else {
+                    continue;
+                };
+                ready_queue.remove(&wrapper);
+                self.get_log_mut().write_ready_queue_removal();
+                let current_time = self.get_current_time();
+                if is_blocked_on_resource(
+                    &mut resource_manager,
+                    &wrapper,
+                    self.get_processor_mut(),
+                    &mut ready_queue,
+                    current_time,
+                ) {
+                    continue;
+                }
+                let node_data = wrapper.convert_node_data();
+                self.allocate_node(
+                    &node_data,
+                    core_i,
+                    managers[node_data.get_params_value("dag_id") as usize].get_release_count()
+                        as usize,
+                );
             }
 
             // Allocate nodes as long as there are idle cores, and attempt to preempt when all cores are busy.
-            while !ready_queue.is_empty() {
-                if let Some(idle_core_i) = self.get_processor().get_idle_core_index() {
+            // Cores reserved for a locked group are neither allocated to other nodes nor preempted.
+            // Nodes of DAGs running `max_parallelism` nodes are skipped.
+            while let Some(head) = self.select_ready_node(&ready_queue, can_allocate) {
+                if let Some(resource_manager) = &resource_manager {
+                    resource_manager.boost_holders(self.get_processor_mut());
+                }
+                if self.should_drop_optional_node(&head.node_data) {
+                    let node_data = take_from_ready_queue(&mut ready_queue, &head);
+                    self.get_log_mut().write_ready_queue_removal();
+                    for ready_node in self.drop_optional_node(&node_data, &mut managers) {
+                        ready_queue.insert_new(self.create_node_data_wrapper(ready_node));
+                        self.get_log_mut().write_ready_queue_insertion();
+                    }
+                    continue;
+                }
+                let idle_core_indices: Vec<usize> = self
+                    .get_processor()
+                    .get_idle_core_indices()
+                    .into_iter()
+                    .filter(|&core_i| {
+                        core_locks[core_i].is_none()
+                            && !throttled_cores[core_i]
+                            && !stolen_cores[core_i]
+                    })
+                    .collect();
+                let idle_core_selection = self.get_idle_core_selection();
+                let asleep_cores: Vec<bool> = (0..number_of_cores)
+                    .map(|core_i| self.get_processor().is_core_asleep(core_i))
+                    .collect();
+                if let Some(idle_core_i) = idle_core_selection.select(
+                    &idle_core_indices,
+                    self.get_log_mut(),
+                    &asleep_cores,
+                ) {
+                    if !self.is_dispatch_eligible(&head.node_data, &managers) {
+                        break;
+                    }
+                    let current_time = self.get_current_time();
+                    if is_blocked_on_resource(
+                        &mut resource_manager,
+                        &head,
+                        self.get_processor_mut(),
+                        &mut ready_queue,
+                        current_time,
+                    ) {
+                        self.get_log_mut().write_ready_queue_removal();
+                        continue;
+                    }
                     // Allocate the node to the idle core
-                    let node_data = ready_queue.pop_first().unwrap().convert_node_data();
+                    let node_data = take_from_ready_queue(&mut ready_queue, &head);
+                    self.get_log_mut().write_ready_queue_removal();
                     self.allocate_node(
                         &node_data,
                         idle_core_i,
                         managers[node_data.get_params_value("dag_id") as usize].get_release_count()
                             as usize,
                     );
-                } else if let Some(core_i) =
-                    self.can_preempt(&preemptive_type, ready_queue.first().unwrap())
+                    core_locks[idle_core_i] = get_lock_key(&node_data);
+                } else if let Some(core_i) = self
+                    .can_preempt(&preemptive_type, &ready_queue, &head)
+                    .filter(|&core_i| {
+                        core_locks[core_i].is_none()
+                            && !throttled_cores[core_i]
+                            && !stolen_cores[core_i]
+                    })
                 {
-                    // Preempt the node with the lowest priority
                     let current_time = self.get_current_time();
+                    if is_blocked_on_resource(
+                        &mut resource_manager,
+                        &head,
+                        self.get_processor_mut(),
+                        &mut ready_queue,
+                        current_time,
+                    ) {
+                        self.get_log_mut().write_ready_queue_removal();
+                        continue;
+                    }
+                    // Preempt the node with the lowest priority
                     let processor = self.get_processor_mut();
                     // Preempted node data
                     let preempted_node_data = processor.preempt(core_i).unwrap();
@@ -272,54 +1685,427 @@ pub trait DAGSetSchedulerBase<T: ProcessorBase + Clone> {
                         JobEventTimes::PreemptedTime(current_time),
                     );
                     // Allocate the preempted node
-                    let allocate_node_data = &ready_queue.pop_first().unwrap().convert_node_data();
+                    let allocate_node_data = &take_from_ready_queue(&mut ready_queue, &head);
+                    self.get_log_mut().write_ready_queue_removal();
                     self.allocate_node(
                         allocate_node_data,
                         core_i,
                         managers[allocate_node_data.get_params_value("dag_id") as usize]
                             .get_release_count() as usize,
                     );
+                    core_locks[core_i] = get_lock_key(allocate_node_data);
                     // Insert the preempted node into the ready queue
-                    ready_queue.insert(NodeDataWrapper {
-                        node_data: preempted_node_data,
-                    });
+                    ready_queue.insert_new(self.create_node_data_wrapper(preempted_node_data));
+                    self.get_log_mut().write_ready_queue_insertion();
                 } else {
                     break; // No core is idle and can not preempt. Exit the loop.
                 }
             }
 
+            // Count the time in which ready nodes are held back only by `max_parallelism`.
+            if self
+                .get_processor()
+                .get_idle_core_indices()
+                .into_iter()
+                .any(|core_i| {
+                    core_locks[core_i].is_none()
+                        && !throttled_cores[core_i]
+                        && !stolen_cores[core_i]
+                })
+            {
+                let throttled_dag_ids: BTreeSet<usize> = ready_queue
+                    .iter()
+                    .filter(|wrapper| !can_allocate(self.get_processor(), &wrapper.node_data))
+                    .map(|wrapper| wrapper.node_data.get_params_value("dag_id") as usize)
+                    .collect();
+                for dag_id in throttled_dag_ids {
+                    self.get_log_mut().write_throttled_time(dag_id);
+                }
+            }
+
+            for core_i in (0..number_of_cores).filter(|&core_i| stolen_cores[core_i]) {
+                let stalled_dag_id = self
+                    .get_processor()
+                    .get_processing_node(core_i)
+                    .map(|node_data| node_data.get_params_value("dag_id") as usize);
+                self.get_log_mut()
+                    .write_core_stolen_time(core_i, stalled_dag_id);
+            }
+
+            // The nodes executed in this unit advance their critical sections and LO budgets.
+            let executed_nodes: Vec<NodeData> = if resource_manager.is_some()
+                || criticality_manager.is_some()
+            {
+                (0..number_of_cores)
+                    .filter(|&core_i| !stolen_cores[core_i])
+                    .filter_map(|core_i| self.get_processor().get_processing_node(core_i).cloned())
+                    .collect()
+            } else {
+                Vec::new()
+            };
+
             // Process unit time
-            let process_result = self.process_unit_time();
+            let mut process_result = if stolen_cores.contains(&true) {
+                self.set_current_time(self.get_current_time() + 1);
+                self.get_processor_mut().process_except(&stolen_cores)
+            } else {
+                self.process_unit_time()
+            };
             // TODO: Will be refactoring the core structure to have a core log.
             // Write the processing time of the core to the log.
             let log = self.get_log_mut();
-            let indices: Vec<usize> = get_process_core_indices(&process_result);
+            let indices: Vec<usize> = get_process_core_indices(&process_result)
+                .into_iter()
+                .filter(|&core_i| !stolen_cores[core_i])
+                .collect();
             log.write_processing_time(&indices);
+            for &core_i in indices.iter() {
+                window_busy_times[core_i] += 1;
+            }
+
+            // Hand the released resources to the blocked nodes.
+            if let Some(resource_manager) = &mut resource_manager {
+                let current_time = self.get_current_time();
+                let blocking_events = resource_manager.advance(
+                    &executed_nodes,
+                    &mut process_result,
+                    self.get_processor_mut(),
+                    &mut ready_queue,
+                    current_time,
+                );
+                for blocking_event in blocking_events {
+                    self.get_log_mut().write_blocking_event(blocking_event);
+                    self.get_log_mut().write_ready_queue_insertion();
+                }
+            }
 
             // Post-process on completion of node execution
             for (core_id, result) in process_result.iter().enumerate() {
                 if let ProcessResult::Done(node_data) = result {
+                    // A faulty execution is re-executed, and the core keeps its reservation.
+                    if let Some(retry_node_data) =
+                        self.retry_on_fault(node_data, core_id, &managers)
+                    {
+                        ready_queue.insert_new(self.create_node_data_wrapper(retry_node_data));
+                        self.get_log_mut().write_ready_queue_insertion();
+                        continue;
+                    }
+                    // Keep the core reserved until the last member of the locked group completes.
+                    if core_locks[core_id].is_some() {
+                        core_locks[core_id] = get_core_lock(
+                            self.get_dag_set_ref(),
+                            self.get_node_index_maps(),
+                            node_data,
+                        );
+                    }
                     let ready_nodes =
                         self.post_process_on_node_completion(node_data, core_id, &mut managers);
                     for ready_node in ready_nodes {
-                        ready_queue.insert(NodeDataWrapper {
-                            node_data: ready_node,
-                        });
+                        ready_queue.insert_new(self.create_node_data_wrapper(ready_node));
+                        self.get_log_mut().write_ready_queue_insertion();
+                    }
+                }
+            }
+
+            // Switch to the HI mode on an overrun, dropping the jobs of the LO-criticality DAGs.
+            if let Some(criticality_manager) = &mut criticality_manager {
+                if let Some(overrun_node) =
+                    criticality_manager.advance(&executed_nodes, &process_result)
+                {
+                    let dropped_dag_ids = self.drop_jobs(
+                        &criticality_manager.get_dropped_dag_ids(),
+                        &mut ready_queue,
+                        &mut managers,
+                    );
+                    if virtual_deadline_factor.is_some() {
+                        self.restore_native_deadlines(&mut ready_queue);
+                    }
+                    for core_lock in core_locks.iter_mut() {
+                        if core_lock.is_some_and(|(dag_id, _)| {
+                            criticality_manager.is_dropped(dag_id as usize)
+                        }) {
+                            *core_lock = None;
+                        }
                     }
+                    let current_time = self.get_current_time();
+                    self.get_log_mut().write_mode_change_event(ModeChangeEvent {
+                        time: current_time,
+                        mode: Criticality::Hi,
+                        dag_id: Some(overrun_node.get_params_value("dag_id") as usize),
+                        node_id: Some(overrun_node.get_id()),
+                        dropped_dag_ids,
+                    });
                 }
             }
         }
 
+        self.get_log_mut()
+            .write_comparisons(ready_queue.get_comparison_count());
         self.calculate_log();
         self.get_current_time()
     }
 
+    /// Add the metrics computed by the Rhai script at `script_path` to the log.
+    #[cfg(feature = "plugin")]
+    fn run_post_processing_script(&mut self, script_path: &str) {
+        let metrics = crate::plugin::run_post_processing_script(script_path, &*self.get_log_mut());
+        self.get_log_mut().write_plugin_metrics(metrics);
+    }
+
+    #[cfg(feature = "yaml")]
     fn dump_log(&mut self, dir_path: &str, alg_name: &str) -> String {
         let file_path = create_scheduler_log_yaml(dir_path, alg_name);
         self.get_log_mut().dump_log_to_yaml(&file_path);
 
         file_path
     }
+
+    /// Dump the log and the result as a single document in `format`.
+    #[cfg(feature = "yaml")]
+    fn dump_simulation_log(
+        &mut self,
+        dir_path: &str,
+        alg_name: &str,
+        result: bool,
+        format: LogFormat,
+    ) -> String {
+        let file_path = create_scheduler_log_file(dir_path, alg_name, format.get_extension());
+        SimulationLog::new(&*self.get_log_mut(), result).dump(&file_path, format);
+
+        file_path
+    }
+
+    /// Dump only the DAGs and the events selected by `filter` (see `DAGSetSchedulerLog::filter`).
+    #[cfg(feature = "yaml")]
+    fn dump_filtered_simulation_log(
+        &mut self,
+        dir_path: &str,
+        alg_name: &str,
+        result: bool,
+        format: LogFormat,
+        filter: &LogFilter,
+    ) -> String {
+        let file_path = create_scheduler_log_file(dir_path, alg_name, format.get_extension());
+        SimulationLog::new(self.get_log_mut().filter(filter), result).dump(&file_path, format);
+
+        file_path
+    }
+
+    /// Dump the log, a summary and a manifest into a new run directory under `dir_path`.
+    /// The directory is named after the input `dag_set` as in `dump_fixture`.
+    ///
+    /// # Returns
+    ///
+    /// * The path of the run directory.
+    #[cfg(all(feature = "yaml", feature = "analysis"))]
+    fn dump_run_dir(
+        &mut self,
+        dir_path: &str,
+        alg_name: &str,
+        dag_set: &[Graph<NodeData, i32>],
+        result: bool,
+        format: LogFormat,
+    ) -> String {
+        let run_dir = RunDirectory::create(dir_path, alg_name, dag_set);
+        let log = &*self.get_log_mut();
+        SimulationLog::new(log, result).dump(
+            &run_dir.get_file_path("log", format.get_extension()),
+            format,
+        );
+        run_dir.write_summary(&RunSummary {
+            alg_name: alg_name.to_string(),
+            status: RunStatus::Simulated,
+            result,
+            worst_response_times: log.get_worst_response_times(),
+            screening_violations: Vec::new(),
+            resource_usage: log.get_resource_usage(),
+            slack: log.get_slack_summaries(),
+            score: Some(calculate_schedule_score(log, &ScoreWeights::default())),
+        });
+        run_dir.write_manifest(self.get_processor().get_number_of_cores(), dag_set.len());
+
+        run_dir.get_path().to_string()
+    }
+
+    /// Dump the log and the result as YAML to the fixture of `alg_name` (see `fixture`).
+    /// The fixture is named after the input `dag_set` because scheduling adds params to the
+    /// DAGs of the scheduler.
+    ///
+    /// # Returns
+    ///
+    /// * The path of the fixture.
+    #[cfg(all(feature = "yaml", feature = "analysis"))]
+    fn dump_fixture(
+        &mut self,
+        alg_name: &str,
+        dag_set: &[Graph<NodeData, i32>],
+        result: bool,
+    ) -> String {
+        std::fs::create_dir_all(FIXTURE_DIR).unwrap();
+        let fixture_path = get_fixture_path(alg_name, dag_set);
+        SimulationLog::new(&*self.get_log_mut(), result).dump(&fixture_path, LogFormat::Yaml);
+
+        fixture_path
+    }
+
+    /// Write a run directory with only the summary and the manifest for a DAG set that failed
+    /// the schedulability screening (see `screen_dag_set`) and was not simulated.
+    #[cfg(all(feature = "yaml", feature = "analysis"))]
+    fn dump_screened_out_run_dir(
+        &self,
+        dir_path: &str,
+        alg_name: &str,
+        dag_set: &[Graph<NodeData, i32>],
+        screening_violations: Vec<ScreeningViolation>,
+    ) -> String {
+        let run_dir = RunDirectory::create(dir_path, alg_name, dag_set);
+        run_dir.write_summary(&RunSummary {
+            alg_name: alg_name.to_string(),
+            status: RunStatus::ScreenedOut,
+            result: false,
+            worst_response_times: Vec::new(),
+            screening_violations,
+            resource_usage: None,
+            slack: Vec::new(),
+            score: None,
+        });
+        run_dir.write_manifest(self.get_processor().get_number_of_cores(), dag_set.len());
+
+        run_dir.get_path().to_string()
+    }
+}
+
+/// Set `execution_time` of each node to its time in the trace for the job `job_index`,
+/// or to the WCET (kept as `wcet`) if the trace has no entry.
+fn apply_execution_time_trace(
+    dag: &mut Graph<NodeData, i32>,
+    trace: &ExecutionTimeTrace,
+    dag_id: usize,
+    job_index: i32,
+) {
+    for node_i in dag.node_indices() {
+        let node_data = &mut dag[node_i];
+        let execution_time = node_data.get_params_value("execution_time");
+        let wcet = *node_data
+            .params
+            .entry("wcet".to_string())
+            .or_insert(execution_time);
+        let exec_time = trace
+            .get_exec_time(dag_id, node_data.id, job_index)
+            .unwrap_or(wcet);
+        node_data
+            .params
+            .insert("execution_time".to_string(), exec_time);
+    }
+}
+
+/// Leave the absolute deadline of a triggering job on the source of the triggered DAG until it
+/// is released. If triggers pile up, the earliest deadline and the first trigger are kept.
+fn donate_deadline(
+    triggered_dag: &mut Graph<NodeData, i32>,
+    absolute_deadline: i32,
+    job_id: i32,
+    release_time: i32,
+) {
+    let source_i = triggered_dag.get_source_nodes()[0];
+    let source_params = &mut triggered_dag[source_i].params;
+    let donated_deadline = source_params
+        .entry("donated_deadline".to_string())
+        .or_insert(absolute_deadline);
+    *donated_deadline = (*donated_deadline).min(absolute_deadline);
+    source_params
+        .entry("donor_job_id".to_string())
+        .or_insert(job_id);
+    source_params
+        .entry("donor_release_time".to_string())
+        .or_insert(release_time);
+}
+
+/// Remove the deadline donated to the triggered DAG being released.
+///
+/// # Returns
+///
+/// * The donated deadline, and the job id and the release time of the donor.
+fn take_donated_deadline(triggered_dag: &mut Graph<NodeData, i32>) -> Option<(i32, i32, i32)> {
+    let source_i = triggered_dag.get_source_nodes()[0];
+    let source_params = &mut triggered_dag[source_i].params;
+    let donated_deadline = source_params.remove("donated_deadline")?;
+    Some((
+        donated_deadline,
+        source_params.remove("donor_job_id").unwrap(),
+        source_params.remove("donor_release_time").unwrap(),
+    ))
+}
+
+/// Number of triggers that have not yet released the triggered DAG.
+fn get_pending_trigger_count(dag: &Graph<NodeData, i32>) -> i32 {
+    dag[dag.get_source_nodes()[0]]
+        .params
+        .get("pending_trigger_count")
+        .copied()
+        .unwrap_or(0)
+}
+
+/// Whether the DAG `dag_id` runs fewer nodes than `max_parallelism` (`None` means no limit).
+pub fn is_below_max_parallelism(
+    processor: &impl ProcessorBase,
+    dag_id: i32,
+    max_parallelism: Option<usize>,
+) -> bool {
+    max_parallelism.is_none_or(|max_parallelism| {
+        processor.get_number_of_running_nodes(dag_id) < max_parallelism
+    })
+}
+
+/// Whether `wrapper` blocks on a resource held by another node (see `ResourceManager::try_lock`),
+/// in which case it is taken out of the ready queue if still there.
+fn is_blocked_on_resource(
+    resource_manager: &mut Option<ResourceManager>,
+    wrapper: &NodeDataWrapper,
+    processor: &mut impl ProcessorBase,
+    ready_queue: &mut ReadyQueue,
+    current_time: i32,
+) -> bool {
+    let Some(resource_manager) = resource_manager else {
+        return false;
+    };
+    if resource_manager.try_lock(wrapper, processor, ready_queue, current_time) {
+        return false;
+    }
+    ready_queue.remove(wrapper);
+    true
+}
+
+/// Remove `wrapper` from the ready queue. The head is popped without comparisons.
+fn take_from_ready_queue(ready_queue: &mut ReadyQueue, wrapper: &NodeDataWrapper) -> NodeData {
+    if ready_queue.first() == Some(wrapper) {
+        ready_queue.pop_first();
+    } else {
+        ready_queue.remove(wrapper);
+    }
+    wrapper.convert_node_data()
+}
+
+/// Return `(dag_id, lock_group)` if the node belongs to a locked group.
+fn get_lock_key(node_data: &NodeData) -> Option<(i32, i32)> {
+    node_data
+        .params
+        .get("lock_group")
+        .map(|&lock_group| (node_data.get_params_value("dag_id"), lock_group))
+}
+
+/// Return the lock to keep on the core after `node_data` completes on it,
+/// i.e., `(dag_id, lock_group)` if the node is followed by another member of its group.
+fn get_core_lock(
+    dag_set: &[Graph<NodeData, i32>],
+    node_index_maps: &[NodeIndexMap],
+    node_data: &NodeData,
+) -> Option<(i32, i32)> {
+    let lock_key = get_lock_key(node_data)?;
+    let dag_id = lock_key.0 as usize;
+    dag_set[dag_id]
+        .get_lock_group_successor(node_index_maps[dag_id].get_node_index(node_data.id))
+        .map(|_| lock_key)
 }
 
 #[macro_export]
@@ -328,9 +2114,15 @@ macro_rules! getset_dag_set_scheduler {
         fn get_dag_set(&self) -> Vec<Graph<NodeData, i32>>{
             self.dag_set.clone()
         }
+        fn get_dag_set_ref(&self) -> &[Graph<NodeData, i32>]{
+            &self.dag_set
+        }
         fn set_dag_set(&mut self, dag_set: Vec<Graph<NodeData, i32>>){
             self.dag_set = dag_set;
         }
+        fn get_node_index_maps(&self) -> &[NodeIndexMap]{
+            &self.node_index_maps
+        }
         fn get_processor_mut(&mut self) -> &mut $t{
             &mut self.processor
         }
@@ -348,3 +2140,122 @@ macro_rules! getset_dag_set_scheduler {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn create_wrapper(params: &[(&str, i32)], id: i32) -> NodeDataWrapper {
+        let params: BTreeMap<String, i32> = params
+            .iter()
+            .map(|&(key, value)| (key.to_string(), value))
+            .collect();
+        NodeDataWrapper::new(NodeData { id, params })
+    }
+
+    fn create_job(deadline: i32, dag_id: i32, node_id: i32, job_id: i32) -> NodeDataWrapper {
+        create_wrapper(
+            &[
+                ("node_absolute_deadline", deadline),
+                ("dag_id", dag_id),
+                ("job_id", job_id),
+            ],
+            node_id,
+        )
+    }
+
+    #[test]
+    fn test_node_data_wrapper_order_by_each_key() {
+        let base = create_job(10, 1, 1, 1);
+        // Each key decides the order only if the preceding keys are equal.
+        assert!(create_job(9, 2, 2, 2) < base);
+        assert!(create_job(10, 0, 2, 2) < base);
+        assert!(create_job(10, 1, 0, 2) < base);
+        assert!(create_job(10, 1, 1, 0) < base);
+        assert_eq!(base.cmp(&create_job(10, 1, 1, 1)), Ordering::Equal);
+        assert!(create_job(11, 0, 0, 0) > base);
+    }
+
+    #[test]
+    fn test_node_data_wrapper_order_int_scaled_deadline() {
+        let segment = |int_scaled_deadline: i32, node_deadline: i32, node_id: i32| {
+            create_wrapper(
+                &[
+                    ("int_scaled_node_absolute_deadline", int_scaled_deadline),
+                    ("node_absolute_deadline", node_deadline),
+                    ("dag_id", 0),
+                ],
+                node_id,
+            )
+        };
+        // Both nodes have the scaled deadline, so node_absolute_deadline is ignored.
+        assert!(segment(5, 20, 0) < segment(6, 10, 1));
+        // Otherwise node_absolute_deadline is compared.
+        assert!(create_job(10, 0, 1, 0) < segment(5, 20, 0));
+    }
+
+    #[test]
+    fn test_node_data_wrapper_order_independent_of_insertion_order() {
+        let jobs = vec![
+            create_job(10, 1, 0, 0),
+            create_job(10, 0, 1, 0),
+            create_job(10, 0, 0, 1),
+            create_job(10, 0, 0, 0),
+            create_job(5, 2, 3, 0),
+        ];
+        let expected = vec![
+            create_job(5, 2, 3, 0),
+            create_job(10, 0, 0, 0),
+            create_job(10, 0, 0, 1),
+            create_job(10, 0, 1, 0),
+            create_job(10, 1, 0, 0),
+        ];
+        let ready_queue: BTreeSet<NodeDataWrapper> = jobs.iter().cloned().collect();
+        let reversed_ready_queue: BTreeSet<NodeDataWrapper> = jobs.into_iter().rev().collect();
+        assert_eq!(ready_queue.into_iter().collect::<Vec<_>>(), expected);
+        assert_eq!(
+            reversed_ready_queue.into_iter().collect::<Vec<_>>(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_ready_queue_insert_duplicate() {
+        let mut ready_queue = ReadyQueue::new();
+        assert!(ready_queue.insert(create_job(10, 0, 0, 0)));
+        assert!(!ready_queue.insert(create_job(10, 0, 0, 0)));
+        assert_eq!(ready_queue.len(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Node 0 of DAG Some(0) is already in the ready queue.")]
+    fn test_ready_queue_insert_new_duplicate() {
+        let mut ready_queue = ReadyQueue::new();
+        ready_queue.insert_new(create_job(10, 0, 0, 0));
+        ready_queue.insert_new(create_job(10, 0, 0, 0));
+    }
+
+    #[test]
+    fn test_tie_breaker_remaining_work() {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_wrapper(&[("execution_time", 1)], 0).node_data);
+        let n1 = dag.add_node(create_wrapper(&[("execution_time", 3)], 1).node_data);
+        let n2 = dag.add_node(create_wrapper(&[("execution_time", 2)], 2).node_data);
+        dag.add_edge(n0, n1, 1);
+        dag.add_edge(n0, n2, 1);
+        dag.set_dag_param("dag_id", 0);
+        let dag_set = [dag];
+        let tie_breaker = TieBreaker::new(TieBreakingPolicy::RemainingWork, &dag_set);
+
+        let keys: Vec<i32> = dag_set[0]
+            .node_weights()
+            .map(|node| tie_breaker.get_tie_break_key(node))
+            .collect();
+        // The longer remaining path first, without writing the keys to the DAG.
+        assert_eq!(keys, vec![-4, -3, -2]);
+        assert!(dag_set[0]
+            .node_weights()
+            .all(|node| !node.params.contains_key("tie_break_key")));
+    }
+}
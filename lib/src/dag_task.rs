@@ -0,0 +1,165 @@
+//! A DAG with its DAG-level params held as metadata instead of node params.
+//!
+//! In the node representation, `period` and `offset` live on the source node,
+//! `end_to_end_deadline` on the sink node and `dag_id` on every node, so a param on the wrong
+//! node is silently ignored. `DagTask` reads them once and keeps the graph free of them.
+//! `release_dags` and `DAGInfo` read the DAG-level params through `DagTask`.
+use crate::graph_extension::{GraphExtension, NodeData};
+use petgraph::Graph;
+use serde_derive::{Deserialize, Serialize};
+
+const DAG_LEVEL_PARAM_KEYS: [&str; 4] = ["period", "end_to_end_deadline", "offset", "dag_id"];
+
+#[derive(Debug, Clone)]
+pub struct DagTask {
+    pub dag_id: Option<usize>,
+    pub period: Option<i32>,
+    pub end_to_end_deadline: Option<i32>,
+    pub offset: i32,
+    /// The graph without DAG-level params.
+    pub graph: Graph<NodeData, i32>,
+}
+
+impl DagTask {
+    /// Move the DAG-level params out of the node params (see `get_dag_params`).
+    /// Periods of non-source nodes (multi-rate DAGs) remain node params.
+    pub fn from_graph(graph: &Graph<NodeData, i32>) -> Self {
+        let dag_params = graph.get_dag_params();
+        let dag_id = graph
+            .node_weights()
+            .find_map(|node_data| node_data.params.get("dag_id"))
+            .map(|&dag_id| dag_id as usize);
+
+        let mut graph = graph.clone();
+        for key in DAG_LEVEL_PARAM_KEYS {
+            for (node_i, _) in graph.get_dag_param_values(key) {
+                graph[node_i].params.remove(key);
+            }
+        }
+
+        Self {
+            dag_id,
+            period: dag_params.period,
+            end_to_end_deadline: dag_params.end_to_end_deadline,
+            offset: dag_params.offset.unwrap_or(0),
+            graph,
+        }
+    }
+
+    /// The node representation read by the schedulers: `period` and `offset` on the first
+    /// source node, `end_to_end_deadline` on the first sink node and `dag_id` on every node.
+    pub fn to_graph(&self) -> Graph<NodeData, i32> {
+        let mut graph = self.graph.clone();
+        let source_i = graph.get_source_nodes()[0];
+        let sink_i = graph.get_sink_nodes()[0];
+        if let Some(period) = self.period {
+            graph.add_param(source_i, "period", period);
+        }
+        if self.offset != 0 {
+            graph.add_param(source_i, "offset", self.offset);
+        }
+        if let Some(end_to_end_deadline) = self.end_to_end_deadline {
+            graph.add_param(sink_i, "end_to_end_deadline", end_to_end_deadline);
+        }
+        if let Some(dag_id) = self.dag_id {
+            graph.set_dag_param("dag_id", dag_id as i32);
+        }
+        graph
+    }
+
+    /// `end_to_end_deadline`, or `period` for implicit deadlines.
+    pub fn get_relative_deadline(&self) -> Option<i32> {
+        self.end_to_end_deadline.or(self.period)
+    }
+
+    pub fn get_utilization(&self) -> Option<f32> {
+        self.period
+            .map(|period| self.graph.get_volume() as f32 / period as f32)
+    }
+}
+
+pub fn create_dag_task_set(dag_set: &[Graph<NodeData, i32>]) -> Vec<DagTask> {
+    dag_set.iter().map(DagTask::from_graph).collect()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UtilizationClass {
+    Light,
+    Heavy,
+}
+
+impl UtilizationClass {
+    /// Read the class from the `utilization_class` param of the source node.
+    pub fn from_dag(dag: &Graph<NodeData, i32>) -> Option<Self> {
+        let source_i = *dag.get_source_nodes().first()?;
+        match dag[source_i].params.get("utilization_class") {
+            Some(0) => Some(Self::Light),
+            Some(1) => Some(Self::Heavy),
+            Some(value) => panic!("Unknown utilization_class: {}", value),
+            None => None,
+        }
+    }
+
+    /// Classify the DAG by its utilization (volume / period) as in federated scheduling:
+    /// heavy if the utilization exceeds one, i.e., the DAG needs more than one core.
+    /// `None` if the period is not set.
+    pub fn classify(dag: &Graph<NodeData, i32>) -> Option<Self> {
+        Self::classify_dag_task(&DagTask::from_graph(dag))
+    }
+
+    /// `classify` for a DAG already converted to a `DagTask`.
+    pub fn classify_dag_task(dag_task: &DagTask) -> Option<Self> {
+        let utilization = dag_task.get_utilization()?;
+        Some(if utilization > 1.0 {
+            Self::Heavy
+        } else {
+            Self::Light
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::create_node;
+
+    fn create_sample_dag() -> Graph<NodeData, i32> {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 3));
+        let n1 = dag.add_node(create_node(1, "execution_time", 5));
+        dag.add_param(n0, "period", 20);
+        dag.add_param(n0, "offset", 2);
+        dag.add_param(n1, "end_to_end_deadline", 15);
+        dag.add_edge(n0, n1, 0);
+        dag.set_dag_param("dag_id", 4);
+        dag
+    }
+
+    #[test]
+    fn test_dag_task_from_graph_normal() {
+        let dag_task = DagTask::from_graph(&create_sample_dag());
+        assert_eq!(dag_task.dag_id, Some(4));
+        assert_eq!(dag_task.period, Some(20));
+        assert_eq!(dag_task.end_to_end_deadline, Some(15));
+        assert_eq!(dag_task.offset, 2);
+        assert_eq!(dag_task.get_relative_deadline(), Some(15));
+        assert_eq!(dag_task.get_utilization(), Some(0.4));
+        for node_data in dag_task.graph.node_weights() {
+            assert_eq!(
+                node_data.params.keys().collect::<Vec<_>>(),
+                ["execution_time"]
+            );
+        }
+    }
+
+    #[test]
+    fn test_dag_task_round_trip() {
+        let dag = create_sample_dag();
+        let restored_dag = DagTask::from_graph(&dag).to_graph();
+        for node_i in dag.node_indices() {
+            assert_eq!(restored_dag[node_i], dag[node_i]);
+        }
+        assert_eq!(restored_dag.edge_count(), dag.edge_count());
+    }
+}
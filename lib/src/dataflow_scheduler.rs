@@ -0,0 +1,375 @@
+//! Synchronous dataflow (SDF) execution of DAGs with bounded buffers on the edges.
+//!
+//! Instead of running once per DAG job, each node fires repeatedly until the end of the hyper
+//! period, as in a streaming pipeline. A firing consumes `consumed_tokens` (default 1) from each
+//! incoming edge and produces `produced_tokens` (default 1) to each outgoing edge.
+//! The buffer of an edge holds at most the `buffer_capacity` of its consumer, which defaults to
+//! the minimum deadlock-free capacity of the edge, `produced + consumed - gcd(produced, consumed)`.
+//! A node is ready when each input buffer has enough tokens and each output buffer has enough
+//! room, so a producer runs ahead of its consumer only until the buffer fills (backpressure).
+//! Source nodes fire whenever their outputs have room.
+//!
+//! A firing consumes its input tokens and reserves its output space when it starts, and produces
+//! its output tokens when it finishes. A node fires at most once at a time, and an idle core
+//! takes the ready node that has waited longest since its last firing. Communication times are
+//! ignored. The k-th firing of the first sink node finishes the DAG job released by the k-th
+//! firing of the first source node, so the response time is the latency through the pipeline.
+use crate::dag_set_scheduler::{DAGSetSchedulerBase, PreemptiveType};
+use crate::getset_dag_set_scheduler;
+use crate::{
+    core::ProcessResult,
+    graph_extension::{get_node_index_maps, GraphExtension, NodeData, NodeIndexMap},
+    homogeneous::HomogeneousProcessor,
+    log::{BufferLog, DAGSetSchedulerLog, JobEventTimes},
+    processor::ProcessorBase,
+    util::{clear_interrupted, get_hyper_period, get_process_core_indices, is_interrupted},
+};
+use num_integer::gcd;
+use petgraph::{
+    graph::{Graph, NodeIndex},
+    visit::EdgeRef,
+    Direction::{Incoming, Outgoing},
+};
+
+/// The minimum capacity with which an edge alone never deadlocks.
+pub fn get_default_buffer_capacity(produced_tokens: i32, consumed_tokens: i32) -> i32 {
+    produced_tokens + consumed_tokens - gcd(produced_tokens, consumed_tokens)
+}
+
+fn get_tokens_param(node_data: &NodeData, key: &str) -> i32 {
+    let tokens = node_data.params.get(key).copied().unwrap_or(1);
+    if tokens <= 0 {
+        panic!("{} of node {} must be positive.", key, node_data.id);
+    }
+    tokens
+}
+
+struct Buffer {
+    dag_id: usize,
+    producer_i: NodeIndex,
+    consumer_i: NodeIndex,
+    produced_tokens: i32,
+    consumed_tokens: i32,
+    capacity: i32,
+    tokens: i32,
+    reserved: i32,
+}
+
+impl Buffer {
+    fn has_tokens(&self) -> bool {
+        self.tokens >= self.consumed_tokens
+    }
+
+    fn has_room(&self) -> bool {
+        self.tokens + self.reserved + self.produced_tokens <= self.capacity
+    }
+}
+
+#[derive(Clone, Default)]
+struct NodeState {
+    is_running: bool,
+    firing_count: usize,
+    last_start_time: Option<i32>,
+}
+
+pub struct DataflowScheduler {
+    dag_set: Vec<Graph<NodeData, i32>>,
+    node_index_maps: Vec<NodeIndexMap>,
+    processor: HomogeneousProcessor,
+    log: DAGSetSchedulerLog,
+    current_time: i32,
+    /// Indexed in the same order as the buffer logs.
+    buffers: Vec<Buffer>,
+}
+
+impl DAGSetSchedulerBase<HomogeneousProcessor> for DataflowScheduler {
+    fn new(dag_set: &[Graph<NodeData, i32>], processor: &HomogeneousProcessor) -> Self {
+        let mut log = DAGSetSchedulerLog::new(dag_set, processor.get_number_of_cores());
+        let mut buffers = Vec::new();
+        for dag in dag_set {
+            let dag_id = dag.get_dag_param("dag_id") as usize;
+            for edge in dag.edge_references() {
+                let (producer, consumer) = (&dag[edge.source()], &dag[edge.target()]);
+                let produced_tokens = get_tokens_param(producer, "produced_tokens");
+                let consumed_tokens = get_tokens_param(consumer, "consumed_tokens");
+                let capacity = consumer
+                    .params
+                    .get("buffer_capacity")
+                    .copied()
+                    .unwrap_or_else(|| {
+                        get_default_buffer_capacity(produced_tokens, consumed_tokens)
+                    });
+                if capacity < produced_tokens.max(consumed_tokens) {
+                    panic!(
+                        "buffer_capacity of node {} of DAG {} must be at least {}.",
+                        consumer.id,
+                        dag_id,
+                        produced_tokens.max(consumed_tokens)
+                    );
+                }
+                log.add_buffer_log(BufferLog::new(dag_id, producer.id, consumer.id, capacity));
+                buffers.push(Buffer {
+                    dag_id,
+                    producer_i: edge.source(),
+                    consumer_i: edge.target(),
+                    produced_tokens,
+                    consumed_tokens,
+                    capacity,
+                    tokens: 0,
+                    reserved: 0,
+                });
+            }
+        }
+
+        Self {
+            node_index_maps: get_node_index_maps(dag_set),
+            dag_set: dag_set.to_vec(),
+            processor: processor.clone(),
+            log,
+            current_time: 0,
+            buffers,
+        }
+    }
+
+    getset_dag_set_scheduler!(HomogeneousProcessor);
+
+    /// The preemptive type is ignored because a firing cannot be preempted.
+    fn schedule(&mut self, _preemptive_type: PreemptiveType) -> i32 {
+        clear_interrupted();
+        self.log.write_schedule_start();
+        let mut node_states: Vec<Vec<NodeState>> = self
+            .dag_set
+            .iter()
+            .map(|dag| vec![NodeState::default(); dag.node_count()])
+            .collect();
+        // Start times of the firings of the first source node of each DAG.
+        let mut release_times: Vec<Vec<i32>> = vec![Vec::new(); self.dag_set.len()];
+        let hyper_period = get_hyper_period(&self.dag_set);
+        while self.current_time < hyper_period && !is_interrupted() {
+            // Fire ready nodes on idle cores.
+            while let Some(idle_core_i) = self.processor.get_idle_core_index() {
+                let Some((dag_id, node_i)) = self.get_next_ready_node(&node_states) else {
+                    break;
+                };
+                if node_i == self.dag_set[dag_id].get_source_nodes()[0] {
+                    release_times[dag_id].push(self.current_time);
+                }
+                self.start_firing(
+                    dag_id,
+                    node_i,
+                    idle_core_i,
+                    &mut node_states[dag_id][node_i.index()],
+                );
+            }
+            self.write_blocked_time(&node_states);
+
+            // Process unit time
+            self.current_time += 1;
+            let process_result = self.processor.process();
+            let indices: Vec<usize> = get_process_core_indices(&process_result);
+            self.log.write_processing_time(&indices);
+
+            // Produce the output tokens of the finished firings
+            for (core_id, result) in process_result.iter().enumerate() {
+                if let ProcessResult::Done(node_data) = result {
+                    let dag_id = node_data.get_params_value("dag_id") as usize;
+                    let node_i = self.get_node_index(dag_id, node_data.id);
+                    let node_state = &mut node_states[dag_id][node_i.index()];
+                    self.finish_firing(node_data, core_id, node_state);
+                    if node_i != self.dag_set[dag_id].get_sink_nodes()[0] {
+                        continue;
+                    }
+                    // Firings in different rates may leave the finished firing without a release.
+                    if let Some(&release_time) =
+                        release_times[dag_id].get(node_state.firing_count - 1)
+                    {
+                        self.log.write_dag_release_time(dag_id, release_time);
+                        self.log.write_dag_finish_time(dag_id, self.current_time);
+                    }
+                }
+            }
+        }
+
+        self.calculate_log();
+        self.get_current_time()
+    }
+}
+
+impl DataflowScheduler {
+    fn get_buffer_indices(
+        &self,
+        dag_id: usize,
+        node_i: NodeIndex,
+        direction: petgraph::Direction,
+    ) -> Vec<usize> {
+        self.buffers
+            .iter()
+            .enumerate()
+            .filter(|(_, buffer)| {
+                buffer.dag_id == dag_id
+                    && match direction {
+                        Incoming => buffer.consumer_i == node_i,
+                        Outgoing => buffer.producer_i == node_i,
+                    }
+            })
+            .map(|(buffer_i, _)| buffer_i)
+            .collect()
+    }
+
+    fn has_input_tokens(&self, dag_id: usize, node_i: NodeIndex) -> bool {
+        self.get_buffer_indices(dag_id, node_i, Incoming)
+            .into_iter()
+            .all(|buffer_i| self.buffers[buffer_i].has_tokens())
+    }
+
+    fn has_output_room(&self, dag_id: usize, node_i: NodeIndex) -> bool {
+        self.get_buffer_indices(dag_id, node_i, Outgoing)
+            .into_iter()
+            .all(|buffer_i| self.buffers[buffer_i].has_room())
+    }
+
+    /// The ready node that has waited longest since its last firing.
+    fn get_next_ready_node(&self, node_states: &[Vec<NodeState>]) -> Option<(usize, NodeIndex)> {
+        self.dag_set
+            .iter()
+            .enumerate()
+            .flat_map(|(dag_id, dag)| dag.node_indices().map(move |node_i| (dag_id, node_i)))
+            .filter(|&(dag_id, node_i)| {
+                !node_states[dag_id][node_i.index()].is_running
+                    && self.has_input_tokens(dag_id, node_i)
+                    && self.has_output_room(dag_id, node_i)
+            })
+            .min_by_key(|&(dag_id, node_i)| {
+                (
+                    node_states[dag_id][node_i.index()].last_start_time,
+                    dag_id,
+                    node_i,
+                )
+            })
+    }
+
+    fn write_buffer_state(&mut self, buffer_i: usize) {
+        let buffer = &self.buffers[buffer_i];
+        self.log
+            .write_buffer_state(buffer_i, self.current_time, buffer.tokens, buffer.reserved);
+    }
+
+    /// Consume the input tokens, reserve the output space and allocate the node to `core_i`.
+    fn start_firing(
+        &mut self,
+        dag_id: usize,
+        node_i: NodeIndex,
+        core_i: usize,
+        node_state: &mut NodeState,
+    ) {
+        for buffer_i in self.get_buffer_indices(dag_id, node_i, Incoming) {
+            let buffer = &mut self.buffers[buffer_i];
+            buffer.tokens -= buffer.consumed_tokens;
+            self.write_buffer_state(buffer_i);
+        }
+        for buffer_i in self.get_buffer_indices(dag_id, node_i, Outgoing) {
+            let buffer = &mut self.buffers[buffer_i];
+            buffer.reserved += buffer.produced_tokens;
+            self.write_buffer_state(buffer_i);
+        }
+
+        node_state.is_running = true;
+        node_state.firing_count += 1;
+        node_state.last_start_time = Some(self.current_time);
+        let node_data = self.dag_set[dag_id][node_i].clone();
+        self.log.write_allocating_job(
+            &node_data,
+            core_i,
+            node_state.firing_count,
+            self.current_time,
+        );
+        self.processor.allocate_specific_core(core_i, &node_data);
+    }
+
+    /// Produce the output tokens into the reserved space.
+    fn finish_firing(&mut self, node_data: &NodeData, core_id: usize, node_state: &mut NodeState) {
+        let dag_id = node_data.get_params_value("dag_id") as usize;
+        let node_i = self.get_node_index(dag_id, node_data.id);
+        for buffer_i in self.get_buffer_indices(dag_id, node_i, Outgoing) {
+            let buffer = &mut self.buffers[buffer_i];
+            buffer.reserved -= buffer.produced_tokens;
+            buffer.tokens += buffer.produced_tokens;
+            self.write_buffer_state(buffer_i);
+        }
+
+        node_state.is_running = false;
+        self.log.write_job_event(
+            node_data,
+            core_id,
+            node_state.firing_count - 1,
+            JobEventTimes::FinishTime(self.current_time),
+        );
+    }
+
+    /// Count the time each full buffer blocks a producer that has its input tokens.
+    fn write_blocked_time(&mut self, node_states: &[Vec<NodeState>]) {
+        let blocked_buffers: Vec<usize> = self
+            .buffers
+            .iter()
+            .enumerate()
+            .filter(|(_, buffer)| {
+                !node_states[buffer.dag_id][buffer.producer_i.index()].is_running
+                    && !buffer.has_room()
+                    && self.has_input_tokens(buffer.dag_id, buffer.producer_i)
+            })
+            .map(|(buffer_i, _)| buffer_i)
+            .collect();
+        for buffer_i in blocked_buffers {
+            self.log.write_buffer_blocked_time(buffer_i);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::create_node;
+
+    fn create_pipeline(buffer_capacity: Option<i32>) -> Graph<NodeData, i32> {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 1));
+        let n1 = dag.add_node(create_node(1, "execution_time", 3));
+        dag.add_param(n0, "period", 12);
+        if let Some(buffer_capacity) = buffer_capacity {
+            dag.add_param(n1, "buffer_capacity", buffer_capacity);
+        }
+        dag.add_edge(n0, n1, 0);
+        dag.set_dag_param("dag_id", 0);
+        dag
+    }
+
+    #[test]
+    fn test_get_default_buffer_capacity_normal() {
+        assert_eq!(get_default_buffer_capacity(1, 1), 1);
+        assert_eq!(get_default_buffer_capacity(2, 3), 4);
+        assert_eq!(get_default_buffer_capacity(2, 4), 4);
+    }
+
+    #[test]
+    fn test_dataflow_backpressure() {
+        let processor = HomogeneousProcessor::new(2);
+        let mut dataflow_scheduler = DataflowScheduler::new(&[create_pipeline(None)], &processor);
+        dataflow_scheduler.schedule(PreemptiveType::NonPreemptive);
+        let log = dataflow_scheduler.get_log_mut();
+        // The producer fires only after the consumer takes the token, at 0, 1, 4, 7 and 10.
+        assert_eq!(log.get_release_times(0), &[0, 1, 4]);
+        assert_eq!(log.get_worst_response_time(0), 6);
+        let buffer_log = &log.get_buffer_logs()[0];
+        assert_eq!(buffer_log.get_capacity(), 1);
+        assert_eq!(buffer_log.get_max_tokens(), 1);
+        assert_eq!(buffer_log.get_blocked_time(), 7);
+
+        // A larger buffer lets the producer run ahead until it fills.
+        let mut dataflow_scheduler =
+            DataflowScheduler::new(&[create_pipeline(Some(3))], &processor);
+        dataflow_scheduler.schedule(PreemptiveType::NonPreemptive);
+        let buffer_log = &dataflow_scheduler.get_log_mut().get_buffer_logs()[0];
+        assert_eq!(buffer_log.get_max_tokens(), 3);
+        assert!(buffer_log.get_blocked_time() > 0);
+    }
+}
@@ -1,5 +1,19 @@
-use lib::graph_extension::{GraphExtension, NodeData};
-use petgraph::Graph;
+//! Decomposition of DAGs into sequential segments with intermediate deadlines
+//! (Saifullah et al., "Parallel real-time scheduling of DAGs", TPDS 2014).
+//!
+//! The DAG is cut at the earliest finish times of its nodes into segments, which are classified
+//! as heavy or light by their parallelism and share the period of the DAG as their deadlines.
+//! Each node gets the sum of the deadlines of its segments, offset by the deadlines of its
+//! predecessors, so that the DAG can be scheduled as independent nodes under global EDF
+//! (see `DecompositionScheduler`).
+use crate::graph_extension::{GraphExtension, NodeData, NodeIndexMap};
+use petgraph::{graph::Graph, visit::Topo};
+
+/// Scale of the intermediate deadlines to integers. The fifth decimal place is truncated.
+pub const DEADLINE_FACTOR: i32 = 100000;
+/// Scale of `int_scaled_node_absolute_deadline`, which adds the release time to the relative
+/// deadline. It is coarser than `DEADLINE_FACTOR` so that long simulations do not overflow.
+pub const ABSOLUTE_DEADLINE_FACTOR: i32 = 100;
 
 pub enum SegmentClassification {
     Heavy,
@@ -161,16 +175,60 @@ pub fn calculate_segments_deadline(dag: &mut Graph<NodeData, i32>, segments: &mu
     }
 }
 
+/// Set the intermediate relative deadline of each node, from the release of the DAG, scaled by
+/// `DEADLINE_FACTOR` as `int_scaled_node_relative_deadline`.
+pub fn decompose(dag: &mut Graph<NodeData, i32>) {
+    let mut segments = create_segments(dag);
+    calculate_segments_deadline(dag, &mut segments);
+
+    let node_index_map = NodeIndexMap::new(dag);
+    let mut int_scaled_deadline = vec![0; dag.node_count()];
+    for segment in segments.iter() {
+        segment.nodes.iter().for_each(|node| {
+            int_scaled_deadline[node_index_map.get_node_index(node.id).index()] +=
+                (segment.deadline * DEADLINE_FACTOR as f32) as i32;
+        });
+    }
+    let int_scaled_offset = calc_int_scaled_offsets(dag, &int_scaled_deadline);
+
+    // Set integer scaled node relative deadline.
+    for node_i in dag.node_indices() {
+        dag.add_param(
+            node_i,
+            "int_scaled_node_relative_deadline",
+            int_scaled_deadline[node_i.index()] + int_scaled_offset[node_i.index()],
+        );
+    }
+}
+
+fn calc_int_scaled_offsets(dag: &Graph<NodeData, i32>, deadlines: &[i32]) -> Vec<i32> {
+    let mut int_scaled_offsets = vec![0; dag.node_count()];
+
+    // Sort because offsets need to be calculated in the order of execution.
+    let mut topo_order = Topo::new(dag);
+    while let Some(node_i) = topo_order.next(dag) {
+        if let Some(pre_nodes) = dag.get_pre_nodes(node_i) {
+            // offset = maximum of offset + deadline of predecessor nodes.
+            let max_offset = pre_nodes
+                .iter()
+                .map(|pre_node_i| {
+                    let pre_idx = pre_node_i.index();
+                    int_scaled_offsets[pre_idx] + deadlines[pre_idx]
+                })
+                .max()
+                .unwrap_or(0);
+            int_scaled_offsets[node_i.index()] = max_offset;
+        }
+    }
+
+    int_scaled_offsets
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::BTreeMap;
+    use crate::test_util::create_node;
 
-    fn create_node(id: i32, key: &str, value: i32) -> NodeData {
-        let mut params = BTreeMap::new();
-        params.insert(key.to_string(), value);
-        NodeData { id, params }
-    }
     fn create_sample_dag(period: i32) -> Graph<NodeData, i32> {
         let mut dag = Graph::<NodeData, i32>::new();
         let n0 = dag.add_node(create_node(0, "execution_time", 4));
@@ -288,4 +346,18 @@ mod tests {
         assert_eq!(segments[3].deadline, 9.685715);
         assert_eq!(segments[4].deadline, 43.585712);
     }
+
+    #[test]
+    fn test_decompose_normal_float() {
+        let mut dag = create_sample_dag(120);
+        decompose(&mut dag);
+
+        let expect_relative_deadline = [322857, 1356578, 7641428, 6672857, 11999999];
+        for node_i in dag.node_indices() {
+            assert_eq!(
+                dag[node_i].params["int_scaled_node_relative_deadline"],
+                expect_relative_deadline[node_i.index()]
+            );
+        }
+    }
 }
@@ -0,0 +1,98 @@
+//! Decomposition-based global EDF scheduling of DAG sets (see `decomposition`).
+//!
+//! The DAGs are decomposed when the scheduler is created, and each node is dispatched as an
+//! independent job by its intermediate deadline, `int_scaled_node_absolute_deadline`, which the
+//! canonical ready queue order compares. For preemptive scheduling, pass
+//! `PreemptiveType::Preemptive { key: "int_scaled_node_absolute_deadline".to_string() }`.
+use crate::dag_set_scheduler::DAGSetSchedulerBase;
+use crate::getset_dag_set_scheduler;
+use crate::{
+    graph_extension::{get_node_index_maps, GraphExtension, NodeData, NodeIndexMap},
+    homogeneous::HomogeneousProcessor,
+    log::DAGSetSchedulerLog,
+    processor::ProcessorBase,
+};
+use petgraph::graph::Graph;
+
+pub struct DecompositionScheduler {
+    dag_set: Vec<Graph<NodeData, i32>>,
+    node_index_maps: Vec<NodeIndexMap>,
+    processor: HomogeneousProcessor,
+    log: DAGSetSchedulerLog,
+    current_time: i32,
+}
+
+impl DAGSetSchedulerBase<HomogeneousProcessor> for DecompositionScheduler {
+    fn new(dag_set: &[Graph<NodeData, i32>], processor: &HomogeneousProcessor) -> Self {
+        let mut decomposed_dag_set = dag_set.to_vec();
+        for dag in decomposed_dag_set.iter_mut() {
+            if dag.get_head_period().is_none() {
+                panic!(
+                    "DAG {} has no period, which the decomposition needs.",
+                    dag.get_dag_param("dag_id")
+                );
+            }
+            dag.decompose();
+        }
+        Self {
+            node_index_maps: get_node_index_maps(&decomposed_dag_set),
+            dag_set: decomposed_dag_set,
+            processor: processor.clone(),
+            log: DAGSetSchedulerLog::new(dag_set, processor.get_number_of_cores()),
+            current_time: 0,
+        }
+    }
+
+    getset_dag_set_scheduler!(HomogeneousProcessor);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dag_set_scheduler::PreemptiveType;
+    use crate::global_edf_scheduler::GlobalEDFScheduler;
+    use crate::test_util::create_node;
+
+    fn create_sample_dag_set() -> Vec<Graph<NodeData, i32>> {
+        let mut chain_dag = Graph::<NodeData, i32>::new();
+        let n0 = chain_dag.add_node(create_node(0, "execution_time", 2));
+        let n1 = chain_dag.add_node(create_node(1, "execution_time", 2));
+        chain_dag.add_edge(n0, n1, 1);
+        chain_dag.add_param(n0, "period", 10);
+        chain_dag.add_param(n1, "end_to_end_deadline", 10);
+        let mut single_dag = Graph::<NodeData, i32>::new();
+        let n0 = single_dag.add_node(create_node(0, "execution_time", 3));
+        single_dag.add_param(n0, "period", 6);
+        single_dag.add_param(n0, "end_to_end_deadline", 6);
+        chain_dag.set_dag_param("dag_id", 0);
+        single_dag.set_dag_param("dag_id", 1);
+        vec![chain_dag, single_dag]
+    }
+
+    #[test]
+    fn test_decomposition_scheduler_intermediate_deadlines() {
+        let mut decomposition_scheduler =
+            DecompositionScheduler::new(&create_sample_dag_set(), &HomogeneousProcessor::new(1));
+        decomposition_scheduler.schedule(PreemptiveType::Preemptive {
+            key: "int_scaled_node_absolute_deadline".to_string(),
+        });
+
+        // The first node of the chain has the intermediate deadline 5 and runs before the single
+        // node. The second job of the single node (deadline 12) does not preempt the second node
+        // of the chain (deadline 10) at 6.
+        let log = decomposition_scheduler.get_log_mut();
+        assert_eq!(log.get_worst_response_time(0), 7);
+        assert_eq!(log.get_worst_response_time(1), 5);
+
+        // Global EDF runs the single node first by the end-to-end deadlines, so the chain blocks
+        // only its second job.
+        let mut global_edf_scheduler =
+            GlobalEDFScheduler::new(&create_sample_dag_set(), &HomogeneousProcessor::new(1));
+        global_edf_scheduler.schedule(PreemptiveType::Preemptive {
+            key: "node_absolute_deadline".to_string(),
+        });
+        let log = global_edf_scheduler.get_log_mut();
+        assert_eq!(log.get_worst_response_time(0), 7);
+        assert_eq!(log.get_worst_response_time(1), 4);
+    }
+}
@@ -0,0 +1,219 @@
+//! Checks that simulations are deterministic.
+//!
+//! A configuration is simulated several times, optionally on concurrent threads, and the summary
+//! of every run must be byte-identical to that of the first. The summary is the log serialized as
+//! JSON, which excludes the wall-clock time and the memory usage of the simulation.
+use crate::{
+    dag_set_scheduler::{DAGSetSchedulerBase, PreemptiveType},
+    graph_extension::NodeData,
+    log::DAGSetSchedulerLog,
+    processor::ProcessorBase,
+};
+use petgraph::graph::Graph;
+use std::{fmt, thread};
+
+/// The first line of a summary differing from that of the first run.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DeterminismMismatch {
+    /// Index of the run, where the first run is 0.
+    pub run: usize,
+    /// 1-based line number in the summaries.
+    pub line: usize,
+    pub expected: String,
+    pub actual: String,
+}
+
+impl fmt::Display for DeterminismMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Run {} differs from the first run at line {} of the summary:\n  expected: {}\n  actual:   {}",
+            self.run, self.line, self.expected, self.actual
+        )
+    }
+}
+
+pub fn get_summary(log: &DAGSetSchedulerLog) -> String {
+    serde_json::to_string_pretty(log).expect("Failed to serialize the log.")
+}
+
+fn find_mismatch(run: usize, expected: &str, actual: &str) -> Option<DeterminismMismatch> {
+    if expected == actual {
+        return None;
+    }
+    let mut expected_lines = expected.lines();
+    let mut actual_lines = actual.lines();
+    let mut line = 1;
+    loop {
+        match (expected_lines.next(), actual_lines.next()) {
+            (Some(expected_line), Some(actual_line)) if expected_line == actual_line => line += 1,
+            (expected_line, actual_line) => {
+                return Some(DeterminismMismatch {
+                    run,
+                    line,
+                    expected: expected_line.unwrap_or_default().to_string(),
+                    actual: actual_line.unwrap_or_default().to_string(),
+                })
+            }
+        }
+    }
+}
+
+/// Run `simulate` `runs` times and compare the summaries of the logs with that of the first run.
+///
+/// The first run is on the calling thread. The others are spread over `threads` threads running
+/// concurrently, or run one after another on the calling thread if `threads` is 0 or 1.
+///
+/// # Returns
+///
+/// * The mismatch of the earliest run differing from the first, if any.
+pub fn check_determinism<F>(simulate: F, runs: usize, threads: usize) -> Option<DeterminismMismatch>
+where
+    F: Fn() -> DAGSetSchedulerLog + Sync,
+{
+    if runs < 2 {
+        panic!(
+            "At least two runs are needed to compare, but {} given.",
+            runs
+        );
+    }
+    let expected = get_summary(&simulate());
+    let summaries: Vec<(usize, String)> = if threads <= 1 {
+        (1..runs)
+            .map(|run| (run, get_summary(&simulate())))
+            .collect()
+    } else {
+        thread::scope(|scope| {
+            let handles: Vec<_> = (0..threads.min(runs - 1))
+                .map(|thread_i| {
+                    let simulate = &simulate;
+                    scope.spawn(move || {
+                        (1 + thread_i..runs)
+                            .step_by(threads)
+                            .map(|run| (run, get_summary(&simulate())))
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+            let mut summaries: Vec<(usize, String)> = handles
+                .into_iter()
+                .flat_map(|handle| handle.join().expect("A simulation thread panicked."))
+                .collect();
+            summaries.sort_by_key(|(run, _)| *run);
+            summaries
+        })
+    };
+    summaries
+        .iter()
+        .find_map(|(run, actual)| find_mismatch(*run, &expected, actual))
+}
+
+/// Panic with the first mismatch of `check_determinism`, for use in tests and CI.
+pub fn assert_deterministic<F>(simulate: F, runs: usize, threads: usize)
+where
+    F: Fn() -> DAGSetSchedulerLog + Sync,
+{
+    if let Some(mismatch) = check_determinism(simulate, runs, threads) {
+        panic!("The simulation is not deterministic. {}", mismatch);
+    }
+}
+
+/// Assert that the scheduler `S` schedules `dag_set` deterministically, both when run again on
+/// the same thread and when run on concurrent threads.
+pub fn assert_scheduler_deterministic<T, S>(
+    dag_set: &[Graph<NodeData, i32>],
+    processor: &T,
+    preemptive_type: PreemptiveType,
+) where
+    T: ProcessorBase + Clone + Sync,
+    S: DAGSetSchedulerBase<T>,
+{
+    let simulate = || {
+        let mut scheduler = S::new(dag_set, processor);
+        scheduler.schedule(preemptive_type.clone());
+        scheduler.get_log_mut().clone()
+    };
+    assert_deterministic(simulate, 2, 1);
+    assert_deterministic(simulate, 5, 4);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::create_node;
+    #[cfg(feature = "random")]
+    use crate::work_stealing_scheduler::WorkStealingScheduler;
+    use crate::{
+        cbs_scheduler::CBSScheduler, dp_fair_scheduler::DPFairScheduler,
+        global_edf_scheduler::GlobalEDFScheduler, graph_extension::GraphExtension,
+        homogeneous::HomogeneousProcessor,
+    };
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn create_dag_set() -> Vec<Graph<NodeData, i32>> {
+        let mut dag_set = Vec::new();
+        for (dag_id, period) in [(0, 12), (1, 8), (2, 6)] {
+            let mut dag = Graph::<NodeData, i32>::new();
+            let n0 = dag.add_node(create_node(0, "execution_time", 2));
+            let n1 = dag.add_node(create_node(1, "execution_time", 3));
+            let n2 = dag.add_node(create_node(2, "execution_time", 1));
+            let n3 = dag.add_node(create_node(3, "execution_time", 1));
+            dag.add_param(n0, "period", period);
+            dag.add_param(n0, "end_to_end_deadline", period);
+            dag.add_edge(n0, n1, 1);
+            dag.add_edge(n0, n2, 1);
+            dag.add_edge(n1, n3, 1);
+            dag.add_edge(n2, n3, 1);
+            dag.set_dag_param("dag_id", dag_id);
+            dag_set.push(dag);
+        }
+        dag_set
+    }
+
+    #[test]
+    fn test_assert_scheduler_deterministic_normal() {
+        let dag_set = create_dag_set();
+        let processor = HomogeneousProcessor::new(2);
+        assert_scheduler_deterministic::<_, GlobalEDFScheduler>(
+            &dag_set,
+            &processor,
+            PreemptiveType::Preemptive {
+                key: "node_absolute_deadline".to_string(),
+            },
+        );
+        assert_scheduler_deterministic::<_, DPFairScheduler>(
+            &dag_set,
+            &processor,
+            PreemptiveType::NonPreemptive,
+        );
+        assert_scheduler_deterministic::<_, CBSScheduler>(
+            &dag_set,
+            &processor,
+            PreemptiveType::NonPreemptive,
+        );
+        #[cfg(feature = "random")]
+        assert_scheduler_deterministic::<_, WorkStealingScheduler>(
+            &dag_set,
+            &processor,
+            PreemptiveType::NonPreemptive,
+        );
+    }
+
+    #[test]
+    fn test_check_determinism_mismatch() {
+        let dag_set = create_dag_set();
+        let run_count = AtomicUsize::new(0);
+        // The third run has one more core than the others.
+        let simulate = || {
+            let number_of_cores = 2 + (run_count.fetch_add(1, Ordering::SeqCst) == 2) as usize;
+            let mut scheduler =
+                GlobalEDFScheduler::new(&dag_set, &HomogeneousProcessor::new(number_of_cores));
+            scheduler.schedule(PreemptiveType::NonPreemptive);
+            scheduler.get_log_mut().clone()
+        };
+        let mismatch = check_determinism(simulate, 4, 1).unwrap();
+        assert_eq!(mismatch.run, 2);
+        assert_ne!(mismatch.expected, mismatch.actual);
+    }
+}
@@ -0,0 +1,311 @@
+//! Deadline-partitioned fair scheduler (DP-Fair).
+//!
+//! Time is divided into windows by the release times and absolute deadlines of all jobs
+//! (see `get_deadline_partitions`). At the start of each window, each released DAG receives a
+//! local budget proportional to its density, and the budgets are consumed within the window,
+//! the DAG with the smallest local laxity first. This avoids the Dhall effect of global EDF.
+use crate::dag_set_scheduler::{
+    DAGSetSchedulerBase, DAGState, DAGStateManager, DAGStateManagerBase, PreemptiveType,
+};
+use crate::getset_dag_set_scheduler;
+use crate::{
+    core::ProcessResult,
+    graph_extension::{get_node_index_maps, GraphExtension, NodeData, NodeIndexMap},
+    homogeneous::HomogeneousProcessor,
+    log::{DAGSetSchedulerLog, JobEventTimes},
+    processor::ProcessorBase,
+    util::{clear_interrupted, get_deadline_partitions, get_process_core_indices, is_interrupted},
+};
+use petgraph::graph::Graph;
+
+pub struct DPFairScheduler {
+    dag_set: Vec<Graph<NodeData, i32>>,
+    node_index_maps: Vec<NodeIndexMap>,
+    processor: HomogeneousProcessor,
+    log: DAGSetSchedulerLog,
+    current_time: i32,
+}
+
+impl DAGSetSchedulerBase<HomogeneousProcessor> for DPFairScheduler {
+    fn new(dag_set: &[Graph<NodeData, i32>], processor: &HomogeneousProcessor) -> Self {
+        Self {
+            node_index_maps: get_node_index_maps(dag_set),
+            dag_set: dag_set.to_vec(),
+            processor: processor.clone(),
+            log: DAGSetSchedulerLog::new(dag_set, processor.get_number_of_cores()),
+            current_time: 0,
+        }
+    }
+
+    getset_dag_set_scheduler!(HomogeneousProcessor);
+
+    /// The preemptive type is ignored because DP-Fair preempts at every window boundary.
+    fn schedule(&mut self, _preemptive_type: PreemptiveType) -> i32 {
+        clear_interrupted();
+        self.log.write_schedule_start();
+        let dag_set = self.get_dag_set();
+        let mut managers = vec![DAGStateManager::default(); dag_set.len()];
+        let mut ready_nodes: Vec<Vec<NodeData>> = vec![Vec::new(); dag_set.len()];
+        let densities: Vec<f32> = dag_set
+            .iter()
+            .map(|dag| dag.get_volume() as f32 / dag.get_end_to_end_deadline().unwrap() as f32)
+            .collect();
+        let mut budgets = vec![0.0; dag_set.len()];
+        let partitions = get_deadline_partitions(&dag_set);
+
+        for (window_start, window_end) in partitions {
+            while self.get_current_time() < window_end && !is_interrupted() {
+                // Release DAGs
+                for ready_node in self.release_dags(&mut managers) {
+                    ready_nodes[ready_node.get_params_value("dag_id") as usize].push(ready_node);
+                }
+
+                // Assign the local budgets at the start of the window.
+                if self.get_current_time() == window_start {
+                    for (dag_id, budget) in budgets.iter_mut().enumerate() {
+                        *budget = if managers[dag_id].get_dag_state() == DAGState::Waiting {
+                            0.0
+                        } else {
+                            densities[dag_id] * (window_end - window_start) as f32
+                        };
+                    }
+                }
+
+                let selected_nodes = self.select_nodes(&ready_nodes, &budgets, window_end as f32);
+
+                // Preempt the running nodes that are not selected.
+                let current_time = self.get_current_time();
+                for core_i in 0..self.processor.get_number_of_cores() {
+                    let Some(running_node) =
+                        self.processor.cores[core_i].get_processing_node().clone()
+                    else {
+                        continue;
+                    };
+                    if selected_nodes.contains(&get_node_key(&running_node)) {
+                        continue;
+                    }
+                    let preempted_node_data = self.processor.preempt(core_i).unwrap();
+                    let dag_id = preempted_node_data.get_params_value("dag_id") as usize;
+                    self.log.write_job_event(
+                        &preempted_node_data,
+                        core_i,
+                        managers[dag_id].get_release_count() as usize - 1,
+                        JobEventTimes::PreemptedTime(current_time),
+                    );
+                    ready_nodes[dag_id].push(preempted_node_data);
+                }
+
+                // Allocate the selected ready nodes to the idle cores.
+                for (dag_id, node_id) in selected_nodes {
+                    let Some(position) = ready_nodes[dag_id]
+                        .iter()
+                        .position(|node| node.get_id() == node_id)
+                    else {
+                        continue;
+                    };
+                    let node_data = ready_nodes[dag_id].remove(position);
+                    let idle_core_i = self.processor.get_idle_core_index().unwrap();
+                    self.allocate_node(
+                        &node_data,
+                        idle_core_i,
+                        managers[dag_id].get_release_count() as usize,
+                    );
+                }
+
+                // Consume the budgets of the running DAGs.
+                for core in self.processor.cores.iter() {
+                    if let Some(node_data) = core.get_processing_node() {
+                        budgets[node_data.get_params_value("dag_id") as usize] -= 1.0;
+                    }
+                }
+
+                // Process unit time
+                let process_result = self.process_unit_time();
+                let indices: Vec<usize> = get_process_core_indices(&process_result);
+                self.log.write_processing_time(&indices);
+
+                // Post-process on completion of node execution
+                for (core_id, result) in process_result.iter().enumerate() {
+                    if let ProcessResult::Done(node_data) = result {
+                        for ready_node in
+                            self.post_process_on_node_completion(node_data, core_id, &mut managers)
+                        {
+                            ready_nodes[ready_node.get_params_value("dag_id") as usize]
+                                .push(ready_node);
+                        }
+                    }
+                }
+            }
+        }
+
+        self.calculate_log();
+        self.get_current_time()
+    }
+}
+
+impl DPFairScheduler {
+    /// Select the `(dag_id, node_id)` to execute in the next time unit.
+    ///
+    /// DAGs are visited in ascending order of local laxity and each takes as many cores as its
+    /// remaining budget, preferring its running nodes to avoid preemptions.
+    /// The cores left over are then given to the remaining nodes in the same order.
+    /// No DAG takes more cores than its `max_parallelism`.
+    fn select_nodes(
+        &self,
+        ready_nodes: &[Vec<NodeData>],
+        budgets: &[f32],
+        window_end: f32,
+    ) -> Vec<(usize, i32)> {
+        let mut running_nodes = vec![Vec::new(); ready_nodes.len()];
+        for core in self.processor.cores.iter() {
+            if let Some(node_data) = core.get_processing_node() {
+                running_nodes[node_data.get_params_value("dag_id") as usize]
+                    .push(node_data.get_id());
+            }
+        }
+
+        let remaining_time = window_end - self.current_time as f32;
+        let mut dag_ids: Vec<usize> = (0..ready_nodes.len()).collect();
+        dag_ids.sort_by(|&a, &b| {
+            (remaining_time - budgets[a]).total_cmp(&(remaining_time - budgets[b]))
+        });
+
+        let number_of_cores = self.processor.get_number_of_cores();
+        let max_parallelisms: Vec<usize> = self
+            .dag_set
+            .iter()
+            .map(|dag| dag.get_max_parallelism().unwrap_or(number_of_cores))
+            .collect();
+        let mut selected_nodes = Vec::new();
+        for use_budget in [true, false] {
+            for &dag_id in dag_ids.iter() {
+                let quota = if use_budget {
+                    budgets[dag_id].max(0.0).ceil() as usize
+                } else {
+                    number_of_cores
+                }
+                .min(max_parallelisms[dag_id]);
+                let candidates = running_nodes[dag_id]
+                    .iter()
+                    .copied()
+                    .chain(ready_nodes[dag_id].iter().map(|node| node.get_id()))
+                    .map(|node_id| (dag_id, node_id))
+                    .filter(|node_key| !selected_nodes.contains(node_key));
+                let taken = selected_nodes
+                    .iter()
+                    .filter(|(selected_dag_id, _)| *selected_dag_id == dag_id)
+                    .count();
+                let available = quota
+                    .saturating_sub(taken)
+                    .min(number_of_cores - selected_nodes.len());
+                selected_nodes.extend(candidates.take(available).collect::<Vec<_>>());
+            }
+        }
+        selected_nodes
+    }
+}
+
+fn get_node_key(node_data: &NodeData) -> (usize, i32) {
+    (
+        node_data.get_params_value("dag_id") as usize,
+        node_data.get_id(),
+    )
+}
+
+#[cfg(all(test, feature = "yaml"))]
+mod tests {
+    use super::*;
+    use crate::test_util::create_node;
+    use crate::{global_edf_scheduler::GlobalEDFScheduler, util::load_yaml};
+    use std::fs::remove_file;
+
+    fn create_single_node_dag(execution_time: i32, period: i32) -> Graph<NodeData, i32> {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", execution_time));
+        dag.add_param(n0, "period", period);
+        dag.add_param(n0, "end_to_end_deadline", period);
+        dag
+    }
+
+    fn get_worst_response_time(
+        scheduler: &mut impl DAGSetSchedulerBase<HomogeneousProcessor>,
+        test_name: &str,
+        dag_id: usize,
+    ) -> i64 {
+        let file_path = scheduler.dump_log("../lib/tests", test_name);
+        let yaml_docs = load_yaml(&file_path);
+        remove_file(file_path).unwrap();
+        yaml_docs[0]["dag_set_log"][dag_id]["worst_response_time"]
+            .as_i64()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_dp_fair_avoids_dhall_effect() {
+        // Two light DAGs and a heavy DAG (Dhall effect).
+        let mut dag_set = vec![
+            create_single_node_dag(2, 20),
+            create_single_node_dag(2, 20),
+            create_single_node_dag(20, 21),
+        ];
+        for (dag_id, dag) in dag_set.iter_mut().enumerate() {
+            dag.set_dag_param("dag_id", dag_id as i32);
+        }
+        let processor = HomogeneousProcessor::new(2);
+
+        let mut dp_fair_scheduler = DPFairScheduler::new(&dag_set, &processor);
+        dp_fair_scheduler.schedule(PreemptiveType::NonPreemptive);
+        assert!(get_worst_response_time(&mut dp_fair_scheduler, "dp_fair_test", 2) <= 21);
+        for dag_id in 0..2 {
+            assert!(
+                get_worst_response_time(&mut dp_fair_scheduler, "dp_fair_light_test", dag_id) <= 20
+            );
+        }
+
+        // Global EDF runs the light DAGs first and the heavy DAG misses its deadline.
+        let mut global_edf_scheduler = GlobalEDFScheduler::new(&dag_set, &processor);
+        global_edf_scheduler.schedule(PreemptiveType::Preemptive {
+            key: "node_absolute_deadline".to_string(),
+        });
+        assert_eq!(
+            get_worst_response_time(&mut global_edf_scheduler, "dp_fair_gedf_test", 2),
+            22
+        );
+    }
+
+    #[test]
+    fn test_dp_fair_tie() {
+        // Two DAGs of the same density on a single core. The tie on the local laxity is broken
+        // by the smallest dag_id, so that DAG 0 completes first.
+        let mut dag_set = vec![create_single_node_dag(5, 10), create_single_node_dag(5, 10)];
+        for (dag_id, dag) in dag_set.iter_mut().enumerate() {
+            dag.set_dag_param("dag_id", dag_id as i32);
+        }
+        let mut dp_fair_scheduler = DPFairScheduler::new(&dag_set, &HomogeneousProcessor::new(1));
+        dp_fair_scheduler.schedule(PreemptiveType::NonPreemptive);
+        assert_eq!(
+            dp_fair_scheduler.get_log_mut().get_worst_response_times(),
+            vec![9, 10]
+        );
+    }
+
+    #[test]
+    fn test_dp_fair_overload() {
+        // Utilizations 0.8 on two cores, which DP-Fair cannot schedule.
+        let mut dag_set = vec![
+            create_single_node_dag(8, 10),
+            create_single_node_dag(8, 10),
+            create_single_node_dag(8, 10),
+        ];
+        for (dag_id, dag) in dag_set.iter_mut().enumerate() {
+            dag.set_dag_param("dag_id", dag_id as i32);
+        }
+        let mut dp_fair_scheduler = DPFairScheduler::new(&dag_set, &HomogeneousProcessor::new(2));
+        dp_fair_scheduler.schedule(PreemptiveType::NonPreemptive);
+        // The DAGs share the cores by their budgets, so that all of them miss their deadlines.
+        assert_eq!(
+            dp_fair_scheduler.get_log_mut().get_worst_response_times(),
+            vec![i32::MAX; 3]
+        );
+    }
+}
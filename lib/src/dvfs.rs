@@ -0,0 +1,196 @@
+//! Dynamic voltage and frequency scaling (DVFS) of the cores of a processor.
+//!
+//! Each core runs at one of the discrete frequency levels of a `DvfsModel`. The frequencies are
+//! relative to the frequency at which `execution_time` was measured, so a node takes
+//! `ceil(execution_time / frequency)` on its core. The energy is accounted in units of the power
+//! of a busy core at frequency 1 over a time unit, as in `power_down`.
+use crate::log::{CoreEnergyLog, EnergyLog};
+
+/// Tolerance for the floating-point error of the scaled execution times.
+const FREQUENCY_EPSILON: f64 = 1e-4;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FrequencyLevel {
+    /// In (0, 1].
+    pub frequency: f32,
+    /// Power of a busy core at the frequency.
+    pub power: f32,
+}
+
+impl FrequencyLevel {
+    pub fn new(frequency: f32, power: f32) -> Self {
+        if frequency <= 0.0 || frequency > 1.0 {
+            panic!("The frequency must be in (0, 1]: {}", frequency);
+        }
+        if power < 0.0 {
+            panic!("The power must not be negative: {}", power);
+        }
+        Self { frequency, power }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DvfsModel {
+    /// In ascending order of frequency.
+    levels: Vec<FrequencyLevel>,
+    /// Power of an idle core, independent of its frequency.
+    idle_power: f32,
+}
+
+impl DvfsModel {
+    pub fn new(mut levels: Vec<FrequencyLevel>, idle_power: f32) -> Self {
+        if levels.is_empty() {
+            panic!("DVFS needs at least one frequency level.");
+        }
+        if idle_power < 0.0 {
+            panic!("The idle power must not be negative: {}", idle_power);
+        }
+        levels.sort_by(|a, b| a.frequency.total_cmp(&b.frequency));
+        Self { levels, idle_power }
+    }
+
+    /// The levels at `frequencies` with the power proportional to the cube of the frequency.
+    pub fn new_cubic(frequencies: &[f32], idle_power: f32) -> Self {
+        Self::new(
+            frequencies
+                .iter()
+                .map(|&frequency| FrequencyLevel::new(frequency, frequency.powi(3)))
+                .collect(),
+            idle_power,
+        )
+    }
+
+    pub fn get_levels(&self) -> &[FrequencyLevel] {
+        &self.levels
+    }
+
+    pub fn get_max_level_index(&self) -> usize {
+        self.levels.len() - 1
+    }
+
+    pub fn get_idle_power(&self) -> f32 {
+        self.idle_power
+    }
+
+    pub fn get_execution_time(&self, execution_time: i32, level_i: usize) -> i32 {
+        let time = execution_time as f64 / self.levels[level_i].frequency as f64;
+        if (time - time.round()).abs() < FREQUENCY_EPSILON {
+            time.round() as i32
+        } else {
+            time.ceil() as i32
+        }
+    }
+}
+
+/// Frequency levels and busy times of the cores, advanced by the processor with its own clock.
+#[derive(Clone, Debug)]
+pub struct CoreFrequencies {
+    model: DvfsModel,
+    time: i32,
+    level_indices: Vec<usize>,
+    busy_times: Vec<i32>,
+}
+
+impl CoreFrequencies {
+    /// All cores start at the highest frequency.
+    pub fn new(model: DvfsModel, number_of_cores: usize) -> Self {
+        let max_level_i = model.get_max_level_index();
+        Self {
+            model,
+            time: 0,
+            level_indices: vec![max_level_i; number_of_cores],
+            busy_times: vec![0; number_of_cores],
+        }
+    }
+
+    pub fn get_model(&self) -> &DvfsModel {
+        &self.model
+    }
+
+    pub fn get_level_index(&self, core_id: usize) -> usize {
+        self.level_indices[core_id]
+    }
+
+    pub fn set_level_index(&mut self, core_id: usize, level_i: usize) {
+        if level_i >= self.model.levels.len() {
+            panic!(
+                "Frequency level {} does not exist. The model has {} levels.",
+                level_i,
+                self.model.levels.len()
+            );
+        }
+        self.level_indices[core_id] = level_i;
+    }
+
+    /// Time the core takes to run `execution_time` at its frequency.
+    pub fn get_execution_time(&self, core_id: usize, execution_time: i32) -> i32 {
+        self.model
+            .get_execution_time(execution_time, self.level_indices[core_id])
+    }
+
+    /// Advance the clock by `time` units in which the cores in `busy_cores` were busy.
+    pub fn advance(&mut self, time: i32, busy_cores: &[bool]) {
+        for (busy_time, &is_busy) in self.busy_times.iter_mut().zip(busy_cores) {
+            if is_busy {
+                *busy_time += time;
+            }
+        }
+        self.time += time;
+    }
+
+    pub fn get_energy_log(&self) -> EnergyLog {
+        let core_energy_logs: Vec<CoreEnergyLog> = self
+            .level_indices
+            .iter()
+            .zip(&self.busy_times)
+            .enumerate()
+            .map(|(core_id, (&level_i, &busy_time))| {
+                let level = self.model.levels[level_i];
+                CoreEnergyLog {
+                    core_id,
+                    frequency: level.frequency,
+                    busy_time,
+                    energy: busy_time as f32 * level.power
+                        + (self.time - busy_time) as f32 * self.model.idle_power,
+                }
+            })
+            .collect();
+        EnergyLog {
+            idle_power: self.model.idle_power,
+            total_energy: core_energy_logs
+                .iter()
+                .map(|core_energy_log| core_energy_log.energy)
+                .sum(),
+            core_energy_logs,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_core_frequencies_energy() {
+        let model = DvfsModel::new_cubic(&[1.0, 0.5], 0.1);
+        assert_eq!(model.get_levels()[0].frequency, 0.5);
+        assert_eq!(model.get_execution_time(3, 0), 6);
+        assert_eq!(
+            DvfsModel::new_cubic(&[0.7], 0.0).get_execution_time(7, 0),
+            10
+        );
+
+        let mut core_frequencies = CoreFrequencies::new(model, 2);
+        core_frequencies.set_level_index(1, 0);
+        assert_eq!(core_frequencies.get_execution_time(0, 3), 3);
+        assert_eq!(core_frequencies.get_execution_time(1, 3), 6);
+        core_frequencies.advance(4, &[true, true]);
+        core_frequencies.advance(6, &[false, true]);
+
+        // Core 0: 4 busy units at power 1 and 6 idle units. Core 1: 10 busy units at power 1/8.
+        let energy_log = core_frequencies.get_energy_log();
+        assert_eq!(energy_log.core_energy_logs[0].energy, 4.6);
+        assert_eq!(energy_log.core_energy_logs[1].energy, 1.25);
+        assert_eq!(energy_log.total_energy, 5.85);
+    }
+}
@@ -0,0 +1,188 @@
+//! Energy-aware partitioned EDF with static DVFS: the DAGs are partitioned onto the cores by
+//! First-Fit as in `partitioned_edf_scheduler`, and each core runs at the frequency level of the
+//! DVFS model of the processor that minimizes its energy while its DAGs meet their deadlines.
+//!
+//! A core is feasible at a level if the density of its DAGs, with the execution times scaled by
+//! the frequency, does not exceed one (the EDF density test). The energy of a level is estimated
+//! from the scaled utilization of the core, busy at the power of the level and idle otherwise,
+//! so that a higher level is chosen where the idle power makes racing to idle cheaper. A core
+//! that is infeasible at every level runs at the highest level.
+use crate::dag_set_scheduler::{DAGSetSchedulerBase, PreemptiveType};
+use crate::getset_dag_set_scheduler;
+use crate::{
+    dag_task::DagTask,
+    dvfs::DvfsModel,
+    graph_extension::{get_node_index_maps, NodeData, NodeIndexMap},
+    homogeneous::HomogeneousProcessor,
+    log::{DAGSetSchedulerLog, PartitioningLog},
+    partitioned_edf_scheduler::{
+        partition_dag_set, schedule_partitioned_edf, Partition, PartitioningHeuristic,
+    },
+    processor::ProcessorBase,
+    util::clear_interrupted,
+};
+use log::warn;
+use petgraph::graph::Graph;
+
+/// Tolerance for the floating-point error of the summed densities.
+const DENSITY_EPSILON: f64 = 1e-9;
+
+/// The frequency level of each core, where `core_ids` is the core of each DAG by `dag_id`.
+pub fn select_frequency_levels(
+    dag_set: &[Graph<NodeData, i32>],
+    core_ids: &[usize],
+    number_of_cores: usize,
+    dvfs_model: &DvfsModel,
+) -> Vec<usize> {
+    (0..number_of_cores)
+        .map(|core_i| {
+            let dag_tasks: Vec<DagTask> = dag_set
+                .iter()
+                .zip(core_ids)
+                .filter(|&(_, &core_id)| core_id == core_i)
+                .map(|(dag, _)| DagTask::from_graph(dag))
+                .collect();
+            let feasible_levels = (0..dvfs_model.get_levels().len()).filter_map(|level_i| {
+                let mut density = 0.0;
+                let mut utilization = 0.0;
+                for dag_task in dag_tasks.iter() {
+                    let volume: i32 = dag_task
+                        .graph
+                        .node_weights()
+                        .map(|node_data| {
+                            dvfs_model.get_execution_time(
+                                node_data.get_params_value("execution_time"),
+                                level_i,
+                            )
+                        })
+                        .sum();
+                    density += volume as f64 / dag_task.get_relative_deadline().unwrap() as f64;
+                    utilization += volume as f64 / dag_task.period.unwrap() as f64;
+                }
+                (density <= 1.0 + DENSITY_EPSILON).then(|| {
+                    let level = dvfs_model.get_levels()[level_i];
+                    let energy = utilization * level.power as f64
+                        + (1.0 - utilization) * dvfs_model.get_idle_power() as f64;
+                    (level_i, energy)
+                })
+            });
+            feasible_levels
+                .min_by(|(_, a), (_, b)| a.total_cmp(b))
+                .map(|(level_i, _)| level_i)
+                .unwrap_or_else(|| {
+                    warn!(
+                        "The DAGs on core {} miss their deadlines even at the highest frequency.",
+                        core_i
+                    );
+                    dvfs_model.get_max_level_index()
+                })
+        })
+        .collect()
+}
+
+pub struct DvfsScheduler {
+    dag_set: Vec<Graph<NodeData, i32>>,
+    node_index_maps: Vec<NodeIndexMap>,
+    processor: HomogeneousProcessor,
+    log: DAGSetSchedulerLog,
+    current_time: i32,
+    partition: Partition,
+    frequency_levels: Vec<usize>,
+}
+
+impl DAGSetSchedulerBase<HomogeneousProcessor> for DvfsScheduler {
+    /// The processor needs a DVFS model (see `HomogeneousProcessor::set_dvfs_model`).
+    fn new(dag_set: &[Graph<NodeData, i32>], processor: &HomogeneousProcessor) -> Self {
+        let dvfs_model = processor.get_dvfs_model().unwrap_or_else(|| {
+            panic!("DVFS scheduling needs the frequency levels of the processor.")
+        });
+        let number_of_cores = processor.get_number_of_cores();
+        let heuristic = PartitioningHeuristic::FirstFit;
+        let partition = partition_dag_set(dag_set, number_of_cores, heuristic);
+        let frequency_levels =
+            select_frequency_levels(dag_set, &partition.core_ids, number_of_cores, dvfs_model);
+        let mut processor = processor.clone();
+        for (core_i, &level_i) in frequency_levels.iter().enumerate() {
+            processor.set_frequency_level(core_i, level_i);
+        }
+        let mut log = DAGSetSchedulerLog::new(dag_set, number_of_cores);
+        log.write_partitioning(PartitioningLog::new(heuristic, partition.clone()));
+        Self {
+            node_index_maps: get_node_index_maps(dag_set),
+            dag_set: dag_set.to_vec(),
+            processor,
+            log,
+            current_time: 0,
+            partition,
+            frequency_levels,
+        }
+    }
+
+    getset_dag_set_scheduler!(HomogeneousProcessor);
+
+    fn schedule(&mut self, preemptive_type: PreemptiveType) -> i32 {
+        clear_interrupted();
+        self.get_log_mut().write_schedule_start();
+        let core_ids = self.partition.core_ids.clone();
+        schedule_partitioned_edf(self, &core_ids, preemptive_type)
+    }
+}
+
+impl DvfsScheduler {
+    pub fn get_partition(&self) -> &Partition {
+        &self.partition
+    }
+
+    /// The frequency level of each core.
+    pub fn get_frequency_levels(&self) -> &[usize] {
+        &self.frequency_levels
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph_extension::GraphExtension;
+    use crate::partitioned_edf_scheduler::PartitionedEDFScheduler;
+    use crate::test_util::create_node;
+
+    fn create_single_node_dag(execution_time: i32, dag_id: i32) -> Graph<NodeData, i32> {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", execution_time));
+        dag.add_param(n0, "period", 8);
+        dag.add_param(n0, "end_to_end_deadline", 8);
+        dag.set_dag_param("dag_id", dag_id);
+        dag
+    }
+
+    #[test]
+    fn test_dvfs_scheduler_normal() {
+        let dag_set = vec![
+            create_single_node_dag(2, 0),
+            create_single_node_dag(2, 1),
+            create_single_node_dag(6, 2),
+        ];
+        let mut processor = HomogeneousProcessor::new(2);
+        processor.set_dvfs_model(DvfsModel::new_cubic(&[0.5, 0.75, 1.0], 0.0));
+        let preemptive_type = PreemptiveType::Preemptive {
+            key: "node_absolute_deadline".to_string(),
+        };
+
+        // DAGs 0 and 1 fill core 0 at half the frequency, and DAG 2 fills core 1 at 0.75.
+        let mut dvfs_scheduler = DvfsScheduler::new(&dag_set, &processor);
+        assert_eq!(dvfs_scheduler.get_partition().core_ids, vec![0, 0, 1]);
+        assert_eq!(dvfs_scheduler.get_frequency_levels(), &[0, 1]);
+        dvfs_scheduler.schedule(preemptive_type.clone());
+        let log = dvfs_scheduler.get_log_mut();
+        assert_eq!(log.get_worst_response_times(), vec![4, 8, 8]);
+        let energy_log = log.get_energy_log().unwrap();
+        assert_eq!(energy_log.core_energy_logs[0].frequency, 0.5);
+        assert_eq!(energy_log.total_energy, 4.375);
+
+        // At the highest frequency, the same partition takes 10 units of energy.
+        let mut partitioned_edf_scheduler = PartitionedEDFScheduler::new(&dag_set, &processor);
+        partitioned_edf_scheduler.schedule(preemptive_type);
+        let log = partitioned_edf_scheduler.get_log_mut();
+        assert_eq!(log.get_energy_log().unwrap().total_energy, 10.0);
+    }
+}
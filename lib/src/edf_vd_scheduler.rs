@@ -0,0 +1,191 @@
+//! Global EDF with Virtual Deadlines (EDF-VD) for mixed-criticality DAG sets (see
+//! `mixed_criticality`): in the LO mode, the jobs of the HI-criticality DAGs have virtual
+//! deadlines at `x` of their relative deadlines, so that they run ahead of the LO-criticality jobs
+//! and keep slack for an overrun. On the switch to the HI mode, the LO-criticality jobs are
+//! dropped and the HI-criticality jobs return to their native deadlines.
+use crate::dag_set_scheduler::DAGSetSchedulerBase;
+use crate::getset_dag_set_scheduler;
+use crate::{
+    graph_extension::{get_node_index_maps, NodeData, NodeIndexMap},
+    homogeneous::HomogeneousProcessor,
+    log::DAGSetSchedulerLog,
+    mixed_criticality::calculate_virtual_deadline_factor,
+    processor::ProcessorBase,
+};
+use petgraph::graph::Graph;
+
+pub struct EdfVdScheduler {
+    dag_set: Vec<Graph<NodeData, i32>>,
+    node_index_maps: Vec<NodeIndexMap>,
+    processor: HomogeneousProcessor,
+    log: DAGSetSchedulerLog,
+    current_time: i32,
+    virtual_deadline_factor: f32,
+}
+
+impl DAGSetSchedulerBase<HomogeneousProcessor> for EdfVdScheduler {
+    /// The factor by `calculate_virtual_deadline_factor`.
+    fn new(dag_set: &[Graph<NodeData, i32>], processor: &HomogeneousProcessor) -> Self {
+        Self::new_with_virtual_deadline_factor(
+            dag_set,
+            processor,
+            calculate_virtual_deadline_factor(dag_set, processor.get_number_of_cores()),
+        )
+    }
+
+    getset_dag_set_scheduler!(HomogeneousProcessor);
+
+    fn get_virtual_deadline_factor(&self) -> Option<f32> {
+        Some(self.virtual_deadline_factor)
+    }
+}
+
+impl EdfVdScheduler {
+    pub fn new_with_virtual_deadline_factor(
+        dag_set: &[Graph<NodeData, i32>],
+        processor: &HomogeneousProcessor,
+        virtual_deadline_factor: f32,
+    ) -> Self {
+        if virtual_deadline_factor <= 0.0 || virtual_deadline_factor > 1.0 {
+            panic!(
+                "The virtual deadline factor must be in (0, 1]: {}",
+                virtual_deadline_factor
+            );
+        }
+        let mut log = DAGSetSchedulerLog::new(dag_set, processor.get_number_of_cores());
+        log.write_virtual_deadline_factor(virtual_deadline_factor);
+        Self {
+            node_index_maps: get_node_index_maps(dag_set),
+            dag_set: dag_set.to_vec(),
+            processor: processor.clone(),
+            log,
+            current_time: 0,
+            virtual_deadline_factor,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dag_set_scheduler::PreemptiveType;
+    use crate::global_edf_scheduler::GlobalEDFScheduler;
+    use crate::graph_extension::GraphExtension;
+    use crate::log::{ModeChangeEvent, ReleaseEvent};
+    use crate::mixed_criticality::Criticality;
+    use crate::test_util::create_node;
+
+    fn create_dag(dag_id: i32, execution_time: i32, deadline: i32) -> Graph<NodeData, i32> {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", execution_time));
+        dag.add_param(n0, "period", 20);
+        dag.add_param(n0, "end_to_end_deadline", deadline);
+        dag.set_dag_param("dag_id", dag_id);
+        dag
+    }
+
+    #[test]
+    fn test_edf_vd_scheduler_normal() {
+        // The HI node overruns its LO WCET of 2.
+        let mut hi_dag = create_dag(0, 6, 10);
+        let n0 = petgraph::graph::NodeIndex::new(0);
+        hi_dag.add_param(n0, "criticality", 1);
+        hi_dag.add_param(n0, "lo_execution_time", 2);
+        hi_dag.add_param(n0, "hi_execution_time", 6);
+        let mut lo_dag = create_dag(1, 5, 8);
+        lo_dag.add_param(n0, "criticality", 0);
+        let dag_set = vec![hi_dag, lo_dag];
+        let processor = HomogeneousProcessor::new(1);
+        let preemptive_type = PreemptiveType::Preemptive {
+            key: "node_absolute_deadline".to_string(),
+        };
+
+        // x = 0.2 / (1 - 0.625), so the virtual deadline of the HI job is ceil(5.33) = 6.
+        let mut edf_vd_scheduler = EdfVdScheduler::new(&dag_set, &processor);
+        assert!((edf_vd_scheduler.virtual_deadline_factor - 8.0 / 15.0).abs() < 1e-6);
+        edf_vd_scheduler.schedule(preemptive_type.clone());
+        let log = edf_vd_scheduler.get_log_mut();
+        // The HI job runs first and overruns at 2, dropping the LO job.
+        assert_eq!(
+            log.get_mode_change_log()[0],
+            ModeChangeEvent {
+                time: 2,
+                mode: Criticality::Hi,
+                dag_id: Some(0),
+                node_id: Some(0),
+                dropped_dag_ids: vec![1],
+            }
+        );
+        assert_eq!(
+            log.get_release_events(1),
+            &[ReleaseEvent::Dropped { release_time: 0 }]
+        );
+        assert_eq!(log.get_worst_response_time(0), 6);
+        // The slack is to the native deadline.
+        assert_eq!(log.get_slack_summaries()[0].min_slack, 4);
+
+        // With the native deadline, the LO job runs first and the HI job misses its deadline.
+        let mut global_edf_scheduler = GlobalEDFScheduler::new(&dag_set, &processor);
+        global_edf_scheduler.schedule(preemptive_type);
+        let log = global_edf_scheduler.get_log_mut();
+        assert_eq!(log.get_mode_change_log()[0].time, 7);
+        assert_eq!(log.get_worst_response_time(0), 11);
+    }
+
+    #[test]
+    fn test_edf_vd_scheduler_tie() {
+        // The virtual deadline of the HI job, 0.8 * 10, ties with the deadline of the LO job,
+        // which runs first by its smaller dag_id.
+        let mut lo_dag = create_dag(0, 3, 8);
+        let n0 = petgraph::graph::NodeIndex::new(0);
+        lo_dag.add_param(n0, "criticality", 0);
+        let mut hi_dag = create_dag(1, 3, 10);
+        hi_dag.add_param(n0, "criticality", 1);
+        let dag_set = vec![lo_dag, hi_dag];
+        let mut edf_vd_scheduler = EdfVdScheduler::new_with_virtual_deadline_factor(
+            &dag_set,
+            &HomogeneousProcessor::new(1),
+            0.8,
+        );
+        edf_vd_scheduler.schedule(PreemptiveType::Preemptive {
+            key: "node_absolute_deadline".to_string(),
+        });
+        assert_eq!(
+            edf_vd_scheduler.get_log_mut().get_worst_response_times(),
+            vec![3, 6]
+        );
+    }
+
+    #[test]
+    fn test_edf_vd_scheduler_lo_overload() {
+        // The LO DAG fills the core, so that the deadlines are not scaled.
+        let mut hi_dag = create_dag(0, 2, 10);
+        let n0 = petgraph::graph::NodeIndex::new(0);
+        hi_dag.add_param(n0, "criticality", 1);
+        let mut lo_dag = create_dag(1, 8, 8);
+        lo_dag.add_param(n0, "criticality", 0);
+        let dag_set = vec![hi_dag, lo_dag];
+        let edf_vd_scheduler = EdfVdScheduler::new(&dag_set, &HomogeneousProcessor::new(1));
+        assert_eq!(edf_vd_scheduler.get_virtual_deadline_factor(), Some(1.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "The virtual deadline factor must be in (0, 1]: 0")]
+    fn test_edf_vd_scheduler_zero_factor() {
+        EdfVdScheduler::new_with_virtual_deadline_factor(
+            &[create_dag(0, 1, 10)],
+            &HomogeneousProcessor::new(1),
+            0.0,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "The virtual deadline factor must be in (0, 1]: 1.5")]
+    fn test_edf_vd_scheduler_factor_above_one() {
+        EdfVdScheduler::new_with_virtual_deadline_factor(
+            &[create_dag(0, 1, 10)],
+            &HomogeneousProcessor::new(1),
+            1.5,
+        );
+    }
+}
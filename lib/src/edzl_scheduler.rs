@@ -0,0 +1,174 @@
+//! Global EDF with zero-laxity promotion (EDZL).
+//!
+//! The laxity of a node is `node_absolute_deadline - current time - remaining execution time`.
+//! A ready node whose laxity reaches zero must run from now on to meet its deadline, so it is
+//! dispatched before the nodes with earlier deadlines, and preempts the running node with the
+//! latest deadline among those with positive laxity even if the scheduling is non-preemptive.
+//! The other nodes are scheduled by EDF with the given preemptive type.
+use crate::dag_set_scheduler::{DAGSetSchedulerBase, NodeDataWrapper, PreemptiveType, ReadyQueue};
+use crate::getset_dag_set_scheduler;
+use crate::{
+    graph_extension::{get_node_index_maps, NodeData, NodeIndexMap},
+    homogeneous::HomogeneousProcessor,
+    log::DAGSetSchedulerLog,
+    processor::ProcessorBase,
+};
+use petgraph::graph::Graph;
+
+/// `None` if the node has no `node_absolute_deadline`.
+pub fn get_laxity(node_data: &NodeData, remain_proc_time: i32, current_time: i32) -> Option<i32> {
+    let node_absolute_deadline = *node_data.params.get("node_absolute_deadline")?;
+    Some(node_absolute_deadline - current_time - remain_proc_time)
+}
+
+pub struct EDZLScheduler {
+    dag_set: Vec<Graph<NodeData, i32>>,
+    node_index_maps: Vec<NodeIndexMap>,
+    processor: HomogeneousProcessor,
+    log: DAGSetSchedulerLog,
+    current_time: i32,
+}
+
+impl DAGSetSchedulerBase<HomogeneousProcessor> for EDZLScheduler {
+    fn new(dag_set: &[Graph<NodeData, i32>], processor: &HomogeneousProcessor) -> Self {
+        Self {
+            node_index_maps: get_node_index_maps(dag_set),
+            dag_set: dag_set.to_vec(),
+            processor: processor.clone(),
+            log: DAGSetSchedulerLog::new(dag_set, processor.get_number_of_cores()),
+            current_time: 0,
+        }
+    }
+
+    getset_dag_set_scheduler!(HomogeneousProcessor);
+
+    /// The zero-laxity node with the least laxity, or the head of the ready queue.
+    fn select_ready_node(
+        &mut self,
+        ready_queue: &ReadyQueue,
+        can_allocate: impl Fn(&HomogeneousProcessor, &NodeData) -> bool,
+    ) -> Option<NodeDataWrapper> {
+        let candidates: Vec<&NodeDataWrapper> = ready_queue
+            .iter()
+            .filter(|wrapper| can_allocate(&self.processor, &wrapper.node_data))
+            .collect();
+        candidates
+            .iter()
+            .filter_map(|wrapper| {
+                let laxity = self.get_ready_laxity(&wrapper.node_data)?;
+                (laxity <= 0).then_some((laxity, wrapper))
+            })
+            .min_by_key(|&(laxity, _)| laxity)
+            .map(|(_, wrapper)| wrapper)
+            .or(candidates.first())
+            .map(|wrapper| (*wrapper).clone())
+    }
+
+    fn can_preempt(
+        &self,
+        preemptive_type: &PreemptiveType,
+        ready_queue: &ReadyQueue,
+        ready_head_node: &NodeDataWrapper,
+    ) -> Option<usize> {
+        ready_queue.count_comparison();
+        if self
+            .get_ready_laxity(&ready_head_node.node_data)
+            .is_some_and(|laxity| laxity <= 0)
+        {
+            return self.get_zero_laxity_preemptable_core_index();
+        }
+        let preemptive_key = preemptive_type.get_key()?;
+        self.processor.get_preemptable_core_index_after(
+            preemptive_key,
+            ready_head_node.node_data.get_params_value(preemptive_key),
+            preemptive_type.get_non_preemptive_region(),
+        )
+    }
+}
+
+impl EDZLScheduler {
+    /// Laxity of a ready node, whose `execution_time` is the remaining time if preempted.
+    fn get_ready_laxity(&self, node_data: &NodeData) -> Option<i32> {
+        get_laxity(
+            node_data,
+            node_data.get_params_value("execution_time"),
+            self.current_time,
+        )
+    }
+
+    /// The core running the node with the latest deadline among those with positive laxity.
+    fn get_zero_laxity_preemptable_core_index(&self) -> Option<usize> {
+        self.processor
+            .cores
+            .iter()
+            .enumerate()
+            .filter_map(|(core_i, core)| {
+                let node_data = core.get_processing_node().as_ref()?;
+                let laxity = get_laxity(node_data, core.remain_proc_time, self.current_time)?;
+                (laxity > 0).then(|| (node_data.get_params_value("node_absolute_deadline"), core_i))
+            })
+            .max()
+            .map(|(_, core_i)| core_i)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::create_node;
+    use crate::{global_edf_scheduler::GlobalEDFScheduler, graph_extension::GraphExtension};
+
+    fn create_single_node_dag(
+        execution_time: i32,
+        offset: i32,
+        end_to_end_deadline: i32,
+        dag_id: i32,
+    ) -> Graph<NodeData, i32> {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", execution_time));
+        dag.add_param(n0, "period", 10);
+        dag.add_param(n0, "offset", offset);
+        dag.add_param(n0, "end_to_end_deadline", end_to_end_deadline);
+        dag.set_dag_param("dag_id", dag_id);
+        dag
+    }
+
+    #[test]
+    fn test_get_laxity_normal() {
+        let mut node_data = create_node(0, "execution_time", 3);
+        assert_eq!(get_laxity(&node_data, 3, 0), None);
+        node_data
+            .params
+            .insert("node_absolute_deadline".to_string(), 5);
+        assert_eq!(get_laxity(&node_data, 3, 1), Some(1));
+        assert_eq!(get_laxity(&node_data, 3, 2), Some(0));
+    }
+
+    #[test]
+    fn test_edzl_zero_laxity_promotion() {
+        // DAG 2 has zero laxity at its release, while DAGs 0 and 1 with earlier deadlines run.
+        let dag_set = vec![
+            create_single_node_dag(2, 0, 3, 0),
+            create_single_node_dag(2, 0, 3, 1),
+            create_single_node_dag(3, 1, 3, 2),
+        ];
+        let processor = HomogeneousProcessor::new(2);
+
+        let mut edzl_scheduler = EDZLScheduler::new(&dag_set, &processor);
+        edzl_scheduler.schedule(PreemptiveType::NonPreemptive);
+        let log = edzl_scheduler.get_log_mut();
+        // DAG 1 is preempted at 1 and resumes at 2 with zero laxity.
+        assert_eq!(log.get_worst_response_times(), vec![2, 3, 3]);
+
+        let mut global_edf_scheduler = GlobalEDFScheduler::new(&dag_set, &processor);
+        global_edf_scheduler.schedule(PreemptiveType::Preemptive {
+            key: "node_absolute_deadline".to_string(),
+        });
+        assert_eq!(
+            global_edf_scheduler
+                .get_log_mut()
+                .get_worst_response_time(2),
+            4
+        );
+    }
+}
@@ -0,0 +1,272 @@
+//! Elastic scheduling (Buttazzo et al.): when the total utilization of the DAG set exceeds the
+//! number of cores, the periods of the DAGs are stretched like springs in proportion to their
+//! elasticity, and the DAGs are then scheduled by global EDF with the adapted periods.
+//!
+//! A DAG declares its elastic coefficient with the `elasticity` param; DAGs without it (or with 0)
+//! keep their period. `max_period` bounds how far the period of a DAG is stretched. Deadlines
+//! equal to the period are stretched along with it.
+use crate::dag_set_scheduler::DAGSetSchedulerBase;
+use crate::getset_dag_set_scheduler;
+use crate::{
+    graph_extension::{get_node_index_maps, GraphExtension, NodeData, NodeIndexMap},
+    homogeneous::HomogeneousProcessor,
+    log::DAGSetSchedulerLog,
+    processor::ProcessorBase,
+};
+use log::warn;
+use petgraph::graph::Graph;
+
+/// Stretch the periods of `dag_set` so that its total utilization does not exceed `capacity`.
+///
+/// The utilization of each elastic DAG is compressed in proportion to its `elasticity`, and DAGs
+/// reaching their `max_period` are fixed there while the others are compressed further. Adapted
+/// periods are rounded up to a multiple of `granularity`.
+///
+/// # Returns
+///
+/// * The periods in the order of `dag_set`, unchanged if the DAG set fits in `capacity`.
+pub fn adapt_periods(
+    dag_set: &mut [Graph<NodeData, i32>],
+    capacity: f64,
+    granularity: i32,
+) -> Vec<i32> {
+    if granularity <= 0 {
+        panic!(
+            "The granularity must be positive. granularity: {}",
+            granularity
+        );
+    }
+    let get_dag_param = |dag: &Graph<NodeData, i32>, key: &str| {
+        dag.get_dag_param_values(key)
+            .first()
+            .map(|&(_, value)| value)
+    };
+    let periods: Vec<i32> = dag_set
+        .iter()
+        .map(|dag| {
+            dag.get_head_period()
+                .unwrap_or_else(|| panic!("Elastic scheduling needs the period of each DAG."))
+        })
+        .collect();
+    let volumes: Vec<f64> = dag_set.iter().map(|dag| dag.get_volume() as f64).collect();
+    let elasticities: Vec<f64> = dag_set
+        .iter()
+        .map(|dag| {
+            let elasticity = get_dag_param(dag, "elasticity").unwrap_or(0);
+            if elasticity < 0 {
+                panic!(
+                    "The elasticity must not be negative. elasticity: {}",
+                    elasticity
+                );
+            }
+            elasticity as f64
+        })
+        .collect();
+    let max_periods: Vec<Option<i32>> = dag_set
+        .iter()
+        .map(|dag| get_dag_param(dag, "max_period"))
+        .collect();
+    let nominal_utilizations: Vec<f64> = volumes
+        .iter()
+        .zip(&periods)
+        .map(|(volume, &period)| volume / period as f64)
+        .collect();
+    if nominal_utilizations.iter().sum::<f64>() <= capacity {
+        return periods;
+    }
+
+    let mut utilizations = nominal_utilizations.clone();
+    let mut is_fixed: Vec<bool> = elasticities
+        .iter()
+        .map(|&elasticity| elasticity == 0.0)
+        .collect();
+    let mut is_at_max_period = vec![false; dag_set.len()];
+    loop {
+        let variable_dag_ids: Vec<usize> = (0..dag_set.len())
+            .filter(|&dag_i| !is_fixed[dag_i])
+            .collect();
+        if variable_dag_ids.is_empty() {
+            warn!(
+                "The total utilization cannot be compressed to {} within the max periods.",
+                capacity
+            );
+            break;
+        }
+        let fixed_utilization: f64 = (0..dag_set.len())
+            .filter(|&dag_i| is_fixed[dag_i])
+            .map(|dag_i| utilizations[dag_i])
+            .sum();
+        let variable_utilization: f64 = variable_dag_ids
+            .iter()
+            .map(|&dag_i| nominal_utilizations[dag_i])
+            .sum();
+        let total_elasticity: f64 = variable_dag_ids
+            .iter()
+            .map(|&dag_i| elasticities[dag_i])
+            .sum();
+        let excess = variable_utilization + fixed_utilization - capacity;
+        let mut is_compressed = true;
+        for &dag_i in variable_dag_ids.iter() {
+            utilizations[dag_i] =
+                nominal_utilizations[dag_i] - excess * elasticities[dag_i] / total_elasticity;
+            let min_utilization =
+                max_periods[dag_i].map_or(0.0, |max_period| volumes[dag_i] / max_period as f64);
+            if utilizations[dag_i] <= min_utilization {
+                if max_periods[dag_i].is_none() {
+                    panic!(
+                        "DAG {} cannot be stretched enough. Set its max_period.",
+                        dag_set[dag_i].get_dag_param("dag_id")
+                    );
+                }
+                utilizations[dag_i] = min_utilization;
+                is_fixed[dag_i] = true;
+                is_at_max_period[dag_i] = true;
+                is_compressed = false;
+            }
+        }
+        if is_compressed {
+            break;
+        }
+    }
+
+    dag_set
+        .iter_mut()
+        .enumerate()
+        .map(|(dag_i, dag)| {
+            let nominal_period = periods[dag_i];
+            let period = if is_at_max_period[dag_i] {
+                max_periods[dag_i].unwrap()
+            } else if elasticities[dag_i] == 0.0 {
+                nominal_period
+            } else {
+                let quanta = volumes[dag_i] / utilizations[dag_i] / granularity as f64;
+                // Periods that are multiples of the granularity up to the float error.
+                let quanta = if (quanta - quanta.round()).abs() < 1e-9 {
+                    quanta.round()
+                } else {
+                    quanta.ceil()
+                };
+                (quanta as i32 * granularity).max(nominal_period)
+            };
+            for (node_i, _) in dag.get_dag_param_values("period") {
+                dag.update_param(node_i, "period", period);
+            }
+            for (node_i, end_to_end_deadline) in dag.get_dag_param_values("end_to_end_deadline") {
+                if end_to_end_deadline == nominal_period {
+                    dag.update_param(node_i, "end_to_end_deadline", period);
+                }
+            }
+            period
+        })
+        .collect()
+}
+
+pub struct ElasticScheduler {
+    dag_set: Vec<Graph<NodeData, i32>>,
+    node_index_maps: Vec<NodeIndexMap>,
+    processor: HomogeneousProcessor,
+    log: DAGSetSchedulerLog,
+    current_time: i32,
+}
+
+impl DAGSetSchedulerBase<HomogeneousProcessor> for ElasticScheduler {
+    fn new(dag_set: &[Graph<NodeData, i32>], processor: &HomogeneousProcessor) -> Self {
+        Self::new_with_granularity(dag_set, processor, 1)
+    }
+
+    getset_dag_set_scheduler!(HomogeneousProcessor);
+}
+
+impl ElasticScheduler {
+    /// The adapted periods are rounded up to a multiple of `granularity`, which keeps the hyper
+    /// period short.
+    pub fn new_with_granularity(
+        dag_set: &[Graph<NodeData, i32>],
+        processor: &HomogeneousProcessor,
+        granularity: i32,
+    ) -> Self {
+        let mut adapted_dag_set = dag_set.to_vec();
+        let adapted_periods = adapt_periods(
+            &mut adapted_dag_set,
+            processor.get_number_of_cores() as f64,
+            granularity,
+        );
+        let mut log = DAGSetSchedulerLog::new(dag_set, processor.get_number_of_cores());
+        log.write_adapted_periods(&adapted_periods);
+        Self {
+            node_index_maps: get_node_index_maps(&adapted_dag_set),
+            dag_set: adapted_dag_set,
+            processor: processor.clone(),
+            log,
+            current_time: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dag_set_scheduler::PreemptiveType;
+    use crate::test_util::create_node;
+
+    fn create_elastic_dag(
+        dag_id: i32,
+        execution_time: i32,
+        period: i32,
+        elasticity: i32,
+        max_period: Option<i32>,
+    ) -> Graph<NodeData, i32> {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", execution_time));
+        dag.add_param(n0, "period", period);
+        dag.add_param(n0, "end_to_end_deadline", period);
+        dag.add_param(n0, "elasticity", elasticity);
+        if let Some(max_period) = max_period {
+            dag.add_param(n0, "max_period", max_period);
+        }
+        dag.set_dag_param("dag_id", dag_id);
+        dag
+    }
+
+    #[test]
+    fn test_adapt_periods_normal() {
+        // The utilizations 0.5 + 0.5 + 0.5 exceed one core by 0.5, compressed 1:1 between the
+        // elastic DAGs, while the rigid DAG keeps its period.
+        let mut dag_set = vec![
+            create_elastic_dag(0, 5, 10, 0, None),
+            create_elastic_dag(1, 5, 10, 1, None),
+            create_elastic_dag(2, 5, 10, 1, None),
+        ];
+        assert_eq!(adapt_periods(&mut dag_set, 1.0, 1), vec![10, 20, 20]);
+        assert_eq!(dag_set[1].get_end_to_end_deadline(), Some(20));
+
+        // DAG 1 stops at its max period, and DAG 2 is compressed further.
+        let mut dag_set = vec![
+            create_elastic_dag(0, 5, 10, 0, None),
+            create_elastic_dag(1, 5, 10, 1, Some(15)),
+            create_elastic_dag(2, 5, 10, 1, None),
+        ];
+        assert_eq!(adapt_periods(&mut dag_set, 1.0, 1), vec![10, 15, 30]);
+
+        // A DAG set within the capacity keeps its periods.
+        let mut dag_set = vec![create_elastic_dag(0, 5, 10, 1, None)];
+        assert_eq!(adapt_periods(&mut dag_set, 1.0, 1), vec![10]);
+    }
+
+    #[test]
+    fn test_elastic_scheduler_normal() {
+        let dag_set = vec![
+            create_elastic_dag(0, 3, 4, 1, None),
+            create_elastic_dag(1, 3, 4, 1, None),
+        ];
+        let mut elastic_scheduler =
+            ElasticScheduler::new_with_granularity(&dag_set, &HomogeneousProcessor::new(1), 4);
+        elastic_scheduler.schedule(PreemptiveType::NonPreemptive);
+
+        // Both periods stretch from 4 to 6, rounded up to 8, and meet their deadlines.
+        let log = elastic_scheduler.get_log_mut();
+        assert_eq!(log.get_adapted_periods(), Some([8, 8].as_slice()));
+        assert!(log.get_worst_response_time(0) <= 8);
+        assert!(log.get_worst_response_time(1) <= 8);
+    }
+}
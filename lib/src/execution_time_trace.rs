@@ -0,0 +1,82 @@
+//! Replay measured execution times instead of the constant WCETs.
+//!
+//! The trace is a CSV file with the header `dag_id,node_id,job_index,exec_time`, where
+//! `job_index` counts the jobs of the DAG from 0. Nodes of jobs without an entry run for their
+//! `execution_time` as usual.
+#[cfg(feature = "csv")]
+use serde_derive::Deserialize;
+use std::collections::BTreeMap;
+
+#[cfg(feature = "csv")]
+#[derive(Deserialize)]
+struct TraceRecord {
+    dag_id: usize,
+    node_id: i32,
+    job_index: i32,
+    exec_time: i32,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ExecutionTimeTrace {
+    exec_times: BTreeMap<(usize, i32, i32), i32>,
+}
+
+impl ExecutionTimeTrace {
+    #[cfg(feature = "csv")]
+    pub fn from_csv(file_path: &str) -> Self {
+        let mut reader = csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .from_path(file_path)
+            .unwrap_or_else(|err| panic!("Failed to open {}: {}", file_path, err));
+        let mut trace = Self::default();
+        for record in reader.deserialize() {
+            let record: TraceRecord =
+                record.unwrap_or_else(|err| panic!("Invalid record in {}: {}", file_path, err));
+            if record.exec_time <= 0 {
+                panic!(
+                    "exec_time must be positive, but {} for node {} of job {} of DAG {}.",
+                    record.exec_time, record.node_id, record.job_index, record.dag_id
+                );
+            }
+            trace.insert(
+                record.dag_id,
+                record.node_id,
+                record.job_index,
+                record.exec_time,
+            );
+        }
+        trace
+    }
+
+    pub fn insert(&mut self, dag_id: usize, node_id: i32, job_index: i32, exec_time: i32) {
+        self.exec_times
+            .insert((dag_id, node_id, job_index), exec_time);
+    }
+
+    pub fn get_exec_time(&self, dag_id: usize, node_id: i32, job_index: i32) -> Option<i32> {
+        self.exec_times.get(&(dag_id, node_id, job_index)).copied()
+    }
+}
+
+#[cfg(all(test, feature = "csv"))]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_from_csv_normal() {
+        let file_path = "../lib/tests/execution_time_trace_test.csv";
+        fs::write(
+            file_path,
+            "dag_id,node_id,job_index,exec_time\n0, 1, 0, 3\n0, 1, 1, 5\n",
+        )
+        .unwrap();
+        let trace = ExecutionTimeTrace::from_csv(file_path);
+        fs::remove_file(file_path).unwrap();
+
+        assert_eq!(trace.get_exec_time(0, 1, 0), Some(3));
+        assert_eq!(trace.get_exec_time(0, 1, 1), Some(5));
+        assert_eq!(trace.get_exec_time(0, 1, 2), None);
+        assert_eq!(trace.get_exec_time(1, 1, 0), None);
+    }
+}
@@ -0,0 +1,114 @@
+//! Inject transient faults into node executions, with bounded re-execution.
+//!
+//! The fault trace is a CSV file with the header `dag_id,node_id,job_index,attempt`, where
+//! `job_index` counts the jobs of the DAG and `attempt` the executions of the node in the job,
+//! both from 0. A fault is detected when the execution completes, and the node is re-executed for
+//! its whole execution time up to `max_retries` times, taken from the `max_retries` parameter of
+//! the node or the default of the injection. A job whose retries are exhausted has failed, but its
+//! remaining nodes still run.
+use crate::graph_extension::NodeData;
+#[cfg(feature = "csv")]
+use serde_derive::Deserialize;
+use std::collections::BTreeSet;
+
+#[cfg(feature = "csv")]
+#[derive(Deserialize)]
+struct FaultRecord {
+    dag_id: usize,
+    node_id: i32,
+    job_index: i32,
+    attempt: i32,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FaultInjection {
+    /// (dag_id, node_id, job_index, attempt) of the faulty executions.
+    faults: BTreeSet<(usize, i32, i32, i32)>,
+    /// Retries of the nodes without `max_retries`.
+    default_max_retries: i32,
+}
+
+impl FaultInjection {
+    pub fn new(default_max_retries: i32) -> Self {
+        if default_max_retries < 0 {
+            panic!("max_retries must not be negative.");
+        }
+        Self {
+            faults: BTreeSet::new(),
+            default_max_retries,
+        }
+    }
+
+    #[cfg(feature = "csv")]
+    pub fn from_csv(file_path: &str, default_max_retries: i32) -> Self {
+        let mut reader = csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .from_path(file_path)
+            .unwrap_or_else(|err| panic!("Failed to open {}: {}", file_path, err));
+        let mut fault_injection = Self::new(default_max_retries);
+        for record in reader.deserialize() {
+            let record: FaultRecord =
+                record.unwrap_or_else(|err| panic!("Invalid record in {}: {}", file_path, err));
+            fault_injection.insert(
+                record.dag_id,
+                record.node_id,
+                record.job_index,
+                record.attempt,
+            );
+        }
+        fault_injection
+    }
+
+    pub fn insert(&mut self, dag_id: usize, node_id: i32, job_index: i32, attempt: i32) {
+        if job_index < 0 || attempt < 0 {
+            panic!(
+                "job_index and attempt must not be negative, but {} and {} for node {} of DAG {}.",
+                job_index, attempt, node_id, dag_id
+            );
+        }
+        self.faults.insert((dag_id, node_id, job_index, attempt));
+    }
+
+    pub fn is_faulty(&self, dag_id: usize, node_id: i32, job_index: i32, attempt: i32) -> bool {
+        self.faults.contains(&(dag_id, node_id, job_index, attempt))
+    }
+
+    pub fn get_max_retries(&self, node_data: &NodeData) -> i32 {
+        node_data
+            .params
+            .get("max_retries")
+            .copied()
+            .unwrap_or(self.default_max_retries)
+    }
+}
+
+#[cfg(all(test, feature = "csv"))]
+mod tests {
+    use super::*;
+    use std::{collections::BTreeMap, fs};
+
+    #[test]
+    fn test_from_csv_normal() {
+        let file_path = "../lib/tests/fault_injection_test.csv";
+        fs::write(
+            file_path,
+            "dag_id,node_id,job_index,attempt\n0, 1, 0, 0\n0, 1, 0, 1\n",
+        )
+        .unwrap();
+        let fault_injection = FaultInjection::from_csv(file_path, 2);
+        fs::remove_file(file_path).unwrap();
+
+        assert!(fault_injection.is_faulty(0, 1, 0, 0));
+        assert!(fault_injection.is_faulty(0, 1, 0, 1));
+        assert!(!fault_injection.is_faulty(0, 1, 0, 2));
+        assert!(!fault_injection.is_faulty(0, 1, 1, 0));
+
+        let mut node_data = NodeData {
+            id: 1,
+            params: BTreeMap::new(),
+        };
+        assert_eq!(fault_injection.get_max_retries(&node_data), 2);
+        node_data.params.insert("max_retries".to_string(), 0);
+        assert_eq!(fault_injection.get_max_retries(&node_data), 0);
+    }
+}
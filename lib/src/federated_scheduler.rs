@@ -0,0 +1,379 @@
+//! Federated scheduling (Li et al., ECRTS 2014) simulated end to end: each heavy DAG runs alone
+//! on `ceil((volume - L) / (D - L))` dedicated cores by list scheduling, where `L` is the length
+//! of its critical path and `D` its relative deadline, and the light DAGs share the remaining
+//! cores under global EDF.
+//!
+//! The heavy DAGs take their cores in the order of `dag_id`. A heavy DAG whose critical path
+//! exceeds its deadline, or for which not enough cores remain, shares the remaining cores with
+//! the light DAGs and is recorded as unfit.
+use crate::dag_set_scheduler::{DAGSetSchedulerBase, NodeDataWrapper, PreemptiveType, ReadyQueue};
+use crate::getset_dag_set_scheduler;
+use crate::{
+    core::ProcessResult,
+    dag_set_scheduler::{DAGStateManager, DAGStateManagerBase},
+    dag_task::{DagTask, UtilizationClass},
+    graph_extension::{get_node_index_maps, GraphExtension, NodeData, NodeIndexMap},
+    homogeneous::HomogeneousProcessor,
+    log::{DAGSetSchedulerLog, JobEventTimes},
+    processor::ProcessorBase,
+    util::{clear_interrupted, get_hyper_period, get_process_core_indices, is_interrupted},
+};
+use log::warn;
+use petgraph::graph::Graph;
+use serde_derive::{Deserialize, Serialize};
+
+/// Allocation of the cores to the DAGs by federated scheduling.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct FederatedAllocation {
+    /// Dedicated cores of each DAG by `dag_id`, empty for the DAGs on the shared cores.
+    pub dedicated_core_ids: Vec<Vec<usize>>,
+    pub shared_core_ids: Vec<usize>,
+    /// DAGs on the shared cores, i.e., the light DAGs and the unfit heavy DAGs.
+    pub shared_dag_ids: Vec<usize>,
+    /// Heavy DAGs that did not get their dedicated cores.
+    pub unfit_dag_ids: Vec<usize>,
+}
+
+/// Dedicated cores of a heavy DAG, `ceil((volume - L) / (D - L))`.
+/// `None` if the critical path is not shorter than the relative deadline.
+pub fn get_dedicated_core_count(dag: &Graph<NodeData, i32>) -> Option<usize> {
+    let relative_deadline = DagTask::from_graph(dag).get_relative_deadline()?;
+    let critical_path_length = dag.get_total_wcet_from_nodes(&dag.clone().get_critical_path());
+    if critical_path_length >= relative_deadline {
+        return None;
+    }
+    Some(
+        ((dag.get_volume() - critical_path_length) as f32
+            / (relative_deadline - critical_path_length) as f32)
+            .ceil() as usize,
+    )
+}
+
+pub fn allocate_federated_cores(
+    dag_set: &[Graph<NodeData, i32>],
+    number_of_cores: usize,
+) -> FederatedAllocation {
+    let mut allocation = FederatedAllocation::default();
+    let mut next_core_id = 0;
+    for (dag_id, dag) in dag_set.iter().enumerate() {
+        let utilization_class = UtilizationClass::classify(dag)
+            .unwrap_or_else(|| panic!("DAG {} has no period.", dag_id));
+        let mut dedicated_core_ids = Vec::new();
+        if utilization_class == UtilizationClass::Heavy {
+            if let Some(number_of_dedicated_cores) = get_dedicated_core_count(dag) {
+                if number_of_dedicated_cores <= number_of_cores - next_core_id {
+                    dedicated_core_ids =
+                        (next_core_id..next_core_id + number_of_dedicated_cores).collect();
+                    next_core_id += number_of_dedicated_cores;
+                }
+            }
+            if dedicated_core_ids.is_empty() {
+                allocation.unfit_dag_ids.push(dag_id);
+            }
+        }
+        if dedicated_core_ids.is_empty() {
+            allocation.shared_dag_ids.push(dag_id);
+        }
+        allocation.dedicated_core_ids.push(dedicated_core_ids);
+    }
+    allocation.shared_core_ids = (next_core_id..number_of_cores).collect();
+    allocation
+}
+
+pub struct FederatedScheduler {
+    dag_set: Vec<Graph<NodeData, i32>>,
+    node_index_maps: Vec<NodeIndexMap>,
+    processor: HomogeneousProcessor,
+    log: DAGSetSchedulerLog,
+    current_time: i32,
+    allocation: FederatedAllocation,
+}
+
+impl DAGSetSchedulerBase<HomogeneousProcessor> for FederatedScheduler {
+    fn new(dag_set: &[Graph<NodeData, i32>], processor: &HomogeneousProcessor) -> Self {
+        let number_of_cores = processor.get_number_of_cores();
+        let allocation = allocate_federated_cores(dag_set, number_of_cores);
+        if !allocation.unfit_dag_ids.is_empty() {
+            warn!(
+                "Heavy DAGs {:?} get no dedicated cores and share the remaining cores.",
+                allocation.unfit_dag_ids
+            );
+        }
+        if allocation.shared_core_ids.is_empty() && !allocation.shared_dag_ids.is_empty() {
+            warn!(
+                "No cores remain for DAGs {:?}, which never run.",
+                allocation.shared_dag_ids
+            );
+        }
+        let mut log = DAGSetSchedulerLog::new(dag_set, number_of_cores);
+        log.write_federated_allocation(allocation.clone());
+        Self {
+            node_index_maps: get_node_index_maps(dag_set),
+            dag_set: dag_set.to_vec(),
+            processor: processor.clone(),
+            log,
+            current_time: 0,
+            allocation,
+        }
+    }
+
+    getset_dag_set_scheduler!(HomogeneousProcessor);
+
+    /// `preemptive_type` applies to the shared cores. The dedicated cores are non-preemptive.
+    fn schedule(&mut self, preemptive_type: PreemptiveType) -> i32 {
+        clear_interrupted();
+        self.log.write_schedule_start();
+        let mut managers = vec![DAGStateManager::default(); self.dag_set.len()];
+        // The ready queue of each DAG on its dedicated cores, followed by that of the shared
+        // cores.
+        let mut ready_queues = vec![ReadyQueue::new(); self.dag_set.len() + 1];
+        let hyper_period = get_hyper_period(&self.dag_set);
+        while self.current_time < hyper_period && !is_interrupted() {
+            // Release DAGs
+            for ready_node in self.release_dags(&mut managers) {
+                self.insert_ready_node(&mut ready_queues, ready_node);
+            }
+
+            // List-schedule each heavy DAG on its dedicated cores.
+            for dag_id in 0..self.dag_set.len() {
+                let ready_queue = &mut ready_queues[dag_id];
+                for core_i in self.allocation.dedicated_core_ids[dag_id].clone() {
+                    if !self.processor.cores[core_i].get_is_idle() {
+                        continue;
+                    }
+                    let Some(head) = ready_queue.pop_first() else {
+                        break;
+                    };
+                    self.log.write_ready_queue_removal();
+                    let node_data = head.convert_node_data();
+                    self.allocate_node(
+                        &node_data,
+                        core_i,
+                        managers[dag_id].get_release_count() as usize,
+                    );
+                }
+            }
+
+            // Allocate the ready nodes of the shared cores by global EDF.
+            let ready_queue = ready_queues.last_mut().unwrap();
+            while let Some(head) = ready_queue.first().cloned() {
+                let core_i = if let Some(&idle_core_i) = self
+                    .allocation
+                    .shared_core_ids
+                    .iter()
+                    .find(|&&core_id| self.processor.cores[core_id].get_is_idle())
+                {
+                    idle_core_i
+                } else {
+                    let Some(preemptive_key) = preemptive_type.get_key() else {
+                        break;
+                    };
+                    ready_queue.count_comparison();
+                    let Some(core_i) = self.processor.get_preemptable_core_index_among(
+                        self.allocation.shared_core_ids.iter().copied(),
+                        preemptive_key,
+                        head.node_data.get_params_value(preemptive_key),
+                        preemptive_type.get_non_preemptive_region(),
+                    ) else {
+                        break;
+                    };
+                    let current_time = self.current_time;
+                    let preempted_node_data = self.processor.preempt(core_i).unwrap();
+                    self.log.write_job_event(
+                        &preempted_node_data,
+                        core_i,
+                        managers[preempted_node_data.get_params_value("dag_id") as usize]
+                            .get_release_count() as usize
+                            - 1,
+                        JobEventTimes::PreemptedTime(current_time),
+                    );
+                    ready_queue.insert_new(NodeDataWrapper::new(preempted_node_data));
+                    self.log.write_ready_queue_insertion();
+                    core_i
+                };
+                ready_queue.remove(&head);
+                self.log.write_ready_queue_removal();
+                let node_data = head.convert_node_data();
+                self.allocate_node(
+                    &node_data,
+                    core_i,
+                    managers[node_data.get_params_value("dag_id") as usize].get_release_count()
+                        as usize,
+                );
+            }
+
+            // Process unit time
+            let process_result = self.process_unit_time();
+            self.log
+                .write_processing_time(&get_process_core_indices(&process_result));
+
+            // Post-process on completion of node execution
+            for (core_id, result) in process_result.iter().enumerate() {
+                if let ProcessResult::Done(node_data) = result {
+                    let ready_nodes =
+                        self.post_process_on_node_completion(node_data, core_id, &mut managers);
+                    for ready_node in ready_nodes {
+                        self.insert_ready_node(&mut ready_queues, ready_node);
+                    }
+                }
+            }
+        }
+
+        self.log.write_comparisons(
+            ready_queues
+                .iter()
+                .map(ReadyQueue::get_comparison_count)
+                .sum(),
+        );
+        self.calculate_log();
+        self.current_time
+    }
+}
+
+impl FederatedScheduler {
+    pub fn get_allocation(&self) -> &FederatedAllocation {
+        &self.allocation
+    }
+
+    /// Insert the node into the ready queue of the dedicated cores of its DAG if any, or else of
+    /// the shared cores.
+    fn insert_ready_node(&mut self, ready_queues: &mut [ReadyQueue], node_data: NodeData) {
+        let dag_id = node_data.get_params_value("dag_id") as usize;
+        let queue_i = if self.allocation.dedicated_core_ids[dag_id].is_empty() {
+            self.dag_set.len()
+        } else {
+            dag_id
+        };
+        ready_queues[queue_i].insert_new(NodeDataWrapper::new(node_data));
+        self.log.write_ready_queue_insertion();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::create_node;
+
+    fn create_heavy_dag() -> Graph<NodeData, i32> {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 1));
+        dag.add_param(n0, "period", 10);
+        dag.add_param(n0, "end_to_end_deadline", 10);
+        let n4 = dag.add_node(create_node(4, "execution_time", 1));
+        for node_id in 1..4 {
+            let node_i = dag.add_node(create_node(node_id, "execution_time", 4));
+            dag.add_edge(n0, node_i, 1);
+            dag.add_edge(node_i, n4, 1);
+        }
+        dag.set_dag_param("dag_id", 0);
+        dag
+    }
+
+    fn create_single_node_dag(execution_time: i32, dag_id: i32) -> Graph<NodeData, i32> {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", execution_time));
+        dag.add_param(n0, "period", 10);
+        dag.add_param(n0, "end_to_end_deadline", 10);
+        dag.set_dag_param("dag_id", dag_id);
+        dag
+    }
+
+    #[test]
+    fn test_federated_scheduler_normal() {
+        // The heavy DAG (volume 14, critical path 6) needs ceil(8 / 4) = 2 dedicated cores.
+        let dag_set = vec![
+            create_heavy_dag(),
+            create_single_node_dag(6, 1),
+            create_single_node_dag(6, 2),
+            create_single_node_dag(3, 3),
+        ];
+        let mut federated_scheduler =
+            FederatedScheduler::new(&dag_set, &HomogeneousProcessor::new(4));
+        let allocation = federated_scheduler.get_allocation();
+        assert_eq!(
+            allocation.dedicated_core_ids,
+            vec![vec![0, 1], vec![], vec![], vec![]]
+        );
+        assert_eq!(allocation.shared_core_ids, vec![2, 3]);
+        assert_eq!(allocation.shared_dag_ids, vec![1, 2, 3]);
+
+        federated_scheduler.schedule(PreemptiveType::Preemptive {
+            key: "node_absolute_deadline".to_string(),
+        });
+        let log = federated_scheduler.get_log_mut();
+        // DAG 3 waits for the shared cores, although core 1 is idle from 5.
+        assert_eq!(log.get_worst_response_times(), vec![10, 6, 6, 9]);
+        assert_eq!(
+            log.get_federated_allocation().unwrap().shared_core_ids,
+            vec![2, 3]
+        );
+
+        // With three cores, the light DAGs share the last core. With a single core, the heavy DAG
+        // shares it with them.
+        let allocation = allocate_federated_cores(&dag_set, 3);
+        assert_eq!(allocation.shared_core_ids, vec![2]);
+        let allocation = allocate_federated_cores(&dag_set, 1);
+        assert_eq!(allocation.unfit_dag_ids, vec![0]);
+        assert_eq!(allocation.shared_dag_ids, vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_federated_scheduler_critical_path_exceeds_deadline() {
+        // A heavy DAG whose critical path is not shorter than its deadline gets no cores.
+        let dag_set = vec![create_single_node_dag(12, 0), create_single_node_dag(3, 1)];
+        assert_eq!(get_dedicated_core_count(&dag_set[0]), None);
+        assert_eq!(
+            get_dedicated_core_count(&create_single_node_dag(10, 0)),
+            None
+        );
+        let mut federated_scheduler =
+            FederatedScheduler::new(&dag_set, &HomogeneousProcessor::new(4));
+        let allocation = federated_scheduler.get_allocation();
+        assert_eq!(allocation.unfit_dag_ids, vec![0]);
+        assert_eq!(allocation.shared_dag_ids, vec![0, 1]);
+        assert_eq!(allocation.shared_core_ids, vec![0, 1, 2, 3]);
+
+        federated_scheduler.schedule(PreemptiveType::Preemptive {
+            key: "node_absolute_deadline".to_string(),
+        });
+        // The node of DAG 0 is longer than the hyper period.
+        assert_eq!(
+            federated_scheduler.get_log_mut().get_worst_response_times(),
+            vec![i32::MAX, 3]
+        );
+    }
+
+    #[test]
+    fn test_federated_scheduler_no_shared_cores() {
+        // Two heavy DAGs take all four cores in the order of dag_id, so that the light DAG has no
+        // core. With three cores, the second heavy DAG shares the last core instead.
+        let mut second_heavy_dag = create_heavy_dag();
+        second_heavy_dag.set_dag_param("dag_id", 1);
+        let dag_set = vec![
+            create_heavy_dag(),
+            second_heavy_dag,
+            create_single_node_dag(3, 2),
+        ];
+        let allocation = allocate_federated_cores(&dag_set, 4);
+        assert_eq!(
+            allocation.dedicated_core_ids,
+            vec![vec![0, 1], vec![2, 3], vec![]]
+        );
+        assert!(allocation.shared_core_ids.is_empty());
+        assert_eq!(allocation.shared_dag_ids, vec![2]);
+        assert!(allocation.unfit_dag_ids.is_empty());
+
+        let allocation = allocate_federated_cores(&dag_set, 3);
+        assert_eq!(allocation.unfit_dag_ids, vec![1]);
+        assert_eq!(allocation.shared_core_ids, vec![2]);
+        assert_eq!(allocation.shared_dag_ids, vec![1, 2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "DAG 0 has no period.")]
+    fn test_federated_scheduler_no_period() {
+        let mut dag = Graph::<NodeData, i32>::new();
+        dag.add_node(create_node(0, "execution_time", 1));
+        allocate_federated_cores(&[dag], 1);
+    }
+}
@@ -0,0 +1,124 @@
+//! FIFO scheduling of DAG sets: ready nodes are dispatched strictly in the order they became
+//! ready, regardless of their deadlines, as a lower-bound baseline.
+//!
+//! Nodes that became ready in the same time unit are ordered by the canonical ready queue order.
+//! A preempted node arrives again when it returns to the ready queue.
+use crate::dag_set_scheduler::{DAGSetSchedulerBase, NodeDataWrapper, ReadyQueue};
+use crate::getset_dag_set_scheduler;
+use crate::{
+    graph_extension::{get_node_index_maps, NodeData, NodeIndexMap},
+    homogeneous::HomogeneousProcessor,
+    log::DAGSetSchedulerLog,
+    processor::ProcessorBase,
+};
+use petgraph::graph::Graph;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// `(dag_id, node_id, job_id)` of a node in the ready queue.
+type ArrivalKey = (i32, i32, i32);
+
+fn get_arrival_key(node_data: &NodeData) -> ArrivalKey {
+    (
+        node_data.get_params_value("dag_id"),
+        node_data.get_id(),
+        node_data.params.get("job_id").copied().unwrap_or(0),
+    )
+}
+
+pub struct FifoScheduler {
+    dag_set: Vec<Graph<NodeData, i32>>,
+    node_index_maps: Vec<NodeIndexMap>,
+    processor: HomogeneousProcessor,
+    log: DAGSetSchedulerLog,
+    current_time: i32,
+    /// Time each node in the ready queue became ready.
+    arrival_times: BTreeMap<ArrivalKey, i32>,
+}
+
+impl DAGSetSchedulerBase<HomogeneousProcessor> for FifoScheduler {
+    fn new(dag_set: &[Graph<NodeData, i32>], processor: &HomogeneousProcessor) -> Self {
+        Self {
+            node_index_maps: get_node_index_maps(dag_set),
+            dag_set: dag_set.to_vec(),
+            processor: processor.clone(),
+            log: DAGSetSchedulerLog::new(dag_set, processor.get_number_of_cores()),
+            current_time: 0,
+            arrival_times: BTreeMap::new(),
+        }
+    }
+
+    getset_dag_set_scheduler!(HomogeneousProcessor);
+
+    /// The ready node that arrived first, first in the canonical order among ties.
+    fn select_ready_node(
+        &mut self,
+        ready_queue: &ReadyQueue,
+        can_allocate: impl Fn(&HomogeneousProcessor, &NodeData) -> bool,
+    ) -> Option<NodeDataWrapper> {
+        // Stamp the new arrivals, and forget the nodes that left the ready queue.
+        let arrival_keys: BTreeSet<ArrivalKey> = ready_queue
+            .iter()
+            .map(|wrapper| get_arrival_key(&wrapper.node_data))
+            .collect();
+        self.arrival_times
+            .retain(|arrival_key, _| arrival_keys.contains(arrival_key));
+        for arrival_key in arrival_keys {
+            self.arrival_times
+                .entry(arrival_key)
+                .or_insert(self.current_time);
+        }
+        ready_queue
+            .iter()
+            .filter(|wrapper| can_allocate(&self.processor, &wrapper.node_data))
+            .min_by_key(|wrapper| {
+                ready_queue.count_comparison();
+                self.arrival_times[&get_arrival_key(&wrapper.node_data)]
+            })
+            .cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dag_set_scheduler::PreemptiveType;
+    use crate::global_edf_scheduler::GlobalEDFScheduler;
+    use crate::graph_extension::GraphExtension;
+    use crate::test_util::create_node;
+
+    fn create_single_node_dag(
+        dag_id: i32,
+        execution_time: i32,
+        end_to_end_deadline: i32,
+        offset: i32,
+    ) -> Graph<NodeData, i32> {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", execution_time));
+        dag.add_param(n0, "period", 20);
+        dag.add_param(n0, "end_to_end_deadline", end_to_end_deadline);
+        dag.add_param(n0, "offset", offset);
+        dag.set_dag_param("dag_id", dag_id);
+        dag
+    }
+
+    #[test]
+    fn test_fifo_scheduler_arrival_order() {
+        let dag_set = vec![
+            create_single_node_dag(0, 3, 20, 0),
+            create_single_node_dag(1, 2, 20, 1),
+            create_single_node_dag(2, 2, 6, 2),
+        ];
+
+        // DAG 1 arrived before DAG 2, which has the earlier deadline, so it runs first at 3.
+        let mut fifo_scheduler = FifoScheduler::new(&dag_set, &HomogeneousProcessor::new(1));
+        fifo_scheduler.schedule(PreemptiveType::NonPreemptive);
+        let log = fifo_scheduler.get_log_mut();
+        assert_eq!(log.get_worst_response_times(), vec![3, 4, 5]);
+
+        let mut global_edf_scheduler =
+            GlobalEDFScheduler::new(&dag_set, &HomogeneousProcessor::new(1));
+        global_edf_scheduler.schedule(PreemptiveType::NonPreemptive);
+        let log = global_edf_scheduler.get_log_mut();
+        assert_eq!(log.get_worst_response_times(), vec![3, 6, 3]);
+    }
+}
@@ -1,5 +1,6 @@
 use crate::{
-    dag_scheduler::DAGSchedulerBase, graph_extension::NodeData, log::*, processor::ProcessorBase,
+    dag_scheduler::DAGSchedulerBase, dag_set_scheduler::PreemptiveType, graph_extension::NodeData,
+    log::*, processor::ProcessorBase,
 };
 use log::warn;
 use petgraph::Graph;
@@ -13,6 +14,7 @@ where
     dag: Graph<NodeData, i32>,
     processor: T,
     log: DAGSchedulerLog,
+    preemptive_type: PreemptiveType,
 }
 
 impl<T> DAGSchedulerBase<T> for FixedPriorityScheduler<T>
@@ -24,6 +26,7 @@ where
             dag: dag.clone(),
             processor: processor.clone(),
             log: DAGSchedulerLog::new(dag, processor.get_number_of_cores()),
+            preemptive_type: PreemptiveType::NonPreemptive,
         }
     }
 
@@ -62,25 +65,35 @@ where
             })
         });
     }
+
+    fn get_preemptive_type(&self) -> PreemptiveType {
+        self.preemptive_type.clone()
+    }
 }
 
-#[cfg(test)]
+impl<T> FixedPriorityScheduler<T>
+where
+    T: ProcessorBase + Clone,
+{
+    /// Preempt running nodes by the `priority` param, e.g., with
+    /// `PreemptiveType::Preemptive { key: "priority".to_string() }`.
+    /// A running node with a `preemption_threshold` is only preempted by a node whose priority is
+    /// smaller than its own priority minus the threshold.
+    pub fn set_preemptive_type(&mut self, preemptive_type: PreemptiveType) {
+        self.preemptive_type = preemptive_type;
+    }
+}
+
+#[cfg(all(test, feature = "yaml"))]
 mod tests {
-    use std::{collections::BTreeMap, fs::remove_file};
 
     use super::*;
     use crate::graph_extension::GraphExtension;
     use crate::homogeneous::HomogeneousProcessor;
     use crate::processor::ProcessorBase;
-    use crate::util::load_yaml;
+    use crate::test_util::create_node;
     use petgraph::graph::{Graph, NodeIndex};
 
-    fn create_node(id: i32, key: &str, value: i32) -> NodeData {
-        let mut params = BTreeMap::new();
-        params.insert(key.to_string(), value);
-        NodeData { id, params }
-    }
-
     #[test]
     fn test_fixed_priority_scheduler_schedule_normal() {
         let mut dag = Graph::<NodeData, i32>::new();
@@ -178,7 +191,79 @@ mod tests {
     }
 
     #[test]
+    fn test_fixed_priority_scheduler_preemption_threshold() {
+        let get_schedule_length = |preemptive_type: PreemptiveType, threshold: Option<i32>| {
+            let mut dag = Graph::<NodeData, i32>::new();
+            let n0 = dag.add_node(create_node(0, "execution_time", 1));
+            let n1 = dag.add_node(create_node(1, "execution_time", 2));
+            let n2 = dag.add_node(create_node(2, "execution_time", 10));
+            let n3 = dag.add_node(create_node(3, "execution_time", 3));
+            let n4 = dag.add_node(create_node(4, "execution_time", 3));
+            dag.add_param(n0, "period", 100);
+            for (node_i, priority) in [(n0, 0), (n1, 1), (n2, 5), (n3, 0), (n4, 0)] {
+                dag.add_param(node_i, "priority", priority);
+            }
+            if let Some(threshold) = threshold {
+                dag.add_param(n2, "preemption_threshold", threshold);
+            }
+            dag.add_edge(n0, n1, 1);
+            dag.add_edge(n0, n2, 1);
+            dag.add_edge(n1, n3, 1);
+            dag.add_edge(n1, n4, 1);
+
+            let mut fixed_priority_scheduler =
+                FixedPriorityScheduler::new(&dag, &HomogeneousProcessor::new(2));
+            fixed_priority_scheduler.set_preemptive_type(preemptive_type);
+            fixed_priority_scheduler.schedule().0
+        };
+        let preemptive = || PreemptiveType::Preemptive {
+            key: "priority".to_string(),
+        };
+
+        // Node 2 runs from 1 to 11 while nodes 3 and 4 share the other core.
+        assert_eq!(get_schedule_length(PreemptiveType::NonPreemptive, None), 11);
+        // Node 4 preempts node 2 at 3, which resumes at 6 with 8 remaining.
+        assert_eq!(get_schedule_length(preemptive(), None), 14);
+        assert_eq!(get_schedule_length(preemptive(), Some(4)), 14);
+        // Priority 0 is not higher than 5 minus the threshold.
+        assert_eq!(get_schedule_length(preemptive(), Some(5)), 11);
+    }
+
+    #[test]
+    fn test_fixed_priority_scheduler_critical_path_first_priorities() {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 1));
+        dag.add_param(n0, "period", 100);
+        for node_id in 1..4 {
+            let node_i = dag.add_node(create_node(node_id, "execution_time", 5));
+            dag.add_edge(n0, node_i, 1);
+        }
+        let n4 = dag.add_node(create_node(4, "execution_time", 10));
+        let n5 = dag.add_node(create_node(5, "execution_time", 10));
+        dag.add_edge(n0, n4, 1);
+        dag.add_edge(n4, n5, 1);
+        for node_i in dag.node_indices() {
+            let node_id = dag[node_i].get_id();
+            dag.add_param(node_i, "priority", node_id);
+        }
+
+        // In the id order, the critical path waits for nodes 1 and 2.
+        let mut fixed_priority_scheduler =
+            FixedPriorityScheduler::new(&dag, &HomogeneousProcessor::new(2));
+        assert_eq!(fixed_priority_scheduler.schedule().0, 26);
+
+        dag.assign_critical_path_first_priorities();
+        let mut fixed_priority_scheduler =
+            FixedPriorityScheduler::new(&dag, &HomogeneousProcessor::new(2));
+        assert_eq!(fixed_priority_scheduler.schedule().0, 21);
+    }
+
+    #[test]
+    #[cfg(feature = "analysis")]
     fn test_fixed_priority_scheduler_log_normal() {
+        use crate::fixture::{assert_matches_fixture, create_test_output_dir, get_fixture_path};
+        use std::fs;
+
         let mut dag = Graph::<NodeData, i32>::new();
         //cX is the Xth critical node.
         let c0 = dag.add_node(create_node(0, "execution_time", 52));
@@ -203,69 +288,21 @@ mod tests {
             FixedPriorityScheduler::new(&dag, &HomogeneousProcessor::new(2));
         fixed_priority_scheduler.schedule();
 
-        let file_path = fixed_priority_scheduler.dump_log("tests", "test");
-        let yaml_docs = load_yaml(&file_path);
-        let yaml_doc = &yaml_docs[0];
+        let dir_path = create_test_output_dir("fixed_priority_log_normal");
+        let file_path = fixed_priority_scheduler.dump_log(&dir_path, "fixed_priority_log_normal");
 
-        assert_eq!(
-            yaml_doc["dag_info"]["critical_path_length"]
-                .as_i64()
-                .unwrap(),
-            92
+        assert_matches_fixture(
+            &fs::read_to_string(&file_path).unwrap(),
+            &get_fixture_path("fixed_priority_log_normal", std::slice::from_ref(&dag)),
         );
-
-        assert_eq!(yaml_doc["dag_info"]["period"].as_i64().unwrap(), 100);
+        // Key values of the fixture, checked by hand.
+        let yaml_doc = &crate::util::load_yaml(&file_path)[0];
         assert_eq!(
-            yaml_doc["dag_info"]["end_to_end_deadline"]
-                .as_i64()
-                .unwrap(),
-            0
-        );
-        assert_eq!(yaml_doc["dag_info"]["volume"].as_i64().unwrap(), 114);
-        assert_eq!(
-            yaml_doc["dag_info"]["utilization"].as_f64().unwrap(),
-            0.877193
-        );
-
-        assert_eq!(
-            yaml_doc["processor_info"]["number_of_cores"]
-                .as_i64()
-                .unwrap(),
-            2
-        );
-
-        assert_eq!(
-            yaml_doc["processor_log"]["average_utilization"]
-                .as_f64()
-                .unwrap(),
-            0.61956525
-        );
-        assert_eq!(
-            yaml_doc["processor_log"]["variance_utilization"]
-                .as_f64()
-                .unwrap(),
-            0.14473063
-        );
-        assert_eq!(
-            yaml_doc["processor_log"]["core_logs"][0]["core_id"]
-                .as_i64()
-                .unwrap(),
-            0
-        );
-        assert_eq!(
-            yaml_doc["processor_log"]["core_logs"][0]["total_proc_time"]
+            yaml_doc["dag_info"]["critical_path_length"]
                 .as_i64()
                 .unwrap(),
             92
         );
-        assert_eq!(
-            yaml_doc["processor_log"]["core_logs"][0]["utilization"]
-                .as_f64()
-                .unwrap(),
-            1.0
-        );
-
-        assert_eq!(yaml_doc["node_logs"][0]["dag_id"].as_i64().unwrap(), 0);
         assert_eq!(yaml_doc["node_logs"][0]["node_id"].as_i64().unwrap(), 0);
         assert_eq!(yaml_doc["node_logs"][0]["core_id"].as_i64().unwrap(), 0);
         // start_time
@@ -278,7 +315,17 @@ mod tests {
             yaml_doc["node_logs"][1]["event_time"].as_str().unwrap(),
             "52"
         );
-
-        remove_file(file_path).unwrap();
+        let processor_log = &yaml_doc["processor_log"];
+        assert_eq!(
+            processor_log["average_utilization"].as_f64().unwrap(),
+            0.61956525
+        );
+        assert_eq!(
+            processor_log["core_logs"][0]["total_proc_time"]
+                .as_i64()
+                .unwrap(),
+            92
+        );
+        fs::remove_dir_all(dir_path).unwrap();
     }
 }
@@ -0,0 +1,254 @@
+//! Golden fixtures: logs recorded by the binaries with `--record_fixtures` into
+//! `lib/tests/fixtures/`, against which later runs are compared field by field.
+//!
+//! A fixture is named `<alg_name>-<hash>.yaml` with the hash of the input DAG set
+//! (see `get_dag_set_hash`), so that recording the same run again overwrites it.
+//! Unit tests name their fixtures after the test instead of the algorithm, and dump the logs
+//! to compare into `create_test_output_dir`.
+use crate::{graph_extension::NodeData, output_log::get_dag_set_hash};
+use petgraph::graph::Graph;
+use serde::Deserialize;
+use serde_yaml::Value;
+use std::fs;
+
+pub const FIXTURE_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures");
+
+/// Relative tolerance of floats, which absorbs the rounding of `f32` values printed to YAML.
+pub const FLOAT_TOLERANCE: f64 = 1e-5;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FieldMismatch {
+    /// Path of the field, e.g., `dag_set_log[0].worst_response_time`.
+    pub path: String,
+    pub expected: String,
+    pub actual: String,
+}
+
+pub fn get_fixture_path(alg_name: &str, dag_set: &[Graph<NodeData, i32>]) -> String {
+    format!(
+        "{}/{}-{}.yaml",
+        FIXTURE_DIR,
+        alg_name,
+        get_dag_set_hash(dag_set)
+    )
+}
+
+/// Copy the YAML log of a run to its fixture.
+///
+/// # Returns
+///
+/// * The path of the fixture.
+pub fn record_fixture(log_path: &str, alg_name: &str, dag_set: &[Graph<NodeData, i32>]) -> String {
+    if !log_path.ends_with(".yaml") {
+        panic!("Fixtures are recorded from YAML logs: {}", log_path);
+    }
+    fs::create_dir_all(FIXTURE_DIR).unwrap();
+    let fixture_path = get_fixture_path(alg_name, dag_set);
+    fs::copy(log_path, &fixture_path).unwrap();
+    fixture_path
+}
+
+/// Create an empty directory for the logs of the test `name` under the temporary directory of
+/// the system, so that tests compared with fixtures do not write into the source tree.
+///
+/// # Returns
+///
+/// * The path of the directory.
+pub fn create_test_output_dir(name: &str) -> String {
+    let dir_path = std::env::temp_dir().join(format!("sched_sim-{}-{}", name, std::process::id()));
+    if dir_path.exists() {
+        fs::remove_dir_all(&dir_path).unwrap();
+    }
+    fs::create_dir_all(&dir_path).unwrap();
+    dir_path.to_str().unwrap().to_string()
+}
+
+/// Parse all the documents of a YAML text.
+fn load_documents(yaml: &str) -> Vec<Value> {
+    serde_yaml::Deserializer::from_str(yaml)
+        .map(|document| Value::deserialize(document).expect("Failed to parse YAML."))
+        .collect()
+}
+
+fn is_close(expected: f64, actual: f64, tolerance: f64) -> bool {
+    (expected - actual).abs() <= tolerance * expected.abs().max(actual.abs()).max(1.0)
+}
+
+fn get_key_path(path: &str, key: &Value) -> String {
+    let key = match key.as_str() {
+        Some(key) => key.to_string(),
+        None => serde_yaml::to_string(key).unwrap().trim_end().to_string(),
+    };
+    if path.is_empty() {
+        key
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+fn compare_values(
+    path: &str,
+    expected: &Value,
+    actual: &Value,
+    tolerance: f64,
+    mismatches: &mut Vec<FieldMismatch>,
+) {
+    let mut push_mismatch = |expected: &Value, actual: &Value| {
+        mismatches.push(FieldMismatch {
+            path: path.to_string(),
+            expected: serde_yaml::to_string(expected)
+                .unwrap()
+                .trim_end()
+                .to_string(),
+            actual: serde_yaml::to_string(actual)
+                .unwrap()
+                .trim_end()
+                .to_string(),
+        })
+    };
+    match (expected, actual) {
+        (Value::Mapping(expected_map), Value::Mapping(actual_map)) => {
+            for (key, expected_value) in expected_map {
+                compare_values(
+                    &get_key_path(path, key),
+                    expected_value,
+                    actual_map.get(key).unwrap_or(&Value::Null),
+                    tolerance,
+                    mismatches,
+                );
+            }
+            for (key, actual_value) in actual_map {
+                if !expected_map.contains_key(key) {
+                    compare_values(
+                        &get_key_path(path, key),
+                        &Value::Null,
+                        actual_value,
+                        tolerance,
+                        mismatches,
+                    );
+                }
+            }
+        }
+        (Value::Sequence(expected_seq), Value::Sequence(actual_seq))
+            if expected_seq.len() == actual_seq.len() =>
+        {
+            for (i, (expected_value, actual_value)) in
+                expected_seq.iter().zip(actual_seq).enumerate()
+            {
+                compare_values(
+                    &format!("{}[{}]", path, i),
+                    expected_value,
+                    actual_value,
+                    tolerance,
+                    mismatches,
+                );
+            }
+        }
+        (Value::Number(expected_number), Value::Number(actual_number))
+            if expected_number.is_f64() || actual_number.is_f64() =>
+        {
+            if !is_close(
+                expected_number.as_f64().unwrap(),
+                actual_number.as_f64().unwrap(),
+                tolerance,
+            ) {
+                push_mismatch(expected, actual);
+            }
+        }
+        _ => {
+            if expected != actual {
+                push_mismatch(expected, actual);
+            }
+        }
+    }
+}
+
+/// Compare a YAML log with a fixture field by field, with `tolerance` for floats.
+///
+/// # Returns
+///
+/// * The mismatched fields. A field missing on one side is compared with null.
+pub fn compare_with_fixture(
+    actual_yaml: &str,
+    fixture_path: &str,
+    tolerance: f64,
+) -> Vec<FieldMismatch> {
+    let fixture = fs::read_to_string(fixture_path)
+        .unwrap_or_else(|_| panic!("Fixture not found: {}", fixture_path));
+    let expected_documents = load_documents(&fixture);
+    let actual_documents = load_documents(actual_yaml);
+    let mut mismatches = Vec::new();
+    if expected_documents.len() != actual_documents.len() {
+        mismatches.push(FieldMismatch {
+            path: String::new(),
+            expected: format!("{} documents", expected_documents.len()),
+            actual: format!("{} documents", actual_documents.len()),
+        });
+        return mismatches;
+    }
+    for (expected, actual) in expected_documents.iter().zip(&actual_documents) {
+        compare_values("", expected, actual, tolerance, &mut mismatches);
+    }
+    mismatches
+}
+
+/// Panic with the mismatched fields if a YAML log differs from a fixture.
+pub fn assert_matches_fixture(actual_yaml: &str, fixture_path: &str) {
+    let mismatches = compare_with_fixture(actual_yaml, fixture_path, FLOAT_TOLERANCE);
+    if !mismatches.is_empty() {
+        let details: Vec<String> = mismatches
+            .iter()
+            .map(|mismatch| {
+                format!(
+                    "{}: expected {}, actual {}",
+                    mismatch.path, mismatch.expected, mismatch.actual
+                )
+            })
+            .collect();
+        panic!(
+            "The log differs from the fixture {}:\n{}",
+            fixture_path,
+            details.join("\n")
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compare_with_fixture_normal() {
+        let dir_path = create_test_output_dir("compare_with_fixture");
+        let fixture_path = format!("{}/fixture.yaml", dir_path);
+        fs::write(
+            &fixture_path,
+            "utilization: 0.3333333\ndag_set_log:\n- worst_response_time: 10\n- worst_response_time: 6\n",
+        )
+        .unwrap();
+
+        let mismatches = compare_with_fixture(
+            "utilization: 0.33333334\ndag_set_log:\n- worst_response_time: 10\n- worst_response_time: 7\n",
+            &fixture_path,
+            FLOAT_TOLERANCE,
+        );
+        assert_eq!(
+            mismatches,
+            vec![FieldMismatch {
+                path: "dag_set_log[1].worst_response_time".to_string(),
+                expected: "6".to_string(),
+                actual: "7".to_string(),
+            }]
+        );
+
+        let mismatches = compare_with_fixture("utilization: 0.4\n", &fixture_path, FLOAT_TOLERANCE);
+        assert_eq!(
+            mismatches
+                .iter()
+                .map(|mismatch| mismatch.path.as_str())
+                .collect::<Vec<_>>(),
+            vec!["utilization", "dag_set_log"]
+        );
+        fs::remove_dir_all(dir_path).unwrap();
+    }
+}
@@ -0,0 +1,133 @@
+//! Fractional core containers: a container reserves a bandwidth (a fraction of a core) for a DAG,
+//! and the containers are packed onto the shared cores, each of which serves its containers by EDF.
+//!
+//! A shared core can serve its containers as long as their total bandwidth does not exceed one.
+//! The containers are packed in decreasing order of bandwidth, so the heuristics behave as
+//! their decreasing variants (e.g., First-Fit Decreasing).
+use crate::partitioned_edf_scheduler::PartitioningHeuristic;
+use serde_derive::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CoreContainer {
+    pub dag_id: usize,
+    /// Fraction of a core in (0, 1].
+    pub bandwidth: f32,
+}
+
+impl CoreContainer {
+    pub fn new(dag_id: usize, bandwidth: f32) -> Self {
+        if bandwidth <= 0.0 || bandwidth > 1.0 {
+            panic!(
+                "The bandwidth of the container of DAG {} must be in (0, 1]: {}",
+                dag_id, bandwidth
+            );
+        }
+        Self { dag_id, bandwidth }
+    }
+}
+
+/// Assignment of the containers to the shared cores.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ContainerPacking {
+    /// Containers of each shared core.
+    pub core_containers: Vec<Vec<CoreContainer>>,
+    /// Containers that fit on no shared core.
+    pub unfit_containers: Vec<CoreContainer>,
+}
+
+impl ContainerPacking {
+    pub fn get_core_bandwidth(&self, core_i: usize) -> f32 {
+        self.core_containers[core_i]
+            .iter()
+            .map(|container| container.bandwidth)
+            .sum()
+    }
+
+    /// Shared core of the container of the DAG.
+    pub fn get_core_index(&self, dag_id: usize) -> Option<usize> {
+        self.core_containers.iter().position(|containers| {
+            containers
+                .iter()
+                .any(|container| container.dag_id == dag_id)
+        })
+    }
+
+    pub fn is_feasible(&self) -> bool {
+        self.unfit_containers.is_empty()
+    }
+}
+
+/// Ties are broken by the smallest core index.
+pub fn pack_containers(
+    containers: &[CoreContainer],
+    number_of_cores: usize,
+    heuristic: PartitioningHeuristic,
+) -> ContainerPacking {
+    let mut sorted_containers = containers.to_vec();
+    // Stable, so that containers of the same bandwidth are packed in the given order.
+    sorted_containers.sort_by(|a, b| b.bandwidth.total_cmp(&a.bandwidth));
+
+    let mut packing = ContainerPacking {
+        core_containers: vec![Vec::new(); number_of_cores],
+        unfit_containers: Vec::new(),
+    };
+    let mut core_bandwidths = vec![0.0; number_of_cores];
+    for container in sorted_containers {
+        let fit_core_indices = (0..number_of_cores)
+            .filter(|&core_i| core_bandwidths[core_i] + container.bandwidth <= 1.0);
+        let core_i = match heuristic {
+            PartitioningHeuristic::FirstFit => fit_core_indices.min(),
+            PartitioningHeuristic::BestFit => fit_core_indices
+                .rev()
+                .max_by(|&a, &b| core_bandwidths[a].total_cmp(&core_bandwidths[b])),
+            PartitioningHeuristic::WorstFit => {
+                fit_core_indices.min_by(|&a, &b| core_bandwidths[a].total_cmp(&core_bandwidths[b]))
+            }
+        };
+        match core_i {
+            Some(core_i) => {
+                core_bandwidths[core_i] += container.bandwidth;
+                packing.core_containers[core_i].push(container);
+            }
+            None => packing.unfit_containers.push(container),
+        }
+    }
+    packing
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pack_containers_normal() {
+        let containers = vec![
+            CoreContainer::new(0, 0.25),
+            CoreContainer::new(1, 0.5),
+            CoreContainer::new(2, 0.75),
+            CoreContainer::new(3, 0.5),
+        ];
+        let packing = pack_containers(&containers, 2, PartitioningHeuristic::FirstFit);
+        assert!(packing.is_feasible());
+        // 0.75 and 0.25 on core 0, and 0.5 and 0.5 on core 1.
+        assert_eq!(packing.get_core_index(2), Some(0));
+        assert_eq!(packing.get_core_index(0), Some(0));
+        assert_eq!(packing.get_core_bandwidth(1), 1.0);
+
+        let packing = pack_containers(&containers, 1, PartitioningHeuristic::WorstFit);
+        assert_eq!(
+            packing
+                .unfit_containers
+                .iter()
+                .map(|container| container.dag_id)
+                .collect::<Vec<_>>(),
+            vec![1, 3]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_core_container_new_over_bandwidth() {
+        CoreContainer::new(0, 1.5);
+    }
+}
@@ -0,0 +1,291 @@
+//! Global non-preemptive EDF for rigid gang nodes (Gang-EDF).
+//!
+//! A node with `gang_size` runs on `gang_size` cores simultaneously for its `execution_time`
+//! (e.g., a parallel section with a fixed number of threads). Nodes without `gang_size` need
+//! one core. Ready nodes are visited in EDF order and each is dispatched only when enough cores
+//! are idle at the same time.
+//!
+//! The first node that does not fit reserves the cores that become idle the earliest, and later
+//! nodes are backfilled only if they do not delay it (EASY backfilling), so that a stream of
+//! narrow nodes cannot starve a wide gang. The job events of a gang are logged on its first
+//! core only.
+use crate::dag_set_scheduler::{
+    DAGSetSchedulerBase, DAGStateManager, DAGStateManagerBase, NodeDataWrapper, PreemptiveType,
+    ReadyQueue,
+};
+use crate::getset_dag_set_scheduler;
+use crate::{
+    core::ProcessResult,
+    graph_extension::{get_node_index_maps, NodeData, NodeIndexMap},
+    homogeneous::HomogeneousProcessor,
+    log::DAGSetSchedulerLog,
+    processor::ProcessorBase,
+    util::{clear_interrupted, get_hyper_period, get_process_core_indices, is_interrupted},
+};
+use petgraph::graph::Graph;
+use std::collections::HashSet;
+
+/// Marks the copies of a gang node on the cores other than the first one.
+const GANG_MEMBER_KEY: &str = "gang_member";
+
+pub fn get_gang_size(node_data: &NodeData) -> usize {
+    node_data
+        .params
+        .get("gang_size")
+        .map_or(1, |&gang_size| gang_size as usize)
+}
+
+/// Cores reserved for the first ready node in EDF order that does not fit.
+struct Reservation {
+    /// Time until enough cores are idle for the reserved node.
+    start_delay: i32,
+    /// Cores left over when the reserved node starts.
+    extra_cores: usize,
+}
+
+impl Reservation {
+    /// Whether a node on `gang_size` cores for `execution_time` does not delay the reserved node,
+    /// i.e., it finishes before the reserved node starts or runs on the left-over cores.
+    fn can_backfill(&self, gang_size: usize, execution_time: i32) -> bool {
+        execution_time <= self.start_delay || gang_size <= self.extra_cores
+    }
+
+    fn backfill(&mut self, gang_size: usize, execution_time: i32) {
+        if execution_time > self.start_delay {
+            self.extra_cores -= gang_size;
+        }
+    }
+}
+
+pub struct GangEDFScheduler {
+    dag_set: Vec<Graph<NodeData, i32>>,
+    node_index_maps: Vec<NodeIndexMap>,
+    processor: HomogeneousProcessor,
+    log: DAGSetSchedulerLog,
+    current_time: i32,
+}
+
+impl DAGSetSchedulerBase<HomogeneousProcessor> for GangEDFScheduler {
+    fn new(dag_set: &[Graph<NodeData, i32>], processor: &HomogeneousProcessor) -> Self {
+        Self {
+            node_index_maps: get_node_index_maps(dag_set),
+            dag_set: dag_set.to_vec(),
+            processor: processor.clone(),
+            log: DAGSetSchedulerLog::new(dag_set, processor.get_number_of_cores()),
+            current_time: 0,
+        }
+    }
+
+    getset_dag_set_scheduler!(HomogeneousProcessor);
+
+    /// The preemptive type is ignored because a gang cannot be preempted.
+    fn schedule(&mut self, _preemptive_type: PreemptiveType) -> i32 {
+        clear_interrupted();
+        self.log.write_schedule_start();
+        let number_of_cores = self.processor.get_number_of_cores();
+        for dag in self.dag_set.iter() {
+            for node_data in dag.node_weights() {
+                if get_gang_size(node_data) > number_of_cores {
+                    panic!(
+                        "gang_size of node {} is {}, but there are only {} cores.",
+                        node_data.id,
+                        get_gang_size(node_data),
+                        number_of_cores
+                    );
+                }
+            }
+        }
+
+        let mut managers = vec![DAGStateManager::default(); self.dag_set.len()];
+        let mut ready_queue = ReadyQueue::new();
+        let hyper_period = get_hyper_period(&self.dag_set);
+        while self.get_current_time() < hyper_period && !is_interrupted() {
+            // Release DAGs
+            for ready_node in self.release_dags(&mut managers) {
+                ready_queue.insert_new(NodeDataWrapper::new(ready_node));
+                self.log.write_ready_queue_insertion();
+            }
+
+            // Allocate each ready node in EDF order to `gang_size` idle cores if available.
+            let wrappers: Vec<NodeDataWrapper> = ready_queue.iter().cloned().collect();
+            let mut reservation: Option<Reservation> = None;
+            for wrapper in wrappers {
+                if !self.can_allocate_node(&wrapper.node_data) {
+                    continue;
+                }
+                let gang_size = get_gang_size(&wrapper.node_data);
+                let execution_time = wrapper.node_data.get_params_value("execution_time");
+                if reservation
+                    .as_ref()
+                    .is_some_and(|reservation| !reservation.can_backfill(gang_size, execution_time))
+                {
+                    continue;
+                }
+                let node_data = wrapper.convert_node_data();
+                let mut member_node_data = node_data.clone();
+                member_node_data
+                    .params
+                    .insert(GANG_MEMBER_KEY.to_string(), 1);
+                let mut gang = vec![member_node_data; gang_size];
+                gang[0] = node_data.clone();
+                let Some(core_indices) = self.processor.allocate_gang(&gang) else {
+                    if reservation.is_none() {
+                        reservation = Some(self.reserve(gang_size));
+                    }
+                    continue;
+                };
+                if let Some(reservation) = &mut reservation {
+                    reservation.backfill(gang_size, execution_time);
+                }
+                ready_queue.remove(&wrapper);
+                self.log.write_ready_queue_removal();
+                let dag_id = node_data.get_params_value("dag_id") as usize;
+                let job_id = managers[dag_id].get_release_count() as usize;
+                self.log.write_allocating_job(
+                    &node_data,
+                    core_indices[0],
+                    job_id,
+                    self.current_time,
+                );
+            }
+
+            // The remaining ready nodes wait for enough idle cores.
+            if self.processor.get_idle_core_num() > 0 {
+                let waiting_dag_ids: HashSet<usize> = ready_queue
+                    .iter()
+                    .map(|wrapper| wrapper.node_data.get_params_value("dag_id") as usize)
+                    .collect();
+                for dag_id in waiting_dag_ids {
+                    self.log.write_gang_waiting_time(dag_id);
+                }
+            }
+
+            // Process unit time
+            self.current_time += 1;
+            let process_result = self.processor.process();
+            let indices: Vec<usize> = get_process_core_indices(&process_result);
+            self.log.write_processing_time(&indices);
+
+            // Post-process on completion of node execution. The gang members finish together.
+            for (core_id, result) in process_result.iter().enumerate() {
+                if let ProcessResult::Done(node_data) = result {
+                    if node_data.params.contains_key(GANG_MEMBER_KEY) {
+                        continue;
+                    }
+                    for ready_node in
+                        self.post_process_on_node_completion(node_data, core_id, &mut managers)
+                    {
+                        ready_queue.insert_new(NodeDataWrapper::new(ready_node));
+                        self.log.write_ready_queue_insertion();
+                    }
+                }
+            }
+        }
+
+        self.log
+            .write_comparisons(ready_queue.get_comparison_count());
+        self.calculate_log();
+        self.get_current_time()
+    }
+}
+
+impl GangEDFScheduler {
+    /// Reserve the `gang_size` cores that become idle the earliest.
+    fn reserve(&self, gang_size: usize) -> Reservation {
+        let idle_cores = self.processor.get_idle_core_num();
+        let mut remain_proc_times: Vec<i32> = self
+            .processor
+            .cores
+            .iter()
+            .filter(|core| !core.is_idle)
+            .map(|core| core.remain_proc_time)
+            .collect();
+        remain_proc_times.sort();
+        let start_delay = remain_proc_times[gang_size - idle_cores - 1];
+        let freed_cores = remain_proc_times
+            .iter()
+            .filter(|&&remain_proc_time| remain_proc_time <= start_delay)
+            .count();
+        Reservation {
+            start_delay,
+            extra_cores: idle_cores + freed_cores - gang_size,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph_extension::GraphExtension;
+    use crate::test_util::create_node;
+    use petgraph::graph::NodeIndex;
+
+    fn create_single_node_dag(
+        execution_time: i32,
+        gang_size: i32,
+        end_to_end_deadline: i32,
+    ) -> Graph<NodeData, i32> {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", execution_time));
+        dag.add_param(n0, "gang_size", gang_size);
+        dag.add_param(n0, "period", 20);
+        dag.add_param(n0, "end_to_end_deadline", end_to_end_deadline);
+        dag
+    }
+
+    #[test]
+    fn test_gang_edf_normal() {
+        let mut gang_dag = create_single_node_dag(3, 2, 10);
+        let mut dag = create_single_node_dag(2, 1, 5);
+        gang_dag.set_dag_param("dag_id", 0);
+        dag.set_dag_param("dag_id", 1);
+        let dag_set = vec![gang_dag, dag];
+
+        let mut gang_edf_scheduler = GangEDFScheduler::new(&dag_set, &HomogeneousProcessor::new(2));
+        gang_edf_scheduler.schedule(PreemptiveType::NonPreemptive);
+        let log = gang_edf_scheduler.get_log_mut();
+        // The gang waits until dag releases its core.
+        assert_eq!(log.get_worst_response_time(0), 5);
+        assert_eq!(log.get_worst_response_time(1), 2);
+        assert_eq!(log.get_gang_waiting_time(0), 2);
+        assert_eq!(log.get_gang_waiting_time(1), 0);
+    }
+
+    #[test]
+    fn test_gang_edf_reservation() {
+        let create_dag = |execution_time, gang_size, period, end_to_end_deadline, dag_id| {
+            let mut dag = Graph::<NodeData, i32>::new();
+            let n0 = dag.add_node(create_node(0, "execution_time", execution_time));
+            dag.add_param(n0, "gang_size", gang_size);
+            dag.add_param(n0, "period", period);
+            dag.add_param(n0, "end_to_end_deadline", end_to_end_deadline);
+            dag.set_dag_param("dag_id", dag_id);
+            dag
+        };
+        // Two streams of narrow nodes with late deadlines keep one of the two cores busy.
+        let mut narrow_dag = create_dag(2, 1, 2, 20, 1);
+        narrow_dag.add_param(NodeIndex::new(0), "offset", 1);
+        let mut gang_dag = create_dag(1, 2, 20, 10, 0);
+        gang_dag.add_param(NodeIndex::new(0), "offset", 1);
+        let dag_set = vec![gang_dag, narrow_dag, create_dag(2, 1, 2, 20, 2)];
+
+        let mut gang_edf_scheduler = GangEDFScheduler::new(&dag_set, &HomogeneousProcessor::new(2));
+        gang_edf_scheduler.schedule(PreemptiveType::NonPreemptive);
+        let log = gang_edf_scheduler.get_log_mut();
+        // The narrow node released at 1 is not backfilled because it would run past 2, when the
+        // reserved cores are idle.
+        assert_eq!(log.get_worst_response_time(0), 2);
+        assert_eq!(log.get_gang_waiting_time(0), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_gang_edf_gang_size_exceeds_cores() {
+        let mut gang_dag = create_single_node_dag(3, 3, 10);
+        gang_dag.set_dag_param("dag_id", 0);
+
+        let mut gang_edf_scheduler =
+            GangEDFScheduler::new(&[gang_dag], &HomogeneousProcessor::new(2));
+        gang_edf_scheduler.schedule(PreemptiveType::NonPreemptive);
+    }
+}
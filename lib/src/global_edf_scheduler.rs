@@ -1,80 +1,327 @@
-use crate::dag_set_scheduler::{DAGSetSchedulerBase, NodeDataWrapper};
+use crate::admission_control::{AdmissionControl, AdmissionTest};
+use crate::anticipatory_edf_scheduler::{
+    get_critical_path_lengths, is_anticipatory_dispatch_eligible,
+};
+use crate::dag_set_scheduler::{
+    AbsoluteDeadlineFormula, CoreUtilizationCaps, DAGSetSchedulerBase, DAGStateManagerBase,
+    IdleCoreSelection, IdleInsertionPolicy, NodeDataWrapper, TieBreaker, TieBreakingPolicy,
+};
+use crate::execution_time_trace::ExecutionTimeTrace;
+use crate::fault_injection::FaultInjection;
 use crate::getset_dag_set_scheduler;
+#[cfg(feature = "random")]
+use crate::interrupt_model::InterruptModel;
+use crate::resource_manager::LockingProtocol;
 use crate::{
-    graph_extension::NodeData, homogeneous::HomogeneousProcessor, log::DAGSetSchedulerLog,
+    graph_extension::{get_node_index_maps, GraphExtension, NodeData, NodeIndexMap},
+    homogeneous::HomogeneousProcessor,
+    log::DAGSetSchedulerLog,
     processor::ProcessorBase,
 };
 use petgraph::graph::Graph;
-use std::cmp::Ordering;
-
-impl PartialOrd for NodeDataWrapper {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        // Compare by absolute_deadline or int_scaled_absolute_deadline.
-        let mut comparison_metric = "node_absolute_deadline";
-        if self
-            .node_data
-            .params
-            .contains_key("int_scaled_node_absolute_deadline")
-            && other
-                .node_data
-                .params
-                .contains_key("int_scaled_node_absolute_deadline")
-        {
-            comparison_metric = "int_scaled_node_absolute_deadline"; // decomposition-based algorithm
-        }
-
-        match self
-            .node_data
-            .get_params_value(comparison_metric)
-            .cmp(&other.node_data.get_params_value(comparison_metric))
-        {
-            // If the keys are equal, compare by id
-            Ordering::Equal => match self.node_data.id.partial_cmp(&other.node_data.id) {
-                // If the ids are also equal, compare by dag_id
-                Some(Ordering::Equal) => Some(
-                    self.node_data
-                        .get_params_value("dag_id")
-                        .cmp(&other.node_data.get_params_value("dag_id")),
-                ),
-                other => other,
-            },
-            other => Some(other),
-        }
-    }
-}
+#[cfg(feature = "random")]
+use rand::{rngs::StdRng, SeedableRng};
+use std::collections::BTreeMap;
 
 pub struct GlobalEDFScheduler {
     dag_set: Vec<Graph<NodeData, i32>>,
+    node_index_maps: Vec<NodeIndexMap>,
     processor: HomogeneousProcessor,
     log: DAGSetSchedulerLog,
     current_time: i32,
+    absolute_deadline_formula: AbsoluteDeadlineFormula,
+    execution_time_trace: Option<ExecutionTimeTrace>,
+    fault_injection: Option<FaultInjection>,
+    idle_core_selection: IdleCoreSelection,
+    idle_insertion_policy: IdleInsertionPolicy,
+    /// Only computed for the anticipatory idle insertion.
+    critical_path_lengths: Vec<i32>,
+    /// `get_remaining_path_lengths` of each DAG, only computed for the critical path boost.
+    remaining_path_lengths: Option<Vec<Vec<i32>>>,
+    #[cfg(feature = "random")]
+    random_dispatch_rng: Option<StdRng>,
+    core_utilization_caps: Option<CoreUtilizationCaps>,
+    #[cfg(feature = "random")]
+    interrupt_model: Option<InterruptModel>,
+    priority_inheritance: bool,
+    locking_protocol: LockingProtocol,
+    admission_control: Option<AdmissionControl>,
+    tie_breaker: TieBreaker,
 }
 
 impl DAGSetSchedulerBase<HomogeneousProcessor> for GlobalEDFScheduler {
+    /// Break the ties of the deadlines by `dag_id` and node id.
     fn new(dag_set: &[Graph<NodeData, i32>], processor: &HomogeneousProcessor) -> Self {
+        Self::new_with_tie_breaker(
+            dag_set,
+            processor,
+            TieBreaker::new(TieBreakingPolicy::default(), dag_set),
+        )
+    }
+
+    getset_dag_set_scheduler!(HomogeneousProcessor);
+
+    fn get_absolute_deadline_formula(&self) -> AbsoluteDeadlineFormula {
+        self.absolute_deadline_formula
+    }
+
+    fn get_execution_time_trace(&self) -> Option<&ExecutionTimeTrace> {
+        self.execution_time_trace.as_ref()
+    }
+
+    fn get_fault_injection(&self) -> Option<&FaultInjection> {
+        self.fault_injection.as_ref()
+    }
+
+    fn get_idle_core_selection(&self) -> IdleCoreSelection {
+        self.idle_core_selection
+    }
+
+    fn is_dispatch_eligible(
+        &self,
+        node_data: &NodeData,
+        managers: &[impl DAGStateManagerBase],
+    ) -> bool {
+        match self.idle_insertion_policy {
+            IdleInsertionPolicy::WorkConserving => true,
+            IdleInsertionPolicy::Anticipatory => is_anticipatory_dispatch_eligible(
+                self,
+                &self.critical_path_lengths,
+                node_data,
+                managers,
+            ),
+        }
+    }
+
+    #[cfg(feature = "random")]
+    fn get_random_dispatch_rng_mut(&mut self) -> Option<&mut StdRng> {
+        self.random_dispatch_rng.as_mut()
+    }
+
+    /// With the critical path boost, the first node with the earliest deadline that starts the
+    /// remaining critical path of its job, i.e., the longest path among the candidates of the
+    /// job. As nodes finish, the remaining critical path moves to the branch left longest.
+    fn select_candidate<'a>(
+        &self,
+        mut candidates: impl Iterator<Item = &'a NodeDataWrapper>,
+    ) -> Option<NodeDataWrapper> {
+        let Some(remaining_path_lengths) = &self.remaining_path_lengths else {
+            return candidates.next().cloned();
+        };
+        let candidates: Vec<&NodeDataWrapper> = candidates.collect();
+        let head = *candidates.first()?;
+        let get_job = |node_data: &NodeData| {
+            (
+                node_data.get_params_value("dag_id"),
+                node_data.params.get("job_id").copied(),
+            )
+        };
+        let get_remaining_path_length = |node_data: &NodeData| {
+            let dag_id = node_data.get_params_value("dag_id") as usize;
+            let node_i = self.get_node_index(dag_id, node_data.get_id());
+            remaining_path_lengths[dag_id][node_i.index()]
+        };
+        let mut critical_path_lengths = BTreeMap::new();
+        for wrapper in candidates.iter() {
+            let critical_path_length = critical_path_lengths
+                .entry(get_job(&wrapper.node_data))
+                .or_insert(0);
+            *critical_path_length =
+                get_remaining_path_length(&wrapper.node_data).max(*critical_path_length);
+        }
+        let boosted = candidates
+            .iter()
+            .take_while(|wrapper| wrapper.has_same_deadline(head))
+            .find(|wrapper| {
+                get_remaining_path_length(&wrapper.node_data)
+                    == critical_path_lengths[&get_job(&wrapper.node_data)]
+            });
+        Some((*boosted.unwrap_or(&head)).clone())
+    }
+
+    fn get_core_utilization_caps(&self) -> Option<&CoreUtilizationCaps> {
+        self.core_utilization_caps.as_ref()
+    }
+
+    #[cfg(feature = "random")]
+    fn get_interrupt_model(&self) -> Option<&InterruptModel> {
+        self.interrupt_model.as_ref()
+    }
+
+    fn get_priority_inheritance(&self) -> bool {
+        self.priority_inheritance
+    }
+
+    fn get_locking_protocol(&self) -> LockingProtocol {
+        self.locking_protocol
+    }
+
+    fn get_admission_control(&self) -> Option<&AdmissionControl> {
+        self.admission_control.as_ref()
+    }
+
+    fn get_tie_breaker(&self) -> Option<&TieBreaker> {
+        Some(&self.tie_breaker)
+    }
+
+    fn get_tie_breaker_mut(&mut self) -> Option<&mut TieBreaker> {
+        Some(&mut self.tie_breaker)
+    }
+}
+
+impl GlobalEDFScheduler {
+    pub fn new_with_tie_breaker(
+        dag_set: &[Graph<NodeData, i32>],
+        processor: &HomogeneousProcessor,
+        tie_breaker: TieBreaker,
+    ) -> Self {
+        let mut log = DAGSetSchedulerLog::new(dag_set, processor.get_number_of_cores());
+        let tie_breaking_policy = tie_breaker.get_tie_breaking_policy();
+        if tie_breaking_policy != TieBreakingPolicy::NodeId {
+            log.write_tie_breaking_policy(tie_breaking_policy);
+        }
         Self {
+            node_index_maps: get_node_index_maps(dag_set),
             dag_set: dag_set.to_vec(),
             processor: processor.clone(),
-            log: DAGSetSchedulerLog::new(dag_set, processor.get_number_of_cores()),
+            log,
             current_time: 0,
+            absolute_deadline_formula: AbsoluteDeadlineFormula::default(),
+            execution_time_trace: None,
+            fault_injection: None,
+            idle_core_selection: IdleCoreSelection::default(),
+            idle_insertion_policy: IdleInsertionPolicy::default(),
+            critical_path_lengths: Vec::new(),
+            remaining_path_lengths: None,
+            #[cfg(feature = "random")]
+            random_dispatch_rng: None,
+            core_utilization_caps: None,
+            #[cfg(feature = "random")]
+            interrupt_model: None,
+            priority_inheritance: false,
+            locking_protocol: LockingProtocol::default(),
+            admission_control: None,
+            tie_breaker,
         }
     }
 
-    getset_dag_set_scheduler!(HomogeneousProcessor);
+    pub fn set_absolute_deadline_formula(&mut self, formula: AbsoluteDeadlineFormula) {
+        self.absolute_deadline_formula = formula;
+    }
+
+    pub fn set_execution_time_trace(&mut self, trace: ExecutionTimeTrace) {
+        self.execution_time_trace = Some(trace);
+    }
+
+    pub fn set_fault_injection(&mut self, fault_injection: FaultInjection) {
+        self.fault_injection = Some(fault_injection);
+    }
+
+    /// Admit each released job only if it passes `test`, and log the rejections.
+    pub fn set_admission_control(&mut self, test: AdmissionTest) {
+        self.admission_control = Some(AdmissionControl::new(
+            test,
+            &self.dag_set,
+            self.processor.get_number_of_cores(),
+        ));
+        self.log.write_admission_test(test);
+    }
+
+    pub fn set_idle_core_selection(&mut self, idle_core_selection: IdleCoreSelection) {
+        self.idle_core_selection = idle_core_selection;
+        if idle_core_selection != IdleCoreSelection::FirstFit {
+            self.log.write_idle_core_selection(idle_core_selection);
+        }
+    }
+
+    /// Set whether a core may idle with ready nodes. Meant for non-preemptive scheduling, where a
+    /// dispatched node blocks the later released ones.
+    pub fn set_idle_insertion_policy(&mut self, idle_insertion_policy: IdleInsertionPolicy) {
+        self.idle_insertion_policy = idle_insertion_policy;
+        if idle_insertion_policy == IdleInsertionPolicy::Anticipatory {
+            self.critical_path_lengths = get_critical_path_lengths(&self.dag_set);
+            self.log.write_idle_insertion_policy(idle_insertion_policy);
+        }
+    }
+
+    pub fn set_locking_protocol(&mut self, locking_protocol: LockingProtocol) {
+        self.locking_protocol = locking_protocol;
+    }
+
+    /// Dispatch a random ready node instead of the one with the earliest deadline.
+    #[cfg(feature = "random")]
+    pub fn set_random_dispatch(&mut self, seed: u64) {
+        self.random_dispatch_rng = Some(StdRng::seed_from_u64(seed));
+        self.log.write_random_dispatch_seed(seed);
+    }
+
+    pub fn set_core_utilization_caps(&mut self, core_utilization_caps: CoreUtilizationCaps) {
+        if core_utilization_caps.get_caps().len() != self.processor.get_number_of_cores() {
+            panic!(
+                "{} utilization caps are given for {} cores.",
+                core_utilization_caps.get_caps().len(),
+                self.processor.get_number_of_cores()
+            );
+        }
+        self.core_utilization_caps = Some(core_utilization_caps);
+    }
+
+    /// Dispatch the nodes on the remaining critical path of their job before the other nodes with
+    /// the same deadline, e.g., their siblings, so that the rest of the job is not delayed
+    /// (see `select_ready_node`).
+    pub fn set_critical_path_boost(&mut self) {
+        self.remaining_path_lengths = Some(
+            self.dag_set
+                .iter()
+                .map(|dag| dag.get_remaining_path_lengths())
+                .collect(),
+        );
+        self.log.write_critical_path_boost();
+    }
+
+    /// Release triggered DAGs with the deadline of the triggering job if it is earlier.
+    pub fn set_priority_inheritance(&mut self) {
+        self.priority_inheritance = true;
+    }
+
+    #[cfg(feature = "random")]
+    pub fn set_interrupt_model(&mut self, interrupt_model: InterruptModel) {
+        if interrupt_model.get_sources().len() != self.processor.get_number_of_cores() {
+            panic!(
+                "{} interrupt sources are given for {} cores.",
+                interrupt_model.get_sources().len(),
+                self.processor.get_number_of_cores()
+            );
+        }
+        self.interrupt_model = Some(interrupt_model);
+    }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "yaml", feature = "analysis"))]
 mod tests {
     use super::*;
     use crate::graph_extension::GraphExtension;
-    use crate::{dag_set_scheduler::PreemptiveType, util::load_yaml};
-    use std::{collections::BTreeMap, fs::remove_file};
-
-    fn create_node(id: i32, key: &str, value: i32) -> NodeData {
-        let mut params = BTreeMap::new();
-        params.insert(key.to_string(), value);
-        NodeData { id, params }
-    }
+    #[cfg(feature = "random")]
+    use crate::interrupt_model::InterruptSource;
+    #[cfg(all(feature = "json", feature = "msgpack"))]
+    use crate::log::SimulationLog;
+    #[cfg(feature = "json")]
+    use crate::output_log::get_dag_set_hash;
+    use crate::power_down::PowerDownModel;
+    use crate::test_util::create_node;
+    use crate::{
+        dag_set_scheduler::{PreemptiveType, ReadyQueue},
+        fixture::{assert_matches_fixture, create_test_output_dir, get_fixture_path},
+        log::{
+            BlockingEvent, DAGReliability, DegradationDecision, InheritanceEvent, JobEventTimes,
+            LogFilter, LogFormat, ModeChangeEvent, ReadinessGate, ReleaseEvent, TraceEntry,
+        },
+        mixed_criticality::Criticality,
+        schedulability_screening::screen_dag_set,
+        util::load_yaml,
+    };
+    use petgraph::graph::NodeIndex;
+    use std::fs::{self, remove_file};
 
     fn create_sample_dag() -> Graph<NodeData, i32> {
         let mut dag = Graph::<NodeData, i32>::new();
@@ -163,72 +410,39 @@ mod tests {
 
         assert_eq!(time, 300);
 
-        let file_path = global_edf_scheduler.dump_log("../lib/tests", "edf_normal_test");
-        let yaml_docs = load_yaml(&file_path);
-        let yaml_doc = &yaml_docs[0];
+        let dir_path = create_test_output_dir("global_edf_normal");
+        let file_path = global_edf_scheduler.dump_log(&dir_path, "global_edf_normal");
 
-        // Check the value of total_utilization
-        assert_eq!(
-            yaml_doc["dag_set_info"]["total_utilization"]
-                .as_f64()
-                .unwrap(),
-            3.8095236
+        assert_matches_fixture(
+            &fs::read_to_string(&file_path).unwrap(),
+            &get_fixture_path("global_edf_normal", &dag_set),
         );
-
-        // Check the value of each_dag_info
-        let each_dag_info = &yaml_doc["dag_set_info"]["each_dag_info"][0];
-        assert_eq!(each_dag_info["critical_path_length"].as_i64().unwrap(), 50);
-        assert_eq!(each_dag_info["period"].as_i64().unwrap(), 150);
-        assert_eq!(each_dag_info["end_to_end_deadline"].as_i64().unwrap(), 50);
-        assert_eq!(each_dag_info["volume"].as_i64().unwrap(), 70);
-        assert_eq!(each_dag_info["utilization"].as_f64().unwrap(), 2.142857);
-
-        // Check the value of processor_info
-        assert_eq!(
-            yaml_doc["processor_info"]["number_of_cores"]
-                .as_i64()
-                .unwrap(),
-            4
-        );
-
-        // Check the value of dag_set_log
+        // Key values of the fixture, checked by hand.
+        let yaml_doc = &load_yaml(&file_path)[0];
         let dag_set_log = &yaml_doc["dag_set_log"][0];
-        assert_eq!(dag_set_log["dag_id"].as_i64().unwrap(), 0);
-        let release_time = &dag_set_log["release_time"];
-        assert_eq!(release_time[0].as_i64().unwrap(), 0);
-        assert_eq!(release_time[1].as_i64().unwrap(), 150);
-        let finish_time = &dag_set_log["finish_time"];
-        assert_eq!(finish_time[0].as_i64().unwrap(), 50);
-        assert_eq!(finish_time[1].as_i64().unwrap(), 200);
-
-        // Check the value of node_set_logs
+        assert_eq!(dag_set_log["release_time"][0].as_i64().unwrap(), 0);
+        assert_eq!(dag_set_log["release_time"][1].as_i64().unwrap(), 150);
+        assert_eq!(dag_set_log["finish_time"][0].as_i64().unwrap(), 50);
+        assert_eq!(dag_set_log["finish_time"][1].as_i64().unwrap(), 200);
         let node_set_logs = &yaml_doc["node_set_logs"][0];
         assert_eq!(node_set_logs[0]["core_id"].as_i64().unwrap(), 0);
-        assert_eq!(node_set_logs[0]["dag_id"].as_i64().unwrap(), 0);
         assert_eq!(node_set_logs[0]["node_id"].as_i64().unwrap(), 0);
         // start_time
         assert_eq!(node_set_logs[0]["event_time"].as_str().unwrap(), "0");
         // finish_time
         assert_eq!(node_set_logs[1]["event_time"].as_str().unwrap(), "10");
-
-        // Check the value of processor_log
         let processor_log = &yaml_doc["processor_log"];
         assert_eq!(
             processor_log["average_utilization"].as_f64().unwrap(),
-            0.2666667
+            0.26666668
         );
         assert_eq!(
-            processor_log["variance_utilization"].as_f64().unwrap(),
-            0.060000006
+            processor_log["core_logs"][0]["total_proc_time"]
+                .as_i64()
+                .unwrap(),
+            200
         );
-
-        // Check the value of core_logs
-        let core_logs = &processor_log["core_logs"][0];
-        assert_eq!(core_logs["core_id"].as_i64().unwrap(), 0);
-        assert_eq!(core_logs["total_proc_time"].as_i64().unwrap(), 200);
-        assert_eq!(core_logs["utilization"].as_f64().unwrap(), 0.6666667);
-
-        remove_file(file_path).unwrap();
+        fs::remove_dir_all(dir_path).unwrap();
     }
 
     #[test]
@@ -248,72 +462,1498 @@ mod tests {
 
         assert_eq!(time, 150);
 
-        let file_path = global_edf_scheduler.dump_log("../lib/tests", "edf_preemptive_test");
+        let dir_path = create_test_output_dir("global_edf_preemptive");
+        let file_path = global_edf_scheduler.dump_log(&dir_path, "global_edf_preemptive");
+
+        assert_matches_fixture(
+            &fs::read_to_string(&file_path).unwrap(),
+            &get_fixture_path("global_edf_preemptive", &dag_set),
+        );
+        // Key values of the fixture, checked by hand.
+        let yaml_doc = &load_yaml(&file_path)[0];
+        let dag_set_log = &yaml_doc["dag_set_log"][0];
+        assert_eq!(dag_set_log["release_time"][0].as_i64().unwrap(), 0);
+        assert_eq!(dag_set_log["finish_time"][0].as_i64().unwrap(), 80);
+        assert_eq!(dag_set_log["response_time"][0].as_i64().unwrap(), 80);
+        let node_set_logs = &yaml_doc["node_set_logs"][0];
+        assert_eq!(node_set_logs[0]["core_id"].as_i64().unwrap(), 1);
+        assert_eq!(node_set_logs[0]["node_id"].as_i64().unwrap(), 0);
+        // start_time, preempt_time, resume_time and finish_time
+        for (job_log_i, event_time) in ["0", "5", "10", "15"].into_iter().enumerate() {
+            assert_eq!(
+                node_set_logs[job_log_i]["event_time"].as_str().unwrap(),
+                event_time
+            );
+        }
+        let processor_log = &yaml_doc["processor_log"];
+        assert_eq!(
+            processor_log["average_utilization"].as_f64().unwrap(),
+            0.73333335
+        );
+        assert_eq!(
+            processor_log["core_logs"][0]["total_proc_time"]
+                .as_i64()
+                .unwrap(),
+            130
+        );
+        fs::remove_dir_all(dir_path).unwrap();
+    }
+
+    #[test]
+    fn test_global_edf_core_share() {
+        let mut dag = create_sample_dag();
+        let mut dag2 = create_sample_dag2();
+        dag.set_dag_param("dag_id", 0);
+        dag2.set_dag_param("dag_id", 1);
+        let dag_set = vec![dag, dag2];
+
+        let processor = HomogeneousProcessor::new(4);
+
+        let mut global_edf_scheduler = GlobalEDFScheduler::new(&dag_set, &processor);
+        global_edf_scheduler.schedule(PreemptiveType::NonPreemptive);
+
+        let file_path = global_edf_scheduler.dump_log("../lib/tests", "edf_core_share_test");
         let yaml_docs = load_yaml(&file_path);
-        let yaml_doc = &yaml_docs[0];
+        let core_share_log = &yaml_docs[0]["core_share_log"];
 
-        // Check the value of total_utilization
+        // Every job finished, so each DAG received exactly its entitlement.
+        assert!((core_share_log["fairness_index"].as_f64().unwrap() - 1.0).abs() < 1e-6);
+        let each_dag_share = &core_share_log["each_dag_share"];
+        assert_eq!(each_dag_share[0]["dag_id"].as_i64().unwrap(), 0);
+        assert_eq!(each_dag_share[0]["total_proc_time"].as_i64().unwrap(), 140);
         assert_eq!(
-            yaml_doc["dag_set_info"]["total_utilization"]
-                .as_f64()
+            each_dag_share[0]["core_time_share"].as_f64().unwrap(),
+            0.4375
+        );
+        assert_eq!(each_dag_share[1]["total_proc_time"].as_i64().unwrap(), 180);
+        assert_eq!(
+            each_dag_share[1]["core_time_share"].as_f64().unwrap(),
+            0.5625
+        );
+        assert_eq!(each_dag_share[1]["entitlement"].as_f64().unwrap(), 0.5625);
+        assert_eq!(each_dag_share[1]["share_ratio"].as_f64().unwrap(), 1.0);
+
+        remove_file(file_path).unwrap();
+    }
+
+    #[test]
+    fn test_global_edf_decision_log() {
+        let mut dag = create_sample_dag();
+        let mut dag2 = create_sample_dag2();
+        dag.set_dag_param("dag_id", 0);
+        dag2.set_dag_param("dag_id", 1);
+        let dag_set = vec![dag, dag2];
+
+        let processor = HomogeneousProcessor::new(4);
+
+        let mut global_edf_scheduler = GlobalEDFScheduler::new(&dag_set, &processor);
+        global_edf_scheduler.schedule(PreemptiveType::NonPreemptive);
+
+        let file_path = global_edf_scheduler.dump_log("../lib/tests", "edf_decision_log_test");
+        let yaml_docs = load_yaml(&file_path);
+        let decision_log = &yaml_docs[0]["decision_log"];
+
+        // Each of the 22 released node jobs enters and leaves the ready queue exactly once.
+        assert_eq!(decision_log["ready_queue_insertions"].as_i64().unwrap(), 22);
+        assert_eq!(decision_log["ready_queue_removals"].as_i64().unwrap(), 22);
+        assert_eq!(decision_log["comparisons"].as_i64().unwrap(), 15);
+        assert_eq!(decision_log["total_operations"].as_i64().unwrap(), 59);
+        assert_eq!(
+            decision_log["operations_per_time_unit"].as_f64().unwrap(),
+            0.19666667
+        );
+        remove_file(file_path).unwrap();
+
+        // The comparisons are counted per ready queue, so a second run on the same thread
+        // does not inherit the count of the first.
+        let mut global_edf_scheduler = GlobalEDFScheduler::new(&dag_set, &processor);
+        global_edf_scheduler.schedule(PreemptiveType::NonPreemptive);
+        let file_path = global_edf_scheduler.dump_log("../lib/tests", "edf_decision_log_test");
+        let yaml_docs = load_yaml(&file_path);
+        assert_eq!(
+            yaml_docs[0]["decision_log"]["comparisons"]
+                .as_i64()
                 .unwrap(),
-            3.142857
+            15
         );
 
-        // Check the value of each_dag_info
-        let each_dag_info = &yaml_doc["dag_set_info"]["each_dag_info"][0];
-        assert_eq!(each_dag_info["critical_path_length"].as_i64().unwrap(), 50);
-        assert_eq!(each_dag_info["period"].as_i64().unwrap(), 150);
-        assert_eq!(each_dag_info["end_to_end_deadline"].as_i64().unwrap(), 50);
-        assert_eq!(each_dag_info["volume"].as_i64().unwrap(), 70);
-        assert_eq!(each_dag_info["utilization"].as_f64().unwrap(), 2.142857);
+        remove_file(file_path).unwrap();
+    }
+
+    #[test]
+    fn test_global_edf_lock_group() {
+        let mut dag = create_sample_dag();
+        let mut dag3 = create_sample_dag3();
+        // c1 and c2 must run back-to-back on the same core.
+        dag.add_param(NodeIndex::new(1), "lock_group", 0);
+        dag.add_param(NodeIndex::new(2), "lock_group", 0);
+        dag.set_dag_param("dag_id", 0);
+        dag3.set_dag_param("dag_id", 1);
+        let dag_set = vec![dag, dag3];
+
+        let processor = HomogeneousProcessor::new(2);
+
+        let mut global_edf_scheduler = GlobalEDFScheduler::new(&dag_set, &processor);
+        global_edf_scheduler.schedule(PreemptiveType::Preemptive {
+            key: "node_absolute_deadline".to_string(),
+        });
+
+        let log = global_edf_scheduler.get_log_mut();
+        assert!(log.verify_lock_groups(&dag_set).is_empty());
+    }
+
+    #[test]
+    fn test_verify_lock_groups_interleaved() {
+        let mut dag = create_sample_dag();
+        dag.add_param(NodeIndex::new(1), "lock_group", 0);
+        dag.add_param(NodeIndex::new(2), "lock_group", 0);
+        dag.set_dag_param("dag_id", 0);
+        let dag_set = vec![dag];
+
+        let mut log = DAGSetSchedulerLog::new(&dag_set, 1);
+        let events = [
+            (1, JobEventTimes::StartTime(10)),
+            (1, JobEventTimes::FinishTime(30)),
+            (3, JobEventTimes::StartTime(30)),
+            (3, JobEventTimes::FinishTime(40)),
+            (2, JobEventTimes::StartTime(40)),
+            (2, JobEventTimes::FinishTime(60)),
+        ];
+        for (node_i, event_time) in events {
+            log.write_job_event(&dag_set[0][NodeIndex::new(node_i)], 0, 0, event_time);
+        }
+
+        let violations = log.verify_lock_groups(&dag_set);
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].contains("interleaved with node 3"));
+    }
+
+    #[test]
+    fn test_global_edf_dump_simulation_log() {
+        let mut dag = create_sample_dag();
+        let mut dag2 = create_sample_dag2();
+        dag.set_dag_param("dag_id", 0);
+        dag2.set_dag_param("dag_id", 1);
+        let dag_set = vec![dag, dag2];
+
+        let processor = HomogeneousProcessor::new(4);
+
+        let mut global_edf_scheduler = GlobalEDFScheduler::new(&dag_set, &processor);
+        global_edf_scheduler.schedule(PreemptiveType::NonPreemptive);
 
-        // Check the value of processor_info
+        // The log and the result are written as one document.
+        let file_path = global_edf_scheduler.dump_simulation_log(
+            "../lib/tests",
+            "edf_simulation_log_test",
+            true,
+            LogFormat::Yaml,
+        );
+        let yaml_docs = load_yaml(&file_path);
+        assert_eq!(yaml_docs.len(), 1);
         assert_eq!(
-            yaml_doc["processor_info"]["number_of_cores"]
+            yaml_docs[0]["dag_set_log"][1]["worst_response_time"]
                 .as_i64()
                 .unwrap(),
-            2
+            50
         );
+        assert!(yaml_docs[0]["result"].as_bool().unwrap());
+        remove_file(file_path).unwrap();
 
-        // Check the value of dag_set_log
-        let dag_set_log = &yaml_doc["dag_set_log"][0];
-        assert_eq!(dag_set_log["dag_id"].as_i64().unwrap(), 0);
-        assert_eq!(dag_set_log["release_time"][0].as_i64().unwrap(), 0);
-        assert_eq!(dag_set_log["finish_time"][0].as_i64().unwrap(), 80);
-        assert_eq!(dag_set_log["response_time"][0].as_i64().unwrap(), 80);
+        #[cfg(all(feature = "json", feature = "msgpack"))]
+        for format in [LogFormat::Json, LogFormat::MessagePack] {
+            let file_path = global_edf_scheduler.dump_simulation_log(
+                "../lib/tests",
+                "edf_simulation_log_test",
+                false,
+                format,
+            );
+            assert!(file_path.ends_with(format.get_extension()));
+            let simulation_log =
+                SimulationLog::<DAGSetSchedulerLog, bool>::load(&file_path, format);
+            assert!(!simulation_log.result);
+            assert_eq!(simulation_log.log.get_worst_response_time(0), 50);
+            assert_eq!(simulation_log.log.get_worst_response_time(1), 50);
+            remove_file(file_path).unwrap();
+        }
+    }
 
-        // Check the value of node_set_logs
-        let node_set_logs = &yaml_doc["node_set_logs"][0];
-        assert_eq!(node_set_logs[0]["core_id"].as_i64().unwrap(), 1);
-        assert_eq!(node_set_logs[0]["dag_id"].as_i64().unwrap(), 0);
-        assert_eq!(node_set_logs[0]["node_id"].as_i64().unwrap(), 0);
-        // start_time
-        assert_eq!(node_set_logs[0]["event_time"].as_str().unwrap(), "0");
-        // preempt_time
-        assert_eq!(node_set_logs[1]["event_time"].as_str().unwrap(), "5");
-        // resume_time
-        assert_eq!(node_set_logs[2]["event_time"].as_str().unwrap(), "10");
-        // finish_time
-        assert_eq!(node_set_logs[3]["event_time"].as_str().unwrap(), "15");
+    #[test]
+    #[cfg(feature = "json")]
+    fn test_global_edf_dump_run_dir() {
+        let mut dag = create_sample_dag();
+        dag.set_dag_param("dag_id", 0);
+        let dag_set = vec![dag];
+        let processor = HomogeneousProcessor::new(4);
+
+        let mut global_edf_scheduler = GlobalEDFScheduler::new(&dag_set, &processor);
+        global_edf_scheduler.schedule(PreemptiveType::NonPreemptive);
+        let run_dir_path = global_edf_scheduler.dump_run_dir(
+            "../lib/tests/edf_run_dir_test",
+            "edf",
+            &dag_set,
+            true,
+            LogFormat::Json,
+        );
+        // The directory is named after the input DAG set, not the DAGs changed by scheduling.
+        assert!(run_dir_path.ends_with(&format!("-edf-{}", get_dag_set_hash(&dag_set))));
+
+        let manifest = &load_yaml(&format!("{}/manifest.yaml", run_dir_path))[0];
+        assert_eq!(manifest["alg_name"].as_str().unwrap(), "edf");
+        assert_eq!(manifest["number_of_cores"].as_i64().unwrap(), 4);
+        assert_eq!(manifest["files"][0].as_str().unwrap(), "log.json");
+        assert_eq!(manifest["files"][1].as_str().unwrap(), "summary.yaml");
+        let summary = &load_yaml(&format!("{}/summary.yaml", run_dir_path))[0];
+        assert!(summary["result"].as_bool().unwrap());
+        assert_eq!(summary["worst_response_times"][0].as_i64().unwrap(), 50);
+        // The DAG finishes just at its deadline.
+        assert_eq!(summary["slack"][0]["min_slack"].as_i64().unwrap(), 0);
+        assert_eq!(summary["slack"][0]["average_slack"].as_f64().unwrap(), 0.0);
+        let resource_usage = &summary["resource_usage"];
+        assert!(resource_usage["wall_time_ms"].as_i64().is_some());
+        // Start and finish of each of the 5 nodes
+        assert_eq!(resource_usage["processed_events"].as_i64().unwrap(), 10);
+        std::fs::remove_dir_all("../lib/tests/edf_run_dir_test").unwrap();
+    }
+
+    #[test]
+    fn test_global_edf_dump_screened_out_run_dir() {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 10));
+        dag.add_param(n0, "period", 5);
+        dag.set_dag_param("dag_id", 0);
+        let dag_set = vec![dag];
+        let screening_violations = screen_dag_set(&dag_set, 1);
+        assert_eq!(screening_violations.len(), 3);
+
+        let global_edf_scheduler = GlobalEDFScheduler::new(&dag_set, &HomogeneousProcessor::new(1));
+        let run_dir_path = global_edf_scheduler.dump_screened_out_run_dir(
+            "../lib/tests/edf_screened_out_test",
+            "edf",
+            &dag_set,
+            screening_violations,
+        );
+
+        let manifest = &load_yaml(&format!("{}/manifest.yaml", run_dir_path))[0];
+        assert_eq!(manifest["files"].as_vec().unwrap().len(), 1);
+        let summary = &load_yaml(&format!("{}/summary.yaml", run_dir_path))[0];
+        assert_eq!(summary["status"].as_str().unwrap(), "screened-out");
+        assert!(!summary["result"].as_bool().unwrap());
+        assert_eq!(summary["screening_violations"].as_vec().unwrap().len(), 3);
+        std::fs::remove_dir_all("../lib/tests/edf_screened_out_test").unwrap();
+    }
+
+    #[test]
+    fn test_global_edf_dump_filtered_simulation_log() {
+        let mut dag = create_sample_dag();
+        let mut dag2 = create_sample_dag2();
+        dag.set_dag_param("dag_id", 0);
+        dag2.set_dag_param("dag_id", 1);
+        let dag_set = vec![dag, dag2];
+
+        let mut global_edf_scheduler =
+            GlobalEDFScheduler::new(&dag_set, &HomogeneousProcessor::new(4));
+        global_edf_scheduler.schedule(PreemptiveType::NonPreemptive);
+        let filter = LogFilter {
+            dag_ids: Some(0..=0),
+            time_window: Some(100..=300),
+        };
+        let file_path = global_edf_scheduler.dump_filtered_simulation_log(
+            "../lib/tests",
+            "edf_filtered_test",
+            true,
+            LogFormat::Yaml,
+            &filter,
+        );
+        let yaml_doc = &load_yaml(&file_path)[0];
+        remove_file(&file_path).unwrap();
 
-        // Check the value of processor_log
-        let processor_log = &yaml_doc["processor_log"];
         assert_eq!(
-            processor_log["average_utilization"].as_f64().unwrap(),
-            0.73333335
+            yaml_doc["dag_set_info"]["each_dag_info"]
+                .as_vec()
+                .unwrap()
+                .len(),
+            1
         );
+        let dag_set_log = yaml_doc["dag_set_log"].as_vec().unwrap();
+        assert_eq!(dag_set_log.len(), 1);
+        assert_eq!(dag_set_log[0]["dag_id"].as_i64().unwrap(), 0);
+        // Only the second job is released in the window.
+        assert_eq!(dag_set_log[0]["release_time"][0].as_i64().unwrap(), 150);
+        assert_eq!(dag_set_log[0]["release_time"].as_vec().unwrap().len(), 1);
         assert_eq!(
-            processor_log["variance_utilization"].as_f64().unwrap(),
-            0.017777776
+            dag_set_log[0]["worst_response_time"],
+            dag_set_log[0]["response_time"][0]
         );
+        for job_logs in yaml_doc["node_set_logs"].as_vec().unwrap() {
+            for job_log in job_logs.as_vec().unwrap() {
+                assert_eq!(job_log["dag_id"].as_i64().unwrap(), 0);
+                let event_time: i32 = job_log["event_time"].as_str().unwrap().parse().unwrap();
+                assert!((100..=300).contains(&event_time));
+            }
+        }
+    }
+
+    #[test]
+    fn test_global_edf_truncated_log() {
+        let mut dag = create_sample_dag();
+        dag.set_dag_param("dag_id", 0);
+        let dag_set = vec![dag];
+        let processor = HomogeneousProcessor::new(4);
 
-        // Check the value of core_logs
-        let core_logs = &processor_log["core_logs"][0];
-        assert_eq!(core_logs["core_id"].as_i64().unwrap(), 0);
-        assert_eq!(core_logs["total_proc_time"].as_i64().unwrap(), 130);
-        assert_eq!(core_logs["utilization"].as_f64().unwrap(), 0.8666667);
+        let mut global_edf_scheduler = GlobalEDFScheduler::new(&dag_set, &processor);
+        global_edf_scheduler.schedule(PreemptiveType::NonPreemptive);
 
+        // A completed simulation has no marker.
+        let file_path = global_edf_scheduler.dump_log("../lib/tests", "edf_complete_test");
+        let yaml_docs = load_yaml(&file_path);
+        assert!(yaml_docs[0]["truncated"].is_badvalue());
         remove_file(file_path).unwrap();
+
+        global_edf_scheduler.get_log_mut().write_truncated();
+        let file_path = global_edf_scheduler.dump_log("../lib/tests", "edf_truncated_test");
+        let yaml_docs = load_yaml(&file_path);
+        assert!(yaml_docs[0]["truncated"].as_bool().unwrap());
+        remove_file(file_path).unwrap();
+    }
+
+    #[test]
+    fn test_global_edf_core_traces() {
+        let mut dag = create_sample_dag();
+        dag.set_dag_param("dag_id", 0);
+        let dag_set = vec![dag];
+        let processor = HomogeneousProcessor::new(2);
+
+        let mut global_edf_scheduler = GlobalEDFScheduler::new(&dag_set, &processor);
+        let schedule_length = global_edf_scheduler.schedule(PreemptiveType::NonPreemptive);
+        let log = global_edf_scheduler.get_log_mut();
+        assert!(log.get_core_traces().is_none());
+        log.write_core_traces(schedule_length);
+
+        let run = |node_id, duration| TraceEntry {
+            dag_id: Some(0),
+            node_id: Some(node_id),
+            duration,
+            idle: false,
+        };
+        let idle = |duration| TraceEntry {
+            dag_id: None,
+            node_id: None,
+            duration,
+            idle: true,
+        };
+        let core_traces = log.get_core_traces().unwrap();
+        assert_eq!(
+            core_traces[0].entries,
+            vec![run(0, 10), run(1, 20), run(2, 20), idle(100)]
+        );
+        assert_eq!(
+            core_traces[1].entries,
+            vec![idle(10), run(3, 10), run(4, 10), idle(120)]
+        );
+    }
+
+    #[test]
+    fn test_global_edf_readiness_gates() {
+        let mut dag = create_sample_dag();
+        dag.set_dag_param("dag_id", 0);
+        let dag_set = vec![dag];
+
+        let mut global_edf_scheduler =
+            GlobalEDFScheduler::new(&dag_set, &HomogeneousProcessor::new(2));
+        global_edf_scheduler.schedule(PreemptiveType::NonPreemptive);
+        let log = global_edf_scheduler.get_log_mut();
+        assert!(log.get_readiness_gates().is_none());
+        log.write_readiness_gates(&dag_set);
+
+        let gate = |node_id, gating_node_id, ready_time, wait_time| ReadinessGate {
+            dag_id: 0,
+            node_id,
+            job_id: 0,
+            gating_node_id,
+            ready_time,
+            wait_time,
+        };
+        // c1 and n1_0 finish at 30 and gate c2, which waited since n0_0 finished at 20.
+        assert_eq!(
+            log.get_readiness_gates().unwrap(),
+            &vec![
+                gate(1, 0, 10, 0),
+                gate(2, 1, 30, 10),
+                gate(3, 0, 10, 0),
+                gate(4, 0, 10, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_global_edf_preemption_threshold() {
+        let get_response_times = |preemption_threshold: i32| {
+            let mut long_dag = Graph::<NodeData, i32>::new();
+            let n0 = long_dag.add_node(create_node(0, "execution_time", 10));
+            long_dag.add_param(n0, "period", 100);
+            long_dag.add_param(n0, "end_to_end_deadline", 100);
+            long_dag.add_param(n0, "preemption_threshold", preemption_threshold);
+            let mut urgent_dag = Graph::<NodeData, i32>::new();
+            let n0 = urgent_dag.add_node(create_node(0, "execution_time", 2));
+            urgent_dag.add_param(n0, "period", 100);
+            urgent_dag.add_param(n0, "end_to_end_deadline", 5);
+            urgent_dag.add_param(n0, "offset", 1);
+            long_dag.set_dag_param("dag_id", 0);
+            urgent_dag.set_dag_param("dag_id", 1);
+            let dag_set = vec![long_dag, urgent_dag];
+
+            let mut global_edf_scheduler =
+                GlobalEDFScheduler::new(&dag_set, &HomogeneousProcessor::new(1));
+            global_edf_scheduler.schedule(PreemptiveType::Preemptive {
+                key: "node_absolute_deadline".to_string(),
+            });
+            let log = global_edf_scheduler.get_log_mut();
+            (
+                log.get_worst_response_time(0),
+                log.get_worst_response_time(1),
+            )
+        };
+
+        // The absolute deadline 5 of the urgent node is below 100 - 94.
+        assert_eq!(get_response_times(94), (12, 2));
+        // The threshold protects the long node, which behaves as non-preemptive.
+        assert_eq!(get_response_times(95), (10, 11));
+    }
+
+    #[test]
+    fn test_global_edf_priority_inheritance_protocol() {
+        let create_single_node_dag =
+            |dag_id: i32, execution_time: i32, end_to_end_deadline: i32, offset: i32| {
+                let mut dag = Graph::<NodeData, i32>::new();
+                let n0 = dag.add_node(create_node(0, "execution_time", execution_time));
+                dag.add_param(n0, "period", 20);
+                dag.add_param(n0, "end_to_end_deadline", end_to_end_deadline);
+                dag.add_param(n0, "offset", offset);
+                dag.set_dag_param("dag_id", dag_id);
+                dag
+            };
+        let mut low_dag = create_single_node_dag(0, 4, 20, 0);
+        let middle_dag = create_single_node_dag(1, 5, 12, 1);
+        let mut high_dag = create_single_node_dag(2, 2, 5, 2);
+        let n0 = NodeIndex::new(0);
+        low_dag.add_param(n0, "resource_id", 0);
+        low_dag.add_param(n0, "cs_length", 3);
+        high_dag.add_param(n0, "resource_id", 0);
+        let dag_set = vec![low_dag, middle_dag, high_dag];
+
+        let mut global_edf_scheduler =
+            GlobalEDFScheduler::new(&dag_set, &HomogeneousProcessor::new(1));
+        global_edf_scheduler.schedule(PreemptiveType::Preemptive {
+            key: "node_absolute_deadline".to_string(),
+        });
+
+        // The high node blocks at 2 on the resource locked by the low node, which inherits the
+        // deadline 7, preempts the middle node and releases the resource at 4.
+        let log = global_edf_scheduler.get_log_mut();
+        assert_eq!(log.get_worst_response_time(0), 11);
+        assert_eq!(log.get_worst_response_time(1), 9);
+        assert_eq!(log.get_worst_response_time(2), 4);
+        assert_eq!(
+            log.get_blocking_log(),
+            &[BlockingEvent {
+                dag_id: 2,
+                node_id: 0,
+                job_id: 0,
+                resource_id: 0,
+                holder_dag_id: 0,
+                holder_node_id: 0,
+                time: 2,
+                blocking_time: 2,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_global_edf_mpcp() {
+        let get_response_times = |locking_protocol: LockingProtocol| {
+            let mut low_dag = Graph::<NodeData, i32>::new();
+            let n0 = low_dag.add_node(create_node(0, "execution_time", 4));
+            low_dag.add_param(n0, "period", 20);
+            low_dag.add_param(n0, "end_to_end_deadline", 20);
+            low_dag.add_param(n0, "resource_id", 0);
+            low_dag.add_param(n0, "cs_length", 3);
+            let mut urgent_dag = Graph::<NodeData, i32>::new();
+            let n0 = urgent_dag.add_node(create_node(0, "execution_time", 2));
+            urgent_dag.add_param(n0, "period", 20);
+            urgent_dag.add_param(n0, "end_to_end_deadline", 5);
+            urgent_dag.add_param(n0, "offset", 1);
+            low_dag.set_dag_param("dag_id", 0);
+            urgent_dag.set_dag_param("dag_id", 1);
+
+            let mut global_edf_scheduler =
+                GlobalEDFScheduler::new(&[low_dag, urgent_dag], &HomogeneousProcessor::new(1));
+            global_edf_scheduler.set_locking_protocol(locking_protocol);
+            global_edf_scheduler.schedule(PreemptiveType::Preemptive {
+                key: "node_absolute_deadline".to_string(),
+            });
+            let log = global_edf_scheduler.get_log_mut();
+            (
+                log.get_worst_response_time(0),
+                log.get_worst_response_time(1),
+            )
+        };
+
+        // Without blocked nodes, PIP lets the urgent node preempt the critical section at 1.
+        assert_eq!(
+            get_response_times(LockingProtocol::PriorityInheritance),
+            (6, 2)
+        );
+        // MPCP runs the critical section above all normal priorities until 3.
+        assert_eq!(get_response_times(LockingProtocol::Mpcp), (6, 4));
+    }
+
+    #[test]
+    fn test_global_edf_mixed_criticality() {
+        let mut hi_dag = Graph::<NodeData, i32>::new();
+        let n0 = hi_dag.add_node(create_node(0, "execution_time", 8));
+        hi_dag.add_param(n0, "period", 20);
+        hi_dag.add_param(n0, "end_to_end_deadline", 20);
+        hi_dag.add_param(n0, "criticality", 1);
+        hi_dag.add_param(n0, "lo_execution_time", 2);
+        hi_dag.add_param(n0, "hi_execution_time", 8);
+        let mut lo_dag = Graph::<NodeData, i32>::new();
+        let n0 = lo_dag.add_node(create_node(0, "execution_time", 2));
+        let n1 = lo_dag.add_node(create_node(1, "execution_time", 2));
+        lo_dag.add_edge(n0, n1, 1);
+        lo_dag.add_param(n0, "period", 5);
+        lo_dag.add_param(n1, "end_to_end_deadline", 5);
+        lo_dag.add_param(n0, "criticality", 0);
+        lo_dag.add_param(n1, "criticality", 0);
+        hi_dag.set_dag_param("dag_id", 0);
+        lo_dag.set_dag_param("dag_id", 1);
+        let dag_set = vec![hi_dag, lo_dag];
+
+        let mut global_edf_scheduler =
+            GlobalEDFScheduler::new(&dag_set, &HomogeneousProcessor::new(2));
+        global_edf_scheduler.schedule(PreemptiveType::NonPreemptive);
+
+        // The HI node overruns its LO WCET at 2, dropping the LO job after its first node and the
+        // LO job released at 5, until the system is idle at 8.
+        let log = global_edf_scheduler.get_log_mut();
+        assert_eq!(
+            log.get_mode_change_log(),
+            &[
+                ModeChangeEvent {
+                    time: 2,
+                    mode: Criticality::Hi,
+                    dag_id: Some(0),
+                    node_id: Some(0),
+                    dropped_dag_ids: vec![1],
+                },
+                ModeChangeEvent {
+                    time: 8,
+                    mode: Criticality::Lo,
+                    dag_id: None,
+                    node_id: None,
+                    dropped_dag_ids: Vec::new(),
+                },
+            ]
+        );
+        assert_eq!(
+            log.get_release_events(1),
+            &[
+                ReleaseEvent::Dropped { release_time: 0 },
+                ReleaseEvent::Dropped { release_time: 5 },
+            ]
+        );
+        assert_eq!(log.get_release_times(1), &[10, 15]);
+        assert_eq!(log.get_worst_response_time(0), 8);
+        assert_eq!(log.get_worst_response_time(1), 4);
+    }
+
+    #[test]
+    fn test_global_edf_limited_preemption() {
+        let mut long_dag = Graph::<NodeData, i32>::new();
+        let n0 = long_dag.add_node(create_node(0, "execution_time", 10));
+        long_dag.add_param(n0, "period", 100);
+        long_dag.add_param(n0, "end_to_end_deadline", 100);
+        let mut urgent_dag = Graph::<NodeData, i32>::new();
+        let n0 = urgent_dag.add_node(create_node(0, "execution_time", 2));
+        urgent_dag.add_param(n0, "period", 100);
+        urgent_dag.add_param(n0, "end_to_end_deadline", 5);
+        urgent_dag.add_param(n0, "offset", 1);
+        long_dag.set_dag_param("dag_id", 0);
+        urgent_dag.set_dag_param("dag_id", 1);
+        let dag_set = vec![long_dag, urgent_dag];
+        let get_response_times = |non_preemptive_region: i32| {
+            let mut global_edf_scheduler =
+                GlobalEDFScheduler::new(&dag_set, &HomogeneousProcessor::new(1));
+            global_edf_scheduler.schedule(PreemptiveType::LimitedPreemptive {
+                key: "node_absolute_deadline".to_string(),
+                non_preemptive_region,
+            });
+            let log = global_edf_scheduler.get_log_mut();
+            (
+                log.get_worst_response_time(0),
+                log.get_worst_response_time(1),
+            )
+        };
+
+        // Without a region, the urgent node preempts the long node on its release at 1.
+        assert_eq!(get_response_times(0), (12, 2));
+        // The long node is preempted at 3 after running for the region.
+        assert_eq!(get_response_times(3), (12, 4));
+        // A region longer than the node only allows preemption at its boundary.
+        assert_eq!(get_response_times(10), (10, 11));
+    }
+
+    #[test]
+    fn test_global_edf_constrained_deadline() {
+        let create_single_node_dag = |execution_time, period, end_to_end_deadline| {
+            let mut dag = Graph::<NodeData, i32>::new();
+            let n0 = dag.add_node(create_node(0, "execution_time", execution_time));
+            dag.add_param(n0, "period", period);
+            dag.add_param(n0, "end_to_end_deadline", end_to_end_deadline);
+            dag
+        };
+        // The second job of the constrained DAG is released at 10 with the deadline 10 + 2.
+        let mut constrained_dag = create_single_node_dag(2, 10, 2);
+        let mut loose_dag = create_single_node_dag(1, 20, 3);
+        loose_dag.add_param(NodeIndex::new(0), "offset", 10);
+        constrained_dag.set_dag_param("dag_id", 0);
+        loose_dag.set_dag_param("dag_id", 1);
+        let dag_set = vec![constrained_dag, loose_dag];
+        let processor = HomogeneousProcessor::new(1);
+
+        let get_worst_response_times = |formula| {
+            let mut global_edf_scheduler = GlobalEDFScheduler::new(&dag_set, &processor);
+            global_edf_scheduler.set_absolute_deadline_formula(formula);
+            global_edf_scheduler.schedule(PreemptiveType::NonPreemptive);
+            let log = global_edf_scheduler.get_log_mut();
+            (
+                log.get_worst_response_time(0),
+                log.get_worst_response_time(1),
+            )
+        };
+
+        // The constrained DAG (deadline 12) runs before the DAG released at 10 (deadline 13),
+        // and both meet their deadlines.
+        assert_eq!(
+            get_worst_response_times(AbsoluteDeadlineFormula::ReleaseTime),
+            (2, 3)
+        );
+        // Ignoring the offset gives the deadline 3 * 1 to the other DAG, which is earlier than
+        // 2 * 2, and the constrained DAG misses its deadline.
+        assert_eq!(
+            get_worst_response_times(AbsoluteDeadlineFormula::ReleaseCount),
+            (3, 1)
+        );
+    }
+
+    #[test]
+    fn test_global_edf_triggered_dag() {
+        let get_release_and_finish_times = |triggered_by_node: Option<i32>| {
+            let mut periodic_dag = Graph::<NodeData, i32>::new();
+            let n0 = periodic_dag.add_node(create_node(0, "execution_time", 3));
+            let n1 = periodic_dag.add_node(create_node(1, "execution_time", 4));
+            periodic_dag.add_param(n0, "period", 20);
+            periodic_dag.add_param(n1, "end_to_end_deadline", 20);
+            periodic_dag.add_edge(n0, n1, 0);
+            // The triggered DAG has no period.
+            let mut triggered_dag = Graph::<NodeData, i32>::new();
+            let n0 = triggered_dag.add_node(create_node(0, "execution_time", 2));
+            triggered_dag.add_param(n0, "end_to_end_deadline", 10);
+            triggered_dag.add_param(n0, "triggered_by_dag", 0);
+            if let Some(node_id) = triggered_by_node {
+                triggered_dag.add_param(n0, "triggered_by_node", node_id);
+            }
+            periodic_dag.set_dag_param("dag_id", 0);
+            triggered_dag.set_dag_param("dag_id", 1);
+            let dag_set = vec![periodic_dag, triggered_dag];
+
+            let mut global_edf_scheduler =
+                GlobalEDFScheduler::new(&dag_set, &HomogeneousProcessor::new(2));
+            let time = global_edf_scheduler.schedule(PreemptiveType::NonPreemptive);
+            assert_eq!(time, 20);
+            let file_path = global_edf_scheduler.dump_log("../lib/tests", "edf_triggered_test");
+            let yaml_docs = load_yaml(&file_path);
+            remove_file(file_path).unwrap();
+            let dag_set_log = &yaml_docs[0]["dag_set_log"][1];
+            (
+                dag_set_log["release_time"][0].as_i64().unwrap(),
+                dag_set_log["finish_time"][0].as_i64().unwrap(),
+            )
+        };
+
+        // Released when the periodic DAG completes.
+        assert_eq!(get_release_and_finish_times(None), (7, 9));
+        // Released when node 0 of the periodic DAG completes, in parallel with node 1.
+        assert_eq!(get_release_and_finish_times(Some(0)), (3, 5));
+    }
+
+    #[test]
+    fn test_global_edf_priority_inheritance() {
+        let schedule = |priority_inheritance: bool| {
+            let mut triggering_dag = Graph::<NodeData, i32>::new();
+            let n0 = triggering_dag.add_node(create_node(0, "execution_time", 2));
+            triggering_dag.add_param(n0, "period", 20);
+            triggering_dag.add_param(n0, "end_to_end_deadline", 8);
+            let mut triggered_dag = Graph::<NodeData, i32>::new();
+            let n0 = triggered_dag.add_node(create_node(0, "execution_time", 2));
+            triggered_dag.add_param(n0, "end_to_end_deadline", 20);
+            triggered_dag.add_param(n0, "triggered_by_dag", 0);
+            // Released with the triggered DAG but with an earlier deadline of its own.
+            let mut periodic_dag = Graph::<NodeData, i32>::new();
+            let n0 = periodic_dag.add_node(create_node(0, "execution_time", 5));
+            periodic_dag.add_param(n0, "period", 20);
+            periodic_dag.add_param(n0, "offset", 2);
+            periodic_dag.add_param(n0, "end_to_end_deadline", 15);
+            triggering_dag.set_dag_param("dag_id", 0);
+            triggered_dag.set_dag_param("dag_id", 1);
+            periodic_dag.set_dag_param("dag_id", 2);
+            let dag_set = vec![triggering_dag, triggered_dag, periodic_dag];
+
+            let mut global_edf_scheduler =
+                GlobalEDFScheduler::new(&dag_set, &HomogeneousProcessor::new(1));
+            if priority_inheritance {
+                global_edf_scheduler.set_priority_inheritance();
+            }
+            global_edf_scheduler.schedule(PreemptiveType::NonPreemptive);
+            global_edf_scheduler.get_log_mut().clone()
+        };
+
+        let log = schedule(false);
+        assert_eq!(log.get_worst_response_time(1), 7);
+        assert!(log.get_inheritance_log().is_empty());
+
+        let log = schedule(true);
+        assert_eq!(log.get_worst_response_time(1), 2);
+        assert_eq!(log.get_worst_response_time(2), 7);
+        assert_eq!(
+            log.get_inheritance_log(),
+            &[InheritanceEvent {
+                triggering_dag_id: 0,
+                triggering_job_id: 0,
+                triggering_release_time: 0,
+                triggered_dag_id: 1,
+                triggered_job_id: 0,
+                time: 2,
+                own_deadline: 22,
+                inherited_deadline: 8,
+                chain_latency: Some(4),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_global_edf_power_down() {
+        let mut dag_0 = Graph::<NodeData, i32>::new();
+        let n0 = dag_0.add_node(create_node(0, "execution_time", 2));
+        dag_0.add_param(n0, "period", 10);
+        dag_0.add_param(n0, "end_to_end_deadline", 10);
+        let mut dag_1 = Graph::<NodeData, i32>::new();
+        let n0 = dag_1.add_node(create_node(0, "execution_time", 1));
+        dag_1.add_param(n0, "period", 20);
+        dag_1.add_param(n0, "end_to_end_deadline", 20);
+        dag_0.set_dag_param("dag_id", 0);
+        dag_1.set_dag_param("dag_id", 1);
+
+        let mut processor = HomogeneousProcessor::new(1);
+        processor.set_power_down_model(PowerDownModel::new(2, 1, 0.5, 0.1));
+        let mut global_edf_scheduler = GlobalEDFScheduler::new(&[dag_0, dag_1], &processor);
+        global_edf_scheduler.schedule(PreemptiveType::NonPreemptive);
+        let log = global_edf_scheduler.get_log_mut();
+        // The core sleeps from 5 and wakes up for the second job of DAG 0 at 10.
+        assert_eq!(log.get_worst_response_time(0), 3);
+        let power_log = log.get_power_log().unwrap();
+        assert_eq!(power_log.wake_ups, 1);
+        assert_eq!(power_log.sleep_time, 10);
+        // 6 busy, 4 idle and 10 asleep.
+        assert_eq!(power_log.energy, 6.0 + 4.0 * 0.5 + 10.0 * 0.1);
+    }
+
+    #[test]
+    fn test_global_edf_max_parallelism() {
+        let get_response_and_throttled_time = |max_parallelism: Option<i32>| {
+            let mut dag = Graph::<NodeData, i32>::new();
+            let n0 = dag.add_node(create_node(0, "execution_time", 1));
+            dag.add_param(n0, "period", 20);
+            dag.add_param(n0, "end_to_end_deadline", 20);
+            if let Some(max_parallelism) = max_parallelism {
+                dag.add_param(n0, "max_parallelism", max_parallelism);
+            }
+            let fork_nodes: Vec<_> = (1..4)
+                .map(|id| dag.add_node(create_node(id, "execution_time", 2)))
+                .collect();
+            let n4 = dag.add_node(create_node(4, "execution_time", 1));
+            for n in fork_nodes {
+                dag.add_edge(n0, n, 0);
+                dag.add_edge(n, n4, 0);
+            }
+            dag.set_dag_param("dag_id", 0);
+
+            let mut global_edf_scheduler =
+                GlobalEDFScheduler::new(&[dag], &HomogeneousProcessor::new(3));
+            global_edf_scheduler.schedule(PreemptiveType::NonPreemptive);
+            let log = global_edf_scheduler.get_log_mut();
+            (log.get_worst_response_time(0), log.get_throttled_time(0))
+        };
+
+        assert_eq!(get_response_and_throttled_time(None), (4, 0));
+        // The fork nodes run one or two at a time despite the idle cores.
+        assert_eq!(get_response_and_throttled_time(Some(2)), (6, 2));
+        assert_eq!(get_response_and_throttled_time(Some(1)), (8, 4));
+    }
+
+    #[test]
+    fn test_global_edf_execution_time_trace() {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 2));
+        let n1 = dag.add_node(create_node(1, "execution_time", 3));
+        dag.add_param(n0, "period", 10);
+        dag.add_param(n1, "end_to_end_deadline", 10);
+        dag.add_edge(n0, n1, 0);
+        dag.set_dag_param("dag_id", 0);
+        let mut dag2 = Graph::<NodeData, i32>::new();
+        let n0 = dag2.add_node(create_node(0, "execution_time", 1));
+        dag2.add_param(n0, "period", 20);
+        dag2.add_param(n0, "end_to_end_deadline", 20);
+        dag2.set_dag_param("dag_id", 1);
+
+        let mut trace = ExecutionTimeTrace::default();
+        trace.insert(0, 1, 0, 1);
+        let mut global_edf_scheduler =
+            GlobalEDFScheduler::new(&[dag, dag2], &HomogeneousProcessor::new(1));
+        global_edf_scheduler.set_execution_time_trace(trace);
+        global_edf_scheduler.schedule(PreemptiveType::NonPreemptive);
+
+        let file_path = global_edf_scheduler.dump_log("../lib/tests", "edf_trace_test");
+        let yaml_docs = load_yaml(&file_path);
+        remove_file(file_path).unwrap();
+        // The first job replays the trace and the second job runs for the WCET.
+        let response_time = &yaml_docs[0]["dag_set_log"][0]["response_time"];
+        assert_eq!(response_time[0].as_i64().unwrap(), 3);
+        assert_eq!(response_time[1].as_i64().unwrap(), 5);
+    }
+
+    #[test]
+    fn test_global_edf_tie_breaking_policy() {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 1));
+        let n1 = dag.add_node(create_node(1, "execution_time", 3));
+        dag.add_param(n0, "period", 10);
+        dag.add_param(n0, "end_to_end_deadline", 10);
+        dag.add_edge(n0, n1, 1);
+        dag.set_dag_param("dag_id", 0);
+        let mut dag2 = Graph::<NodeData, i32>::new();
+        let n0 = dag2.add_node(create_node(0, "execution_time", 5));
+        dag2.add_param(n0, "period", 10);
+        dag2.add_param(n0, "end_to_end_deadline", 10);
+        dag2.set_dag_param("dag_id", 1);
+        let dag_set = [dag, dag2];
+
+        // All nodes have the same deadline on a single core.
+        let get_response_times = |tie_breaking_policy| {
+            let mut global_edf_scheduler = GlobalEDFScheduler::new_with_tie_breaker(
+                &dag_set,
+                &HomogeneousProcessor::new(1),
+                TieBreaker::new(tie_breaking_policy, &dag_set),
+            );
+            global_edf_scheduler.schedule(PreemptiveType::NonPreemptive);
+            global_edf_scheduler
+                .get_log_mut()
+                .get_worst_response_times()
+        };
+        assert_eq!(get_response_times(TieBreakingPolicy::NodeId), vec![4, 9]);
+        // DAG 1, ready since 0, precedes node 1 of DAG 0, ready since 1.
+        assert_eq!(get_response_times(TieBreakingPolicy::Fifo), vec![9, 6]);
+        // DAG 1 has the higher utilization and the longer remaining path than node 0 of DAG 0.
+        assert_eq!(
+            get_response_times(TieBreakingPolicy::Utilization),
+            vec![9, 5]
+        );
+        assert_eq!(
+            get_response_times(TieBreakingPolicy::RemainingWork),
+            vec![9, 5]
+        );
+    }
+
+    #[test]
+    fn test_global_edf_admission_control() {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 3));
+        dag.add_param(n0, "period", 4);
+        dag.add_param(n0, "end_to_end_deadline", 4);
+        dag.set_dag_param("dag_id", 0);
+        let mut dag2 = Graph::<NodeData, i32>::new();
+        let n0 = dag2.add_node(create_node(0, "execution_time", 4));
+        dag2.add_param(n0, "period", 8);
+        dag2.add_param(n0, "end_to_end_deadline", 8);
+        dag2.set_dag_param("dag_id", 1);
+
+        // DAG 1 released with DAG 0 raises the density of the single core to 1.25.
+        let mut global_edf_scheduler =
+            GlobalEDFScheduler::new(&[dag, dag2], &HomogeneousProcessor::new(1));
+        global_edf_scheduler.set_admission_control(AdmissionTest::Density);
+        global_edf_scheduler.schedule(PreemptiveType::NonPreemptive);
+
+        let log = global_edf_scheduler.get_log_mut();
+        assert_eq!(log.get_worst_response_time(0), 3);
+        assert_eq!(
+            log.get_release_events(1),
+            &[ReleaseEvent::Rejected { release_time: 0 }]
+        );
+        let admission_log = log.get_admission_log().unwrap();
+        assert_eq!(admission_log.get_admitted_jobs(), 2);
+        assert_eq!(admission_log.get_rejected_jobs_per_dag(), &vec![0, 1]);
+        assert_eq!(admission_log.get_rejection_ratio(), 1.0 / 3.0);
+    }
+
+    #[test]
+    fn test_global_edf_fault_injection() {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 2));
+        let n1 = dag.add_node(create_node(1, "execution_time", 3));
+        dag.add_param(n0, "period", 10);
+        dag.add_param(n1, "end_to_end_deadline", 8);
+        dag.add_param(n1, "max_retries", 1);
+        dag.add_edge(n0, n1, 0);
+        dag.set_dag_param("dag_id", 0);
+        // Releases DAG 0 twice in the hyper period.
+        let mut dag2 = Graph::<NodeData, i32>::new();
+        let n0 = dag2.add_node(create_node(0, "execution_time", 1));
+        dag2.add_param(n0, "period", 20);
+        dag2.add_param(n0, "end_to_end_deadline", 20);
+        dag2.set_dag_param("dag_id", 1);
+
+        // n1 is re-executed once in the first job, and fails twice in the second job.
+        let mut fault_injection = FaultInjection::new(0);
+        fault_injection.insert(0, 1, 0, 0);
+        fault_injection.insert(0, 1, 1, 0);
+        fault_injection.insert(0, 1, 1, 1);
+        let mut global_edf_scheduler =
+            GlobalEDFScheduler::new(&[dag, dag2], &HomogeneousProcessor::new(2));
+        global_edf_scheduler.set_fault_injection(fault_injection);
+        global_edf_scheduler.schedule(PreemptiveType::NonPreemptive);
+
+        let log = global_edf_scheduler.get_log_mut();
+        assert_eq!(log.get_worst_response_time(0), 8);
+        let retried: Vec<(usize, i32, bool)> = log
+            .get_fault_log()
+            .iter()
+            .map(|fault_event| (fault_event.job_id, fault_event.time, fault_event.retried))
+            .collect();
+        assert_eq!(retried, vec![(0, 5, true), (1, 15, true), (1, 18, false)]);
+        assert_eq!(
+            log.get_reliability()[0],
+            DAGReliability {
+                dag_id: 0,
+                jobs: 2,
+                faults: 3,
+                re_executions: 2,
+                failed_jobs: 1,
+                reliability: 0.5,
+            }
+        );
+        // The re-executions are busy time of the core.
+        let intervals = log.get_execution_intervals();
+        assert_eq!(
+            intervals
+                .iter()
+                .filter(|interval| interval.dag_id == 0)
+                .count(),
+            6
+        );
+    }
+
+    #[test]
+    fn test_global_edf_feedback_edge() {
+        let create_chain_dag = |period: i32| {
+            let mut dag = Graph::<NodeData, i32>::new();
+            let n0 = dag.add_node(create_node(0, "execution_time", 6));
+            let n1 = dag.add_node(create_node(1, "execution_time", 6));
+            dag.add_param(n0, "period", period);
+            dag.add_param(n1, "end_to_end_deadline", period);
+            dag.add_edge(n0, n1, 1);
+            dag
+        };
+        // The chain takes 12 on a single core, which is longer than the period.
+        let mut dag = create_chain_dag(10);
+        let mut dag2 = create_chain_dag(40);
+        dag.add_param(NodeIndex::new(1), "feedback", 1);
+        dag.set_dag_param("dag_id", 0);
+        dag2.set_dag_param("dag_id", 1);
+        let dag_set = vec![dag, dag2];
+
+        let processor = HomogeneousProcessor::new(2);
+
+        let mut global_edf_scheduler = GlobalEDFScheduler::new(&dag_set, &processor);
+        global_edf_scheduler.schedule(PreemptiveType::NonPreemptive);
+        let log = global_edf_scheduler.get_log_mut();
+        assert_eq!(
+            log.get_release_events(0)[..2],
+            [
+                ReleaseEvent::Queued { release_time: 10 },
+                ReleaseEvent::LateStart {
+                    release_time: 10,
+                    start_time: 12,
+                    delay: 2
+                }
+            ]
+        );
+        assert!(log.get_release_events(1).is_empty());
+
+        let file_path = global_edf_scheduler.dump_log("../lib/tests", "edf_feedback_edge_test");
+        let yaml_docs = load_yaml(&file_path);
+        let release_time = &yaml_docs[0]["dag_set_log"][0]["release_time"];
+        // Each job is released when the previous one completes.
+        let release_time: Vec<i64> = release_time
+            .as_vec()
+            .unwrap()
+            .iter()
+            .map(|time| time.as_i64().unwrap())
+            .collect();
+        assert_eq!(release_time, vec![0, 12, 24, 36]);
+        // The DAG without a feedback edge is released on time.
+        assert_eq!(
+            yaml_docs[0]["dag_set_log"][1]["release_time"][0]
+                .as_i64()
+                .unwrap(),
+            0
+        );
+
+        remove_file(file_path).unwrap();
+    }
+
+    #[test]
+    fn test_global_edf_skipped_release() {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 6));
+        let n1 = dag.add_node(create_node(1, "execution_time", 6));
+        dag.add_param(n0, "period", 10);
+        dag.add_param(n1, "end_to_end_deadline", 10);
+        dag.add_edge(n0, n1, 1);
+        dag.set_dag_param("dag_id", 0);
+        // Extends the hyper period to 40.
+        let mut dag2 = Graph::<NodeData, i32>::new();
+        let n2 = dag2.add_node(create_node(0, "execution_time", 1));
+        dag2.add_param(n2, "period", 40);
+        dag2.add_param(n2, "end_to_end_deadline", 40);
+        dag2.set_dag_param("dag_id", 1);
+
+        let mut global_edf_scheduler =
+            GlobalEDFScheduler::new(&[dag, dag2], &HomogeneousProcessor::new(1));
+        global_edf_scheduler.schedule(PreemptiveType::NonPreemptive);
+        // The jobs are still running at 10 and 30, so those releases are dropped and the DAG
+        // is released again at the next periods.
+        let log = global_edf_scheduler.get_log_mut();
+        assert_eq!(
+            log.get_release_events(0),
+            [
+                ReleaseEvent::Skipped { release_time: 10 },
+                ReleaseEvent::Skipped { release_time: 30 }
+            ]
+        );
+        assert_eq!(log.get_release_times(0), [0, 20]);
+    }
+
+    #[test]
+    fn test_global_edf_drop_optional_node() {
+        let create_dag_set = |is_optional: bool| {
+            let mut dag = Graph::<NodeData, i32>::new();
+            let n0 = dag.add_node(create_node(0, "execution_time", 2));
+            let n1 = dag.add_node(create_node(1, "execution_time", 5));
+            let n2 = dag.add_node(create_node(2, "execution_time", 1));
+            dag.add_param(n0, "period", 20);
+            dag.add_param(n2, "end_to_end_deadline", 6);
+            if is_optional {
+                dag.add_param(n1, "optional", 1);
+            }
+            dag.add_edge(n0, n1, 1);
+            dag.add_edge(n1, n2, 1);
+            dag.set_dag_param("dag_id", 0);
+            let mut dag2 = Graph::<NodeData, i32>::new();
+            let n3 = dag2.add_node(create_node(0, "execution_time", 3));
+            dag2.add_param(n3, "period", 20);
+            dag2.add_param(n3, "end_to_end_deadline", 5);
+            dag2.set_dag_param("dag_id", 1);
+            vec![dag, dag2]
+        };
+        let processor = HomogeneousProcessor::new(1);
+
+        let mut global_edf_scheduler = GlobalEDFScheduler::new(&create_dag_set(false), &processor);
+        global_edf_scheduler.schedule(PreemptiveType::NonPreemptive);
+        assert_eq!(
+            global_edf_scheduler
+                .get_log_mut()
+                .get_worst_response_time(0),
+            11
+        );
+
+        // At 5, the rest of the DAG takes 6 and the deadline is 6, so the optional node is dropped.
+        let mut global_edf_scheduler = GlobalEDFScheduler::new(&create_dag_set(true), &processor);
+        global_edf_scheduler.schedule(PreemptiveType::NonPreemptive);
+        let log = global_edf_scheduler.get_log_mut();
+        assert_eq!(log.get_worst_response_time(0), 6);
+        assert_eq!(
+            log.get_degradation_log(),
+            [DegradationDecision {
+                dag_id: 0,
+                node_id: 1,
+                job_id: 0,
+                time: 5,
+                laxity: -5
+            }]
+        );
+    }
+
+    #[test]
+    fn test_global_edf_optional_execution_time() {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 2));
+        let n1 = dag.add_node(create_node(1, "execution_time", 2));
+        dag.add_param(n0, "optional_execution_time", 3);
+        dag.add_param(n1, "optional_execution_time", 4);
+        dag.add_param(n0, "period", 10);
+        dag.add_param(n1, "end_to_end_deadline", 8);
+        dag.add_edge(n0, n1, 1);
+        dag.set_dag_param("dag_id", 0);
+
+        let mut global_edf_scheduler =
+            GlobalEDFScheduler::new(&[dag], &HomogeneousProcessor::new(1));
+        global_edf_scheduler.schedule(PreemptiveType::NonPreemptive);
+        let log = global_edf_scheduler.get_log_mut();
+        // n0 runs its whole optional part (laxity 4), n1 only 1 of 4 (laxity 1).
+        assert_eq!(log.get_worst_response_time(0), 8);
+        assert_eq!(log.get_quality(0), [4.0 / 7.0]);
+    }
+
+    #[test]
+    fn test_global_edf_idle_core_selection() {
+        let create_chain_dag = |execution_times: [i32; 2], deadline: i32, dag_id: i32| {
+            let mut dag = Graph::<NodeData, i32>::new();
+            let n0 = dag.add_node(create_node(0, "execution_time", execution_times[0]));
+            let n1 = dag.add_node(create_node(1, "execution_time", execution_times[1]));
+            dag.add_edge(n0, n1, 0);
+            dag.add_param(n0, "period", 20);
+            dag.add_param(n1, "end_to_end_deadline", deadline);
+            dag.set_dag_param("dag_id", dag_id);
+            dag
+        };
+        // At time 3, node 1 of DAG 0 chooses between core 0 (idle since 2) and core 1 (idle since 3).
+        let dag_set = vec![
+            create_chain_dag([3, 1], 20, 0),
+            create_chain_dag([1, 1], 10, 1),
+        ];
+        let get_total_proc_times = |idle_core_selection| {
+            let mut global_edf_scheduler =
+                GlobalEDFScheduler::new(&dag_set, &HomogeneousProcessor::new(2));
+            global_edf_scheduler.set_idle_core_selection(idle_core_selection);
+            global_edf_scheduler.schedule(PreemptiveType::NonPreemptive);
+            let log = global_edf_scheduler.get_log_mut();
+            [log.get_total_proc_time(0), log.get_total_proc_time(1)]
+        };
+
+        assert_eq!(get_total_proc_times(IdleCoreSelection::FirstFit), [3, 3]);
+        assert_eq!(get_total_proc_times(IdleCoreSelection::LastUsed), [2, 4]);
+        assert_eq!(
+            get_total_proc_times(IdleCoreSelection::LeastUtilized),
+            [3, 3]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "random")]
+    fn test_global_edf_random_dispatch() {
+        let mut dag = create_sample_dag();
+        let mut dag2 = create_sample_dag2();
+        dag.set_dag_param("dag_id", 0);
+        dag2.set_dag_param("dag_id", 1);
+        let dag_set = vec![dag, dag2];
+        let get_dispatch_order = |seed: Option<u64>| {
+            let mut global_edf_scheduler =
+                GlobalEDFScheduler::new(&dag_set, &HomogeneousProcessor::new(2));
+            if let Some(seed) = seed {
+                global_edf_scheduler.set_random_dispatch(seed);
+            }
+            global_edf_scheduler.schedule(PreemptiveType::NonPreemptive);
+            global_edf_scheduler
+                .get_log_mut()
+                .get_job_logs_in_time_order()
+                .into_iter()
+                .filter(|job_log| matches!(job_log.get_event_time(), JobEventTimes::StartTime(_)))
+                .map(|job_log| (job_log.get_dag_id(), job_log.get_node_id()))
+                .collect::<Vec<_>>()
+        };
+
+        let edf_order = get_dispatch_order(None);
+        assert_eq!(get_dispatch_order(Some(1)), get_dispatch_order(Some(1)));
+        // Every node still runs once per job, in a different order for some seed.
+        let random_orders: Vec<_> = (0..10).map(|seed| get_dispatch_order(Some(seed))).collect();
+        assert!(random_orders
+            .iter()
+            .all(|random_order| random_order.len() == edf_order.len()));
+        assert!(random_orders
+            .iter()
+            .any(|random_order| *random_order != edf_order));
+    }
+
+    #[test]
+    fn test_global_edf_core_utilization_caps() {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 10));
+        dag.add_param(n0, "period", 20);
+        dag.add_param(n0, "end_to_end_deadline", 20);
+        dag.set_dag_param("dag_id", 0);
+        let dag_set = vec![dag];
+        // The same work split into a chain of nodes, each of which fits in the budget.
+        let mut chain = Graph::<NodeData, i32>::new();
+        let chain_nodes: Vec<NodeIndex> = (0..5)
+            .map(|id| chain.add_node(create_node(id, "execution_time", 2)))
+            .collect();
+        for pair in chain_nodes.windows(2) {
+            chain.add_edge(pair[0], pair[1], 1);
+        }
+        chain.add_param(chain_nodes[0], "period", 20);
+        chain.add_param(chain_nodes[4], "end_to_end_deadline", 20);
+        chain.set_dag_param("dag_id", 0);
+
+        // The core may be busy for 2 of every 4 time units.
+        let caps = CoreUtilizationCaps::new(vec![0.5], 4);
+        let mut global_edf_scheduler =
+            GlobalEDFScheduler::new(&dag_set, &HomogeneousProcessor::new(1));
+        global_edf_scheduler.set_core_utilization_caps(caps.clone());
+        global_edf_scheduler.schedule(PreemptiveType::Preemptive {
+            key: "node_absolute_deadline".to_string(),
+        });
+        let log = global_edf_scheduler.get_log_mut();
+        assert_eq!(log.get_worst_response_time(0), 18);
+        assert_eq!(log.get_core_throttled_time(0), Some(10));
+        assert!(log.verify_core_utilization_caps().is_empty());
+
+        let mut global_edf_scheduler =
+            GlobalEDFScheduler::new(&[chain], &HomogeneousProcessor::new(1));
+        global_edf_scheduler.set_core_utilization_caps(caps.clone());
+        global_edf_scheduler.schedule(PreemptiveType::NonPreemptive);
+        let log = global_edf_scheduler.get_log_mut();
+        assert_eq!(log.get_worst_response_time(0), 18);
+        assert_eq!(log.get_core_throttled_time(0), Some(10));
+        assert!(log.verify_core_utilization_caps().is_empty());
+
+        // Without preemption, the node runs to its completion, and the core is throttled only for
+        // the rest of the window in which the node completes.
+        let mut global_edf_scheduler =
+            GlobalEDFScheduler::new(&dag_set, &HomogeneousProcessor::new(1));
+        global_edf_scheduler.set_core_utilization_caps(caps);
+        global_edf_scheduler.schedule(PreemptiveType::NonPreemptive);
+        let log = global_edf_scheduler.get_log_mut();
+        assert_eq!(log.get_worst_response_time(0), 10);
+        assert_eq!(log.get_core_throttled_time(0), Some(2));
+    }
+
+    #[test]
+    fn test_global_edf_critical_path_boost() {
+        // 0 -> {1, 2, 3} -> 4 on two cores, where 0 -> 3 -> 4 is the critical path.
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 1));
+        let n1 = dag.add_node(create_node(1, "execution_time", 2));
+        let n2 = dag.add_node(create_node(2, "execution_time", 2));
+        let n3 = dag.add_node(create_node(3, "execution_time", 3));
+        let n4 = dag.add_node(create_node(4, "execution_time", 4));
+        for node_i in [n1, n2, n3] {
+            dag.add_edge(n0, node_i, 0);
+            dag.add_edge(node_i, n4, 0);
+        }
+        dag.add_param(n0, "period", 20);
+        dag.add_param(n4, "end_to_end_deadline", 20);
+        dag.set_dag_param("dag_id", 0);
+        let dag_set = vec![dag];
+
+        let mut global_edf_scheduler =
+            GlobalEDFScheduler::new(&dag_set, &HomogeneousProcessor::new(2));
+        global_edf_scheduler.schedule(PreemptiveType::NonPreemptive);
+        // Nodes 1 and 2 precede node 3 by the node id.
+        assert_eq!(
+            global_edf_scheduler
+                .get_log_mut()
+                .get_worst_response_time(0),
+            10
+        );
+
+        let mut global_edf_scheduler =
+            GlobalEDFScheduler::new(&dag_set, &HomogeneousProcessor::new(2));
+        global_edf_scheduler.set_critical_path_boost();
+        global_edf_scheduler.schedule(PreemptiveType::NonPreemptive);
+        let log = global_edf_scheduler.get_log_mut();
+        assert_eq!(log.get_worst_response_time(0), 9);
+        assert!(log.get_critical_path_boost());
+    }
+
+    #[test]
+    fn test_global_edf_critical_path_boost_rejected_node() {
+        // 0 -> {1, 2, 3} -> 4, where node 3 starts the longest remaining path.
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 1));
+        let n1 = dag.add_node(create_node(1, "execution_time", 2));
+        let n2 = dag.add_node(create_node(2, "execution_time", 3));
+        let n3 = dag.add_node(create_node(3, "execution_time", 5));
+        let n4 = dag.add_node(create_node(4, "execution_time", 4));
+        for node_i in [n1, n2, n3] {
+            dag.add_edge(n0, node_i, 0);
+            dag.add_edge(node_i, n4, 0);
+        }
+        dag.add_param(n0, "period", 20);
+        dag.set_dag_param("dag_id", 0);
+        let dag_set = vec![dag];
+
+        let mut global_edf_scheduler =
+            GlobalEDFScheduler::new(&dag_set, &HomogeneousProcessor::new(2));
+        global_edf_scheduler.set_critical_path_boost();
+        let mut ready_queue = ReadyQueue::new();
+        for node_i in [n1, n2, n3] {
+            let mut node_data = dag_set[0][node_i].clone();
+            node_data
+                .params
+                .insert("node_absolute_deadline".to_string(), 20);
+            node_data.params.insert("job_id".to_string(), 0);
+            ready_queue.insert_new(NodeDataWrapper::new(node_data));
+        }
+        // Node 3 cannot be allocated, so node 2 starts the remaining critical path.
+        let selected = global_edf_scheduler
+            .select_ready_node(&ready_queue, |_, node_data| node_data.get_id() != 3)
+            .unwrap();
+        assert_eq!(selected.node_data.get_id(), 2);
+    }
+
+    #[test]
+    fn test_global_edf_critical_path_boost_remaining_path() {
+        // 0 -> {1, 2, 3, 4, 5 -> 6} -> 7 on two cores. 0 -> 1 -> 7 is the critical path, and
+        // 5 -> 6 -> 7 becomes the remaining critical path once node 1 is dispatched.
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 1));
+        let n1 = dag.add_node(create_node(1, "execution_time", 6));
+        let mut branches = vec![n1];
+        for id in 2..5 {
+            branches.push(dag.add_node(create_node(id, "execution_time", 1)));
+        }
+        let n5 = dag.add_node(create_node(5, "execution_time", 1));
+        let n6 = dag.add_node(create_node(6, "execution_time", 4));
+        let n7 = dag.add_node(create_node(7, "execution_time", 1));
+        for &node_i in branches.iter() {
+            dag.add_edge(n0, node_i, 0);
+            dag.add_edge(node_i, n7, 0);
+        }
+        dag.add_edge(n0, n5, 0);
+        dag.add_edge(n5, n6, 0);
+        dag.add_edge(n6, n7, 0);
+        dag.add_param(n0, "period", 20);
+        dag.add_param(n7, "end_to_end_deadline", 20);
+        dag.set_dag_param("dag_id", 0);
+        let dag_set = vec![dag];
+
+        // Nodes 2, 3, and 4 precede node 5 by the node id.
+        let mut global_edf_scheduler =
+            GlobalEDFScheduler::new(&dag_set, &HomogeneousProcessor::new(2));
+        global_edf_scheduler.schedule(PreemptiveType::NonPreemptive);
+        assert_eq!(
+            global_edf_scheduler
+                .get_log_mut()
+                .get_worst_response_time(0),
+            10
+        );
+
+        let mut global_edf_scheduler =
+            GlobalEDFScheduler::new(&dag_set, &HomogeneousProcessor::new(2));
+        global_edf_scheduler.set_critical_path_boost();
+        global_edf_scheduler.schedule(PreemptiveType::NonPreemptive);
+        assert_eq!(
+            global_edf_scheduler
+                .get_log_mut()
+                .get_worst_response_time(0),
+            9
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "yaml")]
+    fn test_global_edf_non_contiguous_ids() {
+        let mut dag = crate::dag_creator::create_dag_from_yaml(
+            "tests/sample_dags/non_contiguous_ids.yaml",
+            false,
+        );
+        dag.set_dag_param("dag_id", 0);
+        let mut global_edf_scheduler =
+            GlobalEDFScheduler::new(&[dag], &HomogeneousProcessor::new(2));
+        global_edf_scheduler.schedule(PreemptiveType::NonPreemptive);
+
+        let log = global_edf_scheduler.get_log_mut();
+        assert_eq!(log.get_worst_response_time(0), 8);
+        let mut node_ids: Vec<usize> = log
+            .get_job_logs_in_time_order()
+            .iter()
+            .map(|job_log| job_log.get_node_id())
+            .collect();
+        node_ids.sort();
+        node_ids.dedup();
+        assert_eq!(node_ids, vec![10, 20, 30, 40]);
+    }
+
+    #[test]
+    #[cfg(feature = "random")]
+    fn test_global_edf_interrupt_model() {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 6));
+        dag.add_param(n0, "period", 20);
+        dag.add_param(n0, "end_to_end_deadline", 20);
+        dag.set_dag_param("dag_id", 0);
+        let dag_set = vec![dag];
+
+        // Bursts of 2 time units every 5 time units: at 0, 5, 10 and 15.
+        let mut global_edf_scheduler =
+            GlobalEDFScheduler::new(&dag_set, &HomogeneousProcessor::new(1));
+        global_edf_scheduler.set_interrupt_model(InterruptModel::new_uniform(
+            InterruptSource::new(5, 0, 2),
+            1,
+            0,
+        ));
+        global_edf_scheduler.schedule(PreemptiveType::NonPreemptive);
+        let log = global_edf_scheduler.get_log_mut();
+        // The node starts at 2 after the first burst and is stalled by the second.
+        assert_eq!(log.get_worst_response_time(0), 10);
+        assert_eq!(log.get_core_stolen_time(0), Some(8));
+        assert_eq!(log.get_interrupt_interference_time(0), Some(2));
+    }
+
+    #[test]
+    fn test_global_edf_anticipatory_idle_insertion() {
+        let mut long_dag = Graph::<NodeData, i32>::new();
+        let n0 = long_dag.add_node(create_node(0, "execution_time", 10));
+        long_dag.add_param(n0, "period", 40);
+        long_dag.add_param(n0, "end_to_end_deadline", 40);
+        long_dag.set_dag_param("dag_id", 0);
+        // Released at 1 with a tight deadline.
+        let mut urgent_dag = Graph::<NodeData, i32>::new();
+        let n0 = urgent_dag.add_node(create_node(0, "execution_time", 2));
+        urgent_dag.add_param(n0, "period", 40);
+        urgent_dag.add_param(n0, "end_to_end_deadline", 6);
+        urgent_dag.add_param(n0, "offset", 1);
+        urgent_dag.set_dag_param("dag_id", 1);
+        let dag_set = vec![long_dag, urgent_dag];
+
+        let mut global_edf_scheduler =
+            GlobalEDFScheduler::new(&dag_set, &HomogeneousProcessor::new(1));
+        global_edf_scheduler.set_idle_insertion_policy(IdleInsertionPolicy::Anticipatory);
+        global_edf_scheduler.schedule(PreemptiveType::NonPreemptive);
+        // The core idles at 0 so that the urgent node meets its deadline.
+        let log = global_edf_scheduler.get_log_mut();
+        assert_eq!(log.get_worst_response_time(0), 13);
+        assert_eq!(log.get_worst_response_time(1), 2);
+        assert_eq!(
+            log.get_idle_insertion_policy(),
+            Some(IdleInsertionPolicy::Anticipatory)
+        );
+
+        // The work-conserving default starts the long node at 0.
+        let mut global_edf_scheduler =
+            GlobalEDFScheduler::new(&dag_set, &HomogeneousProcessor::new(1));
+        global_edf_scheduler.schedule(PreemptiveType::NonPreemptive);
+        let log = global_edf_scheduler.get_log_mut();
+        assert_eq!(log.get_worst_response_time(1), 11);
+        assert_eq!(log.get_idle_insertion_policy(), None);
     }
 }
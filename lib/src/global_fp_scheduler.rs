@@ -0,0 +1,219 @@
+//! Global fixed-priority scheduling of DAG sets.
+//!
+//! All nodes of a DAG share the priority of the DAG, `dag_priority`, where a smaller value is a
+//! higher priority. The priorities are read from the `dag_priority` param of the DAGs, e.g., on a
+//! node in the YAML file, or assigned rate-monotonically if a DAG has none.
+//! `new_with_priority_assignment` assigns them by a heuristic of `priority_assignment` instead.
+//! Nodes of the same priority are ordered by the canonical ready queue order. For preemptive
+//! scheduling, pass `PreemptiveType::Preemptive { key: "dag_priority".to_string() }`.
+use crate::dag_set_scheduler::{DAGSetSchedulerBase, NodeDataWrapper, ReadyQueue};
+use crate::getset_dag_set_scheduler;
+use crate::{
+    graph_extension::{get_node_index_maps, GraphExtension, NodeData, NodeIndexMap},
+    homogeneous::HomogeneousProcessor,
+    log::DAGSetSchedulerLog,
+    priority_assignment::{write_priorities, PriorityAssignment},
+    processor::ProcessorBase,
+    resource_manager::LockingProtocol,
+};
+use log::warn;
+use petgraph::graph::Graph;
+
+pub struct GlobalFPScheduler {
+    dag_set: Vec<Graph<NodeData, i32>>,
+    node_index_maps: Vec<NodeIndexMap>,
+    processor: HomogeneousProcessor,
+    log: DAGSetSchedulerLog,
+    current_time: i32,
+    locking_protocol: LockingProtocol,
+}
+
+impl DAGSetSchedulerBase<HomogeneousProcessor> for GlobalFPScheduler {
+    fn new(dag_set: &[Graph<NodeData, i32>], processor: &HomogeneousProcessor) -> Self {
+        let mut scheduler = Self {
+            node_index_maps: get_node_index_maps(dag_set),
+            dag_set: dag_set.to_vec(),
+            processor: processor.clone(),
+            log: DAGSetSchedulerLog::new(dag_set, processor.get_number_of_cores()),
+            current_time: 0,
+            locking_protocol: LockingProtocol::default(),
+        };
+        let priorities: Option<Vec<i32>> = scheduler
+            .dag_set
+            .iter()
+            .map(|dag| {
+                dag.get_dag_param_values("dag_priority")
+                    .first()
+                    .map(|&(_, priority)| priority)
+            })
+            .collect();
+        match priorities {
+            Some(priorities) => scheduler.set_priorities(&priorities),
+            None => {
+                if dag_set
+                    .iter()
+                    .any(|dag| !dag.get_dag_param_values("dag_priority").is_empty())
+                {
+                    warn!("Some DAGs have no dag_priority. Rate-monotonic priorities are used.");
+                }
+                scheduler.set_rate_monotonic_priorities();
+            }
+        }
+        scheduler
+    }
+
+    getset_dag_set_scheduler!(HomogeneousProcessor);
+
+    fn get_locking_protocol(&self) -> LockingProtocol {
+        self.locking_protocol
+    }
+
+    /// The ready node with the highest priority, first in the canonical order among ties.
+    fn select_ready_node(
+        &mut self,
+        ready_queue: &ReadyQueue,
+        can_allocate: impl Fn(&HomogeneousProcessor, &NodeData) -> bool,
+    ) -> Option<NodeDataWrapper> {
+        ready_queue
+            .iter()
+            .filter(|wrapper| can_allocate(&self.processor, &wrapper.node_data))
+            .min_by_key(|wrapper| {
+                ready_queue.count_comparison();
+                wrapper.node_data.get_params_value("dag_priority")
+            })
+            .cloned()
+    }
+}
+
+impl GlobalFPScheduler {
+    /// Prioritize the DAGs by `priority_assignment` for the cores of `processor`, overriding
+    /// their `dag_priority`.
+    pub fn new_with_priority_assignment(
+        dag_set: &[Graph<NodeData, i32>],
+        processor: &HomogeneousProcessor,
+        priority_assignment: PriorityAssignment,
+    ) -> Self {
+        let mut dag_set = dag_set.to_vec();
+        write_priorities(
+            &mut dag_set,
+            processor.get_number_of_cores(),
+            priority_assignment,
+        );
+        let mut scheduler = Self::new(&dag_set, processor);
+        scheduler.log.write_priority_assignment(priority_assignment);
+        scheduler
+    }
+
+    /// Set the priority of each DAG, indexed by `dag_id`.
+    pub fn set_priorities(&mut self, priorities: &[i32]) {
+        if priorities.len() != self.dag_set.len() {
+            panic!(
+                "{} priorities are given for {} DAGs.",
+                priorities.len(),
+                self.dag_set.len()
+            );
+        }
+        for dag in self.dag_set.iter_mut() {
+            let dag_id = dag.get_dag_param("dag_id") as usize;
+            dag.set_dag_param("dag_priority", priorities[dag_id]);
+        }
+    }
+
+    /// MPCP computes the ceilings of the resources from `dag_priority`.
+    pub fn set_locking_protocol(&mut self, locking_protocol: LockingProtocol) {
+        self.locking_protocol = locking_protocol;
+    }
+
+    /// Prioritize the DAGs with shorter periods, breaking ties by `dag_id`.
+    /// DAGs without a period, i.e., triggered DAGs, have the lowest priorities.
+    pub fn set_rate_monotonic_priorities(&mut self) {
+        let mut dag_ids: Vec<usize> = (0..self.dag_set.len()).collect();
+        dag_ids.sort_by_key(|&dag_id| {
+            let dag = self
+                .dag_set
+                .iter()
+                .find(|dag| dag.get_dag_param("dag_id") as usize == dag_id)
+                .unwrap();
+            (dag.get_head_period().unwrap_or(i32::MAX), dag_id)
+        });
+        let mut priorities = vec![0; self.dag_set.len()];
+        for (priority, dag_id) in dag_ids.into_iter().enumerate() {
+            priorities[dag_id] = priority as i32;
+        }
+        self.set_priorities(&priorities);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dag_set_scheduler::PreemptiveType;
+    use crate::test_util::create_node;
+    use petgraph::graph::NodeIndex;
+
+    fn create_chain_dag(
+        execution_times: &[i32],
+        period: i32,
+        end_to_end_deadline: i32,
+    ) -> Graph<NodeData, i32> {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let nodes: Vec<_> = execution_times
+            .iter()
+            .enumerate()
+            .map(|(id, &execution_time)| {
+                dag.add_node(create_node(id as i32, "execution_time", execution_time))
+            })
+            .collect();
+        for pair in nodes.windows(2) {
+            dag.add_edge(pair[0], pair[1], 1);
+        }
+        dag.add_param(nodes[0], "period", period);
+        dag.add_param(
+            *nodes.last().unwrap(),
+            "end_to_end_deadline",
+            end_to_end_deadline,
+        );
+        dag
+    }
+
+    fn create_sample_dag_set() -> Vec<Graph<NodeData, i32>> {
+        // The long DAG has the earlier deadline but the longer period.
+        let mut long_dag = create_chain_dag(&[4, 4], 20, 9);
+        let mut short_dag = create_chain_dag(&[3], 10, 10);
+        short_dag.add_param(NodeIndex::new(0), "offset", 2);
+        long_dag.set_dag_param("dag_id", 0);
+        short_dag.set_dag_param("dag_id", 1);
+        vec![long_dag, short_dag]
+    }
+
+    #[test]
+    fn test_global_fp_rate_monotonic_preemptive() {
+        let mut global_fp_scheduler =
+            GlobalFPScheduler::new(&create_sample_dag_set(), &HomogeneousProcessor::new(1));
+        global_fp_scheduler.schedule(PreemptiveType::Preemptive {
+            key: "dag_priority".to_string(),
+        });
+
+        // The short DAG has the higher priority and preempts the long DAG at 2.
+        let log = global_fp_scheduler.get_log_mut();
+        assert_eq!(log.get_worst_response_time(0), 11);
+        assert_eq!(log.get_worst_response_time(1), 3);
+    }
+
+    #[test]
+    fn test_global_fp_given_priorities() {
+        let mut dag_set = create_sample_dag_set();
+        dag_set[0].add_param(NodeIndex::new(0), "dag_priority", 0);
+        dag_set[1].add_param(NodeIndex::new(0), "dag_priority", 1);
+        let mut global_fp_scheduler =
+            GlobalFPScheduler::new(&dag_set, &HomogeneousProcessor::new(1));
+        global_fp_scheduler.schedule(PreemptiveType::Preemptive {
+            key: "dag_priority".to_string(),
+        });
+
+        // The long DAG is never preempted, and the short DAG waits for it until 8.
+        let log = global_fp_scheduler.get_log_mut();
+        assert_eq!(log.get_worst_response_time(0), 8);
+        assert_eq!(log.get_worst_response_time(1), 9);
+    }
+}
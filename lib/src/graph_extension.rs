@@ -1,6 +1,6 @@
 use log::warn;
 use petgraph::{
-    algo::toposort,
+    algo::{connected_components, toposort},
     graph::{Graph, NodeIndex},
     visit::EdgeRef,
     Direction::{Incoming, Outgoing},
@@ -11,6 +11,88 @@ use std::collections::{BTreeMap, HashMap, VecDeque};
 const DUMMY_SOURCE_NODE_FLAG: i32 = -1;
 const DUMMY_SINK_NODE_FLAG: i32 = -2;
 
+/// Structural problem of a DAG reported by `lint_structure`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StructureIssue {
+    /// `count` edges connect the same pair of nodes.
+    DuplicateEdge {
+        source: NodeIndex,
+        target: NodeIndex,
+        count: usize,
+    },
+    SelfLoop {
+        node: NodeIndex,
+    },
+    /// The DAG consists of `components` weakly connected components.
+    DisconnectedComponents {
+        components: usize,
+    },
+    /// `count` nodes have the same id.
+    DuplicateNodeId {
+        id: i32,
+        count: usize,
+    },
+}
+
+/// DAG-level params consolidated from the node params by `get_dag_params`.
+///
+/// `period` and `offset` are read from the source nodes and `end_to_end_deadline` from any node.
+/// If several nodes carry different values, the value of the first node (in index order) is used.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DAGParams {
+    pub period: Option<i32>,
+    pub end_to_end_deadline: Option<i32>,
+    pub offset: Option<i32>,
+}
+
+/// A DAG-level param carried by several nodes with different values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParamConflict {
+    pub key: String,
+    pub values: Vec<(NodeIndex, i32)>,
+}
+
+/// How the jobs of a DAG are released.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReleaseSource {
+    /// Released every `period` from `offset`.
+    Periodic,
+    /// Released when node `node_id` of DAG `dag_id` completes,
+    /// or when the whole DAG completes if `node_id` is `None`.
+    Triggered { dag_id: usize, node_id: Option<i32> },
+}
+
+/// Speeds of the cores on which the nodes run, relative to the speed at which
+/// `execution_time` was measured. A node takes `ceil(execution_time / speed)` on its core.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum SpeedModel {
+    /// All nodes run at speed 1 (homogeneous processors).
+    #[default]
+    Uniform,
+    /// All nodes run at the same speed.
+    Fixed(f32),
+    /// Speed of the core each node (by id) is assigned to. Unassigned nodes run at speed 1.
+    Assignment(HashMap<i32, f32>),
+}
+
+impl SpeedModel {
+    pub fn get_speed(&self, node_id: i32) -> f32 {
+        match self {
+            Self::Uniform => 1.0,
+            Self::Fixed(speed) => *speed,
+            Self::Assignment(speeds) => speeds.get(&node_id).copied().unwrap_or(1.0),
+        }
+    }
+
+    pub fn get_execution_time(&self, node_data: &NodeData) -> i32 {
+        let execution_time = node_data.get_params_value("execution_time");
+        match self {
+            Self::Uniform => execution_time,
+            _ => (execution_time as f32 / self.get_speed(node_data.id)).ceil() as i32,
+        }
+    }
+}
+
 /// custom node data structure for dag nodes (petgraph)
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct NodeData {
@@ -35,6 +117,91 @@ impl NodeData {
     }
 }
 
+/// Human-readable names of a DAG and its nodes (`name` in the input YAML).
+///
+/// The params of `NodeData` are integers, so the names are kept apart from the DAG and written
+/// into the logs (see `DAGSetSchedulerLog::write_dag_names`).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DAGNames {
+    pub dag_name: Option<String>,
+    /// Name of each node by id.
+    pub node_names: BTreeMap<i32, String>,
+}
+
+impl DAGNames {
+    pub fn get_node_name(&self, node_id: i32) -> Option<&str> {
+        self.node_names.get(&node_id).map(String::as_str)
+    }
+}
+
+/// The `NodeIndex` of each node id of a DAG.
+///
+/// Ids differ from the indices if the ids of the input do not start at zero or are not
+/// contiguous, and after removals, since petgraph moves the last node into the index of a
+/// removed node. The map is kept apart from the DAG like `DAGNames`, so the nodes are added and
+/// removed through the map to keep it in sync.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NodeIndexMap {
+    node_indices: BTreeMap<i32, NodeIndex>,
+}
+
+impl NodeIndexMap {
+    pub fn new(dag: &Graph<NodeData, i32>) -> Self {
+        let mut node_index_map = Self::default();
+        for node_i in dag.node_indices() {
+            node_index_map.insert(dag[node_i].id, node_i);
+        }
+        node_index_map
+    }
+
+    fn insert(&mut self, node_id: i32, node_i: NodeIndex) {
+        if self.node_indices.insert(node_id, node_i).is_some() {
+            panic!("Node id {} is used by several nodes.", node_id);
+        }
+    }
+
+    pub fn get(&self, node_id: i32) -> Option<NodeIndex> {
+        self.node_indices.get(&node_id).copied()
+    }
+
+    pub fn get_node_index(&self, node_id: i32) -> NodeIndex {
+        self.get(node_id)
+            .unwrap_or_else(|| panic!("Node {} does not exist.", node_id))
+    }
+
+    pub fn add_node(&mut self, dag: &mut Graph<NodeData, i32>, node: NodeData) -> NodeIndex {
+        let node_id = node.id;
+        let node_i = dag.add_node(node);
+        self.insert(node_id, node_i);
+        node_i
+    }
+
+    /// Remove the node from `dag`, and move the last node into its index as petgraph does.
+    pub fn remove_node(
+        &mut self,
+        dag: &mut Graph<NodeData, i32>,
+        node_i: NodeIndex,
+    ) -> Option<NodeData> {
+        let node = dag.remove_node(node_i)?;
+        self.node_indices.remove(&node.id);
+        if let Some(moved_node) = dag.node_weight(node_i) {
+            self.node_indices.insert(moved_node.id, node_i);
+        }
+        Some(node)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (i32, NodeIndex)> + '_ {
+        self.node_indices
+            .iter()
+            .map(|(&node_id, &node_i)| (node_id, node_i))
+    }
+}
+
+/// `NodeIndexMap` of each DAG of the set.
+pub fn get_node_index_maps(dag_set: &[Graph<NodeData, i32>]) -> Vec<NodeIndexMap> {
+    dag_set.iter().map(NodeIndexMap::new).collect()
+}
+
 pub trait GraphExtension {
     fn add_param(&mut self, node_i: NodeIndex, key: &str, value: i32);
     fn update_param(&mut self, node_i: NodeIndex, key: &str, value: i32);
@@ -47,6 +214,7 @@ pub trait GraphExtension {
     fn calculate_earliest_finish_times(&mut self);
     fn calculate_latest_start_times(&mut self);
     fn calculate_latest_finish_times(&mut self);
+    fn calculate_asap_alap_times(&mut self, end_to_end_deadline: i32);
     fn get_critical_path(&mut self) -> Vec<NodeIndex>;
     fn get_non_critical_nodes(&self, critical_path: &[NodeIndex]) -> Option<Vec<NodeIndex>>;
     fn get_source_nodes(&self) -> Vec<NodeIndex>;
@@ -65,7 +233,25 @@ pub trait GraphExtension {
     fn get_dag_param(&self, key: &str) -> i32;
     fn set_dag_param(&mut self, key: &str, value: i32);
     fn add_node_with_id_consistency(&mut self, node: NodeData) -> NodeIndex;
+    fn get_next_node_id(&self) -> i32;
     fn is_node_ready(&self, node_i: NodeIndex) -> bool;
+    fn get_lock_group_successor(&self, node_i: NodeIndex) -> Option<NodeIndex>;
+    fn validate_lock_groups(&self);
+    fn get_feedback_sinks(&self) -> Vec<NodeIndex>;
+    fn lint_structure(&self) -> Vec<StructureIssue>;
+    fn remove_duplicate_edges(&mut self) -> usize;
+    fn get_release_source(&self) -> ReleaseSource;
+    fn get_max_parallelism(&self) -> Option<usize>;
+    fn get_volume_with_speed(&self, speed_model: &SpeedModel) -> i32;
+    fn get_critical_path_length_with_speed(&self, speed_model: &SpeedModel) -> i32;
+    fn get_dag_param_values(&self, key: &str) -> Vec<(NodeIndex, i32)>;
+    fn get_param_conflicts(&self) -> Vec<ParamConflict>;
+    fn get_dag_params(&self) -> DAGParams;
+    fn get_remaining_path_length(&self, node_i: NodeIndex) -> i32;
+    fn get_remaining_path_lengths(&self) -> Vec<i32>;
+    fn get_parallel_segments(&mut self) -> Vec<Vec<NodeIndex>>;
+    fn decompose(&mut self);
+    fn assign_critical_path_first_priorities(&mut self);
 }
 
 impl GraphExtension for Graph<NodeData, i32> {
@@ -92,7 +278,7 @@ impl GraphExtension for Graph<NodeData, i32> {
             self[i]
                 .params
                 .get("dummy")
-                .map_or(false, |&v| v == DUMMY_SOURCE_NODE_FLAG)
+                .is_some_and(|&v| v == DUMMY_SOURCE_NODE_FLAG)
         }) {
             panic!(
                 "The dummy source node has already been added. NodeIndex: {:?}",
@@ -101,7 +287,7 @@ impl GraphExtension for Graph<NodeData, i32> {
         }
         let source_nodes = self.get_source_nodes();
         let dummy_source_i = self.add_node(NodeData::new(
-            self.node_count() as i32,
+            self.get_next_node_id(),
             BTreeMap::from([
                 ("execution_time".to_string(), 0),
                 ("dummy".to_string(), DUMMY_SOURCE_NODE_FLAG),
@@ -118,7 +304,7 @@ impl GraphExtension for Graph<NodeData, i32> {
             self[i]
                 .params
                 .get("dummy")
-                .map_or(false, |&v| v == DUMMY_SINK_NODE_FLAG)
+                .is_some_and(|&v| v == DUMMY_SINK_NODE_FLAG)
         }) {
             panic!(
                 "The dummy sink node has already been added. NodeIndex: {:?}",
@@ -127,7 +313,7 @@ impl GraphExtension for Graph<NodeData, i32> {
         }
         let sink_nodes = self.get_sink_nodes();
         let dummy_sink_i = self.add_node(NodeData::new(
-            self.node_count() as i32,
+            self.get_next_node_id(),
             BTreeMap::from([
                 ("execution_time".to_string(), 0),
                 ("dummy".to_string(), DUMMY_SINK_NODE_FLAG),
@@ -144,7 +330,7 @@ impl GraphExtension for Graph<NodeData, i32> {
             self[i]
                 .params
                 .get("dummy")
-                .map_or(false, |&v| v == DUMMY_SOURCE_NODE_FLAG)
+                .is_some_and(|&v| v == DUMMY_SOURCE_NODE_FLAG)
         }) {
             self.remove_node(dummy_source_node);
         } else {
@@ -157,7 +343,7 @@ impl GraphExtension for Graph<NodeData, i32> {
             self[i]
                 .params
                 .get("dummy")
-                .map_or(false, |&v| v == DUMMY_SINK_NODE_FLAG)
+                .is_some_and(|&v| v == DUMMY_SINK_NODE_FLAG)
         }) {
             self.remove_node(dummy_sink_node);
         } else {
@@ -262,6 +448,44 @@ impl GraphExtension for Graph<NodeData, i32> {
         }
     }
 
+    /// Calculate the ASAP/ALAP start times and the slack of each node for the given deadline.
+    ///
+    /// Unlike `calculate_latest_start_times`, the ALAP start time is derived from
+    /// `end_to_end_deadline` instead of the critical path length, so `slack` becomes negative
+    /// when a node cannot finish before the deadline even on unlimited cores.
+    /// The results are stored in the `asap_start_time`, `alap_start_time` and `slack` params.
+    fn calculate_asap_alap_times(&mut self, end_to_end_deadline: i32) {
+        self.calculate_earliest_start_times();
+        let sorted_nodes = toposort(&*self, None).unwrap();
+        let mut alap_start_times = vec![0; self.node_count()];
+
+        for &node_i in sorted_nodes.iter().rev() {
+            let exe_time = self[node_i].params["execution_time"];
+            let alap_finish_time = self
+                .edges_directed(node_i, Outgoing)
+                .map(|edge| alap_start_times[edge.target().index()])
+                .min()
+                .unwrap_or(end_to_end_deadline);
+            alap_start_times[node_i.index()] = alap_finish_time - exe_time;
+        }
+
+        for node_i in self.node_indices() {
+            let asap_start_time = self[node_i].params["earliest_start_time"];
+            let alap_start_time = alap_start_times[node_i.index()];
+            for (key, value) in [
+                ("asap_start_time", asap_start_time),
+                ("alap_start_time", alap_start_time),
+                ("slack", alap_start_time - asap_start_time),
+            ] {
+                if self[node_i].params.contains_key(key) {
+                    self.update_param(node_i, key, value);
+                } else {
+                    self.add_param(node_i, key, value);
+                }
+            }
+        }
+    }
+
     /// Returns the critical path of a DAG
     /// Multiple critical paths are obtained using Breadth-First Search, BFS
     ///
@@ -379,34 +603,22 @@ impl GraphExtension for Graph<NodeData, i32> {
     }
 
     fn get_end_to_end_deadline(&self) -> Option<i32> {
-        self.node_indices()
-            .find_map(|i| match self[i].params.get("end_to_end_deadline") {
-                Some(end_to_end_deadline) => Some(*end_to_end_deadline),
-                None => {
-                    warn!("The end-to-end deadline does not exist.");
-                    None
-                }
-            })
+        let end_to_end_deadline = self.get_dag_params().end_to_end_deadline;
+        if end_to_end_deadline.is_none() {
+            warn!("The end-to-end deadline does not exist.");
+        }
+        end_to_end_deadline
     }
 
     fn get_head_period(&self) -> Option<i32> {
-        let source_nodes = self.get_source_nodes();
-        let periods: Vec<&i32> = source_nodes
-            .iter()
-            .filter_map(|&node_i| self[node_i].params.get("period"))
-            .collect();
-
-        if source_nodes.len() > 1 {
+        if self.get_source_nodes().len() > 1 {
             warn!("Multiple source nodes found.");
         }
-        if periods.len() > 1 {
-            warn!("Multiple periods found. The first period is used.");
-        }
-        if periods.is_empty() {
+        let period = self.get_dag_params().period;
+        if period.is_none() {
             warn!("No period found.");
-            return None;
         }
-        Some(*periods[0])
+        period
     }
 
     fn get_all_periods(&self) -> Option<HashMap<NodeIndex, i32>> {
@@ -424,23 +636,13 @@ impl GraphExtension for Graph<NodeData, i32> {
     }
 
     fn get_head_offset(&self) -> i32 {
-        let source_nodes = self.get_source_nodes();
-        let offsets: Vec<&i32> = source_nodes
-            .iter()
-            .filter_map(|&node_i| self[node_i].params.get("offset"))
-            .collect();
-        if source_nodes.len() > 1 {
+        if self.get_source_nodes().len() > 1 {
             warn!("Multiple source nodes found.");
         }
-        if offsets.len() > 1 {
-            warn!("Multiple offsets found. The first offset is used.");
-        }
-        if offsets.is_empty() {
+        self.get_dag_params().offset.unwrap_or_else(|| {
             warn!("No offset found. 0 is used");
             0
-        } else {
-            *offsets[0]
-        }
+        })
     }
 
     fn get_pre_nodes(&self, node_i: NodeIndex) -> Option<Vec<NodeIndex>> {
@@ -571,22 +773,326 @@ impl GraphExtension for Graph<NodeData, i32> {
         node_index
     }
 
+    /// An id not used by any node, for the nodes added to the DAG.
+    fn get_next_node_id(&self) -> i32 {
+        self.node_weights()
+            .map(|node| node.id + 1)
+            .max()
+            .unwrap_or(0)
+    }
+
     fn is_node_ready(&self, node_i: NodeIndex) -> bool {
         let pre_nodes_count = self.get_pre_nodes(node_i).unwrap_or_default().len() as i32;
         let pre_done_nodes_count = self[node_i].params.get("pre_done_count").unwrap_or(&0);
         pre_nodes_count == *pre_done_nodes_count
     }
+
+    /// Length of the longest path from `node_i` (inclusive) to a sink node,
+    /// i.e., the time the DAG needs at least after `node_i` starts.
+    fn get_remaining_path_length(&self, node_i: NodeIndex) -> i32 {
+        self.get_remaining_path_lengths()[node_i.index()]
+    }
+
+    /// `get_remaining_path_length` of all the nodes indexed by `NodeIndex`, computed in one pass
+    /// in the reverse topological order.
+    fn get_remaining_path_lengths(&self) -> Vec<i32> {
+        let mut remaining_path_lengths = vec![0; self.node_count()];
+        for node_i in toposort(self, None).unwrap().into_iter().rev() {
+            remaining_path_lengths[node_i.index()] = self
+                .get_suc_nodes(node_i)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|suc_i| remaining_path_lengths[suc_i.index()])
+                .max()
+                .unwrap_or(0)
+                + self[node_i].get_params_value("execution_time");
+        }
+        remaining_path_lengths
+    }
+
+    /// Returns the nodes grouped into segments in the order of their earliest start times.
+    ///
+    /// A segment is the set of nodes that start together when the DAG runs alone on enough
+    /// cores. A node that depends on a node of the same earliest start time (i.e., after a zero
+    /// execution time) is put into a later segment, so that no segment depends on itself.
+    fn get_parallel_segments(&mut self) -> Vec<Vec<NodeIndex>> {
+        self.calculate_earliest_start_times();
+        let mut ranks = vec![0; self.node_count()];
+        let mut segments: BTreeMap<(i32, usize), Vec<NodeIndex>> = BTreeMap::new();
+        for node_i in toposort(&*self, None).unwrap() {
+            let earliest_start_time = self[node_i].params["earliest_start_time"];
+            ranks[node_i.index()] = self
+                .get_pre_nodes(node_i)
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|pre_i| self[*pre_i].params["earliest_start_time"] == earliest_start_time)
+                .map(|pre_i| ranks[pre_i.index()] + 1)
+                .max()
+                .unwrap_or(0);
+            segments
+                .entry((earliest_start_time, ranks[node_i.index()]))
+                .or_default()
+                .push(node_i);
+        }
+        segments
+            .into_values()
+            .map(|mut segment| {
+                segment.sort();
+                segment
+            })
+            .collect()
+    }
+
+    /// Set the intermediate deadline of each node by the decomposition into segments
+    /// (see `decomposition`) as `int_scaled_node_relative_deadline`. The DAG needs a period.
+    fn decompose(&mut self) {
+        crate::decomposition::decompose(self);
+    }
+
+    /// Set the `priority` of each node for `FixedPriorityScheduler`: the nodes on the critical
+    /// path get the top priority 0, and the other nodes follow in the descending order of their
+    /// longest path to a sink (see `get_remaining_path_length`), ties broken by the node id.
+    fn assign_critical_path_first_priorities(&mut self) {
+        let critical_path = self.get_critical_path();
+        let remaining_path_lengths = self.get_remaining_path_lengths();
+        let mut non_critical_nodes = self
+            .get_non_critical_nodes(&critical_path)
+            .unwrap_or_default();
+        non_critical_nodes.sort_by_key(|node_i| {
+            (
+                std::cmp::Reverse(remaining_path_lengths[node_i.index()]),
+                self[*node_i].get_id(),
+            )
+        });
+        // Overwrite the priorities assigned before, if any.
+        for node_i in critical_path {
+            self[node_i].params.insert("priority".to_string(), 0);
+        }
+        for (rank, node_i) in non_critical_nodes.into_iter().enumerate() {
+            self[node_i]
+                .params
+                .insert("priority".to_string(), rank as i32 + 1);
+        }
+    }
+
+    /// Returns the next node of the same `lock_group`, which must run right after `node_i`.
+    fn get_lock_group_successor(&self, node_i: NodeIndex) -> Option<NodeIndex> {
+        let lock_group = self[node_i].params.get("lock_group")?;
+        self.get_suc_nodes(node_i)
+            .unwrap_or_default()
+            .into_iter()
+            .find(|&suc_i| self[suc_i].params.get("lock_group") == Some(lock_group))
+    }
+
+    /// Returns the sink nodes with a feedback edge to the next job.
+    ///
+    /// A sink node with `feedback` feeds the sources of the next job, i.e., job k+1 may be released
+    /// only after the sink of job k completes. Since a job is released only after the previous job
+    /// completes, the edge needs no target. It is kept as a param because an actual edge would make
+    /// the graph cyclic.
+    fn get_feedback_sinks(&self) -> Vec<NodeIndex> {
+        self.node_indices()
+            .filter(|&node_i| self[node_i].params.contains_key("feedback"))
+            .inspect(|&node_i| {
+                if self.get_suc_nodes(node_i).is_some() {
+                    panic!(
+                        "The feedback edge must start from a sink node, but node {} is not.",
+                        self[node_i].id
+                    );
+                }
+            })
+            .collect()
+    }
+
+    /// Detect duplicate edges, self-loops and disconnected components.
+    ///
+    /// These are usually generator bugs. Duplicate edges are counted twice in the number of
+    /// predecessors, and disconnected components silently change the volume and critical path.
+    fn lint_structure(&self) -> Vec<StructureIssue> {
+        let mut issues = Vec::new();
+        let mut edge_counts: BTreeMap<(NodeIndex, NodeIndex), usize> = BTreeMap::new();
+        for edge in self.edge_references() {
+            if edge.source() == edge.target() {
+                issues.push(StructureIssue::SelfLoop {
+                    node: edge.source(),
+                });
+            } else {
+                *edge_counts
+                    .entry((edge.source(), edge.target()))
+                    .or_default() += 1;
+            }
+        }
+        for ((source, target), count) in edge_counts {
+            if count > 1 {
+                issues.push(StructureIssue::DuplicateEdge {
+                    source,
+                    target,
+                    count,
+                });
+            }
+        }
+
+        let components = connected_components(self);
+        if components > 1 {
+            issues.push(StructureIssue::DisconnectedComponents { components });
+        }
+
+        let mut id_counts: BTreeMap<i32, usize> = BTreeMap::new();
+        for node in self.node_weights() {
+            *id_counts.entry(node.id).or_default() += 1;
+        }
+        for (id, count) in id_counts {
+            if count > 1 {
+                issues.push(StructureIssue::DuplicateNodeId { id, count });
+            }
+        }
+        issues
+    }
+
+    /// Keep only the first of the edges connecting the same pair of nodes.
+    ///
+    /// # Returns
+    ///
+    /// * The number of removed edges.
+    fn remove_duplicate_edges(&mut self) -> usize {
+        let mut seen_pairs = Vec::new();
+        let mut duplicate_edges = Vec::new();
+        for edge in self.edge_references() {
+            let pair = (edge.source(), edge.target());
+            if seen_pairs.contains(&pair) {
+                duplicate_edges.push(edge.id());
+            } else {
+                seen_pairs.push(pair);
+            }
+        }
+        // Remove from the back because removing an edge moves the last edge into its index.
+        duplicate_edges.sort();
+        for &edge_i in duplicate_edges.iter().rev() {
+            self.remove_edge(edge_i);
+        }
+        duplicate_edges.len()
+    }
+
+    /// Returns `Triggered` if the source node has `triggered_by_dag` (and `triggered_by_node`),
+    /// otherwise `Periodic`. A triggered DAG needs no period.
+    fn get_release_source(&self) -> ReleaseSource {
+        let source_nodes = self.get_source_nodes();
+        let Some(&source_i) = source_nodes.first() else {
+            return ReleaseSource::Periodic;
+        };
+        match self[source_i].params.get("triggered_by_dag") {
+            Some(&dag_id) => ReleaseSource::Triggered {
+                dag_id: dag_id as usize,
+                node_id: self[source_i].params.get("triggered_by_node").copied(),
+            },
+            None => ReleaseSource::Periodic,
+        }
+    }
+
+    /// Nodes carrying the DAG-level param `key` and its values.
+    /// `period` and `offset` are DAG-level only on the source nodes, because the other nodes
+    /// may have their own periods (e.g., timer-driven nodes of multi-rate DAGs).
+    fn get_dag_param_values(&self, key: &str) -> Vec<(NodeIndex, i32)> {
+        let candidates = match key {
+            "period" | "offset" => self.get_source_nodes(),
+            _ => self.node_indices().collect(),
+        };
+        candidates
+            .into_iter()
+            .filter_map(|node_i| self[node_i].params.get(key).map(|&value| (node_i, value)))
+            .collect()
+    }
+
+    /// DAG-level params carried by several nodes with different values.
+    fn get_param_conflicts(&self) -> Vec<ParamConflict> {
+        ["period", "end_to_end_deadline", "offset"]
+            .into_iter()
+            .filter_map(|key| {
+                let values = self.get_dag_param_values(key);
+                values
+                    .iter()
+                    .any(|&(_, value)| value != values[0].1)
+                    .then(|| ParamConflict {
+                        key: key.to_string(),
+                        values,
+                    })
+            })
+            .collect()
+    }
+
+    /// Consolidate the DAG-level params. Conflicts are resolved by the value of the first node.
+    ///
+    /// This is called on every release, so conflicts are not reported here but once when the
+    /// DAG is loaded (see `validate_dag_structure`).
+    fn get_dag_params(&self) -> DAGParams {
+        let get_value = |key: &str| {
+            self.get_dag_param_values(key)
+                .first()
+                .map(|&(_, value)| value)
+        };
+        DAGParams {
+            period: get_value("period"),
+            end_to_end_deadline: get_value("end_to_end_deadline"),
+            offset: get_value("offset"),
+        }
+    }
+
+    /// Volume when each node runs at its speed in `speed_model`.
+    fn get_volume_with_speed(&self, speed_model: &SpeedModel) -> i32 {
+        self.node_weights()
+            .map(|node_data| speed_model.get_execution_time(node_data))
+            .sum()
+    }
+
+    /// Length of the critical path when each node runs at its speed in `speed_model`.
+    /// The critical path itself may differ from the one at speed 1.
+    fn get_critical_path_length_with_speed(&self, speed_model: &SpeedModel) -> i32 {
+        let mut dag = self.clone();
+        for node_i in dag.node_indices() {
+            let execution_time = speed_model.get_execution_time(&dag[node_i]);
+            dag.update_param(node_i, "execution_time", execution_time);
+        }
+        let critical_path = dag.get_critical_path();
+        dag.get_total_wcet_from_nodes(&critical_path)
+    }
+
+    /// Returns `max_parallelism` of the source node, the number of nodes of the DAG
+    /// that may run at the same time (e.g., the size of its thread pool).
+    fn get_max_parallelism(&self) -> Option<usize> {
+        self.get_source_nodes()
+            .iter()
+            .find_map(|&source_i| self[source_i].params.get("max_parallelism"))
+            .map(|&max_parallelism| max_parallelism as usize)
+    }
+
+    /// Check that the nodes of each `lock_group` form a chain of direct successors.
+    ///
+    /// Locked nodes are executed back-to-back on the same core, so the group must have a single
+    /// entry node and each member must be followed by exactly one member.
+    fn validate_lock_groups(&self) {
+        let mut lock_groups: BTreeMap<i32, Vec<NodeIndex>> = BTreeMap::new();
+        for node_i in toposort(self, None).unwrap() {
+            if let Some(&lock_group) = self[node_i].params.get("lock_group") {
+                lock_groups.entry(lock_group).or_default().push(node_i);
+            }
+        }
+        for (lock_group, members) in lock_groups {
+            for pair in members.windows(2) {
+                if self.find_edge(pair[0], pair[1]).is_none() {
+                    panic!(
+                        "Lock group {} is not a chain. Node {} is not followed by node {}.",
+                        lock_group, self[pair[0]].id, self[pair[1]].id
+                    );
+                }
+            }
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    fn create_node(id: i32, key: &str, value: i32) -> NodeData {
-        let mut params = BTreeMap::new();
-        params.insert(key.to_string(), value);
-        NodeData { id, params }
-    }
+    use crate::test_util::create_node;
 
     #[test]
     fn test_add_param_normal() {
@@ -736,6 +1242,50 @@ mod tests {
         assert_eq!(critical_path, &[n0, n2, n4]);
     }
 
+    #[test]
+    fn test_get_remaining_path_length_normal() {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 4));
+        let n1 = dag.add_node(create_node(1, "execution_time", 7));
+        let n2 = dag.add_node(create_node(2, "execution_time", 55));
+        let n3 = dag.add_node(create_node(3, "execution_time", 36));
+        let n4 = dag.add_node(create_node(4, "execution_time", 54));
+        dag.add_edge(n0, n1, 1);
+        dag.add_edge(n0, n2, 1);
+        dag.add_edge(n1, n3, 1);
+        dag.add_edge(n2, n4, 1);
+
+        assert_eq!(dag.get_remaining_path_length(n0), 113);
+        assert_eq!(dag.get_remaining_path_length(n1), 43);
+        assert_eq!(dag.get_remaining_path_length(n4), 54);
+        assert_eq!(dag.get_remaining_path_lengths(), [113, 43, 109, 36, 54]);
+    }
+
+    #[test]
+    fn test_assign_critical_path_first_priorities_normal() {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 4));
+        let n1 = dag.add_node(create_node(1, "execution_time", 7));
+        let n2 = dag.add_node(create_node(2, "execution_time", 55));
+        let n3 = dag.add_node(create_node(3, "execution_time", 36));
+        let n4 = dag.add_node(create_node(4, "execution_time", 54));
+        let n5 = dag.add_node(create_node(5, "execution_time", 50));
+        dag.add_param(n5, "priority", 0);
+        dag.add_edge(n0, n1, 1);
+        dag.add_edge(n0, n2, 1);
+        dag.add_edge(n0, n5, 1);
+        dag.add_edge(n1, n3, 1);
+        dag.add_edge(n2, n4, 1);
+
+        dag.assign_critical_path_first_priorities();
+        // The previous priority of n5 is overwritten, and n5 (50) goes before n1 (43) and n3 (36).
+        let priorities: Vec<i32> = dag
+            .node_indices()
+            .map(|node_i| dag[node_i].params["priority"])
+            .collect();
+        assert_eq!(priorities, vec![0, 2, 0, 3, 0, 1]);
+    }
+
     #[test]
     fn test_get_non_critical_nodes_when_critical_path_single() {
         let mut dag = Graph::<NodeData, i32>::new();
@@ -921,6 +1471,28 @@ mod tests {
         assert_eq!(dag.get_volume(), 14);
     }
 
+    #[test]
+    fn test_get_metrics_with_speed() {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 3));
+        let n1 = dag.add_node(create_node(1, "execution_time", 6));
+        let n2 = dag.add_node(create_node(2, "execution_time", 5));
+        dag.add_edge(n0, n1, 1);
+        dag.add_edge(n0, n2, 1);
+
+        assert_eq!(dag.get_volume_with_speed(&SpeedModel::Uniform), 14);
+        assert_eq!(
+            dag.get_critical_path_length_with_speed(&SpeedModel::Uniform),
+            9
+        );
+        // ceil(3 / 2) + ceil(6 / 2) + ceil(5 / 2)
+        assert_eq!(dag.get_volume_with_speed(&SpeedModel::Fixed(2.0)), 8);
+        // n1 on a fast core makes n0 -> n2 critical.
+        let speed_model = SpeedModel::Assignment(HashMap::from([(1, 3.0)]));
+        assert_eq!(dag.get_volume_with_speed(&speed_model), 10);
+        assert_eq!(dag.get_critical_path_length_with_speed(&speed_model), 8);
+    }
+
     #[test]
     #[should_panic]
     fn test_get_volume_node_no_includes_execution_time() {
@@ -1051,6 +1623,46 @@ mod tests {
         assert_eq!(dag.get_head_offset(), 3);
     }
 
+    #[test]
+    fn test_get_param_conflicts_normal() {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "period", 10));
+        let n1 = dag.add_node(create_node(1, "period", 20));
+        let n2 = dag.add_node(create_node(2, "end_to_end_deadline", 10));
+        let n3 = dag.add_node(create_node(3, "end_to_end_deadline", 8));
+        dag.add_edge(n0, n2, 1);
+        dag.add_edge(n1, n3, 1);
+        dag.add_edge(n2, n3, 1);
+
+        assert_eq!(
+            dag.get_param_conflicts(),
+            vec![
+                ParamConflict {
+                    key: "period".to_string(),
+                    values: vec![(n0, 10), (n1, 20)],
+                },
+                ParamConflict {
+                    key: "end_to_end_deadline".to_string(),
+                    values: vec![(n2, 10), (n3, 8)],
+                },
+            ]
+        );
+        assert_eq!(
+            dag.get_dag_params(),
+            DAGParams {
+                period: Some(10),
+                end_to_end_deadline: Some(10),
+                offset: None,
+            }
+        );
+
+        // Periods of non-source nodes are not DAG-level.
+        dag.update_param(n1, "period", 10);
+        dag.add_param(n2, "period", 5);
+        dag.update_param(n3, "end_to_end_deadline", 10);
+        assert!(dag.get_param_conflicts().is_empty());
+    }
+
     #[test]
     fn test_get_offset_no_exist() {
         let dag = Graph::<NodeData, i32>::new();
@@ -1293,6 +1905,58 @@ mod tests {
         dag.add_node_with_id_consistency(create_node(0, "execution_time", 3));
     }
 
+    #[test]
+    fn test_node_index_map_after_removal() {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let mut node_index_map = NodeIndexMap::default();
+        let n0 = node_index_map.add_node(&mut dag, create_node(0, "execution_time", 3));
+        let n1 = node_index_map.add_node(&mut dag, create_node(1, "execution_time", 3));
+        let n2 = node_index_map.add_node(&mut dag, create_node(2, "execution_time", 3));
+        dag.add_edge(n0, n1, 1);
+        dag.add_edge(n1, n2, 1);
+        // Node 2 moves into the index of node 0.
+        node_index_map.remove_node(&mut dag, n0);
+
+        assert_eq!(node_index_map.get_node_index(1), n1);
+        assert_eq!(node_index_map.get_node_index(2), NodeIndex::new(0));
+        assert_eq!(node_index_map, NodeIndexMap::new(&dag));
+        assert_eq!(dag.get_next_node_id(), 3);
+        let dummy_source_i = dag.add_dummy_source_node();
+        assert_eq!(dag[dummy_source_i].id, 3);
+    }
+
+    #[test]
+    fn test_node_index_map_non_contiguous_ids() {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(10, "execution_time", 3));
+        let n1 = dag.add_node(create_node(30, "execution_time", 3));
+
+        let node_index_map = NodeIndexMap::new(&dag);
+        assert_eq!(node_index_map.get_node_index(10), n0);
+        assert_eq!(node_index_map.get(30), Some(n1));
+        assert_eq!(
+            node_index_map.iter().collect::<Vec<_>>(),
+            vec![(10, n0), (30, n1)]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_node_index_map_no_exist_node() {
+        let mut dag = Graph::<NodeData, i32>::new();
+        dag.add_node(create_node(10, "execution_time", 3));
+        NodeIndexMap::new(&dag).get_node_index(0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_node_index_map_id_duplication() {
+        let mut dag = Graph::<NodeData, i32>::new();
+        dag.add_node(create_node(0, "execution_time", 3));
+        dag.add_node(create_node(0, "execution_time", 3));
+        NodeIndexMap::new(&dag);
+    }
+
     #[test]
     fn test_is_node_ready_normal() {
         let mut dag = Graph::<NodeData, i32>::new();
@@ -1305,4 +1969,203 @@ mod tests {
         dag.add_param(n1, "pre_done_count", 1);
         assert!(dag.is_node_ready(n1));
     }
+
+    #[test]
+    fn test_calculate_asap_alap_times_normal() {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 4));
+        let n1 = dag.add_node(create_node(1, "execution_time", 7));
+        let n2 = dag.add_node(create_node(2, "execution_time", 55));
+        let n3 = dag.add_node(create_node(3, "execution_time", 36));
+        let n4 = dag.add_node(create_node(4, "execution_time", 54));
+        dag.add_edge(n0, n1, 1);
+        dag.add_edge(n0, n2, 1);
+        dag.add_edge(n1, n3, 1);
+        dag.add_edge(n2, n4, 1);
+
+        dag.calculate_asap_alap_times(120);
+        assert_eq!(dag[n0].params["asap_start_time"], 0);
+        assert_eq!(dag[n0].params["alap_start_time"], 7);
+        assert_eq!(dag[n0].params["slack"], 7);
+        assert_eq!(dag[n1].params["asap_start_time"], 4);
+        assert_eq!(dag[n1].params["alap_start_time"], 77);
+        assert_eq!(dag[n3].params["alap_start_time"], 84);
+        assert_eq!(dag[n3].params["slack"], 73);
+        assert_eq!(dag[n4].params["asap_start_time"], 59);
+        assert_eq!(dag[n4].params["alap_start_time"], 66);
+        assert_eq!(dag[n4].params["slack"], 7);
+    }
+
+    #[test]
+    fn test_calculate_asap_alap_times_infeasible_deadline() {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 4));
+        let n1 = dag.add_node(create_node(1, "execution_time", 7));
+        dag.add_edge(n0, n1, 1);
+
+        dag.calculate_asap_alap_times(10);
+        assert_eq!(dag[n0].params["slack"], -1);
+        assert_eq!(dag[n1].params["slack"], -1);
+
+        // Recalculation overwrites the previous results.
+        dag.calculate_asap_alap_times(20);
+        assert_eq!(dag[n0].params["slack"], 9);
+        assert_eq!(dag[n1].params["alap_start_time"], 13);
+    }
+
+    #[test]
+    fn test_get_lock_group_successor_normal() {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "lock_group", 0));
+        let n1 = dag.add_node(create_node(1, "execution_time", 1));
+        let n2 = dag.add_node(create_node(2, "lock_group", 0));
+        dag.add_edge(n0, n1, 1);
+        dag.add_edge(n0, n2, 1);
+
+        assert_eq!(dag.get_lock_group_successor(n0), Some(n2));
+        assert_eq!(dag.get_lock_group_successor(n1), None);
+        assert_eq!(dag.get_lock_group_successor(n2), None);
+        dag.validate_lock_groups();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_validate_lock_groups_not_chain() {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "lock_group", 0));
+        let n1 = dag.add_node(create_node(1, "lock_group", 0));
+        let n2 = dag.add_node(create_node(2, "lock_group", 0));
+        dag.add_edge(n0, n1, 1);
+        dag.add_edge(n0, n2, 1);
+
+        dag.validate_lock_groups();
+    }
+
+    #[test]
+    fn test_get_feedback_sinks_normal() {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 1));
+        let n1 = dag.add_node(create_node(1, "execution_time", 1));
+        let n2 = dag.add_node(create_node(2, "feedback", 1));
+        dag.add_edge(n0, n1, 1);
+        dag.add_edge(n1, n2, 1);
+
+        assert_eq!(dag.get_feedback_sinks(), vec![n2]);
+    }
+
+    #[test]
+    fn test_lint_structure_normal() {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 1));
+        let n1 = dag.add_node(create_node(1, "execution_time", 1));
+        let n2 = dag.add_node(create_node(2, "execution_time", 1));
+        dag.add_edge(n0, n1, 1);
+        assert_eq!(
+            dag.lint_structure(),
+            vec![StructureIssue::DisconnectedComponents { components: 2 }]
+        );
+
+        dag.add_edge(n1, n2, 1);
+        dag.add_edge(n0, n1, 2);
+        dag.add_edge(n2, n2, 1);
+        assert_eq!(
+            dag.lint_structure(),
+            vec![
+                StructureIssue::SelfLoop { node: n2 },
+                StructureIssue::DuplicateEdge {
+                    source: n0,
+                    target: n1,
+                    count: 2
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_remove_duplicate_edges_normal() {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 1));
+        let n1 = dag.add_node(create_node(1, "execution_time", 1));
+        let n2 = dag.add_node(create_node(2, "execution_time", 1));
+        dag.add_edge(n0, n1, 1);
+        dag.add_edge(n0, n1, 2);
+        dag.add_edge(n1, n2, 3);
+        dag.add_edge(n0, n1, 4);
+
+        assert_eq!(dag.remove_duplicate_edges(), 2);
+        assert_eq!(dag.edge_count(), 2);
+        assert_eq!(dag[dag.find_edge(n0, n1).unwrap()], 1);
+        assert_eq!(dag[dag.find_edge(n1, n2).unwrap()], 3);
+        assert!(dag.lint_structure().is_empty());
+    }
+
+    #[test]
+    fn test_get_release_source_normal() {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 1));
+        let n1 = dag.add_node(create_node(1, "execution_time", 1));
+        dag.add_edge(n0, n1, 1);
+        assert_eq!(dag.get_release_source(), ReleaseSource::Periodic);
+
+        dag.add_param(n0, "triggered_by_dag", 2);
+        assert_eq!(
+            dag.get_release_source(),
+            ReleaseSource::Triggered {
+                dag_id: 2,
+                node_id: None
+            }
+        );
+        dag.add_param(n0, "triggered_by_node", 3);
+        assert_eq!(
+            dag.get_release_source(),
+            ReleaseSource::Triggered {
+                dag_id: 2,
+                node_id: Some(3)
+            }
+        );
+    }
+
+    #[test]
+    fn test_get_max_parallelism_normal() {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 1));
+        let n1 = dag.add_node(create_node(1, "execution_time", 1));
+        dag.add_edge(n0, n1, 1);
+        assert_eq!(dag.get_max_parallelism(), None);
+
+        dag.add_param(n0, "max_parallelism", 2);
+        assert_eq!(dag.get_max_parallelism(), Some(2));
+    }
+
+    #[test]
+    fn test_get_parallel_segments_normal() {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 1));
+        let n1 = dag.add_node(create_node(1, "execution_time", 2));
+        let n2 = dag.add_node(create_node(2, "execution_time", 2));
+        let n3 = dag.add_node(create_node(3, "execution_time", 0));
+        let n4 = dag.add_node(create_node(4, "execution_time", 1));
+        dag.add_edge(n0, n1, 1);
+        dag.add_edge(n0, n2, 1);
+        dag.add_edge(n1, n3, 1);
+        dag.add_edge(n3, n4, 1);
+        dag.add_edge(n2, n4, 1);
+
+        // n4 starts at 3 as n3 does, but after n3.
+        assert_eq!(
+            dag.get_parallel_segments(),
+            vec![vec![n0], vec![n1, n2], vec![n3], vec![n4]]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_get_feedback_sinks_not_sink() {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "feedback", 1));
+        let n1 = dag.add_node(create_node(1, "execution_time", 1));
+        dag.add_edge(n0, n1, 1);
+
+        dag.get_feedback_sinks();
+    }
 }
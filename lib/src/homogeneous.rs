@@ -1,24 +1,68 @@
 //! Homogeneous processor module. This module uses Core struct.
-use crate::{core::Core, core::ProcessResult, graph_extension::NodeData, processor::ProcessorBase};
+use crate::{
+    core::Core,
+    core::ProcessResult,
+    dvfs::{CoreFrequencies, DvfsModel},
+    graph_extension::NodeData,
+    log::{EnergyLog, PowerLog},
+    power_down::{CorePowerStates, PowerDownModel},
+    processor::ProcessorBase,
+};
 
 #[derive(Clone, Debug)]
 pub struct HomogeneousProcessor {
     pub cores: Vec<Core>,
+    /// `None` if the cores do not power down when idle.
+    power_states: Option<CorePowerStates>,
+    /// `None` if the cores do not scale their frequencies.
+    frequencies: Option<CoreFrequencies>,
 }
 
 impl ProcessorBase for HomogeneousProcessor {
     fn new(num_cores: usize) -> Self {
         Self {
             cores: vec![Core::default(); num_cores],
+            power_states: None,
+            frequencies: None,
         }
     }
 
+    /// A node runs for its `execution_time` scaled by the frequency of the core. A preempted node
+    /// keeps the remaining time at the frequency of the core it ran on.
     fn allocate_specific_core(&mut self, core_id: usize, node_data: &NodeData) -> bool {
-        self.cores[core_id].allocate(node_data)
+        let is_allocated = self.cores[core_id].allocate(node_data);
+        if let (true, Some(power_states)) = (is_allocated, &mut self.power_states) {
+            power_states.start(core_id);
+        }
+        if let (true, Some(frequencies)) = (is_allocated, &self.frequencies) {
+            if !node_data.params.contains_key("is_preempted") {
+                let core = &mut self.cores[core_id];
+                core.remain_proc_time =
+                    frequencies.get_execution_time(core_id, core.remain_proc_time);
+            }
+        }
+        is_allocated
     }
 
     fn process(&mut self) -> Vec<ProcessResult> {
-        self.cores.iter_mut().map(|core| core.process()).collect()
+        self.process_cores(1, &[])
+    }
+
+    fn process_except(&mut self, stalled_cores: &[bool]) -> Vec<ProcessResult> {
+        self.process_cores(1, stalled_cores)
+    }
+
+    /// Advance directly to the earliest completion instead of processing each unit.
+    fn process_for(&mut self, max_time: i32) -> (i32, Vec<ProcessResult>) {
+        let elapsed_time = self
+            .cores
+            .iter()
+            .enumerate()
+            .filter(|(_, core)| !core.get_is_idle())
+            .map(|(core_i, core)| core.remain_proc_time + self.get_wake_up_time(core_i))
+            .fold(max_time, i32::min)
+            .max(1);
+        (elapsed_time, self.process_cores(elapsed_time, &[]))
     }
 
     fn get_number_of_cores(&self) -> usize {
@@ -38,8 +82,31 @@ impl ProcessorBase for HomogeneousProcessor {
         None
     }
 
+    fn get_idle_core_indices(&self) -> Vec<usize> {
+        self.cores
+            .iter()
+            .enumerate()
+            .filter(|(_, core)| core.get_is_idle())
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    fn get_processing_node(&self, core_id: usize) -> Option<&NodeData> {
+        self.cores[core_id].get_processing_node().as_ref()
+    }
+
     fn preempt(&mut self, core_id: usize) -> Option<NodeData> {
-        self.cores[core_id].preempt()
+        let node_data = self.cores[core_id].preempt();
+        if let (Some(_), Some(power_states)) = (&node_data, &mut self.power_states) {
+            power_states.stop(core_id);
+        }
+        node_data
+    }
+
+    fn set_processing_node_param(&mut self, core_id: usize, key: &str, value: i32) {
+        if let Some(node_data) = self.cores[core_id].processing_node.as_mut() {
+            node_data.params.insert(key.to_string(), value);
+        }
     }
 
     fn get_max_value_and_index(&self, key: &str) -> Option<(i32, usize)> {
@@ -53,9 +120,159 @@ impl ProcessorBase for HomogeneousProcessor {
             })
             .max_by_key(|&(value, _)| value)
     }
+
+    /// Return the core running the lowest-priority node (largest `key`) that a node with `value`
+    /// may preempt.
+    ///
+    /// A running node with `preemption_threshold` can only be preempted by a node whose `value` is
+    /// smaller than its own value minus the threshold. Without the param, the threshold is 0
+    /// (fully preemptive). A large threshold makes the node non-preemptive.
+    ///
+    /// A node that has run for less than `non_preemptive_region` since it started or resumed
+    /// cannot be preempted either.
+    fn get_preemptable_core_index_after(
+        &self,
+        key: &str,
+        value: i32,
+        non_preemptive_region: i32,
+    ) -> Option<usize> {
+        self.get_preemptable_core_index_among(
+            0..self.cores.len(),
+            key,
+            value,
+            non_preemptive_region,
+        )
+    }
+
+    fn get_number_of_running_nodes(&self, dag_id: i32) -> usize {
+        self.cores
+            .iter()
+            .filter_map(|core| core.get_processing_node().as_ref())
+            .filter(|node_data| node_data.params.get("dag_id") == Some(&dag_id))
+            .count()
+    }
+
+    fn get_run_time(&self, core_id: usize) -> i32 {
+        let core = &self.cores[core_id];
+        if core.get_is_idle() {
+            0
+        } else {
+            core.run_time
+        }
+    }
+
+    fn is_core_asleep(&self, core_id: usize) -> bool {
+        self.power_states
+            .as_ref()
+            .is_some_and(|power_states| power_states.is_asleep(core_id))
+    }
+
+    fn get_power_log(&self) -> Option<PowerLog> {
+        self.power_states
+            .as_ref()
+            .map(|power_states| power_states.get_power_log())
+    }
+
+    fn get_energy_log(&self) -> Option<EnergyLog> {
+        self.frequencies
+            .as_ref()
+            .map(|frequencies| frequencies.get_energy_log())
+    }
 }
 
 impl HomogeneousProcessor {
+    /// Power down the cores idle for the threshold of `power_down_model`.
+    pub fn set_power_down_model(&mut self, power_down_model: PowerDownModel) {
+        self.power_states = Some(CorePowerStates::new(power_down_model, self.cores.len()));
+    }
+
+    /// Scale the frequencies of the cores by `dvfs_model`, starting at its highest level.
+    pub fn set_dvfs_model(&mut self, dvfs_model: DvfsModel) {
+        self.frequencies = Some(CoreFrequencies::new(dvfs_model, self.cores.len()));
+    }
+
+    pub fn get_dvfs_model(&self) -> Option<&DvfsModel> {
+        self.frequencies
+            .as_ref()
+            .map(|frequencies| frequencies.get_model())
+    }
+
+    /// Set the frequency of the core to level `level_i` of the DVFS model, for the nodes
+    /// allocated from now on.
+    pub fn set_frequency_level(&mut self, core_id: usize, level_i: usize) {
+        self.frequencies
+            .as_mut()
+            .unwrap_or_else(|| panic!("The processor has no DVFS model."))
+            .set_level_index(core_id, level_i);
+    }
+
+    fn get_wake_up_time(&self, core_id: usize) -> i32 {
+        self.power_states
+            .as_ref()
+            .map_or(0, |power_states| power_states.get_wake_up_time(core_id))
+    }
+
+    /// Process each core not in `stalled_cores` for `time` units, of which waking up the core
+    /// comes first. `time` must not exceed the remaining processing and wake-up time.
+    fn process_cores(&mut self, time: i32, stalled_cores: &[bool]) -> Vec<ProcessResult> {
+        let busy_cores: Vec<bool> = self.cores.iter().map(|core| !core.get_is_idle()).collect();
+        let mut process_result = Vec::with_capacity(self.cores.len());
+        for (core_i, core) in self.cores.iter_mut().enumerate() {
+            let is_stalled = stalled_cores.get(core_i).copied().unwrap_or(false);
+            let wake_up_time = match &mut self.power_states {
+                Some(power_states) if !is_stalled => {
+                    power_states.consume_wake_up_time(core_i, time)
+                }
+                _ => 0,
+            };
+            process_result.push(
+                match (is_stalled || wake_up_time == time, core.get_is_idle()) {
+                    (false, _) => core.process_for(time - wake_up_time),
+                    (true, true) => ProcessResult::Idle,
+                    (true, false) => ProcessResult::Continue,
+                },
+            );
+        }
+        if let Some(frequencies) = &mut self.frequencies {
+            frequencies.advance(time, &busy_cores);
+        }
+        if let Some(power_states) = &mut self.power_states {
+            power_states.advance(time, &busy_cores);
+            for (core_i, result) in process_result.iter().enumerate() {
+                if let ProcessResult::Done(_) = result {
+                    power_states.stop(core_i);
+                }
+            }
+        }
+        process_result
+    }
+
+    /// `get_preemptable_core_index_after` among the cores in `core_ids`, e.g., of a cluster.
+    pub fn get_preemptable_core_index_among(
+        &self,
+        core_ids: impl IntoIterator<Item = usize>,
+        key: &str,
+        value: i32,
+        non_preemptive_region: i32,
+    ) -> Option<usize> {
+        core_ids
+            .into_iter()
+            .map(|index| (index, &self.cores[index]))
+            .filter(|(_, core)| core.run_time >= non_preemptive_region)
+            .filter_map(|(index, core)| {
+                let node_data = core.get_processing_node().as_ref()?;
+                let running_value = *node_data.params.get(key)?;
+                let threshold = node_data
+                    .params
+                    .get("preemption_threshold")
+                    .copied()
+                    .unwrap_or(0);
+                (value < running_value - threshold).then_some((running_value, index))
+            })
+            .max_by_key(|&(running_value, _)| running_value)
+            .map(|(_, index)| index)
+    }
+
     pub fn allocate_any_idle_core(&mut self, node_data: &NodeData) -> bool {
         if let Some(idle_core_i) = self.get_idle_core_index() {
             self.cores[idle_core_i].allocate(node_data)
@@ -68,14 +285,8 @@ impl HomogeneousProcessor {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{core::ProcessResult, graph_extension::NodeData, processor::ProcessorBase};
-    use std::collections::BTreeMap;
-
-    fn create_node(id: i32, key: &str, value: i32) -> NodeData {
-        let mut params = BTreeMap::new();
-        params.insert(key.to_string(), value);
-        NodeData { id, params }
-    }
+    use crate::test_util::create_node;
+    use crate::{core::ProcessResult, processor::ProcessorBase};
 
     #[test]
     fn test_processor_new() {
@@ -136,6 +347,67 @@ mod tests {
         )));
     }
 
+    #[test]
+    fn test_processor_power_down_normal() {
+        let mut homogeneous_processor = HomogeneousProcessor::new(1);
+        homogeneous_processor.set_power_down_model(PowerDownModel::new(1, 2, 0.5, 0.1));
+        homogeneous_processor.process();
+        homogeneous_processor.process();
+        assert!(homogeneous_processor.is_core_asleep(0));
+
+        let node = create_node(0, "execution_time", 2);
+        homogeneous_processor.allocate_specific_core(0, &node);
+        // The node runs after the core wakes up.
+        assert_eq!(
+            homogeneous_processor.process_for(i32::MAX),
+            (4, vec![ProcessResult::Done(node)])
+        );
+        assert!(!homogeneous_processor.is_core_asleep(0));
+        assert_eq!(homogeneous_processor.get_power_log().unwrap().wake_ups, 1);
+    }
+
+    #[test]
+    fn test_processor_dvfs_normal() {
+        let mut homogeneous_processor = HomogeneousProcessor::new(1);
+        homogeneous_processor.set_dvfs_model(DvfsModel::new_cubic(&[0.5, 1.0], 0.0));
+        homogeneous_processor.set_frequency_level(0, 0);
+
+        // The node takes 4 at half the frequency, and resumes with its remaining time.
+        homogeneous_processor.allocate_specific_core(0, &create_node(0, "execution_time", 2));
+        homogeneous_processor.process();
+        let preempted_node = homogeneous_processor.preempt(0).unwrap();
+        homogeneous_processor.allocate_specific_core(0, &preempted_node);
+        assert_eq!(homogeneous_processor.process_for(i32::MAX).0, 3);
+        let energy_log = homogeneous_processor.get_energy_log().unwrap();
+        assert_eq!(energy_log.core_energy_logs[0].busy_time, 4);
+        assert_eq!(energy_log.total_energy, 0.5);
+    }
+
+    #[test]
+    fn test_processor_allocate_gang_normal() {
+        let mut homogeneous_processor = HomogeneousProcessor::new(3);
+        homogeneous_processor.allocate_specific_core(1, &create_node(0, "execution_time", 2));
+
+        let gang = vec![
+            create_node(1, "execution_time", 2),
+            create_node(2, "execution_time", 2),
+        ];
+        assert_eq!(homogeneous_processor.allocate_gang(&gang), Some(vec![0, 2]));
+        assert_eq!(homogeneous_processor.get_idle_core_num(), 0);
+
+        homogeneous_processor.process();
+        homogeneous_processor.process();
+        homogeneous_processor.allocate_specific_core(0, &create_node(3, "execution_time", 2));
+        // No node is allocated if the gang does not fit.
+        let gang = vec![
+            create_node(4, "execution_time", 2),
+            create_node(5, "execution_time", 2),
+            create_node(6, "execution_time", 2),
+        ];
+        assert_eq!(homogeneous_processor.allocate_gang(&gang), None);
+        assert_eq!(homogeneous_processor.get_idle_core_num(), 2);
+    }
+
     #[test]
     fn test_processor_process_normal() {
         let mut homogeneous_processor = HomogeneousProcessor::new(2);
@@ -172,6 +444,47 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_processor_process_for_normal() {
+        let mut homogeneous_processor = HomogeneousProcessor::new(3);
+        homogeneous_processor.allocate_specific_core(0, &create_node(0, "execution_time", 5));
+        homogeneous_processor.allocate_specific_core(1, &create_node(1, "execution_time", 3));
+
+        assert_eq!(
+            homogeneous_processor.process_for(10),
+            (
+                3,
+                vec![
+                    ProcessResult::Continue,
+                    ProcessResult::Done(create_node(1, "execution_time", 3)),
+                    ProcessResult::Idle
+                ]
+            )
+        );
+        assert_eq!(
+            homogeneous_processor.process_for(1),
+            (
+                1,
+                vec![
+                    ProcessResult::Continue,
+                    ProcessResult::Idle,
+                    ProcessResult::Idle
+                ]
+            )
+        );
+        assert_eq!(homogeneous_processor.cores[0].remain_proc_time, 1);
+    }
+
+    #[test]
+    fn test_processor_process_for_when_processor_no_allocated() {
+        let mut homogeneous_processor = HomogeneousProcessor::new(2);
+
+        assert_eq!(
+            homogeneous_processor.process_for(5),
+            (5, vec![ProcessResult::Idle, ProcessResult::Idle])
+        );
+    }
+
     #[test]
     fn test_processor_process_when_processor_no_allocated() {
         let mut homogeneous_processor = HomogeneousProcessor::new(2);
@@ -216,6 +529,15 @@ mod tests {
         assert_eq!(homogeneous_processor.get_idle_core_num(), 0);
     }
 
+    #[test]
+    fn test_processor_get_idle_core_indices_normal() {
+        let mut homogeneous_processor = HomogeneousProcessor::new(3);
+        assert_eq!(homogeneous_processor.get_idle_core_indices(), vec![0, 1, 2]);
+
+        homogeneous_processor.allocate_specific_core(1, &create_node(0, "execution_time", 2));
+        assert_eq!(homogeneous_processor.get_idle_core_indices(), vec![0, 2]);
+    }
+
     #[test]
     fn test_processor_preempt_execution_normal() {
         let mut homogeneous_processor = HomogeneousProcessor::new(2);
@@ -265,4 +587,28 @@ mod tests {
             Some((11, 1))
         );
     }
+
+    #[test]
+    fn test_get_preemptable_core_index_with_threshold() {
+        let mut homogeneous_processor = HomogeneousProcessor::new(2);
+        let n0 = create_node(0, "execution_time", 10);
+        let mut n1 = create_node(1, "execution_time", 20);
+        n1.params.insert("preemption_threshold".to_string(), 12);
+
+        homogeneous_processor.allocate_specific_core(0, &n0);
+        homogeneous_processor.allocate_specific_core(1, &n1);
+        // n1 is protected by its threshold, so n0 is preempted instead.
+        assert_eq!(
+            homogeneous_processor.get_preemptable_core_index("execution_time", 8),
+            Some(0)
+        );
+        assert_eq!(
+            homogeneous_processor.get_preemptable_core_index("execution_time", 7),
+            Some(1)
+        );
+        assert_eq!(
+            homogeneous_processor.get_preemptable_core_index("execution_time", 10),
+            None
+        );
+    }
 }
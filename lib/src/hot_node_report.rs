@@ -0,0 +1,222 @@
+//! Rank the nodes of each DAG by how much they delay the DAG in a simulated schedule,
+//! to show which WCETs are worth optimizing in the real system.
+//!
+//! For each completed DAG job, the critical chain is traced back from the last finished sink
+//! through the last finished predecessor of each node. A node is hot if it is often on the
+//! critical chain, or if it waits long in the ready queue (from the finish of its predecessors,
+//! or the release, until it finishes, minus its execution).
+use crate::{graph_extension::NodeData, log::DAGSetSchedulerLog};
+use petgraph::{
+    graph::NodeIndex,
+    Direction::{Incoming, Outgoing},
+    Graph,
+};
+use serde_derive::{Deserialize, Serialize};
+#[cfg(feature = "yaml")]
+use std::fs;
+use std::{cmp::Reverse, collections::BTreeMap};
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HotNode {
+    pub node_id: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node_name: Option<String>,
+    /// Completed DAG jobs whose critical chain contains the node.
+    pub critical_chain_count: usize,
+    pub total_waiting_time: i32,
+    pub max_waiting_time: i32,
+}
+
+/// Nodes of a DAG from the hottest.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DAGHotNodes {
+    pub dag_id: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dag_name: Option<String>,
+    pub completed_jobs: usize,
+    pub hot_nodes: Vec<HotNode>,
+}
+
+/// Finish time and executed time of each node job by `(node_id, job_id)`.
+type NodeJobTimes = BTreeMap<(usize, usize), (i32, i32)>;
+
+fn get_node_job_times(log: &DAGSetSchedulerLog, dag_id: usize) -> NodeJobTimes {
+    let mut node_job_times = NodeJobTimes::new();
+    for interval in log.get_execution_intervals() {
+        if interval.dag_id != dag_id {
+            continue;
+        }
+        let times = node_job_times
+            .entry((interval.node_id, interval.job_id))
+            .or_default();
+        times.0 = times.0.max(interval.end_time);
+        times.1 += interval.end_time - interval.begin_time;
+    }
+    node_job_times
+}
+
+/// The predecessor of `node_i` in `job_id` that finished last.
+fn get_last_finished_pre_node(
+    dag: &Graph<NodeData, i32>,
+    node_i: NodeIndex,
+    job_id: usize,
+    node_job_times: &NodeJobTimes,
+) -> Option<NodeIndex> {
+    dag.neighbors_directed(node_i, Incoming)
+        .filter_map(|pre_node_i| {
+            node_job_times
+                .get(&(dag[pre_node_i].id as usize, job_id))
+                .map(|&(finish_time, _)| (finish_time, pre_node_i))
+        })
+        .max()
+        .map(|(_, pre_node_i)| pre_node_i)
+}
+
+pub fn get_hot_node_report(
+    dag_set: &[Graph<NodeData, i32>],
+    log: &DAGSetSchedulerLog,
+) -> Vec<DAGHotNodes> {
+    dag_set
+        .iter()
+        .enumerate()
+        .map(|(dag_id, dag)| {
+            let node_job_times = get_node_job_times(log, dag_id);
+            let mut hot_nodes: BTreeMap<usize, HotNode> = dag
+                .node_weights()
+                .map(|node_data| {
+                    let node_id = node_data.id as usize;
+                    (
+                        node_id,
+                        HotNode {
+                            node_id,
+                            node_name: log.get_node_name(dag_id, node_id).map(str::to_string),
+                            ..Default::default()
+                        },
+                    )
+                })
+                .collect();
+
+            // Waiting time in the ready queue
+            let release_times = log.get_release_times(dag_id);
+            for node_i in dag.node_indices() {
+                let node_id = dag[node_i].id as usize;
+                for (job_id, &release_time) in release_times.iter().enumerate() {
+                    let Some(&(finish_time, executed_time)) =
+                        node_job_times.get(&(node_id, job_id))
+                    else {
+                        continue;
+                    };
+                    let ready_time =
+                        get_last_finished_pre_node(dag, node_i, job_id, &node_job_times)
+                            .map_or(release_time, |pre_node_i| {
+                                node_job_times[&(dag[pre_node_i].id as usize, job_id)].0
+                            });
+                    let waiting_time = finish_time - ready_time - executed_time;
+                    let hot_node = hot_nodes.get_mut(&node_id).unwrap();
+                    hot_node.total_waiting_time += waiting_time;
+                    hot_node.max_waiting_time = hot_node.max_waiting_time.max(waiting_time);
+                }
+            }
+
+            // Critical chains of the completed jobs
+            let sink_nodes: Vec<NodeIndex> = dag.externals(Outgoing).collect();
+            let mut completed_jobs = 0;
+            for job_id in 0..release_times.len() {
+                let sink_finish_times: Option<Vec<(i32, NodeIndex)>> = sink_nodes
+                    .iter()
+                    .map(|&sink_i| {
+                        node_job_times
+                            .get(&(dag[sink_i].id as usize, job_id))
+                            .map(|&(finish_time, _)| (finish_time, sink_i))
+                    })
+                    .collect();
+                let Some(last_sink) = sink_finish_times.and_then(|times| times.into_iter().max())
+                else {
+                    continue;
+                };
+                completed_jobs += 1;
+                let mut node_i = Some(last_sink.1);
+                while let Some(chain_node_i) = node_i {
+                    hot_nodes
+                        .get_mut(&(dag[chain_node_i].id as usize))
+                        .unwrap()
+                        .critical_chain_count += 1;
+                    node_i = get_last_finished_pre_node(dag, chain_node_i, job_id, &node_job_times);
+                }
+            }
+
+            let mut hot_nodes: Vec<HotNode> = hot_nodes.into_values().collect();
+            hot_nodes.sort_by_key(|hot_node| {
+                (
+                    Reverse(hot_node.critical_chain_count),
+                    Reverse(hot_node.total_waiting_time),
+                    hot_node.node_id,
+                )
+            });
+            DAGHotNodes {
+                dag_id,
+                dag_name: log.get_dag_name(dag_id).map(str::to_string),
+                completed_jobs,
+                hot_nodes,
+            }
+        })
+        .collect()
+}
+
+#[cfg(feature = "yaml")]
+pub fn write_hot_node_report(report: &[DAGHotNodes], file_path: &str) {
+    let yaml = serde_yaml::to_string(report).expect("Failed to serialize.");
+    fs::write(file_path, yaml)
+        .unwrap_or_else(|err| panic!("Failed to write {}: {}", file_path, err));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::create_node;
+    use crate::{
+        dag_set_scheduler::{DAGSetSchedulerBase, PreemptiveType},
+        global_edf_scheduler::GlobalEDFScheduler,
+        graph_extension::GraphExtension,
+        homogeneous::HomogeneousProcessor,
+        processor::ProcessorBase,
+    };
+
+    #[test]
+    fn test_get_hot_node_report_normal() {
+        // 0 -> {1, 2, 3} -> 4 on two cores. Node 3 waits for node 1 or 2 to finish.
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 1));
+        let n1 = dag.add_node(create_node(1, "execution_time", 2));
+        let n2 = dag.add_node(create_node(2, "execution_time", 2));
+        let n3 = dag.add_node(create_node(3, "execution_time", 1));
+        let n4 = dag.add_node(create_node(4, "execution_time", 1));
+        for node_i in [n1, n2, n3] {
+            dag.add_edge(n0, node_i, 0);
+            dag.add_edge(node_i, n4, 0);
+        }
+        dag.add_param(n0, "period", 10);
+        dag.add_param(n4, "end_to_end_deadline", 10);
+        dag.set_dag_param("dag_id", 0);
+        let dag_set = vec![dag];
+
+        let mut global_edf_scheduler =
+            GlobalEDFScheduler::new(&dag_set, &HomogeneousProcessor::new(2));
+        global_edf_scheduler.schedule(PreemptiveType::NonPreemptive);
+        let report = get_hot_node_report(&dag_set, global_edf_scheduler.get_log_mut());
+
+        assert_eq!(report[0].completed_jobs, 1);
+        let hot_nodes = &report[0].hot_nodes;
+        // 0 -> 3 -> 4 is the critical chain because node 3 finishes at 4.
+        assert_eq!(
+            hot_nodes
+                .iter()
+                .map(|hot_node| hot_node.node_id)
+                .collect::<Vec<_>>(),
+            vec![3, 0, 4, 1, 2]
+        );
+        assert_eq!(hot_nodes[0].critical_chain_count, 1);
+        assert_eq!(hot_nodes[0].total_waiting_time, 2);
+        assert_eq!(hot_nodes[3].total_waiting_time, 0);
+    }
+}
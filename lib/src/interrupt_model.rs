@@ -0,0 +1,148 @@
+//! Interrupt workload modeled as sporadic bursts of non-DAG execution on each core.
+//!
+//! A burst occupies its core at the highest priority: the node running on the core is stalled
+//! (neither preempted nor migrated) until the burst ends, and no node is dispatched to the core
+//! meanwhile. The bursts are drawn from a seed, so that runs with the same model are identical.
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// Sporadic bursts of one core. The first burst arrives within `max_jitter` from time 0, and each
+/// following burst `min_inter_arrival_time` plus a jitter within `max_jitter` after the previous.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InterruptSource {
+    pub min_inter_arrival_time: i32,
+    pub max_jitter: i32,
+    pub burst_length: i32,
+}
+
+impl InterruptSource {
+    pub fn new(min_inter_arrival_time: i32, max_jitter: i32, burst_length: i32) -> Self {
+        if burst_length <= 0 {
+            panic!("The burst length of interrupts must be positive.");
+        }
+        if min_inter_arrival_time < burst_length {
+            panic!(
+                "The minimum inter-arrival time {} of interrupts is shorter than the burst length {}.",
+                min_inter_arrival_time, burst_length
+            );
+        }
+        if max_jitter < 0 {
+            panic!("The jitter of interrupts must not be negative.");
+        }
+        Self {
+            min_inter_arrival_time,
+            max_jitter,
+            burst_length,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct InterruptModel {
+    /// Source of each core. `None` for cores without interrupts.
+    sources: Vec<Option<InterruptSource>>,
+    seed: u64,
+}
+
+impl InterruptModel {
+    pub fn new(sources: Vec<Option<InterruptSource>>, seed: u64) -> Self {
+        Self { sources, seed }
+    }
+
+    /// The same source for all cores, with bursts drawn independently per core.
+    pub fn new_uniform(source: InterruptSource, number_of_cores: usize, seed: u64) -> Self {
+        Self::new(vec![Some(source); number_of_cores], seed)
+    }
+
+    pub fn get_sources(&self) -> &[Option<InterruptSource>] {
+        &self.sources
+    }
+
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Draw the bursts of each core that arrive before `end_time`.
+    pub fn generate_bursts(&self, end_time: i32) -> InterruptBursts {
+        let burst_start_times = self
+            .sources
+            .iter()
+            .enumerate()
+            .map(|(core_i, source)| {
+                let Some(source) = source else {
+                    return Vec::new();
+                };
+                let mut rng = StdRng::seed_from_u64(self.seed.wrapping_add(core_i as u64));
+                let mut start_times = Vec::new();
+                let mut start_time = rng.gen_range(0..=source.max_jitter);
+                while start_time < end_time {
+                    start_times.push(start_time);
+                    start_time +=
+                        source.min_inter_arrival_time + rng.gen_range(0..=source.max_jitter);
+                }
+                start_times
+            })
+            .collect();
+        InterruptBursts {
+            burst_start_times,
+            burst_lengths: self
+                .sources
+                .iter()
+                .map(|source| source.map_or(0, |source| source.burst_length))
+                .collect(),
+            next_burst_indices: vec![0; self.sources.len()],
+        }
+    }
+}
+
+/// Bursts drawn by `InterruptModel::generate_bursts`, queried in time order.
+#[derive(Clone, Debug)]
+pub struct InterruptBursts {
+    burst_start_times: Vec<Vec<i32>>,
+    burst_lengths: Vec<i32>,
+    /// The first burst of each core that has not ended yet.
+    next_burst_indices: Vec<usize>,
+}
+
+impl InterruptBursts {
+    /// Whether each core is in a burst in the time unit from `time`.
+    /// `time` must not decrease between calls.
+    pub fn get_stolen_cores(&mut self, time: i32) -> Vec<bool> {
+        (0..self.burst_start_times.len())
+            .map(|core_i| {
+                let start_times = &self.burst_start_times[core_i];
+                let next_burst_i = &mut self.next_burst_indices[core_i];
+                while start_times
+                    .get(*next_burst_i)
+                    .is_some_and(|&start_time| start_time + self.burst_lengths[core_i] <= time)
+                {
+                    *next_burst_i += 1;
+                }
+                start_times
+                    .get(*next_burst_i)
+                    .is_some_and(|&start_time| start_time <= time)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_stolen_cores_normal() {
+        let model = InterruptModel::new(vec![Some(InterruptSource::new(5, 0, 2)), None], 0);
+        let mut bursts = model.generate_bursts(10);
+        let stolen_times: Vec<i32> = (0..10)
+            .filter(|&time| bursts.get_stolen_cores(time)[0])
+            .collect();
+        assert_eq!(stolen_times, vec![0, 1, 5, 6]);
+        assert_eq!(bursts.get_stolen_cores(9), vec![false, false]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_interrupt_source_new_overlapping_bursts() {
+        InterruptSource::new(2, 0, 3);
+    }
+}
@@ -1,11 +1,92 @@
+pub mod admission_control;
+pub mod anticipatory_edf_scheduler;
+pub mod aperiodic_server;
+#[cfg(feature = "yaml")]
+pub mod benchmark_loader;
+#[cfg(feature = "analysis")]
+pub mod breakdown_factor;
+pub mod cbs_scheduler;
+#[cfg(feature = "yaml")]
+pub mod cli_config;
+pub mod clustered_processor;
+pub mod clustered_scheduler;
+pub mod compact_dag;
 pub mod core;
+#[cfg(feature = "analysis")]
+pub mod core_recommendation;
+pub mod cpop_scheduler;
+#[cfg(feature = "yaml")]
 pub mod dag_creator;
 pub mod dag_scheduler;
+#[cfg(feature = "generator")]
+pub mod dag_set_generator;
 pub mod dag_set_scheduler;
+pub mod dag_task;
+pub mod dataflow_scheduler;
+pub mod decomposition;
+pub mod decomposition_scheduler;
+#[cfg(feature = "json")]
+pub mod determinism;
+pub mod dp_fair_scheduler;
+pub mod dvfs;
+pub mod dvfs_scheduler;
+pub mod edf_vd_scheduler;
+pub mod edzl_scheduler;
+pub mod elastic_scheduler;
+pub mod execution_time_trace;
+pub mod fault_injection;
+pub mod federated_scheduler;
+pub mod fifo_scheduler;
 pub mod fixed_priority_scheduler;
+#[cfg(all(feature = "yaml", feature = "analysis"))]
+pub mod fixture;
+pub mod fractional_core_container;
+pub mod gang_edf_scheduler;
 pub mod global_edf_scheduler;
+pub mod global_fp_scheduler;
 pub mod graph_extension;
 pub mod homogeneous;
+#[cfg(feature = "analysis")]
+pub mod hot_node_report;
+#[cfg(feature = "random")]
+pub mod interrupt_model;
 pub mod log;
+pub mod lpt_scheduler;
+pub mod memory_centric_scheduler;
+pub mod mixed_criticality;
+#[cfg(all(feature = "yaml", feature = "analysis"))]
+pub mod output_log;
+pub mod partitioned_edf_scheduler;
+#[cfg(feature = "yaml")]
+pub mod period_assignment;
+pub mod pfair_scheduler;
+#[cfg(feature = "export")]
+pub mod playback;
+#[cfg(feature = "plugin")]
+pub mod plugin;
+pub mod power_down;
+#[cfg(feature = "analysis")]
+pub mod preprocessing_cache;
+pub mod priority_assignment;
 pub mod processor;
+pub mod qps_scheduler;
+#[cfg(feature = "random")]
+pub mod random_scheduler;
+pub mod resource_manager;
+#[cfg(feature = "analysis")]
+pub mod schedulability_screening;
+#[cfg(feature = "analysis")]
+pub mod schedule_score;
+#[cfg(feature = "export")]
+pub mod schedule_witness;
+pub mod scheduler_creator;
+pub mod segment_gang_scheduler;
+pub mod semi_partitioned_scheduler;
+pub mod static_schedule_executor;
+#[cfg(feature = "analysis")]
+pub mod statistics;
+#[cfg(test)]
+mod test_util;
 pub mod util;
+#[cfg(feature = "random")]
+pub mod work_stealing_scheduler;
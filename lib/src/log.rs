@@ -1,53 +1,147 @@
-use crate::graph_extension::{GraphExtension, NodeData};
+use crate::admission_control::AdmissionTest;
+#[cfg(feature = "analysis")]
+use crate::breakdown_factor::BreakdownFactor;
+use crate::clustered_scheduler::ClusterAssignment;
+#[cfg(feature = "analysis")]
+use crate::core_recommendation::CoreRecommendation;
+#[cfg(feature = "yaml")]
+use crate::dag_creator::TimeQuantization;
+use crate::dag_set_scheduler::{IdleCoreSelection, IdleInsertionPolicy, TieBreakingPolicy};
+use crate::dag_task::{create_dag_task_set, DagTask, UtilizationClass};
+use crate::federated_scheduler::FederatedAllocation;
+use crate::graph_extension::{DAGNames, GraphExtension, NodeData, SpeedModel};
+use crate::mixed_criticality::Criticality;
+use crate::partitioned_edf_scheduler::{Partition, PartitioningHeuristic};
+use crate::priority_assignment::PriorityAssignment;
+use crate::qps_scheduler::QpsAllocation;
+#[cfg(feature = "yaml")]
 use crate::util::append_info_to_yaml;
+use crate::util::{get_peak_rss_kb, sum_in_fixed_order, validate_dag_ids};
+use getset::{CopyGetters, Getters};
 use log::warn;
 use petgraph::Graph;
-use serde::Serialize;
+#[cfg(feature = "yaml")]
+use serde::de::DeserializeOwned;
 use serde_derive::{Deserialize, Serialize};
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, BTreeSet},
+    ops::RangeInclusive,
+    time::Instant,
+};
+#[cfg(feature = "yaml")]
+use std::{fs, str::FromStr};
 
-pub fn dump_struct(file_path: &str, target_struct: &impl Serialize) {
+#[cfg(feature = "yaml")]
+pub fn dump_struct(file_path: &str, target_struct: &impl serde::Serialize) {
     let yaml = serde_yaml::to_string(&target_struct).expect("Failed to serialize.");
     append_info_to_yaml(file_path, &yaml);
 }
 
-#[derive(Clone, Default, Serialize, Deserialize)]
+#[derive(Clone, Default, Getters, Serialize, Deserialize)]
 pub struct DAGSetInfo {
     total_utilization: f32,
     each_dag_info: Vec<DAGInfo>,
+    /// DAGs classified by `UtilizationClass::classify`.
+    #[serde(default)]
+    #[get = "pub with_prefix"]
+    heavy_dags: UtilizationClassInfo,
+    #[serde(default)]
+    #[get = "pub with_prefix"]
+    light_dags: UtilizationClassInfo,
+    /// Periods stretched by the elastic scheduling, in the order of `each_dag_info`, which keeps
+    /// the nominal periods.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    adapted_periods: Option<Vec<i32>>,
+}
+
+#[derive(Clone, Copy, Default, Debug, CopyGetters, Serialize, Deserialize)]
+pub struct UtilizationClassInfo {
+    #[get_copy = "pub with_prefix"]
+    number_of_dags: usize,
+    #[get_copy = "pub with_prefix"]
+    total_utilization: f32,
+}
+
+impl UtilizationClassInfo {
+    fn new(dag_tasks: &[&DagTask]) -> Self {
+        Self {
+            number_of_dags: dag_tasks.len(),
+            total_utilization: sum_in_fixed_order(
+                dag_tasks
+                    .iter()
+                    .map(|dag_task| dag_task.get_utilization().unwrap_or(0.0)),
+            ),
+        }
+    }
 }
 
 impl DAGSetInfo {
     pub fn new(dag_set: &[Graph<NodeData, i32>]) -> Self {
-        let mut total_utilization = 0.0;
+        Self::new_with_speed_models(dag_set, &vec![SpeedModel::Uniform; dag_set.len()])
+    }
+
+    /// The metrics of each DAG are calculated with the speeds of the cores its nodes run on.
+    pub fn new_with_speed_models(
+        dag_set: &[Graph<NodeData, i32>],
+        speed_models: &[SpeedModel],
+    ) -> Self {
         let mut each_dag_info = Vec::new();
+        let mut heavy_dags = Vec::new();
+        let mut light_dags = Vec::new();
 
-        for dag in dag_set.iter() {
-            let dag_info = DAGInfo::new(dag);
-            total_utilization += dag_info.get_utilization();
+        let dag_task_set = create_dag_task_set(dag_set);
+        for (dag_id, (dag_task, speed_model)) in dag_task_set.iter().zip(speed_models).enumerate() {
+            let mut dag_info = DAGInfo::new_with_speed_model(dag_task, speed_model);
+            dag_info.dag_id = Some(dag_id);
             each_dag_info.push(dag_info);
+            match UtilizationClass::classify_dag_task(dag_task) {
+                Some(UtilizationClass::Heavy) => heavy_dags.push(dag_task),
+                Some(UtilizationClass::Light) => light_dags.push(dag_task),
+                None => {}
+            }
         }
 
         Self {
-            total_utilization,
+            total_utilization: sum_in_fixed_order(
+                each_dag_info
+                    .iter()
+                    .map(|dag_info| dag_info.get_utilization()),
+            ),
             each_dag_info,
+            heavy_dags: UtilizationClassInfo::new(&heavy_dags),
+            light_dags: UtilizationClassInfo::new(&light_dags),
+            adapted_periods: None,
         }
     }
 }
 
 #[derive(Clone, Default, Serialize, Deserialize)]
 pub struct DAGInfo {
+    /// Always set in a DAG set, whose info is looked up by `dag_id` once filtered.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    dag_id: Option<usize>,
     critical_path_length: i32,
     period: i32,
     end_to_end_deadline: i32,
     volume: i32,
     utilization: f32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    utilization_class: Option<UtilizationClass>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    max_parallelism: Option<usize>,
 }
 
 impl DAGInfo {
-    pub fn new(dag: &Graph<NodeData, i32>) -> Self {
-        let period = dag.get_head_period().unwrap_or(0);
-        let end_to_end_deadline = dag.get_end_to_end_deadline().unwrap_or(0);
-        let volume = dag.get_volume();
+    pub fn new(dag_task: &DagTask) -> Self {
+        Self::new_with_speed_model(dag_task, &SpeedModel::Uniform)
+    }
+
+    pub fn new_with_speed_model(dag_task: &DagTask, speed_model: &SpeedModel) -> Self {
+        let dag = &dag_task.graph;
+        let period = dag_task.period.unwrap_or(0);
+        let end_to_end_deadline = dag_task.end_to_end_deadline.unwrap_or(0);
+        let volume = dag.get_volume_with_speed(speed_model);
         let utilization = match (end_to_end_deadline, period) {
             (0, 0) => {
                 warn!("Both period and end_to_end_deadline are not set.");
@@ -61,19 +155,29 @@ impl DAGInfo {
             (_, _) => period as f32 / volume as f32,
         };
 
-        let critical_path = dag.clone().get_critical_path();
         Self {
-            critical_path_length: dag.get_total_wcet_from_nodes(&critical_path),
+            dag_id: dag_task.dag_id,
+            critical_path_length: dag.get_critical_path_length_with_speed(speed_model),
             period,
             end_to_end_deadline,
             volume,
             utilization,
+            utilization_class: UtilizationClass::from_dag(dag),
+            max_parallelism: dag.get_max_parallelism(),
         }
     }
 
     fn get_utilization(&self) -> f32 {
         self.utilization
     }
+
+    fn get_volume(&self) -> i32 {
+        self.volume
+    }
+
+    fn get_period(&self) -> i32 {
+        self.period
+    }
 }
 
 #[derive(Clone, Default, Serialize, Deserialize)]
@@ -90,22 +194,59 @@ impl ProcessorInfo {
 #[derive(Clone, Default, Serialize, Deserialize)]
 pub struct DAGLog {
     dag_id: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    dag_name: Option<String>,
     release_time: Vec<i32>,
     finish_time: Vec<i32>,
     response_time: Vec<i32>,
     average_response_time: f32,
     worst_response_time: i32,
+    /// Time units in which ready nodes waited only because of `max_parallelism`.
+    #[serde(default)]
+    throttled_time: i32,
+    /// Time units in which ready gang nodes waited for enough idle cores.
+    #[serde(default)]
+    gang_waiting_time: i32,
+    /// Time units in which running nodes were stalled by interrupt bursts, which delay the
+    /// response times by as much at most.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    interrupt_interference_time: Option<i32>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    release_events: Vec<ReleaseEvent>,
+    /// `absolute deadline - finish_time` of each completed job.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    slack: Vec<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    min_slack: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    average_slack: Option<f32>,
+    /// Fraction of the optional parts executed in each completed job with optional parts.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    quality: Vec<f32>,
+    /// (executed, total) optional execution time of the current job.
+    #[serde(skip)]
+    optional_progress: (i32, i32),
 }
 
 impl DAGLog {
     pub fn new(dag_id: usize) -> Self {
         Self {
             dag_id,
+            dag_name: None,
             release_time: Default::default(),
             finish_time: Default::default(),
             response_time: Default::default(),
             average_response_time: Default::default(),
             worst_response_time: Default::default(),
+            throttled_time: Default::default(),
+            gang_waiting_time: Default::default(),
+            interrupt_interference_time: None,
+            release_events: Default::default(),
+            slack: Default::default(),
+            min_slack: Default::default(),
+            average_slack: Default::default(),
+            quality: Default::default(),
+            optional_progress: Default::default(),
         }
     }
 
@@ -113,7 +254,7 @@ impl DAGLog {
         // Unequal lengths indicate that the DAG was not completed within the hyper_period, and deadline miss occurred.
         if self.release_time.len() != self.finish_time.len() {
             // Mark as a deadline miss by maximizing the response time.
-            self.finish_time.push(std::i32::MAX);
+            self.finish_time.push(i32::MAX);
         }
         self.response_time = self
             .release_time
@@ -129,11 +270,512 @@ impl DAGLog {
     }
 
     pub fn calculate_worst_response_time(&mut self) {
-        self.worst_response_time = *self.response_time.iter().max().unwrap();
+        self.worst_response_time = self.response_time.iter().max().copied().unwrap_or(0);
+    }
+
+    pub fn calculate_slack(&mut self) {
+        self.min_slack = self.slack.iter().min().copied();
+        self.average_slack = (!self.slack.is_empty())
+            .then(|| self.slack.iter().sum::<i32>() as f32 / self.slack.len() as f32);
+    }
+
+    /// Keep only the jobs released in `time_window`.
+    fn retain_jobs(&mut self, time_window: &RangeInclusive<i32>) {
+        let job_indices: Vec<usize> = self
+            .release_time
+            .iter()
+            .enumerate()
+            .filter(|(_, release_time)| time_window.contains(release_time))
+            .map(|(job_i, _)| job_i)
+            .collect();
+        for times in [
+            &mut self.release_time,
+            &mut self.finish_time,
+            &mut self.response_time,
+            &mut self.slack,
+        ] {
+            *times = job_indices
+                .iter()
+                .filter_map(|&job_i| times.get(job_i).copied())
+                .collect();
+        }
+        self.release_events
+            .retain(|release_event| time_window.contains(&release_event.get_release_time()));
+        // The response times were calculated from all the jobs before the filtering.
+        if !self.response_time.is_empty() {
+            self.calculate_average_response_time();
+        }
+        self.calculate_worst_response_time();
+        self.calculate_slack();
     }
 }
 
-#[derive(Clone, Serialize, Deserialize)]
+/// An optional node dropped because the remaining laxity of its DAG was insufficient.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DegradationDecision {
+    pub dag_id: usize,
+    pub node_id: i32,
+    pub job_id: usize,
+    pub time: i32,
+    /// `absolute deadline - time - remaining path length` if the node had run.
+    pub laxity: i32,
+}
+
+/// A transient fault of an execution of a node (see `fault_injection`).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FaultEvent {
+    pub dag_id: usize,
+    pub node_id: i32,
+    pub job_id: usize,
+    pub time: i32,
+    pub core_id: usize,
+    /// Executions of the node in the job before the faulty one.
+    pub attempt: i32,
+    /// Whether the node is re-executed. Otherwise the retries are exhausted and the job failed.
+    pub retried: bool,
+}
+
+/// Reliability of a DAG under the injected faults.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DAGReliability {
+    pub dag_id: usize,
+    pub jobs: usize,
+    pub faults: usize,
+    pub re_executions: usize,
+    /// Jobs with a node whose retries were exhausted.
+    pub failed_jobs: usize,
+    /// Ratio of the jobs completed by the deadline without failing.
+    pub reliability: f32,
+}
+
+/// A node job moved from one core to another at a planned migration point.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MigrationEvent {
+    pub dag_id: usize,
+    pub node_id: i32,
+    pub job_id: usize,
+    pub time: i32,
+    pub from_core_id: usize,
+    pub to_core_id: usize,
+}
+
+/// A job of a triggered DAG released with the deadline of the job triggering it, if earlier than
+/// its own, so that it is not delayed by less urgent jobs (see `get_priority_inheritance`).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InheritanceEvent {
+    pub triggering_dag_id: usize,
+    pub triggering_job_id: usize,
+    pub triggering_release_time: i32,
+    pub triggered_dag_id: usize,
+    pub triggered_job_id: usize,
+    /// Release time of the triggered job.
+    pub time: i32,
+    pub own_deadline: i32,
+    pub inherited_deadline: i32,
+    /// From the release of the triggering job to the finish of the triggered job.
+    /// `None` if the triggered job did not finish.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chain_latency: Option<i32>,
+}
+
+/// A node job blocked on a shared resource held by another node (see `resource_manager`).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockingEvent {
+    pub dag_id: usize,
+    pub node_id: i32,
+    pub job_id: usize,
+    pub resource_id: i32,
+    /// The node that released the resource to the blocked node.
+    pub holder_dag_id: usize,
+    pub holder_node_id: i32,
+    /// Time the node blocked.
+    pub time: i32,
+    pub blocking_time: i32,
+}
+
+/// A switch of the criticality mode (see `mixed_criticality`).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModeChangeEvent {
+    pub time: i32,
+    pub mode: Criticality,
+    /// The node that overran its `lo_execution_time`.
+    /// `None` for the return to the LO mode at an idle instant.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dag_id: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node_id: Option<i32>,
+    /// The LO-criticality DAGs whose unfinished jobs were dropped.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dropped_dag_ids: Vec<usize>,
+}
+
+/// Utilization of a DAG loaded with a time quantum.
+#[cfg(feature = "yaml")]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DAGQuantization {
+    pub original_utilization: f32,
+    pub quantized_utilization: f32,
+}
+
+/// Rounding of the float times of the DAG set to integer multiples of the time quantum.
+#[cfg(feature = "yaml")]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Getters, CopyGetters)]
+pub struct QuantizationLog {
+    #[get_copy = "pub with_prefix"]
+    quantization: TimeQuantization,
+    #[get = "pub with_prefix"]
+    each_dag_quantization: Vec<DAGQuantization>,
+    /// Total utilization after the quantization minus before. Positive if pessimistic.
+    #[get_copy = "pub with_prefix"]
+    utilization_inflation: f32,
+}
+
+#[cfg(feature = "yaml")]
+impl QuantizationLog {
+    pub fn new(
+        quantization: TimeQuantization,
+        each_dag_quantization: Vec<DAGQuantization>,
+    ) -> Self {
+        let utilization_inflation = each_dag_quantization
+            .iter()
+            .map(|dag_quantization| {
+                dag_quantization.quantized_utilization - dag_quantization.original_utilization
+            })
+            .sum();
+        Self {
+            quantization,
+            each_dag_quantization,
+            utilization_inflation,
+        }
+    }
+}
+
+/// Cost of the simulation itself, to compare implementations by simulation cost.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResourceUsage {
+    /// Wall-clock time from the start of `schedule` (`write_schedule_start`), after the setup of
+    /// the scheduler, to the end of the simulation.
+    pub wall_time_ms: u64,
+    /// Number of job events (start, resume, preemption and finish) processed.
+    pub processed_events: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub peak_rss_kb: Option<u64>,
+}
+
+/// Slack (`absolute deadline - finish_time`) of the completed jobs of a DAG.
+/// A near-zero min slack marks a fragile DAG even without deadline misses.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SlackSummary {
+    pub dag_id: usize,
+    pub min_slack: i32,
+    pub average_slack: f32,
+}
+
+/// Assignment of the DAGs to the cores by partitioned scheduling.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Getters, CopyGetters)]
+pub struct PartitioningLog {
+    #[get_copy = "pub with_prefix"]
+    heuristic: PartitioningHeuristic,
+    #[serde(flatten)]
+    #[get = "pub with_prefix"]
+    partition: Partition,
+}
+
+impl PartitioningLog {
+    pub fn new(heuristic: PartitioningHeuristic, partition: Partition) -> Self {
+        Self {
+            heuristic,
+            partition,
+        }
+    }
+}
+
+/// Cores, DAGs, and utilization of a cluster of clustered scheduling.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Getters, CopyGetters)]
+pub struct ClusterLog {
+    #[get_copy = "pub with_prefix"]
+    cluster_id: usize,
+    #[get = "pub with_prefix"]
+    core_ids: Vec<usize>,
+    #[get = "pub with_prefix"]
+    dag_ids: Vec<usize>,
+    /// Total utilization of the DAGs assigned to the cluster.
+    #[get_copy = "pub with_prefix"]
+    assigned_utilization: f32,
+    /// Average utilization of the cores of the cluster over the simulation.
+    #[get_copy = "pub with_prefix"]
+    average_utilization: f32,
+}
+
+/// Assignment of the DAGs to the clusters by clustered scheduling.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Getters, CopyGetters)]
+pub struct ClusteringLog {
+    #[get_copy = "pub with_prefix"]
+    heuristic: PartitioningHeuristic,
+    #[get = "pub with_prefix"]
+    cluster_logs: Vec<ClusterLog>,
+    /// DAGs that fit in no cluster.
+    #[get = "pub with_prefix"]
+    unfit_dag_ids: Vec<usize>,
+}
+
+impl ClusteringLog {
+    /// `cluster_core_ids` are the cores of each cluster.
+    pub fn new(
+        heuristic: PartitioningHeuristic,
+        cluster_core_ids: Vec<Vec<usize>>,
+        assignment: &ClusterAssignment,
+    ) -> Self {
+        let cluster_logs = cluster_core_ids
+            .into_iter()
+            .enumerate()
+            .map(|(cluster_id, core_ids)| ClusterLog {
+                cluster_id,
+                core_ids,
+                dag_ids: (0..assignment.cluster_ids.len())
+                    .filter(|&dag_id| assignment.cluster_ids[dag_id] == cluster_id)
+                    .collect(),
+                assigned_utilization: assignment.cluster_utilizations[cluster_id],
+                average_utilization: Default::default(),
+            })
+            .collect();
+        Self {
+            heuristic,
+            cluster_logs,
+            unfit_dag_ids: assignment.unfit_dag_ids.clone(),
+        }
+    }
+
+    fn calculate_average_utilizations(&mut self, processor_log: &ProcessorLog) {
+        for cluster_log in self.cluster_logs.iter_mut() {
+            cluster_log.average_utilization = sum_in_fixed_order(
+                cluster_log
+                    .core_ids
+                    .iter()
+                    .map(|&core_id| processor_log.core_logs[core_id].utilization),
+            ) / cluster_log.core_ids.len() as f32;
+        }
+    }
+}
+
+/// State of the buffer on an edge after a change, in tokens.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BufferState {
+    pub time: i32,
+    /// Tokens available to the consumer.
+    pub tokens: i32,
+    /// Space reserved by running firings of the producer.
+    pub reserved: i32,
+}
+
+/// Tokens on an edge of a dataflow DAG over time.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, CopyGetters, Getters)]
+pub struct BufferLog {
+    #[get_copy = "pub with_prefix"]
+    dag_id: usize,
+    #[get_copy = "pub with_prefix"]
+    producer_id: i32,
+    #[get_copy = "pub with_prefix"]
+    consumer_id: i32,
+    #[get_copy = "pub with_prefix"]
+    capacity: i32,
+    #[get_copy = "pub with_prefix"]
+    max_tokens: i32,
+    /// Time the producer could fire except that this buffer was full (backpressure).
+    #[get_copy = "pub with_prefix"]
+    blocked_time: i32,
+    #[get = "pub with_prefix"]
+    states: Vec<BufferState>,
+}
+
+impl BufferLog {
+    pub fn new(dag_id: usize, producer_id: i32, consumer_id: i32, capacity: i32) -> Self {
+        Self {
+            dag_id,
+            producer_id,
+            consumer_id,
+            capacity,
+            max_tokens: 0,
+            blocked_time: 0,
+            states: Vec::new(),
+        }
+    }
+}
+
+/// A time interval in which a core was powered down.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SleepInterval {
+    pub core_id: usize,
+    pub start_time: i32,
+    pub end_time: i32,
+}
+
+/// Sleep states and energy of the cores that power down when idle (see `power_down`).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PowerLog {
+    pub idle_threshold: i32,
+    pub wake_up_latency: i32,
+    /// Nodes dispatched to a core asleep, each delayed by the wake-up latency.
+    pub wake_ups: usize,
+    pub sleep_time: i32,
+    /// In units of the power of a busy core over a time unit.
+    pub energy: f32,
+    pub sleep_intervals: Vec<SleepInterval>,
+}
+
+/// Frequency and energy of each core of a DVFS processor (see `dvfs`).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EnergyLog {
+    pub idle_power: f32,
+    /// In units of the power of a busy core at frequency 1 over a time unit.
+    pub total_energy: f32,
+    pub core_energy_logs: Vec<CoreEnergyLog>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CoreEnergyLog {
+    pub core_id: usize,
+    pub frequency: f32,
+    pub busy_time: i32,
+    pub energy: f32,
+}
+
+/// Steals of a core of the work-stealing scheduler (see `work_stealing_scheduler`).
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CoreStealLog {
+    pub core_id: usize,
+    /// Steal attempts started by the core, successful or not.
+    pub attempts: usize,
+    /// Attempts that took a node from the victim.
+    pub steals: usize,
+    /// Time spent in steal attempts instead of executing nodes.
+    pub steal_time: i32,
+    /// Nodes stolen from the deque of the core by others.
+    pub stolen_from: usize,
+}
+
+/// Jobs admitted and rejected by the online admission control (see `admission_control`).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Getters, CopyGetters)]
+pub struct AdmissionLog {
+    #[get_copy = "pub with_prefix"]
+    test: AdmissionTest,
+    #[get_copy = "pub with_prefix"]
+    admitted_jobs: usize,
+    #[get_copy = "pub with_prefix"]
+    rejected_jobs: usize,
+    /// Rejected jobs of each DAG by `dag_id`.
+    #[get = "pub with_prefix"]
+    rejected_jobs_per_dag: Vec<usize>,
+    /// Share of the released jobs that were rejected.
+    #[get_copy = "pub with_prefix"]
+    rejection_ratio: f32,
+}
+
+impl AdmissionLog {
+    fn new(test: AdmissionTest, number_of_dags: usize) -> Self {
+        Self {
+            test,
+            admitted_jobs: 0,
+            rejected_jobs: 0,
+            rejected_jobs_per_dag: vec![0; number_of_dags],
+            rejection_ratio: 0.0,
+        }
+    }
+
+    fn calculate_rejection_ratio(&mut self) {
+        self.rejection_ratio =
+            self.rejected_jobs as f32 / (self.admitted_jobs + self.rejected_jobs) as f32;
+    }
+}
+
+/// Response times of the aperiodic jobs served by an aperiodic server.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct AperiodicLog {
+    response_time: Vec<i32>,
+    average_response_time: f32,
+    worst_response_time: i32,
+    /// Jobs not completed by the end of the simulation.
+    unfinished_jobs: usize,
+}
+
+impl AperiodicLog {
+    fn calculate_response_time(&mut self) {
+        if !self.response_time.is_empty() {
+            self.average_response_time =
+                self.response_time.iter().sum::<i32>() as f32 / self.response_time.len() as f32;
+        }
+        self.worst_response_time = self.response_time.iter().max().copied().unwrap_or(0);
+    }
+
+    pub fn get_response_time(&self) -> &[i32] {
+        &self.response_time
+    }
+
+    pub fn get_worst_response_time(&self) -> i32 {
+        self.worst_response_time
+    }
+
+    pub fn get_unfinished_jobs(&self) -> usize {
+        self.unfinished_jobs
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ServerEventKind {
+    /// A job arrived at an idle server whose budget could not be used by its deadline, so the
+    /// budget was recharged with a new deadline one period later.
+    Replenished,
+    /// The budget was exhausted and recharged with the deadline postponed by a period.
+    Depleted,
+}
+
+/// Budget and deadline of a constant bandwidth server after an event (see `cbs_scheduler`).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServerEvent {
+    /// The served DAG, or `None` for the server of aperiodic jobs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dag_id: Option<usize>,
+    pub time: i32,
+    pub kind: ServerEventKind,
+    pub budget: i32,
+    pub deadline: i32,
+}
+
+/// A periodic release that could not happen on time because the previous job was still running,
+/// or a job dropped in the HI-criticality mode.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReleaseEvent {
+    /// The job is dropped.
+    Skipped { release_time: i32 },
+    /// The job waits for the previous job to complete (DAGs with feedback edges).
+    Queued { release_time: i32 },
+    /// The queued job is released `delay` after its release time.
+    LateStart {
+        release_time: i32,
+        start_time: i32,
+        delay: i32,
+    },
+    /// The job of a LO-criticality DAG is dropped in the HI-criticality mode.
+    Dropped { release_time: i32 },
+    /// The job fails the admission test (see `admission_control`).
+    Rejected { release_time: i32 },
+}
+
+impl ReleaseEvent {
+    pub fn get_release_time(&self) -> i32 {
+        match self {
+            ReleaseEvent::Skipped { release_time }
+            | ReleaseEvent::Queued { release_time }
+            | ReleaseEvent::LateStart { release_time, .. }
+            | ReleaseEvent::Dropped { release_time }
+            | ReleaseEvent::Rejected { release_time } => *release_time,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum JobEventTimes {
     StartTime(i32),
     ResumeTime(i32),
@@ -141,13 +783,56 @@ pub enum JobEventTimes {
     PreemptedTime(i32),
 }
 
-#[derive(Clone, Serialize, Deserialize)]
+impl JobEventTimes {
+    pub fn get_time(&self) -> i32 {
+        match self {
+            JobEventTimes::StartTime(time)
+            | JobEventTimes::ResumeTime(time)
+            | JobEventTimes::FinishTime(time)
+            | JobEventTimes::PreemptedTime(time) => *time,
+        }
+    }
+}
+
+/// Restrict a log to some DAGs and a time window when dumping it,
+/// e.g., to debug a single deadline miss in a large run.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LogFilter {
+    /// Keep only the DAGs with these ids.
+    pub dag_ids: Option<RangeInclusive<usize>>,
+    /// Keep only the jobs released and the job events in this window.
+    pub time_window: Option<RangeInclusive<i32>>,
+}
+
+impl LogFilter {
+    fn contains_dag(&self, dag_id: usize) -> bool {
+        self.dag_ids
+            .as_ref()
+            .is_none_or(|dag_ids| dag_ids.contains(&dag_id))
+    }
+
+    fn contains_time(&self, time: i32) -> bool {
+        self.time_window
+            .as_ref()
+            .is_none_or(|time_window| time_window.contains(&time))
+    }
+}
+
+#[derive(Clone, Debug, CopyGetters, Getters, Serialize, Deserialize)]
 pub struct JobLog {
+    #[get_copy = "pub with_prefix"]
     core_id: usize,
+    #[get_copy = "pub with_prefix"]
     dag_id: usize, // Used to distinguish DAGs when the scheduler input is DAGSet
+    #[get_copy = "pub with_prefix"]
     node_id: usize,
+    #[get_copy = "pub with_prefix"]
     job_id: usize,
+    #[get = "pub with_prefix"]
     event_time: JobEventTimes,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[get = "pub with_prefix"]
+    node_name: Option<String>,
 }
 
 impl JobLog {
@@ -165,6 +850,7 @@ impl JobLog {
             node_id,
             job_id,
             event_time,
+            node_name: None,
         }
     }
 }
@@ -173,6 +859,9 @@ impl JobLog {
 pub struct ProcessorLog {
     average_utilization: f32,
     variance_utilization: f32,
+    /// The policy choosing among idle cores, which affects the variance. Not set if first fit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    idle_core_selection: Option<IdleCoreSelection>,
     core_logs: Vec<CoreLog>,
 }
 
@@ -181,26 +870,23 @@ impl ProcessorLog {
         Self {
             average_utilization: Default::default(),
             variance_utilization: Default::default(),
+            idle_core_selection: None,
             core_logs: (0..num_cores).map(CoreLog::new).collect(),
         }
     }
 
     fn calculate_average_utilization(&mut self) {
-        self.average_utilization = self
-            .core_logs
-            .iter()
-            .map(|core_log| core_log.utilization)
-            .sum::<f32>()
-            / self.core_logs.len() as f32;
+        self.average_utilization =
+            sum_in_fixed_order(self.core_logs.iter().map(|core_log| core_log.utilization))
+                / self.core_logs.len() as f32;
     }
 
     fn calculate_variance_utilization(&mut self) {
-        self.variance_utilization = self
-            .core_logs
-            .iter()
-            .map(|core_log| (core_log.utilization - self.average_utilization).powi(2))
-            .sum::<f32>()
-            / self.core_logs.len() as f32;
+        self.variance_utilization = sum_in_fixed_order(
+            self.core_logs
+                .iter()
+                .map(|core_log| (core_log.utilization - self.average_utilization).powi(2)),
+        ) / self.core_logs.len() as f32;
     }
 
     fn calculate_cores_utilization(&mut self, schedule_length: i32) {
@@ -215,6 +901,17 @@ pub struct CoreLog {
     core_id: usize,
     total_proc_time: i32,
     utilization: f32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    utilization_cap: Option<f32>,
+    /// Time units in which the core was throttled by its utilization cap.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    throttled_time: Option<i32>,
+    /// Time units stolen from the DAGs by interrupt bursts on the core.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    stolen_time: Option<i32>,
+    /// The last time a job finished or was preempted on the core.
+    #[serde(skip)]
+    last_idle_time: Option<i32>,
 }
 
 impl CoreLog {
@@ -223,6 +920,10 @@ impl CoreLog {
             core_id,
             total_proc_time: Default::default(),
             utilization: Default::default(),
+            utilization_cap: None,
+            throttled_time: None,
+            stolen_time: None,
+            last_idle_time: None,
         }
     }
 
@@ -231,55 +932,344 @@ impl CoreLog {
     }
 }
 
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct CoreShareLog {
+    fairness_index: f32,
+    each_dag_share: Vec<DAGShareLog>,
+}
+
+impl CoreShareLog {
+    fn new(num_dags: usize) -> Self {
+        Self {
+            fairness_index: Default::default(),
+            each_dag_share: (0..num_dags).map(DAGShareLog::new).collect(),
+        }
+    }
+
+    /// Compare the core time each DAG received with its utilization entitlement.
+    ///
+    /// The processing time of each DAG is restored from the job events, and the entitlement of
+    /// each DAG is `volume / period` normalized by the sum over the DAG set.
+    /// `fairness_index` is Jain's fairness index over `share_ratio` (1.0 means perfectly fair).
+    fn calculate_core_share(&mut self, dag_set_info: &DAGSetInfo, node_set_logs: &[Vec<JobLog>]) {
+        for (dag_share, node_logs) in self.each_dag_share.iter_mut().zip(node_set_logs) {
+            dag_share.total_proc_time = get_total_proc_time_from_job_logs(node_logs);
+        }
+        let total_proc_time: i32 = self
+            .each_dag_share
+            .iter()
+            .map(|dag_share| dag_share.total_proc_time)
+            .sum();
+        let demands: Vec<f32> = dag_set_info
+            .each_dag_info
+            .iter()
+            .map(|dag_info| match dag_info.get_period() {
+                0 => 0.0,
+                period => dag_info.get_volume() as f32 / period as f32,
+            })
+            .collect();
+        let total_demand = sum_in_fixed_order(demands.iter().copied());
+
+        for (dag_share, demand) in self.each_dag_share.iter_mut().zip(demands) {
+            dag_share.core_time_share = if total_proc_time == 0 {
+                0.0
+            } else {
+                dag_share.total_proc_time as f32 / total_proc_time as f32
+            };
+            dag_share.entitlement = if total_demand == 0.0 {
+                0.0
+            } else {
+                demand / total_demand
+            };
+            dag_share.share_ratio = if dag_share.entitlement == 0.0 {
+                0.0
+            } else {
+                dag_share.core_time_share / dag_share.entitlement
+            };
+        }
+
+        let ratios: Vec<f32> = self
+            .each_dag_share
+            .iter()
+            .map(|dag_share| dag_share.share_ratio)
+            .collect();
+        let sum_of_squares = sum_in_fixed_order(ratios.iter().map(|ratio| ratio.powi(2)));
+        self.fairness_index = if sum_of_squares == 0.0 {
+            0.0
+        } else {
+            sum_in_fixed_order(ratios.iter().copied()).powi(2)
+                / (ratios.len() as f32 * sum_of_squares)
+        };
+    }
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct DAGShareLog {
+    dag_id: usize,
+    total_proc_time: i32,
+    core_time_share: f32,
+    entitlement: f32,
+    share_ratio: f32,
+}
+
+impl DAGShareLog {
+    fn new(dag_id: usize) -> Self {
+        Self {
+            dag_id,
+            total_proc_time: Default::default(),
+            core_time_share: Default::default(),
+            entitlement: Default::default(),
+            share_ratio: Default::default(),
+        }
+    }
+}
+
+/// A continuous execution of a job on a core, restored from the job events.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExecutionInterval {
+    pub core_id: usize,
+    pub dag_id: usize,
+    pub node_id: usize,
+    pub job_id: usize,
+    pub begin_time: i32,
+    pub end_time: i32,
+}
+
+/// Pair each start/resume event with the following finish/preempted event of the same job.
+pub fn get_execution_intervals(job_logs: &[JobLog]) -> Vec<ExecutionInterval> {
+    let mut begin_times = BTreeMap::new();
+    let mut intervals = Vec::new();
+    for job_log in job_logs {
+        let key = (job_log.dag_id, job_log.node_id, job_log.job_id);
+        match job_log.event_time {
+            JobEventTimes::StartTime(time) | JobEventTimes::ResumeTime(time) => {
+                begin_times.insert(key, time);
+            }
+            JobEventTimes::FinishTime(time) | JobEventTimes::PreemptedTime(time) => {
+                if let Some(begin_time) = begin_times.remove(&key) {
+                    intervals.push(ExecutionInterval {
+                        core_id: job_log.core_id,
+                        dag_id: job_log.dag_id,
+                        node_id: job_log.node_id,
+                        job_id: job_log.job_id,
+                        begin_time,
+                        end_time: time,
+                    });
+                }
+            }
+        }
+    }
+    intervals
+}
+
+/// An entry of the run-length encoded trace of a core.
+/// An entry without a node is an idle period.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TraceEntry {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dag_id: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node_id: Option<usize>,
+    pub duration: i32,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub idle: bool,
+}
+
+/// The predecessor whose completion made a job of a node ready.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReadinessGate {
+    pub dag_id: usize,
+    pub node_id: usize,
+    pub job_id: usize,
+    /// The last-finishing predecessor. Ties are broken by the smallest node id.
+    pub gating_node_id: usize,
+    pub ready_time: i32,
+    /// Time from the completion of the first-finishing predecessor to `ready_time`.
+    pub wait_time: i32,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CoreTrace {
+    pub core_id: usize,
+    pub entries: Vec<TraceEntry>,
+}
+
+/// Run-length encode the execution intervals on `core_id` over `[0, schedule_length)`.
+/// Consecutive executions of the same node are merged into a single entry.
+pub fn get_core_trace(
+    intervals: &[ExecutionInterval],
+    core_id: usize,
+    schedule_length: i32,
+) -> CoreTrace {
+    let mut core_intervals: Vec<&ExecutionInterval> = intervals
+        .iter()
+        .filter(|interval| interval.core_id == core_id)
+        .collect();
+    core_intervals.sort_by_key(|interval| interval.begin_time);
+
+    let mut entries: Vec<TraceEntry> = Vec::new();
+    let mut push_entry = |entry: TraceEntry| {
+        if entry.duration <= 0 {
+            return;
+        }
+        match entries.last_mut() {
+            Some(last)
+                if last.dag_id == entry.dag_id
+                    && last.node_id == entry.node_id
+                    && last.idle == entry.idle =>
+            {
+                last.duration += entry.duration
+            }
+            _ => entries.push(entry),
+        }
+    };
+    let idle_entry = |duration| TraceEntry {
+        dag_id: None,
+        node_id: None,
+        duration,
+        idle: true,
+    };
+
+    let mut current_time = 0;
+    for interval in core_intervals {
+        push_entry(idle_entry(interval.begin_time - current_time));
+        push_entry(TraceEntry {
+            dag_id: Some(interval.dag_id),
+            node_id: Some(interval.node_id),
+            duration: interval.end_time - interval.begin_time,
+            idle: false,
+        });
+        current_time = interval.end_time;
+    }
+    push_entry(idle_entry(schedule_length - current_time));
+
+    CoreTrace { core_id, entries }
+}
+
+fn get_total_proc_time_from_job_logs(job_logs: &[JobLog]) -> i32 {
+    get_execution_intervals(job_logs)
+        .iter()
+        .map(|interval| interval.end_time - interval.begin_time)
+        .sum()
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct DecisionLog {
+    ready_queue_insertions: u64,
+    ready_queue_removals: u64,
+    comparisons: u64,
+    total_operations: u64,
+    operations_per_time_unit: f32,
+    /// Seed of the random dispatch baseline. Not set if the head of the ready queue is dispatched.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    random_dispatch_seed: Option<u64>,
+    /// Whether the nodes on the critical path of their DAG precede the others of the same deadline.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    critical_path_boost: bool,
+    /// Policy deciding whether a core may idle with ready nodes. Not set if work-conserving.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    idle_insertion_policy: Option<IdleInsertionPolicy>,
+    /// Order of the ready nodes with the same deadline. Not set if by `dag_id` and node id.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tie_breaking_policy: Option<TieBreakingPolicy>,
+    /// Heuristic assigning the fixed priorities of the DAGs. Not set if the priorities are given
+    /// or rate-monotonic.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    priority_assignment: Option<PriorityAssignment>,
+}
+
+impl DecisionLog {
+    fn calculate_operations_per_time_unit(&mut self, schedule_length: i32) {
+        self.total_operations =
+            self.ready_queue_insertions + self.ready_queue_removals + self.comparisons;
+        self.operations_per_time_unit = if schedule_length == 0 {
+            0.0
+        } else {
+            self.total_operations as f32 / schedule_length as f32
+        };
+    }
+}
+
 #[derive(Clone, Default, Serialize, Deserialize)]
 pub struct DAGSchedulerLog {
     dag_info: DAGInfo,
     processor_info: ProcessorInfo,
     node_logs: Vec<JobLog>,
     processor_log: ProcessorLog,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    dag_name: Option<String>,
+    #[serde(skip)]
+    node_names: BTreeMap<i32, String>,
 }
 
 impl DAGSchedulerLog {
     pub fn new(dag: &Graph<NodeData, i32>, num_cores: usize) -> Self {
         Self {
-            dag_info: DAGInfo::new(dag),
+            dag_info: DAGInfo::new(&DagTask::from_graph(dag)),
             processor_info: ProcessorInfo::new(num_cores),
             node_logs: Vec::new(),
             processor_log: ProcessorLog::new(num_cores),
+            dag_name: None,
+            node_names: BTreeMap::new(),
         }
     }
 
+    /// Name the DAG and the node of each job log, including those to be written.
+    pub fn write_dag_names(&mut self, dag_names: DAGNames) {
+        for job_log in self.node_logs.iter_mut() {
+            job_log.node_name = dag_names
+                .get_node_name(job_log.node_id as i32)
+                .map(str::to_string);
+        }
+        self.dag_name = dag_names.dag_name;
+        self.node_names = dag_names.node_names;
+    }
+
     pub fn write_allocating_job(
         &mut self,
         node_data: &NodeData,
         core_id: usize,
         current_time: i32,
     ) {
-        let job_log = JobLog::new(
+        if node_data.params.contains_key("is_preempted") {
+            self.write_job_event(node_data, core_id, JobEventTimes::ResumeTime(current_time));
+        } else {
+            self.write_job_event(node_data, core_id, JobEventTimes::StartTime(current_time));
+        }
+    }
+
+    pub fn write_preempted_job(&mut self, node_data: &NodeData, core_id: usize, current_time: i32) {
+        self.write_job_event(
+            node_data,
+            core_id,
+            JobEventTimes::PreemptedTime(current_time),
+        );
+    }
+
+    fn write_job_event(&mut self, node_data: &NodeData, core_id: usize, event_time: JobEventTimes) {
+        let mut job_log = JobLog::new(
             core_id,
             0, // This is a fixed value because DAG is only one.
             node_data.id as usize,
             0, // This is a fixed value because DAG is released only once.
-            JobEventTimes::StartTime(current_time),
+            event_time,
         );
+        job_log.node_name = self.node_names.get(&node_data.id).cloned();
         self.node_logs.push(job_log);
     }
 
     pub fn write_processing_time(&mut self, core_indices: &[usize]) {
+        self.write_processing_time_for(core_indices, 1);
+    }
+
+    pub fn write_processing_time_for(&mut self, core_indices: &[usize], time: i32) {
         for core_index in core_indices {
-            self.processor_log.core_logs[*core_index].total_proc_time += 1;
+            self.processor_log.core_logs[*core_index].total_proc_time += time;
         }
     }
 
     pub fn write_finishing_job(&mut self, node_data: &NodeData, core_id: usize, current_time: i32) {
-        let job_log = JobLog::new(
-            core_id,
-            0, // This is a fixed value because DAG is only one.
-            node_data.id as usize,
-            0, // This is a fixed value because DAG is released only once.
-            JobEventTimes::FinishTime(current_time),
-        );
-        self.node_logs.push(job_log);
+        self.write_job_event(node_data, core_id, JobEventTimes::FinishTime(current_time));
     }
 
     pub fn calculate_utilization(&mut self, schedule_length: i32) {
@@ -289,6 +1279,7 @@ impl DAGSchedulerLog {
         self.processor_log.calculate_variance_utilization();
     }
 
+    #[cfg(feature = "yaml")]
     pub fn dump_log_to_yaml(&self, file_path: &str) {
         dump_struct(file_path, self);
     }
@@ -301,10 +1292,87 @@ pub struct DAGSetSchedulerLog {
     dag_set_log: Vec<DAGLog>,
     node_set_logs: Vec<Vec<JobLog>>,
     processor_log: ProcessorLog,
+    core_share_log: CoreShareLog,
+    decision_log: DecisionLog,
+    #[cfg(feature = "analysis")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    core_recommendation: Option<CoreRecommendation>,
+    #[cfg(feature = "analysis")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    breakdown_factor: Option<BreakdownFactor>,
+    /// Run-length encoded schedule of each core, written only on request.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    core_traces: Option<Vec<CoreTrace>>,
+    /// Gating predecessor of each job of each node with predecessors, written only on request.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    readiness_gates: Option<Vec<ReadinessGate>>,
+    /// Whether the simulation was interrupted before the end of the hyper period.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    truncated: bool,
+    /// Not serialized so that logs of the same simulation are identical.
+    #[serde(skip)]
+    started_at: Option<Instant>,
+    #[serde(skip)]
+    resource_usage: Option<ResourceUsage>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    aperiodic_log: Option<AperiodicLog>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    server_log: Vec<ServerEvent>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    degradation_log: Vec<DegradationDecision>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    migration_log: Vec<MigrationEvent>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    fault_log: Vec<FaultEvent>,
+    /// Written only with fault injection.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    reliability: Vec<DAGReliability>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    inheritance_log: Vec<InheritanceEvent>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    blocking_log: Vec<BlockingEvent>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    mode_change_log: Vec<ModeChangeEvent>,
+    /// Factor of the virtual deadlines of EDF-VD.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    virtual_deadline_factor: Option<f32>,
+    /// Custom metrics computed by a post-processing script.
+    #[cfg(feature = "plugin")]
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    plugin_metrics: BTreeMap<String, serde_json::Value>,
+    /// Buffers of the edges of dataflow DAGs.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    buffer_logs: Vec<BufferLog>,
+    #[cfg(feature = "yaml")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    quantization: Option<QuantizationLog>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    partitioning: Option<PartitioningLog>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    clustering: Option<ClusteringLog>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    federated_allocation: Option<FederatedAllocation>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    qps_allocation: Option<QpsAllocation>,
+    /// Kept whole by `filter`, as the statistics.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    admission_log: Option<AdmissionLog>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    power_log: Option<PowerLog>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    energy_log: Option<EnergyLog>,
+    /// Kept whole by `filter`, as the statistics.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    steal_logs: Vec<CoreStealLog>,
+    /// Names of each DAG. The node names are written into the job logs.
+    #[serde(skip)]
+    dag_names: Vec<DAGNames>,
 }
 
 impl DAGSetSchedulerLog {
+    /// The logs of each DAG are indexed by `dag_id`, which is validated here.
     pub fn new(dag_set: &[Graph<NodeData, i32>], num_cores: usize) -> Self {
+        validate_dag_ids(dag_set);
         let mut dag_set_log = Vec::with_capacity(dag_set.len());
         for i in 0..dag_set.len() {
             dag_set_log.push(DAGLog::new(i));
@@ -316,21 +1384,606 @@ impl DAGSetSchedulerLog {
             dag_set_log,
             node_set_logs: vec![Vec::new(); dag_set.len()],
             processor_log: ProcessorLog::new(num_cores),
+            core_share_log: CoreShareLog::new(dag_set.len()),
+            decision_log: DecisionLog::default(),
+            #[cfg(feature = "analysis")]
+            core_recommendation: None,
+            #[cfg(feature = "analysis")]
+            breakdown_factor: None,
+            core_traces: None,
+            readiness_gates: None,
+            truncated: false,
+            started_at: None,
+            resource_usage: None,
+            aperiodic_log: None,
+            server_log: Vec::new(),
+            degradation_log: Vec::new(),
+            migration_log: Vec::new(),
+            fault_log: Vec::new(),
+            reliability: Vec::new(),
+            inheritance_log: Vec::new(),
+            blocking_log: Vec::new(),
+            mode_change_log: Vec::new(),
+            virtual_deadline_factor: None,
+            #[cfg(feature = "plugin")]
+            plugin_metrics: BTreeMap::new(),
+            buffer_logs: Vec::new(),
+            #[cfg(feature = "yaml")]
+            quantization: None,
+            partitioning: None,
+            clustering: None,
+            federated_allocation: None,
+            qps_allocation: None,
+            admission_log: None,
+            power_log: None,
+            energy_log: None,
+            steal_logs: Vec::new(),
+            dag_names: Vec::new(),
         }
     }
 
-    pub fn write_dag_release_time(&mut self, dag_id: usize, release_time: i32) {
-        self.dag_set_log[dag_id].release_time.push(release_time);
+    /// Name the DAGs and the node of each job log, including those to be written.
+    pub fn write_dag_names(&mut self, dag_names: &[DAGNames]) {
+        if dag_names.len() != self.dag_set_log.len() {
+            panic!(
+                "The number of DAG names {} does not match the number of DAGs {}.",
+                dag_names.len(),
+                self.dag_set_log.len()
+            );
+        }
+        for (dag_log, names) in self.dag_set_log.iter_mut().zip(dag_names) {
+            dag_log.dag_name = names.dag_name.clone();
+        }
+        for (job_logs, names) in self.node_set_logs.iter_mut().zip(dag_names) {
+            for job_log in job_logs.iter_mut() {
+                job_log.node_name = names
+                    .get_node_name(job_log.node_id as i32)
+                    .map(str::to_string);
+            }
+        }
+        self.dag_names = dag_names.to_vec();
     }
 
-    pub fn write_dag_finish_time(&mut self, dag_id: usize, finish_time: i32) {
-        self.dag_set_log[dag_id].finish_time.push(finish_time);
+    pub fn get_dag_name(&self, dag_id: usize) -> Option<&str> {
+        self.get_dag_log(dag_id).dag_name.as_deref()
     }
 
-    pub fn write_allocating_job(
+    pub fn get_node_name(&self, dag_id: usize, node_id: usize) -> Option<&str> {
+        self.dag_names
+            .get(dag_id)
+            .and_then(|names| names.get_node_name(node_id as i32))
+    }
+
+    /// Recalculate the DAG set info for processors whose cores run at different speeds.
+    pub fn write_speed_models(
         &mut self,
-        node_data: &NodeData,
-        core_id: usize,
+        dag_set: &[Graph<NodeData, i32>],
+        speed_models: &[SpeedModel],
+    ) {
+        self.dag_set_info = DAGSetInfo::new_with_speed_models(dag_set, speed_models);
+    }
+
+    /// A copy of the log with the DAGs and the events outside `filter` removed.
+    ///
+    /// The statistics (utilization, worst response times, core share, ...) and the core traces
+    /// are kept as calculated over the whole simulation.
+    pub fn filter(&self, filter: &LogFilter) -> Self {
+        let mut log = self.clone();
+        log.dag_set_info.each_dag_info = self
+            .dag_set_info
+            .each_dag_info
+            .iter()
+            .filter(|dag_info| {
+                dag_info
+                    .dag_id
+                    .is_some_and(|dag_id| filter.contains_dag(dag_id))
+            })
+            .cloned()
+            .collect();
+        if let Some(adapted_periods) = &mut log.dag_set_info.adapted_periods {
+            *adapted_periods = adapted_periods
+                .iter()
+                .enumerate()
+                .filter(|&(dag_id, _)| filter.contains_dag(dag_id))
+                .map(|(_, &adapted_period)| adapted_period)
+                .collect();
+        }
+        // The job logs of each DAG stay at the index of its DAG log.
+        log.node_set_logs = self
+            .dag_set_log
+            .iter()
+            .zip(&self.node_set_logs)
+            .filter(|(dag_log, _)| filter.contains_dag(dag_log.dag_id))
+            .map(|(_, job_logs)| job_logs.clone())
+            .collect();
+        log.dag_set_log
+            .retain(|dag_log| filter.contains_dag(dag_log.dag_id));
+        if let Some(time_window) = &filter.time_window {
+            for dag_log in log.dag_set_log.iter_mut() {
+                dag_log.retain_jobs(time_window);
+            }
+        }
+        for job_logs in log.node_set_logs.iter_mut() {
+            job_logs.retain(|job_log| filter.contains_time(job_log.event_time.get_time()));
+        }
+        log.degradation_log.retain(|degradation_decision| {
+            filter.contains_dag(degradation_decision.dag_id)
+                && filter.contains_time(degradation_decision.time)
+        });
+        log.migration_log.retain(|migration_event| {
+            filter.contains_dag(migration_event.dag_id)
+                && filter.contains_time(migration_event.time)
+        });
+        log.fault_log.retain(|fault_event| {
+            filter.contains_dag(fault_event.dag_id) && filter.contains_time(fault_event.time)
+        });
+        log.reliability
+            .retain(|dag_reliability| filter.contains_dag(dag_reliability.dag_id));
+        if let Some(readiness_gates) = &mut log.readiness_gates {
+            readiness_gates.retain(|readiness_gate| {
+                filter.contains_dag(readiness_gate.dag_id)
+                    && filter.contains_time(readiness_gate.ready_time)
+            });
+        }
+        log.server_log.retain(|server_event| {
+            server_event
+                .dag_id
+                .is_none_or(|dag_id| filter.contains_dag(dag_id))
+                && filter.contains_time(server_event.time)
+        });
+        log.inheritance_log.retain(|inheritance_event| {
+            filter.contains_dag(inheritance_event.triggered_dag_id)
+                && filter.contains_time(inheritance_event.time)
+        });
+        log.blocking_log.retain(|blocking_event| {
+            filter.contains_dag(blocking_event.dag_id) && filter.contains_time(blocking_event.time)
+        });
+        log.mode_change_log
+            .retain(|mode_change_event| filter.contains_time(mode_change_event.time));
+        if let Some(power_log) = &mut log.power_log {
+            power_log
+                .sleep_intervals
+                .retain(|sleep_interval| filter.contains_time(sleep_interval.start_time));
+        }
+        log.buffer_logs
+            .retain(|buffer_log| filter.contains_dag(buffer_log.dag_id));
+        for buffer_log in log.buffer_logs.iter_mut() {
+            buffer_log
+                .states
+                .retain(|buffer_state| filter.contains_time(buffer_state.time));
+        }
+        log.core_share_log
+            .each_dag_share
+            .retain(|dag_share| filter.contains_dag(dag_share.dag_id));
+        log
+    }
+
+    /// Index of the DAG log and the job logs of `dag_id`, which is not `dag_id` in a log filtered
+    /// by DAG ids.
+    fn get_dag_log_index(&self, dag_id: usize) -> usize {
+        self.dag_set_log
+            .binary_search_by_key(&dag_id, |dag_log| dag_log.dag_id)
+            .unwrap_or_else(|_| panic!("DAG {} is not in the log.", dag_id))
+    }
+
+    fn get_dag_log(&self, dag_id: usize) -> &DAGLog {
+        &self.dag_set_log[self.get_dag_log_index(dag_id)]
+    }
+
+    fn get_dag_log_mut(&mut self, dag_id: usize) -> &mut DAGLog {
+        let dag_log_i = self.get_dag_log_index(dag_id);
+        &mut self.dag_set_log[dag_log_i]
+    }
+
+    fn get_dag_info(&self, dag_id: usize) -> &DAGInfo {
+        self.dag_set_info
+            .each_dag_info
+            .iter()
+            .find(|dag_info| dag_info.dag_id == Some(dag_id))
+            .unwrap_or_else(|| panic!("DAG {} is not in the log.", dag_id))
+    }
+
+    pub fn get_worst_response_time(&self, dag_id: usize) -> i32 {
+        self.get_dag_log(dag_id).worst_response_time
+    }
+
+    pub fn get_worst_response_times(&self) -> Vec<i32> {
+        self.dag_set_log
+            .iter()
+            .map(|dag_log| dag_log.worst_response_time)
+            .collect()
+    }
+
+    pub fn get_throttled_time(&self, dag_id: usize) -> i32 {
+        self.get_dag_log(dag_id).throttled_time
+    }
+
+    pub fn write_throttled_time(&mut self, dag_id: usize) {
+        self.get_dag_log_mut(dag_id).throttled_time += 1;
+    }
+
+    pub fn get_gang_waiting_time(&self, dag_id: usize) -> i32 {
+        self.get_dag_log(dag_id).gang_waiting_time
+    }
+
+    pub fn write_gang_waiting_time(&mut self, dag_id: usize) {
+        self.get_dag_log_mut(dag_id).gang_waiting_time += 1;
+    }
+
+    pub fn get_release_events(&self, dag_id: usize) -> &[ReleaseEvent] {
+        &self.get_dag_log(dag_id).release_events
+    }
+
+    pub fn write_release_event(&mut self, dag_id: usize, release_event: ReleaseEvent) {
+        self.get_dag_log_mut(dag_id)
+            .release_events
+            .push(release_event);
+    }
+
+    pub fn get_release_times(&self, dag_id: usize) -> &[i32] {
+        &self.get_dag_log(dag_id).release_time
+    }
+
+    pub fn get_period(&self, dag_id: usize) -> i32 {
+        self.get_dag_info(dag_id).period
+    }
+
+    pub fn get_end_to_end_deadline(&self, dag_id: usize) -> i32 {
+        self.get_dag_info(dag_id).end_to_end_deadline
+    }
+
+    pub fn write_dag_release_time(&mut self, dag_id: usize, release_time: i32) {
+        self.get_dag_log_mut(dag_id).release_time.push(release_time);
+    }
+
+    pub fn write_dag_finish_time(&mut self, dag_id: usize, finish_time: i32) {
+        self.get_dag_log_mut(dag_id).finish_time.push(finish_time);
+    }
+
+    /// Withdraw the release time of the unfinished job of the DAG, which has no response time.
+    pub fn write_dropped_job(&mut self, dag_id: usize) {
+        let dag_log = &mut self.get_dag_log_mut(dag_id);
+        if dag_log.release_time.len() > dag_log.finish_time.len() {
+            let release_time = dag_log.release_time.pop().unwrap();
+            dag_log
+                .release_events
+                .push(ReleaseEvent::Dropped { release_time });
+        }
+    }
+
+    pub fn get_execution_intervals(&self) -> Vec<ExecutionInterval> {
+        self.node_set_logs
+            .iter()
+            .flat_map(|job_logs| get_execution_intervals(job_logs))
+            .collect()
+    }
+
+    /// The job events of all DAGs by time. At the same time, finishes and preemptions come
+    /// before starts and resumes.
+    pub fn get_job_logs_in_time_order(&self) -> Vec<&JobLog> {
+        let mut job_logs: Vec<&JobLog> = self.node_set_logs.iter().flatten().collect();
+        job_logs.sort_by_key(|job_log| {
+            (
+                job_log.event_time.get_time(),
+                matches!(
+                    job_log.event_time,
+                    JobEventTimes::StartTime(_) | JobEventTimes::ResumeTime(_)
+                ),
+            )
+        });
+        job_logs
+    }
+
+    #[cfg(feature = "yaml")]
+    pub fn write_quantization(&mut self, quantization: QuantizationLog) {
+        self.quantization = Some(quantization);
+    }
+
+    #[cfg(feature = "yaml")]
+    pub fn get_quantization(&self) -> Option<&QuantizationLog> {
+        self.quantization.as_ref()
+    }
+
+    pub fn write_partitioning(&mut self, partitioning: PartitioningLog) {
+        self.partitioning = Some(partitioning);
+    }
+
+    pub fn get_partitioning(&self) -> Option<&PartitioningLog> {
+        self.partitioning.as_ref()
+    }
+
+    pub fn write_clustering(&mut self, clustering: ClusteringLog) {
+        self.clustering = Some(clustering);
+    }
+
+    pub fn get_clustering(&self) -> Option<&ClusteringLog> {
+        self.clustering.as_ref()
+    }
+
+    pub fn write_federated_allocation(&mut self, federated_allocation: FederatedAllocation) {
+        self.federated_allocation = Some(federated_allocation);
+    }
+
+    pub fn get_federated_allocation(&self) -> Option<&FederatedAllocation> {
+        self.federated_allocation.as_ref()
+    }
+
+    pub fn write_qps_allocation(&mut self, qps_allocation: QpsAllocation) {
+        self.qps_allocation = Some(qps_allocation);
+    }
+
+    pub fn get_qps_allocation(&self) -> Option<&QpsAllocation> {
+        self.qps_allocation.as_ref()
+    }
+
+    pub fn write_power_log(&mut self, power_log: PowerLog) {
+        self.power_log = Some(power_log);
+    }
+
+    pub fn get_power_log(&self) -> Option<&PowerLog> {
+        self.power_log.as_ref()
+    }
+
+    pub fn write_energy_log(&mut self, energy_log: EnergyLog) {
+        self.energy_log = Some(energy_log);
+    }
+
+    pub fn get_energy_log(&self) -> Option<&EnergyLog> {
+        self.energy_log.as_ref()
+    }
+
+    /// Start counting the jobs admitted and rejected by `test`.
+    pub fn write_admission_test(&mut self, test: AdmissionTest) {
+        self.admission_log = Some(AdmissionLog::new(test, self.dag_set_log.len()));
+    }
+
+    pub fn write_admitted_job(&mut self) {
+        let admission_log = self.admission_log.as_mut().unwrap();
+        admission_log.admitted_jobs += 1;
+        admission_log.calculate_rejection_ratio();
+    }
+
+    pub fn write_rejected_job(&mut self, dag_id: usize, release_time: i32) {
+        let admission_log = self.admission_log.as_mut().unwrap();
+        admission_log.rejected_jobs += 1;
+        admission_log.rejected_jobs_per_dag[dag_id] += 1;
+        admission_log.calculate_rejection_ratio();
+        self.get_dag_log_mut(dag_id)
+            .release_events
+            .push(ReleaseEvent::Rejected { release_time });
+    }
+
+    pub fn get_admission_log(&self) -> Option<&AdmissionLog> {
+        self.admission_log.as_ref()
+    }
+
+    pub fn write_steal_logs(&mut self, steal_logs: Vec<CoreStealLog>) {
+        self.steal_logs = steal_logs;
+    }
+
+    pub fn get_steal_logs(&self) -> &[CoreStealLog] {
+        &self.steal_logs
+    }
+
+    /// Add the log of a buffer and return its index.
+    pub fn add_buffer_log(&mut self, buffer_log: BufferLog) -> usize {
+        self.buffer_logs.push(buffer_log);
+        self.buffer_logs.len() - 1
+    }
+
+    pub fn write_buffer_state(&mut self, buffer_i: usize, time: i32, tokens: i32, reserved: i32) {
+        let buffer_log = &mut self.buffer_logs[buffer_i];
+        buffer_log.max_tokens = buffer_log.max_tokens.max(tokens);
+        buffer_log.states.push(BufferState {
+            time,
+            tokens,
+            reserved,
+        });
+    }
+
+    pub fn write_buffer_blocked_time(&mut self, buffer_i: usize) {
+        self.buffer_logs[buffer_i].blocked_time += 1;
+    }
+
+    pub fn get_buffer_logs(&self) -> &[BufferLog] {
+        &self.buffer_logs
+    }
+
+    pub fn write_fault_event(&mut self, fault_event: FaultEvent) {
+        self.fault_log.push(fault_event);
+    }
+
+    pub fn get_fault_log(&self) -> &[FaultEvent] {
+        &self.fault_log
+    }
+
+    /// Summarize the fault log into the reliability of each DAG. A job is reliable if it did not
+    /// fail and its response time is within the deadline, or the period with an implicit deadline.
+    /// Call after `calculate_response_time`.
+    pub fn calculate_reliability(&mut self) {
+        self.reliability = self
+            .dag_set_log
+            .iter()
+            .map(|dag_log| {
+                let dag_id = dag_log.dag_id;
+                let deadline = match self.get_end_to_end_deadline(dag_id) {
+                    0 => self.get_period(dag_id),
+                    end_to_end_deadline => end_to_end_deadline,
+                };
+                let fault_events: Vec<&FaultEvent> = self
+                    .fault_log
+                    .iter()
+                    .filter(|fault_event| fault_event.dag_id == dag_id)
+                    .collect();
+                let failed_job_ids: BTreeSet<usize> = fault_events
+                    .iter()
+                    .filter(|fault_event| !fault_event.retried)
+                    .map(|fault_event| fault_event.job_id)
+                    .collect();
+                let jobs = dag_log.release_time.len();
+                let reliable_jobs = dag_log
+                    .response_time
+                    .iter()
+                    .take(jobs)
+                    .enumerate()
+                    .filter(|&(job_id, &response_time)| {
+                        !failed_job_ids.contains(&job_id) && response_time <= deadline
+                    })
+                    .count();
+                DAGReliability {
+                    dag_id,
+                    jobs,
+                    faults: fault_events.len(),
+                    re_executions: fault_events
+                        .iter()
+                        .filter(|fault_event| fault_event.retried)
+                        .count(),
+                    failed_jobs: failed_job_ids.len(),
+                    reliability: if jobs == 0 {
+                        1.0
+                    } else {
+                        reliable_jobs as f32 / jobs as f32
+                    },
+                }
+            })
+            .collect();
+    }
+
+    pub fn get_reliability(&self) -> &[DAGReliability] {
+        &self.reliability
+    }
+
+    pub fn write_degradation_decision(&mut self, degradation_decision: DegradationDecision) {
+        self.degradation_log.push(degradation_decision);
+    }
+
+    pub fn get_degradation_log(&self) -> &[DegradationDecision] {
+        &self.degradation_log
+    }
+
+    pub fn write_migration_event(&mut self, migration_event: MigrationEvent) {
+        self.migration_log.push(migration_event);
+    }
+
+    pub fn get_migration_log(&self) -> &[MigrationEvent] {
+        &self.migration_log
+    }
+
+    pub fn write_inheritance_event(&mut self, inheritance_event: InheritanceEvent) {
+        self.inheritance_log.push(inheritance_event);
+    }
+
+    /// Set the chain latency of the inheritance event of the finished job, if any.
+    pub fn write_chain_finish_time(&mut self, dag_id: usize, job_id: usize, finish_time: i32) {
+        if let Some(inheritance_event) = self
+            .inheritance_log
+            .iter_mut()
+            .find(|event| event.triggered_dag_id == dag_id && event.triggered_job_id == job_id)
+        {
+            inheritance_event.chain_latency =
+                Some(finish_time - inheritance_event.triggering_release_time);
+        }
+    }
+
+    pub fn get_inheritance_log(&self) -> &[InheritanceEvent] {
+        &self.inheritance_log
+    }
+
+    pub fn write_blocking_event(&mut self, blocking_event: BlockingEvent) {
+        self.blocking_log.push(blocking_event);
+    }
+
+    pub fn get_blocking_log(&self) -> &[BlockingEvent] {
+        &self.blocking_log
+    }
+
+    pub fn write_mode_change_event(&mut self, mode_change_event: ModeChangeEvent) {
+        self.mode_change_log.push(mode_change_event);
+    }
+
+    pub fn get_mode_change_log(&self) -> &[ModeChangeEvent] {
+        &self.mode_change_log
+    }
+
+    pub fn write_virtual_deadline_factor(&mut self, virtual_deadline_factor: f32) {
+        self.virtual_deadline_factor = Some(virtual_deadline_factor);
+    }
+
+    pub fn get_virtual_deadline_factor(&self) -> Option<f32> {
+        self.virtual_deadline_factor
+    }
+
+    pub fn write_aperiodic_response_time(&mut self, response_time: i32) {
+        self.aperiodic_log
+            .get_or_insert_with(Default::default)
+            .response_time
+            .push(response_time);
+    }
+
+    pub fn calculate_aperiodic_log(&mut self, unfinished_jobs: usize) {
+        let aperiodic_log = self.aperiodic_log.get_or_insert_with(Default::default);
+        aperiodic_log.unfinished_jobs = unfinished_jobs;
+        aperiodic_log.calculate_response_time();
+    }
+
+    pub fn get_aperiodic_log(&self) -> Option<&AperiodicLog> {
+        self.aperiodic_log.as_ref()
+    }
+
+    pub fn write_server_event(&mut self, server_event: ServerEvent) {
+        self.server_log.push(server_event);
+    }
+
+    pub fn get_server_log(&self) -> &[ServerEvent] {
+        &self.server_log
+    }
+
+    pub fn write_optional_execution(
+        &mut self,
+        dag_id: usize,
+        executed_time: i32,
+        optional_execution_time: i32,
+    ) {
+        let (executed, total) = &mut self.get_dag_log_mut(dag_id).optional_progress;
+        *executed += executed_time;
+        *total += optional_execution_time;
+    }
+
+    /// Record the quality of the completed job of `dag_id` if it had optional parts.
+    pub fn write_dag_quality(&mut self, dag_id: usize) {
+        let dag_log = &mut self.get_dag_log_mut(dag_id);
+        let (executed, total) = std::mem::take(&mut dag_log.optional_progress);
+        if total > 0 {
+            dag_log.quality.push(executed as f32 / total as f32);
+        }
+    }
+
+    pub fn get_quality(&self, dag_id: usize) -> &[f32] {
+        &self.get_dag_log(dag_id).quality
+    }
+
+    pub fn write_dag_slack(&mut self, dag_id: usize, slack: i32) {
+        self.get_dag_log_mut(dag_id).slack.push(slack);
+    }
+
+    /// Min and average slack of the DAGs with completed jobs.
+    pub fn get_slack_summaries(&self) -> Vec<SlackSummary> {
+        self.dag_set_log
+            .iter()
+            .filter_map(|dag_log| {
+                Some(SlackSummary {
+                    dag_id: dag_log.dag_id,
+                    min_slack: dag_log.min_slack?,
+                    average_slack: dag_log.average_slack?,
+                })
+            })
+            .collect()
+    }
+
+    pub fn write_allocating_job(
+        &mut self,
+        node_data: &NodeData,
+        core_id: usize,
         job_id: usize,
         current_time: i32,
     ) {
@@ -359,13 +2012,97 @@ impl DAGSetSchedulerLog {
         event_time: JobEventTimes,
     ) {
         let dag_id = node_data.get_params_value("dag_id") as usize;
-        let job_log = JobLog::new(core_id, dag_id, node_data.id as usize, job_id, event_time);
-        self.node_set_logs[dag_id].push(job_log);
+        if let JobEventTimes::FinishTime(time) | JobEventTimes::PreemptedTime(time) = event_time {
+            self.processor_log.core_logs[core_id].last_idle_time = Some(time);
+        }
+        let mut job_log = JobLog::new(core_id, dag_id, node_data.id as usize, job_id, event_time);
+        job_log.node_name = self
+            .get_node_name(dag_id, node_data.id as usize)
+            .map(str::to_string);
+        let dag_log_i = self.get_dag_log_index(dag_id);
+        self.node_set_logs[dag_log_i].push(job_log);
+    }
+
+    pub fn get_total_utilization(&self) -> f32 {
+        self.dag_set_info.total_utilization
+    }
+
+    pub fn write_adapted_periods(&mut self, adapted_periods: &[i32]) {
+        self.dag_set_info.adapted_periods = Some(adapted_periods.to_vec());
+    }
+
+    pub fn get_adapted_periods(&self) -> Option<&[i32]> {
+        self.dag_set_info.adapted_periods.as_deref()
+    }
+
+    pub fn get_total_proc_time(&self, core_id: usize) -> i32 {
+        self.processor_log.core_logs[core_id].total_proc_time
+    }
+
+    pub fn write_core_utilization_caps(&mut self, caps: &[f32]) {
+        for (core_log, &cap) in self.processor_log.core_logs.iter_mut().zip(caps) {
+            core_log.utilization_cap = Some(cap);
+            core_log.throttled_time = Some(0);
+        }
+    }
+
+    pub fn write_core_throttled_time(&mut self, core_id: usize) {
+        if let Some(throttled_time) = &mut self.processor_log.core_logs[core_id].throttled_time {
+            *throttled_time += 1;
+        }
+    }
+
+    pub fn get_core_throttled_time(&self, core_id: usize) -> Option<i32> {
+        self.processor_log.core_logs[core_id].throttled_time
+    }
+
+    /// Start counting the stolen time of the cores and the interference of the DAGs.
+    pub fn write_interrupt_model(&mut self) {
+        for core_log in self.processor_log.core_logs.iter_mut() {
+            core_log.stolen_time = Some(0);
+        }
+        for dag_log in self.dag_set_log.iter_mut() {
+            dag_log.interrupt_interference_time = Some(0);
+        }
+    }
+
+    /// A time unit stolen by an interrupt burst, stalling a node of `stalled_dag_id` if any.
+    pub fn write_core_stolen_time(&mut self, core_id: usize, stalled_dag_id: Option<usize>) {
+        if let Some(stolen_time) = &mut self.processor_log.core_logs[core_id].stolen_time {
+            *stolen_time += 1;
+        }
+        if let Some(interference_time) = stalled_dag_id.and_then(|dag_id| {
+            self.get_dag_log_mut(dag_id)
+                .interrupt_interference_time
+                .as_mut()
+        }) {
+            *interference_time += 1;
+        }
+    }
+
+    pub fn get_core_stolen_time(&self, core_id: usize) -> Option<i32> {
+        self.processor_log.core_logs[core_id].stolen_time
+    }
+
+    pub fn get_interrupt_interference_time(&self, dag_id: usize) -> Option<i32> {
+        self.get_dag_log(dag_id).interrupt_interference_time
+    }
+
+    pub fn get_last_idle_time(&self, core_id: usize) -> Option<i32> {
+        self.processor_log.core_logs[core_id].last_idle_time
+    }
+
+    pub fn write_idle_core_selection(&mut self, idle_core_selection: IdleCoreSelection) {
+        self.processor_log.idle_core_selection = Some(idle_core_selection);
     }
 
     pub fn write_processing_time(&mut self, core_indices: &[usize]) {
+        self.write_processing_time_for(core_indices, 1);
+    }
+
+    pub fn write_processing_time_for(&mut self, core_indices: &[usize], time: i32) {
         for core_index in core_indices {
-            self.processor_log.core_logs[*core_index].total_proc_time += 1;
+            self.processor_log.core_logs[*core_index].total_proc_time += time;
         }
     }
 
@@ -374,6 +2111,7 @@ impl DAGSetSchedulerLog {
             dag_log.calculate_response_time();
             dag_log.calculate_average_response_time();
             dag_log.calculate_worst_response_time();
+            dag_log.calculate_slack();
         }
     }
 
@@ -382,40 +2120,699 @@ impl DAGSetSchedulerLog {
             .calculate_cores_utilization(schedule_length);
         self.processor_log.calculate_average_utilization();
         self.processor_log.calculate_variance_utilization();
+        if let Some(clustering) = &mut self.clustering {
+            clustering.calculate_average_utilizations(&self.processor_log);
+        }
+    }
+
+    pub fn calculate_core_share(&mut self) {
+        self.core_share_log
+            .calculate_core_share(&self.dag_set_info, &self.node_set_logs);
+    }
+
+    pub fn write_ready_queue_insertion(&mut self) {
+        self.decision_log.ready_queue_insertions += 1;
+    }
+
+    pub fn write_random_dispatch_seed(&mut self, seed: u64) {
+        self.decision_log.random_dispatch_seed = Some(seed);
+    }
+
+    pub fn write_critical_path_boost(&mut self) {
+        self.decision_log.critical_path_boost = true;
+    }
+
+    pub fn get_critical_path_boost(&self) -> bool {
+        self.decision_log.critical_path_boost
+    }
+
+    pub fn write_idle_insertion_policy(&mut self, idle_insertion_policy: IdleInsertionPolicy) {
+        self.decision_log.idle_insertion_policy = Some(idle_insertion_policy);
+    }
+
+    pub fn get_idle_insertion_policy(&self) -> Option<IdleInsertionPolicy> {
+        self.decision_log.idle_insertion_policy
+    }
+
+    pub fn write_tie_breaking_policy(&mut self, tie_breaking_policy: TieBreakingPolicy) {
+        self.decision_log.tie_breaking_policy = Some(tie_breaking_policy);
+    }
+
+    pub fn get_tie_breaking_policy(&self) -> Option<TieBreakingPolicy> {
+        self.decision_log.tie_breaking_policy
+    }
+
+    pub fn write_priority_assignment(&mut self, priority_assignment: PriorityAssignment) {
+        self.decision_log.priority_assignment = Some(priority_assignment);
+    }
+
+    pub fn get_priority_assignment(&self) -> Option<PriorityAssignment> {
+        self.decision_log.priority_assignment
+    }
+
+    pub fn write_ready_queue_removal(&mut self) {
+        self.decision_log.ready_queue_removals += 1;
+    }
+
+    /// Start measuring the wall time of the simulation, excluding the setup of the scheduler.
+    pub fn write_schedule_start(&mut self) {
+        self.started_at = Some(Instant::now());
+    }
+
+    /// Measure the cost of the simulation since `write_schedule_start`.
+    pub fn calculate_resource_usage(&mut self) {
+        self.resource_usage = Some(ResourceUsage {
+            wall_time_ms: self
+                .started_at
+                .map_or(0, |started_at| started_at.elapsed().as_millis() as u64),
+            processed_events: self.node_set_logs.iter().map(Vec::len).sum::<usize>() as u64,
+            peak_rss_kb: get_peak_rss_kb(),
+        });
+    }
+
+    pub fn get_resource_usage(&self) -> Option<ResourceUsage> {
+        self.resource_usage
+    }
+
+    /// Add the priority comparisons counted by a ready queue (see `ReadyQueue`).
+    pub fn write_comparisons(&mut self, comparisons: u64) {
+        self.decision_log.comparisons += comparisons;
+    }
+
+    pub fn calculate_decision_overhead(&mut self, schedule_length: i32) {
+        self.decision_log
+            .calculate_operations_per_time_unit(schedule_length);
+    }
+
+    /// Check that the utilization of each core is within its cap (see
+    /// `write_core_utilization_caps`).
+    ///
+    /// # Returns
+    ///
+    /// * A description of each violation. Empty if all caps were respected.
+    pub fn verify_core_utilization_caps(&self) -> Vec<String> {
+        self.processor_log
+            .core_logs
+            .iter()
+            .filter_map(|core_log| {
+                let utilization_cap = core_log.utilization_cap?;
+                (core_log.utilization > utilization_cap + 1e-4).then(|| {
+                    format!(
+                        "Core {} has utilization {:.3} above its cap {:.3}.",
+                        core_log.core_id, core_log.utilization, utilization_cap
+                    )
+                })
+            })
+            .collect()
+    }
+
+    /// Check that each job of a locked node group ran back-to-back on a single core.
+    ///
+    /// # Returns
+    ///
+    /// * A description of each violation. Empty if all locked groups were respected.
+    pub fn verify_lock_groups(&self, dag_set: &[Graph<NodeData, i32>]) -> Vec<String> {
+        let intervals = self.get_execution_intervals();
+        let mut violations = Vec::new();
+        for (dag_id, dag) in dag_set.iter().enumerate() {
+            let mut lock_groups: BTreeMap<i32, Vec<usize>> = BTreeMap::new();
+            for node in dag.node_weights() {
+                if let Some(&lock_group) = node.params.get("lock_group") {
+                    lock_groups
+                        .entry(lock_group)
+                        .or_default()
+                        .push(node.id as usize);
+                }
+            }
+            for (lock_group, members) in lock_groups {
+                let is_member = |interval: &ExecutionInterval| {
+                    interval.dag_id == dag_id && members.contains(&interval.node_id)
+                };
+                let mut member_intervals: BTreeMap<usize, Vec<&ExecutionInterval>> =
+                    BTreeMap::new();
+                for interval in intervals.iter().filter(|interval| is_member(interval)) {
+                    member_intervals
+                        .entry(interval.job_id)
+                        .or_default()
+                        .push(interval);
+                }
+                for (job_id, group_intervals) in member_intervals {
+                    let core_id = group_intervals[0].core_id;
+                    if group_intervals
+                        .iter()
+                        .any(|interval| interval.core_id != core_id)
+                    {
+                        violations.push(format!(
+                            "Lock group {} of DAG {} (job {}) migrated between cores.",
+                            lock_group, dag_id, job_id
+                        ));
+                        continue;
+                    }
+                    let begin_time = group_intervals.iter().map(|i| i.begin_time).min().unwrap();
+                    let end_time = group_intervals.iter().map(|i| i.end_time).max().unwrap();
+                    if let Some(other) = intervals.iter().find(|interval| {
+                        interval.core_id == core_id
+                            && !(is_member(interval) && interval.job_id == job_id)
+                            && interval.begin_time < end_time
+                            && begin_time < interval.end_time
+                    }) {
+                        violations.push(format!(
+                            "Lock group {} of DAG {} (job {}) was interleaved with node {} of DAG {} on core {}.",
+                            lock_group, dag_id, job_id, other.node_id, other.dag_id, core_id
+                        ));
+                    }
+                }
+            }
+        }
+        violations
+    }
+
+    #[cfg(feature = "plugin")]
+    pub fn write_plugin_metrics(&mut self, metrics: BTreeMap<String, serde_json::Value>) {
+        self.plugin_metrics.extend(metrics);
+    }
+
+    #[cfg(feature = "plugin")]
+    pub fn get_plugin_metrics(&self) -> &BTreeMap<String, serde_json::Value> {
+        &self.plugin_metrics
+    }
+
+    #[cfg(feature = "analysis")]
+    pub fn write_core_recommendation(&mut self, core_recommendation: CoreRecommendation) {
+        self.core_recommendation = Some(core_recommendation);
+    }
+
+    #[cfg(feature = "analysis")]
+    pub fn write_breakdown_factor(&mut self, breakdown_factor: BreakdownFactor) {
+        self.breakdown_factor = Some(breakdown_factor);
+    }
+
+    #[cfg(feature = "analysis")]
+    pub fn get_breakdown_factor(&self) -> Option<&BreakdownFactor> {
+        self.breakdown_factor.as_ref()
+    }
+
+    /// Write a compact trace of the executed nodes on each core, which is much smaller than
+    /// the job logs for long schedules.
+    pub fn write_core_traces(&mut self, schedule_length: i32) {
+        let intervals = self.get_execution_intervals();
+        self.core_traces = Some(
+            (0..self.processor_info.number_of_cores)
+                .map(|core_id| get_core_trace(&intervals, core_id, schedule_length))
+                .collect(),
+        );
+    }
+
+    pub fn get_core_traces(&self) -> Option<&Vec<CoreTrace>> {
+        self.core_traces.as_ref()
+    }
+
+    /// Write the predecessor that gated the readiness of each job of each node of `dag_set`,
+    /// which points at the structural bottlenecks. Jobs whose predecessors did not all complete
+    /// are skipped.
+    pub fn write_readiness_gates(&mut self, dag_set: &[Graph<NodeData, i32>]) {
+        // The last finish time of each job of each node, after the re-executions if any.
+        let mut finish_times: BTreeMap<(usize, usize, usize), i32> = BTreeMap::new();
+        for job_log in self.node_set_logs.iter().flatten() {
+            if let JobEventTimes::FinishTime(time) = job_log.event_time {
+                let finish_time = finish_times
+                    .entry((job_log.dag_id, job_log.node_id, job_log.job_id))
+                    .or_insert(time);
+                *finish_time = (*finish_time).max(time);
+            }
+        }
+        let job_ids: BTreeSet<(usize, usize)> = finish_times
+            .keys()
+            .map(|&(dag_id, _, job_id)| (dag_id, job_id))
+            .collect();
+
+        let mut readiness_gates = Vec::new();
+        for (dag_id, job_id) in job_ids {
+            let dag = &dag_set[dag_id];
+            for node_i in dag.node_indices() {
+                let Some(pre_nodes) = dag.get_pre_nodes(node_i) else {
+                    continue;
+                };
+                let pre_finish_times: Option<Vec<(i32, usize)>> = pre_nodes
+                    .iter()
+                    .map(|&pre_i| {
+                        let pre_node_id = dag[pre_i].id as usize;
+                        finish_times
+                            .get(&(dag_id, pre_node_id, job_id))
+                            .map(|&time| (time, pre_node_id))
+                    })
+                    .collect();
+                let Some(pre_finish_times) = pre_finish_times else {
+                    continue;
+                };
+                let first_finish_time = pre_finish_times.iter().map(|&(time, _)| time).min();
+                let (ready_time, gating_node_id) = pre_finish_times
+                    .iter()
+                    .copied()
+                    .max_by_key(|&(time, pre_node_id)| (time, Reverse(pre_node_id)))
+                    .unwrap();
+                readiness_gates.push(ReadinessGate {
+                    dag_id,
+                    node_id: dag[node_i].id as usize,
+                    job_id,
+                    gating_node_id,
+                    ready_time,
+                    wait_time: ready_time - first_finish_time.unwrap(),
+                });
+            }
+        }
+        self.readiness_gates = Some(readiness_gates);
+    }
+
+    pub fn get_readiness_gates(&self) -> Option<&Vec<ReadinessGate>> {
+        self.readiness_gates.as_ref()
     }
 
+    pub fn write_truncated(&mut self) {
+        self.truncated = true;
+    }
+
+    #[cfg(feature = "yaml")]
     pub fn dump_log_to_yaml(&self, file_path: &str) {
         dump_struct(file_path, self);
     }
 }
 
-#[derive(Serialize, Deserialize)]
-struct DAGSchedulerResultInfo {
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DAGSchedulerResultInfo {
     schedule_length: i32,
     period_factor: f32,
     result: bool,
 }
 
+impl DAGSchedulerResultInfo {
+    pub fn new(schedule_length: i32, period_factor: f32, result: bool) -> Self {
+        Self {
+            schedule_length,
+            period_factor,
+            result,
+        }
+    }
+}
+
+#[cfg(feature = "yaml")]
 pub fn dump_dag_scheduler_result_to_yaml(
     file_path: &str,
     schedule_length: i32,
     period_factor: f32,
     result: bool,
 ) {
-    let result_info = DAGSchedulerResultInfo {
-        schedule_length,
-        period_factor,
-        result,
-    };
+    let result_info = DAGSchedulerResultInfo::new(schedule_length, period_factor, result);
     dump_struct(file_path, &result_info);
 }
 
+#[cfg(feature = "yaml")]
 #[derive(Serialize, Deserialize)]
 struct DAGSetSchedulerResultInfo {
     result: bool,
 }
 
+#[cfg(feature = "yaml")]
 pub fn dump_dag_set_scheduler_result_to_yaml(file_path: &str, result: bool) {
     let result_info = DAGSetSchedulerResultInfo { result };
     dump_struct(file_path, &result_info);
 }
+
+/// Format of the dumped logs. JSON and MessagePack need the `json` and `msgpack` features.
+#[cfg(feature = "yaml")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    #[default]
+    Yaml,
+    #[cfg(feature = "json")]
+    Json,
+    #[cfg(feature = "msgpack")]
+    MessagePack,
+}
+
+#[cfg(feature = "yaml")]
+impl LogFormat {
+    pub fn get_extension(&self) -> &str {
+        match self {
+            LogFormat::Yaml => "yaml",
+            #[cfg(feature = "json")]
+            LogFormat::Json => "json",
+            #[cfg(feature = "msgpack")]
+            LogFormat::MessagePack => "msgpack",
+        }
+    }
+}
+
+#[cfg(feature = "yaml")]
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format.to_lowercase().as_str() {
+            "yaml" | "yml" => Ok(LogFormat::Yaml),
+            #[cfg(feature = "json")]
+            "json" => Ok(LogFormat::Json),
+            #[cfg(feature = "msgpack")]
+            "msgpack" | "messagepack" => Ok(LogFormat::MessagePack),
+            _ => Err(format!("Unknown log format: {}", format)),
+        }
+    }
+}
+
+/// The whole result of a simulation, serialized as a single document.
+///
+/// The fields of `log` (info, processor and DAG/node logs) are placed at the top level
+/// next to `result`, so the YAML output has the same keys as the appended documents.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SimulationLog<L, R> {
+    #[serde(flatten)]
+    pub log: L,
+    pub result: R,
+}
+
+impl<L: serde::Serialize, R: serde::Serialize> SimulationLog<L, R> {
+    pub fn new(log: L, result: R) -> Self {
+        Self { log, result }
+    }
+
+    /// Write the log to `file_path`, overwriting the file.
+    #[cfg(feature = "yaml")]
+    pub fn dump(&self, file_path: &str, format: LogFormat) {
+        let bytes = match format {
+            LogFormat::Yaml => serde_yaml::to_string(self)
+                .expect("Failed to serialize.")
+                .into_bytes(),
+            #[cfg(feature = "json")]
+            LogFormat::Json => serde_json::to_vec_pretty(self).expect("Failed to serialize."),
+            #[cfg(feature = "msgpack")]
+            LogFormat::MessagePack => rmp_serde::to_vec_named(self).expect("Failed to serialize."),
+        };
+        fs::write(file_path, bytes).expect("Failed to write the log file.");
+    }
+}
+
+/// Dump a filtered copy of a dumped simulation log without rerunning the simulation.
+#[cfg(feature = "yaml")]
+pub fn filter_log_file(input_path: &str, output_path: &str, format: LogFormat, filter: &LogFilter) {
+    let simulation_log: SimulationLog<DAGSetSchedulerLog, bool> =
+        SimulationLog::load(input_path, format);
+    SimulationLog::new(simulation_log.log.filter(filter), simulation_log.result)
+        .dump(output_path, format);
+}
+
+#[cfg(feature = "yaml")]
+impl<L: DeserializeOwned, R: DeserializeOwned> SimulationLog<L, R> {
+    pub fn load(file_path: &str, format: LogFormat) -> Self {
+        let bytes = fs::read(file_path).expect("Failed to read the log file.");
+        match format {
+            LogFormat::Yaml => serde_yaml::from_slice(&bytes).expect("Failed to deserialize."),
+            #[cfg(feature = "json")]
+            LogFormat::Json => serde_json::from_slice(&bytes).expect("Failed to deserialize."),
+            #[cfg(feature = "msgpack")]
+            LogFormat::MessagePack => {
+                rmp_serde::from_slice(&bytes).expect("Failed to deserialize.")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::create_node;
+
+    fn create_dag(dag_id: i32, period: i32) -> Graph<NodeData, i32> {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 2));
+        dag.add_param(n0, "period", period);
+        dag.add_param(n0, "end_to_end_deadline", period);
+        dag.set_dag_param("dag_id", dag_id);
+        dag
+    }
+
+    fn write_job(
+        log: &mut DAGSetSchedulerLog,
+        dag: &Graph<NodeData, i32>,
+        job_id: usize,
+        start_time: i32,
+    ) {
+        let dag_id = dag.get_dag_param("dag_id") as usize;
+        let node_data = &dag[petgraph::graph::NodeIndex::new(0)];
+        log.write_dag_release_time(dag_id, start_time);
+        log.write_job_event(node_data, 0, job_id, JobEventTimes::StartTime(start_time));
+        log.write_job_event(
+            node_data,
+            0,
+            job_id,
+            JobEventTimes::FinishTime(start_time + 2),
+        );
+        log.write_dag_finish_time(dag_id, start_time + 2);
+    }
+
+    /// DAG 0 (period 10) runs at 0 and 10, and DAG 1 (period 20) at 2 on a single core.
+    fn create_sample_log() -> DAGSetSchedulerLog {
+        let dag_set = vec![create_dag(0, 10), create_dag(1, 20)];
+        let mut log = DAGSetSchedulerLog::new(&dag_set, 1);
+        write_job(&mut log, &dag_set[0], 0, 0);
+        write_job(&mut log, &dag_set[1], 0, 2);
+        write_job(&mut log, &dag_set[0], 1, 10);
+        log.calculate_response_time();
+        log
+    }
+
+    #[test]
+    fn test_filter_by_dag_ids() {
+        let log = create_sample_log();
+        let mut filtered_log = log.filter(&LogFilter {
+            dag_ids: Some(1..=1),
+            time_window: None,
+        });
+
+        // The entries of DAG 1 are found by its id, not at the index 1.
+        assert_eq!(filtered_log.dag_set_info.each_dag_info.len(), 1);
+        assert_eq!(filtered_log.dag_set_info.each_dag_info[0].dag_id, Some(1));
+        assert_eq!(filtered_log.get_period(1), 20);
+        assert_eq!(filtered_log.get_end_to_end_deadline(1), 20);
+        assert_eq!(
+            filtered_log.get_worst_response_time(1),
+            log.get_worst_response_time(1)
+        );
+        assert_eq!(filtered_log.get_worst_response_times(), vec![2]);
+        assert_eq!(filtered_log.node_set_logs.len(), 1);
+        assert!(filtered_log.node_set_logs[0]
+            .iter()
+            .all(|job_log| job_log.dag_id == 1));
+
+        filtered_log.write_dag_release_time(1, 20);
+        assert_eq!(filtered_log.get_release_times(1), &[2, 20]);
+    }
+
+    #[test]
+    #[should_panic(expected = "DAG 0 is not in the log.")]
+    fn test_filter_removed_dag() {
+        let filtered_log = create_sample_log().filter(&LogFilter {
+            dag_ids: Some(1..=1),
+            time_window: None,
+        });
+        filtered_log.get_period(0);
+    }
+
+    #[test]
+    fn test_filter_by_time_window() {
+        let filtered_log = create_sample_log().filter(&LogFilter {
+            dag_ids: None,
+            time_window: Some(5..=15),
+        });
+
+        // Only the second job of DAG 0 is released in the window, and DAG 1 keeps its index.
+        assert_eq!(filtered_log.get_release_times(0), &[10]);
+        assert!(filtered_log.get_release_times(1).is_empty());
+        assert_eq!(filtered_log.node_set_logs.len(), 2);
+        let event_times: Vec<i32> = filtered_log.node_set_logs[0]
+            .iter()
+            .map(|job_log| job_log.event_time.get_time())
+            .collect();
+        assert_eq!(event_times, vec![10, 12]);
+        assert!(filtered_log.node_set_logs[1].is_empty());
+        assert_eq!(filtered_log.get_period(1), 20);
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn test_filter_log_file() {
+        let log = create_sample_log();
+        let filter = LogFilter {
+            dag_ids: Some(0..=0),
+            time_window: None,
+        };
+        let dir_path = std::env::temp_dir();
+        let file_path = dir_path.join(format!("sched_sim-log-{}.json", std::process::id()));
+        let filtered_file_path = dir_path.join(format!(
+            "sched_sim-filtered-log-{}.json",
+            std::process::id()
+        ));
+        SimulationLog::new(log.clone(), true).dump(file_path.to_str().unwrap(), LogFormat::Json);
+
+        // The same log is obtained by filtering the dumped log.
+        filter_log_file(
+            file_path.to_str().unwrap(),
+            filtered_file_path.to_str().unwrap(),
+            LogFormat::Json,
+            &filter,
+        );
+        let filtered_log: SimulationLog<DAGSetSchedulerLog, bool> =
+            SimulationLog::load(filtered_file_path.to_str().unwrap(), LogFormat::Json);
+        fs::remove_file(file_path).unwrap();
+        fs::remove_file(filtered_file_path).unwrap();
+        assert!(filtered_log.result);
+        assert_eq!(
+            filtered_log.log.get_worst_response_times(),
+            log.filter(&filter).get_worst_response_times()
+        );
+        assert_eq!(filtered_log.log.get_period(0), 10);
+    }
+
+    #[test]
+    fn test_calculate_core_share() {
+        let mut log = create_sample_log();
+        log.calculate_core_share();
+
+        // DAG 0 demands 2 / 10 and DAG 1 2 / 20, and each receives its entitlement.
+        let core_share_log = &log.core_share_log;
+        assert_eq!(core_share_log.each_dag_share[0].total_proc_time, 4);
+        assert_eq!(core_share_log.each_dag_share[1].total_proc_time, 2);
+        for dag_share in core_share_log.each_dag_share.iter() {
+            assert!((dag_share.core_time_share - dag_share.entitlement).abs() < 1e-6);
+            assert!((dag_share.share_ratio - 1.0).abs() < 1e-6);
+        }
+        assert!((core_share_log.fairness_index - 1.0).abs() < 1e-6);
+
+        // Without the second job of DAG 0, both DAGs receive the same time.
+        let dag_set = vec![create_dag(0, 10), create_dag(1, 20)];
+        let mut log = DAGSetSchedulerLog::new(&dag_set, 1);
+        write_job(&mut log, &dag_set[0], 0, 0);
+        write_job(&mut log, &dag_set[1], 0, 2);
+        log.calculate_core_share();
+        let core_share_log = &log.core_share_log;
+        assert!((core_share_log.each_dag_share[0].share_ratio - 0.75).abs() < 1e-6);
+        assert!((core_share_log.each_dag_share[1].share_ratio - 1.5).abs() < 1e-6);
+        assert!((core_share_log.fairness_index - 0.9).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_calculate_reliability() {
+        let mut log = create_sample_log();
+        // The second job of DAG 0 fails, and the job of DAG 1 succeeds after a re-execution.
+        for (dag_id, job_id, time, retried) in [(0, 1, 11, false), (1, 0, 3, true)] {
+            log.write_fault_event(FaultEvent {
+                dag_id,
+                node_id: 0,
+                job_id,
+                time,
+                core_id: 0,
+                attempt: 0,
+                retried,
+            });
+        }
+        log.calculate_reliability();
+
+        assert_eq!(
+            log.get_reliability(),
+            &[
+                DAGReliability {
+                    dag_id: 0,
+                    jobs: 2,
+                    faults: 1,
+                    re_executions: 0,
+                    failed_jobs: 1,
+                    reliability: 0.5,
+                },
+                DAGReliability {
+                    dag_id: 1,
+                    jobs: 1,
+                    faults: 1,
+                    re_executions: 1,
+                    failed_jobs: 0,
+                    reliability: 1.0,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_write_core_traces() {
+        let mut log = create_sample_log();
+        log.write_core_traces(20);
+
+        let trace_entry = |dag_id: Option<usize>, duration| TraceEntry {
+            dag_id,
+            node_id: dag_id.map(|_| 0),
+            duration,
+            idle: dag_id.is_none(),
+        };
+        assert_eq!(
+            log.get_core_traces().unwrap(),
+            &vec![CoreTrace {
+                core_id: 0,
+                entries: vec![
+                    trace_entry(Some(0), 2),
+                    trace_entry(Some(1), 2),
+                    trace_entry(None, 6),
+                    trace_entry(Some(0), 2),
+                    trace_entry(None, 8),
+                ],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_write_readiness_gates() {
+        // 0 -> {1, 2} -> 3, where node 1 finishes last among the predecessors of node 3.
+        let mut dag = Graph::<NodeData, i32>::new();
+        let nodes: Vec<_> = (0..4)
+            .map(|id| dag.add_node(create_node(id, "execution_time", 1)))
+            .collect();
+        dag.add_edge(nodes[0], nodes[1], 1);
+        dag.add_edge(nodes[0], nodes[2], 1);
+        dag.add_edge(nodes[1], nodes[3], 1);
+        dag.add_edge(nodes[2], nodes[3], 1);
+        dag.add_param(nodes[0], "period", 10);
+        dag.set_dag_param("dag_id", 0);
+        let dag_set = vec![dag];
+
+        let mut log = DAGSetSchedulerLog::new(&dag_set, 2);
+        for (node_i, core_id, start_time, finish_time) in [
+            (nodes[0], 0, 0, 2),
+            (nodes[1], 0, 2, 5),
+            (nodes[2], 1, 2, 4),
+            (nodes[3], 0, 5, 7),
+        ] {
+            let node_data = &dag_set[0][node_i];
+            log.write_job_event(node_data, core_id, 0, JobEventTimes::StartTime(start_time));
+            log.write_job_event(
+                node_data,
+                core_id,
+                0,
+                JobEventTimes::FinishTime(finish_time),
+            );
+        }
+        log.write_readiness_gates(&dag_set);
+
+        let readiness_gate = |node_id, gating_node_id, ready_time, wait_time| ReadinessGate {
+            dag_id: 0,
+            node_id,
+            job_id: 0,
+            gating_node_id,
+            ready_time,
+            wait_time,
+        };
+        assert_eq!(
+            log.get_readiness_gates().unwrap(),
+            &vec![
+                readiness_gate(1, 0, 2, 0),
+                readiness_gate(2, 0, 2, 0),
+                readiness_gate(3, 1, 5, 1),
+            ]
+        );
+    }
+}
@@ -0,0 +1,106 @@
+//! Longest Processing Time first (LPT) list scheduling of a DAG: the ready node with the longest
+//! `execution_time` is dispatched first, as the classic makespan heuristic. Ties are broken by
+//! the node id.
+use crate::{
+    dag_scheduler::DAGSchedulerBase, graph_extension::NodeData, log::DAGSchedulerLog,
+    processor::ProcessorBase,
+};
+use petgraph::Graph;
+use std::cmp::Reverse;
+use std::collections::VecDeque;
+
+#[derive(Clone, Default)]
+pub struct LptScheduler<T>
+where
+    T: ProcessorBase + Clone,
+{
+    dag: Graph<NodeData, i32>,
+    processor: T,
+    log: DAGSchedulerLog,
+}
+
+impl<T> DAGSchedulerBase<T> for LptScheduler<T>
+where
+    T: ProcessorBase + Clone,
+{
+    fn new(dag: &Graph<NodeData, i32>, processor: &T) -> Self {
+        Self {
+            dag: dag.clone(),
+            processor: processor.clone(),
+            log: DAGSchedulerLog::new(dag, processor.get_number_of_cores()),
+        }
+    }
+
+    fn set_dag(&mut self, dag: &Graph<NodeData, i32>) {
+        self.dag = dag.clone();
+    }
+
+    fn set_processor(&mut self, processor: &T) {
+        self.processor = processor.clone();
+    }
+
+    fn set_log(&mut self, log: DAGSchedulerLog) {
+        self.log = log;
+    }
+
+    fn get_dag(&self) -> Graph<NodeData, i32> {
+        self.dag.clone()
+    }
+
+    fn get_processor(&self) -> T {
+        self.processor.clone()
+    }
+
+    fn get_log(&self) -> DAGSchedulerLog {
+        self.log.clone()
+    }
+
+    fn sort_ready_queue(ready_queue: &mut VecDeque<NodeData>) {
+        ready_queue.make_contiguous().sort_by_key(|node| {
+            (
+                Reverse(node.get_params_value("execution_time")),
+                node.get_id(),
+            )
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixed_priority_scheduler::FixedPriorityScheduler;
+    use crate::graph_extension::GraphExtension;
+    use crate::homogeneous::HomogeneousProcessor;
+    use crate::test_util::create_node;
+    use petgraph::graph::NodeIndex;
+
+    #[test]
+    fn test_lpt_scheduler_schedule_normal() {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 1));
+        dag.add_param(n0, "period", 100);
+        dag.add_param(n0, "priority", 0);
+        for (node_id, execution_time) in [(1, 1), (2, 1), (3, 1), (4, 1), (5, 4)] {
+            let node_i = dag.add_node(create_node(node_id, "execution_time", execution_time));
+            dag.add_param(node_i, "priority", node_id);
+            dag.add_edge(n0, node_i, 1);
+        }
+
+        // Node 5 occupies one core while the short nodes share the other.
+        let mut lpt_scheduler = LptScheduler::new(&dag, &HomogeneousProcessor::new(2));
+        let (schedule_length, execution_order) = lpt_scheduler.schedule();
+        assert_eq!(schedule_length, 5);
+        assert_eq!(
+            execution_order,
+            [0, 5, 1, 2, 3, 4]
+                .into_iter()
+                .map(NodeIndex::new)
+                .collect::<VecDeque<_>>()
+        );
+
+        // In the id order, node 5 starts after the short nodes.
+        let mut fixed_priority_scheduler =
+            FixedPriorityScheduler::new(&dag, &HomogeneousProcessor::new(2));
+        assert_eq!(fixed_priority_scheduler.schedule().0, 7);
+    }
+}
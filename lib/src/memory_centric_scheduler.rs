@@ -0,0 +1,279 @@
+//! Global non-preemptive EDF with read/execute/write phases (AER model).
+//!
+//! A node with `read_time` and `write_time` first reads its data from the shared memory, then
+//! executes for `execution_time`, and finally writes its results back to the shared memory.
+//! The node holds its core during all phases, but only one read or write phase can access the
+//! shared memory at a time. A core waiting for the memory stalls.
+//! Phases of nodes on different cores overlap, e.g., one core executes while another reads.
+//! Nodes without `read_time` and `write_time` have only the execute phase.
+use crate::dag_set_scheduler::{
+    DAGSetSchedulerBase, DAGStateManager, DAGStateManagerBase, NodeDataWrapper, PreemptiveType,
+    ReadyQueue,
+};
+use crate::getset_dag_set_scheduler;
+use crate::{
+    core::ProcessResult,
+    graph_extension::{get_node_index_maps, NodeData, NodeIndexMap},
+    homogeneous::HomogeneousProcessor,
+    log::DAGSetSchedulerLog,
+    processor::ProcessorBase,
+    util::{clear_interrupted, get_hyper_period, get_process_core_indices, is_interrupted},
+};
+use petgraph::graph::Graph;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Phase {
+    Read,
+    Execute,
+    Write,
+}
+
+/// Phase of `node_data` on a core with `remain_proc_time` left of all phases.
+pub fn get_phase(node_data: &NodeData, remain_proc_time: i32) -> Phase {
+    let read_time = node_data.params.get("read_time").copied().unwrap_or(0);
+    let write_time = node_data.params.get("write_time").copied().unwrap_or(0);
+    let elapsed_time = get_total_phase_time(node_data) - remain_proc_time;
+    if elapsed_time < read_time {
+        Phase::Read
+    } else if remain_proc_time > write_time {
+        Phase::Execute
+    } else {
+        Phase::Write
+    }
+}
+
+/// `read_time + execution_time + write_time`
+fn get_total_phase_time(node_data: &NodeData) -> i32 {
+    node_data.params.get("read_time").copied().unwrap_or(0)
+        + node_data.get_params_value("execution_time")
+        + node_data.params.get("write_time").copied().unwrap_or(0)
+}
+
+pub struct MemoryCentricScheduler {
+    dag_set: Vec<Graph<NodeData, i32>>,
+    node_index_maps: Vec<NodeIndexMap>,
+    processor: HomogeneousProcessor,
+    log: DAGSetSchedulerLog,
+    current_time: i32,
+}
+
+impl DAGSetSchedulerBase<HomogeneousProcessor> for MemoryCentricScheduler {
+    fn new(dag_set: &[Graph<NodeData, i32>], processor: &HomogeneousProcessor) -> Self {
+        Self {
+            node_index_maps: get_node_index_maps(dag_set),
+            dag_set: dag_set.to_vec(),
+            processor: processor.clone(),
+            log: DAGSetSchedulerLog::new(dag_set, processor.get_number_of_cores()),
+            current_time: 0,
+        }
+    }
+
+    getset_dag_set_scheduler!(HomogeneousProcessor);
+
+    /// The preemptive type is ignored because a node cannot be preempted between its phases.
+    fn schedule(&mut self, _preemptive_type: PreemptiveType) -> i32 {
+        clear_interrupted();
+        self.log.write_schedule_start();
+        let mut managers = vec![DAGStateManager::default(); self.dag_set.len()];
+        let mut ready_queue = ReadyQueue::new();
+        let hyper_period = get_hyper_period(&self.dag_set);
+        // Core whose read or write phase currently uses the shared memory.
+        let mut memory_owner: Option<usize> = None;
+        while self.get_current_time() < hyper_period && !is_interrupted() {
+            // Release DAGs
+            for ready_node in self.release_dags(&mut managers) {
+                ready_queue.insert_new(NodeDataWrapper::new(ready_node));
+                self.log.write_ready_queue_insertion();
+            }
+
+            // Allocate nodes to idle cores. The core processes all phases of the node.
+            // Nodes of DAGs running `max_parallelism` nodes are skipped.
+            while let Some(idle_core_i) = self.processor.get_idle_core_index() {
+                let Some(wrapper) = ready_queue
+                    .iter()
+                    .find(|wrapper| self.can_allocate_node(&wrapper.node_data))
+                    .cloned()
+                else {
+                    break;
+                };
+                ready_queue.remove(&wrapper);
+                self.log.write_ready_queue_removal();
+                let node_data = wrapper.convert_node_data();
+                let dag_id = node_data.get_params_value("dag_id") as usize;
+                let job_id = managers[dag_id].get_release_count() as usize;
+                self.log
+                    .write_allocating_job(&node_data, idle_core_i, job_id, self.current_time);
+                // The node keeps its `execution_time` so that `get_phase` can find the phases.
+                self.processor
+                    .allocate_specific_core(idle_core_i, &node_data);
+                self.processor.cores[idle_core_i].remain_proc_time =
+                    get_total_phase_time(&node_data);
+            }
+
+            // Grant the shared memory to the waiting memory phase with the earliest deadline.
+            let phases = self.get_phases();
+            if memory_owner
+                .is_some_and(|core_i| !matches!(phases[core_i], Some(Phase::Read | Phase::Write)))
+            {
+                memory_owner = None;
+            }
+            if memory_owner.is_none() {
+                memory_owner = phases
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, phase)| matches!(phase, Some(Phase::Read | Phase::Write)))
+                    .map(|(core_i, _)| core_i)
+                    .min_by_key(|&core_i| {
+                        self.processor.cores[core_i]
+                            .get_processing_node()
+                            .as_ref()
+                            .unwrap()
+                            .get_params_value("node_absolute_deadline")
+                    });
+            }
+
+            // Process unit time. Cores waiting for the shared memory stall.
+            self.current_time += 1;
+            let process_result: Vec<ProcessResult> = phases
+                .iter()
+                .enumerate()
+                .map(|(core_i, phase)| match phase {
+                    Some(Phase::Execute) => self.processor.cores[core_i].process(),
+                    Some(_) if memory_owner == Some(core_i) => {
+                        self.processor.cores[core_i].process()
+                    }
+                    _ => ProcessResult::Idle,
+                })
+                .collect();
+            let indices: Vec<usize> = get_process_core_indices(&process_result);
+            self.log.write_processing_time(&indices);
+
+            // Post-process on completion of node execution
+            for (core_id, result) in process_result.iter().enumerate() {
+                if let ProcessResult::Done(node_data) = result {
+                    if memory_owner == Some(core_id) {
+                        memory_owner = None;
+                    }
+                    for ready_node in
+                        self.post_process_on_node_completion(node_data, core_id, &mut managers)
+                    {
+                        ready_queue.insert_new(NodeDataWrapper::new(ready_node));
+                        self.log.write_ready_queue_insertion();
+                    }
+                }
+            }
+        }
+
+        self.log
+            .write_comparisons(ready_queue.get_comparison_count());
+        self.calculate_log();
+        self.get_current_time()
+    }
+}
+
+impl MemoryCentricScheduler {
+    /// Phase of the node on each core (`None` if idle).
+    fn get_phases(&self) -> Vec<Option<Phase>> {
+        self.processor
+            .cores
+            .iter()
+            .map(|core| {
+                core.get_processing_node()
+                    .as_ref()
+                    .map(|node_data| get_phase(node_data, core.remain_proc_time))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::create_node;
+    use crate::{global_edf_scheduler::GlobalEDFScheduler, graph_extension::GraphExtension};
+
+    fn create_phased_dag(end_to_end_deadline: i32) -> Graph<NodeData, i32> {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 3));
+        dag.add_param(n0, "read_time", 2);
+        dag.add_param(n0, "write_time", 2);
+        dag.add_param(n0, "period", 30);
+        dag.add_param(n0, "end_to_end_deadline", end_to_end_deadline);
+        dag
+    }
+
+    #[test]
+    fn test_get_phase_normal() {
+        let mut node = create_node(0, "execution_time", 3);
+        node.params.insert("read_time".to_string(), 2);
+        node.params.insert("write_time".to_string(), 2);
+        let phases: Vec<Phase> = (1..=7)
+            .rev()
+            .map(|remain| get_phase(&node, remain))
+            .collect();
+        assert_eq!(
+            phases,
+            vec![
+                Phase::Read,
+                Phase::Read,
+                Phase::Execute,
+                Phase::Execute,
+                Phase::Execute,
+                Phase::Write,
+                Phase::Write
+            ]
+        );
+
+        // Without the memory phases, the node only executes.
+        assert_eq!(
+            get_phase(&create_node(0, "execution_time", 3), 3),
+            Phase::Execute
+        );
+    }
+
+    #[test]
+    fn test_memory_centric_read_contention() {
+        let create_read_dag = |end_to_end_deadline: i32, dag_id: i32| {
+            let mut dag = Graph::<NodeData, i32>::new();
+            let n0 = dag.add_node(create_node(0, "execution_time", 1));
+            dag.add_param(n0, "read_time", 3);
+            dag.add_param(n0, "period", 30);
+            dag.add_param(n0, "end_to_end_deadline", end_to_end_deadline);
+            dag.set_dag_param("dag_id", dag_id);
+            dag
+        };
+        let dag_set = vec![create_read_dag(20, 0), create_read_dag(21, 1)];
+
+        let mut memory_centric_scheduler =
+            MemoryCentricScheduler::new(&dag_set, &HomogeneousProcessor::new(2));
+        memory_centric_scheduler.schedule(PreemptiveType::NonPreemptive);
+        let log = memory_centric_scheduler.get_log_mut();
+        // dag2 reads after dag finishes reading at 3, and executes at 6.
+        assert_eq!(log.get_worst_response_time(0), 4);
+        assert_eq!(log.get_worst_response_time(1), 7);
+    }
+
+    #[test]
+    fn test_memory_centric_memory_contention() {
+        let mut dag = create_phased_dag(20);
+        let mut dag2 = create_phased_dag(21);
+        dag.set_dag_param("dag_id", 0);
+        dag2.set_dag_param("dag_id", 1);
+        let dag_set = vec![dag, dag2];
+        let processor = HomogeneousProcessor::new(2);
+
+        let mut memory_centric_scheduler = MemoryCentricScheduler::new(&dag_set, &processor);
+        memory_centric_scheduler.schedule(PreemptiveType::NonPreemptive);
+        let log = memory_centric_scheduler.get_log_mut();
+        // dag2 waits for the read of dag, and its write waits until dag finishes writing.
+        assert_eq!(log.get_worst_response_time(0), 7);
+        assert_eq!(log.get_worst_response_time(1), 9);
+
+        // Global EDF ignores the phases and only executes the nodes.
+        let mut global_edf_scheduler = GlobalEDFScheduler::new(&dag_set, &processor);
+        global_edf_scheduler.schedule(PreemptiveType::NonPreemptive);
+        let log = global_edf_scheduler.get_log_mut();
+        assert_eq!(log.get_worst_response_time(0), 3);
+        assert_eq!(log.get_worst_response_time(1), 3);
+    }
+}
@@ -0,0 +1,263 @@
+//! Vestal-style mixed criticality under the Adaptive Mixed Criticality (AMC) mode change.
+//!
+//! Nodes declare their `criticality` level (0 for LO, 1 for HI), and a DAG has the highest level
+//! of its nodes. The nodes of HI-criticality DAGs have a LO WCET `lo_execution_time` and a HI WCET
+//! `hi_execution_time`, while `execution_time` is the time they actually execute. The system
+//! starts in the LO mode. When a node of a HI-criticality DAG executes for its `lo_execution_time`
+//! without completing, the system switches to the HI mode: the unfinished jobs of the
+//! LO-criticality DAGs are dropped, and so are their jobs released until the system returns to the
+//! LO mode at the next idle instant. Mode changes are handled by the default `schedule` of
+//! `DAGSetSchedulerBase`. Resources held by dropped nodes are not released.
+use crate::{
+    core::ProcessResult,
+    dag_task::DagTask,
+    graph_extension::{GraphExtension, NodeData},
+};
+use log::warn;
+use petgraph::Graph;
+use serde_derive::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Criticality {
+    #[default]
+    Lo,
+    Hi,
+}
+
+impl Criticality {
+    fn from_level(level: i32) -> Self {
+        match level {
+            0 => Self::Lo,
+            1 => Self::Hi,
+            _ => panic!(
+                "Unknown criticality level: {}. Use 0 for LO or 1 for HI.",
+                level
+            ),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct CriticalityManager {
+    mode: Criticality,
+    /// Criticality of each DAG, indexed by `dag_id`.
+    dag_criticalities: Vec<Criticality>,
+    /// Time each node of the HI-criticality DAGs executed in the LO mode.
+    executed_times: BTreeMap<(usize, i32), i32>,
+}
+
+impl CriticalityManager {
+    /// `None` if no node of `dag_set` declares a `criticality`.
+    pub fn new(dag_set: &[Graph<NodeData, i32>]) -> Option<Self> {
+        let mut dag_criticalities = vec![Criticality::Lo; dag_set.len()];
+        let mut has_criticality = false;
+        for dag in dag_set {
+            let dag_id = dag.get_dag_param("dag_id") as usize;
+            for node_data in dag.node_weights() {
+                let Some(&level) = node_data.params.get("criticality") else {
+                    continue;
+                };
+                has_criticality = true;
+                dag_criticalities[dag_id] =
+                    dag_criticalities[dag_id].max(Criticality::from_level(level));
+                if let Some(&hi_execution_time) = node_data.params.get("hi_execution_time") {
+                    if node_data.get_params_value("execution_time") > hi_execution_time {
+                        warn!(
+                            "Node {} of DAG {} executes longer than its hi_execution_time.",
+                            node_data.get_id(),
+                            dag_id
+                        );
+                    }
+                    if node_data
+                        .params
+                        .get("lo_execution_time")
+                        .is_some_and(|&lo_execution_time| lo_execution_time > hi_execution_time)
+                    {
+                        warn!(
+                            "Node {} of DAG {} has a lo_execution_time above its hi_execution_time.",
+                            node_data.get_id(),
+                            dag_id
+                        );
+                    }
+                }
+            }
+        }
+        has_criticality.then(|| Self {
+            mode: Criticality::Lo,
+            dag_criticalities,
+            executed_times: BTreeMap::new(),
+        })
+    }
+
+    pub fn get_mode(&self) -> Criticality {
+        self.mode
+    }
+
+    /// The DAGs whose jobs are dropped in the current mode.
+    pub fn get_dropped_dag_ids(&self) -> Vec<usize> {
+        (0..self.dag_criticalities.len())
+            .filter(|&dag_id| self.is_dropped(dag_id))
+            .collect()
+    }
+
+    pub fn get_dag_criticality(&self, dag_id: usize) -> Criticality {
+        self.dag_criticalities[dag_id]
+    }
+
+    pub fn is_dropped(&self, dag_id: usize) -> bool {
+        self.dag_criticalities[dag_id] < self.mode
+    }
+
+    /// Account the unit time executed by `executed_nodes` in the LO mode.
+    ///
+    /// # Returns
+    ///
+    /// * The node that used up its `lo_execution_time` without completing in `process_result`.
+    ///   The system is then in the HI mode.
+    pub fn advance(
+        &mut self,
+        executed_nodes: &[NodeData],
+        process_result: &[ProcessResult],
+    ) -> Option<NodeData> {
+        if self.mode == Criticality::Hi {
+            return None;
+        }
+        let mut overrun_node = None;
+        for node_data in executed_nodes {
+            let dag_id = node_data.get_params_value("dag_id") as usize;
+            let Some(&lo_execution_time) = node_data.params.get("lo_execution_time") else {
+                continue;
+            };
+            if self.dag_criticalities[dag_id] == Criticality::Lo {
+                continue;
+            }
+            let key = (dag_id, node_data.get_id());
+            let is_completed = process_result.iter().any(|result| {
+                matches!(result, ProcessResult::Done(done_node_data)
+                    if done_node_data.get_params_value("dag_id") as usize == dag_id
+                        && done_node_data.get_id() == node_data.get_id())
+            });
+            if is_completed {
+                self.executed_times.remove(&key);
+                continue;
+            }
+            let executed_time = self.executed_times.entry(key).or_insert(0);
+            *executed_time += 1;
+            if *executed_time >= lo_execution_time && overrun_node.is_none() {
+                overrun_node = Some(node_data.clone());
+            }
+        }
+        if overrun_node.is_some() {
+            self.mode = Criticality::Hi;
+            self.executed_times.clear();
+        }
+        overrun_node
+    }
+
+    /// Return to the LO mode, if in the HI mode, when the system is idle.
+    pub fn return_to_lo(&mut self) -> bool {
+        let is_hi = self.mode == Criticality::Hi;
+        self.mode = Criticality::Lo;
+        is_hi
+    }
+}
+
+/// Factor `x` of the virtual deadlines of EDF-VD (Baruah et al., ECRTS 2012),
+/// `x = U_HI^LO / (m - U_LO^LO)`, where `U_HI^LO` is the total density of the HI-criticality DAGs
+/// with their LO WCETs (`lo_execution_time` if set), `U_LO^LO` that of the LO-criticality DAGs,
+/// and `m` the number of cores. The densities (`volume / D`) generalize the utilizations to
+/// constrained deadlines. At most one, which is also the factor if the LO-criticality DAGs alone
+/// fill the cores.
+pub fn calculate_virtual_deadline_factor(
+    dag_set: &[Graph<NodeData, i32>],
+    number_of_cores: usize,
+) -> f32 {
+    let mut hi_density = 0.0;
+    let mut lo_density = 0.0;
+    for dag in dag_set {
+        let relative_deadline = DagTask::from_graph(dag).get_relative_deadline().unwrap();
+        let is_hi = dag
+            .node_weights()
+            .any(|node_data| node_data.params.get("criticality") == Some(&1));
+        let volume: i32 = dag
+            .node_weights()
+            .map(|node_data| {
+                node_data
+                    .params
+                    .get("lo_execution_time")
+                    .copied()
+                    .filter(|_| is_hi)
+                    .unwrap_or_else(|| node_data.get_params_value("execution_time"))
+            })
+            .sum();
+        let density = volume as f32 / relative_deadline as f32;
+        if is_hi {
+            hi_density += density;
+        } else {
+            lo_density += density;
+        }
+    }
+    let remaining_capacity = number_of_cores as f32 - lo_density;
+    if remaining_capacity <= 0.0 {
+        warn!(
+            "The LO-criticality DAGs fill the cores (density {}). The deadlines are not scaled.",
+            lo_density
+        );
+        return 1.0;
+    }
+    (hi_density / remaining_capacity).min(1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::create_node;
+
+    fn create_dag(dag_id: i32, criticality: i32) -> Graph<NodeData, i32> {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 3));
+        dag.add_param(n0, "criticality", criticality);
+        dag.add_param(n0, "lo_execution_time", 2);
+        dag.add_param(n0, "hi_execution_time", 4);
+        dag.set_dag_param("dag_id", dag_id);
+        dag
+    }
+
+    #[test]
+    fn test_criticality_manager_mode_change() {
+        let dag_set = vec![create_dag(0, 1), create_dag(1, 0)];
+        let mut criticality_manager = CriticalityManager::new(&dag_set).unwrap();
+        let hi_node = dag_set[0][petgraph::graph::NodeIndex::new(0)].clone();
+        let lo_node = dag_set[1][petgraph::graph::NodeIndex::new(0)].clone();
+        let executed_nodes = vec![hi_node, lo_node];
+        let process_result = vec![ProcessResult::Continue, ProcessResult::Continue];
+
+        // The LO node never triggers, and the HI node triggers when it uses up its LO WCET.
+        assert!(criticality_manager
+            .advance(&executed_nodes, &process_result)
+            .is_none());
+        assert_eq!(criticality_manager.get_mode(), Criticality::Lo);
+        assert!(!criticality_manager.is_dropped(1));
+        let overrun_node = criticality_manager
+            .advance(&executed_nodes, &process_result)
+            .unwrap();
+        assert_eq!(overrun_node.get_params_value("dag_id"), 0);
+        assert_eq!(criticality_manager.get_mode(), Criticality::Hi);
+        assert_eq!(criticality_manager.get_dropped_dag_ids(), vec![1]);
+        assert!(!criticality_manager.is_dropped(0));
+
+        assert!(criticality_manager.return_to_lo());
+        assert!(!criticality_manager.return_to_lo());
+        assert!(criticality_manager.get_dropped_dag_ids().is_empty());
+    }
+
+    #[test]
+    fn test_criticality_manager_no_criticality() {
+        let mut dag = Graph::<NodeData, i32>::new();
+        dag.add_node(create_node(0, "execution_time", 3));
+        dag.set_dag_param("dag_id", 0);
+        assert!(CriticalityManager::new(&[dag]).is_none());
+    }
+}
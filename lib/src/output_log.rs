@@ -0,0 +1,184 @@
+//! Collect all outputs of a run into a single directory.
+//!
+//! Each run gets `<output_dir>/<date>-<alg>-<hash>/`, where `hash` identifies the input DAG set,
+//! containing the log, a summary of the result, any exports and a manifest listing the files.
+pub use crate::log::{ResourceUsage, SlackSummary};
+use crate::{
+    graph_extension::NodeData,
+    preprocessing_cache::{get_dag_hash, get_fnv_hash},
+    schedulability_screening::ScreeningViolation,
+    schedule_score::ScheduleScore,
+};
+use chrono::{DateTime, Utc};
+use petgraph::graph::Graph;
+use serde_derive::{Deserialize, Serialize};
+use std::fs;
+
+/// Hash of the input DAG set, used to tell runs on different inputs apart.
+///
+/// It combines the `get_dag_hash` of each DAG in order, so it is stable across builds.
+/// Pass the DAG set given to the scheduler, since scheduling adds params to its own copy.
+pub fn get_dag_set_hash(dag_set: &[Graph<NodeData, i32>]) -> String {
+    let content: String = dag_set
+        .iter()
+        .map(|dag| format!("{:016x};", get_dag_hash(dag)))
+        .collect();
+    format!("{:08x}", get_fnv_hash(&content) as u32)
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RunManifest {
+    pub alg_name: String,
+    pub created_at: String,
+    pub number_of_cores: usize,
+    pub number_of_dags: usize,
+    pub dag_set_hash: String,
+    /// Files in the run directory other than the manifest.
+    pub files: Vec<String>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RunStatus {
+    #[default]
+    Simulated,
+    /// The DAG set failed the schedulability screening and was not simulated.
+    ScreenedOut,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RunSummary {
+    pub alg_name: String,
+    #[serde(default)]
+    pub status: RunStatus,
+    pub result: bool,
+    pub worst_response_times: Vec<i32>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub screening_violations: Vec<ScreeningViolation>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resource_usage: Option<ResourceUsage>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub slack: Vec<SlackSummary>,
+    /// Quality of the schedule with the default weights. Not set if not simulated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score: Option<ScheduleScore>,
+}
+
+pub struct RunDirectory {
+    path: String,
+    alg_name: String,
+    created_at: String,
+    dag_set_hash: String,
+}
+
+impl RunDirectory {
+    /// Create `<output_dir>/<date>-<alg_name>-<hash>/`.
+    pub fn create(output_dir: &str, alg_name: &str, dag_set: &[Graph<NodeData, i32>]) -> Self {
+        let now: DateTime<Utc> = Utc::now();
+        let created_at = now.format("%Y-%m-%d-%H-%M-%S-%3f").to_string();
+        let dag_set_hash = get_dag_set_hash(dag_set);
+        let path = format!(
+            "{}/{}-{}-{}",
+            output_dir, created_at, alg_name, dag_set_hash
+        );
+        fs::create_dir_all(&path).unwrap();
+        Self {
+            path,
+            alg_name: alg_name.to_string(),
+            created_at,
+            dag_set_hash,
+        }
+    }
+
+    pub fn get_path(&self) -> &str {
+        &self.path
+    }
+
+    /// Path of `<name>.<extension>` in the run directory.
+    pub fn get_file_path(&self, name: &str, extension: &str) -> String {
+        format!("{}/{}.{}", self.path, name, extension)
+    }
+
+    pub fn write_summary(&self, summary: &RunSummary) {
+        write_yaml(&self.get_file_path("summary", "yaml"), summary);
+    }
+
+    /// Write the manifest listing the files written so far. Call this last.
+    pub fn write_manifest(&self, number_of_cores: usize, number_of_dags: usize) -> RunManifest {
+        let mut files: Vec<String> = fs::read_dir(&self.path)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+            .filter(|file_name| file_name != "manifest.yaml")
+            .collect();
+        files.sort();
+        let manifest = RunManifest {
+            alg_name: self.alg_name.clone(),
+            created_at: self.created_at.clone(),
+            number_of_cores,
+            number_of_dags,
+            dag_set_hash: self.dag_set_hash.clone(),
+            files,
+        };
+        write_yaml(&self.get_file_path("manifest", "yaml"), &manifest);
+        manifest
+    }
+}
+
+fn write_yaml(file_path: &str, target_struct: &impl serde::Serialize) {
+    let yaml = serde_yaml::to_string(target_struct).expect("Failed to serialize.");
+    fs::write(file_path, yaml).unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph_extension::GraphExtension;
+    use crate::test_util::create_node;
+
+    fn create_sample_dag(execution_time: i32) -> Graph<NodeData, i32> {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", execution_time));
+        let n1 = dag.add_node(create_node(1, "execution_time", 2));
+        dag.add_param(n0, "period", 10);
+        dag.add_edge(n0, n1, 0);
+        dag
+    }
+
+    #[test]
+    fn test_get_dag_set_hash_normal() {
+        let hash = get_dag_set_hash(&[create_sample_dag(1)]);
+        // The hash does not depend on the build.
+        assert_eq!(hash, "85a07b73");
+        assert_ne!(hash, get_dag_set_hash(&[create_sample_dag(3)]));
+    }
+
+    #[test]
+    fn test_run_directory_normal() {
+        let dag_set = vec![create_sample_dag(1)];
+        let run_dir = RunDirectory::create("../lib/tests/run_dir_test", "alg", &dag_set);
+        assert!(run_dir
+            .get_path()
+            .ends_with(&format!("-alg-{}", get_dag_set_hash(&dag_set))));
+
+        fs::write(run_dir.get_file_path("log", "yaml"), "").unwrap();
+        run_dir.write_summary(&RunSummary {
+            alg_name: "alg".to_string(),
+            status: RunStatus::Simulated,
+            result: true,
+            worst_response_times: vec![3],
+            screening_violations: Vec::new(),
+            resource_usage: None,
+            slack: Vec::new(),
+            score: None,
+        });
+        let manifest = run_dir.write_manifest(2, 1);
+        assert_eq!(manifest.files, vec!["log.yaml", "summary.yaml"]);
+
+        let loaded_manifest: RunManifest = serde_yaml::from_str(
+            &fs::read_to_string(run_dir.get_file_path("manifest", "yaml")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(loaded_manifest, manifest);
+        fs::remove_dir_all("../lib/tests/run_dir_test").unwrap();
+    }
+}
@@ -0,0 +1,310 @@
+//! Partitioned EDF: whole DAGs are assigned to cores by a bin-packing heuristic on their
+//! utilizations, and each core runs EDF on the nodes of its DAGs only, without migration.
+//!
+//! The DAGs are packed in the order of `dag_id`, so the decreasing variants of the heuristics
+//! (e.g., First-Fit Decreasing) are obtained by sorting the DAG set by utilization beforehand.
+//! A DAG that fits on no core is placed on the least utilized core and recorded as unfit,
+//! so that the simulation shows its deadline misses.
+use crate::dag_set_scheduler::{DAGSetSchedulerBase, NodeDataWrapper, PreemptiveType, ReadyQueue};
+use crate::getset_dag_set_scheduler;
+use crate::{
+    core::ProcessResult,
+    dag_set_scheduler::{DAGStateManager, DAGStateManagerBase},
+    dag_task::DagTask,
+    graph_extension::{get_node_index_maps, NodeData, NodeIndexMap},
+    homogeneous::HomogeneousProcessor,
+    log::{DAGSetSchedulerLog, JobEventTimes, PartitioningLog},
+    processor::ProcessorBase,
+    util::{clear_interrupted, get_hyper_period, get_process_core_indices, is_interrupted},
+};
+use petgraph::graph::Graph;
+use serde_derive::{Deserialize, Serialize};
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PartitioningHeuristic {
+    /// The core with the smallest index that fits.
+    #[default]
+    FirstFit,
+    /// The most utilized core that fits, which leaves room for heavy DAGs.
+    BestFit,
+    /// The least utilized core that fits, which balances the load.
+    WorstFit,
+}
+
+impl FromStr for PartitioningHeuristic {
+    type Err = String;
+
+    fn from_str(heuristic: &str) -> Result<Self, Self::Err> {
+        match heuristic.to_lowercase().as_str() {
+            "first_fit" => Ok(Self::FirstFit),
+            "best_fit" => Ok(Self::BestFit),
+            "worst_fit" => Ok(Self::WorstFit),
+            _ => Err(format!("Unknown partitioning heuristic: {}", heuristic)),
+        }
+    }
+}
+
+/// Assignment of the DAGs to the cores.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Partition {
+    /// Core of each DAG by `dag_id`.
+    pub core_ids: Vec<usize>,
+    pub core_utilizations: Vec<f32>,
+    /// DAGs that fit on no core.
+    pub unfit_dag_ids: Vec<usize>,
+}
+
+/// Ties are broken by the smallest core index.
+pub fn partition_dag_set(
+    dag_set: &[Graph<NodeData, i32>],
+    number_of_cores: usize,
+    heuristic: PartitioningHeuristic,
+) -> Partition {
+    let mut partition = Partition {
+        core_ids: Vec::with_capacity(dag_set.len()),
+        core_utilizations: vec![0.0; number_of_cores],
+        unfit_dag_ids: Vec::new(),
+    };
+    for (dag_id, dag) in dag_set.iter().enumerate() {
+        let utilization = DagTask::from_graph(dag)
+            .get_utilization()
+            .unwrap_or_else(|| panic!("DAG {} has no period.", dag_id));
+        let core_utilizations = &partition.core_utilizations;
+        let core_i =
+            select_fit_core(core_utilizations, utilization, heuristic).unwrap_or_else(|| {
+                partition.unfit_dag_ids.push(dag_id);
+                (0..number_of_cores)
+                    .min_by(|&a, &b| core_utilizations[a].total_cmp(&core_utilizations[b]))
+                    .unwrap()
+            });
+        partition.core_ids.push(core_i);
+        partition.core_utilizations[core_i] += utilization;
+    }
+    partition
+}
+
+/// The core on which `utilization` fits by `heuristic`, with ties broken by the smallest core
+/// index.
+pub fn select_fit_core(
+    core_utilizations: &[f32],
+    utilization: f32,
+    heuristic: PartitioningHeuristic,
+) -> Option<usize> {
+    let fit_core_indices = (0..core_utilizations.len())
+        .filter(|&core_i| core_utilizations[core_i] + utilization <= 1.0);
+    match heuristic {
+        PartitioningHeuristic::FirstFit => fit_core_indices.min(),
+        PartitioningHeuristic::BestFit => fit_core_indices
+            .rev()
+            .max_by(|&a, &b| core_utilizations[a].total_cmp(&core_utilizations[b])),
+        PartitioningHeuristic::WorstFit => {
+            fit_core_indices.min_by(|&a, &b| core_utilizations[a].total_cmp(&core_utilizations[b]))
+        }
+    }
+}
+
+/// Run EDF on each core over the nodes of the DAGs assigned to it, where `core_ids` is the core
+/// of each DAG by `dag_id`.
+pub fn schedule_partitioned_edf<S: DAGSetSchedulerBase<HomogeneousProcessor>>(
+    scheduler: &mut S,
+    core_ids: &[usize],
+    preemptive_type: PreemptiveType,
+) -> i32 {
+    let dag_set = scheduler.get_dag_set();
+    let mut managers = vec![DAGStateManager::default(); dag_set.len()];
+    let mut ready_queues = vec![ReadyQueue::new(); scheduler.get_processor().get_number_of_cores()];
+    let hyper_period = get_hyper_period(&dag_set);
+    // Insert the node into the ready queue of the core of its DAG.
+    let insert_ready_node =
+        |log: &mut DAGSetSchedulerLog, ready_queues: &mut [ReadyQueue], node_data: NodeData| {
+            let core_i = core_ids[node_data.get_params_value("dag_id") as usize];
+            ready_queues[core_i].insert_new(NodeDataWrapper::new(node_data));
+            log.write_ready_queue_insertion();
+        };
+    while scheduler.get_current_time() < hyper_period && !is_interrupted() {
+        // Release DAGs
+        for ready_node in scheduler.release_dags(&mut managers) {
+            insert_ready_node(scheduler.get_log_mut(), &mut ready_queues, ready_node);
+        }
+
+        // Allocate the head of each ready queue to its core, preempting a later deadline.
+        for (core_i, ready_queue) in ready_queues.iter_mut().enumerate() {
+            let Some(head) = ready_queue.first().cloned() else {
+                continue;
+            };
+            let core = &scheduler.get_processor().cores[core_i];
+            if !core.get_is_idle() {
+                let Some(preemptive_key) = preemptive_type.get_key() else {
+                    continue;
+                };
+                if core.run_time < preemptive_type.get_non_preemptive_region() {
+                    continue;
+                }
+                ready_queue.count_comparison();
+                let running_node = core.get_processing_node().as_ref().unwrap();
+                if head.node_data.get_params_value(preemptive_key)
+                    >= running_node.get_params_value(preemptive_key)
+                {
+                    continue;
+                }
+                let current_time = scheduler.get_current_time();
+                let preempted_node_data = scheduler.get_processor_mut().preempt(core_i).unwrap();
+                let log = scheduler.get_log_mut();
+                log.write_job_event(
+                    &preempted_node_data,
+                    core_i,
+                    managers[preempted_node_data.get_params_value("dag_id") as usize]
+                        .get_release_count() as usize
+                        - 1,
+                    JobEventTimes::PreemptedTime(current_time),
+                );
+                ready_queue.insert_new(NodeDataWrapper::new(preempted_node_data));
+                log.write_ready_queue_insertion();
+            }
+            ready_queue.remove(&head);
+            scheduler.get_log_mut().write_ready_queue_removal();
+            let node_data = head.convert_node_data();
+            scheduler.allocate_node(
+                &node_data,
+                core_i,
+                managers[node_data.get_params_value("dag_id") as usize].get_release_count()
+                    as usize,
+            );
+        }
+
+        // Process unit time
+        let process_result = scheduler.process_unit_time();
+        scheduler
+            .get_log_mut()
+            .write_processing_time(&get_process_core_indices(&process_result));
+
+        // Post-process on completion of node execution
+        for (core_id, result) in process_result.iter().enumerate() {
+            if let ProcessResult::Done(node_data) = result {
+                let ready_nodes =
+                    scheduler.post_process_on_node_completion(node_data, core_id, &mut managers);
+                for ready_node in ready_nodes {
+                    insert_ready_node(scheduler.get_log_mut(), &mut ready_queues, ready_node);
+                }
+            }
+        }
+    }
+
+    scheduler.get_log_mut().write_comparisons(
+        ready_queues
+            .iter()
+            .map(ReadyQueue::get_comparison_count)
+            .sum(),
+    );
+    scheduler.calculate_log();
+    scheduler.get_current_time()
+}
+
+pub struct PartitionedEDFScheduler {
+    dag_set: Vec<Graph<NodeData, i32>>,
+    node_index_maps: Vec<NodeIndexMap>,
+    processor: HomogeneousProcessor,
+    log: DAGSetSchedulerLog,
+    current_time: i32,
+    partition: Partition,
+}
+
+impl DAGSetSchedulerBase<HomogeneousProcessor> for PartitionedEDFScheduler {
+    /// Partition with First-Fit.
+    fn new(dag_set: &[Graph<NodeData, i32>], processor: &HomogeneousProcessor) -> Self {
+        Self::new_with_heuristic(dag_set, processor, PartitioningHeuristic::default())
+    }
+
+    getset_dag_set_scheduler!(HomogeneousProcessor);
+
+    fn schedule(&mut self, preemptive_type: PreemptiveType) -> i32 {
+        clear_interrupted();
+        self.get_log_mut().write_schedule_start();
+        let core_ids = self.partition.core_ids.clone();
+        schedule_partitioned_edf(self, &core_ids, preemptive_type)
+    }
+}
+
+impl PartitionedEDFScheduler {
+    pub fn new_with_heuristic(
+        dag_set: &[Graph<NodeData, i32>],
+        processor: &HomogeneousProcessor,
+        heuristic: PartitioningHeuristic,
+    ) -> Self {
+        let partition = partition_dag_set(dag_set, processor.get_number_of_cores(), heuristic);
+        let mut log = DAGSetSchedulerLog::new(dag_set, processor.get_number_of_cores());
+        log.write_partitioning(PartitioningLog::new(heuristic, partition.clone()));
+        Self {
+            node_index_maps: get_node_index_maps(dag_set),
+            dag_set: dag_set.to_vec(),
+            processor: processor.clone(),
+            log,
+            current_time: 0,
+            partition,
+        }
+    }
+
+    pub fn get_partition(&self) -> &Partition {
+        &self.partition
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph_extension::GraphExtension;
+    use crate::test_util::create_node;
+
+    fn create_single_node_dag(execution_time: i32, dag_id: i32) -> Graph<NodeData, i32> {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", execution_time));
+        dag.add_param(n0, "period", 8);
+        dag.add_param(n0, "end_to_end_deadline", 8);
+        dag.set_dag_param("dag_id", dag_id);
+        dag
+    }
+
+    #[test]
+    fn test_partitioned_edf_scheduler_normal() {
+        // Utilizations 0.5, 0.625, 0.25, 0.125 on two cores.
+        let dag_set = vec![
+            create_single_node_dag(4, 0),
+            create_single_node_dag(5, 1),
+            create_single_node_dag(2, 2),
+            create_single_node_dag(1, 3),
+        ];
+        let processor = HomogeneousProcessor::new(2);
+        let get_core_ids = |heuristic| partition_dag_set(&dag_set, 2, heuristic).core_ids;
+        assert_eq!(
+            get_core_ids(PartitioningHeuristic::BestFit),
+            vec![0, 1, 1, 1]
+        );
+        assert_eq!(
+            get_core_ids(PartitioningHeuristic::WorstFit),
+            vec![0, 1, 0, 1]
+        );
+        assert_eq!(
+            partition_dag_set(&dag_set, 1, PartitioningHeuristic::FirstFit).unfit_dag_ids,
+            vec![1, 2, 3]
+        );
+
+        let mut scheduler = PartitionedEDFScheduler::new_with_heuristic(
+            &dag_set,
+            &processor,
+            PartitioningHeuristic::FirstFit,
+        );
+        assert_eq!(scheduler.get_partition().core_ids, vec![0, 1, 0, 0]);
+        scheduler.schedule(PreemptiveType::Preemptive {
+            key: "node_absolute_deadline".to_string(),
+        });
+        let log = scheduler.get_log_mut();
+        // DAGs 0, 2, and 3 run one after another on core 0.
+        assert_eq!(log.get_worst_response_times(), vec![4, 5, 6, 7]);
+        assert_eq!(
+            log.get_partitioning().unwrap().get_heuristic(),
+            PartitioningHeuristic::FirstFit
+        );
+    }
+}
@@ -0,0 +1,268 @@
+//! Assign periods to DAGs so that each DAG has the target utilization.
+use crate::{
+    dag_creator::get_yaml_paths_from_dir,
+    graph_extension::{GraphExtension, NodeData},
+    util::load_yaml,
+};
+use log::warn;
+use petgraph::Graph;
+use std::fs;
+use yaml_rust::{Yaml, YamlEmitter};
+
+/// How the raw period (volume / target utilization) is rounded.
+///
+/// Periods are always rounded up, so the resulting utilization never exceeds the target.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PeriodRounding {
+    /// Round up to a multiple of the given granularity.
+    Granularity(i32),
+    /// Use the smallest period in the given set that is not shorter than the raw period.
+    Harmonic(Vec<i32>),
+}
+
+/// Round `raw_period` according to `rounding`.
+pub fn round_period(raw_period: f64, rounding: &PeriodRounding) -> i32 {
+    match rounding {
+        PeriodRounding::Granularity(granularity) => {
+            if *granularity <= 0 {
+                panic!(
+                    "The granularity must be positive. granularity: {}",
+                    granularity
+                );
+            }
+            let granularity = *granularity as f64;
+            ((raw_period / granularity).ceil() * granularity) as i32
+        }
+        PeriodRounding::Harmonic(periods) => {
+            let mut sorted_periods = periods.clone();
+            sorted_periods.sort();
+            match sorted_periods
+                .iter()
+                .find(|&&period| period as f64 >= raw_period)
+            {
+                Some(period) => *period,
+                None => {
+                    let longest_period = *sorted_periods
+                        .last()
+                        .unwrap_or_else(|| panic!("The harmonic period set is empty."));
+                    warn!(
+                        "No harmonic period is longer than {}. The longest period {} is used and the target utilization is exceeded.",
+                        raw_period, longest_period
+                    );
+                    longest_period
+                }
+            }
+        }
+    }
+}
+
+fn calculate_period(volume: f64, target_utilization: f32, rounding: &PeriodRounding) -> i32 {
+    if target_utilization <= 0.0 {
+        panic!(
+            "The target utilization must be positive. target_utilization: {}",
+            target_utilization
+        );
+    }
+    round_period(volume / target_utilization as f64, rounding)
+}
+
+/// Assign a period to each DAG in `dag_set` from its target utilization.
+///
+/// The `period` param of the head node is overwritten if it exists; otherwise it is added to the
+/// first source node.
+///
+/// # Returns
+///
+/// * The assigned periods in the order of `dag_set`.
+pub fn assign_periods(
+    dag_set: &mut [Graph<NodeData, i32>],
+    target_utilizations: &[f32],
+    rounding: &PeriodRounding,
+) -> Vec<i32> {
+    if dag_set.len() != target_utilizations.len() {
+        panic!("The number of target utilizations must equal the number of DAGs.");
+    }
+    dag_set
+        .iter_mut()
+        .zip(target_utilizations)
+        .map(|(dag, &target_utilization)| {
+            let period = calculate_period(dag.get_volume() as f64, target_utilization, rounding);
+            let source_nodes = dag.get_source_nodes();
+            match source_nodes
+                .iter()
+                .find(|&&source_i| dag[source_i].params.contains_key("period"))
+            {
+                Some(&source_i) => dag.update_param(source_i, "period", period),
+                None => dag.add_param(source_nodes[0], "period", period),
+            }
+            period
+        })
+        .collect()
+}
+
+fn get_yaml_number(yaml: &Yaml) -> f64 {
+    match yaml {
+        Yaml::Integer(value) => *value as f64,
+        Yaml::Real(_) => yaml.as_f64().unwrap(),
+        _ => panic!("The value is not a number: {:?}", yaml),
+    }
+}
+
+/// Set `period` on the node that already has it, or on the first source node of the YAML DAG.
+fn set_period_to_yaml_dag(yaml_doc: &mut Yaml, period: i32) {
+    let target_ids: Vec<i64> = yaml_doc["links"]
+        .as_vec()
+        .map(|links| {
+            links
+                .iter()
+                .map(|link| link["target"].as_i64().unwrap())
+                .collect()
+        })
+        .unwrap_or_default();
+    let Yaml::Hash(dag_hash) = yaml_doc else {
+        panic!("YAML files are not DAG structures.");
+    };
+    let Some(Yaml::Array(nodes)) = dag_hash.get_mut(&Yaml::String("nodes".to_string())) else {
+        panic!("YAML files are not DAG structures.");
+    };
+    let period_key = Yaml::String("period".to_string());
+    let node_i = nodes
+        .iter()
+        .position(|node| !node["period"].is_badvalue())
+        .or_else(|| {
+            nodes
+                .iter()
+                .position(|node| !target_ids.contains(&node["id"].as_i64().unwrap()))
+        })
+        .expect("No source node found.");
+    if let Yaml::Hash(node) = &mut nodes[node_i] {
+        node.insert(period_key, Yaml::Integer(period as i64));
+    }
+}
+
+/// Assign periods to the DAG YAML files in `src_dir_path` and write them to `dst_dir_path`.
+///
+/// Files are processed in the same (sorted) order as `create_dag_set_from_dir`, so
+/// `target_utilizations[i]` corresponds to the DAG with `dag_id == i`.
+/// The volume is computed from the values written in each file, so the assigned periods are in the
+/// same time unit as the input execution times. All other fields are kept as they are.
+///
+/// # Returns
+///
+/// * The assigned periods in the order of the files.
+pub fn assign_periods_to_yaml_dir(
+    src_dir_path: &str,
+    dst_dir_path: &str,
+    target_utilizations: &[f32],
+    rounding: &PeriodRounding,
+) -> Vec<i32> {
+    let mut file_path_list = get_yaml_paths_from_dir(src_dir_path);
+    file_path_list.sort();
+    if file_path_list.len() != target_utilizations.len() {
+        panic!("The number of target utilizations must equal the number of DAG files.");
+    }
+    fs::create_dir_all(dst_dir_path).unwrap();
+
+    file_path_list
+        .iter()
+        .zip(target_utilizations)
+        .map(|(file_path, &target_utilization)| {
+            let mut yaml_doc = load_yaml(file_path).remove(0);
+            let volume: f64 = yaml_doc["nodes"]
+                .as_vec()
+                .unwrap_or_else(|| panic!("YAML files are not DAG structures."))
+                .iter()
+                .map(|node| get_yaml_number(&node["execution_time"]))
+                .sum();
+            let period = calculate_period(volume, target_utilization, rounding);
+            set_period_to_yaml_dag(&mut yaml_doc, period);
+
+            let mut yaml_string = String::new();
+            YamlEmitter::new(&mut yaml_string).dump(&yaml_doc).unwrap();
+            let file_name = std::path::Path::new(file_path).file_name().unwrap();
+            fs::write(
+                format!("{}/{}", dst_dir_path, file_name.to_str().unwrap()),
+                yaml_string + "\n",
+            )
+            .unwrap();
+            period
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dag_creator::create_dag_set_from_dir;
+    use crate::test_util::create_node;
+    use petgraph::graph::NodeIndex;
+
+    fn create_sample_dag() -> Graph<NodeData, i32> {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 10));
+        let n1 = dag.add_node(create_node(1, "execution_time", 20));
+        let n2 = dag.add_node(create_node(2, "execution_time", 15));
+        dag.add_edge(n0, n1, 1);
+        dag.add_edge(n0, n2, 1);
+
+        dag
+    }
+
+    #[test]
+    fn test_round_period_granularity() {
+        assert_eq!(round_period(101.0, &PeriodRounding::Granularity(10)), 110);
+        assert_eq!(round_period(100.0, &PeriodRounding::Granularity(10)), 100);
+    }
+
+    #[test]
+    fn test_round_period_harmonic() {
+        let harmonic = PeriodRounding::Harmonic(vec![400, 100, 200]);
+        assert_eq!(round_period(101.0, &harmonic), 200);
+        assert_eq!(round_period(50.0, &harmonic), 100);
+        assert_eq!(round_period(500.0, &harmonic), 400);
+    }
+
+    #[test]
+    fn test_assign_periods_normal() {
+        let mut dag = create_sample_dag();
+        dag.add_param(NodeIndex::new(0), "period", 1000);
+        let mut dag_set = vec![dag, create_sample_dag()];
+
+        let periods = assign_periods(&mut dag_set, &[0.5, 0.3], &PeriodRounding::Granularity(10));
+
+        assert_eq!(periods, vec![90, 150]);
+        assert_eq!(dag_set[0].get_head_period().unwrap(), 90);
+        assert_eq!(dag_set[1].get_head_period().unwrap(), 150);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_assign_periods_length_mismatch() {
+        assign_periods(
+            &mut [create_sample_dag()],
+            &[0.5, 0.3],
+            &PeriodRounding::Granularity(10),
+        );
+    }
+
+    #[test]
+    fn test_assign_periods_to_yaml_dir_normal() {
+        let dst_dir_path = "tests/assign_periods_to_yaml_dir_normal";
+        let periods = assign_periods_to_yaml_dir(
+            "tests/sample_dags/multiple_yaml",
+            dst_dir_path,
+            &[0.5, 0.25],
+            &PeriodRounding::Harmonic(vec![100, 200, 400]),
+        );
+
+        let dag_set = create_dag_set_from_dir(dst_dir_path);
+        assert_eq!(dag_set[0].get_head_period().unwrap(), periods[0]);
+        assert_eq!(dag_set[1].get_head_period().unwrap(), periods[1]);
+        assert!(dag_set[0].get_volume() as f32 / periods[0] as f32 <= 0.5);
+        assert!(dag_set[1].get_volume() as f32 / periods[1] as f32 <= 0.25);
+        // Other params are kept.
+        assert_eq!(dag_set[0].get_end_to_end_deadline().unwrap(), 225);
+
+        fs::remove_dir_all(dst_dir_path).unwrap();
+    }
+}
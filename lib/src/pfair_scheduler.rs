@@ -0,0 +1,394 @@
+//! Proportionate-fair (PFair) scheduler with the PD² priorities (Anderson and Srinivasan).
+//!
+//! Time is divided into slots of `quantum` time units, and the execution time of each node is
+//! decomposed into sub-jobs of a quantum (the last one may be shorter). A DAG with `e` sub-jobs
+//! per job and a relative deadline of `d` slots has the weight `e / d`, and the k-th sub-job
+//! (from 1) of a job released in slot `r` may only run in its window from
+//! `r + floor((k - 1) * d / e)` to `r + ceil(k * d / e)`. In each slot, the eligible sub-jobs
+//! are chosen by the earliest window end, then the b-bit, then the latest group deadline.
+//!
+//! A DAG runs up to `ceil(e / d)` sub-jobs in a slot, each on a distinct ready node, preferring
+//! the nodes already running to avoid preemptions. Since the precedence constraints may leave an
+//! eligible sub-job without a ready node, PFair is optimal only if each DAG has enough ready
+//! nodes, e.g., for DAGs of a single node with weights up to 1.
+use crate::dag_set_scheduler::{
+    DAGSetSchedulerBase, DAGState, DAGStateManager, DAGStateManagerBase, PreemptiveType,
+};
+use crate::getset_dag_set_scheduler;
+use crate::{
+    core::ProcessResult,
+    graph_extension::{get_node_index_maps, GraphExtension, NodeData, NodeIndexMap},
+    homogeneous::HomogeneousProcessor,
+    log::{DAGSetSchedulerLog, JobEventTimes},
+    processor::ProcessorBase,
+    util::{clear_interrupted, get_hyper_period, get_process_core_indices, is_interrupted},
+};
+use log::warn;
+use petgraph::graph::Graph;
+use std::cmp::Reverse;
+
+pub struct PFairScheduler {
+    dag_set: Vec<Graph<NodeData, i32>>,
+    node_index_maps: Vec<NodeIndexMap>,
+    processor: HomogeneousProcessor,
+    log: DAGSetSchedulerLog,
+    current_time: i32,
+    quantum: i32,
+}
+
+/// The sub-jobs per job and the relative deadline in slots of a DAG, whose ratio is its weight.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PFairWeight {
+    pub sub_jobs: i32,
+    pub deadline_slots: i32,
+}
+
+impl PFairWeight {
+    /// Window of the k-th sub-job (from 1) in slots from the release, end exclusive.
+    pub fn get_window(&self, k: i32) -> (i32, i32) {
+        (
+            (k - 1) * self.deadline_slots / self.sub_jobs,
+            (k * self.deadline_slots + self.sub_jobs - 1) / self.sub_jobs,
+        )
+    }
+
+    /// Whether the window of the k-th sub-job overlaps that of the next one.
+    pub fn get_b_bit(&self, k: i32) -> bool {
+        k * self.deadline_slots % self.sub_jobs != 0
+    }
+
+    /// The group deadline of the k-th sub-job, by which a cascade of sub-jobs with overlapping
+    /// windows ends. Only heavy DAGs, with weights in [1/2, 1), have group deadlines (0 otherwise).
+    pub fn get_group_deadline(&self, k: i32) -> i32 {
+        if 2 * self.sub_jobs < self.deadline_slots || self.sub_jobs >= self.deadline_slots {
+            return 0;
+        }
+        let (_, deadline) = self.get_window(k);
+        for j in k..=self.sub_jobs {
+            let (window_start, window_end) = self.get_window(j);
+            if !self.get_b_bit(j) {
+                return window_end;
+            }
+            // A window of 3 slots ends the cascade a slot early, if not before the deadline.
+            if window_end - window_start == 3 && window_end > deadline {
+                return window_end - 1;
+            }
+        }
+        unreachable!("The last sub-job of a job has no b-bit.")
+    }
+
+    /// Sub-jobs of the DAG that may run in the same slot.
+    pub fn get_max_sub_jobs_per_slot(&self) -> usize {
+        ((self.sub_jobs + self.deadline_slots - 1) / self.deadline_slots) as usize
+    }
+}
+
+impl DAGSetSchedulerBase<HomogeneousProcessor> for PFairScheduler {
+    fn new(dag_set: &[Graph<NodeData, i32>], processor: &HomogeneousProcessor) -> Self {
+        Self {
+            node_index_maps: get_node_index_maps(dag_set),
+            dag_set: dag_set.to_vec(),
+            processor: processor.clone(),
+            log: DAGSetSchedulerLog::new(dag_set, processor.get_number_of_cores()),
+            current_time: 0,
+            quantum: 1,
+        }
+    }
+
+    getset_dag_set_scheduler!(HomogeneousProcessor);
+
+    /// The preemptive type is ignored because PFair preempts at every slot boundary.
+    fn schedule(&mut self, _preemptive_type: PreemptiveType) -> i32 {
+        clear_interrupted();
+        self.log.write_schedule_start();
+        let dag_set = self.get_dag_set();
+        let weights: Vec<PFairWeight> = dag_set.iter().map(|dag| self.get_weight(dag)).collect();
+        let total_weight: f32 = weights
+            .iter()
+            .map(|weight| weight.sub_jobs as f32 / weight.deadline_slots as f32)
+            .sum();
+        if total_weight > self.processor.get_number_of_cores() as f32 {
+            warn!(
+                "The total weight {} of the DAG set exceeds the number of cores {}.",
+                total_weight,
+                self.processor.get_number_of_cores()
+            );
+        }
+        let mut managers = vec![DAGStateManager::default(); dag_set.len()];
+        let mut ready_nodes: Vec<Vec<NodeData>> = vec![Vec::new(); dag_set.len()];
+        let mut release_slots = vec![0; dag_set.len()];
+        let mut scheduled_sub_jobs = vec![0; dag_set.len()];
+        let hyper_period = get_hyper_period(&dag_set);
+
+        while self.get_current_time() < hyper_period && !is_interrupted() {
+            let slot = self.get_current_time() / self.quantum;
+
+            // Release DAGs
+            for ready_node in self.release_dags(&mut managers) {
+                let dag_id = ready_node.get_params_value("dag_id") as usize;
+                release_slots[dag_id] = slot;
+                scheduled_sub_jobs[dag_id] = 0;
+                ready_nodes[dag_id].push(ready_node);
+                self.log.write_ready_queue_insertion();
+            }
+
+            let sub_job_counts = self.select_sub_jobs(
+                &weights,
+                &managers,
+                &ready_nodes,
+                &release_slots,
+                &scheduled_sub_jobs,
+                slot,
+            );
+
+            // Preempt the running nodes of the DAGs given fewer sub-jobs than they run.
+            let current_time = self.get_current_time();
+            let mut kept_counts = vec![0; dag_set.len()];
+            for core_i in 0..self.processor.get_number_of_cores() {
+                let Some(running_node) = self.processor.get_processing_node(core_i) else {
+                    continue;
+                };
+                let dag_id = running_node.get_params_value("dag_id") as usize;
+                if kept_counts[dag_id] < sub_job_counts[dag_id] {
+                    kept_counts[dag_id] += 1;
+                    continue;
+                }
+                let preempted_node_data = self.processor.preempt(core_i).unwrap();
+                self.log.write_job_event(
+                    &preempted_node_data,
+                    core_i,
+                    managers[dag_id].get_release_count() as usize - 1,
+                    JobEventTimes::PreemptedTime(current_time),
+                );
+                ready_nodes[dag_id].push(preempted_node_data);
+                self.log.write_ready_queue_insertion();
+            }
+
+            // Allocate ready nodes for the rest of the sub-jobs.
+            for (dag_id, &sub_job_count) in sub_job_counts.iter().enumerate() {
+                ready_nodes[dag_id].sort_by_key(|node_data| node_data.get_id());
+                for _ in kept_counts[dag_id]..sub_job_count {
+                    let node_data = ready_nodes[dag_id].remove(0);
+                    self.log.write_ready_queue_removal();
+                    let idle_core_i = self.processor.get_idle_core_index().unwrap();
+                    self.allocate_node(
+                        &node_data,
+                        idle_core_i,
+                        managers[dag_id].get_release_count() as usize,
+                    );
+                }
+                scheduled_sub_jobs[dag_id] += sub_job_count as i32;
+            }
+
+            // Process the slot. The nodes completing within the slot leave their cores idle.
+            let slot_end = (slot + 1) * self.quantum;
+            while self.get_current_time() < slot_end {
+                let (elapsed_time, process_result) = self
+                    .processor
+                    .process_for(slot_end - self.get_current_time());
+                self.current_time += elapsed_time;
+                let indices: Vec<usize> = get_process_core_indices(&process_result);
+                self.log.write_processing_time_for(&indices, elapsed_time);
+
+                // Post-process on completion of node execution
+                for (core_id, result) in process_result.iter().enumerate() {
+                    if let ProcessResult::Done(node_data) = result {
+                        for ready_node in
+                            self.post_process_on_node_completion(node_data, core_id, &mut managers)
+                        {
+                            ready_nodes[ready_node.get_params_value("dag_id") as usize]
+                                .push(ready_node);
+                            self.log.write_ready_queue_insertion();
+                        }
+                    }
+                }
+            }
+        }
+
+        self.calculate_log();
+        self.get_current_time()
+    }
+}
+
+impl PFairScheduler {
+    /// Length of the slots, to which the periods, offsets and deadlines must be aligned.
+    pub fn set_quantum(&mut self, quantum: i32) {
+        if quantum <= 0 {
+            panic!("The quantum must be positive.");
+        }
+        self.quantum = quantum;
+    }
+
+    /// The weight of `dag` with its nodes decomposed into sub-jobs of a quantum.
+    pub fn get_weight(&self, dag: &Graph<NodeData, i32>) -> PFairWeight {
+        let dag_id = dag.get_dag_param("dag_id");
+        let end_to_end_deadline = dag.get_end_to_end_deadline().unwrap();
+        let is_aligned = [
+            Some(end_to_end_deadline),
+            dag.get_head_period(),
+            Some(dag.get_head_offset()),
+        ]
+        .into_iter()
+        .flatten()
+        .all(|time| time % self.quantum == 0);
+        if !is_aligned {
+            panic!(
+                "The period, offset and deadline of DAG {} are not multiples of the quantum {}.",
+                dag_id, self.quantum
+            );
+        }
+        PFairWeight {
+            sub_jobs: dag
+                .node_weights()
+                .map(|node_data| {
+                    (node_data.get_params_value("execution_time") + self.quantum - 1) / self.quantum
+                })
+                .sum(),
+            deadline_slots: end_to_end_deadline / self.quantum,
+        }
+    }
+
+    /// The number of sub-jobs of each DAG to run in `slot`, chosen by PD² priority among the
+    /// eligible sub-jobs that have a ready or running node.
+    fn select_sub_jobs(
+        &self,
+        weights: &[PFairWeight],
+        managers: &[DAGStateManager],
+        ready_nodes: &[Vec<NodeData>],
+        release_slots: &[i32],
+        scheduled_sub_jobs: &[i32],
+        slot: i32,
+    ) -> Vec<usize> {
+        let mut running_counts = vec![0; weights.len()];
+        for core_i in 0..self.processor.get_number_of_cores() {
+            if let Some(node_data) = self.processor.get_processing_node(core_i) {
+                running_counts[node_data.get_params_value("dag_id") as usize] += 1;
+            }
+        }
+
+        let mut candidates = Vec::new();
+        for (dag_id, weight) in weights.iter().enumerate() {
+            if managers[dag_id].get_dag_state() == DAGState::Waiting {
+                continue;
+            }
+            let max_sub_jobs = weight
+                .get_max_sub_jobs_per_slot()
+                .min(running_counts[dag_id] + ready_nodes[dag_id].len());
+            let first_k = scheduled_sub_jobs[dag_id] + 1;
+            for k in (first_k..=weight.sub_jobs).take(max_sub_jobs) {
+                let (window_start, window_end) = weight.get_window(k);
+                if release_slots[dag_id] + window_start > slot {
+                    break;
+                }
+                let group_deadline = weight.get_group_deadline(k);
+                candidates.push((
+                    (
+                        release_slots[dag_id] + window_end,
+                        Reverse(weight.get_b_bit(k)),
+                        Reverse(group_deadline),
+                        dag_id,
+                        k,
+                    ),
+                    dag_id,
+                ));
+            }
+        }
+        candidates.sort();
+
+        let mut sub_job_counts = vec![0; weights.len()];
+        for (_, dag_id) in candidates
+            .into_iter()
+            .take(self.processor.get_number_of_cores())
+        {
+            sub_job_counts[dag_id] += 1;
+        }
+        sub_job_counts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::global_edf_scheduler::GlobalEDFScheduler;
+    use crate::test_util::create_node;
+
+    fn create_single_node_dag(execution_time: i32, period: i32) -> Graph<NodeData, i32> {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", execution_time));
+        dag.add_param(n0, "period", period);
+        dag.add_param(n0, "end_to_end_deadline", period);
+        dag
+    }
+
+    #[test]
+    fn test_pfair_weight_normal() {
+        // Weight 3/4: windows [0, 2), [1, 3), [2, 4).
+        let weight = PFairWeight {
+            sub_jobs: 3,
+            deadline_slots: 4,
+        };
+        assert_eq!(weight.get_window(1), (0, 2));
+        assert_eq!(weight.get_window(2), (1, 3));
+        assert_eq!(weight.get_window(3), (2, 4));
+        assert!(weight.get_b_bit(1));
+        assert!(!weight.get_b_bit(3));
+        assert_eq!(weight.get_group_deadline(1), 4);
+        // Light DAGs have no group deadline.
+        let weight = PFairWeight {
+            sub_jobs: 1,
+            deadline_slots: 3,
+        };
+        assert_eq!(weight.get_group_deadline(1), 0);
+    }
+
+    #[test]
+    fn test_pfair_schedules_full_utilization() {
+        // Three DAGs with weight 2/3 fully utilize 2 cores, which G-EDF cannot schedule.
+        let mut dag_set = vec![
+            create_single_node_dag(2, 3),
+            create_single_node_dag(2, 3),
+            create_single_node_dag(2, 3),
+        ];
+        for (dag_id, dag) in dag_set.iter_mut().enumerate() {
+            dag.set_dag_param("dag_id", dag_id as i32);
+        }
+        let processor = HomogeneousProcessor::new(2);
+
+        let mut pfair_scheduler = PFairScheduler::new(&dag_set, &processor);
+        pfair_scheduler.schedule(PreemptiveType::NonPreemptive);
+        let log = pfair_scheduler.get_log_mut();
+        for dag_id in 0..3 {
+            assert!(log.get_worst_response_time(dag_id) <= 3);
+        }
+
+        let mut global_edf_scheduler = GlobalEDFScheduler::new(&dag_set, &processor);
+        global_edf_scheduler.schedule(PreemptiveType::Preemptive {
+            key: "node_absolute_deadline".to_string(),
+        });
+        assert!(
+            global_edf_scheduler
+                .get_log_mut()
+                .get_worst_response_time(2)
+                > 3
+        );
+    }
+
+    #[test]
+    fn test_pfair_quantum_normal() {
+        let mut dag = create_single_node_dag(3, 4);
+        dag.set_dag_param("dag_id", 0);
+
+        let mut pfair_scheduler = PFairScheduler::new(&[dag], &HomogeneousProcessor::new(1));
+        pfair_scheduler.set_quantum(2);
+        // 2 sub-jobs in 2 slots, the second of which is 1 time unit.
+        assert_eq!(
+            pfair_scheduler.get_weight(&pfair_scheduler.get_dag_set()[0]),
+            PFairWeight {
+                sub_jobs: 2,
+                deadline_slots: 2,
+            }
+        );
+        pfair_scheduler.schedule(PreemptiveType::NonPreemptive);
+        assert_eq!(pfair_scheduler.get_log_mut().get_worst_response_time(0), 3);
+    }
+}
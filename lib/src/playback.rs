@@ -0,0 +1,108 @@
+//! Replay a computed schedule in scaled wall-clock time.
+//!
+//! Each job event of the log is emitted `event time * time_unit` after the start of the replay,
+//! e.g., to drive an external visualizer live or a hardware-in-the-loop demo.
+//! The replay stops early when the simulation is interrupted (Ctrl-C).
+use crate::{log::DAGSetSchedulerLog, log::JobLog, util::is_interrupted};
+use std::{
+    sync::mpsc::{channel, Receiver},
+    thread,
+    time::{Duration, Instant},
+};
+
+pub struct Playback {
+    job_logs: Vec<JobLog>,
+    time_unit: Duration,
+}
+
+impl Playback {
+    /// `time_unit` is the wall-clock duration of one time unit of the simulation.
+    pub fn new(log: &DAGSetSchedulerLog, time_unit: Duration) -> Self {
+        Self {
+            job_logs: log
+                .get_job_logs_in_time_order()
+                .into_iter()
+                .cloned()
+                .collect(),
+            time_unit,
+        }
+    }
+
+    /// Call `on_event` with each job event at its scaled time. `on_event` returns `false` to stop.
+    fn play_while(&self, mut on_event: impl FnMut(&JobLog) -> bool) {
+        let start = Instant::now();
+        for job_log in self.job_logs.iter() {
+            let event_time = self.time_unit * job_log.get_event_time().get_time() as u32;
+            thread::sleep(event_time.saturating_sub(start.elapsed()));
+            if is_interrupted() || !on_event(job_log) {
+                return;
+            }
+        }
+    }
+
+    /// Call `on_event` with each job event at its scaled time. Blocks until the replay ends.
+    pub fn play(&self, mut on_event: impl FnMut(&JobLog)) {
+        self.play_while(|job_log| {
+            on_event(job_log);
+            true
+        });
+    }
+
+    /// Replay on another thread and send each job event to the returned channel at its scaled time.
+    /// The replay stops when the receiver is dropped.
+    pub fn spawn(self) -> Receiver<JobLog> {
+        let (sender, receiver) = channel();
+        thread::spawn(move || {
+            self.play_while(|job_log| sender.send(job_log.clone()).is_ok());
+        });
+        receiver
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::create_node;
+    use crate::{
+        dag_set_scheduler::{DAGSetSchedulerBase, PreemptiveType},
+        global_edf_scheduler::GlobalEDFScheduler,
+        graph_extension::{GraphExtension, NodeData},
+        homogeneous::HomogeneousProcessor,
+        log::JobEventTimes,
+        processor::ProcessorBase,
+    };
+    use petgraph::Graph;
+
+    #[test]
+    fn test_playback_normal() {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 2));
+        let n1 = dag.add_node(create_node(1, "execution_time", 3));
+        dag.add_param(n0, "period", 10);
+        dag.add_param(n1, "end_to_end_deadline", 10);
+        dag.add_edge(n0, n1, 0);
+        dag.set_dag_param("dag_id", 0);
+
+        let mut global_edf_scheduler =
+            GlobalEDFScheduler::new(&[dag], &HomogeneousProcessor::new(1));
+        global_edf_scheduler.schedule(PreemptiveType::NonPreemptive);
+        let playback = Playback::new(global_edf_scheduler.get_log_mut(), Duration::from_millis(1));
+
+        let start = Instant::now();
+        let event_times: Vec<JobEventTimes> = playback
+            .spawn()
+            .iter()
+            .map(|job_log| job_log.get_event_time().clone())
+            .collect();
+        assert!(start.elapsed() >= Duration::from_millis(5));
+        assert_eq!(
+            event_times,
+            vec![
+                JobEventTimes::StartTime(0),
+                JobEventTimes::FinishTime(2),
+                JobEventTimes::StartTime(2),
+                JobEventTimes::FinishTime(5),
+            ]
+        );
+    }
+}
@@ -0,0 +1,61 @@
+//! Post-process the simulation log with a user-provided Rhai script (feature `plugin`).
+//!
+//! The script sees the log as the map `log` with the same keys as the dumped log, and returns
+//! a map of custom metrics, which are added to the log as `plugin_metrics`. For example:
+//!
+//! ```rhai
+//! let total = 0;
+//! for dag_log in log.dag_set_log {
+//!     total += dag_log.worst_response_time;
+//! }
+//! #{ total_worst_response_time: total }
+//! ```
+use rhai::{serde::from_dynamic, serde::to_dynamic, Engine, Scope};
+use serde::Serialize;
+use std::{collections::BTreeMap, fs};
+
+/// Run the script at `script_path` over `log` and return the metrics it computes.
+pub fn run_post_processing_script(
+    script_path: &str,
+    log: &impl Serialize,
+) -> BTreeMap<String, serde_json::Value> {
+    let script = fs::read_to_string(script_path)
+        .unwrap_or_else(|err| panic!("Failed to read {}: {}", script_path, err));
+    let mut scope = Scope::new();
+    scope.push_dynamic("log", to_dynamic(log).expect("Failed to convert the log."));
+    let metrics = Engine::new()
+        .eval_with_scope(&mut scope, &script)
+        .unwrap_or_else(|err| panic!("Failed to run {}: {}", script_path, err));
+    from_dynamic(&metrics).unwrap_or_else(|err| {
+        panic!(
+            "{} must return a map of metrics, but returned {}: {}",
+            script_path,
+            metrics.type_name(),
+            err
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_run_post_processing_script_normal() {
+        let script_path = "../lib/tests/plugin_test.rhai";
+        fs::write(
+            script_path,
+            "let total = 0;\nfor response_time in log.response_time { total += response_time; }\n#{ total_response_time: total, dags: log.response_time.len() }\n",
+        )
+        .unwrap();
+        let metrics = run_post_processing_script(
+            script_path,
+            &BTreeMap::from([("response_time", vec![3, 4])]),
+        );
+        fs::remove_file(script_path).unwrap();
+
+        assert_eq!(metrics["total_response_time"], json!(7));
+        assert_eq!(metrics["dags"], json!(2));
+    }
+}
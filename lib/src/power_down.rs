@@ -0,0 +1,186 @@
+//! Idle power-down of the cores of a processor.
+//!
+//! A core idle for `idle_threshold` time units powers down. The next node dispatched to it waits
+//! for `wake_up_latency` time units before it runs, during which the core counts as busy. The
+//! energy is accounted in units of the power of a busy core over a time unit.
+use crate::log::{PowerLog, SleepInterval};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PowerDownModel {
+    pub idle_threshold: i32,
+    pub wake_up_latency: i32,
+    /// Power of an idle core that has not powered down, relative to a busy core.
+    pub idle_power: f32,
+    /// Power of a core that has powered down, relative to a busy core.
+    pub sleep_power: f32,
+}
+
+impl PowerDownModel {
+    pub fn new(
+        idle_threshold: i32,
+        wake_up_latency: i32,
+        idle_power: f32,
+        sleep_power: f32,
+    ) -> Self {
+        if idle_threshold < 0 || wake_up_latency < 0 {
+            panic!("The idle threshold and the wake-up latency must not be negative.");
+        }
+        if idle_power < 0.0 || sleep_power < 0.0 {
+            panic!("The power of the idle and sleep states must not be negative.");
+        }
+        Self {
+            idle_threshold,
+            wake_up_latency,
+            idle_power,
+            sleep_power,
+        }
+    }
+}
+
+/// Power states of the cores, advanced by the processor with its own clock.
+#[derive(Clone, Debug)]
+pub struct CorePowerStates {
+    model: PowerDownModel,
+    time: i32,
+    /// Since when each core is idle. `None` while a node is allocated to it.
+    idle_since: Vec<Option<i32>>,
+    /// Time each core still needs to wake up before its node runs.
+    wake_up_times: Vec<i32>,
+    busy_times: Vec<i32>,
+    sleep_intervals: Vec<SleepInterval>,
+}
+
+impl CorePowerStates {
+    pub fn new(model: PowerDownModel, number_of_cores: usize) -> Self {
+        Self {
+            model,
+            time: 0,
+            idle_since: vec![Some(0); number_of_cores],
+            wake_up_times: vec![0; number_of_cores],
+            busy_times: vec![0; number_of_cores],
+            sleep_intervals: Vec::new(),
+        }
+    }
+
+    pub fn get_model(&self) -> &PowerDownModel {
+        &self.model
+    }
+
+    /// The time from which the idle core is asleep, if it has powered down.
+    fn get_sleep_start_time(&self, core_id: usize) -> Option<i32> {
+        self.idle_since[core_id]
+            .map(|idle_since| idle_since + self.model.idle_threshold)
+            .filter(|&sleep_start_time| sleep_start_time <= self.time)
+    }
+
+    pub fn is_asleep(&self, core_id: usize) -> bool {
+        self.get_sleep_start_time(core_id).is_some()
+    }
+
+    /// A node was allocated to the core, which wakes up first if it is asleep.
+    pub fn start(&mut self, core_id: usize) {
+        if let Some(start_time) = self.get_sleep_start_time(core_id) {
+            self.sleep_intervals.push(SleepInterval {
+                core_id,
+                start_time,
+                end_time: self.time,
+            });
+            self.wake_up_times[core_id] = self.model.wake_up_latency;
+        }
+        self.idle_since[core_id] = None;
+    }
+
+    /// The node of the core completed or was preempted.
+    pub fn stop(&mut self, core_id: usize) {
+        self.idle_since[core_id] = Some(self.time);
+        self.wake_up_times[core_id] = 0;
+    }
+
+    pub fn get_wake_up_time(&self, core_id: usize) -> i32 {
+        self.wake_up_times[core_id]
+    }
+
+    /// Spend up to `time` units waking the core up.
+    ///
+    /// # Returns
+    ///
+    /// * The time spent, which the node of the core does not run.
+    pub fn consume_wake_up_time(&mut self, core_id: usize, time: i32) -> i32 {
+        let wake_up_time = self.wake_up_times[core_id].min(time);
+        self.wake_up_times[core_id] -= wake_up_time;
+        wake_up_time
+    }
+
+    /// Advance the clock by `time` units in which the cores in `busy_cores` were busy.
+    pub fn advance(&mut self, time: i32, busy_cores: &[bool]) {
+        for (busy_time, &is_busy) in self.busy_times.iter_mut().zip(busy_cores) {
+            if is_busy {
+                *busy_time += time;
+            }
+        }
+        self.time += time;
+    }
+
+    /// The log up to the current time, in which the cores still asleep wake up now.
+    pub fn get_power_log(&self) -> PowerLog {
+        let mut sleep_intervals = self.sleep_intervals.clone();
+        let wake_ups = sleep_intervals.len();
+        for core_id in 0..self.idle_since.len() {
+            if let Some(start_time) = self.get_sleep_start_time(core_id) {
+                sleep_intervals.push(SleepInterval {
+                    core_id,
+                    start_time,
+                    end_time: self.time,
+                });
+            }
+        }
+        sleep_intervals.sort_by_key(|interval| (interval.core_id, interval.start_time));
+        let busy_time: i32 = self.busy_times.iter().sum();
+        let sleep_time: i32 = sleep_intervals
+            .iter()
+            .map(|interval| interval.end_time - interval.start_time)
+            .sum();
+        let idle_time = self.time * self.busy_times.len() as i32 - busy_time - sleep_time;
+        PowerLog {
+            idle_threshold: self.model.idle_threshold,
+            wake_up_latency: self.model.wake_up_latency,
+            wake_ups,
+            sleep_time,
+            energy: busy_time as f32
+                + idle_time as f32 * self.model.idle_power
+                + sleep_time as f32 * self.model.sleep_power,
+            sleep_intervals,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_core_power_states_normal() {
+        let mut power_states = CorePowerStates::new(PowerDownModel::new(2, 1, 0.5, 0.1), 1);
+        power_states.advance(3, &[false]);
+        assert!(power_states.is_asleep(0));
+        power_states.start(0);
+        assert!(!power_states.is_asleep(0));
+        assert_eq!(power_states.consume_wake_up_time(0, 3), 1);
+        power_states.advance(3, &[true]);
+        power_states.stop(0);
+        power_states.advance(1, &[false]);
+
+        let power_log = power_states.get_power_log();
+        assert_eq!(power_log.wake_ups, 1);
+        assert_eq!(
+            power_log.sleep_intervals,
+            vec![SleepInterval {
+                core_id: 0,
+                start_time: 2,
+                end_time: 3,
+            }]
+        );
+        // 3 busy, 3 idle and 1 asleep.
+        assert_eq!(power_log.energy, 3.0 + 3.0 * 0.5 + 0.1);
+    }
+}
@@ -0,0 +1,133 @@
+//! Cache per-DAG preprocessing results (e.g., critical paths, priorities, minimum cores)
+//! across runs in a directory, so that sweeps over the same DAG sets skip the preprocessing.
+//!
+//! Each result is stored as `<name>-<hash>.json`, where the hash covers the nodes, params and
+//! edges of the DAG except `dag_id`, so the same DAG hits the cache wherever it is loaded from.
+//! Delete the directory to invalidate the cache after changing a preprocessing algorithm.
+use crate::graph_extension::NodeData;
+#[cfg(feature = "json")]
+use log::warn;
+use petgraph::{graph::Graph, visit::EdgeRef};
+#[cfg(feature = "json")]
+use serde::{de::DeserializeOwned, Serialize};
+#[cfg(feature = "json")]
+use std::{fs, path::PathBuf};
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/// FNV-1a hash of the DAG content, which is stable across builds unlike `DefaultHasher`.
+pub fn get_dag_hash(dag: &Graph<NodeData, i32>) -> u64 {
+    let mut content = String::new();
+    for node_data in dag.node_weights() {
+        content.push_str(&format!("n{}", node_data.id));
+        for (key, value) in node_data.params.iter() {
+            if key != "dag_id" {
+                content.push_str(&format!(",{}={}", key, value));
+            }
+        }
+        content.push(';');
+    }
+    for edge in dag.edge_references() {
+        content.push_str(&format!(
+            "e{}-{}={};",
+            edge.source().index(),
+            edge.target().index(),
+            edge.weight()
+        ));
+    }
+    get_fnv_hash(&content)
+}
+
+/// FNV-1a hash of `content`.
+pub fn get_fnv_hash(content: &str) -> u64 {
+    content.bytes().fold(FNV_OFFSET_BASIS, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(FNV_PRIME)
+    })
+}
+
+/// The cache files are JSON, so the cache needs the `json` feature.
+#[cfg(feature = "json")]
+#[derive(Clone, Debug, Default)]
+pub struct PreprocessingCache {
+    /// `None` disables the cache.
+    dir_path: Option<PathBuf>,
+}
+
+#[cfg(feature = "json")]
+impl PreprocessingCache {
+    pub fn new(dir_path: &str) -> Self {
+        Self {
+            dir_path: Some(PathBuf::from(dir_path)),
+        }
+    }
+
+    pub fn disabled() -> Self {
+        Self::default()
+    }
+
+    /// The cached `name` result of the DAG with `dag_hash`, or the result of `compute`, which is
+    /// then cached. A broken cache file is recomputed.
+    pub fn get_or_compute<V: Serialize + DeserializeOwned>(
+        &self,
+        name: &str,
+        dag_hash: u64,
+        compute: impl FnOnce() -> V,
+    ) -> V {
+        let Some(dir_path) = &self.dir_path else {
+            return compute();
+        };
+        let file_path = dir_path.join(format!("{}-{:016x}.json", name, dag_hash));
+        if let Ok(bytes) = fs::read(&file_path) {
+            match serde_json::from_slice(&bytes) {
+                Ok(value) => return value,
+                Err(err) => warn!("Ignore the broken cache {}: {}", file_path.display(), err),
+            }
+        }
+        let value = compute();
+        fs::create_dir_all(dir_path).expect("Failed to create the cache directory.");
+        fs::write(
+            &file_path,
+            serde_json::to_vec(&value).expect("Failed to serialize."),
+        )
+        .expect("Failed to write the cache file.");
+        value
+    }
+}
+
+#[cfg(all(test, feature = "json"))]
+mod tests {
+    use super::*;
+    use crate::graph_extension::GraphExtension;
+    use crate::test_util::create_node;
+    use std::cell::Cell;
+
+    #[test]
+    fn test_preprocessing_cache_normal() {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 3));
+        let n1 = dag.add_node(create_node(1, "execution_time", 4));
+        dag.add_edge(n0, n1, 0);
+        let dag_hash = get_dag_hash(&dag);
+        let mut moved_dag = dag.clone();
+        moved_dag.set_dag_param("dag_id", 5);
+        assert_eq!(get_dag_hash(&moved_dag), dag_hash);
+        moved_dag.update_param(n1, "execution_time", 5);
+        assert_ne!(get_dag_hash(&moved_dag), dag_hash);
+
+        let dir_path = "../lib/tests/preprocessing_cache_test";
+        let cache = PreprocessingCache::new(dir_path);
+        let compute_count = Cell::new(0);
+        let compute = || {
+            compute_count.set(compute_count.get() + 1);
+            vec![0, 1]
+        };
+        assert_eq!(cache.get_or_compute("path", dag_hash, compute), vec![0, 1]);
+        assert_eq!(cache.get_or_compute("path", dag_hash, compute), vec![0, 1]);
+        assert_eq!(compute_count.get(), 1);
+        fs::remove_dir_all(dir_path).unwrap();
+
+        PreprocessingCache::disabled().get_or_compute("path", dag_hash, compute);
+        assert_eq!(compute_count.get(), 2);
+    }
+}
@@ -0,0 +1,157 @@
+//! Priority-assignment heuristics for global fixed-priority scheduling of DAG sets, which write
+//! the `dag_priority` param read by `GlobalFPScheduler` (a smaller value is a higher priority).
+//!
+//! With `m` cores, and the relative deadline `D`, the volume `C`, and the critical path length `L`
+//! of each DAG, the DAGs are prioritized in ascending order of:
+//! - DkC (Davis and Burns): `D - k * C`, where `k = (m - 1 + sqrt(5m^2 - 6m + 1)) / 2m`.
+//! - D-CMPR (D minus C monotonic): `D - C`.
+//! - Slack monotonic: `D - (L + (C - L) / m)`, the slack of the DAG alone by Graham's bound.
+//!
+//! Ties are broken by `dag_id`.
+use crate::{
+    anticipatory_edf_scheduler::get_critical_path_lengths,
+    dag_task::DagTask,
+    graph_extension::{GraphExtension, NodeData},
+};
+use petgraph::graph::Graph;
+use serde_derive::{Deserialize, Serialize};
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PriorityAssignment {
+    #[default]
+    Dkc,
+    DCmpr,
+    SlackMonotonic,
+}
+
+impl FromStr for PriorityAssignment {
+    type Err = String;
+
+    fn from_str(heuristic: &str) -> Result<Self, Self::Err> {
+        match heuristic.to_lowercase().as_str() {
+            "dkc" => Ok(Self::Dkc),
+            "d_cmpr" => Ok(Self::DCmpr),
+            "slack_monotonic" => Ok(Self::SlackMonotonic),
+            _ => Err(format!("Unknown priority assignment: {}", heuristic)),
+        }
+    }
+}
+
+/// The priority of each DAG by `dag_id`, from 0 for the highest.
+pub fn assign_priorities(
+    dag_set: &[Graph<NodeData, i32>],
+    number_of_cores: usize,
+    heuristic: PriorityAssignment,
+) -> Vec<i32> {
+    let m = number_of_cores as f32;
+    let k = (m - 1.0 + (5.0 * m * m - 6.0 * m + 1.0).sqrt()) / (2.0 * m);
+    let critical_path_lengths = get_critical_path_lengths(dag_set);
+    let keys: Vec<f32> = dag_set
+        .iter()
+        .enumerate()
+        .map(|(dag_id, dag)| {
+            let relative_deadline = DagTask::from_graph(dag)
+                .get_relative_deadline()
+                .unwrap_or_else(|| panic!("DAG {} has no deadline.", dag_id))
+                as f32;
+            let volume = dag.get_volume() as f32;
+            let critical_path_length = critical_path_lengths[dag_id] as f32;
+            match heuristic {
+                PriorityAssignment::Dkc => relative_deadline - k * volume,
+                PriorityAssignment::DCmpr => relative_deadline - volume,
+                PriorityAssignment::SlackMonotonic => {
+                    relative_deadline - (critical_path_length + (volume - critical_path_length) / m)
+                }
+            }
+        })
+        .collect();
+    let mut dag_ids: Vec<usize> = (0..dag_set.len()).collect();
+    dag_ids.sort_by(|&a, &b| keys[a].total_cmp(&keys[b]).then(a.cmp(&b)));
+    let mut priorities = vec![0; dag_set.len()];
+    for (priority, dag_id) in dag_ids.into_iter().enumerate() {
+        priorities[dag_id] = priority as i32;
+    }
+    priorities
+}
+
+/// Write the priorities by `assign_priorities` as the `dag_priority` param of the DAGs.
+pub fn write_priorities(
+    dag_set: &mut [Graph<NodeData, i32>],
+    number_of_cores: usize,
+    heuristic: PriorityAssignment,
+) {
+    let priorities = assign_priorities(dag_set, number_of_cores, heuristic);
+    for (dag, priority) in dag_set.iter_mut().zip(priorities) {
+        dag.set_dag_param("dag_priority", priority);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dag_set_scheduler::DAGSetSchedulerBase;
+    use crate::global_fp_scheduler::GlobalFPScheduler;
+    use crate::homogeneous::HomogeneousProcessor;
+    use crate::processor::ProcessorBase;
+    use crate::test_util::create_node;
+
+    fn create_dag(execution_times: &[i32], deadline: i32, dag_id: i32) -> Graph<NodeData, i32> {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", execution_times[0]));
+        dag.add_param(n0, "period", deadline);
+        dag.add_param(n0, "end_to_end_deadline", deadline);
+        for (node_id, &execution_time) in execution_times.iter().enumerate().skip(1) {
+            let node_i = dag.add_node(create_node(
+                node_id as i32,
+                "execution_time",
+                execution_time,
+            ));
+            dag.add_edge(n0, node_i, 1);
+        }
+        dag.set_dag_param("dag_id", dag_id);
+        dag
+    }
+
+    #[test]
+    fn test_assign_priorities_normal() {
+        // (D, C, L): (20, 12, 12), (16, 9, 3), and (9, 2, 2).
+        let dag_set = vec![
+            create_dag(&[12], 20, 0),
+            create_dag(&[1, 2, 2, 2, 2], 16, 1),
+            create_dag(&[2], 9, 2),
+        ];
+        // k = (2 + sqrt(28)) / 6 on three cores, so D - kC is 5.42, 5.06, and 6.57.
+        assert_eq!(
+            assign_priorities(&dag_set, 3, PriorityAssignment::Dkc),
+            vec![1, 0, 2]
+        );
+        // D - C is 8, 7, and 7.
+        assert_eq!(
+            assign_priorities(&dag_set, 3, PriorityAssignment::DCmpr),
+            vec![2, 0, 1]
+        );
+        // The slack is 8, 11, and 7.
+        assert_eq!(
+            assign_priorities(&dag_set, 3, PriorityAssignment::SlackMonotonic),
+            vec![1, 2, 0]
+        );
+
+        let mut global_fp_scheduler = GlobalFPScheduler::new_with_priority_assignment(
+            &dag_set,
+            &HomogeneousProcessor::new(3),
+            PriorityAssignment::SlackMonotonic,
+        );
+        let priorities: Vec<i32> = global_fp_scheduler
+            .get_dag_set()
+            .iter()
+            .map(|dag| dag.get_dag_param("dag_priority"))
+            .collect();
+        assert_eq!(priorities, vec![1, 2, 0]);
+        assert_eq!(
+            global_fp_scheduler.get_log_mut().get_priority_assignment(),
+            Some(PriorityAssignment::SlackMonotonic)
+        );
+    }
+}
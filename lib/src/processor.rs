@@ -1,12 +1,87 @@
-use crate::{core::*, graph_extension::NodeData};
+use crate::{
+    core::*,
+    graph_extension::NodeData,
+    log::{EnergyLog, PowerLog},
+};
 
 pub trait ProcessorBase {
     fn new(num_cores: usize) -> Self;
     fn allocate_specific_core(&mut self, core_id: usize, node_data: &NodeData) -> bool;
     fn process(&mut self) -> Vec<ProcessResult>;
+    /// `process` without the cores in `stalled_cores`, whose nodes keep their remaining time.
+    fn process_except(&mut self, stalled_cores: &[bool]) -> Vec<ProcessResult>;
+    /// Process up to `max_time` units at once, stopping at the first unit in which a node
+    /// finishes, because the scheduler decides again then. If all the cores are idle, the whole
+    /// `max_time` elapses, so `max_time` must keep the current time from overflowing.
+    ///
+    /// # Returns
+    ///
+    /// * The elapsed time and the results of its last unit. The cores not `Idle` there were
+    ///   busy for the whole elapsed time.
+    fn process_for(&mut self, max_time: i32) -> (i32, Vec<ProcessResult>) {
+        let mut elapsed_time = 1;
+        let mut process_result = self.process();
+        while elapsed_time < max_time
+            && !process_result
+                .iter()
+                .any(|result| matches!(result, ProcessResult::Done(_)))
+        {
+            process_result = self.process();
+            elapsed_time += 1;
+        }
+        (elapsed_time, process_result)
+    }
+    /// Allocate each node to a distinct idle core at the same time, or none of them if there
+    /// are not enough idle cores.
+    ///
+    /// # Returns
+    ///
+    /// * The cores of the nodes in order, or `None` if nothing is allocated.
+    fn allocate_gang(&mut self, node_data_list: &[NodeData]) -> Option<Vec<usize>> {
+        let idle_core_indices = self.get_idle_core_indices();
+        if idle_core_indices.len() < node_data_list.len() {
+            return None;
+        }
+        let core_indices = idle_core_indices[..node_data_list.len()].to_vec();
+        for (&core_i, node_data) in core_indices.iter().zip(node_data_list) {
+            self.allocate_specific_core(core_i, node_data);
+        }
+        Some(core_indices)
+    }
     fn get_number_of_cores(&self) -> usize;
     fn get_idle_core_index(&self) -> Option<usize>;
     fn get_idle_core_num(&self) -> usize;
+    fn get_idle_core_indices(&self) -> Vec<usize>;
+    fn get_processing_node(&self, core_id: usize) -> Option<&NodeData>;
     fn preempt(&mut self, core_id: usize) -> Option<NodeData>;
+    /// Set a param of the node running on `core_id`, e.g., an inherited priority.
+    fn set_processing_node_param(&mut self, core_id: usize, key: &str, value: i32);
     fn get_max_value_and_index(&self, key: &str) -> Option<(i32, usize)>;
+    fn get_preemptable_core_index(&self, key: &str, value: i32) -> Option<usize> {
+        self.get_preemptable_core_index_after(key, value, 0)
+    }
+    /// `get_preemptable_core_index` among the nodes that have run for `non_preemptive_region`
+    /// since they started or resumed.
+    fn get_preemptable_core_index_after(
+        &self,
+        key: &str,
+        value: i32,
+        non_preemptive_region: i32,
+    ) -> Option<usize>;
+    fn get_number_of_running_nodes(&self, dag_id: i32) -> usize;
+    /// Time the node on `core_id` has run since it started or resumed, 0 for an idle core.
+    fn get_run_time(&self, core_id: usize) -> i32;
+    /// Whether the idle core has powered down, so that a node dispatched to it waits for the
+    /// wake-up latency. Sleep-aware schedulers prefer the idle cores that are awake.
+    fn is_core_asleep(&self, _core_id: usize) -> bool {
+        false
+    }
+    /// `None` if the cores do not power down.
+    fn get_power_log(&self) -> Option<PowerLog> {
+        None
+    }
+    /// `None` if the cores do not scale their frequencies.
+    fn get_energy_log(&self) -> Option<EnergyLog> {
+        None
+    }
 }
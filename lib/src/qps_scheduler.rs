@@ -0,0 +1,429 @@
+//! Quasi-partitioned scheduling (QPS): whole DAGs are assigned to cores by a bin-packing heuristic
+//! as in partitioned EDF, and the DAGs that fit on no core overflow to a global ready queue
+//! served by a server on each core.
+//!
+//! The server of a core has the spare capacity of the core, i.e., a budget of
+//! `floor((1 - U) * P)` per server period `P`, which is the shortest period of the overflow DAGs.
+//! Each core runs EDF over the nodes of its DAGs and its server, whose deadline is the end of the
+//! current server period and which wins ties. The server runs the head of the global queue by EDF
+//! until its budget is exhausted, after which the node migrates to another server.
+use crate::dag_set_scheduler::{DAGSetSchedulerBase, NodeDataWrapper, PreemptiveType, ReadyQueue};
+use crate::getset_dag_set_scheduler;
+use crate::{
+    core::ProcessResult,
+    dag_set_scheduler::{DAGStateManager, DAGStateManagerBase},
+    dag_task::DagTask,
+    graph_extension::{get_node_index_maps, NodeData, NodeIndexMap},
+    homogeneous::HomogeneousProcessor,
+    log::{DAGSetSchedulerLog, JobEventTimes},
+    partitioned_edf_scheduler::{select_fit_core, PartitioningHeuristic},
+    processor::ProcessorBase,
+    util::{clear_interrupted, get_hyper_period, get_process_core_indices, is_interrupted},
+};
+use log::warn;
+use petgraph::graph::Graph;
+use serde_derive::{Deserialize, Serialize};
+
+/// Tolerance for the floating-point error of the summed utilizations.
+const BUDGET_EPSILON: f32 = 1e-4;
+
+/// Assignment of the DAGs to the cores and the servers by QPS.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct QpsAllocation {
+    pub heuristic: PartitioningHeuristic,
+    /// Core of each DAG by `dag_id`, `None` for the overflow DAGs.
+    pub core_ids: Vec<Option<usize>>,
+    /// Utilization of the DAGs assigned to each core.
+    pub core_utilizations: Vec<f32>,
+    pub overflow_dag_ids: Vec<usize>,
+    /// 0 if no DAG overflows.
+    pub server_period: i32,
+    /// Budget of the server of each core per server period.
+    pub server_budgets: Vec<i32>,
+}
+
+/// The DAGs are packed in the order of `dag_id`.
+pub fn allocate_qps(
+    dag_set: &[Graph<NodeData, i32>],
+    number_of_cores: usize,
+    heuristic: PartitioningHeuristic,
+) -> QpsAllocation {
+    let mut allocation = QpsAllocation {
+        heuristic,
+        core_utilizations: vec![0.0; number_of_cores],
+        ..Default::default()
+    };
+    let mut server_period = i32::MAX;
+    for (dag_id, dag) in dag_set.iter().enumerate() {
+        let dag_task = DagTask::from_graph(dag);
+        let utilization = dag_task
+            .get_utilization()
+            .unwrap_or_else(|| panic!("DAG {} has no period.", dag_id));
+        let core_i = select_fit_core(&allocation.core_utilizations, utilization, heuristic);
+        if let Some(core_i) = core_i {
+            allocation.core_utilizations[core_i] += utilization;
+        } else {
+            allocation.overflow_dag_ids.push(dag_id);
+            server_period = server_period.min(dag_task.period.unwrap());
+        }
+        allocation.core_ids.push(core_i);
+    }
+    if !allocation.overflow_dag_ids.is_empty() {
+        allocation.server_period = server_period;
+        allocation.server_budgets = allocation
+            .core_utilizations
+            .iter()
+            .map(|core_utilization| {
+                ((1.0 - core_utilization) * server_period as f32 + BUDGET_EPSILON).floor() as i32
+            })
+            .collect();
+    } else {
+        allocation.server_budgets = vec![0; number_of_cores];
+    }
+    allocation
+}
+
+pub struct QpsScheduler {
+    dag_set: Vec<Graph<NodeData, i32>>,
+    node_index_maps: Vec<NodeIndexMap>,
+    processor: HomogeneousProcessor,
+    log: DAGSetSchedulerLog,
+    current_time: i32,
+    allocation: QpsAllocation,
+}
+
+impl DAGSetSchedulerBase<HomogeneousProcessor> for QpsScheduler {
+    /// Partition with First-Fit.
+    fn new(dag_set: &[Graph<NodeData, i32>], processor: &HomogeneousProcessor) -> Self {
+        Self::new_with_heuristic(dag_set, processor, PartitioningHeuristic::default())
+    }
+
+    getset_dag_set_scheduler!(HomogeneousProcessor);
+
+    /// QPS is based on EDF, so the nodes are compared by `node_absolute_deadline` whatever the key
+    /// of `preemptive_type`. The budget of a server is enforced even if non-preemptive.
+    fn schedule(&mut self, preemptive_type: PreemptiveType) -> i32 {
+        clear_interrupted();
+        self.log.write_schedule_start();
+        let number_of_cores = self.processor.get_number_of_cores();
+        let mut managers = vec![DAGStateManager::default(); self.dag_set.len()];
+        // The ready queue of each core, followed by the global queue of the servers.
+        let mut ready_queues = vec![ReadyQueue::new(); number_of_cores + 1];
+        let mut remaining_budgets = vec![0; number_of_cores];
+        let hyper_period = get_hyper_period(&self.dag_set);
+        let server_period = self.allocation.server_period;
+        while self.current_time < hyper_period && !is_interrupted() {
+            let server_deadline = if server_period > 0 {
+                if self.current_time % server_period == 0 {
+                    remaining_budgets.clone_from(&self.allocation.server_budgets);
+                }
+                (self.current_time / server_period + 1) * server_period
+            } else {
+                i32::MAX
+            };
+
+            // Release DAGs
+            for ready_node in self.release_dags(&mut managers) {
+                self.insert_ready_node(&mut ready_queues, ready_node);
+            }
+
+            // Suspend the servers whose budget is exhausted.
+            for (core_i, &remaining_budget) in remaining_budgets.iter().enumerate() {
+                if remaining_budget == 0 && self.is_serving(core_i) {
+                    self.preempt_node(core_i, &mut ready_queues, &managers);
+                }
+            }
+
+            // Allocate the earlier of the head of the ready queue of each core and its server,
+            // preempting a later deadline.
+            for (core_i, &remaining_budget) in remaining_budgets.iter().enumerate() {
+                let local_head = ready_queues[core_i].first();
+                let global_head = ready_queues[number_of_cores]
+                    .first()
+                    .filter(|_| remaining_budget > 0);
+                let (queue_i, head, priority) = match (local_head, global_head) {
+                    (Some(local_head), Some(global_head)) => {
+                        ready_queues[core_i].count_comparison();
+                        let local_deadline = local_head
+                            .node_data
+                            .get_params_value("node_absolute_deadline");
+                        if local_deadline < server_deadline {
+                            (core_i, local_head, local_deadline)
+                        } else {
+                            (number_of_cores, global_head, server_deadline)
+                        }
+                    }
+                    (Some(local_head), None) => (
+                        core_i,
+                        local_head,
+                        local_head
+                            .node_data
+                            .get_params_value("node_absolute_deadline"),
+                    ),
+                    (None, Some(global_head)) => (number_of_cores, global_head, server_deadline),
+                    (None, None) => continue,
+                };
+                let head = head.clone();
+                let core = &self.processor.cores[core_i];
+                if !core.get_is_idle() {
+                    if preemptive_type.get_key().is_none()
+                        || core.run_time < preemptive_type.get_non_preemptive_region()
+                    {
+                        continue;
+                    }
+                    ready_queues[core_i].count_comparison();
+                    let running_priority = if self.is_serving(core_i) {
+                        server_deadline
+                    } else {
+                        core.get_processing_node()
+                            .as_ref()
+                            .unwrap()
+                            .get_params_value("node_absolute_deadline")
+                    };
+                    if priority >= running_priority {
+                        continue;
+                    }
+                    self.preempt_node(core_i, &mut ready_queues, &managers);
+                }
+                ready_queues[queue_i].remove(&head);
+                self.log.write_ready_queue_removal();
+                let node_data = head.convert_node_data();
+                self.allocate_node(
+                    &node_data,
+                    core_i,
+                    managers[node_data.get_params_value("dag_id") as usize].get_release_count()
+                        as usize,
+                );
+            }
+
+            // Process unit time
+            let serving_cores: Vec<bool> = (0..number_of_cores)
+                .map(|core_i| self.is_serving(core_i))
+                .collect();
+            let process_result = self.process_unit_time();
+            self.log
+                .write_processing_time(&get_process_core_indices(&process_result));
+            for core_i in get_process_core_indices(&process_result) {
+                if serving_cores[core_i] {
+                    remaining_budgets[core_i] -= 1;
+                }
+            }
+
+            // Post-process on completion of node execution
+            for (core_id, result) in process_result.iter().enumerate() {
+                if let ProcessResult::Done(node_data) = result {
+                    let ready_nodes =
+                        self.post_process_on_node_completion(node_data, core_id, &mut managers);
+                    for ready_node in ready_nodes {
+                        self.insert_ready_node(&mut ready_queues, ready_node);
+                    }
+                }
+            }
+        }
+
+        self.log.write_comparisons(
+            ready_queues
+                .iter()
+                .map(ReadyQueue::get_comparison_count)
+                .sum(),
+        );
+        self.calculate_log();
+        self.current_time
+    }
+}
+
+impl QpsScheduler {
+    pub fn new_with_heuristic(
+        dag_set: &[Graph<NodeData, i32>],
+        processor: &HomogeneousProcessor,
+        heuristic: PartitioningHeuristic,
+    ) -> Self {
+        let number_of_cores = processor.get_number_of_cores();
+        let allocation = allocate_qps(dag_set, number_of_cores, heuristic);
+        let server_utilization = allocation
+            .server_budgets
+            .iter()
+            .map(|&budget| budget as f32 / allocation.server_period as f32)
+            .sum::<f32>();
+        let overflow_utilization = allocation
+            .overflow_dag_ids
+            .iter()
+            .map(|&dag_id| {
+                DagTask::from_graph(&dag_set[dag_id])
+                    .get_utilization()
+                    .unwrap()
+            })
+            .sum::<f32>();
+        if overflow_utilization > server_utilization + BUDGET_EPSILON {
+            warn!(
+                "The servers of utilization {} cannot serve the overflow DAGs {:?} of utilization {}.",
+                server_utilization, allocation.overflow_dag_ids, overflow_utilization
+            );
+        }
+        let mut log = DAGSetSchedulerLog::new(dag_set, number_of_cores);
+        log.write_qps_allocation(allocation.clone());
+        Self {
+            node_index_maps: get_node_index_maps(dag_set),
+            dag_set: dag_set.to_vec(),
+            processor: processor.clone(),
+            log,
+            current_time: 0,
+            allocation,
+        }
+    }
+
+    pub fn get_allocation(&self) -> &QpsAllocation {
+        &self.allocation
+    }
+
+    /// Whether the core runs a node of an overflow DAG for its server.
+    fn is_serving(&self, core_i: usize) -> bool {
+        self.processor.cores[core_i]
+            .get_processing_node()
+            .as_ref()
+            .is_some_and(|node_data| {
+                self.allocation.core_ids[node_data.get_params_value("dag_id") as usize].is_none()
+            })
+    }
+
+    /// Insert the node into the ready queue of the core of its DAG, or into the global queue if
+    /// the DAG overflows.
+    fn insert_ready_node(&mut self, ready_queues: &mut [ReadyQueue], node_data: NodeData) {
+        let dag_id = node_data.get_params_value("dag_id") as usize;
+        let queue_i =
+            self.allocation.core_ids[dag_id].unwrap_or(self.processor.get_number_of_cores());
+        ready_queues[queue_i].insert_new(NodeDataWrapper::new(node_data));
+        self.log.write_ready_queue_insertion();
+    }
+
+    fn preempt_node(
+        &mut self,
+        core_i: usize,
+        ready_queues: &mut [ReadyQueue],
+        managers: &[DAGStateManager],
+    ) {
+        let preempted_node_data = self.processor.preempt(core_i).unwrap();
+        self.log.write_job_event(
+            &preempted_node_data,
+            core_i,
+            managers[preempted_node_data.get_params_value("dag_id") as usize].get_release_count()
+                as usize
+                - 1,
+            JobEventTimes::PreemptedTime(self.current_time),
+        );
+        self.insert_ready_node(ready_queues, preempted_node_data);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph_extension::GraphExtension;
+    use crate::test_util::create_node;
+
+    fn create_single_node_dag(execution_time: i32, dag_id: i32) -> Graph<NodeData, i32> {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", execution_time));
+        dag.add_param(n0, "period", 10);
+        dag.add_param(n0, "end_to_end_deadline", 10);
+        dag.set_dag_param("dag_id", dag_id);
+        dag
+    }
+
+    #[test]
+    fn test_qps_scheduler_normal() {
+        // Utilizations 0.6, 0.6, 0.5 on two cores, so that DAG 2 overflows.
+        let dag_set = vec![
+            create_single_node_dag(6, 0),
+            create_single_node_dag(6, 1),
+            create_single_node_dag(5, 2),
+        ];
+        let mut qps_scheduler = QpsScheduler::new(&dag_set, &HomogeneousProcessor::new(2));
+        let allocation = qps_scheduler.get_allocation();
+        assert_eq!(allocation.core_ids, vec![Some(0), Some(1), None]);
+        assert_eq!(allocation.overflow_dag_ids, vec![2]);
+        assert_eq!(allocation.server_period, 10);
+        assert_eq!(allocation.server_budgets, vec![4, 4]);
+
+        qps_scheduler.schedule(PreemptiveType::Preemptive {
+            key: "node_absolute_deadline".to_string(),
+        });
+        let log = qps_scheduler.get_log_mut();
+        // DAG 2 runs on the server of core 0 until 4 and completes on that of core 1 at 7.
+        assert_eq!(log.get_worst_response_times(), vec![10, 6, 7]);
+    }
+
+    #[test]
+    fn test_qps_scheduler_no_overflow() {
+        // Worst-Fit spreads the DAGs over the cores, so that no server is needed.
+        let dag_set = vec![create_single_node_dag(5, 0), create_single_node_dag(5, 1)];
+        let mut qps_scheduler = QpsScheduler::new_with_heuristic(
+            &dag_set,
+            &HomogeneousProcessor::new(2),
+            PartitioningHeuristic::WorstFit,
+        );
+        let allocation = qps_scheduler.get_allocation();
+        assert_eq!(allocation.core_ids, vec![Some(0), Some(1)]);
+        assert!(allocation.overflow_dag_ids.is_empty());
+        assert_eq!(allocation.server_period, 0);
+        assert_eq!(allocation.server_budgets, vec![0, 0]);
+
+        qps_scheduler.schedule(PreemptiveType::Preemptive {
+            key: "node_absolute_deadline".to_string(),
+        });
+        assert_eq!(
+            qps_scheduler.get_log_mut().get_worst_response_times(),
+            vec![5, 5]
+        );
+    }
+
+    #[test]
+    fn test_qps_scheduler_tie() {
+        // Utilizations 0.4 on two cores. Ties are broken by the smallest core index.
+        let dag_set = vec![
+            create_single_node_dag(4, 0),
+            create_single_node_dag(4, 1),
+            create_single_node_dag(4, 2),
+        ];
+        let get_core_ids = |heuristic| allocate_qps(&dag_set, 2, heuristic).core_ids;
+        assert_eq!(
+            get_core_ids(PartitioningHeuristic::BestFit),
+            vec![Some(0), Some(0), Some(1)]
+        );
+        assert_eq!(
+            get_core_ids(PartitioningHeuristic::WorstFit),
+            vec![Some(0), Some(1), Some(0)]
+        );
+    }
+
+    #[test]
+    fn test_qps_scheduler_overload() {
+        // Utilizations 0.9 on two cores, so that DAG 2 overflows to servers of budget 1.
+        let dag_set = vec![
+            create_single_node_dag(9, 0),
+            create_single_node_dag(9, 1),
+            create_single_node_dag(9, 2),
+        ];
+        let mut qps_scheduler = QpsScheduler::new(&dag_set, &HomogeneousProcessor::new(2));
+        let allocation = qps_scheduler.get_allocation();
+        assert_eq!(allocation.overflow_dag_ids, vec![2]);
+        assert_eq!(allocation.server_budgets, vec![1, 1]);
+
+        qps_scheduler.schedule(PreemptiveType::Preemptive {
+            key: "node_absolute_deadline".to_string(),
+        });
+        // The servers run DAG 2 for 2 per period, so that it never completes.
+        assert_eq!(
+            qps_scheduler.get_log_mut().get_worst_response_times(),
+            vec![10, 9, i32::MAX]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "DAG 0 has no period.")]
+    fn test_qps_scheduler_no_period() {
+        let mut dag = Graph::<NodeData, i32>::new();
+        dag.add_node(create_node(0, "execution_time", 1));
+        allocate_qps(&[dag], 1, PartitioningHeuristic::FirstFit);
+    }
+}
@@ -0,0 +1,187 @@
+//! Randomized baseline: ready nodes are dispatched uniformly at random, reproducibly by the seed.
+//!
+//! `RandomScheduler` schedules a single DAG (`DAGSchedulerBase`) or a DAG set
+//! (`DAGSetSchedulerBase`), depending on the trait it is created with. Since both traits define
+//! `new`, create it with e.g. `<RandomScheduler<_> as DAGSetSchedulerBase<_>>::new`. The ready
+//! nodes have no priority, so it is meant for non-preemptive scheduling.
+use crate::dag_scheduler::DAGSchedulerBase;
+use crate::dag_set_scheduler::DAGSetSchedulerBase;
+use crate::getset_dag_set_scheduler;
+use crate::{
+    graph_extension::{get_node_index_maps, NodeData, NodeIndexMap},
+    log::{DAGSchedulerLog, DAGSetSchedulerLog},
+    processor::ProcessorBase,
+};
+use petgraph::graph::Graph;
+use rand::{rngs::StdRng, SeedableRng};
+use std::collections::VecDeque;
+
+const DEFAULT_SEED: u64 = 0;
+
+#[derive(Clone)]
+pub struct RandomScheduler<T>
+where
+    T: ProcessorBase + Clone,
+{
+    /// Scheduled by `DAGSchedulerBase`.
+    dag: Graph<NodeData, i32>,
+    dag_log: DAGSchedulerLog,
+    /// Scheduled by `DAGSetSchedulerBase`.
+    dag_set: Vec<Graph<NodeData, i32>>,
+    node_index_maps: Vec<NodeIndexMap>,
+    log: DAGSetSchedulerLog,
+    processor: T,
+    current_time: i32,
+    rng: StdRng,
+}
+
+impl<T> RandomScheduler<T>
+where
+    T: ProcessorBase + Clone,
+{
+    /// Restart the random dispatch from `seed`.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+}
+
+impl<T> DAGSchedulerBase<T> for RandomScheduler<T>
+where
+    T: ProcessorBase + Clone,
+{
+    fn new(dag: &Graph<NodeData, i32>, processor: &T) -> Self {
+        Self {
+            dag: dag.clone(),
+            dag_log: DAGSchedulerLog::new(dag, processor.get_number_of_cores()),
+            dag_set: Vec::new(),
+            node_index_maps: Vec::new(),
+            log: DAGSetSchedulerLog::default(),
+            processor: processor.clone(),
+            current_time: 0,
+            rng: StdRng::seed_from_u64(DEFAULT_SEED),
+        }
+    }
+
+    fn set_dag(&mut self, dag: &Graph<NodeData, i32>) {
+        self.dag = dag.clone();
+    }
+
+    fn set_processor(&mut self, processor: &T) {
+        self.processor = processor.clone();
+    }
+
+    fn set_log(&mut self, log: DAGSchedulerLog) {
+        self.dag_log = log;
+    }
+
+    fn get_dag(&self) -> Graph<NodeData, i32> {
+        self.dag.clone()
+    }
+
+    fn get_processor(&self) -> T {
+        self.processor.clone()
+    }
+
+    fn get_log(&self) -> DAGSchedulerLog {
+        self.dag_log.clone()
+    }
+
+    /// The ready queue is kept in arrival order and shuffled by `get_random_dispatch_rng_mut`.
+    fn sort_ready_queue(_ready_queue: &mut VecDeque<NodeData>) {}
+
+    fn get_random_dispatch_rng_mut(&mut self) -> Option<&mut StdRng> {
+        Some(&mut self.rng)
+    }
+}
+
+impl<T> DAGSetSchedulerBase<T> for RandomScheduler<T>
+where
+    T: ProcessorBase + Clone,
+{
+    fn new(dag_set: &[Graph<NodeData, i32>], processor: &T) -> Self {
+        Self {
+            dag: Graph::new(),
+            dag_log: DAGSchedulerLog::default(),
+            node_index_maps: get_node_index_maps(dag_set),
+            dag_set: dag_set.to_vec(),
+            log: DAGSetSchedulerLog::new(dag_set, processor.get_number_of_cores()),
+            processor: processor.clone(),
+            current_time: 0,
+            rng: StdRng::seed_from_u64(DEFAULT_SEED),
+        }
+    }
+
+    getset_dag_set_scheduler!(T);
+
+    fn get_random_dispatch_rng_mut(&mut self) -> Option<&mut StdRng> {
+        Some(&mut self.rng)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dag_set_scheduler::PreemptiveType;
+    use crate::graph_extension::GraphExtension;
+    use crate::homogeneous::HomogeneousProcessor;
+    use crate::test_util::create_node;
+    use petgraph::graph::NodeIndex;
+    use std::collections::BTreeSet;
+
+    fn create_fork_dag() -> Graph<NodeData, i32> {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 1));
+        for node_id in 1..5 {
+            let node_i = dag.add_node(create_node(node_id, "execution_time", node_id));
+            dag.add_edge(n0, node_i, 0);
+        }
+        dag
+    }
+
+    #[test]
+    fn test_random_scheduler_dag() {
+        let dag = create_fork_dag();
+        let processor = HomogeneousProcessor::new(1);
+        let schedule = |seed: u64| {
+            let mut random_scheduler =
+                <RandomScheduler<_> as DAGSchedulerBase<_>>::new(&dag, &processor);
+            random_scheduler.set_seed(seed);
+            DAGSchedulerBase::schedule(&mut random_scheduler)
+        };
+
+        // The order depends only on the seed, and a single core never idles.
+        assert_eq!(schedule(1), schedule(1));
+        let mut execution_orders = BTreeSet::new();
+        for seed in 0..10 {
+            let (schedule_length, execution_order) = schedule(seed);
+            assert_eq!(schedule_length, dag.get_volume());
+            assert_eq!(execution_order[0], NodeIndex::new(0));
+            execution_orders.insert(execution_order);
+        }
+        assert!(execution_orders.len() > 1);
+    }
+
+    #[test]
+    fn test_random_scheduler_dag_set() {
+        let mut dag_set = vec![create_fork_dag(), create_fork_dag()];
+        for (dag_id, dag) in dag_set.iter_mut().enumerate() {
+            let n0 = NodeIndex::new(0);
+            dag.add_param(n0, "period", 40);
+            dag.add_param(n0, "end_to_end_deadline", 40);
+            dag.set_dag_param("dag_id", dag_id as i32);
+        }
+        let processor = HomogeneousProcessor::new(1);
+        let get_worst_response_times = |seed: u64| {
+            let mut random_scheduler =
+                <RandomScheduler<_> as DAGSetSchedulerBase<_>>::new(&dag_set, &processor);
+            random_scheduler.set_seed(seed);
+            DAGSetSchedulerBase::schedule(&mut random_scheduler, PreemptiveType::NonPreemptive);
+            random_scheduler.get_log_mut().get_worst_response_times()
+        };
+
+        assert_eq!(get_worst_response_times(1), get_worst_response_times(1));
+        let worst_response_times: BTreeSet<Vec<i32>> =
+            (0..10).map(get_worst_response_times).collect();
+        assert!(worst_response_times.len() > 1);
+    }
+}
@@ -0,0 +1,409 @@
+//! Shared resources accessed in critical sections under a locking protocol.
+//!
+//! A node with the `resource_id` param locks the resource when it first starts and holds it for
+//! the first `cs_length` time units of its execution, or the whole node without `cs_length`.
+//! A node dispatched while another node holds its resource blocks: it suspends, leaving the ready
+//! queue until the resource is handed to it. Each resource hands itself to the blocked node with
+//! the highest priority. Priorities are the values of the priority key, where smaller is higher.
+//! How the holder is prioritized depends on the `LockingProtocol`. Nested critical sections and
+//! transitive inheritance are not modeled. Resources are handled by the default `schedule` of
+//! `DAGSetSchedulerBase`.
+use crate::{
+    core::ProcessResult,
+    dag_set_scheduler::{NodeDataWrapper, ReadyQueue},
+    graph_extension::{GraphExtension, NodeData},
+    log::BlockingEvent,
+    processor::ProcessorBase,
+};
+use petgraph::Graph;
+use serde_derive::{Deserialize, Serialize};
+use std::{collections::BTreeMap, str::FromStr};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LockingProtocol {
+    /// Priority Inheritance Protocol: the holder inherits the priority of the nodes it blocks.
+    #[default]
+    PriorityInheritance,
+    /// Multiprocessor Priority Ceiling Protocol: the holder runs its critical section above all
+    /// normal priorities, ordered by the ceiling of the resource, i.e., the highest priority
+    /// among the nodes using it. Critical sections are only preempted by those of higher ceilings.
+    Mpcp,
+}
+
+impl FromStr for LockingProtocol {
+    type Err = String;
+
+    fn from_str(protocol: &str) -> Result<Self, Self::Err> {
+        match protocol.to_lowercase().as_str() {
+            "priority_inheritance" | "pip" => Ok(Self::PriorityInheritance),
+            "mpcp" => Ok(Self::Mpcp),
+            _ => Err(format!("Unknown locking protocol: {}", protocol)),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Resource {
+    /// The holder with its own priority.
+    holder: NodeData,
+    remaining_cs_length: i32,
+    /// The blocked nodes, with their keys of the ready queue, and the times they blocked.
+    waiters: Vec<(NodeDataWrapper, i32)>,
+}
+
+#[derive(Clone, Debug)]
+pub struct ResourceManager {
+    priority_key: String,
+    locking_protocol: LockingProtocol,
+    /// Ceiling of each resource used by MPCP.
+    ceilings: BTreeMap<i32, i32>,
+    resources: BTreeMap<i32, Resource>,
+}
+
+impl ResourceManager {
+    /// `None` if no node of `dag_set` declares a `resource_id`.
+    ///
+    /// The ceilings are computed from the static priority of each node: the priority key if set
+    /// before the simulation (e.g., `dag_priority`), or else the `end_to_end_deadline` of its DAG,
+    /// as the deadline-monotonic counterpart of absolute deadlines.
+    pub fn new(
+        dag_set: &[Graph<NodeData, i32>],
+        priority_key: &str,
+        locking_protocol: LockingProtocol,
+    ) -> Option<Self> {
+        let mut ceilings = BTreeMap::new();
+        for dag in dag_set {
+            for node_data in dag.node_weights() {
+                let Some(&resource_id) = node_data.params.get("resource_id") else {
+                    continue;
+                };
+                let static_priority = node_data
+                    .params
+                    .get(priority_key)
+                    .copied()
+                    .or_else(|| dag.get_end_to_end_deadline())
+                    .unwrap_or(i32::MAX);
+                let ceiling = ceilings.entry(resource_id).or_insert(static_priority);
+                *ceiling = (*ceiling).min(static_priority);
+            }
+        }
+        (!ceilings.is_empty()).then(|| Self {
+            priority_key: priority_key.to_string(),
+            locking_protocol,
+            ceilings,
+            resources: BTreeMap::new(),
+        })
+    }
+
+    fn get_priority(&self, node_data: &NodeData) -> i32 {
+        node_data
+            .params
+            .get(&self.priority_key)
+            .copied()
+            .unwrap_or(i32::MAX)
+    }
+
+    /// Lock the resource of the node of `wrapper` before it starts.
+    ///
+    /// # Returns
+    ///
+    /// * `false` if the resource is held by another node. `wrapper` is then blocked and must be
+    ///   taken out of the ready queue, and the holder inherits its priority.
+    pub fn try_lock(
+        &mut self,
+        wrapper: &NodeDataWrapper,
+        processor: &mut impl ProcessorBase,
+        ready_queue: &mut ReadyQueue,
+        current_time: i32,
+    ) -> bool {
+        let node_data = &wrapper.node_data;
+        // A preempted node has already locked its resource when it started.
+        if node_data.params.contains_key("is_preempted") {
+            return true;
+        }
+        let Some(&resource_id) = node_data.params.get("resource_id") else {
+            return true;
+        };
+        let priority = self.get_priority(node_data);
+        let Some(resource) = self.resources.get(&resource_id) else {
+            self.resources
+                .insert(resource_id, create_resource(node_data, Vec::new()));
+            return true;
+        };
+        // The blocked node handed the resource on its release.
+        if is_same_node(&resource.holder, node_data) {
+            return true;
+        }
+        let holder = resource.holder.clone();
+        let holder_priority = self.get_holder_priority(resource_id);
+        self.resources
+            .get_mut(&resource_id)
+            .unwrap()
+            .waiters
+            .push((wrapper.clone(), current_time));
+        if self.locking_protocol == LockingProtocol::PriorityInheritance
+            && priority < holder_priority
+        {
+            set_node_priority(
+                processor,
+                ready_queue,
+                &holder,
+                &self.priority_key,
+                priority,
+            );
+        }
+        false
+    }
+
+    /// The priority of the holder in its critical section: under PIP, inherited from the blocked
+    /// nodes or its own, and under MPCP, the ceiling boosted above all normal priorities.
+    fn get_holder_priority(&self, resource_id: i32) -> i32 {
+        let resource = &self.resources[&resource_id];
+        match self.locking_protocol {
+            LockingProtocol::PriorityInheritance => resource
+                .waiters
+                .iter()
+                .map(|(waiter, _)| self.get_priority(&waiter.node_data))
+                .fold(self.get_priority(&resource.holder), i32::min),
+            LockingProtocol::Mpcp => i32::MIN.saturating_add(self.ceilings[&resource_id]),
+        }
+    }
+
+    /// Boost the holders that started their critical sections on a core to the ceilings of their
+    /// resources under MPCP, before any preemption is decided.
+    pub fn boost_holders(&self, processor: &mut impl ProcessorBase) {
+        if self.locking_protocol != LockingProtocol::Mpcp {
+            return;
+        }
+        for (&resource_id, resource) in self.resources.iter() {
+            let boosted_priority = self.get_holder_priority(resource_id);
+            for core_i in 0..processor.get_number_of_cores() {
+                if processor
+                    .get_processing_node(core_i)
+                    .is_some_and(|node_data| is_same_node(node_data, &resource.holder))
+                {
+                    processor.set_processing_node_param(
+                        core_i,
+                        &self.priority_key,
+                        boosted_priority,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Advance the critical sections of the holders in `executed_nodes`, which ran in the unit
+    /// ending at `current_time`. A finished critical section releases the resource, which is
+    /// handed to the blocked node with the highest priority, the earliest blocked among ties.
+    ///
+    /// # Returns
+    ///
+    /// * The blocking of the nodes handed a resource, which are back in the ready queue.
+    pub fn advance(
+        &mut self,
+        executed_nodes: &[NodeData],
+        process_result: &mut [ProcessResult],
+        processor: &mut impl ProcessorBase,
+        ready_queue: &mut ReadyQueue,
+        current_time: i32,
+    ) -> Vec<BlockingEvent> {
+        let mut blocking_events = Vec::new();
+        let resource_ids: Vec<i32> = self.resources.keys().copied().collect();
+        for resource_id in resource_ids {
+            let resource = self.resources.get_mut(&resource_id).unwrap();
+            if !executed_nodes
+                .iter()
+                .any(|node_data| is_same_node(node_data, &resource.holder))
+            {
+                continue;
+            }
+            resource.remaining_cs_length -= 1;
+            if resource.remaining_cs_length > 0 {
+                continue;
+            }
+
+            // Restore the own priority of the holder, also on its completion.
+            let mut resource = self.resources.remove(&resource_id).unwrap();
+            let own_priority = self.get_priority(&resource.holder);
+            set_node_priority(
+                processor,
+                ready_queue,
+                &resource.holder,
+                &self.priority_key,
+                own_priority,
+            );
+            for result in process_result.iter_mut() {
+                if let ProcessResult::Done(node_data) = result {
+                    if is_same_node(node_data, &resource.holder) {
+                        node_data
+                            .params
+                            .insert(self.priority_key.clone(), own_priority);
+                    }
+                }
+            }
+
+            let Some(waiter_i) = (0..resource.waiters.len()).min_by_key(|&waiter_i| {
+                let (waiter, blocked_time) = &resource.waiters[waiter_i];
+                (self.get_priority(&waiter.node_data), *blocked_time)
+            }) else {
+                continue;
+            };
+            let (mut waiter, blocked_time) = resource.waiters.remove(waiter_i);
+            let waiter_node = &waiter.node_data;
+            blocking_events.push(BlockingEvent {
+                dag_id: waiter_node.get_params_value("dag_id") as usize,
+                node_id: waiter_node.get_id(),
+                job_id: waiter_node.params.get("job_id").copied().unwrap_or(0) as usize,
+                resource_id,
+                holder_dag_id: resource.holder.get_params_value("dag_id") as usize,
+                holder_node_id: resource.holder.get_id(),
+                time: blocked_time,
+                blocking_time: current_time - blocked_time,
+            });
+            self.resources
+                .insert(resource_id, create_resource(waiter_node, resource.waiters));
+            let holder_priority = self.get_holder_priority(resource_id);
+            waiter
+                .node_data
+                .params
+                .insert(self.priority_key.clone(), holder_priority);
+            ready_queue.insert_new(waiter);
+        }
+        blocking_events
+    }
+}
+
+fn create_resource(holder: &NodeData, waiters: Vec<(NodeDataWrapper, i32)>) -> Resource {
+    let execution_time = holder.get_params_value("execution_time");
+    Resource {
+        holder: holder.clone(),
+        remaining_cs_length: holder
+            .params
+            .get("cs_length")
+            .map_or(execution_time, |&cs_length| cs_length.min(execution_time)),
+        waiters,
+    }
+}
+
+fn is_same_node(node_data: &NodeData, other: &NodeData) -> bool {
+    node_data.get_id() == other.get_id()
+        && node_data.params.get("dag_id") == other.params.get("dag_id")
+}
+
+/// Set the priority of `target` wherever it is, running on a core or in the ready queue.
+fn set_node_priority(
+    processor: &mut impl ProcessorBase,
+    ready_queue: &mut ReadyQueue,
+    target: &NodeData,
+    priority_key: &str,
+    priority: i32,
+) {
+    for core_i in 0..processor.get_number_of_cores() {
+        if processor
+            .get_processing_node(core_i)
+            .is_some_and(|node_data| is_same_node(node_data, target))
+        {
+            processor.set_processing_node_param(core_i, priority_key, priority);
+            return;
+        }
+    }
+    if let Some(mut wrapper) = ready_queue
+        .iter()
+        .find(|wrapper| is_same_node(&wrapper.node_data, target))
+        .cloned()
+    {
+        ready_queue.remove(&wrapper);
+        wrapper
+            .node_data
+            .params
+            .insert(priority_key.to_string(), priority);
+        ready_queue.insert_new(wrapper);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::homogeneous::HomogeneousProcessor;
+    use crate::test_util::create_node;
+
+    fn create_locking_node(dag_id: i32, priority: i32) -> NodeData {
+        let mut node_data = create_node(0, "execution_time", 4);
+        node_data.params.insert("dag_id".to_string(), dag_id);
+        node_data
+            .params
+            .insert("node_absolute_deadline".to_string(), priority);
+        node_data.params.insert("resource_id".to_string(), 0);
+        node_data.params.insert("cs_length".to_string(), 2);
+        node_data
+    }
+
+    #[test]
+    fn test_resource_manager_priority_inheritance() {
+        let mut dag = Graph::<NodeData, i32>::new();
+        dag.add_node(create_locking_node(0, 5));
+        let mut resource_manager =
+            ResourceManager::new(&[dag], "node_absolute_deadline", LockingProtocol::default())
+                .unwrap();
+        let mut processor = HomogeneousProcessor::new(1);
+        let mut ready_queue = ReadyQueue::new();
+
+        let holder = create_locking_node(0, 5);
+        assert!(resource_manager.try_lock(
+            &NodeDataWrapper::new(holder.clone()),
+            &mut processor,
+            &mut ready_queue,
+            0
+        ));
+        processor.allocate_specific_core(0, &holder);
+        let waiter = NodeDataWrapper::new(create_locking_node(1, 1));
+        assert!(!resource_manager.try_lock(&waiter, &mut processor, &mut ready_queue, 0));
+        assert_eq!(
+            processor.get_processing_node(0).unwrap().params["node_absolute_deadline"],
+            1
+        );
+
+        let mut process_result = processor.process();
+        let executed_nodes = vec![holder.clone()];
+        assert!(resource_manager
+            .advance(
+                &executed_nodes,
+                &mut process_result,
+                &mut processor,
+                &mut ready_queue,
+                1,
+            )
+            .is_empty());
+        let mut process_result = processor.process();
+        let blocking_events = resource_manager.advance(
+            &executed_nodes,
+            &mut process_result,
+            &mut processor,
+            &mut ready_queue,
+            2,
+        );
+
+        // The critical section ends after 2, and the holder runs on with its own priority.
+        assert_eq!(blocking_events.len(), 1);
+        assert_eq!(blocking_events[0].dag_id, 1);
+        assert_eq!(blocking_events[0].blocking_time, 2);
+        assert_eq!(
+            processor.get_processing_node(0).unwrap().params["node_absolute_deadline"],
+            5
+        );
+        assert_eq!(ready_queue.first().unwrap().node_data.params["dag_id"], 1);
+        // The blocked node holds the resource when it is dispatched again.
+        assert!(resource_manager.try_lock(&waiter, &mut processor, &mut ready_queue, 2));
+    }
+
+    #[test]
+    fn test_resource_manager_no_resources() {
+        let mut dag = Graph::<NodeData, i32>::new();
+        dag.add_node(create_node(0, "execution_time", 4));
+        assert!(
+            ResourceManager::new(&[dag], "node_absolute_deadline", LockingProtocol::default())
+                .is_none()
+        );
+    }
+}
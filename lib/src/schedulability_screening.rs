@@ -0,0 +1,146 @@
+//! Cheap necessary conditions for schedulability, checked before a full simulation.
+//!
+//! A DAG set that violates any of them misses a deadline under every scheduler,
+//! so batch experiments can skip its simulation.
+use crate::{
+    dag_task::DagTask,
+    graph_extension::{GraphExtension, NodeData},
+};
+use log::warn;
+use petgraph::Graph;
+use serde_derive::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScreeningViolation {
+    /// The critical path alone does not fit in the deadline.
+    CriticalPathExceedsDeadline {
+        dag_id: usize,
+        critical_path_length: i32,
+        end_to_end_deadline: i32,
+    },
+    /// The volume does not fit in the deadline even on all cores (`volume / D > m`).
+    DensityExceedsCores {
+        dag_id: usize,
+        density: f32,
+        number_of_cores: usize,
+    },
+    /// The total utilization exceeds the number of cores.
+    UtilizationExceedsCores {
+        total_utilization: f32,
+        number_of_cores: usize,
+    },
+}
+
+/// Check the necessary conditions per DAG and for the DAG set.
+///
+/// The deadline of a DAG without `end_to_end_deadline` is its period.
+/// DAGs without a period (e.g., triggered DAGs) do not count toward the total utilization.
+///
+/// # Returns
+///
+/// * The violated conditions. Empty if the DAG set passes the screening.
+pub fn screen_dag_set(
+    dag_set: &[Graph<NodeData, i32>],
+    number_of_cores: usize,
+) -> Vec<ScreeningViolation> {
+    let mut violations = Vec::new();
+    let mut total_utilization = 0.0;
+    for (dag_id, dag) in dag_set.iter().enumerate() {
+        let dag_task = DagTask::from_graph(dag);
+        total_utilization += dag_task.get_utilization().unwrap_or(0.0);
+        let Some(end_to_end_deadline) = dag_task.get_relative_deadline() else {
+            warn!("DAG {} has neither end_to_end_deadline nor period.", dag_id);
+            continue;
+        };
+
+        let mut graph = dag_task.graph;
+        let volume = graph.get_volume();
+        // A node has a negative slack iff the longest path through it exceeds the deadline,
+        // so the minimum slack is the deadline minus the critical path length.
+        graph.calculate_asap_alap_times(end_to_end_deadline);
+        let min_slack = graph
+            .node_weights()
+            .map(|node| node.get_params_value("slack"))
+            .min()
+            .unwrap_or(0);
+        if min_slack < 0 {
+            violations.push(ScreeningViolation::CriticalPathExceedsDeadline {
+                dag_id,
+                critical_path_length: end_to_end_deadline - min_slack,
+                end_to_end_deadline,
+            });
+        }
+        let density = volume as f32 / end_to_end_deadline as f32;
+        if density > number_of_cores as f32 {
+            violations.push(ScreeningViolation::DensityExceedsCores {
+                dag_id,
+                density,
+                number_of_cores,
+            });
+        }
+    }
+    if total_utilization > number_of_cores as f32 {
+        violations.push(ScreeningViolation::UtilizationExceedsCores {
+            total_utilization,
+            number_of_cores,
+        });
+    }
+
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::create_node;
+    use petgraph::graph::NodeIndex;
+
+    fn create_fork_dag(execution_time: i32, period: i32) -> Graph<NodeData, i32> {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 1));
+        let n1 = dag.add_node(create_node(1, "execution_time", execution_time));
+        let n2 = dag.add_node(create_node(2, "execution_time", execution_time));
+        dag.add_param(n0, "period", period);
+        dag.add_edge(n0, n1, 0);
+        dag.add_edge(n0, n2, 0);
+        dag
+    }
+
+    #[test]
+    fn test_screen_dag_set_normal() {
+        // Utilization 1 + 1
+        let dag_set = vec![create_fork_dag(4, 9), create_fork_dag(4, 9)];
+        assert!(screen_dag_set(&dag_set, 2).is_empty());
+
+        assert_eq!(
+            screen_dag_set(&dag_set, 1),
+            vec![ScreeningViolation::UtilizationExceedsCores {
+                total_utilization: 2.0,
+                number_of_cores: 1,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_screen_dag_set_per_dag_violations() {
+        let mut dag = create_fork_dag(4, 8);
+        dag.add_param(NodeIndex::new(1), "end_to_end_deadline", 4);
+        let violations = screen_dag_set(&[dag], 2);
+        assert_eq!(
+            violations,
+            vec![
+                ScreeningViolation::CriticalPathExceedsDeadline {
+                    dag_id: 0,
+                    critical_path_length: 5,
+                    end_to_end_deadline: 4,
+                },
+                ScreeningViolation::DensityExceedsCores {
+                    dag_id: 0,
+                    density: 2.25,
+                    number_of_cores: 2,
+                },
+            ]
+        );
+    }
+}
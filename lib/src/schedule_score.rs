@@ -0,0 +1,434 @@
+//! Score the quality of a schedule, to test the schedules themselves for regressions.
+//!
+//! The score is a weighted sum of the schedulability (higher is better), the normalized makespan,
+//! and the migrations and preemptions per node job (lower is better).
+//! Each run directory (see `output_log`) records the score of its run in the summary, and
+//! `find_score_regressions` compares the runs of two revisions on the same fixture suite, and
+//! `compare_runs` quantifies the change of the makespan and the response times between them.
+//! `test_paired_runs` tests whether the changes over all the runs are significant, e.g., between
+//! two algorithms on the same DAG sets selected by `select_alg_runs`.
+#[cfg(feature = "yaml")]
+use crate::output_log::{RunManifest, RunSummary};
+use crate::{
+    log::DAGSetSchedulerLog,
+    log::JobEventTimes,
+    statistics::{wilcoxon_signed_rank_test, WilcoxonSignedRankTest},
+};
+use serde_derive::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+#[cfg(feature = "yaml")]
+use std::{fs, path::Path};
+
+/// Cap of the worst response time relative to the deadline, so that a DAG with an unfinished
+/// job does not dominate the normalized makespan.
+pub const MAX_NORMALIZED_RESPONSE_TIME: f32 = 2.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ScoreWeights {
+    pub schedulability: f32,
+    pub makespan: f32,
+    pub migrations: f32,
+    pub preemptions: f32,
+}
+
+impl Default for ScoreWeights {
+    fn default() -> Self {
+        Self {
+            schedulability: 1.0,
+            makespan: 0.5,
+            migrations: 0.1,
+            preemptions: 0.1,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ScheduleScore {
+    /// Ratio of the DAGs whose worst response time is within the deadline.
+    pub schedulability: f32,
+    /// Average over the DAGs of the worst response time relative to the deadline.
+    pub normalized_makespan: f32,
+    /// Resumptions of preempted node jobs on another core.
+    pub migrations: usize,
+    pub preemptions: usize,
+    pub score: f32,
+}
+
+/// The deadline of the DAG, or its period with an implicit deadline.
+fn get_relative_deadline(log: &DAGSetSchedulerLog, dag_id: usize) -> i32 {
+    match log.get_end_to_end_deadline(dag_id) {
+        0 => log.get_period(dag_id),
+        end_to_end_deadline => end_to_end_deadline,
+    }
+}
+
+pub fn calculate_schedule_score(log: &DAGSetSchedulerLog, weights: &ScoreWeights) -> ScheduleScore {
+    let worst_response_times = log.get_worst_response_times();
+    let mut schedulable_dags = 0;
+    let mut total_normalized_response_time = 0.0;
+    for (dag_id, &worst_response_time) in worst_response_times.iter().enumerate() {
+        let deadline = get_relative_deadline(log, dag_id);
+        if worst_response_time <= deadline {
+            schedulable_dags += 1;
+        }
+        total_normalized_response_time += if deadline > 0 {
+            (worst_response_time as f32 / deadline as f32).min(MAX_NORMALIZED_RESPONSE_TIME)
+        } else {
+            MAX_NORMALIZED_RESPONSE_TIME
+        };
+    }
+    let number_of_dags = worst_response_times.len().max(1) as f32;
+    let schedulability = schedulable_dags as f32 / number_of_dags;
+    let normalized_makespan = total_normalized_response_time / number_of_dags;
+
+    // Core of the last execution of each node job
+    let mut last_core_ids = BTreeMap::new();
+    let mut migrations = 0;
+    for interval in log.get_execution_intervals() {
+        let key = (interval.dag_id, interval.node_id, interval.job_id);
+        if last_core_ids
+            .insert(key, interval.core_id)
+            .is_some_and(|core_id| core_id != interval.core_id)
+        {
+            migrations += 1;
+        }
+    }
+    let preemptions = log
+        .get_job_logs_in_time_order()
+        .into_iter()
+        .filter(|job_log| matches!(job_log.get_event_time(), JobEventTimes::PreemptedTime(_)))
+        .count();
+    let number_of_node_jobs = last_core_ids.len().max(1) as f32;
+
+    ScheduleScore {
+        schedulability,
+        normalized_makespan,
+        migrations,
+        preemptions,
+        score: weights.schedulability * schedulability
+            - weights.makespan * normalized_makespan
+            - weights.migrations * migrations as f32 / number_of_node_jobs
+            - weights.preemptions * preemptions as f32 / number_of_node_jobs,
+    }
+}
+
+/// The summaries of the run directories in `dir_path` by `<alg_name>-<dag_set_hash>-<cores>`.
+/// The latest run is taken for repeated runs, and runs without a score (screened out) are skipped.
+#[cfg(feature = "yaml")]
+pub fn load_run_summaries(dir_path: &str) -> BTreeMap<String, RunSummary> {
+    let mut run_dir_paths: Vec<_> = fs::read_dir(dir_path)
+        .unwrap_or_else(|err| panic!("Failed to read {}: {}", dir_path, err))
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.join("manifest.yaml").is_file())
+        .collect();
+    // Run directories start with the creation date.
+    run_dir_paths.sort();
+
+    let load = |path: &Path| {
+        fs::read_to_string(path).unwrap_or_else(|err| panic!("Failed to read {:?}: {}", path, err))
+    };
+    let mut run_summaries = BTreeMap::new();
+    for run_dir_path in run_dir_paths {
+        let manifest: RunManifest =
+            serde_yaml::from_str(&load(&run_dir_path.join("manifest.yaml"))).unwrap();
+        let summary: RunSummary =
+            serde_yaml::from_str(&load(&run_dir_path.join("summary.yaml"))).unwrap();
+        if summary.score.is_some() {
+            let run_key = format!(
+                "{}-{}-{}",
+                manifest.alg_name, manifest.dag_set_hash, manifest.number_of_cores
+            );
+            run_summaries.insert(run_key, summary);
+        }
+    }
+    run_summaries
+}
+
+/// The scores of the scored runs by run key.
+#[cfg(feature = "yaml")]
+pub fn get_run_scores(run_summaries: &BTreeMap<String, RunSummary>) -> BTreeMap<String, f32> {
+    run_summaries
+        .iter()
+        .filter_map(|(run_key, summary)| Some((run_key.clone(), summary.score?.score)))
+        .collect()
+}
+
+/// The scores of the run directories in `dir_path` (see `load_run_summaries`).
+#[cfg(feature = "yaml")]
+pub fn load_run_scores(dir_path: &str) -> BTreeMap<String, f32> {
+    get_run_scores(&load_run_summaries(dir_path))
+}
+
+/// The runs of `alg_name` by `<dag_set_hash>-<cores>`, to pair them with the runs of another
+/// algorithm on the same DAG sets.
+#[cfg(feature = "yaml")]
+pub fn select_alg_runs(
+    run_summaries: &BTreeMap<String, RunSummary>,
+    alg_name: &str,
+) -> BTreeMap<String, RunSummary> {
+    let prefix = format!("{}-", alg_name);
+    run_summaries
+        .iter()
+        .filter(|(_, summary)| summary.alg_name == alg_name)
+        .filter_map(|(run_key, summary)| {
+            Some((run_key.strip_prefix(&prefix)?.to_string(), summary.clone()))
+        })
+        .collect()
+}
+
+/// Change of the makespan and the response times of a run from the baseline to the candidate,
+/// e.g., to quantify the effect of a scheduling option. Negative changes are improvements.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RunComparison {
+    pub run_key: String,
+    pub normalized_makespan_change: f32,
+    /// Change of the worst response time of each DAG.
+    pub worst_response_time_changes: Vec<i32>,
+}
+
+impl RunComparison {
+    pub fn get_average_worst_response_time_change(&self) -> f32 {
+        self.worst_response_time_changes.iter().sum::<i32>() as f32
+            / self.worst_response_time_changes.len().max(1) as f32
+    }
+}
+
+/// Compare the runs in both the baseline and the candidate.
+#[cfg(feature = "yaml")]
+pub fn compare_runs(
+    baseline_summaries: &BTreeMap<String, RunSummary>,
+    candidate_summaries: &BTreeMap<String, RunSummary>,
+) -> Vec<RunComparison> {
+    baseline_summaries
+        .iter()
+        .filter_map(|(run_key, baseline_summary)| {
+            let candidate_summary = candidate_summaries.get(run_key)?;
+            Some(RunComparison {
+                run_key: run_key.clone(),
+                normalized_makespan_change: candidate_summary.score?.normalized_makespan
+                    - baseline_summary.score?.normalized_makespan,
+                worst_response_time_changes: baseline_summary
+                    .worst_response_times
+                    .iter()
+                    .zip(candidate_summary.worst_response_times.iter())
+                    .map(|(baseline, candidate)| candidate - baseline)
+                    .collect(),
+            })
+        })
+        .collect()
+}
+
+/// Wilcoxon signed-rank tests of the changes over the compared runs, paired by run.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PairedRunTests {
+    pub normalized_makespan: WilcoxonSignedRankTest,
+    pub average_worst_response_time: WilcoxonSignedRankTest,
+}
+
+pub fn test_paired_runs(comparisons: &[RunComparison]) -> PairedRunTests {
+    let normalized_makespan_changes: Vec<f64> = comparisons
+        .iter()
+        .map(|comparison| comparison.normalized_makespan_change as f64)
+        .collect();
+    let average_worst_response_time_changes: Vec<f64> = comparisons
+        .iter()
+        .map(|comparison| comparison.get_average_worst_response_time_change() as f64)
+        .collect();
+    PairedRunTests {
+        normalized_makespan: wilcoxon_signed_rank_test(&normalized_makespan_changes),
+        average_worst_response_time: wilcoxon_signed_rank_test(
+            &average_worst_response_time_changes,
+        ),
+    }
+}
+
+/// A run of the baseline whose score dropped by more than the threshold in the candidate.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScoreRegression {
+    pub run_key: String,
+    pub baseline_score: f32,
+    /// `None` if the candidate has no scored run for the key.
+    pub candidate_score: Option<f32>,
+}
+
+pub fn find_score_regressions(
+    baseline_scores: &BTreeMap<String, f32>,
+    candidate_scores: &BTreeMap<String, f32>,
+    threshold: f32,
+) -> Vec<ScoreRegression> {
+    baseline_scores
+        .iter()
+        .filter_map(|(run_key, &baseline_score)| {
+            let candidate_score = candidate_scores.get(run_key).copied();
+            candidate_score
+                .is_none_or(|candidate_score| candidate_score < baseline_score - threshold)
+                .then(|| ScoreRegression {
+                    run_key: run_key.clone(),
+                    baseline_score,
+                    candidate_score,
+                })
+        })
+        .collect()
+}
+
+#[cfg(all(test, feature = "yaml"))]
+mod tests {
+    use super::*;
+    use crate::test_util::create_node;
+    use crate::{
+        dag_set_scheduler::{DAGSetSchedulerBase, PreemptiveType},
+        global_edf_scheduler::GlobalEDFScheduler,
+        graph_extension::{GraphExtension, NodeData},
+        homogeneous::HomogeneousProcessor,
+        log::LogFormat,
+        processor::ProcessorBase,
+    };
+    use petgraph::Graph;
+
+    fn create_single_node_dag(
+        execution_time: i32,
+        offset: i32,
+        end_to_end_deadline: i32,
+        dag_id: i32,
+    ) -> Graph<NodeData, i32> {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", execution_time));
+        dag.add_param(n0, "period", 10);
+        dag.add_param(n0, "offset", offset);
+        dag.add_param(n0, "end_to_end_deadline", end_to_end_deadline);
+        dag.set_dag_param("dag_id", dag_id);
+        dag
+    }
+
+    #[test]
+    fn test_calculate_schedule_score_normal() {
+        // The DAG released at 1 preempts the other DAG for one time unit.
+        let dag_set = vec![
+            create_single_node_dag(4, 0, 10, 0),
+            create_single_node_dag(1, 1, 2, 1),
+        ];
+        let mut global_edf_scheduler =
+            GlobalEDFScheduler::new(&dag_set, &HomogeneousProcessor::new(1));
+        global_edf_scheduler.schedule(PreemptiveType::Preemptive {
+            key: "node_absolute_deadline".to_string(),
+        });
+        let score =
+            calculate_schedule_score(global_edf_scheduler.get_log_mut(), &ScoreWeights::default());
+        assert_eq!(score.schedulability, 1.0);
+        assert_eq!(score.normalized_makespan, 0.5);
+        assert_eq!(score.migrations, 0);
+        assert_eq!(score.preemptions, 1);
+        assert_eq!(score.score, 0.7);
+
+        let dir_path = "../lib/tests/schedule_score_test";
+        global_edf_scheduler.dump_run_dir(dir_path, "gedf", &dag_set, true, LogFormat::Yaml);
+        let baseline_scores = load_run_scores(dir_path);
+        fs::remove_dir_all(dir_path).unwrap();
+        assert_eq!(baseline_scores.values().collect::<Vec<_>>(), vec![&0.7]);
+
+        let run_key = baseline_scores.keys().next().unwrap().clone();
+        let mut candidate_scores = BTreeMap::from([(run_key.clone(), 0.65)]);
+        assert!(find_score_regressions(&baseline_scores, &candidate_scores, 0.1).is_empty());
+        assert_eq!(
+            find_score_regressions(&baseline_scores, &candidate_scores, 0.01),
+            vec![ScoreRegression {
+                run_key: run_key.clone(),
+                baseline_score: 0.7,
+                candidate_score: Some(0.65),
+            }]
+        );
+        candidate_scores.clear();
+        assert_eq!(
+            find_score_regressions(&baseline_scores, &candidate_scores, 0.1)[0].candidate_score,
+            None
+        );
+    }
+
+    fn create_summary(
+        alg_name: &str,
+        worst_response_times: Vec<i32>,
+        normalized_makespan: f32,
+    ) -> RunSummary {
+        RunSummary {
+            alg_name: alg_name.to_string(),
+            status: Default::default(),
+            result: true,
+            worst_response_times,
+            screening_violations: Vec::new(),
+            resource_usage: None,
+            slack: Vec::new(),
+            score: Some(ScheduleScore {
+                schedulability: 1.0,
+                normalized_makespan,
+                migrations: 0,
+                preemptions: 0,
+                score: 1.0 - 0.5 * normalized_makespan,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_compare_runs_normal() {
+        let baseline_summaries = BTreeMap::from([
+            (
+                "gedf-0-2".to_string(),
+                create_summary("gedf", vec![10, 6], 0.5),
+            ),
+            ("gedf-1-2".to_string(), create_summary("gedf", vec![8], 0.8)),
+        ]);
+        let candidate_summaries = BTreeMap::from([(
+            "gedf-0-2".to_string(),
+            create_summary("gedf", vec![9, 4], 0.25),
+        )]);
+
+        let comparisons = compare_runs(&baseline_summaries, &candidate_summaries);
+        assert_eq!(
+            comparisons,
+            vec![RunComparison {
+                run_key: "gedf-0-2".to_string(),
+                normalized_makespan_change: -0.25,
+                worst_response_time_changes: vec![-1, -2],
+            }]
+        );
+        assert_eq!(
+            comparisons[0].get_average_worst_response_time_change(),
+            -1.5
+        );
+    }
+
+    #[test]
+    fn test_paired_runs_between_algorithms() {
+        let mut run_summaries = BTreeMap::new();
+        for (dag_set_hash, (baseline, candidate)) in
+            [(10, 8), (12, 9), (9, 9), (15, 11), (11, 10), (14, 10)]
+                .into_iter()
+                .enumerate()
+        {
+            run_summaries.insert(
+                format!("gedf-{}-2", dag_set_hash),
+                create_summary("gedf", vec![baseline], baseline as f32 / 20.0),
+            );
+            run_summaries.insert(
+                format!("dynfed-{}-2", dag_set_hash),
+                create_summary("dynfed", vec![candidate], candidate as f32 / 20.0),
+            );
+        }
+        let baseline_summaries = select_alg_runs(&run_summaries, "gedf");
+        let candidate_summaries = select_alg_runs(&run_summaries, "dynfed");
+        assert_eq!(
+            baseline_summaries.keys().collect::<Vec<_>>(),
+            vec!["0-2", "1-2", "2-2", "3-2", "4-2", "5-2"]
+        );
+
+        let comparisons = compare_runs(&baseline_summaries, &candidate_summaries);
+        let tests = test_paired_runs(&comparisons);
+        let test = tests.average_worst_response_time;
+        assert_eq!((test.pairs, test.nonzero_pairs), (6, 5));
+        assert_eq!(test.positive_rank_sum, 0.0);
+        assert_eq!(test.rank_biserial_correlation, -1.0);
+        assert_eq!(test.median_difference, -2.5);
+        // Only 1 of the 32 sign assignments is as extreme on each side.
+        assert_eq!(test.p_value, 0.0625);
+        assert_eq!(tests.normalized_makespan.nonzero_pairs, 5);
+    }
+}
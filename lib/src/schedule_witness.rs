@@ -0,0 +1,259 @@
+//! Export a computed schedule as a witness for external checkers.
+//!
+//! A witness is one entry per continuous execution of a job:
+//! `dag_id, node_id, job_id, core_id, start, end, release, deadline, dag_name, node_name`,
+//! where `release` and `deadline` are those of the DAG job and the names are empty if not given. The witness can also be converted to the job set and
+//! precedence CSV files of the np-schedulability-analysis tool (SAG), so that its verdict
+//! can be compared with the simulated schedule.
+use crate::{
+    graph_extension::{get_node_index_maps, NodeData},
+    log::DAGSetSchedulerLog,
+};
+use petgraph::{visit::EdgeRef, Graph};
+use serde_derive::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WitnessEntry {
+    pub dag_id: usize,
+    pub node_id: usize,
+    pub job_id: usize,
+    pub core_id: usize,
+    pub start: i32,
+    pub end: i32,
+    pub release: i32,
+    pub deadline: i32,
+    #[serde(default)]
+    pub dag_name: Option<String>,
+    #[serde(default)]
+    pub node_name: Option<String>,
+}
+
+/// The witness of the schedule in `log`, ordered by start time.
+pub fn get_schedule_witness(log: &DAGSetSchedulerLog) -> Vec<WitnessEntry> {
+    let mut witness: Vec<WitnessEntry> = log
+        .get_execution_intervals()
+        .into_iter()
+        .map(|interval| {
+            let release = log
+                .get_release_times(interval.dag_id)
+                .get(interval.job_id)
+                .copied()
+                .unwrap_or_else(|| {
+                    panic!(
+                        "Release time of job {} of DAG {} is not logged.",
+                        interval.job_id, interval.dag_id
+                    )
+                });
+            WitnessEntry {
+                dag_id: interval.dag_id,
+                node_id: interval.node_id,
+                job_id: interval.job_id,
+                core_id: interval.core_id,
+                start: interval.begin_time,
+                end: interval.end_time,
+                release,
+                deadline: release + log.get_end_to_end_deadline(interval.dag_id),
+                dag_name: log.get_dag_name(interval.dag_id).map(str::to_string),
+                node_name: log
+                    .get_node_name(interval.dag_id, interval.node_id)
+                    .map(str::to_string),
+            }
+        })
+        .collect();
+    witness.sort_by_key(|entry| (entry.start, entry.core_id));
+    witness
+}
+
+pub fn write_schedule_witness_csv(witness: &[WitnessEntry], file_path: &str) {
+    let mut writer = csv::Writer::from_path(file_path)
+        .unwrap_or_else(|err| panic!("Failed to create {}: {}", file_path, err));
+    for entry in witness {
+        writer.serialize(entry).expect("Failed to serialize.");
+    }
+    writer.flush().expect("Failed to write the witness.");
+}
+
+/// The id of a node job in the SAG files, unique within its DAG (the SAG task).
+fn get_sag_job_id(dag: &Graph<NodeData, i32>, node_id: usize, job_id: usize) -> usize {
+    job_id * dag.node_count() + node_id + 1
+}
+
+/// Convert the witness to the job set and precedence CSV files of np-schedulability-analysis.
+///
+/// Each DAG is a task and each executed node job is a job with a fixed arrival at the release
+/// of the DAG job, the executed time as its cost, and the DAG deadline as both its deadline and
+/// its priority (EDF). Feedback edges are not exported because they connect different jobs.
+pub fn write_sag_csv(
+    witness: &[WitnessEntry],
+    dag_set: &[Graph<NodeData, i32>],
+    jobs_file_path: &str,
+    precedence_file_path: &str,
+) {
+    // (dag_id, job_id, node_id) -> (release, cost, deadline)
+    let mut jobs: BTreeMap<(usize, usize, usize), (i32, i32, i32)> = BTreeMap::new();
+    for entry in witness {
+        let job = jobs
+            .entry((entry.dag_id, entry.job_id, entry.node_id))
+            .or_insert((entry.release, 0, entry.deadline));
+        job.1 += entry.end - entry.start;
+    }
+
+    let mut jobs_writer = csv::Writer::from_path(jobs_file_path)
+        .unwrap_or_else(|err| panic!("Failed to create {}: {}", jobs_file_path, err));
+    jobs_writer
+        .write_record([
+            "Task ID",
+            "Job ID",
+            "Arrival min",
+            "Arrival max",
+            "Cost min",
+            "Cost max",
+            "Deadline",
+            "Priority",
+        ])
+        .expect("Failed to write the header.");
+    for (&(dag_id, job_id, node_id), &(release, cost, deadline)) in jobs.iter() {
+        jobs_writer
+            .serialize((
+                dag_id + 1,
+                get_sag_job_id(&dag_set[dag_id], node_id, job_id),
+                release,
+                release,
+                cost,
+                cost,
+                deadline,
+                deadline,
+            ))
+            .expect("Failed to serialize.");
+    }
+    jobs_writer.flush().expect("Failed to write the jobs.");
+
+    let mut precedence_writer = csv::Writer::from_path(precedence_file_path)
+        .unwrap_or_else(|err| panic!("Failed to create {}: {}", precedence_file_path, err));
+    precedence_writer
+        .write_record([
+            "Predecessor TID",
+            "Predecessor JID",
+            "Successor TID",
+            "Successor JID",
+        ])
+        .expect("Failed to write the header.");
+    let node_index_maps = get_node_index_maps(dag_set);
+    for &(dag_id, job_id, node_id) in jobs.keys() {
+        let dag = &dag_set[dag_id];
+        let node_i = node_index_maps[dag_id].get_node_index(node_id as i32);
+        for edge in dag.edges(node_i) {
+            let successor_id = dag[edge.target()].id as usize;
+            if !jobs.contains_key(&(dag_id, job_id, successor_id)) {
+                continue;
+            }
+            precedence_writer
+                .serialize((
+                    dag_id + 1,
+                    get_sag_job_id(dag, node_id, job_id),
+                    dag_id + 1,
+                    get_sag_job_id(dag, successor_id, job_id),
+                ))
+                .expect("Failed to serialize.");
+        }
+    }
+    precedence_writer
+        .flush()
+        .expect("Failed to write the precedence constraints.");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::create_node;
+    use crate::{
+        dag_set_scheduler::{DAGSetSchedulerBase, PreemptiveType},
+        global_edf_scheduler::GlobalEDFScheduler,
+        graph_extension::{DAGNames, GraphExtension},
+        homogeneous::HomogeneousProcessor,
+        processor::ProcessorBase,
+    };
+    use std::fs;
+
+    #[test]
+    fn test_schedule_witness_normal() {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 2));
+        let n1 = dag.add_node(create_node(1, "execution_time", 3));
+        dag.add_param(n0, "period", 10);
+        dag.add_param(n1, "end_to_end_deadline", 8);
+        dag.add_edge(n0, n1, 0);
+        dag.set_dag_param("dag_id", 0);
+        let dag_set = vec![dag];
+
+        let mut global_edf_scheduler =
+            GlobalEDFScheduler::new(&dag_set, &HomogeneousProcessor::new(1));
+        global_edf_scheduler.schedule(PreemptiveType::NonPreemptive);
+        let witness = get_schedule_witness(global_edf_scheduler.get_log_mut());
+        assert_eq!(witness.len(), 2);
+        assert_eq!(
+            witness[1],
+            WitnessEntry {
+                dag_id: 0,
+                node_id: 1,
+                job_id: 0,
+                core_id: 0,
+                start: 2,
+                end: 5,
+                release: 0,
+                deadline: 8,
+                dag_name: None,
+                node_name: None,
+            }
+        );
+
+        let jobs_file_path = "../lib/tests/schedule_witness_test_jobs.csv";
+        let precedence_file_path = "../lib/tests/schedule_witness_test_precedence.csv";
+        write_sag_csv(&witness, &dag_set, jobs_file_path, precedence_file_path);
+        let jobs = fs::read_to_string(jobs_file_path).unwrap();
+        let precedence = fs::read_to_string(precedence_file_path).unwrap();
+        fs::remove_file(jobs_file_path).unwrap();
+        fs::remove_file(precedence_file_path).unwrap();
+        assert_eq!(
+            jobs.lines().collect::<Vec<_>>(),
+            vec![
+                "Task ID,Job ID,Arrival min,Arrival max,Cost min,Cost max,Deadline,Priority",
+                "1,1,0,0,2,2,8,8",
+                "1,2,0,0,3,3,8,8",
+            ]
+        );
+        assert_eq!(precedence.lines().nth(1), Some("1,1,1,2"));
+    }
+
+    #[test]
+    fn test_schedule_witness_dag_names() {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 2));
+        let n1 = dag.add_node(create_node(1, "execution_time", 3));
+        dag.add_param(n0, "period", 10);
+        dag.add_param(n1, "end_to_end_deadline", 10);
+        dag.add_edge(n0, n1, 0);
+        dag.set_dag_param("dag_id", 0);
+
+        let mut global_edf_scheduler =
+            GlobalEDFScheduler::new(&[dag], &HomogeneousProcessor::new(1));
+        global_edf_scheduler
+            .get_log_mut()
+            .write_dag_names(&[DAGNames {
+                dag_name: Some("pipeline".to_string()),
+                node_names: BTreeMap::from([(1, "filter".to_string())]),
+            }]);
+        global_edf_scheduler.schedule(PreemptiveType::NonPreemptive);
+        let witness = get_schedule_witness(global_edf_scheduler.get_log_mut());
+        assert_eq!(witness[0].dag_name.as_deref(), Some("pipeline"));
+        assert_eq!(witness[0].node_name, None);
+        assert_eq!(witness[1].node_name.as_deref(), Some("filter"));
+        assert!(global_edf_scheduler
+            .get_log_mut()
+            .get_job_logs_in_time_order()
+            .iter()
+            .filter(|job_log| job_log.get_node_id() == 1)
+            .all(|job_log| job_log.get_node_name().as_deref() == Some("filter")));
+    }
+}
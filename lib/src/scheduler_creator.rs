@@ -0,0 +1,130 @@
+//! Select a DAG set scheduler on homogeneous processors by name, e.g., from a command line flag.
+#[cfg(feature = "random")]
+use crate::random_scheduler::RandomScheduler;
+use crate::{
+    dag_set_scheduler::{DAGSetSchedulerBase, PreemptiveType},
+    decomposition_scheduler::DecompositionScheduler,
+    elastic_scheduler::ElasticScheduler,
+    fifo_scheduler::FifoScheduler,
+    global_edf_scheduler::GlobalEDFScheduler,
+    global_fp_scheduler::GlobalFPScheduler,
+    graph_extension::NodeData,
+    homogeneous::HomogeneousProcessor,
+    log::DAGSetSchedulerLog,
+};
+use petgraph::graph::Graph;
+use serde_derive::{Deserialize, Serialize};
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SchedulerType {
+    #[default]
+    GlobalEdf,
+    GlobalFp,
+    /// Ready nodes in arrival order (`FifoScheduler`).
+    Fifo,
+    /// Ready nodes in random order with the default seed (`RandomScheduler`).
+    #[cfg(feature = "random")]
+    Random,
+    Decomposition,
+    Elastic,
+}
+
+impl FromStr for SchedulerType {
+    type Err = String;
+
+    fn from_str(scheduler_type: &str) -> Result<Self, Self::Err> {
+        match scheduler_type.to_lowercase().as_str() {
+            "global_edf" | "gedf" => Ok(Self::GlobalEdf),
+            "global_fp" | "gfp" => Ok(Self::GlobalFp),
+            "fifo" => Ok(Self::Fifo),
+            #[cfg(feature = "random")]
+            "random" => Ok(Self::Random),
+            "decomposition" | "decomp" => Ok(Self::Decomposition),
+            "elastic" => Ok(Self::Elastic),
+            _ => Err(format!("Unknown scheduler type: {}", scheduler_type)),
+        }
+    }
+}
+
+fn schedule_with<S: DAGSetSchedulerBase<HomogeneousProcessor>>(
+    dag_set: &[Graph<NodeData, i32>],
+    processor: &HomogeneousProcessor,
+    preemptive_type: PreemptiveType,
+) -> (i32, DAGSetSchedulerLog) {
+    let mut scheduler = S::new(dag_set, processor);
+    let schedule_length = scheduler.schedule(preemptive_type);
+    (schedule_length, scheduler.get_log_mut().clone())
+}
+
+/// Schedule `dag_set` with the default configuration of the scheduler of `scheduler_type`.
+///
+/// # Returns
+///
+/// * The schedule length and the log.
+pub fn schedule_dag_set(
+    scheduler_type: SchedulerType,
+    dag_set: &[Graph<NodeData, i32>],
+    processor: &HomogeneousProcessor,
+    preemptive_type: PreemptiveType,
+) -> (i32, DAGSetSchedulerLog) {
+    match scheduler_type {
+        SchedulerType::GlobalEdf => {
+            schedule_with::<GlobalEDFScheduler>(dag_set, processor, preemptive_type)
+        }
+        SchedulerType::GlobalFp => {
+            schedule_with::<GlobalFPScheduler>(dag_set, processor, preemptive_type)
+        }
+        SchedulerType::Fifo => schedule_with::<FifoScheduler>(dag_set, processor, preemptive_type),
+        #[cfg(feature = "random")]
+        SchedulerType::Random => schedule_with::<RandomScheduler<HomogeneousProcessor>>(
+            dag_set,
+            processor,
+            preemptive_type,
+        ),
+        SchedulerType::Decomposition => {
+            schedule_with::<DecompositionScheduler>(dag_set, processor, preemptive_type)
+        }
+        SchedulerType::Elastic => {
+            schedule_with::<ElasticScheduler>(dag_set, processor, preemptive_type)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph_extension::GraphExtension;
+    use crate::processor::ProcessorBase;
+    use crate::test_util::create_node;
+
+    #[test]
+    fn test_schedule_dag_set_by_name() {
+        let mut dag_set = Vec::new();
+        for (dag_id, offset) in [(0, 0), (1, 1), (2, 2)] {
+            let mut dag = Graph::<NodeData, i32>::new();
+            let n0 = dag.add_node(create_node(0, "execution_time", 2));
+            dag.add_param(n0, "period", 20);
+            dag.add_param(n0, "end_to_end_deadline", 20 - 6 * dag_id);
+            dag.add_param(n0, "offset", offset);
+            dag.set_dag_param("dag_id", dag_id);
+            dag_set.push(dag);
+        }
+        let processor = HomogeneousProcessor::new(1);
+        let get_worst_response_times = |scheduler_type: &str| {
+            let (_, log) = schedule_dag_set(
+                scheduler_type.parse().unwrap(),
+                &dag_set,
+                &processor,
+                PreemptiveType::NonPreemptive,
+            );
+            log.get_worst_response_times()
+        };
+
+        // DAG 1 arrives before DAG 2, which has the earlier deadline.
+        assert_eq!(get_worst_response_times("fifo"), vec![2, 3, 4]);
+        assert_eq!(get_worst_response_times("global_edf"), vec![2, 5, 2]);
+        assert!("unknown".parse::<SchedulerType>().is_err());
+    }
+}
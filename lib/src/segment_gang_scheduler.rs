@@ -0,0 +1,253 @@
+//! Global non-preemptive EDF with gang scheduling of the parallel segments of DAGs.
+//!
+//! The nodes of a DAG are divided into segments by `GraphExtension::get_parallel_segments`, and
+//! all the nodes of a segment start simultaneously on as many cores. A segment waits until all
+//! its nodes are ready and enough cores are idle at the same time. Segments are visited in EDF
+//! order and a segment that does not fit does not block later segments. Once started, the nodes
+//! of a segment run and complete independently. `max_parallelism` is not applied.
+use crate::dag_set_scheduler::{
+    DAGSetSchedulerBase, DAGStateManager, DAGStateManagerBase, NodeDataWrapper, PreemptiveType,
+    ReadyQueue,
+};
+use crate::getset_dag_set_scheduler;
+use crate::{
+    core::ProcessResult,
+    graph_extension::{get_node_index_maps, GraphExtension, NodeData, NodeIndexMap},
+    homogeneous::HomogeneousProcessor,
+    log::DAGSetSchedulerLog,
+    processor::ProcessorBase,
+    util::{clear_interrupted, get_hyper_period, get_process_core_indices, is_interrupted},
+};
+use petgraph::graph::Graph;
+use std::collections::{BTreeMap, BTreeSet};
+
+pub struct SegmentGangScheduler {
+    dag_set: Vec<Graph<NodeData, i32>>,
+    node_index_maps: Vec<NodeIndexMap>,
+    processor: HomogeneousProcessor,
+    log: DAGSetSchedulerLog,
+    current_time: i32,
+    /// Segment of each node id, for each DAG.
+    segment_indices: Vec<BTreeMap<i32, usize>>,
+    /// Number of nodes of each segment, for each DAG.
+    segment_sizes: Vec<Vec<usize>>,
+}
+
+impl SegmentGangScheduler {
+    /// Add a ready node to its segment, and move the segment to the ready segments when all its
+    /// nodes are ready. A ready segment is ordered in the ready queue by its first node in EDF
+    /// order.
+    fn insert_ready_node(
+        &mut self,
+        node_data: NodeData,
+        waiting_segments: &mut BTreeMap<(usize, usize), Vec<NodeData>>,
+        ready_segments: &mut BTreeMap<(usize, usize), Vec<NodeData>>,
+        ready_queue: &mut ReadyQueue,
+    ) {
+        self.log.write_ready_queue_insertion();
+        let dag_id = node_data.get_params_value("dag_id") as usize;
+        let segment_i = self.segment_indices[dag_id][&node_data.id];
+        let segment = waiting_segments.entry((dag_id, segment_i)).or_default();
+        segment.push(node_data);
+        if segment.len() == self.segment_sizes[dag_id][segment_i] {
+            let segment = waiting_segments.remove(&(dag_id, segment_i)).unwrap();
+            let head = segment
+                .iter()
+                .map(|node_data| NodeDataWrapper::new(node_data.clone()))
+                .min()
+                .unwrap();
+            ready_queue.insert_new(head);
+            ready_segments.insert((dag_id, segment_i), segment);
+        }
+    }
+}
+
+impl DAGSetSchedulerBase<HomogeneousProcessor> for SegmentGangScheduler {
+    fn new(dag_set: &[Graph<NodeData, i32>], processor: &HomogeneousProcessor) -> Self {
+        let mut segment_indices = Vec::with_capacity(dag_set.len());
+        let mut segment_sizes = Vec::with_capacity(dag_set.len());
+        for dag in dag_set {
+            let mut dag = dag.clone();
+            let segments = dag.get_parallel_segments();
+            let mut node_segment_indices = BTreeMap::new();
+            for (segment_i, segment) in segments.iter().enumerate() {
+                for &node_i in segment {
+                    node_segment_indices.insert(dag[node_i].id, segment_i);
+                }
+            }
+            segment_indices.push(node_segment_indices);
+            segment_sizes.push(segments.iter().map(|segment| segment.len()).collect());
+        }
+        Self {
+            node_index_maps: get_node_index_maps(dag_set),
+            dag_set: dag_set.to_vec(),
+            processor: processor.clone(),
+            log: DAGSetSchedulerLog::new(dag_set, processor.get_number_of_cores()),
+            current_time: 0,
+            segment_indices,
+            segment_sizes,
+        }
+    }
+
+    getset_dag_set_scheduler!(HomogeneousProcessor);
+
+    /// The preemptive type is ignored because a gang cannot be preempted.
+    fn schedule(&mut self, _preemptive_type: PreemptiveType) -> i32 {
+        clear_interrupted();
+        self.log.write_schedule_start();
+        let number_of_cores = self.processor.get_number_of_cores();
+        for (dag_i, sizes) in self.segment_sizes.iter().enumerate() {
+            if let Some(&max_size) = sizes.iter().max() {
+                if max_size > number_of_cores {
+                    panic!(
+                        "DAG {} has a segment of {} nodes, but there are only {} cores.",
+                        dag_i, max_size, number_of_cores
+                    );
+                }
+            }
+        }
+
+        let mut managers = vec![DAGStateManager::default(); self.dag_set.len()];
+        let mut waiting_segments = BTreeMap::new();
+        let mut ready_segments = BTreeMap::new();
+        let mut ready_queue = ReadyQueue::new();
+        let hyper_period = get_hyper_period(&self.dag_set);
+        while self.get_current_time() < hyper_period && !is_interrupted() {
+            // Release DAGs
+            for ready_node in self.release_dags(&mut managers) {
+                self.insert_ready_node(
+                    ready_node,
+                    &mut waiting_segments,
+                    &mut ready_segments,
+                    &mut ready_queue,
+                );
+            }
+
+            // Allocate each ready segment in EDF order if enough cores are idle.
+            let heads: Vec<NodeDataWrapper> = ready_queue.iter().cloned().collect();
+            for head in heads {
+                let dag_id = head.node_data.get_params_value("dag_id") as usize;
+                let segment_key = (dag_id, self.segment_indices[dag_id][&head.node_data.id]);
+                let Some(core_indices) =
+                    self.processor.allocate_gang(&ready_segments[&segment_key])
+                else {
+                    continue;
+                };
+                ready_queue.remove(&head);
+                let segment = ready_segments.remove(&segment_key).unwrap();
+                let job_id = managers[dag_id].get_release_count() as usize;
+                for (node_data, core_i) in segment.iter().zip(core_indices) {
+                    self.log.write_ready_queue_removal();
+                    self.log
+                        .write_allocating_job(node_data, core_i, job_id, self.current_time);
+                }
+            }
+
+            // The remaining ready nodes wait for the rest of their segments or enough idle cores.
+            if self.processor.get_idle_core_num() > 0 {
+                let waiting_dag_ids: BTreeSet<usize> = waiting_segments
+                    .keys()
+                    .map(|&(dag_id, _)| dag_id)
+                    .chain(
+                        ready_queue
+                            .iter()
+                            .map(|head| head.node_data.get_params_value("dag_id") as usize),
+                    )
+                    .collect();
+                for dag_id in waiting_dag_ids {
+                    self.log.write_gang_waiting_time(dag_id);
+                }
+            }
+
+            // Process unit time
+            self.current_time += 1;
+            let process_result = self.processor.process();
+            let indices: Vec<usize> = get_process_core_indices(&process_result);
+            self.log.write_processing_time(&indices);
+
+            // Post-process on completion of node execution
+            for (core_id, result) in process_result.iter().enumerate() {
+                if let ProcessResult::Done(node_data) = result {
+                    for ready_node in
+                        self.post_process_on_node_completion(node_data, core_id, &mut managers)
+                    {
+                        self.insert_ready_node(
+                            ready_node,
+                            &mut waiting_segments,
+                            &mut ready_segments,
+                            &mut ready_queue,
+                        );
+                    }
+                }
+            }
+        }
+
+        self.log
+            .write_comparisons(ready_queue.get_comparison_count());
+        self.calculate_log();
+        self.get_current_time()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::create_node;
+
+    /// n0 -> {n1, n2} -> n3, whose segment {n1, n2} needs 2 cores.
+    fn create_fork_join_dag() -> Graph<NodeData, i32> {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 1));
+        let n1 = dag.add_node(create_node(1, "execution_time", 2));
+        let n2 = dag.add_node(create_node(2, "execution_time", 2));
+        let n3 = dag.add_node(create_node(3, "execution_time", 1));
+        dag.add_param(n0, "period", 10);
+        dag.add_param(n0, "end_to_end_deadline", 10);
+        dag.add_edge(n0, n1, 1);
+        dag.add_edge(n0, n2, 1);
+        dag.add_edge(n1, n3, 1);
+        dag.add_edge(n2, n3, 1);
+        dag
+    }
+
+    fn create_single_node_dag(
+        execution_time: i32,
+        end_to_end_deadline: i32,
+    ) -> Graph<NodeData, i32> {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", execution_time));
+        dag.add_param(n0, "period", 10);
+        dag.add_param(n0, "end_to_end_deadline", end_to_end_deadline);
+        dag
+    }
+
+    #[test]
+    fn test_segment_gang_normal() {
+        let mut fork_join_dag = create_fork_join_dag();
+        let mut dag = create_single_node_dag(3, 5);
+        fork_join_dag.set_dag_param("dag_id", 0);
+        dag.set_dag_param("dag_id", 1);
+        let dag_set = vec![fork_join_dag, dag];
+
+        let mut segment_gang_scheduler =
+            SegmentGangScheduler::new(&dag_set, &HomogeneousProcessor::new(2));
+        segment_gang_scheduler.schedule(PreemptiveType::NonPreemptive);
+        let log = segment_gang_scheduler.get_log_mut();
+        // n1 and n2 wait from 1 until dag releases its core at 3.
+        assert_eq!(log.get_worst_response_time(0), 6);
+        assert_eq!(log.get_worst_response_time(1), 3);
+        assert_eq!(log.get_gang_waiting_time(0), 2);
+        assert_eq!(log.get_gang_waiting_time(1), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_segment_gang_segment_exceeds_cores() {
+        let mut fork_join_dag = create_fork_join_dag();
+        fork_join_dag.set_dag_param("dag_id", 0);
+
+        let mut segment_gang_scheduler =
+            SegmentGangScheduler::new(&[fork_join_dag], &HomogeneousProcessor::new(1));
+        segment_gang_scheduler.schedule(PreemptiveType::NonPreemptive);
+    }
+}
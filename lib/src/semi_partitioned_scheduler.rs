@@ -0,0 +1,455 @@
+//! Semi-partitioned EDF with C=D splitting: the nodes are assigned to cores by First-Fit on
+//! their utilizations (`execution_time / period`), and a node that fits on no core is split
+//! into two portions instead of being rejected.
+//!
+//! The first portion runs on the core with the most remaining capacity for a budget that fills
+//! the core, with its deadline equal to the budget (C=D). Since it has no laxity, it runs ahead of
+//! the EDF nodes of its core and is never preempted. When the budget is exhausted, the node
+//! migrates to its second core and runs the remainder by EDF with its own deadline.
+use crate::dag_set_scheduler::{DAGSetSchedulerBase, NodeDataWrapper, PreemptiveType, ReadyQueue};
+use crate::getset_dag_set_scheduler;
+use crate::{
+    core::ProcessResult,
+    dag_set_scheduler::{DAGStateManager, DAGStateManagerBase},
+    graph_extension::{get_node_index_maps, GraphExtension, NodeData, NodeIndexMap},
+    homogeneous::HomogeneousProcessor,
+    log::{DAGSetSchedulerLog, JobEventTimes, MigrationEvent},
+    processor::ProcessorBase,
+    util::{clear_interrupted, get_hyper_period, get_process_core_indices, is_interrupted},
+};
+use petgraph::graph::Graph;
+use serde_derive::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Tolerance for the floating-point error of the summed utilizations.
+const BUDGET_EPSILON: f32 = 1e-4;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SplitNode {
+    pub dag_id: usize,
+    pub node_id: i32,
+    pub first_core_id: usize,
+    pub second_core_id: usize,
+    /// Execution time of the first portion, which is also its relative deadline.
+    pub budget: i32,
+}
+
+/// Assignment of the nodes to the cores.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SemiPartition {
+    /// Core of each node by `dag_id` and node id. The second core for split nodes.
+    pub node_core_ids: Vec<BTreeMap<i32, usize>>,
+    pub core_utilizations: Vec<f32>,
+    pub split_nodes: Vec<SplitNode>,
+    /// Nodes that fit on no core even if split, as `(dag_id, node_id)`.
+    pub unfit_nodes: Vec<(usize, i32)>,
+}
+
+impl SemiPartition {
+    pub fn get_split_node(&self, dag_id: usize, node_id: i32) -> Option<&SplitNode> {
+        self.split_nodes
+            .iter()
+            .find(|split_node| split_node.dag_id == dag_id && split_node.node_id == node_id)
+    }
+}
+
+/// The nodes are packed in the order of `dag_id` and node index, and ties are broken by the
+/// smallest core index. An unfit node is placed on the least utilized core without splitting.
+pub fn semi_partition_dag_set(
+    dag_set: &[Graph<NodeData, i32>],
+    number_of_cores: usize,
+) -> SemiPartition {
+    let mut partition = SemiPartition {
+        node_core_ids: Vec::with_capacity(dag_set.len()),
+        core_utilizations: vec![0.0; number_of_cores],
+        split_nodes: Vec::new(),
+        unfit_nodes: Vec::new(),
+    };
+    for (dag_id, dag) in dag_set.iter().enumerate() {
+        let period = dag
+            .get_head_period()
+            .unwrap_or_else(|| panic!("DAG {} has no period.", dag_id));
+        let mut node_core_ids = BTreeMap::new();
+        for node_data in dag.node_weights() {
+            let execution_time = node_data.get_params_value("execution_time");
+            let utilization = execution_time as f32 / period as f32;
+            let core_utilizations = &mut partition.core_utilizations;
+            let core_i = if let Some(core_i) =
+                (0..number_of_cores).find(|&core_i| core_utilizations[core_i] + utilization <= 1.0)
+            {
+                core_utilizations[core_i] += utilization;
+                core_i
+            } else if let Some(split_node) = split_node(
+                core_utilizations,
+                dag_id,
+                node_data.id,
+                execution_time,
+                period,
+            ) {
+                let second_core_id = split_node.second_core_id;
+                partition.split_nodes.push(split_node);
+                second_core_id
+            } else {
+                partition.unfit_nodes.push((dag_id, node_data.id));
+                let core_i = (0..number_of_cores)
+                    .min_by(|&a, &b| core_utilizations[a].total_cmp(&core_utilizations[b]))
+                    .unwrap();
+                core_utilizations[core_i] += utilization;
+                core_i
+            };
+            node_core_ids.insert(node_data.id, core_i);
+        }
+        partition.node_core_ids.push(node_core_ids);
+    }
+    partition
+}
+
+/// Fill the core with the most remaining capacity with the first portion, and place the
+/// remainder on the first other core that fits. The utilizations are updated if split.
+fn split_node(
+    core_utilizations: &mut [f32],
+    dag_id: usize,
+    node_id: i32,
+    execution_time: i32,
+    period: i32,
+) -> Option<SplitNode> {
+    let first_core_id = (0..core_utilizations.len())
+        .min_by(|&a, &b| core_utilizations[a].total_cmp(&core_utilizations[b]))?;
+    let budget =
+        ((1.0 - core_utilizations[first_core_id]) * period as f32 + BUDGET_EPSILON).floor() as i32;
+    if budget <= 0 {
+        return None;
+    }
+    let remainder_utilization = (execution_time - budget) as f32 / period as f32;
+    let second_core_id = (0..core_utilizations.len()).find(|&core_i| {
+        core_i != first_core_id && core_utilizations[core_i] + remainder_utilization <= 1.0
+    })?;
+    core_utilizations[first_core_id] += budget as f32 / period as f32;
+    core_utilizations[second_core_id] += remainder_utilization;
+    Some(SplitNode {
+        dag_id,
+        node_id,
+        first_core_id,
+        second_core_id,
+        budget,
+    })
+}
+
+/// Whether the node is running (or waiting for) the first portion of its split.
+fn is_first_portion(managers: &[DAGStateManager], node_data: &NodeData) -> bool {
+    managers[node_data.get_params_value("dag_id") as usize]
+        .get_split_budget(node_data.id)
+        .is_some()
+}
+
+pub struct SemiPartitionedScheduler {
+    dag_set: Vec<Graph<NodeData, i32>>,
+    node_index_maps: Vec<NodeIndexMap>,
+    processor: HomogeneousProcessor,
+    log: DAGSetSchedulerLog,
+    current_time: i32,
+    partition: SemiPartition,
+}
+
+impl DAGSetSchedulerBase<HomogeneousProcessor> for SemiPartitionedScheduler {
+    fn new(dag_set: &[Graph<NodeData, i32>], processor: &HomogeneousProcessor) -> Self {
+        Self {
+            node_index_maps: get_node_index_maps(dag_set),
+            dag_set: dag_set.to_vec(),
+            processor: processor.clone(),
+            log: DAGSetSchedulerLog::new(dag_set, processor.get_number_of_cores()),
+            current_time: 0,
+            partition: semi_partition_dag_set(dag_set, processor.get_number_of_cores()),
+        }
+    }
+
+    getset_dag_set_scheduler!(HomogeneousProcessor);
+
+    fn schedule(&mut self, preemptive_type: PreemptiveType) -> i32 {
+        clear_interrupted();
+        self.log.write_schedule_start();
+        let mut managers = vec![DAGStateManager::default(); self.dag_set.len()];
+        let mut ready_queues = vec![ReadyQueue::new(); self.processor.get_number_of_cores()];
+        let hyper_period = get_hyper_period(&self.dag_set);
+        while self.current_time < hyper_period && !is_interrupted() {
+            // Release DAGs
+            for ready_node in self.release_dags(&mut managers) {
+                self.insert_ready_node(&mut ready_queues, &mut managers, ready_node);
+            }
+
+            // Allocate the first portion, or the head of each ready queue, to its core.
+            for (core_i, ready_queue) in ready_queues.iter_mut().enumerate() {
+                let Some(head) = ready_queue
+                    .iter()
+                    .find(|wrapper| is_first_portion(&managers, &wrapper.node_data))
+                    .or(ready_queue.first())
+                    .cloned()
+                else {
+                    continue;
+                };
+                let core = &self.processor.cores[core_i];
+                if !core.get_is_idle() {
+                    let running_node = core.get_processing_node().as_ref().unwrap();
+                    if is_first_portion(&managers, running_node) {
+                        continue;
+                    }
+                    if !is_first_portion(&managers, &head.node_data) {
+                        let Some(preemptive_key) = preemptive_type.get_key() else {
+                            continue;
+                        };
+                        if core.run_time < preemptive_type.get_non_preemptive_region() {
+                            continue;
+                        }
+                        ready_queue.count_comparison();
+                        if head.node_data.get_params_value(preemptive_key)
+                            >= running_node.get_params_value(preemptive_key)
+                        {
+                            continue;
+                        }
+                    }
+                    let current_time = self.current_time;
+                    let preempted_node_data = self.processor.preempt(core_i).unwrap();
+                    self.log.write_job_event(
+                        &preempted_node_data,
+                        core_i,
+                        managers[preempted_node_data.get_params_value("dag_id") as usize]
+                            .get_release_count() as usize
+                            - 1,
+                        JobEventTimes::PreemptedTime(current_time),
+                    );
+                    ready_queue.insert_new(NodeDataWrapper::new(preempted_node_data));
+                    self.log.write_ready_queue_insertion();
+                }
+                ready_queue.remove(&head);
+                self.log.write_ready_queue_removal();
+                let node_data = head.convert_node_data();
+                self.allocate_node(
+                    &node_data,
+                    core_i,
+                    managers[node_data.get_params_value("dag_id") as usize].get_release_count()
+                        as usize,
+                );
+            }
+
+            // Process unit time
+            let process_result = self.process_unit_time();
+            self.log
+                .write_processing_time(&get_process_core_indices(&process_result));
+
+            // Migrate the first portions whose budget is exhausted, and post-process on
+            // completion of node execution.
+            for (core_id, result) in process_result.iter().enumerate() {
+                match result {
+                    ProcessResult::Continue => {
+                        let node_data = self.processor.cores[core_id]
+                            .get_processing_node()
+                            .clone()
+                            .unwrap();
+                        let dag_id = node_data.get_params_value("dag_id") as usize;
+                        if is_first_portion(&managers, &node_data)
+                            && managers[dag_id].consume_split_budget(node_data.id)
+                        {
+                            managers[dag_id].end_split_budget(node_data.id);
+                            self.migrate_node(&mut ready_queues, &managers, core_id);
+                        }
+                    }
+                    ProcessResult::Done(node_data) => {
+                        managers[node_data.get_params_value("dag_id") as usize]
+                            .end_split_budget(node_data.id);
+                        let ready_nodes =
+                            self.post_process_on_node_completion(node_data, core_id, &mut managers);
+                        for ready_node in ready_nodes {
+                            self.insert_ready_node(&mut ready_queues, &mut managers, ready_node);
+                        }
+                    }
+                    ProcessResult::Idle => {}
+                }
+            }
+        }
+
+        self.log.write_comparisons(
+            ready_queues
+                .iter()
+                .map(ReadyQueue::get_comparison_count)
+                .sum(),
+        );
+        self.calculate_log();
+        self.current_time
+    }
+}
+
+impl SemiPartitionedScheduler {
+    pub fn get_partition(&self) -> &SemiPartition {
+        &self.partition
+    }
+
+    /// Insert the node into the ready queue of its core, or of its first core if split.
+    fn insert_ready_node(
+        &mut self,
+        ready_queues: &mut [ReadyQueue],
+        managers: &mut [DAGStateManager],
+        node_data: NodeData,
+    ) {
+        let dag_id = node_data.get_params_value("dag_id") as usize;
+        let core_i = match self.partition.get_split_node(dag_id, node_data.id) {
+            Some(split_node) => {
+                managers[dag_id].start_split_budget(node_data.id, split_node.budget);
+                split_node.first_core_id
+            }
+            None => self.partition.node_core_ids[dag_id][&node_data.id],
+        };
+        ready_queues[core_i].insert_new(NodeDataWrapper::new(node_data));
+        self.log.write_ready_queue_insertion();
+    }
+
+    /// Preempt the node on its first core and move it to the ready queue of its second core.
+    fn migrate_node(
+        &mut self,
+        ready_queues: &mut [ReadyQueue],
+        managers: &[DAGStateManager],
+        core_i: usize,
+    ) {
+        let current_time = self.current_time;
+        let node_data = self.processor.preempt(core_i).unwrap();
+        let dag_id = node_data.get_params_value("dag_id") as usize;
+        let job_id = managers[dag_id].get_release_count() as usize - 1;
+        self.log.write_job_event(
+            &node_data,
+            core_i,
+            job_id,
+            JobEventTimes::PreemptedTime(current_time),
+        );
+        let to_core_id = self.partition.node_core_ids[dag_id][&node_data.id];
+        self.log.write_migration_event(MigrationEvent {
+            dag_id,
+            node_id: node_data.id,
+            job_id,
+            time: current_time,
+            from_core_id: core_i,
+            to_core_id,
+        });
+        ready_queues[to_core_id].insert_new(NodeDataWrapper::new(node_data));
+        self.log.write_ready_queue_insertion();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::create_node;
+
+    fn create_single_node_dag(execution_time: i32, dag_id: i32) -> Graph<NodeData, i32> {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", execution_time));
+        dag.add_param(n0, "period", 10);
+        dag.add_param(n0, "end_to_end_deadline", 10);
+        dag.set_dag_param("dag_id", dag_id);
+        dag
+    }
+
+    #[test]
+    fn test_semi_partitioned_scheduler_normal() {
+        // Three DAGs of utilization 0.6 on two cores. DAG 2 is split into 4 on core 0 and 2 on core 1.
+        let dag_set = vec![
+            create_single_node_dag(6, 0),
+            create_single_node_dag(6, 1),
+            create_single_node_dag(6, 2),
+        ];
+        let mut scheduler = SemiPartitionedScheduler::new(&dag_set, &HomogeneousProcessor::new(2));
+        assert_eq!(
+            scheduler.get_partition().split_nodes,
+            vec![SplitNode {
+                dag_id: 2,
+                node_id: 0,
+                first_core_id: 0,
+                second_core_id: 1,
+                budget: 4,
+            }]
+        );
+        assert!(scheduler.get_partition().unfit_nodes.is_empty());
+
+        scheduler.schedule(PreemptiveType::Preemptive {
+            key: "node_absolute_deadline".to_string(),
+        });
+        let log = scheduler.get_log_mut();
+        // DAG 2 runs first on core 0 until 4, and after DAG 1 on core 1 until 8.
+        assert_eq!(log.get_worst_response_times(), vec![10, 6, 8]);
+        assert_eq!(
+            log.get_migration_log(),
+            &[MigrationEvent {
+                dag_id: 2,
+                node_id: 0,
+                job_id: 0,
+                time: 4,
+                from_core_id: 0,
+                to_core_id: 1,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_semi_partitioned_scheduler_least_utilized_first_core() {
+        // Utilizations 0.8, 0.6 and 0.5 on two cores. The first portion of DAG 2 fills core 1,
+        // which has the most remaining capacity, and the remainder goes to core 0.
+        let dag_set = vec![
+            create_single_node_dag(8, 0),
+            create_single_node_dag(6, 1),
+            create_single_node_dag(5, 2),
+        ];
+        let partition = semi_partition_dag_set(&dag_set, 2);
+        assert_eq!(
+            partition.get_split_node(2, 0),
+            Some(&SplitNode {
+                dag_id: 2,
+                node_id: 0,
+                first_core_id: 1,
+                second_core_id: 0,
+                budget: 4,
+            })
+        );
+        assert_eq!(partition.node_core_ids[2][&0], 0);
+        assert!(partition.get_split_node(1, 0).is_none());
+    }
+
+    #[test]
+    fn test_semi_partitioned_scheduler_unfit() {
+        // Utilizations 0.9 on two cores. The remainder of DAG 2 fits on no other core, so that
+        // DAG 2 is placed on core 0 (tie) without splitting.
+        let dag_set = vec![
+            create_single_node_dag(9, 0),
+            create_single_node_dag(9, 1),
+            create_single_node_dag(9, 2),
+        ];
+        let mut scheduler = SemiPartitionedScheduler::new(&dag_set, &HomogeneousProcessor::new(2));
+        let partition = scheduler.get_partition();
+        assert!(partition.split_nodes.is_empty());
+        assert_eq!(partition.unfit_nodes, vec![(2, 0)]);
+        assert_eq!(partition.node_core_ids[2][&0], 0);
+
+        scheduler.schedule(PreemptiveType::Preemptive {
+            key: "node_absolute_deadline".to_string(),
+        });
+        let log = scheduler.get_log_mut();
+        // DAG 2 runs after DAG 0 on the overloaded core 0 and never completes.
+        assert_eq!(log.get_worst_response_times(), vec![9, 9, i32::MAX]);
+        assert!(log.get_migration_log().is_empty());
+
+        // A full core leaves no budget for the first portion.
+        let dag_set = vec![
+            create_single_node_dag(10, 0),
+            create_single_node_dag(10, 1),
+            create_single_node_dag(5, 2),
+        ];
+        let partition = semi_partition_dag_set(&dag_set, 2);
+        assert!(partition.split_nodes.is_empty());
+        assert_eq!(partition.unfit_nodes, vec![(2, 0)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "DAG 0 has no period.")]
+    fn test_semi_partitioned_scheduler_no_period() {
+        let mut dag = Graph::<NodeData, i32>::new();
+        dag.add_node(create_node(0, "execution_time", 1));
+        semi_partition_dag_set(&[dag], 1);
+    }
+}
@@ -0,0 +1,295 @@
+//! Replay a precomputed table of start times and cores, e.g., a schedule solved by an ILP outside
+//! the simulator, and log it in the same format as the schedulers.
+//!
+//! Each entry starts a node of a job of a DAG on a core and runs it to completion. The replay
+//! panics if an entry starts a node before its job is released, before its predecessors
+//! complete, or on a busy core.
+use crate::{
+    core::ProcessResult,
+    dag_set_scheduler::{
+        DAGSetSchedulerBase, DAGStateManager, DAGStateManagerBase, PreemptiveType,
+    },
+    getset_dag_set_scheduler,
+    graph_extension::{get_node_index_maps, NodeData, NodeIndexMap},
+    homogeneous::HomogeneousProcessor,
+    log::DAGSetSchedulerLog,
+    processor::ProcessorBase,
+    util::{clear_interrupted, get_hyper_period, get_process_core_indices, is_interrupted},
+};
+use log::warn;
+use petgraph::Graph;
+use serde_derive::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StaticScheduleEntry {
+    pub dag_id: usize,
+    pub node_id: i32,
+    /// Index of the job of the DAG, counted from 0.
+    #[serde(default)]
+    pub job_id: usize,
+    pub core_id: usize,
+    pub start_time: i32,
+}
+
+/// Load the table from a YAML list of entries, e.g.,
+/// `- {dag_id: 0, node_id: 1, job_id: 0, core_id: 1, start_time: 4}`.
+#[cfg(feature = "yaml")]
+pub fn load_static_schedule(file_path: &str) -> Vec<StaticScheduleEntry> {
+    let yaml = std::fs::read_to_string(file_path)
+        .unwrap_or_else(|err| panic!("Failed to open {}: {}", file_path, err));
+    serde_yaml::from_str(&yaml)
+        .unwrap_or_else(|err| panic!("Invalid static schedule in {}: {}", file_path, err))
+}
+
+pub struct StaticScheduleExecutor {
+    dag_set: Vec<Graph<NodeData, i32>>,
+    node_index_maps: Vec<NodeIndexMap>,
+    processor: HomogeneousProcessor,
+    log: DAGSetSchedulerLog,
+    current_time: i32,
+    table: Vec<StaticScheduleEntry>,
+}
+
+impl DAGSetSchedulerBase<HomogeneousProcessor> for StaticScheduleExecutor {
+    fn new(dag_set: &[Graph<NodeData, i32>], processor: &HomogeneousProcessor) -> Self {
+        Self {
+            node_index_maps: get_node_index_maps(dag_set),
+            dag_set: dag_set.to_vec(),
+            processor: processor.clone(),
+            log: DAGSetSchedulerLog::new(dag_set, processor.get_number_of_cores()),
+            current_time: 0,
+            table: Vec::new(),
+        }
+    }
+
+    getset_dag_set_scheduler!(HomogeneousProcessor);
+
+    /// The preemptive type is ignored because the table decides when each node runs.
+    fn schedule(&mut self, _preemptive_type: PreemptiveType) -> i32 {
+        clear_interrupted();
+        self.log.write_schedule_start();
+        let mut managers = vec![DAGStateManager::default(); self.dag_set.len()];
+        let mut ready_nodes: Vec<NodeData> = Vec::new();
+        let table = self.table.clone();
+        let mut next_entry_i = 0;
+
+        let hyper_period = get_hyper_period(&self.dag_set);
+        while self.current_time < hyper_period && !is_interrupted() {
+            // Release DAGs
+            ready_nodes.extend(self.release_dags(&mut managers));
+
+            // Start the entries of the current time.
+            while let Some(entry) = table
+                .get(next_entry_i)
+                .filter(|entry| entry.start_time == self.current_time)
+            {
+                let node_data = self.take_ready_node(entry, &managers, &mut ready_nodes);
+                self.allocate_node(
+                    &node_data,
+                    entry.core_id,
+                    managers[entry.dag_id].get_release_count() as usize,
+                );
+                next_entry_i += 1;
+            }
+
+            // Process unit time
+            let process_result = self.process_unit_time();
+            let indices: Vec<usize> = get_process_core_indices(&process_result);
+            self.log.write_processing_time(&indices);
+
+            // Post-process on completion of node execution
+            for (core_id, result) in process_result.iter().enumerate() {
+                if let ProcessResult::Done(node_data) = result {
+                    ready_nodes.extend(self.post_process_on_node_completion(
+                        node_data,
+                        core_id,
+                        &mut managers,
+                    ));
+                }
+            }
+        }
+        if next_entry_i < table.len() && !is_interrupted() {
+            warn!(
+                "{} entries of the static schedule start at or after the hyper period {} and are not replayed.",
+                table.len() - next_entry_i,
+                hyper_period
+            );
+        }
+
+        self.calculate_log();
+        self.get_current_time()
+    }
+}
+
+impl StaticScheduleExecutor {
+    /// Replace the table to replay. The entries may be in any order.
+    pub fn set_table(&mut self, mut table: Vec<StaticScheduleEntry>) {
+        for entry in table.iter() {
+            if entry.dag_id >= self.dag_set.len() {
+                panic!("DAG {} does not exist.", entry.dag_id);
+            }
+            if entry.core_id >= self.processor.get_number_of_cores() {
+                panic!("Core {} does not exist.", entry.core_id);
+            }
+            if entry.start_time < 0 {
+                panic!(
+                    "Node {} of job {} of DAG {} starts at the negative time {}.",
+                    entry.node_id, entry.job_id, entry.dag_id, entry.start_time
+                );
+            }
+        }
+        table.sort_by_key(|entry| (entry.start_time, entry.core_id));
+        self.table = table;
+    }
+
+    /// Remove the node of `entry` from `ready_nodes` after checking that it can start now.
+    fn take_ready_node(
+        &self,
+        entry: &StaticScheduleEntry,
+        managers: &[DAGStateManager],
+        ready_nodes: &mut Vec<NodeData>,
+    ) -> NodeData {
+        let release_count = managers[entry.dag_id].get_release_count() as usize;
+        if entry.job_id >= release_count {
+            panic!(
+                "Node {} of job {} of DAG {} starts at {} before the job is released.",
+                entry.node_id, entry.job_id, entry.dag_id, self.current_time
+            );
+        }
+        let position = ready_nodes.iter().position(|node_data| {
+            node_data.get_params_value("dag_id") as usize == entry.dag_id
+                && node_data.get_id() == entry.node_id
+        });
+        let Some(position) = position.filter(|_| entry.job_id == release_count - 1) else {
+            panic!(
+                "Node {} of job {} of DAG {} starts at {} but is not ready. Its predecessors have not completed or it already ran.",
+                entry.node_id, entry.job_id, entry.dag_id, self.current_time
+            );
+        };
+        if !self
+            .processor
+            .get_idle_core_indices()
+            .contains(&entry.core_id)
+        {
+            panic!(
+                "Node {} of job {} of DAG {} starts at {} on the busy core {}.",
+                entry.node_id, entry.job_id, entry.dag_id, self.current_time, entry.core_id
+            );
+        }
+        ready_nodes.remove(position)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph_extension::GraphExtension;
+    use crate::test_util::create_node;
+
+    fn create_sample_dag() -> Graph<NodeData, i32> {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 2));
+        let n1 = dag.add_node(create_node(1, "execution_time", 3));
+        let n2 = dag.add_node(create_node(2, "execution_time", 1));
+        let n3 = dag.add_node(create_node(3, "execution_time", 1));
+        dag.add_param(n0, "period", 10);
+        dag.add_param(n3, "end_to_end_deadline", 10);
+        dag.add_edge(n0, n1, 1);
+        dag.add_edge(n0, n2, 1);
+        dag.add_edge(n1, n3, 1);
+        dag.add_edge(n2, n3, 1);
+        dag.set_dag_param("dag_id", 0);
+        dag
+    }
+
+    fn create_entry(node_id: i32, core_id: usize, start_time: i32) -> StaticScheduleEntry {
+        StaticScheduleEntry {
+            dag_id: 0,
+            node_id,
+            job_id: 0,
+            core_id,
+            start_time,
+        }
+    }
+
+    #[test]
+    fn test_static_schedule_executor_normal() {
+        let mut executor =
+            StaticScheduleExecutor::new(&[create_sample_dag()], &HomogeneousProcessor::new(2));
+        // n2 waits on core 1 until 3 although it is ready at 2.
+        executor.set_table(vec![
+            create_entry(3, 0, 5),
+            create_entry(2, 1, 3),
+            create_entry(1, 0, 2),
+            create_entry(0, 0, 0),
+        ]);
+        assert_eq!(executor.schedule(PreemptiveType::NonPreemptive), 10);
+
+        let log = executor.get_log_mut();
+        assert_eq!(log.get_worst_response_time(0), 6);
+        let mut intervals: Vec<(usize, usize, i32, i32)> = log
+            .get_execution_intervals()
+            .into_iter()
+            .map(|interval| {
+                (
+                    interval.node_id,
+                    interval.core_id,
+                    interval.begin_time,
+                    interval.end_time,
+                )
+            })
+            .collect();
+        intervals.sort();
+        assert_eq!(
+            intervals,
+            vec![(0, 0, 0, 2), (1, 0, 2, 5), (2, 1, 3, 4), (3, 0, 5, 6)]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Node 3 of job 0 of DAG 0 starts at 4 but is not ready.")]
+    fn test_static_schedule_executor_precedence_violation() {
+        let mut executor =
+            StaticScheduleExecutor::new(&[create_sample_dag()], &HomogeneousProcessor::new(2));
+        executor.set_table(vec![
+            create_entry(0, 0, 0),
+            create_entry(1, 0, 2),
+            create_entry(2, 1, 2),
+            create_entry(3, 1, 4),
+        ]);
+        executor.schedule(PreemptiveType::NonPreemptive);
+    }
+
+    #[test]
+    #[cfg(feature = "yaml")]
+    fn test_load_static_schedule_normal() {
+        let file_path = "../lib/tests/static_schedule_test.yaml";
+        std::fs::write(
+            file_path,
+            "- {dag_id: 0, node_id: 1, job_id: 2, core_id: 1, start_time: 4}\n- {dag_id: 1, node_id: 0, core_id: 0, start_time: 0}\n",
+        )
+        .unwrap();
+        let table = load_static_schedule(file_path);
+        std::fs::remove_file(file_path).unwrap();
+
+        assert_eq!(
+            table,
+            vec![
+                StaticScheduleEntry {
+                    dag_id: 0,
+                    node_id: 1,
+                    job_id: 2,
+                    core_id: 1,
+                    start_time: 4,
+                },
+                StaticScheduleEntry {
+                    dag_id: 1,
+                    node_id: 0,
+                    job_id: 0,
+                    core_id: 0,
+                    start_time: 0,
+                },
+            ]
+        );
+    }
+}
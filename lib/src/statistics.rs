@@ -0,0 +1,217 @@
+//! Paired statistical tests, to back claims that one algorithm improves on another over the same
+//! DAG sets without external tooling.
+use serde_derive::{Deserialize, Serialize};
+
+/// Above this number of nonzero differences, the p-value is approximated by the normal
+/// distribution instead of the exact distribution of the rank sum.
+const MAX_EXACT_PAIRS: usize = 25;
+
+/// Wilcoxon signed-rank test of whether paired differences are centered on zero.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WilcoxonSignedRankTest {
+    pub pairs: usize,
+    /// Pairs with a nonzero difference, which are the ranked ones.
+    pub nonzero_pairs: usize,
+    pub positive_rank_sum: f64,
+    pub negative_rank_sum: f64,
+    /// Two-sided p-value.
+    pub p_value: f64,
+    /// Whether the p-value is exact rather than the normal approximation.
+    pub is_exact: bool,
+    /// Matched-pairs rank-biserial correlation in [-1, 1], positive if the differences tend to be
+    /// positive.
+    pub rank_biserial_correlation: f64,
+    pub median_difference: f64,
+}
+
+/// Ranks of `values` from 1, with the average rank for ties.
+fn get_average_ranks(values: &[f64]) -> Vec<f64> {
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_by(|&a, &b| values[a].total_cmp(&values[b]));
+    let mut ranks = vec![0.0; values.len()];
+    let mut start = 0;
+    while start < order.len() {
+        let mut end = start + 1;
+        while end < order.len() && values[order[end]] == values[order[start]] {
+            end += 1;
+        }
+        // Ranks start + 1 to end share their average.
+        let average_rank = (start + 1 + end) as f64 / 2.0;
+        for &i in &order[start..end] {
+            ranks[i] = average_rank;
+        }
+        start = end;
+    }
+    ranks
+}
+
+fn get_median(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let middle = sorted.len() / 2;
+    if sorted.len().is_multiple_of(2) {
+        (sorted[middle - 1] + sorted[middle]) / 2.0
+    } else {
+        sorted[middle]
+    }
+}
+
+/// Complementary error function with a relative error below 1.2e-7.
+fn erfc(x: f64) -> f64 {
+    let t = 1.0 / (1.0 + 0.5 * x.abs());
+    let polynomial = [
+        -1.26551223,
+        1.00002368,
+        0.37409196,
+        0.09678418,
+        -0.18628806,
+        0.27886807,
+        -1.13520398,
+        1.48851587,
+        -0.82215223,
+        0.17087277,
+    ]
+    .iter()
+    .rev()
+    .fold(0.0, |acc, coefficient| coefficient + t * acc);
+    let value = t * (-x * x + polynomial).exp();
+    if x >= 0.0 {
+        value
+    } else {
+        2.0 - value
+    }
+}
+
+/// Exact two-sided p-value of the positive rank sum, enumerating the signs of the ranks.
+/// Ranks are doubled so that the average ranks of ties are integers.
+fn get_exact_p_value(ranks: &[f64], positive_rank_sum: f64) -> f64 {
+    let doubled_ranks: Vec<usize> = ranks.iter().map(|rank| (rank * 2.0) as usize).collect();
+    let total: usize = doubled_ranks.iter().sum();
+    // Number of sign assignments for each doubled positive rank sum.
+    let mut counts = vec![0.0; total + 1];
+    counts[0] = 1.0;
+    for &doubled_rank in &doubled_ranks {
+        for sum in (doubled_rank..=total).rev() {
+            counts[sum] += counts[sum - doubled_rank];
+        }
+    }
+    let observed = (positive_rank_sum * 2.0) as usize;
+    let assignments = 2f64.powi(ranks.len() as i32);
+    let lower: f64 = counts[..=observed].iter().sum::<f64>() / assignments;
+    let upper: f64 = counts[observed..].iter().sum::<f64>() / assignments;
+    (2.0 * lower.min(upper)).min(1.0)
+}
+
+/// Two-sided p-value by the normal approximation with the tie and continuity corrections.
+fn get_normal_p_value(ranks: &[f64], positive_rank_sum: f64) -> f64 {
+    let n = ranks.len() as f64;
+    let mean = n * (n + 1.0) / 4.0;
+    let mut tie_correction = 0.0;
+    let mut sorted_ranks = ranks.to_vec();
+    sorted_ranks.sort_by(f64::total_cmp);
+    for tie in sorted_ranks.chunk_by(|a, b| a == b) {
+        let t = tie.len() as f64;
+        tie_correction += t * t * t - t;
+    }
+    let variance = n * (n + 1.0) * (2.0 * n + 1.0) / 24.0 - tie_correction / 48.0;
+    if variance <= 0.0 {
+        return 1.0;
+    }
+    let deviation = positive_rank_sum - mean;
+    let z = (deviation.abs() - 0.5).max(0.0) / variance.sqrt();
+    erfc(z / std::f64::consts::SQRT_2).min(1.0)
+}
+
+/// Test the paired `differences`, e.g., candidate minus baseline over the same DAG sets.
+/// Zero differences are dropped before ranking.
+pub fn wilcoxon_signed_rank_test(differences: &[f64]) -> WilcoxonSignedRankTest {
+    let nonzero_differences: Vec<f64> = differences
+        .iter()
+        .copied()
+        .filter(|&difference| difference != 0.0)
+        .collect();
+    let absolute_differences: Vec<f64> = nonzero_differences.iter().map(|d| d.abs()).collect();
+    let ranks = get_average_ranks(&absolute_differences);
+    let mut positive_rank_sum = 0.0;
+    let mut negative_rank_sum = 0.0;
+    for (difference, rank) in nonzero_differences.iter().zip(ranks.iter()) {
+        if *difference > 0.0 {
+            positive_rank_sum += rank;
+        } else {
+            negative_rank_sum += rank;
+        }
+    }
+
+    let is_exact = ranks.len() <= MAX_EXACT_PAIRS;
+    let (p_value, rank_biserial_correlation) = if ranks.is_empty() {
+        (1.0, 0.0)
+    } else {
+        let p_value = if is_exact {
+            get_exact_p_value(&ranks, positive_rank_sum)
+        } else {
+            get_normal_p_value(&ranks, positive_rank_sum)
+        };
+        (
+            p_value,
+            (positive_rank_sum - negative_rank_sum) / (positive_rank_sum + negative_rank_sum),
+        )
+    };
+    WilcoxonSignedRankTest {
+        pairs: differences.len(),
+        nonzero_pairs: ranks.len(),
+        positive_rank_sum,
+        negative_rank_sum,
+        p_value,
+        is_exact,
+        rank_biserial_correlation,
+        median_difference: get_median(differences),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wilcoxon_signed_rank_test_normal() {
+        let test = wilcoxon_signed_rank_test(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+        assert_eq!(test.positive_rank_sum, 15.0);
+        assert_eq!(test.negative_rank_sum, 0.0);
+        // Only 1 of the 32 sign assignments is as extreme on each side.
+        assert_eq!(test.p_value, 0.0625);
+        assert!(test.is_exact);
+        assert_eq!(test.rank_biserial_correlation, 1.0);
+        assert_eq!(test.median_difference, 3.0);
+    }
+
+    #[test]
+    fn test_wilcoxon_signed_rank_test_ties_and_zeros() {
+        let test = wilcoxon_signed_rank_test(&[-1.0, 2.0, -2.0, 0.0, 3.0]);
+        assert_eq!(test.pairs, 5);
+        assert_eq!(test.nonzero_pairs, 4);
+        // The ranks of 1, 2, 2 and 3 are 1, 2.5, 2.5 and 4.
+        assert_eq!(test.positive_rank_sum, 6.5);
+        assert_eq!(test.negative_rank_sum, 3.5);
+        assert_eq!(test.p_value, 0.75);
+        assert_eq!(test.rank_biserial_correlation, 0.3);
+        assert_eq!(test.median_difference, 0.0);
+
+        let test = wilcoxon_signed_rank_test(&[0.0, 0.0]);
+        assert_eq!(test.nonzero_pairs, 0);
+        assert_eq!(test.p_value, 1.0);
+    }
+
+    #[test]
+    fn test_wilcoxon_signed_rank_test_normal_approximation() {
+        let differences: Vec<f64> = (1..=30).map(|d| -d as f64).collect();
+        let test = wilcoxon_signed_rank_test(&differences);
+        assert!(!test.is_exact);
+        assert_eq!(test.negative_rank_sum, 465.0);
+        assert_eq!(test.rank_biserial_correlation, -1.0);
+        // z = (465 - 232.5 - 0.5) / sqrt(2363.75) = 4.77
+        assert!(test.p_value > 1.0e-6 && test.p_value < 3.0e-6);
+    }
+}
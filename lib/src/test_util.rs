@@ -0,0 +1,10 @@
+//! Helpers shared by the unit tests.
+use crate::graph_extension::NodeData;
+use std::collections::BTreeMap;
+
+/// A node with the single param `key: value`.
+pub fn create_node(id: i32, key: &str, value: i32) -> NodeData {
+    let mut params = BTreeMap::new();
+    params.insert(key.to_string(), value);
+    NodeData { id, params }
+}
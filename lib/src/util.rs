@@ -1,26 +1,101 @@
 use crate::{
     core::ProcessResult,
-    graph_extension::{GraphExtension, NodeData},
+    graph_extension::{GraphExtension, NodeData, ReleaseSource},
 };
+#[cfg(feature = "yaml")]
 use chrono::{DateTime, Utc};
 use log::{info, warn};
 use num_integer::lcm;
 use petgraph::graph::Graph;
 use std::{
+    collections::BTreeSet,
     fs::{self, OpenOptions},
     io::Write,
+    sync::atomic::{AtomicBool, Ordering},
 };
+#[cfg(feature = "yaml")]
 use yaml_rust::YamlLoader;
 
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+/// Install a ctrl-c handler so that the schedulers stop at the end of the current time unit
+/// and the log of the simulated prefix can still be dumped. The interrupt only stops the current
+/// simulation, as each `schedule` clears it on start (see `clear_interrupted`).
+#[cfg(feature = "interrupt_handler")]
+pub fn install_interrupt_handler() {
+    if let Err(err) = ctrlc::set_handler(|| INTERRUPTED.store(true, Ordering::SeqCst)) {
+        warn!("Failed to install the ctrl-c handler: {}", err);
+    }
+}
+
+pub fn is_interrupted() -> bool {
+    INTERRUPTED.load(Ordering::SeqCst)
+}
+
+/// Clear the interrupt of a former simulation, e.g., before the reruns of a search.
+pub fn clear_interrupted() {
+    INTERRUPTED.store(false, Ordering::SeqCst);
+}
+
+/// Peak resident set size of this process in KiB (`VmHWM`). `None` if not available (non-Linux).
+pub fn get_peak_rss_kb() -> Option<u64> {
+    fs::read_to_string("/proc/self/status")
+        .ok()?
+        .lines()
+        .find_map(|line| line.strip_prefix("VmHWM:"))?
+        .trim()
+        .trim_end_matches("kB")
+        .trim()
+        .parse()
+        .ok()
+}
+
 pub fn get_hyper_period(dag_set: &[Graph<NodeData, i32>]) -> i32 {
     let mut hyper_period = 1;
-    for dag in dag_set {
+    // Triggered DAGs follow the periods of the DAGs triggering them.
+    for dag in dag_set
+        .iter()
+        .filter(|dag| dag.get_release_source() == ReleaseSource::Periodic)
+    {
         let dag_period = dag.get_head_period().unwrap();
         hyper_period = lcm(hyper_period, dag_period);
     }
     hyper_period
 }
 
+/// Split the hyper period into deadline partitions.
+///
+/// The boundaries of the partitions are all release times and absolute deadlines of the jobs in the
+/// hyper period, so that no job is released or reaches its deadline inside a partition.
+///
+/// # Returns
+///
+/// * The partitions as `(start, end)` in time order.
+pub fn get_deadline_partitions(dag_set: &[Graph<NodeData, i32>]) -> Vec<(i32, i32)> {
+    let hyper_period = get_hyper_period(dag_set);
+    let mut boundaries = BTreeSet::from([0, hyper_period]);
+    for dag in dag_set
+        .iter()
+        .filter(|dag| dag.get_release_source() == ReleaseSource::Periodic)
+    {
+        let period = dag.get_head_period().unwrap();
+        let end_to_end_deadline = dag.get_end_to_end_deadline().unwrap_or(period);
+        let mut release_time = dag.get_head_offset();
+        while release_time < hyper_period {
+            boundaries.insert(release_time);
+            if release_time + end_to_end_deadline < hyper_period {
+                boundaries.insert(release_time + end_to_end_deadline);
+            }
+            release_time += period;
+        }
+    }
+    boundaries
+        .iter()
+        .zip(boundaries.iter().skip(1))
+        .map(|(&start, &end)| (start, end))
+        .collect()
+}
+
 pub fn adjust_to_implicit_deadline(dag_set: &mut [Graph<NodeData, i32>]) {
     for dag in dag_set.iter_mut() {
         let period = dag.get_head_period();
@@ -29,11 +104,9 @@ pub fn adjust_to_implicit_deadline(dag_set: &mut [Graph<NodeData, i32>]) {
             (Some(period_value), Some(_)) => {
                 if end_to_end_deadline != period {
                     warn!("In this algorithm, the period and the end-to-end deadline must be equal. Therefore, the end-to-end deadline is overridden by the period.");
-                    dag.get_sink_nodes().iter().for_each(|&sink_i| {
-                        if dag[sink_i].params.get("end_to_end_deadline").is_some() {
-                            dag.update_param(sink_i, "end_to_end_deadline", period_value);
-                        }
-                    });
+                    for (node_i, _) in dag.get_dag_param_values("end_to_end_deadline") {
+                        dag.update_param(node_i, "end_to_end_deadline", period_value);
+                    }
                 }
             }
             (None, Some(deadline_value)) => {
@@ -49,6 +122,30 @@ pub fn adjust_to_implicit_deadline(dag_set: &mut [Graph<NodeData, i32>]) {
     }
 }
 
+/// Check that every node of each DAG has the position of the DAG in the DAGSet as `dag_id`.
+///
+/// # Panics
+///
+/// If a `dag_id` is missing, collides with another DAG or does not match the position.
+pub fn validate_dag_ids(dag_set: &[Graph<NodeData, i32>]) {
+    for (dag_position, dag) in dag_set.iter().enumerate() {
+        for node_data in dag.node_weights() {
+            match node_data.params.get("dag_id") {
+                Some(&dag_id) if dag_id == dag_position as i32 => {}
+                Some(&dag_id) => panic!(
+                    "Node {} of the DAG at position {} has dag_id {}.",
+                    node_data.id, dag_position, dag_id
+                ),
+                None => panic!(
+                    "Node {} of the DAG at position {} has no dag_id.",
+                    node_data.id, dag_position
+                ),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "yaml")]
 pub fn load_yaml(file_path: &str) -> Vec<yaml_rust::Yaml> {
     if !file_path.ends_with(".yaml") && !file_path.ends_with(".yml") {
         panic!("Invalid file type: {}", file_path);
@@ -58,12 +155,7 @@ pub fn load_yaml(file_path: &str) -> Vec<yaml_rust::Yaml> {
 }
 
 pub fn append_info_to_yaml(file_path: &str, info: &str) {
-    if let Ok(mut file) = OpenOptions::new()
-        .write(true)
-        .append(true)
-        .create(true)
-        .open(file_path)
-    {
+    if let Ok(mut file) = OpenOptions::new().append(true).create(true).open(file_path) {
         if let Err(err) = file.write_all(info.as_bytes()) {
             eprintln!("Failed to write to file: {}", err);
         }
@@ -72,23 +164,34 @@ pub fn append_info_to_yaml(file_path: &str, info: &str) {
     }
 }
 
+#[cfg(feature = "yaml")]
 pub fn create_yaml(folder_path: &str, file_name: &str) -> String {
+    create_file(folder_path, file_name, "yaml")
+}
+
+pub fn create_file(folder_path: &str, file_name: &str, extension: &str) -> String {
     if fs::metadata(folder_path).is_err() {
         let _ = fs::create_dir_all(folder_path);
         info!("Created folder: {}", folder_path);
     }
-    let file_path = format!("{}/{}.yaml", folder_path, file_name);
+    let file_path = format!("{}/{}.{}", folder_path, file_name, extension);
     if let Err(err) = fs::File::create(&file_path) {
         warn!("Failed to create file: {}", err);
     }
     file_path
 }
 
+#[cfg(feature = "yaml")]
 pub fn create_scheduler_log_yaml(dir_path: &str, alg_name: &str) -> String {
+    create_scheduler_log_file(dir_path, alg_name, "yaml")
+}
+
+#[cfg(feature = "yaml")]
+pub fn create_scheduler_log_file(dir_path: &str, alg_name: &str, extension: &str) -> String {
     let now: DateTime<Utc> = Utc::now();
     let date = now.format("%Y-%m-%d-%H-%M-%S-%3f").to_string();
     let file_name = format!("{}-{}-log", date, alg_name);
-    create_yaml(dir_path, &file_name)
+    create_file(dir_path, &file_name, extension)
 }
 
 pub fn get_process_core_indices(process_result: &[ProcessResult]) -> Vec<usize> {
@@ -105,9 +208,20 @@ pub fn get_process_core_indices(process_result: &[ProcessResult]) -> Vec<usize>
         .collect()
 }
 
+/// Sum the values in ascending order with a double-precision accumulator.
+///
+/// The result does not depend on the order of the values (e.g., of the DAGs in a DAG set), so
+/// utilizations summed with it are reproducible across DAG orders, thread counts and platforms.
+pub fn sum_in_fixed_order(values: impl IntoIterator<Item = f32>) -> f32 {
+    let mut values: Vec<f32> = values.into_iter().collect();
+    values.sort_by(f32::total_cmp);
+    values.iter().map(|&value| value as f64).sum::<f64>() as f32
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_util::create_node;
     use std::collections::BTreeMap;
 
     fn create_dag() -> Graph<NodeData, i32> {
@@ -179,6 +293,18 @@ mod tests {
         assert_eq!(get_hyper_period(&dag_set), 120);
     }
 
+    #[test]
+    fn test_get_deadline_partitions_normal() {
+        let dag_set = vec![
+            create_dag_with_period(10),
+            create_dag_with_period_and_deadline(15, 8),
+        ];
+        assert_eq!(
+            get_deadline_partitions(&dag_set),
+            vec![(0, 8), (8, 10), (10, 15), (15, 20), (20, 23), (23, 30)]
+        );
+    }
+
     #[test]
     fn test_adjust_to_implicit_deadline_with_same_period_and_deadline() {
         let mut dag_set = vec![create_dag_with_period_and_deadline(10, 10)];
@@ -218,13 +344,18 @@ mod tests {
         adjust_to_implicit_deadline(&mut dag_set);
     }
 
+    #[test]
+    fn test_sum_in_fixed_order_normal() {
+        let values = [0.1, 1e8, 0.2, -1e8, 0.3];
+        let mut reversed = values;
+        reversed.reverse();
+        assert_eq!(sum_in_fixed_order(values), sum_in_fixed_order(reversed));
+        assert_eq!(sum_in_fixed_order([0.25, 0.5]), 0.75);
+        assert_eq!(sum_in_fixed_order([]), 0.0);
+    }
+
     #[test]
     fn test_get_process_core_indices_normal() {
-        fn create_node(id: i32, key: &str, value: i32) -> NodeData {
-            let mut params = BTreeMap::new();
-            params.insert(key.to_string(), value);
-            NodeData { id, params }
-        }
         let process_result = vec![
             ProcessResult::Continue,
             ProcessResult::Done(create_node(0, "dummy", -1)),
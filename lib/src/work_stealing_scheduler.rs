@@ -0,0 +1,241 @@
+//! Work-stealing runtime, as in the task-parallel runtimes executing DAGs.
+//!
+//! Each core has a deque of ready nodes. A core pushes the nodes made ready by its completions to
+//! the bottom of its deque and pops its next node from the bottom. A core with an empty deque
+//! tries to steal the top node of the deque of a random other core, which takes `steal_latency`
+//! time units and fails if that deque is empty by then. Released source nodes are pushed to the
+//! shortest deque, and idle cores park instead of stealing while all the deques are empty.
+use crate::{
+    core::ProcessResult,
+    dag_set_scheduler::{
+        DAGSetSchedulerBase, DAGStateManager, DAGStateManagerBase, PreemptiveType,
+    },
+    getset_dag_set_scheduler,
+    graph_extension::{get_node_index_maps, NodeData, NodeIndexMap},
+    homogeneous::HomogeneousProcessor,
+    log::{CoreStealLog, DAGSetSchedulerLog},
+    processor::ProcessorBase,
+    util::{clear_interrupted, get_hyper_period, get_process_core_indices, is_interrupted},
+};
+use petgraph::Graph;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::collections::VecDeque;
+
+pub struct WorkStealingScheduler {
+    dag_set: Vec<Graph<NodeData, i32>>,
+    node_index_maps: Vec<NodeIndexMap>,
+    processor: HomogeneousProcessor,
+    log: DAGSetSchedulerLog,
+    current_time: i32,
+    steal_latency: i32,
+    /// Chooses the victims of the steal attempts.
+    rng: StdRng,
+}
+
+impl DAGSetSchedulerBase<HomogeneousProcessor> for WorkStealingScheduler {
+    fn new(dag_set: &[Graph<NodeData, i32>], processor: &HomogeneousProcessor) -> Self {
+        Self {
+            node_index_maps: get_node_index_maps(dag_set),
+            dag_set: dag_set.to_vec(),
+            processor: processor.clone(),
+            log: DAGSetSchedulerLog::new(dag_set, processor.get_number_of_cores()),
+            current_time: 0,
+            steal_latency: 0,
+            rng: StdRng::seed_from_u64(0),
+        }
+    }
+
+    getset_dag_set_scheduler!(HomogeneousProcessor);
+
+    /// The preemptive type is ignored because the nodes run to completion on their core.
+    fn schedule(&mut self, _preemptive_type: PreemptiveType) -> i32 {
+        clear_interrupted();
+        self.log.write_schedule_start();
+        let number_of_cores = self.processor.get_number_of_cores();
+        let mut managers = vec![DAGStateManager::default(); self.dag_set.len()];
+        let mut deques: Vec<VecDeque<NodeData>> = vec![VecDeque::new(); number_of_cores];
+        // Victim and remaining time of the steal attempt of each core.
+        let mut steal_attempts: Vec<Option<(usize, i32)>> = vec![None; number_of_cores];
+        let mut steal_logs: Vec<CoreStealLog> = (0..number_of_cores)
+            .map(|core_id| CoreStealLog {
+                core_id,
+                ..Default::default()
+            })
+            .collect();
+
+        let hyper_period = get_hyper_period(&self.dag_set);
+        while self.current_time < hyper_period && !is_interrupted() {
+            // Release DAGs
+            for node_data in self.release_dags(&mut managers) {
+                let shortest_deque = deques.iter_mut().min_by_key(|deque| deque.len()).unwrap();
+                shortest_deque.push_back(node_data);
+            }
+
+            for core_i in self.processor.get_idle_core_indices() {
+                if steal_attempts[core_i].is_none() {
+                    if let Some(node_data) = take_node(&mut deques[core_i], true, |node_data| {
+                        self.can_allocate_node(node_data)
+                    }) {
+                        let dag_id = node_data.get_params_value("dag_id") as usize;
+                        self.allocate_node(
+                            &node_data,
+                            core_i,
+                            managers[dag_id].get_release_count() as usize,
+                        );
+                        continue;
+                    }
+                    if number_of_cores == 1 || deques.iter().all(VecDeque::is_empty) {
+                        continue;
+                    }
+                    // Any core but itself
+                    let mut victim = self.rng.gen_range(0..number_of_cores - 1);
+                    if victim >= core_i {
+                        victim += 1;
+                    }
+                    steal_logs[core_i].attempts += 1;
+                    steal_attempts[core_i] = Some((victim, self.steal_latency));
+                }
+
+                // Complete the steal attempt whose latency has elapsed.
+                let Some((victim, 0)) = steal_attempts[core_i] else {
+                    continue;
+                };
+                steal_attempts[core_i] = None;
+                if let Some(node_data) = take_node(&mut deques[victim], false, |node_data| {
+                    self.can_allocate_node(node_data)
+                }) {
+                    steal_logs[core_i].steals += 1;
+                    steal_logs[victim].stolen_from += 1;
+                    let dag_id = node_data.get_params_value("dag_id") as usize;
+                    self.allocate_node(
+                        &node_data,
+                        core_i,
+                        managers[dag_id].get_release_count() as usize,
+                    );
+                }
+            }
+
+            // The cores in a steal attempt spend the time unit on it.
+            for (core_i, steal_attempt) in steal_attempts.iter_mut().enumerate() {
+                if let Some((_, remaining_time)) = steal_attempt {
+                    *remaining_time -= 1;
+                    steal_logs[core_i].steal_time += 1;
+                }
+            }
+
+            // Process unit time
+            let process_result = self.process_unit_time();
+            let indices: Vec<usize> = get_process_core_indices(&process_result);
+            self.log.write_processing_time(&indices);
+
+            // Post-process on completion of node execution
+            for (core_id, result) in process_result.iter().enumerate() {
+                if let ProcessResult::Done(node_data) = result {
+                    let ready_nodes =
+                        self.post_process_on_node_completion(node_data, core_id, &mut managers);
+                    deques[core_id].extend(ready_nodes);
+                }
+            }
+        }
+
+        self.log.write_steal_logs(steal_logs);
+        self.calculate_log();
+        self.get_current_time()
+    }
+}
+
+impl WorkStealingScheduler {
+    pub fn set_steal_latency(&mut self, steal_latency: i32) {
+        if steal_latency < 0 {
+            panic!("The steal latency must not be negative.");
+        }
+        self.steal_latency = steal_latency;
+    }
+
+    pub fn set_seed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+}
+
+/// Remove the node nearest to the bottom (or the top) of `deque` accepted by `can_allocate`.
+fn take_node(
+    deque: &mut VecDeque<NodeData>,
+    from_bottom: bool,
+    can_allocate: impl Fn(&NodeData) -> bool,
+) -> Option<NodeData> {
+    let position = if from_bottom {
+        deque.iter().rposition(&can_allocate)
+    } else {
+        deque.iter().position(&can_allocate)
+    }?;
+    deque.remove(position)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph_extension::GraphExtension;
+    use crate::test_util::create_node;
+
+    fn create_fork_join_dag() -> Graph<NodeData, i32> {
+        let mut dag = Graph::<NodeData, i32>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 1));
+        let n1 = dag.add_node(create_node(1, "execution_time", 2));
+        let n2 = dag.add_node(create_node(2, "execution_time", 2));
+        let n3 = dag.add_node(create_node(3, "execution_time", 2));
+        let n4 = dag.add_node(create_node(4, "execution_time", 1));
+        dag.add_param(n0, "period", 20);
+        dag.add_param(n4, "end_to_end_deadline", 20);
+        for fork_i in [n1, n2, n3] {
+            dag.add_edge(n0, fork_i, 1);
+            dag.add_edge(fork_i, n4, 1);
+        }
+        dag.set_dag_param("dag_id", 0);
+        dag
+    }
+
+    #[test]
+    fn test_work_stealing_scheduler_normal() {
+        let mut scheduler =
+            WorkStealingScheduler::new(&[create_fork_join_dag()], &HomogeneousProcessor::new(2));
+        scheduler.set_steal_latency(1);
+        assert_eq!(scheduler.schedule(PreemptiveType::NonPreemptive), 20);
+
+        // Core 1 parks until the fork at 1 and steals a node from core 0 until 2.
+        let log = scheduler.get_log_mut();
+        assert_eq!(log.get_worst_response_time(0), 6);
+        assert_eq!(
+            log.get_steal_logs(),
+            &[
+                CoreStealLog {
+                    core_id: 0,
+                    attempts: 0,
+                    steals: 0,
+                    steal_time: 0,
+                    stolen_from: 1,
+                },
+                CoreStealLog {
+                    core_id: 1,
+                    attempts: 1,
+                    steals: 1,
+                    steal_time: 1,
+                    stolen_from: 0,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_work_stealing_scheduler_steal_latency() {
+        let mut scheduler =
+            WorkStealingScheduler::new(&[create_fork_join_dag()], &HomogeneousProcessor::new(2));
+        scheduler.set_steal_latency(3);
+        scheduler.schedule(PreemptiveType::NonPreemptive);
+
+        // Core 1 steals the last forked node at 4, after core 0 started the second one at 3.
+        let log = scheduler.get_log_mut();
+        assert_eq!(log.get_worst_response_time(0), 7);
+        assert_eq!(log.get_steal_logs()[1].steal_time, 3);
+        assert_eq!(log.get_steal_logs()[1].steals, 1);
+    }
+}
@@ -0,0 +1,93 @@
+//! Shared binary running each algorithm as a subcommand, e.g., `sched_sim gedf -d <dir> -c 4`.
+//!
+//! Without a subcommand, the arguments are those of `federated`,
+//! the algorithm of the former `sched_sim` binary.
+use clap::{CommandFactory, Parser, Subcommand};
+use lib::cli_config::expand_config_args;
+
+#[derive(Parser)]
+#[clap(
+    name = "sched_sim",
+    version = "1.0",
+    about = "Simulate the scheduling algorithms of DAG sets."
+)]
+struct Cli {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    ///Global EDF (ECRTS 2013).
+    Gedf(ecrts_gedf::ArgParser),
+    ///Federated scheduling (ECRTS 2014).
+    Federated(federated::AppArg),
+    ///Decomposition-based algorithm (TPDS 2014).
+    Decomp(basic_decomposition::ArgParser),
+    ///Semi-federated scheduling (RTSS 2017).
+    SemiFederated(rtss_semi_federated::ArgParser),
+    ///CPC model based algorithm (RTSS 2020).
+    Cpc(rtss_cpc::ArgParser),
+    ///Dynamic federated scheduling (RTCSA 2021).
+    Dynfed(rtcsa_dynfed::ArgParser),
+}
+
+/// Insert `federated` if the first argument is no subcommand, and the arguments of the file
+/// given by `--config` after the subcommand.
+fn normalize_args(mut args: Vec<String>) -> Vec<String> {
+    let command = Cli::command();
+    let is_subcommand_or_top_level_flag = args.get(1).is_none_or(|arg| {
+        command.find_subcommand(arg).is_some()
+            || ["help", "-h", "--help", "-V", "--version"].contains(&arg.as_str())
+    });
+    if !is_subcommand_or_top_level_flag {
+        args.insert(1, "federated".to_string());
+    }
+    if args.len() < 2 {
+        return args;
+    }
+    let subcommand_args = args.split_off(1);
+    args.extend(expand_config_args(subcommand_args));
+    args
+}
+
+fn main() {
+    let cli = Cli::parse_from(normalize_args(std::env::args().collect()));
+    match cli.command {
+        Command::Gedf(arg) => ecrts_gedf::run(arg),
+        Command::Federated(arg) => federated::run(arg),
+        Command::Decomp(arg) => basic_decomposition::run(arg),
+        Command::SemiFederated(arg) => rtss_semi_federated::run(arg),
+        Command::Cpc(arg) => rtss_cpc::run(arg),
+        Command::Dynfed(arg) => rtcsa_dynfed::run(arg),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_normalize_args_normal() {
+        let args = to_args(&["sched_sim", "gedf", "-d", "dags", "-c", "4"]);
+        assert_eq!(normalize_args(args.clone()), args);
+        assert!(matches!(Cli::parse_from(args).command, Command::Gedf(_)));
+    }
+
+    #[test]
+    fn test_normalize_args_without_subcommand() {
+        let args = normalize_args(to_args(&["sched_sim", "-d", "dags", "-c", "4"]));
+        assert_eq!(
+            args,
+            to_args(&["sched_sim", "federated", "-d", "dags", "-c", "4"])
+        );
+        assert!(matches!(
+            Cli::parse_from(args).command,
+            Command::Federated(_)
+        ));
+    }
+}
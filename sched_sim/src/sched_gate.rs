@@ -0,0 +1,109 @@
+//! Compare the schedule scores of two revisions on the same fixture suite, e.g.,
+//! `sched_gate -b <baseline_runs> -c <candidate_runs> -t 0.05`.
+//!
+//! Both directories hold the run directories (`--run_dir`) of each revision on the suite.
+//! Exits with status 1 if a run scores lower than the baseline by more than the threshold,
+//! or is missing from the candidate. With `--report`, the change of the makespan and the response
+//! times of each run is also printed, e.g., to quantify a scheduling option such as
+//! `--critical_path_boost` of `gedf` with the runs without and with it, followed by Wilcoxon
+//! signed-rank tests of the changes over all the runs with their effect sizes.
+//!
+//! With `--baseline_alg` and `--candidate_alg`, the runs of the two algorithms are compared on
+//! the same DAG sets instead, e.g., with both directories the same.
+use clap::Parser;
+use lib::{
+    schedule_score::{
+        compare_runs, find_score_regressions, get_run_scores, load_run_summaries, select_alg_runs,
+        test_paired_runs,
+    },
+    statistics::WilcoxonSignedRankTest,
+};
+
+#[derive(Parser)]
+#[clap(
+    name = "sched_gate",
+    version = "1.0",
+    about = "Fail if the schedule quality regresses between two revisions."
+)]
+struct ArgParser {
+    ///Path to the directory of the run directories of the baseline revision.
+    #[clap(short = 'b', long = "baseline_dir_path", required = true)]
+    baseline_dir_path: String,
+    ///Path to the directory of the run directories of the candidate revision.
+    #[clap(short = 'c', long = "candidate_dir_path", required = true)]
+    candidate_dir_path: String,
+    ///Allowed drop of the score of each run.
+    #[clap(short = 't', long = "threshold", default_value = "0.0")]
+    threshold: f32,
+    ///Print the change of the normalized makespan and the worst response times of each run.
+    #[clap(short = 'r', long = "report", default_value = "false")]
+    report: bool,
+    ///Algorithm of the baseline runs, compared with those of `--candidate_alg` on the same DAG sets.
+    #[clap(long = "baseline_alg", requires = "candidate_alg")]
+    baseline_alg: Option<String>,
+    ///Algorithm of the candidate runs, compared with those of `--baseline_alg` on the same DAG sets.
+    #[clap(long = "candidate_alg", requires = "baseline_alg")]
+    candidate_alg: Option<String>,
+}
+
+fn print_paired_test(metric: &str, test: &WilcoxonSignedRankTest) {
+    println!(
+        "Paired test: {} changed by {:+.3} (median) over {} runs, W+ {} W- {}, p {:.4}{}, rank-biserial {:+.2}",
+        metric,
+        test.median_difference,
+        test.pairs,
+        test.positive_rank_sum,
+        test.negative_rank_sum,
+        test.p_value,
+        if test.is_exact { "" } else { " (normal approximation)" },
+        test.rank_biserial_correlation
+    );
+}
+
+fn main() {
+    let arg = ArgParser::parse();
+    let mut baseline_summaries = load_run_summaries(&arg.baseline_dir_path);
+    let mut candidate_summaries = load_run_summaries(&arg.candidate_dir_path);
+    if let (Some(baseline_alg), Some(candidate_alg)) = (&arg.baseline_alg, &arg.candidate_alg) {
+        baseline_summaries = select_alg_runs(&baseline_summaries, baseline_alg);
+        candidate_summaries = select_alg_runs(&candidate_summaries, candidate_alg);
+    }
+    if arg.report {
+        let comparisons = compare_runs(&baseline_summaries, &candidate_summaries);
+        for comparison in comparisons.iter() {
+            println!(
+                "Compared: {} changed the normalized makespan by {:+.3} and the worst response times by {:?} (average {:+.2})",
+                comparison.run_key,
+                comparison.normalized_makespan_change,
+                comparison.worst_response_time_changes,
+                comparison.get_average_worst_response_time_change()
+            );
+        }
+        let paired_tests = test_paired_runs(&comparisons);
+        print_paired_test("the normalized makespan", &paired_tests.normalized_makespan);
+        print_paired_test(
+            "the average worst response time",
+            &paired_tests.average_worst_response_time,
+        );
+    }
+    let baseline_scores = get_run_scores(&baseline_summaries);
+    let candidate_scores = get_run_scores(&candidate_summaries);
+    let regressions = find_score_regressions(&baseline_scores, &candidate_scores, arg.threshold);
+    for regression in regressions.iter() {
+        match regression.candidate_score {
+            Some(candidate_score) => println!(
+                "Regressed: {} scored {} (baseline {})",
+                regression.run_key, candidate_score, regression.baseline_score
+            ),
+            None => println!("Missing: {} has no scored run", regression.run_key),
+        }
+    }
+    println!(
+        "{} of {} runs regressed.",
+        regressions.len(),
+        baseline_scores.len()
+    );
+    if !regressions.is_empty() {
+        std::process::exit(1);
+    }
+}